@@ -0,0 +1,53 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The edp-rs crates behind a single dependency.
+//!
+//! Re-exports `erltf`, `edp_client` and, behind feature flags, `edp_node`,
+//! `erltf_serde` and `edp_elixir_terms`, all at the same version.
+//! The [`prelude`] covers what most applications need.
+//!
+//! # Features
+//!
+//! - `node` (default): `edp_node`
+//! - `serde` (default): `erltf_serde`
+//! - `elixir`: `edp_elixir_terms`
+//!
+//! # Example
+//!
+//! ```no_run
+//! use edp::prelude::*;
+//!
+//! # async fn example() -> edp::edp_node::Result<()> {
+//! let mut node = Node::new("rust@localhost", "secret");
+//! node.start(0).await?;
+//! node.connect("erlang@localhost").await?;
+//! let reply = node
+//!     .rpc_call("erlang@localhost", "erlang", "node", vec![])
+//!     .await?;
+//! println!("{}", reply);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod prelude;
+
+pub use edp_client;
+#[cfg(feature = "elixir")]
+pub use edp_elixir_terms;
+#[cfg(feature = "node")]
+pub use edp_node;
+pub use erltf;
+#[cfg(feature = "serde")]
+pub use erltf_serde;
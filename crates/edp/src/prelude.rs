@@ -0,0 +1,37 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The commonly used types, traits and macros: `use edp::prelude::*;`.
+//!
+//! The crates' `Error` and `Result` types are left out to avoid clashes,
+//! refer to them through their crates, e.g. `edp::edp_node::Result`.
+
+pub use edp_client::epmd_client::EpmdClient;
+pub use edp_client::{Connection, ConnectionConfig, DistributionFlags};
+pub use erltf::{
+    Atom, ErlResult, ExternalPid, ExternalReference, FromTerm, Mfa, OwnedTerm, ToTerm, erl_atom,
+    erl_atoms, erl_int, erl_list, erl_map, erl_tuple,
+};
+
+#[cfg(feature = "node")]
+pub use edp_node::{
+    CallResult, ExitReason, GenServer, GenServerProcess, Message, Node, Process, RpcPool,
+    RpcPoolConfig, RpcResult, rpc_result,
+};
+
+#[cfg(feature = "serde")]
+pub use erltf_serde::{OwnedTermExt, from_term, to_term};
+
+#[cfg(feature = "elixir")]
+pub use edp_elixir_terms::{AtomKeyMapBuilder, ElixirMapSet, ElixirRange, KeywordListBuilder};
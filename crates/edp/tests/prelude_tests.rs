@@ -0,0 +1,58 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp::prelude::*;
+
+#[test]
+fn test_prelude_covers_terms_and_client_types() {
+    let pid = ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1);
+    let term = erl_tuple![erl_atom!("ok"), OwnedTerm::Pid(pid)];
+    assert!(term.is_tuple());
+
+    let config = ConnectionConfig::new("rust@localhost", "erlang@localhost", "secret");
+    let conn = Connection::new(config);
+    assert!(!conn.is_connected());
+    assert!(DistributionFlags::default().has_mandatory_otp26());
+}
+
+#[test]
+fn test_crates_are_re_exported() {
+    let term = edp::erltf::OwnedTerm::atom("ok");
+    let encoded = edp::erltf::encode(&term).unwrap();
+    assert_eq!(edp::erltf::decode(&encoded).unwrap(), term);
+}
+
+#[cfg(feature = "node")]
+#[test]
+fn test_prelude_covers_nodes() {
+    let node = Node::new("prelude@localhost", "secret");
+    assert_eq!(node.name(), &Atom::new("prelude@localhost"));
+    let reason = ExitReason::from(&OwnedTerm::atom("normal"));
+    assert_eq!(reason, ExitReason::Normal);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_prelude_covers_serde() {
+    let term = to_term(&42i64).unwrap();
+    let value: i64 = from_term(&term).unwrap();
+    assert_eq!(value, 42);
+}
+
+#[cfg(feature = "elixir")]
+#[test]
+fn test_prelude_covers_elixir_terms() {
+    let keywords = KeywordListBuilder::new().put("name", "Alice").build();
+    assert!(keywords.is_list());
+}
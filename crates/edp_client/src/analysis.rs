@@ -0,0 +1,352 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rendering captured distribution traffic as sequence diagrams.
+//!
+//! Feed decoded control messages (and their payloads) into a [`SequenceDiagram`]
+//! in the order they were observed, then render it with
+//! [`SequenceDiagram::to_mermaid`] or [`SequenceDiagram::to_plantuml`].
+
+use crate::control::{ControlMessage, ControlMessageType};
+use erltf::OwnedTerm;
+use std::fmt::Write;
+use std::time::Duration;
+
+pub const DEFAULT_MAX_PAYLOAD_LEN: usize = 60;
+
+/// A control message observed on a connection between two nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedMessage {
+    pub sender_node: String,
+    pub receiver_node: String,
+    pub control: ControlMessage,
+    pub payload: Option<OwnedTerm>,
+    /// Time since the start of the capture.
+    pub timestamp: Option<Duration>,
+}
+
+impl CapturedMessage {
+    pub fn new(
+        sender_node: impl Into<String>,
+        receiver_node: impl Into<String>,
+        control: ControlMessage,
+        payload: Option<OwnedTerm>,
+    ) -> Self {
+        Self {
+            sender_node: sender_node.into(),
+            receiver_node: receiver_node.into(),
+            control,
+            payload,
+            timestamp: None,
+        }
+    }
+
+    pub fn with_timestamp(mut self, timestamp: Duration) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// The protocol name of the control message type, e.g. `REG_SEND`.
+    pub fn message_type(&self) -> &'static str {
+        match &self.control {
+            ControlMessage::Generic { .. } => "UNKNOWN",
+            control => match control.to_term() {
+                OwnedTerm::Tuple(elements) => elements
+                    .first()
+                    .and_then(|t| t.as_integer())
+                    .and_then(|t| u8::try_from(t).ok())
+                    .and_then(ControlMessageType::from_u8)
+                    .map_or("UNKNOWN", ControlMessageType::name),
+                _ => "UNKNOWN",
+            },
+        }
+    }
+
+    /// The sending participant: the sending process where the message carries one,
+    /// otherwise the sending node.
+    pub fn sender(&self) -> String {
+        match endpoints(&self.control).0 {
+            Some(term) => participant_label(term, &self.sender_node),
+            None => self.sender_node.clone(),
+        }
+    }
+
+    /// The receiving participant: the receiving process or registered name where
+    /// the message carries one, otherwise the receiving node.
+    pub fn receiver(&self) -> String {
+        match endpoints(&self.control).1 {
+            Some(term) => participant_label(term, &self.receiver_node),
+            None => self.receiver_node.clone(),
+        }
+    }
+}
+
+/// Builds a sequence diagram out of captured messages.
+#[derive(Debug, Clone)]
+pub struct SequenceDiagram {
+    messages: Vec<CapturedMessage>,
+    title: Option<String>,
+    include_payloads: bool,
+    max_payload_len: usize,
+}
+
+impl Default for SequenceDiagram {
+    fn default() -> Self {
+        Self {
+            messages: Vec::new(),
+            title: None,
+            include_payloads: true,
+            max_payload_len: DEFAULT_MAX_PAYLOAD_LEN,
+        }
+    }
+}
+
+impl SequenceDiagram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Whether message payloads are shown next to the message type.
+    pub fn with_payloads(mut self, include_payloads: bool) -> Self {
+        self.include_payloads = include_payloads;
+        self
+    }
+
+    /// Longer payloads are truncated to this many characters.
+    pub fn with_max_payload_len(mut self, max_payload_len: usize) -> Self {
+        self.max_payload_len = max_payload_len;
+        self
+    }
+
+    pub fn push(&mut self, message: CapturedMessage) {
+        self.messages.push(message);
+    }
+
+    pub fn messages(&self) -> &[CapturedMessage] {
+        &self.messages
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Participants in order of first appearance.
+    pub fn participants(&self) -> Vec<String> {
+        let mut participants: Vec<String> = Vec::new();
+        for message in &self.messages {
+            for participant in [message.sender(), message.receiver()] {
+                if !participants.contains(&participant) {
+                    participants.push(participant);
+                }
+            }
+        }
+        participants
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let participants = self.participants();
+        let mut out = String::new();
+        if let Some(title) = &self.title {
+            let _ = writeln!(out, "---\ntitle: {}\n---", title);
+        }
+        out.push_str("sequenceDiagram\n");
+        for (i, participant) in participants.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    participant P{} as {}",
+                i,
+                escape_mermaid(participant)
+            );
+        }
+        for message in &self.messages {
+            let from = index_of(&participants, &message.sender());
+            let to = index_of(&participants, &message.receiver());
+            let _ = writeln!(
+                out,
+                "    P{}->>P{}: {}",
+                from,
+                to,
+                escape_mermaid(&self.label(message))
+            );
+        }
+        out
+    }
+
+    pub fn to_plantuml(&self) -> String {
+        let participants = self.participants();
+        let mut out = String::from("@startuml\n");
+        if let Some(title) = &self.title {
+            let _ = writeln!(out, "title {}", title);
+        }
+        for (i, participant) in participants.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "participant \"{}\" as P{}",
+                participant.replace('"', "'"),
+                i
+            );
+        }
+        for message in &self.messages {
+            let from = index_of(&participants, &message.sender());
+            let to = index_of(&participants, &message.receiver());
+            let _ = writeln!(
+                out,
+                "P{} -> P{} : {}",
+                from,
+                to,
+                self.label(message).replace('\n', " ")
+            );
+        }
+        out.push_str("@enduml\n");
+        out
+    }
+
+    fn label(&self, message: &CapturedMessage) -> String {
+        let mut label = String::new();
+        if let Some(ts) = message.timestamp {
+            let _ = write!(label, "[+{:.3} ms] ", ts.as_secs_f64() * 1000.0);
+        }
+        label.push_str(message.message_type());
+        if self.include_payloads
+            && let Some(payload) = &message.payload
+        {
+            let _ = write!(
+                label,
+                " {}",
+                truncate(&payload.to_string(), self.max_payload_len)
+            );
+        }
+        label
+    }
+}
+
+fn index_of(participants: &[String], participant: &str) -> usize {
+    participants
+        .iter()
+        .position(|p| p == participant)
+        .unwrap_or_default()
+}
+
+fn participant_label(term: &OwnedTerm, node: &str) -> String {
+    match term {
+        OwnedTerm::Pid(pid) => format!("{} {}", pid.node, pid),
+        OwnedTerm::Atom(name) => format!("{} ({})", name, node),
+        OwnedTerm::Reference(reference) => format!("{} alias {}", reference.node, term),
+        other => format!("{} ({})", other, node),
+    }
+}
+
+fn endpoints(control: &ControlMessage) -> (Option<&OwnedTerm>, Option<&OwnedTerm>) {
+    match control {
+        ControlMessage::Link { from_pid, to_pid }
+        | ControlMessage::Unlink { from_pid, to_pid }
+        | ControlMessage::UnlinkId {
+            from_pid, to_pid, ..
+        }
+        | ControlMessage::UnlinkIdAck {
+            from_pid, to_pid, ..
+        }
+        | ControlMessage::Exit {
+            from_pid, to_pid, ..
+        }
+        | ControlMessage::Exit2 {
+            from_pid, to_pid, ..
+        }
+        | ControlMessage::ExitTt {
+            from_pid, to_pid, ..
+        }
+        | ControlMessage::Exit2Tt {
+            from_pid, to_pid, ..
+        }
+        | ControlMessage::GroupLeader { from_pid, to_pid }
+        | ControlMessage::SendSender { from_pid, to_pid }
+        | ControlMessage::SendSenderTt {
+            from_pid, to_pid, ..
+        }
+        | ControlMessage::PayloadExit { from_pid, to_pid }
+        | ControlMessage::PayloadExitTt {
+            from_pid, to_pid, ..
+        }
+        | ControlMessage::PayloadExit2 { from_pid, to_pid }
+        | ControlMessage::PayloadExit2Tt {
+            from_pid, to_pid, ..
+        } => (Some(from_pid), Some(to_pid)),
+        ControlMessage::Send { to_pid, .. } | ControlMessage::SendTt { to_pid, .. } => {
+            (None, Some(to_pid))
+        }
+        ControlMessage::RegSend {
+            from_pid, to_name, ..
+        }
+        | ControlMessage::RegSendTt {
+            from_pid, to_name, ..
+        } => (Some(from_pid), Some(to_name)),
+        ControlMessage::MonitorP {
+            from_pid, to_proc, ..
+        }
+        | ControlMessage::DemonitorP {
+            from_pid, to_proc, ..
+        } => (Some(from_pid), Some(to_proc)),
+        ControlMessage::MonitorPExit {
+            from_proc, to_pid, ..
+        }
+        | ControlMessage::PayloadMonitorPExit {
+            from_proc, to_pid, ..
+        } => (Some(from_proc), Some(to_pid)),
+        ControlMessage::SpawnRequest { from, .. } | ControlMessage::SpawnRequestTt { from, .. } => {
+            (Some(from), None)
+        }
+        ControlMessage::SpawnReply { to, .. } | ControlMessage::SpawnReplyTt { to, .. } => {
+            (None, Some(to))
+        }
+        ControlMessage::AliasSend {
+            from_pid, alias, ..
+        }
+        | ControlMessage::AliasSendTt {
+            from_pid, alias, ..
+        } => (Some(from_pid), Some(alias)),
+        ControlMessage::NodeLink | ControlMessage::Generic { .. } => (None, None),
+    }
+}
+
+fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        return s.to_string();
+    }
+    let mut truncated: String = s.chars().take(max_len).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+// Mermaid treats ';' and '#' specially and does not allow line breaks in messages
+fn escape_mermaid(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '#' => escaped.push_str("#35;"),
+            ';' => escaped.push_str("#59;"),
+            '\n' => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
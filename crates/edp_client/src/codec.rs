@@ -0,0 +1,251 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! How a [`Connection`](crate::Connection) turns terms into frame bodies and back.
+//!
+//! [`EtfCodec`], the external term format with distribution headers and atom caches,
+//! is what Erlang nodes speak and the default. Other [`Codec`]s, e.g. experimental
+//! encodings between two Rust nodes or future format revisions, can be developed
+//! against the same connection logic, see [`Connection::with_codec`](crate::Connection::with_codec).
+
+use crate::connection::ConnectionConfig;
+use crate::errors::Result;
+use crate::flags::DistributionFlags;
+use crate::protocol::{DIST_HEADER, PASS_THROUGH, VERSION};
+use bytes::Bytes;
+use erltf::decoder::{self, AtomCache};
+use erltf::{
+    AtomTable, DecodeConfig, DistHeaderOptions, EncodeConfig, OutgoingAtomCache, OwnedTerm,
+};
+use tracing::{trace, warn};
+
+/// The control message, payload and undecoded payload of a message. The undecoded
+/// payload is only set when the payload failed to decode and raw payloads were requested.
+pub type DecodedFrame = (OwnedTerm, Option<OwnedTerm>, Option<Vec<u8>>);
+
+/// What a codec knows about the connection when it encodes a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeContext {
+    /// The flags negotiated in the handshake
+    pub flags: Option<DistributionFlags>,
+    /// How pids, ports and references, as well as NaN and infinities, are encoded for
+    /// the peer, and the longest atom that can be sent
+    pub config: EncodeConfig,
+}
+
+/// Encodes and decodes the bodies of distribution frames, keeping per-connection state
+/// such as atom caches. Framing, fragmentation and compression are up to the connection.
+///
+/// Offloaded decodes run on the blocking thread pool with a clone of the codec, which
+/// replaces the connection's once the decode finishes.
+pub trait Codec: Clone + Send + 'static {
+    /// Encodes a control message and its payload into a frame body, without the length prefix.
+    fn encode(
+        &mut self,
+        control: &OwnedTerm,
+        payload: Option<&OwnedTerm>,
+        context: &EncodeContext,
+    ) -> Result<Vec<u8>>;
+
+    /// Decodes a complete, possibly reassembled, frame body. With `raw_payloads` a payload
+    /// that fails to decode is returned undecoded instead of failing.
+    ///
+    /// Payload binaries may be decoded as slices of `data`, see
+    /// [`DecodeConfig::with_shared_binaries`].
+    fn decode(
+        &mut self,
+        data: &Bytes,
+        config: &DecodeConfig,
+        raw_payloads: bool,
+    ) -> Result<DecodedFrame>;
+
+    /// Called when a connection is established, the peer starts with fresh state.
+    fn reset(&mut self, _config: &ConnectionConfig) {}
+
+    /// Called when an encoded frame was not sent, so the peer never saw state it set up.
+    fn discard_unsent(&mut self) {}
+
+    /// Releases spare memory of an idle connection. With `forget_outgoing`, state the peer
+    /// was sent is dropped too, see [`ConnectionConfig::with_hibernation_atom_cache_reset`].
+    fn shrink(&mut self, _forget_outgoing: bool) {}
+
+    /// Approximately the memory the terms in a frame body decode to, without decoding them,
+    /// so that [`DecodeBudget::max_message_size`](crate::DecodeBudget::max_message_size)
+    /// applies before a frame is decoded. Codecs that cannot tell return `None`.
+    fn estimate_heap_size(&self, _data: &Bytes) -> Result<Option<usize>> {
+        Ok(None)
+    }
+
+    /// The distinct atoms decoded so far, if the codec tracks them. A read half taken
+    /// from the connection continues counting from them.
+    fn atom_table(&self) -> Option<&AtomTable> {
+        None
+    }
+}
+
+/// The external term format, with `DIST_HEADER` atom caches when
+/// `DIST_HDR_ATOM_CACHE` is negotiated and pass-through frames otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct EtfCodec {
+    atom_cache: AtomCache,
+    outgoing_atom_cache: OutgoingAtomCache,
+    atom_table: AtomTable,
+}
+
+impl EtfCodec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The distinct atoms decoded on this connection, recorded only under an atom limit.
+    #[must_use]
+    pub fn atom_table(&self) -> &AtomTable {
+        &self.atom_table
+    }
+
+    /// Atoms sent to the peer's atom cache and how often they were used.
+    #[must_use]
+    pub fn outgoing_atom_cache(&self) -> &OutgoingAtomCache {
+        &self.outgoing_atom_cache
+    }
+}
+
+impl Codec for EtfCodec {
+    fn encode(
+        &mut self,
+        control: &OwnedTerm,
+        payload: Option<&OwnedTerm>,
+        context: &EncodeContext,
+    ) -> Result<Vec<u8>> {
+        let use_pass_through = context
+            .flags
+            .is_none_or(|f| !f.has(DistributionFlags::DIST_HDR_ATOM_CACHE));
+
+        if use_pass_through {
+            let mut body = vec![PASS_THROUGH];
+            body.extend(erltf::encode_with_config(control, &context.config)?);
+            if let Some(payload) = payload {
+                body.extend(erltf::encode_with_config(payload, &context.config)?);
+            }
+            trace!("Encoded pass-through message: len={}", body.len());
+            return Ok(body);
+        }
+
+        let terms = match payload {
+            Some(payload) => vec![control, payload],
+            None => vec![control],
+        };
+        let options = DistHeaderOptions::new().with_encode_config(context.config);
+        let (encoded, _) =
+            erltf::encode_with_dist_header_report(&terms, &mut self.outgoing_atom_cache, &options)?;
+        trace!("Encoded DIST_HEADER message: len={}", encoded.len());
+        Ok(encoded)
+    }
+
+    fn decode(
+        &mut self,
+        data: &Bytes,
+        config: &DecodeConfig,
+        raw_payloads: bool,
+    ) -> Result<DecodedFrame> {
+        let pass_through = data.first() == Some(&PASS_THROUGH);
+        let (control, remaining) = if pass_through {
+            trace!("Pass-through message detected");
+            decoder::decode_with_trailing_and_config(&data[1..], config, &mut self.atom_table)?
+        } else if data.len() >= 2 && data[0] == VERSION && data[1] == DIST_HEADER {
+            decoder::decode_control_with_atom_cache_and_config(
+                data,
+                &mut self.atom_cache,
+                config,
+                &mut self.atom_table,
+            )?
+        } else {
+            (
+                decoder::decode_bytes_with_config(data, config, &mut self.atom_table)?,
+                &data[data.len()..],
+            )
+        };
+
+        if remaining.is_empty() {
+            return Ok((control, None, None));
+        }
+        trace!("Decoding payload from {} bytes", remaining.len());
+        let shared = data.slice_ref(remaining);
+        let payload = if pass_through {
+            decoder::decode_bytes_with_trailing_and_config(&shared, config, &mut self.atom_table)
+                .map(|(payload, _)| payload)
+        } else {
+            decoder::decode_payload_bytes_with_atom_cache_and_config(
+                &shared,
+                &self.atom_cache,
+                config,
+                &mut self.atom_table,
+            )
+        };
+
+        match payload {
+            Ok(payload) => Ok((control, Some(payload), None)),
+            Err(e) if raw_payloads => {
+                warn!(
+                    "Failed to decode a {}-byte payload, keeping it undecoded: {}",
+                    remaining.len(),
+                    e
+                );
+                Ok((control, None, Some(remaining.to_vec())))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Announces the seed atoms and the atoms used most on the previous connection.
+    /// Atoms decoded on the previous connection no longer count against the atom limit.
+    fn reset(&mut self, config: &ConnectionConfig) {
+        let hottest = self.outgoing_atom_cache.hottest(config.atom_cache_warmup);
+        self.outgoing_atom_cache.reset();
+        self.outgoing_atom_cache
+            .seed(config.atom_cache_seed.iter().cloned().chain(hottest));
+        self.atom_cache.clear();
+        self.atom_table.clear();
+    }
+
+    fn discard_unsent(&mut self) {
+        self.outgoing_atom_cache.reset();
+    }
+
+    /// Atoms announced by the peer are kept.
+    fn shrink(&mut self, forget_outgoing: bool) {
+        self.atom_cache.shrink_to_fit();
+        if forget_outgoing {
+            self.outgoing_atom_cache.reset();
+        }
+        self.outgoing_atom_cache.shrink_to_fit();
+    }
+
+    fn estimate_heap_size(&self, data: &Bytes) -> Result<Option<usize>> {
+        estimate_frame_heap_size(data).map(Some)
+    }
+
+    fn atom_table(&self) -> Option<&AtomTable> {
+        Some(&self.atom_table)
+    }
+}
+
+/// Estimates the memory the terms of a pass-through or `DIST_HEADER` frame body decode to,
+/// see [`TermSummary::estimated_heap_size`](erltf::TermSummary::estimated_heap_size).
+pub(crate) fn estimate_frame_heap_size(data: &[u8]) -> Result<usize> {
+    let summary = match data.split_first() {
+        Some((&PASS_THROUGH, terms)) => decoder::validate_all(terms)?,
+        _ => decoder::validate(data)?,
+    };
+    Ok(summary.estimated_heap_size())
+}
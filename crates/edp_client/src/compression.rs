@@ -0,0 +1,113 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Off-spec zstd compression of distribution frames, for links where both peers
+//! use this crate.
+//!
+//! Erlang nodes do not understand compressed frames, so compression is never negotiated:
+//! both ends enable it with [`ConnectionConfig::with_zstd_compression`](crate::ConnectionConfig::with_zstd_compression)
+//! and [`ReceiveOptions::with_zstd_compression`](crate::ReceiveOptions::with_zstd_compression).
+//!
+//! Once the handshake completes, frame bodies of at least [`ZstdCompression::min_size`] bytes
+//! are replaced by a zstd frame. Ticks and handshake messages are never compressed.
+//! A distribution frame body starts with `131` or `112`, a zstd frame with its magic number,
+//! so receivers tell compressed bodies apart and accept uncompressed ones as well.
+
+use bytes::{BufMut, BytesMut};
+use std::io::{self, Read};
+
+/// The first four bytes of every zstd frame
+pub const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// The zstd level used by default, the library's default
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Bodies smaller than this are sent uncompressed by default
+pub const DEFAULT_ZSTD_MIN_SIZE: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZstdCompression {
+    /// The zstd compression level, from 1 to 22
+    pub level: i32,
+    /// Bodies smaller than this are sent uncompressed
+    pub min_size: usize,
+}
+
+impl Default for ZstdCompression {
+    fn default() -> Self {
+        Self {
+            level: DEFAULT_ZSTD_LEVEL,
+            min_size: DEFAULT_ZSTD_MIN_SIZE,
+        }
+    }
+}
+
+impl ZstdCompression {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_level(mut self, level: i32) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn with_min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Compresses a frame body. Returns `None` when the body is below the minimum size
+    /// or does not get smaller.
+    pub fn compress(&self, body: &[u8]) -> io::Result<Option<Vec<u8>>> {
+        if body.is_empty() || body.len() < self.min_size {
+            return Ok(None);
+        }
+        let compressed = zstd::bulk::compress(body, self.level)?;
+        Ok((compressed.len() < body.len()).then_some(compressed))
+    }
+
+    /// Compresses the body of a frame that starts with its 4-byte length prefix.
+    pub(crate) fn compress_frame(&self, frame: BytesMut) -> io::Result<BytesMut> {
+        let Some(compressed) = self.compress(&frame[4..])? else {
+            return Ok(frame);
+        };
+        let mut buf = BytesMut::with_capacity(4 + compressed.len());
+        buf.put_u32(compressed.len() as u32);
+        buf.put_slice(&compressed);
+        Ok(buf)
+    }
+}
+
+pub fn is_compressed(body: &[u8]) -> bool {
+    body.starts_with(&ZSTD_MAGIC)
+}
+
+/// Decompresses a frame body, failing if it would exceed `max_size` bytes.
+/// Bodies that are not compressed are returned as is.
+pub fn decompress(body: Vec<u8>, max_size: usize) -> io::Result<Vec<u8>> {
+    if !is_compressed(&body) {
+        return Ok(body);
+    }
+    let mut decompressed = Vec::new();
+    zstd::stream::read::Decoder::new(body.as_slice())?
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() > max_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Decompressed message exceeds {max_size} bytes"),
+        ));
+    }
+    Ok(decompressed)
+}
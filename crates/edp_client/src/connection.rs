@@ -14,42 +14,117 @@
 
 //! Distribution protocol connection orchestration.
 
-use crate::control::ControlMessage;
-use crate::epmd_client::EpmdClient;
+use crate::codec::{self, Codec, DecodedFrame, EncodeContext, EtfCodec};
+#[cfg(feature = "zstd")]
+use crate::compression::{self, ZstdCompression};
+use crate::control::{ControlMessage, ControlMessageType};
+use crate::decode_budget::{DecodeAccounting, DecodeBudget};
+use crate::decode_offload::DecodeOffload;
+use crate::digest::{ChallengeSource, OsChallengeSource};
+use crate::epmd_client::{EpmdClient, EpmdClientConfig, NodeInfo};
 use crate::errors::{Error, Result};
-use crate::flags::DistributionFlags;
+use crate::flags::{CompatibilityReport, DistributionFlags};
 use crate::fragmentation::FragmentAssembler;
 use crate::framing::FrameMode;
-use crate::state_machine::{ConnectionState, HandshakeStateMachine};
+use crate::happy_eyeballs::{self, DEFAULT_CONNECTION_ATTEMPT_DELAY};
+use crate::metrics::{
+    ConnectionMetrics, DEFAULT_RTT_WINDOW, DEFAULT_TOP_TALKERS, MessageDirection, RttCallback,
+};
+use crate::protocol::{DIST_FRAG_CONT, DIST_FRAG_HEADER, DIST_HEADER, PASS_THROUGH, VERSION};
+use crate::resolver::{Resolver, default_resolver};
+use crate::socket_options::SocketOptions;
+use crate::state_machine::{
+    AliveCallback, ConnectionState, HandshakeAction, HandshakeEvent, HandshakeStateMachine,
+};
 use crate::transport::FramedTransport;
-use crate::types::Creation;
-use bytes::{BufMut, BytesMut};
+use crate::types::{ChannelKey, ConnectionId, Creation, SequenceId};
+use crate::unlinking::UnlinkTracker;
+use bytes::{BufMut, Bytes, BytesMut};
 use erltf::decoder::AtomCache;
 use erltf::types::{Atom, ExternalPid, ExternalReference};
+use erltf::{AtomTable, DecodeConfig, EncodeConfig, EncodeMode, OutgoingAtomCache};
 use erltf::{OwnedTerm, decoder};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::TcpStream;
 use tokio::net::tcp::OwnedReadHalf;
-use tracing::{debug, trace};
+use tokio::task::spawn_blocking;
+use tokio::time;
+use tracing::{Instrument, Span, debug, debug_span, trace};
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
-const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
-
-const VERSION_TAG: u8 = 131;
-const DIST_HEADER: u8 = 68;
-const DIST_FRAG_HEADER: u8 = 69;
-const DIST_FRAG_CONT: u8 = 70;
-const PASS_THROUGH: u8 = 112;
+pub(crate) const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+/// Exit reasons estimated to encode to more than this many bytes are sent
+/// as the payload of a `PAYLOAD_EXIT2` instead of inside an `EXIT2`.
+pub const LARGE_EXIT_REASON_SIZE: usize = 1024;
 
 pub struct ConnectionConfig {
     pub local_node_name: String,
     pub remote_node_name: String,
     pub cookie: String,
     pub epmd_host: String,
+    /// Used for port lookups instead of a client for `epmd_host`, e.g. to share its cache
+    pub epmd_client: Option<EpmdClient>,
+    /// When set, EPMD is not queried and the connection goes straight to this port
+    pub remote_port: Option<u16>,
+    /// Delay between staggered connection attempts when the remote host has several addresses
+    pub connection_attempt_delay: Duration,
+    /// Resolves the remote host and, unless `epmd_client` is set, the EPMD host
+    pub resolver: Arc<dyn Resolver>,
+    /// Applied to the stream before the handshake
+    pub socket_options: SocketOptions,
     pub flags: DistributionFlags,
     pub creation: Creation,
     pub timeout: Duration,
+    pub decode_config: DecodeConfig,
+    /// The float policy and atom length limit for outgoing messages. The encode mode
+    /// follows the negotiated flags, see [`Connection::encode_mode`]
+    pub encode_config: EncodeConfig,
+    pub required_flags: DistributionFlags,
+    /// Atoms announced to the peer's atom cache in the first frames after connecting
+    pub atom_cache_seed: Vec<Atom>,
+    /// How many of the most used atoms are announced again after a reconnect
+    pub atom_cache_warmup: usize,
+    /// What message sends do with pids from before the peer's last restart
+    pub stale_pid_policy: StalePidPolicy,
+    /// How many RTT samples [`ConnectionMetrics`] keeps
+    pub rtt_window: usize,
+    /// How many (control message type, registered name) pairs [`ConnectionMetrics::top_talkers`] tracks
+    pub top_talkers: usize,
+    /// Invoked when a recorded RTT exceeds the threshold
+    pub rtt_alert: Option<(Duration, RttCallback)>,
+    /// Whether unknown control messages are received as [`ControlMessage::Generic`]
+    /// instead of failing, see [`ControlMessage::from_term_strict`]
+    pub permissive_control_messages: bool,
+    /// Whether payloads that fail to decode are received as [`ReceivedMessage::raw_payload`]
+    /// instead of failing, see [`ConnectionConfig::with_raw_payloads`]
+    pub raw_payloads: bool,
+    /// What receives do when a frame fails to decode, see [`ConnectionConfig::with_decode_error_policy`]
+    pub decode_error_policy: DecodeErrorPolicy,
+    /// Produces the challenges this node sends during the handshake
+    pub challenge_source: Arc<dyn ChallengeSource>,
+    /// Decodes large messages on the blocking thread pool, see [`ConnectionConfig::with_decode_offload`]
+    pub decode_offload: Option<DecodeOffload>,
+    /// Limits on the memory decoded payloads take, see [`ConnectionConfig::with_decode_budget`]
+    pub decode_budget: DecodeBudget,
+    /// Off-spec frame compression, see [`ConnectionConfig::with_zstd_compression`]
+    #[cfg(feature = "zstd")]
+    pub zstd_compression: Option<ZstdCompression>,
+    /// How long a connection goes without messages before [`Connection::hibernate_if_idle`]
+    /// hibernates it
+    pub idle_hibernation: Option<Duration>,
+    /// Whether [`Connection::hibernate`] also forgets which atoms the peer was sent
+    pub hibernation_resets_atom_cache: bool,
+    /// The id of the connection, generated when not set, see [`ConnectionConfig::with_connection_id`]
+    pub connection_id: Option<ConnectionId>,
+    /// Answers received `UNLINK_ID`s with `UNLINK_ID_ACK`, see [`ConnectionConfig::with_unlink_acks`]
+    pub unlink_acks: bool,
+    /// Decides whether to replace a connection the peer still has from this node,
+    /// see [`ConnectionConfig::with_alive_answer`]
+    pub alive_answer: Option<AliveCallback>,
 }
 
 impl ConnectionConfig {
@@ -63,9 +138,36 @@ impl ConnectionConfig {
             remote_node_name: remote_node_name.into(),
             cookie: cookie.into(),
             epmd_host: "localhost".to_string(),
+            epmd_client: None,
+            remote_port: None,
+            connection_attempt_delay: DEFAULT_CONNECTION_ATTEMPT_DELAY,
+            resolver: default_resolver(),
+            socket_options: SocketOptions::default(),
             flags: DistributionFlags::default(),
             creation: Creation::default(),
             timeout: DEFAULT_TIMEOUT,
+            decode_config: DecodeConfig::default(),
+            encode_config: EncodeConfig::default(),
+            required_flags: DistributionFlags::empty(),
+            atom_cache_seed: Vec::new(),
+            atom_cache_warmup: 0,
+            stale_pid_policy: StalePidPolicy::default(),
+            rtt_window: DEFAULT_RTT_WINDOW,
+            top_talkers: DEFAULT_TOP_TALKERS,
+            rtt_alert: None,
+            permissive_control_messages: false,
+            raw_payloads: false,
+            decode_error_policy: DecodeErrorPolicy::default(),
+            challenge_source: Arc::new(OsChallengeSource),
+            decode_offload: None,
+            decode_budget: DecodeBudget::default(),
+            #[cfg(feature = "zstd")]
+            zstd_compression: None,
+            idle_hibernation: None,
+            hibernation_resets_atom_cache: false,
+            connection_id: None,
+            unlink_acks: true,
+            alive_answer: None,
         }
     }
 
@@ -79,9 +181,36 @@ impl ConnectionConfig {
             remote_node_name: remote_node_name.into(),
             cookie: cookie.into(),
             epmd_host: "localhost".to_string(),
+            epmd_client: None,
+            remote_port: None,
+            connection_attempt_delay: DEFAULT_CONNECTION_ATTEMPT_DELAY,
+            resolver: default_resolver(),
+            socket_options: SocketOptions::default(),
             flags: DistributionFlags::default_hidden(),
             creation: Creation::default(),
             timeout: DEFAULT_TIMEOUT,
+            decode_config: DecodeConfig::default(),
+            encode_config: EncodeConfig::default(),
+            required_flags: DistributionFlags::empty(),
+            atom_cache_seed: Vec::new(),
+            atom_cache_warmup: 0,
+            stale_pid_policy: StalePidPolicy::default(),
+            rtt_window: DEFAULT_RTT_WINDOW,
+            top_talkers: DEFAULT_TOP_TALKERS,
+            rtt_alert: None,
+            permissive_control_messages: false,
+            raw_payloads: false,
+            decode_error_policy: DecodeErrorPolicy::default(),
+            challenge_source: Arc::new(OsChallengeSource),
+            decode_offload: None,
+            decode_budget: DecodeBudget::default(),
+            #[cfg(feature = "zstd")]
+            zstd_compression: None,
+            idle_hibernation: None,
+            hibernation_resets_atom_cache: false,
+            connection_id: None,
+            unlink_acks: true,
+            alive_answer: None,
         }
     }
 
@@ -90,11 +219,138 @@ impl ConnectionConfig {
         self
     }
 
+    /// Looks up the remote node with `client`, which can be shared between connections
+    /// to share its lookup cache.
+    pub fn with_epmd_client(mut self, client: EpmdClient) -> Self {
+        self.epmd_client = Some(client);
+        self
+    }
+
+    /// Connects directly to `port` on the remote host, bypassing EPMD, e.g. when the
+    /// distribution port is pinned with `inet_dist_listen_min`/`inet_dist_listen_max`.
+    /// The protocol version 6 handshake is used since EPMD is not asked for the peer's versions.
+    pub fn with_remote_port(mut self, port: u16) -> Self {
+        self.remote_port = Some(port);
+        self
+    }
+
+    pub fn with_connection_attempt_delay(mut self, delay: Duration) -> Self {
+        self.connection_attempt_delay = delay;
+        self
+    }
+
+    /// Resolves host names with `resolver` instead of the operating system's resolver.
+    /// A client set with [`ConnectionConfig::with_epmd_client`] keeps its own resolver.
+    pub fn with_resolver(mut self, resolver: impl Resolver + 'static) -> Self {
+        self.resolver = Arc::new(resolver);
+        self
+    }
+
+    /// Draws handshake challenges from `source` instead of the OS CSPRNG, e.g. a
+    /// [`FixedChallenge`](crate::digest::FixedChallenge) for reproducible handshakes in tests.
+    pub fn with_challenge_source(mut self, source: impl ChallengeSource + 'static) -> Self {
+        self.challenge_source = Arc::new(source);
+        self
+    }
+
+    /// Decodes messages above the offload threshold with [`tokio::task::spawn_blocking`]
+    /// so that they do not stall the runtime's worker thread. Applies to
+    /// [`Connection::receive_envelope`] and, via [`Connection::receive_options`],
+    /// to a [`DecodePipeline`](crate::DecodePipeline) for the read half.
+    pub fn with_decode_offload(mut self, offload: DecodeOffload) -> Self {
+        self.decode_offload = Some(offload);
+        self
+    }
+
+    /// Holds the payloads decoded from the peer to `budget`, by pausing reads or failing
+    /// oversized messages. Applies to [`Connection::receive_envelope`] and, via
+    /// [`Connection::receive_options`], to reads from the read half. See
+    /// [`Connection::decode_accounting`] for the sizes decoded so far.
+    pub fn with_decode_budget(mut self, budget: DecodeBudget) -> Self {
+        self.decode_budget = budget;
+        self
+    }
+
+    /// Compresses distribution frames with zstd after the handshake.
+    ///
+    /// This is not part of the distribution protocol: only use it when the peer is built on
+    /// this crate and enables it too, never toward Erlang or Elixir nodes.
+    #[cfg(feature = "zstd")]
+    pub fn with_zstd_compression(mut self, compression: ZstdCompression) -> Self {
+        self.zstd_compression = Some(compression);
+        self
+    }
+
+    /// Makes [`Connection::hibernate_if_idle`] hibernate the connection once it has sent
+    /// and received no messages, ticks aside, for `after`.
+    pub fn with_idle_hibernation(mut self, after: Duration) -> Self {
+        self.idle_hibernation = Some(after);
+        self
+    }
+
+    /// Makes [`Connection::hibernate`] release the outgoing atom cache too. Atoms are
+    /// announced to the peer again the next time they are sent.
+    pub fn with_hibernation_atom_cache_reset(mut self, reset: bool) -> Self {
+        self.hibernation_resets_atom_cache = reset;
+        self
+    }
+
+    /// Makes the connection use `id` instead of a new one, e.g. the id of the connection
+    /// it replaces, so that logs of both can be correlated.
+    pub fn with_connection_id(mut self, id: ConnectionId) -> Self {
+        self.connection_id = Some(id);
+        self
+    }
+
+    /// Builds a handshake state machine with this configuration's node names, cookie and
+    /// flags, for embedders that drive the handshake themselves, see [`Connection::adopt_handshake`].
+    pub fn handshake_state_machine(&self) -> HandshakeStateMachine {
+        let handshake = HandshakeStateMachine::new(
+            self.local_node_name.clone(),
+            self.remote_node_name.clone(),
+            self.cookie.clone(),
+            self.flags,
+            self.creation,
+        )
+        .with_required_flags(self.required_flags)
+        .with_challenge_source(Arc::clone(&self.challenge_source));
+        match &self.alive_answer {
+            Some(callback) => handshake.with_alive_answer(Arc::clone(callback)),
+            None => handshake,
+        }
+    }
+
+    /// Consults `callback` with the peer's name when the peer answers the handshake with
+    /// `alive`, meaning it still has a connection from this node. Returning `true` makes the
+    /// peer tear that connection down and continue, `false` fails the handshake with
+    /// [`Error::PeerConnectionAlive`]. Without a callback the handshake continues.
+    pub fn with_alive_answer(
+        mut self,
+        callback: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.alive_answer = Some(Arc::new(callback));
+        self
+    }
+
+    /// Sets keepalive, `TCP_NODELAY`, buffer sizes and TOS for the connection's socket.
+    pub fn with_socket_options(mut self, socket_options: SocketOptions) -> Self {
+        self.socket_options = socket_options;
+        self
+    }
+
     pub fn with_flags(mut self, flags: DistributionFlags) -> Self {
         self.flags = flags;
         self
     }
 
+    /// Fails the handshake with [`Error::MissingMandatoryFlags`] if the peer
+    /// does not accept all of `flags`. The flags are also added to the requested ones.
+    pub fn require_flags(mut self, flags: DistributionFlags) -> Self {
+        self.flags |= flags;
+        self.required_flags |= flags;
+        self
+    }
+
     pub fn with_creation<C: Into<Creation>>(mut self, creation: C) -> Self {
         self.creation = creation.into();
         self
@@ -104,219 +360,1329 @@ impl ConnectionConfig {
         self.timeout = timeout;
         self
     }
+
+    pub fn with_decode_config(mut self, decode_config: DecodeConfig) -> Self {
+        self.decode_config = decode_config;
+        self
+    }
+
+    /// Encodes outgoing messages with the float policy and atom length limit of `encode_config`.
+    pub fn with_encode_config(mut self, encode_config: EncodeConfig) -> Self {
+        self.encode_config = encode_config;
+        self
+    }
+
+    /// Announces `atoms` to the peer's atom cache in the first frames after connecting,
+    /// so that later frames can refer to them without their text. Only applies when
+    /// `DIST_HDR_ATOM_CACHE` is negotiated.
+    pub fn with_atom_cache_seed(mut self, atoms: impl IntoIterator<Item = Atom>) -> Self {
+        self.atom_cache_seed = atoms.into_iter().collect();
+        self
+    }
+
+    /// After a reconnect, announces the `count` atoms used most on the previous connection.
+    pub fn with_atom_cache_warmup(mut self, count: usize) -> Self {
+        self.atom_cache_warmup = count;
+        self
+    }
+
+    /// Rejects funs in incoming messages. Use for untrusted peers.
+    pub fn with_safe_mode(mut self, safe: bool) -> Self {
+        self.decode_config = self.decode_config.with_safe(safe);
+        self
+    }
+
+    pub fn with_stale_pid_policy(mut self, policy: StalePidPolicy) -> Self {
+        self.stale_pid_policy = policy;
+        self
+    }
+
+    pub fn with_rtt_window(mut self, window: usize) -> Self {
+        self.rtt_window = window;
+        self
+    }
+
+    pub fn with_top_talkers(mut self, capacity: usize) -> Self {
+        self.top_talkers = capacity;
+        self
+    }
+
+    pub fn with_rtt_alert(
+        mut self,
+        threshold: Duration,
+        callback: impl Fn(Duration) + Send + Sync + 'static,
+    ) -> Self {
+        self.rtt_alert = Some((threshold, Arc::new(callback)));
+        self
+    }
+
+    pub fn with_permissive_control_messages(mut self, permissive: bool) -> Self {
+        self.permissive_control_messages = permissive;
+        self
+    }
+
+    /// Makes [`Connection::receive_envelope`] return messages whose payload cannot be decoded,
+    /// e.g. because it uses a term tag this crate does not know, with the payload bytes
+    /// in [`ReceivedMessage::raw_payload`]. Useful for relaying and inspecting such messages.
+    pub fn with_raw_payloads(mut self, raw_payloads: bool) -> Self {
+        self.raw_payloads = raw_payloads;
+        self
+    }
+
+    /// With [`DecodeErrorPolicy::Resume`], a frame that fails to decode fails only its own
+    /// receive, with [`Error::FrameDecode`]. Frames are length-prefixed, so the next receive
+    /// continues with the next frame and one bad message does not take down the link.
+    pub fn with_decode_error_policy(mut self, policy: DecodeErrorPolicy) -> Self {
+        self.decode_error_policy = policy;
+        self
+    }
+
+    /// Whether [`Connection::receive_envelope`] answers each `UNLINK_ID` with the
+    /// `UNLINK_ID_ACK` the link protocol requires, whether or not the link is known.
+    /// Enabled by default. Readers of a read half acknowledge with [`Connection::unlink_ack`],
+    /// see [`ReceiveOptions::unlink_acks`].
+    pub fn with_unlink_acks(mut self, unlink_acks: bool) -> Self {
+        self.unlink_acks = unlink_acks;
+        self
+    }
 }
 
-pub struct Connection {
-    config: ConnectionConfig,
-    handshake: HandshakeStateMachine,
-    transport: FramedTransport,
-    atom_cache: AtomCache,
-    fragment_assembler: FragmentAssembler,
+/// What message sends do with pids whose creation does not match the peer's current one,
+/// see [`Connection::is_stale`].
+///
+/// Such pids belong to processes that died when the peer restarted, so the peer
+/// drops messages sent to them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StalePidPolicy {
+    /// Send the message anyway
+    #[default]
+    Deliver,
+    /// Do not send the message
+    Drop,
+    /// Fail the send with [`Error::StalePid`]
+    Reject,
 }
 
-impl Connection {
-    pub fn new(config: ConnectionConfig) -> Self {
-        let handshake = HandshakeStateMachine::new(
-            config.local_node_name.clone(),
-            config.remote_node_name.clone(),
-            config.cookie.clone(),
-            config.flags,
-            config.creation,
-        );
-        let transport = FramedTransport::new(config.timeout);
+/// What a receive does with a complete frame that fails to decode,
+/// see [`ConnectionConfig::with_decode_error_policy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeErrorPolicy {
+    /// Fail the receive with the decode error, the caller is expected to close the connection
+    #[default]
+    Fail,
+    /// Fail the receive with [`Error::FrameDecode`], which carries the undecoded frame.
+    /// The connection stays up and the next receive reads the next frame
+    Resume,
+}
 
-        Self {
-            config,
-            handshake,
-            transport,
-            atom_cache: AtomCache::new(),
-            fragment_assembler: FragmentAssembler::new(),
+impl DecodeErrorPolicy {
+    /// Wraps `error`, which `frame` failed to decode with, as the policy requires.
+    pub(crate) fn apply(self, error: Error, frame: impl Into<Vec<u8>>) -> Error {
+        match self {
+            DecodeErrorPolicy::Fail => error,
+            DecodeErrorPolicy::Resume => Error::FrameDecode {
+                frame: frame.into(),
+                source: Box::new(error),
+            },
         }
     }
+}
 
-    #[must_use]
-    pub fn state(&self) -> ConnectionState {
-        self.handshake.state()
+/// Options for [`Connection::send_opts`], equivalent to the `erlang:send/3` options.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SendOpts {
+    pub noconnect: bool,
+    pub nosuspend: bool,
+}
+
+impl SendOpts {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    #[must_use]
-    pub fn is_connected(&self) -> bool {
-        self.handshake.state() == ConnectionState::Connected
+    pub fn with_noconnect(mut self, noconnect: bool) -> Self {
+        self.noconnect = noconnect;
+        self
     }
 
-    #[must_use]
-    pub fn negotiated_flags(&self) -> Option<DistributionFlags> {
-        self.handshake.negotiated_flags()
+    pub fn with_nosuspend(mut self, nosuspend: bool) -> Self {
+        self.nosuspend = nosuspend;
+        self
     }
+}
 
-    fn validate_node_name(name: &str) -> Result<(&str, &str)> {
-        let (node_name, host) = name
-            .split_once('@')
-            .ok_or_else(|| Error::InvalidNodeName(name.to_string()))?;
+/// How [`Connection::receive_message_from_read_half_with_options`] treats what it reads,
+/// see [`Connection::receive_options`].
+#[derive(Debug, Clone, Default)]
+pub struct ReceiveOptions {
+    /// Records received ticks and frames
+    pub metrics: Option<ConnectionMetrics>,
+    /// Receives unknown control messages as [`ControlMessage::Generic`] instead of failing
+    pub permissive_control_messages: bool,
+    /// Which messages a [`DecodePipeline`](crate::DecodePipeline) decodes on the blocking pool
+    pub decode_offload: Option<DecodeOffload>,
+    /// Charged with decoded payloads, reads wait for its budget
+    pub decode_accounting: Option<DecodeAccounting>,
+    /// Decompresses frames compressed by the peer, see [`crate::compression`]
+    #[cfg(feature = "zstd")]
+    pub zstd_compression: Option<ZstdCompression>,
+    /// Attached to receive errors, see [`Error::with_connection_id`]
+    pub connection_id: Option<ConnectionId>,
+    /// Reads are instrumented with this span, see [`Connection::span`]
+    pub span: Option<Span>,
+    /// Discards link exit signals received while unlinking, see [`Connection::unlinks`]
+    pub unlinks: Option<UnlinkTracker>,
+    /// Whether the reader should answer `UNLINK_ID`s with [`Connection::unlink_ack`],
+    /// a read half cannot write
+    pub unlink_acks: bool,
+    /// Applied to frames read without an explicit configuration
+    pub decode_config: Option<DecodeConfig>,
+    /// Records the atoms decoded with `decode_config` when it limits atoms. Offloaded
+    /// decodes take turns on it
+    pub atom_table: Option<Arc<Mutex<AtomTable>>>,
+    /// What reads do when a frame fails to decode
+    pub decode_error_policy: DecodeErrorPolicy,
+    /// Reported as [`ReceivedMessage::from_node`], the empty atom if not set
+    pub remote_node: Option<Atom>,
+}
 
-        if node_name.is_empty() || host.is_empty() {
-            return Err(Error::InvalidNodeName(name.to_string()));
-        }
+impl ReceiveOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-        if node_name.len() > 255 {
-            return Err(Error::NodeNameTooLong {
-                size: node_name.len(),
-                max: 255,
-            });
-        }
+    pub fn with_metrics(mut self, metrics: ConnectionMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
 
-        Ok((node_name, host))
+    pub fn with_permissive_control_messages(mut self, permissive: bool) -> Self {
+        self.permissive_control_messages = permissive;
+        self
     }
 
-    async fn lookup_remote_node(&self) -> Result<u16> {
-        let epmd = EpmdClient::new(&self.config.epmd_host).with_timeout(self.config.timeout);
+    pub fn with_decode_offload(mut self, offload: DecodeOffload) -> Self {
+        self.decode_offload = Some(offload);
+        self
+    }
 
-        let (node_name, _host) = Self::validate_node_name(&self.config.remote_node_name)?;
+    pub fn with_decode_accounting(mut self, accounting: DecodeAccounting) -> Self {
+        self.decode_accounting = Some(accounting);
+        self
+    }
 
-        let node_info = epmd.lookup_node(node_name).await?;
-        debug!(
-            "EPMD node info: port={}, highest_version={}, lowest_version={}",
-            node_info.port, node_info.highest_version, node_info.lowest_version
-        );
-        Ok(node_info.port)
+    #[cfg(feature = "zstd")]
+    pub fn with_zstd_compression(mut self, compression: ZstdCompression) -> Self {
+        self.zstd_compression = Some(compression);
+        self
     }
 
-    async fn read_message(&mut self) -> Result<Vec<u8>> {
-        self.transport.read().await
+    pub fn with_connection_id(mut self, id: ConnectionId) -> Self {
+        self.connection_id = Some(id);
+        self
     }
 
-    async fn write_message(&mut self, data: &[u8]) -> Result<()> {
-        self.transport.write(data).await
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
     }
 
-    pub async fn connect(&mut self) -> Result<()> {
-        self.handshake.begin_connect()?;
-        debug!("Connection state: {:?}", self.state());
+    pub fn with_unlinks(mut self, unlinks: UnlinkTracker) -> Self {
+        self.unlinks = Some(unlinks);
+        self
+    }
 
-        let (_node_name, remote_host) = self
-            .config
-            .remote_node_name
-            .split_once('@')
-            .ok_or_else(|| Error::InvalidNodeName(self.config.remote_node_name.clone()))?;
+    pub fn with_unlink_acks(mut self, unlink_acks: bool) -> Self {
+        self.unlink_acks = unlink_acks;
+        self
+    }
 
-        debug!(
-            "Looking up node via EPMD on host: {}",
-            self.config.epmd_host
-        );
-        let port = self.lookup_remote_node().await?;
-        debug!("EPMD returned port: {}", port);
+    pub fn with_decode_config(mut self, decode_config: DecodeConfig) -> Self {
+        self.decode_config = Some(decode_config);
+        self
+    }
 
-        let addr = format!("{}:{}", remote_host, port);
-        debug!("Connecting to: {}", addr);
+    pub fn with_decode_error_policy(mut self, policy: DecodeErrorPolicy) -> Self {
+        self.decode_error_policy = policy;
+        self
+    }
 
-        let stream = tokio::time::timeout(self.config.timeout, TcpStream::connect(&addr))
-            .await
-            .map_err(|_| Error::Timeout(self.config.timeout))?
-            .map_err(Error::Io)?;
+    pub fn with_remote_node(mut self, node: Atom) -> Self {
+        self.remote_node = Some(node);
+        self
+    }
 
-        debug!("TCP connection established");
-        self.transport.connect(stream);
+    /// Continues counting atoms from `atom_table`, e.g. the one of the connection.
+    pub fn with_atom_table(mut self, atom_table: AtomTable) -> Self {
+        self.atom_table = Some(Arc::new(Mutex::new(atom_table)));
+        self
+    }
 
-        debug!("Starting handshake sequence");
-        self.send_name().await?;
-        self.receive_status().await?;
-        self.send_complement().await?;
-        self.receive_challenge().await?;
-        self.send_challenge_reply().await?;
-        self.receive_challenge_ack().await?;
+    /// The shared atom table, when `decode_config` limits atoms.
+    fn lock_atom_table(&self) -> Option<MutexGuard<'_, AtomTable>> {
+        self.decode_config.as_ref()?.atom_limit()?;
+        self.atom_table
+            .as_ref()
+            .map(|table| table.lock().unwrap_or_else(PoisonError::into_inner))
+    }
 
-        self.transport.set_frame_mode(FrameMode::Distribution);
-        debug!("Handshake complete, connection established");
+    /// Whether a received message is delivered, see [`UnlinkTracker::accept`].
+    pub(crate) fn accepts(&self, control: &ControlMessage) -> bool {
+        match &self.unlinks {
+            Some(unlinks) => unlinks.accept(control),
+            None => true,
+        }
+    }
+}
 
-        Ok(())
+/// A received message with its provenance, see [`Connection::receive_envelope`].
+#[derive(Debug, Clone)]
+pub struct ReceivedMessage {
+    pub control: ControlMessage,
+    pub payload: Option<OwnedTerm>,
+    /// The undecoded payload, set instead of `payload` when it failed to decode
+    /// and the connection uses [`ConnectionConfig::with_raw_payloads`]
+    pub raw_payload: Option<Vec<u8>>,
+    /// The peer the message was received from
+    pub from_node: Atom,
+    /// When the message, or its last fragment, was read
+    pub received_at: SystemTime,
+    /// The size of the encoded message without the distribution framing,
+    /// the reassembled size for a fragmented message
+    pub byte_size: usize,
+    /// The approximate memory the decoded payload takes, see [`OwnedTerm::heap_size`].
+    /// Pass it to [`DecodeAccounting::release`] once the payload is consumed.
+    pub heap_size: usize,
+}
+
+impl ReceivedMessage {
+    pub fn into_parts(self) -> (ControlMessage, Option<OwnedTerm>) {
+        (self.control, self.payload)
     }
+}
 
-    async fn send_name(&mut self) -> Result<()> {
-        debug!("Sending name: {}", self.config.local_node_name);
-        let data = self.handshake.prepare_send_name()?;
-        trace!("SendName (old format) encoded: {} bytes", data.len());
-        trace!("SendName data (hex): {:02x?}", data);
-        self.transport.write_raw(&data).await?;
-        debug!("Name sent, awaiting status");
-        Ok(())
+/// The result of [`Connection::send_opts`], equivalent to the `erlang:send/3` return values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendOutcome {
+    Ok,
+    NoConnect,
+    NoSuspend,
+}
+
+/// A distribution connection to one remote node.
+///
+/// # Ordering
+///
+/// Messages and signals are written to the socket in the order the send functions
+/// are called and the peer processes them in that order. There are no priorities:
+/// a large message delays everything sent after it. [`Connection::send_ordered`]
+/// makes the per-stream guarantee explicit for applications that multiplex many
+/// logical streams over one connection.
+pub struct Connection<C: Codec = EtfCodec> {
+    config: ConnectionConfig,
+    handshake: HandshakeStateMachine,
+    transport: FramedTransport,
+    codec: C,
+    fragment_assembler: FragmentAssembler,
+    peer_addr: Option<SocketAddr>,
+    peer_creation: Option<u32>,
+    metrics: ConnectionMetrics,
+    decode_accounting: DecodeAccounting,
+    /// The last sequence number assigned to each key by [`Connection::send_ordered`]
+    ordered_sequences: HashMap<ChannelKey, SequenceId>,
+    last_sent: Instant,
+    hibernated_at: Option<Instant>,
+    id: ConnectionId,
+    span: Span,
+    unlinks: UnlinkTracker,
+}
+
+impl Connection {
+    pub fn new(config: ConnectionConfig) -> Self {
+        Self::with_codec(config, EtfCodec::default())
     }
 
-    async fn send_complement(&mut self) -> Result<()> {
-        debug!("Sending complement message");
-        let data = self.handshake.prepare_complement()?;
-        trace!("Complement data (hex): {:02x?}", data);
-        self.transport.write_raw(&data).await?;
-        debug!("Complement sent");
-        Ok(())
+    /// Returns the distinct atoms decoded from this connection's peer so far.
+    #[must_use]
+    pub fn atom_table(&self) -> &AtomTable {
+        self.codec.atom_table()
     }
 
-    async fn receive_status(&mut self) -> Result<()> {
-        debug!("Waiting for status message");
-        let data = self.read_message().await?;
-        trace!("Received status message: {} bytes", data.len());
-        self.handshake.handle_status(&data)?;
-        Ok(())
+    /// Atoms sent to the peer's atom cache and how often they were used.
+    pub fn outgoing_atom_cache(&self) -> &OutgoingAtomCache {
+        self.codec.outgoing_atom_cache()
     }
 
-    async fn receive_challenge(&mut self) -> Result<()> {
-        let data = self.read_message().await?;
-        self.handshake.handle_challenge(&data)?;
-        debug!("Negotiated flags: {:?}", self.handshake.negotiated_flags());
-        Ok(())
+    /// Returns up to `limit` atoms, most used first, e.g. to persist them and pass them to
+    /// [`ConnectionConfig::with_atom_cache_seed`] for a later connection.
+    pub fn hot_atoms(&self, limit: usize) -> Vec<Atom> {
+        self.codec.outgoing_atom_cache().hottest(limit)
     }
 
-    async fn send_challenge_reply(&mut self) -> Result<()> {
-        debug!("Sending challenge reply");
-        let data = self.handshake.prepare_challenge_reply()?;
-        trace!("Challenge reply data (hex): {:02x?}", data);
-        self.transport.write_raw(&data).await?;
-        Ok(())
+    #[doc(hidden)]
+    pub fn decode_complete_fragment(
+        complete_data: &[u8],
+        atom_cache: &mut AtomCache,
+    ) -> Result<(ControlMessage, Option<OwnedTerm>)> {
+        let (control_term, message) = if complete_data.len() >= 2
+            && complete_data[0] == VERSION
+            && complete_data[1] == DIST_HEADER
+        {
+            decoder::decode_with_atom_cache(complete_data, atom_cache)?
+        } else {
+            (decoder::decode(complete_data)?, None)
+        };
+
+        let control = ControlMessage::from_term(&control_term)?;
+        Ok((control, message))
     }
 
-    async fn receive_challenge_ack(&mut self) -> Result<()> {
-        let data = self.read_message().await?;
-        self.handshake.handle_challenge_ack(&data)?;
-        Ok(())
+    #[doc(hidden)]
+    pub fn decode_complete_fragment_with_config(
+        complete_data: &[u8],
+        atom_cache: &mut AtomCache,
+        decode_config: &DecodeConfig,
+        atom_table: &mut AtomTable,
+    ) -> Result<(ControlMessage, Option<OwnedTerm>)> {
+        let (control_term, message) = if complete_data.len() >= 2
+            && complete_data[0] == VERSION
+            && complete_data[1] == DIST_HEADER
+        {
+            decoder::decode_with_atom_cache_and_config(
+                complete_data,
+                atom_cache,
+                decode_config,
+                atom_table,
+            )?
+        } else {
+            (
+                decoder::decode_with_config(complete_data, decode_config, atom_table)?,
+                None,
+            )
+        };
+
+        let control = ControlMessage::from_term(&control_term)?;
+        Ok((control, message))
     }
 
-    pub async fn send_raw(&mut self, data: &[u8]) -> Result<()> {
-        if self.state() != ConnectionState::Connected {
-            return Err(Error::InvalidState {
-                state: self.state(),
-            });
-        }
+    pub async fn receive_message_from_read_half(
+        read_half: &mut OwnedReadHalf,
+        timeout: Duration,
+    ) -> Result<(ControlMessage, Option<OwnedTerm>)> {
+        Self::receive_from_read_half(read_half, timeout, None, &ReceiveOptions::default())
+            .await
+            .map(ReceivedMessage::into_parts)
+    }
 
-        if data.len() > MAX_MESSAGE_SIZE {
-            return Err(Error::MessageTooLarge {
-                size: data.len(),
-                max: MAX_MESSAGE_SIZE,
+    /// Like [`Connection::receive_message_from_read_half`] but applies `decode_config`
+    /// and records decoded atoms in `atom_table`.
+    pub async fn receive_message_from_read_half_with_config(
+        read_half: &mut OwnedReadHalf,
+        timeout: Duration,
+        decode_config: &DecodeConfig,
+        atom_table: &mut AtomTable,
+    ) -> Result<(ControlMessage, Option<OwnedTerm>)> {
+        Self::receive_from_read_half(
+            read_half,
+            timeout,
+            Some((decode_config, atom_table)),
+            &ReceiveOptions::default(),
+        )
+        .await
+        .map(ReceivedMessage::into_parts)
+    }
+
+    /// Like [`Connection::receive_message_from_read_half`] with [`ReceiveOptions`],
+    /// e.g. the ones of the connection the read half was taken from.
+    pub async fn receive_message_from_read_half_with_options(
+        read_half: &mut OwnedReadHalf,
+        timeout: Duration,
+        options: &ReceiveOptions,
+    ) -> Result<(ControlMessage, Option<OwnedTerm>)> {
+        Self::receive_envelope_from_read_half_with_options(read_half, timeout, options)
+            .await
+            .map(ReceivedMessage::into_parts)
+    }
+
+    /// Like [`Connection::receive_message_from_read_half_with_options`] but returns the message
+    /// with its provenance and the size charged to [`ReceiveOptions::decode_accounting`].
+    pub async fn receive_envelope_from_read_half_with_options(
+        read_half: &mut OwnedReadHalf,
+        timeout: Duration,
+        options: &ReceiveOptions,
+    ) -> Result<ReceivedMessage> {
+        Self::receive_from_read_half(read_half, timeout, None, options).await
+    }
+
+    async fn receive_from_read_half(
+        read_half: &mut OwnedReadHalf,
+        timeout: Duration,
+        decoding: Option<(&DecodeConfig, &mut AtomTable)>,
+        options: &ReceiveOptions,
+    ) -> Result<ReceivedMessage> {
+        let span = options.span.clone().unwrap_or_else(Span::none);
+        let mut decoding = decoding;
+        let result: Result<_> = async {
+            loop {
+                let buf = Self::read_pass_through_frame(read_half, timeout, options).await?;
+                let decoding = decoding
+                    .as_mut()
+                    .map(|(config, atom_table)| (*config, &mut **atom_table));
+                let received = Self::decode_pass_through_frame(&buf, decoding, options)?;
+                if options.accepts(&received.control) {
+                    return Ok(received);
+                }
+                trace!(
+                    "Ignoring a link exit signal received while unlinking: {:?}",
+                    received.control
+                );
+            }
+        }
+        .instrument(span)
+        .await;
+        match options.connection_id {
+            Some(id) => result.map_err(|e| e.with_connection_id(id)),
+            None => result,
+        }
+    }
+
+    /// Reads the next frame that is not a tick, including its pass-through marker.
+    pub(crate) async fn read_pass_through_frame(
+        read_half: &mut OwnedReadHalf,
+        timeout: Duration,
+        options: &ReceiveOptions,
+    ) -> Result<Vec<u8>> {
+        if let Some(accounting) = &options.decode_accounting {
+            accounting.wait_for_capacity().await;
+        }
+        loop {
+            let len = {
+                trace!("Attempting to read message length (4 bytes, distribution protocol)...");
+                let mut len_bytes = [0u8; 4];
+                time::timeout(timeout, read_half.read_exact(&mut len_bytes))
+                    .await
+                    .map_err(|_| Error::Timeout(timeout))??;
+                let len = u32::from_be_bytes(len_bytes);
+                trace!(
+                    "Read message length: {} bytes (raw bytes: {:02x?})",
+                    len, len_bytes
+                );
+                len as usize
+            };
+
+            if len == 0 {
+                trace!("Received tick (heartbeat), continuing...");
+                if let Some(metrics) = &options.metrics {
+                    metrics.record_tick_received();
+                }
+                continue;
+            }
+            if let Some(metrics) = &options.metrics {
+                metrics.record_received();
+            }
+
+            if len > MAX_MESSAGE_SIZE {
+                return Err(Error::MessageTooLarge {
+                    size: len,
+                    max: MAX_MESSAGE_SIZE,
+                });
+            }
+
+            let mut buf = vec![0u8; len];
+            trace!("Reading {} bytes of message data...", len);
+            time::timeout(timeout, read_half.read_exact(&mut buf))
+                .await
+                .map_err(|_| Error::Timeout(timeout))??;
+
+            trace!("Read message data (hex): {:02x?}", buf);
+            #[cfg(feature = "zstd")]
+            if options.zstd_compression.is_some() {
+                return Ok(compression::decompress(buf, MAX_MESSAGE_SIZE)?);
+            }
+            return Ok(buf);
+        }
+    }
+
+    /// Decodes a frame read by [`Connection::read_pass_through_frame`].
+    pub(crate) fn decode_pass_through_frame(
+        buf: &[u8],
+        decoding: Option<(&DecodeConfig, &mut AtomTable)>,
+        options: &ReceiveOptions,
+    ) -> Result<ReceivedMessage> {
+        Self::decode_pass_through(buf, decoding, options)
+            .map_err(|e| options.decode_error_policy.apply(e, buf))
+    }
+
+    fn decode_pass_through(
+        buf: &[u8],
+        decoding: Option<(&DecodeConfig, &mut AtomTable)>,
+        options: &ReceiveOptions,
+    ) -> Result<ReceivedMessage> {
+        if buf.is_empty() {
+            return Err(Error::InvalidStateMessage(
+                "Empty message received".to_string(),
+            ));
+        }
+
+        let pass_through_marker = buf[0];
+        trace!("Pass-through marker: {}", pass_through_marker);
+
+        if pass_through_marker != PASS_THROUGH {
+            return Err(Error::Protocol(format!(
+                "Expected pass-through marker {}, got {}",
+                PASS_THROUGH, pass_through_marker
+            )));
+        }
+
+        if let Some(accounting) = &options.decode_accounting
+            && accounting.budget().max_message_size.is_some()
+        {
+            accounting.check(codec::estimate_frame_heap_size(buf)?)?;
+        }
+
+        let control_and_payload = &buf[1..];
+        trace!(
+            "Decoding control and payload from {} bytes",
+            control_and_payload.len()
+        );
+
+        let mut shared_atom_table = options.lock_atom_table();
+        let mut atom_table = AtomTable::new();
+        let decoding = match (decoding, &options.decode_config) {
+            (Some(decoding), _) => Some(decoding),
+            (None, Some(decode_config)) => Some((
+                decode_config,
+                shared_atom_table.as_deref_mut().unwrap_or(&mut atom_table),
+            )),
+            (None, None) => None,
+        };
+        let mut terms = match decoding {
+            Some((decode_config, atom_table)) => {
+                decoder::decode_all_with_config(control_and_payload, decode_config, atom_table)
+            }
+            None => decoder::decode_all(control_and_payload),
+        };
+        let control_term = terms.next().ok_or_else(|| Error::UnexpectedEof {
+            context: "control message".to_string(),
+        })??;
+        trace!("Decoded control term: {:?}", control_term);
+        trace!("Remaining bytes after control: {}", terms.remaining().len());
+
+        let mut control_msg = ControlMessage::from_term(&control_term)?;
+        if !options.permissive_control_messages {
+            control_msg = control_msg.deny_generic()?;
+        }
+        trace!("Parsed control message: {:?}", control_msg);
+        if let Some(metrics) = &options.metrics {
+            metrics.record_control_message(MessageDirection::Inbound, &control_msg, buf.len());
+        }
+
+        let payload = terms.next().transpose()?;
+        trace!("Decoded payload: {:?}", payload);
+        let heap_size = payload.as_ref().map_or(0, OwnedTerm::heap_size);
+        if let Some(accounting) = &options.decode_accounting {
+            accounting.charge(heap_size)?;
+        }
+
+        Ok(ReceivedMessage {
+            control: control_msg,
+            payload,
+            raw_payload: None,
+            from_node: options.remote_node.clone().unwrap_or_else(|| Atom::new("")),
+            received_at: SystemTime::now(),
+            byte_size: buf.len(),
+            heap_size,
+        })
+    }
+}
+
+impl<C: Codec> Connection<C> {
+    /// A connection that encodes and decodes messages with `codec`.
+    pub fn with_codec(config: ConnectionConfig, codec: C) -> Self {
+        let handshake = config.handshake_state_machine();
+        let transport = FramedTransport::new(config.timeout);
+        let metrics =
+            ConnectionMetrics::new(config.rtt_window).with_top_talkers(config.top_talkers);
+        if let Some((threshold, callback)) = &config.rtt_alert {
+            metrics.set_rtt_alert(*threshold, Arc::clone(callback));
+        }
+        let decode_accounting = DecodeAccounting::new(config.decode_budget);
+        let id = config.connection_id.unwrap_or_default();
+        let span = debug_span!(
+            "connection",
+            connection_id = %id,
+            remote_node = %config.remote_node_name
+        );
+
+        Self {
+            config,
+            handshake,
+            transport,
+            codec,
+            fragment_assembler: FragmentAssembler::new(),
+            peer_addr: None,
+            peer_creation: None,
+            metrics,
+            decode_accounting,
+            ordered_sequences: HashMap::new(),
+            last_sent: Instant::now(),
+            hibernated_at: None,
+            id,
+            span,
+            unlinks: UnlinkTracker::new(),
+        }
+    }
+
+    /// The codec messages are encoded and decoded with.
+    #[must_use]
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    /// Stays the same across reconnects of this connection.
+    #[must_use]
+    pub fn id(&self) -> ConnectionId {
+        self.id
+    }
+
+    /// The span the connection's operations are instrumented with. It carries
+    /// `connection_id` and `remote_node` fields.
+    #[must_use]
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    #[must_use]
+    pub fn state(&self) -> ConnectionState {
+        self.handshake.state()
+    }
+
+    #[must_use]
+    pub fn is_connected(&self) -> bool {
+        self.handshake.state() == ConnectionState::Connected
+    }
+
+    #[must_use]
+    pub fn negotiated_flags(&self) -> Option<DistributionFlags> {
+        self.handshake.negotiated_flags()
+    }
+
+    /// Which optional behaviors the negotiated flags enable, `None` before the handshake.
+    #[must_use]
+    pub fn compatibility_report(&self) -> Option<CompatibilityReport> {
+        self.negotiated_flags()
+            .map(|flags| flags.compatibility_report())
+    }
+
+    /// How pids, ports and references are encoded for the peer: peers that did not
+    /// negotiate `BIG_CREATION` get the legacy tags with 8-bit creations.
+    #[must_use]
+    pub fn encode_mode(&self) -> EncodeMode {
+        match self.negotiated_flags() {
+            Some(flags) if !flags.has(DistributionFlags::BIG_CREATION) => EncodeMode::Legacy,
+            _ => EncodeMode::Current,
+        }
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &ConnectionConfig {
+        &self.config
+    }
+
+    /// The address the connection was established to, out of those the remote host resolved to.
+    #[must_use]
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.peer_addr
+    }
+
+    /// The creation of the peer as of the most recent handshake. It is kept after
+    /// the connection closes and changes when the peer restarts.
+    #[must_use]
+    pub fn peer_creation(&self) -> Option<u32> {
+        self.peer_creation
+    }
+
+    /// Returns true if `pid` belongs to the peer but predates its current incarnation,
+    /// that is, the process is gone because the peer restarted since the pid was obtained.
+    ///
+    /// Pids of other nodes, pids with the [`Creation::WILDCARD`] creation and peers
+    /// that did not send a creation are never stale.
+    #[must_use]
+    pub fn is_stale(&self, pid: &ExternalPid) -> bool {
+        match self.peer_creation {
+            Some(current) => {
+                !Creation::new(pid.creation).matches(current)
+                    && pid.node.as_str() == self.config.remote_node_name
+            }
+            None => false,
+        }
+    }
+
+    /// RTT samples and tick counters, shared by clones of the returned value
+    #[must_use]
+    pub fn metrics(&self) -> &ConnectionMetrics {
+        &self.metrics
+    }
+
+    /// The sizes of the payloads decoded on this connection, kept across reconnects.
+    #[must_use]
+    pub fn decode_accounting(&self) -> &DecodeAccounting {
+        &self.decode_accounting
+    }
+
+    /// Releases memory an idle connection does not need while keeping it open: the capacity
+    /// of the fragment assembler and the atom caches, and the outgoing atom cache itself with
+    /// [`ConnectionConfig::with_hibernation_atom_cache_reset`]. Atoms announced by the peer
+    /// are kept. Buffers grow again as messages flow.
+    pub fn hibernate(&mut self) {
+        debug!(
+            "Hibernating the connection to {}",
+            self.config.remote_node_name
+        );
+        self.fragment_assembler.shrink_to_fit();
+        self.codec.shrink(self.config.hibernation_resets_atom_cache);
+        self.ordered_sequences.shrink_to_fit();
+        self.hibernated_at = Some(Instant::now());
+    }
+
+    /// Hibernates the connection if it has been idle for [`ConnectionConfig::idle_hibernation`].
+    /// Returns whether it did. Meant to be called periodically.
+    pub fn hibernate_if_idle(&mut self) -> bool {
+        let Some(after) = self.config.idle_hibernation else {
+            return false;
+        };
+        if !self.is_connected() || self.is_hibernating() || self.idle_for() < after {
+            return false;
+        }
+        self.hibernate();
+        true
+    }
+
+    /// Whether the connection was hibernated and has not sent or received a message since.
+    pub fn is_hibernating(&self) -> bool {
+        self.hibernated_at
+            .is_some_and(|at| self.last_activity() <= at)
+    }
+
+    /// How long the connection has gone without sending or receiving a message.
+    /// Ticks do not count. Messages read from the read half count via [`Connection::metrics`].
+    pub fn idle_for(&self) -> Duration {
+        self.last_activity().elapsed()
+    }
+
+    fn last_activity(&self) -> Instant {
+        match self.metrics.last_message_received() {
+            Some(received) => received.max(self.last_sent),
+            None => self.last_sent,
+        }
+    }
+
+    fn validate_node_name(name: &str) -> Result<(&str, &str)> {
+        let (node_name, host) = name
+            .split_once('@')
+            .ok_or_else(|| Error::InvalidNodeName(name.to_string()))?;
+
+        if node_name.is_empty() || host.is_empty() {
+            return Err(Error::InvalidNodeName(name.to_string()));
+        }
+
+        if node_name.len() > 255 {
+            return Err(Error::NodeNameTooLong {
+                size: node_name.len(),
+                max: 255,
+            });
+        }
+
+        Ok((node_name, host))
+    }
+
+    async fn lookup_remote_node(&self) -> Result<NodeInfo> {
+        let epmd = match &self.config.epmd_client {
+            Some(client) => client.clone(),
+            None => EpmdClient::from_config(
+                EpmdClientConfig::new(&self.config.epmd_host)
+                    .with_timeout(self.config.timeout)
+                    .with_resolver(Arc::clone(&self.config.resolver)),
+            ),
+        };
+
+        let (node_name, _host) = Self::validate_node_name(&self.config.remote_node_name)?;
+
+        let node_info = epmd.lookup_node(node_name).await?;
+        debug!(
+            "EPMD node info: port={}, highest_version={}, lowest_version={}",
+            node_info.port, node_info.highest_version, node_info.lowest_version
+        );
+        Ok(node_info)
+    }
+
+    async fn read_message(&mut self) -> Result<Vec<u8>> {
+        self.transport.read().await
+    }
+
+    async fn write_message(&mut self, data: &[u8]) -> Result<()> {
+        self.transport.write(data).await
+    }
+
+    /// Errors carry the connection's id, see [`Error::connection_id`].
+    pub async fn connect(&mut self) -> Result<()> {
+        let id = self.id;
+        let span = self.span.clone();
+        self.establish()
+            .instrument(span)
+            .await
+            .map_err(|e| e.with_connection_id(id))
+    }
+
+    async fn establish(&mut self) -> Result<()> {
+        self.handshake.begin_connect()?;
+        debug!("Connection state: {:?}", self.state());
+
+        let (_node_name, remote_host) = self
+            .config
+            .remote_node_name
+            .split_once('@')
+            .ok_or_else(|| Error::InvalidNodeName(self.config.remote_node_name.clone()))?;
+
+        let port = match self.config.remote_port {
+            Some(port) => {
+                debug!("Skipping EPMD lookup, using port {}", port);
+                port
+            }
+            None => {
+                debug!(
+                    "Looking up node via EPMD on host: {}",
+                    self.config.epmd_host
+                );
+                let node_info = self.lookup_remote_node().await?;
+                debug!("EPMD returned port: {}", node_info.port);
+                self.handshake.select_handshake_version(
+                    node_info.lowest_version,
+                    node_info.highest_version,
+                )?;
+                node_info.port
+            }
+        };
+        debug!(
+            "Using handshake protocol version {}",
+            self.handshake.handshake_version().as_u16()
+        );
+
+        debug!("Connecting to: {}:{}", remote_host, port);
+        let (stream, peer_addr) = time::timeout(
+            self.config.timeout,
+            happy_eyeballs::connect_with_resolver(
+                self.config.resolver.as_ref(),
+                remote_host,
+                port,
+                self.config.connection_attempt_delay,
+            ),
+        )
+        .await
+        .map_err(|_| Error::Timeout(self.config.timeout))??;
+
+        debug!("TCP connection established to {}", peer_addr);
+        if !self.config.socket_options.is_empty() {
+            self.config.socket_options.apply(&stream)?;
+            debug!("Applied socket options: {:?}", self.config.socket_options);
+        }
+        self.peer_addr = Some(peer_addr);
+        self.transport.connect(stream);
+
+        self.complete_handshake().await
+    }
+
+    /// Performs the handshake over a transport the caller has connected, e.g. after
+    /// exchanging a proxy protocol preamble with [`FramedTransport::write_raw`] and
+    /// [`FramedTransport::read_raw`]. The transport is switched to [`FrameMode::Handshake`].
+    pub async fn connect_over(&mut self, mut transport: FramedTransport) -> Result<()> {
+        if !transport.is_connected() {
+            return Err(Error::InvalidStateMessage("no active stream".to_string()));
+        }
+        self.handshake
+            .begin_connect()
+            .map_err(|e| e.with_connection_id(self.id))?;
+        transport.set_frame_mode(FrameMode::Handshake);
+        self.peer_addr = transport.peer_addr();
+        self.transport = transport;
+
+        let id = self.id;
+        let span = self.span.clone();
+        self.complete_handshake()
+            .instrument(span)
+            .await
+            .map_err(|e| e.with_connection_id(id))
+    }
+
+    /// Takes over a transport on which the caller completed the handshake by driving
+    /// `handshake` itself, e.g. one made with [`ConnectionConfig::handshake_state_machine`].
+    /// `handshake` is used for later reconnects too.
+    pub fn adopt_handshake(
+        &mut self,
+        transport: FramedTransport,
+        handshake: HandshakeStateMachine,
+    ) -> Result<()> {
+        if handshake.state() != ConnectionState::Connected {
+            return Err(Error::InvalidState {
+                state: handshake.state(),
+            });
+        }
+        if !transport.is_connected() {
+            return Err(Error::InvalidStateMessage("no active stream".to_string()));
+        }
+        self.peer_addr = transport.peer_addr();
+        self.transport = transport;
+        self.handshake = handshake;
+        let span = self.span.clone();
+        let _entered = span.enter();
+        self.finish_connect();
+        Ok(())
+    }
+
+    async fn complete_handshake(&mut self) -> Result<()> {
+        debug!("Starting handshake sequence");
+        self.drive_handshake().await?;
+        if let Some(negotiated) = self.handshake.negotiated_flags() {
+            let diff = self.handshake.requested_flags().diff(&negotiated);
+            debug!("Negotiated flags: {}", negotiated);
+            if !diff.missing.is_empty() {
+                debug!("Flags not accepted by the peer: {}", diff.missing);
+            }
+        }
+        self.finish_connect();
+        Ok(())
+    }
+
+    /// Switches the transport to distribution framing and resets per-connection state.
+    fn finish_connect(&mut self) {
+        self.transport.set_frame_mode(FrameMode::Distribution);
+        #[cfg(feature = "zstd")]
+        self.transport.set_compression(self.config.zstd_compression);
+        self.codec.reset(&self.config);
+        self.update_peer_creation();
+        self.last_sent = Instant::now();
+        self.hibernated_at = None;
+        debug!("Handshake complete, connection established");
+    }
+
+    fn update_peer_creation(&mut self) {
+        let current = self.handshake.peer_creation();
+        if let (Some(previous), Some(current)) = (self.peer_creation, current)
+            && previous != current
+        {
+            debug!(
+                "Node {} restarted: creation changed from {} to {}",
+                self.config.remote_node_name, previous, current
+            );
+        }
+        if current.is_some() {
+            self.peer_creation = current;
+        }
+    }
+
+    /// Applies [`ConnectionConfig::stale_pid_policy`], returns false if the message must not be sent.
+    fn check_stale_pid(&self, to_pid: &ExternalPid) -> Result<bool> {
+        if !self.is_stale(to_pid) {
+            return Ok(true);
+        }
+        match self.config.stale_pid_policy {
+            StalePidPolicy::Deliver => Ok(true),
+            StalePidPolicy::Drop => {
+                debug!(
+                    "Dropping a message to stale pid {}, the peer has creation {:?}",
+                    to_pid, self.peer_creation
+                );
+                Ok(false)
+            }
+            StalePidPolicy::Reject => Err(Error::StalePid {
+                pid: to_pid.to_string(),
+                node: self.config.remote_node_name.clone(),
+                creation: to_pid.creation,
+                current: self.peer_creation.unwrap_or_default(),
+            }),
+        }
+    }
+
+    /// Performs the actions of the handshake state machine and feeds it
+    /// the peer's messages until the handshake completes.
+    async fn drive_handshake(&mut self) -> Result<()> {
+        let (mut actions, mut state) = self.handshake.on_event(HandshakeEvent::Connect)?;
+        loop {
+            for action in actions {
+                match action {
+                    HandshakeAction::Send(data) => {
+                        trace!(
+                            "Sending handshake message in state {}: {:02x?}",
+                            state, data
+                        );
+                        self.transport.write_raw(&data).await?;
+                    }
+                    HandshakeAction::Complete => return Ok(()),
+                }
+            }
+            if state == ConnectionState::Failed {
+                return Err(Error::PeerConnectionAlive {
+                    node: self.config.remote_node_name.clone(),
+                });
+            }
+            debug!("Handshake state: {}", state);
+            let data = self.read_message().await?;
+            (actions, state) = self.handshake.on_event(HandshakeEvent::Received(data))?;
+        }
+    }
+
+    pub async fn send_raw(&mut self, data: &[u8]) -> Result<()> {
+        if self.state() != ConnectionState::Connected {
+            return Err(Error::InvalidState {
+                state: self.state(),
+            });
+        }
+
+        if data.len() > MAX_MESSAGE_SIZE {
+            return Err(Error::MessageTooLarge {
+                size: data.len(),
+                max: MAX_MESSAGE_SIZE,
+            });
+        }
+
+        if !data.is_empty() {
+            self.last_sent = Instant::now();
+        }
+        self.write_message(data).await
+    }
+
+    /// Sends a tick (an empty frame), which keeps the peer's `net_ticktime` timer from expiring.
+    pub async fn send_tick(&mut self) -> Result<()> {
+        self.send_raw(&[]).await?;
+        self.metrics.record_tick_sent();
+        Ok(())
+    }
+
+    pub async fn receive_raw(&mut self) -> Result<Vec<u8>> {
+        if self.state() != ConnectionState::Connected {
+            return Err(Error::InvalidState {
+                state: self.state(),
+            });
+        }
+
+        self.read_message().await
+    }
+
+    pub async fn close(&mut self) -> Result<()> {
+        self.transport.close();
+        self.handshake.disconnect();
+        self.peer_addr = None;
+        Ok(())
+    }
+
+    /// Sends `message` to `to_pid`. When the peer negotiated `SEND_SENDER`, the message
+    /// is sent with a `SEND_SENDER` that carries `from_pid`, otherwise with a `SEND`.
+    pub async fn send_message(
+        &mut self,
+        from_pid: ExternalPid,
+        to_pid: ExternalPid,
+        message: OwnedTerm,
+    ) -> Result<()> {
+        if !self.is_connected() {
+            return Err(Error::InvalidState {
+                state: self.state(),
+            });
+        }
+        if !self.check_stale_pid(&to_pid)? {
+            return Ok(());
+        }
+
+        let control = match self.negotiated_flags() {
+            Some(flags) if flags.has(DistributionFlags::SEND_SENDER) => {
+                ControlMessage::SendSender {
+                    from_pid: OwnedTerm::Pid(from_pid),
+                    to_pid: OwnedTerm::Pid(to_pid),
+                }
+            }
+            _ => ControlMessage::Send {
+                cookie: OwnedTerm::Atom(Atom::new("")),
+                to_pid: OwnedTerm::Pid(to_pid),
+            },
+        };
+
+        self.send_control_message(control, Some(message)).await
+    }
+
+    pub async fn send_to_name(
+        &mut self,
+        from_pid: ExternalPid,
+        to_name: Atom,
+        message: OwnedTerm,
+    ) -> Result<()> {
+        if !self.is_connected() {
+            return Err(Error::InvalidState {
+                state: self.state(),
+            });
+        }
+
+        let control = ControlMessage::RegSend {
+            from_pid: OwnedTerm::Pid(from_pid),
+            cookie: OwnedTerm::Atom(Atom::new("")),
+            to_name: OwnedTerm::Atom(to_name),
+        };
+
+        self.send_control_message(control, Some(message)).await
+    }
+
+    /// Sends `message` to a process alias, such as the one in the `[alias | Ref]` tag
+    /// of a `gen_server:call` from OTP 24+.
+    pub async fn send_to_alias(
+        &mut self,
+        from_pid: ExternalPid,
+        alias: ExternalReference,
+        message: OwnedTerm,
+    ) -> Result<()> {
+        if !self.is_connected() {
+            return Err(Error::InvalidState {
+                state: self.state(),
+            });
+        }
+        if let Some(flags) = self.negotiated_flags()
+            && !flags.has(DistributionFlags::ALIAS)
+        {
+            return Err(Error::AliasesNotSupported {
+                node: self.config.remote_node_name.clone(),
+            });
+        }
+
+        let control = ControlMessage::AliasSend {
+            from_pid: OwnedTerm::Pid(from_pid),
+            alias: OwnedTerm::Reference(alias),
+        };
+
+        self.send_control_message(control, Some(message)).await
+    }
+
+    pub async fn link(&mut self, from_pid: &ExternalPid, to_pid: &ExternalPid) -> Result<()> {
+        if !self.is_connected() {
+            return Err(Error::InvalidState {
+                state: self.state(),
+            });
+        }
+
+        let control = ControlMessage::Link {
+            from_pid: OwnedTerm::Pid(from_pid.clone()),
+            to_pid: OwnedTerm::Pid(to_pid.clone()),
+        };
+
+        self.send_control_message(control, None).await
+    }
+
+    pub async fn unlink(
+        &mut self,
+        from_pid: &ExternalPid,
+        to_pid: &ExternalPid,
+        unlink_id: u64,
+    ) -> Result<()> {
+        if !self.is_connected() {
+            return Err(Error::InvalidState {
+                state: self.state(),
+            });
+        }
+
+        let control = ControlMessage::UnlinkId {
+            id: unlink_id,
+            from_pid: OwnedTerm::Pid(from_pid.clone()),
+            to_pid: OwnedTerm::Pid(to_pid.clone()),
+        };
+
+        // Recorded first: an exit signal the other process sent before it sees the unlink
+        // may be read before the write completes
+        self.unlinks.begin(from_pid, to_pid, unlink_id);
+        let result = self.send_control_message(control, None).await;
+        if result.is_err() {
+            self.unlinks.forget(from_pid, to_pid);
+        }
+        result
+    }
+
+    /// The unlinks of this connection that await their `UNLINK_ID_ACK`.
+    pub fn unlinks(&self) -> &UnlinkTracker {
+        &self.unlinks
+    }
+
+    pub async fn monitor(
+        &mut self,
+        from_pid: &ExternalPid,
+        to_proc: &ExternalPid,
+        reference: &ExternalReference,
+    ) -> Result<()> {
+        if !self.is_connected() {
+            return Err(Error::InvalidState {
+                state: self.state(),
             });
         }
 
-        self.write_message(data).await
+        let control = ControlMessage::MonitorP {
+            from_pid: OwnedTerm::Pid(from_pid.clone()),
+            to_proc: OwnedTerm::Pid(to_proc.clone()),
+            reference: OwnedTerm::Reference(reference.clone()),
+        };
+
+        self.send_control_message(control, None).await
     }
 
-    pub async fn receive_raw(&mut self) -> Result<Vec<u8>> {
-        if self.state() != ConnectionState::Connected {
+    /// Asks the remote node to spawn `module:function(args...)`, mirroring
+    /// `erlang:spawn_request/5`.
+    ///
+    /// The result arrives as a `SPAWN_REPLY` addressed to `from_pid` carrying `req_id`.
+    /// On the wire the argument list travels as the message payload.
+    ///
+    /// `group_leader` receives the I/O of the spawned process. It can be a pid on the
+    /// remote node or a local one, such as a Rust process acting as an I/O server.
+    /// Use [`Connection::set_group_leader`] to change it later.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn spawn_request(
+        &mut self,
+        req_id: &ExternalReference,
+        from_pid: &ExternalPid,
+        group_leader: &ExternalPid,
+        module: Atom,
+        function: Atom,
+        args: Vec<OwnedTerm>,
+        opts: Vec<OwnedTerm>,
+    ) -> Result<()> {
+        if !self.is_connected() {
             return Err(Error::InvalidState {
                 state: self.state(),
             });
         }
 
-        self.read_message().await
-    }
-
-    pub async fn close(&mut self) -> Result<()> {
-        self.transport.close();
-        self.handshake.disconnect();
+        let control_term = OwnedTerm::Tuple(vec![
+            OwnedTerm::Integer(ControlMessageType::SpawnRequest as i64),
+            OwnedTerm::Reference(req_id.clone()),
+            OwnedTerm::Pid(from_pid.clone()),
+            OwnedTerm::Pid(group_leader.clone()),
+            OwnedTerm::Tuple(vec![
+                OwnedTerm::Atom(module),
+                OwnedTerm::Atom(function),
+                OwnedTerm::Integer(args.len() as i64),
+            ]),
+            OwnedTerm::List(opts),
+        ]);
+        let frame = self.frame_control_term(
+            &control_term,
+            Some(&OwnedTerm::List(args)),
+            Some(ControlMessageType::SpawnRequest),
+            None,
+        )?;
+        self.write_frame(&frame).await?;
+
+        trace!("Sent spawn request: {:?}", control_term);
         Ok(())
     }
 
-    pub async fn send_message(
+    /// Makes `leader_pid` the group leader of `target_pid`, the equivalent of
+    /// `erlang:group_leader(LeaderPid, TargetPid)`.
+    pub async fn set_group_leader(
         &mut self,
-        _from_pid: ExternalPid,
-        to_pid: ExternalPid,
-        message: OwnedTerm,
+        target_pid: &ExternalPid,
+        leader_pid: &ExternalPid,
     ) -> Result<()> {
         if !self.is_connected() {
             return Err(Error::InvalidState {
@@ -324,19 +1690,19 @@ impl Connection {
             });
         }
 
-        let control = ControlMessage::Send {
-            cookie: OwnedTerm::Atom(Atom::new("")),
-            to_pid: OwnedTerm::Pid(to_pid),
+        let control = ControlMessage::GroupLeader {
+            from_pid: OwnedTerm::Pid(leader_pid.clone()),
+            to_pid: OwnedTerm::Pid(target_pid.clone()),
         };
 
-        self.send_control_message(control, Some(message)).await
+        self.send_control_message(control, None).await
     }
 
-    pub async fn send_to_name(
+    pub async fn demonitor(
         &mut self,
-        from_pid: ExternalPid,
-        to_name: Atom,
-        message: OwnedTerm,
+        from_pid: &ExternalPid,
+        to_proc: &ExternalPid,
+        reference: &ExternalReference,
     ) -> Result<()> {
         if !self.is_connected() {
             return Err(Error::InvalidState {
@@ -344,35 +1710,64 @@ impl Connection {
             });
         }
 
-        let control = ControlMessage::RegSend {
-            from_pid: OwnedTerm::Pid(from_pid),
-            cookie: OwnedTerm::Atom(Atom::new("")),
-            to_name: OwnedTerm::Atom(to_name),
+        let control = ControlMessage::DemonitorP {
+            from_pid: OwnedTerm::Pid(from_pid.clone()),
+            to_proc: OwnedTerm::Pid(to_proc.clone()),
+            reference: OwnedTerm::Reference(reference.clone()),
         };
 
-        self.send_control_message(control, Some(message)).await
+        self.send_control_message(control, None).await
     }
 
-    pub async fn link(&mut self, from_pid: &ExternalPid, to_pid: &ExternalPid) -> Result<()> {
+    /// Sends an exit signal, the equivalent of `erlang:exit(ToPid, Reason)` called by `from_pid`.
+    ///
+    /// Unlike the signal a linked process emits on termination (see [`Connection::link_exit`]),
+    /// this one does not require a link. `normal` is ignored by processes that do not trap exits,
+    /// and any other reason terminates them unless they trap exits, in which case it arrives
+    /// as an `{'EXIT', From, Reason}` message. Reasons larger than [`LARGE_EXIT_REASON_SIZE`]
+    /// are sent with a `PAYLOAD_EXIT2`.
+    pub async fn exit(
+        &mut self,
+        from_pid: &ExternalPid,
+        to_pid: &ExternalPid,
+        reason: OwnedTerm,
+    ) -> Result<()> {
         if !self.is_connected() {
             return Err(Error::InvalidState {
                 state: self.state(),
             });
         }
 
-        let control = ControlMessage::Link {
-            from_pid: OwnedTerm::Pid(from_pid.clone()),
-            to_pid: OwnedTerm::Pid(to_pid.clone()),
-        };
+        let from_pid = OwnedTerm::Pid(from_pid.clone());
+        let to_pid = OwnedTerm::Pid(to_pid.clone());
+        if reason.estimated_encoded_size() > LARGE_EXIT_REASON_SIZE {
+            let control = ControlMessage::PayloadExit2 { from_pid, to_pid };
+            return self.send_control_message(control, Some(reason)).await;
+        }
 
+        let control = ControlMessage::Exit2 {
+            from_pid,
+            to_pid,
+            reason,
+        };
         self.send_control_message(control, None).await
     }
 
-    pub async fn unlink(
+    /// Unconditionally terminates `to_pid`, the equivalent of `erlang:exit(ToPid, kill)`.
+    ///
+    /// `kill` cannot be trapped. The process terminates with the reason `killed`,
+    /// which is what its links and monitors observe.
+    pub async fn kill(&mut self, from_pid: &ExternalPid, to_pid: &ExternalPid) -> Result<()> {
+        self.exit(from_pid, to_pid, OwnedTerm::Atom(Atom::new("kill")))
+            .await
+    }
+
+    /// Sends the exit signal a process linked to `to_pid` emits when it terminates with `reason`.
+    pub async fn link_exit(
         &mut self,
         from_pid: &ExternalPid,
         to_pid: &ExternalPid,
-        unlink_id: u64,
+        reason: OwnedTerm,
     ) -> Result<()> {
         if !self.is_connected() {
             return Err(Error::InvalidState {
@@ -380,20 +1775,24 @@ impl Connection {
             });
         }
 
-        let control = ControlMessage::UnlinkId {
-            id: unlink_id,
+        let control = ControlMessage::Exit {
             from_pid: OwnedTerm::Pid(from_pid.clone()),
             to_pid: OwnedTerm::Pid(to_pid.clone()),
+            reason,
         };
 
         self.send_control_message(control, None).await
     }
 
-    pub async fn monitor(
+    /// Notifies `to_pid` that the process it monitors with `reference` terminated with `reason`.
+    ///
+    /// `from_proc` is the monitored pid, or the registered name the monitor was set up with.
+    pub async fn monitor_exit(
         &mut self,
-        from_pid: &ExternalPid,
-        to_proc: &ExternalPid,
+        from_proc: OwnedTerm,
+        to_pid: &ExternalPid,
         reference: &ExternalReference,
+        reason: OwnedTerm,
     ) -> Result<()> {
         if !self.is_connected() {
             return Err(Error::InvalidState {
@@ -401,20 +1800,22 @@ impl Connection {
             });
         }
 
-        let control = ControlMessage::MonitorP {
-            from_pid: OwnedTerm::Pid(from_pid.clone()),
-            to_proc: OwnedTerm::Pid(to_proc.clone()),
+        let control = ControlMessage::MonitorPExit {
+            from_proc,
+            to_pid: OwnedTerm::Pid(to_pid.clone()),
             reference: OwnedTerm::Reference(reference.clone()),
+            reason,
         };
 
         self.send_control_message(control, None).await
     }
 
-    pub async fn demonitor(
+    /// Acknowledges an `UNLINK_ID` received from `to_pid`.
+    pub async fn unlink_ack(
         &mut self,
+        unlink_id: u64,
         from_pid: &ExternalPid,
-        to_proc: &ExternalPid,
-        reference: &ExternalReference,
+        to_pid: &ExternalPid,
     ) -> Result<()> {
         if !self.is_connected() {
             return Err(Error::InvalidState {
@@ -422,54 +1823,82 @@ impl Connection {
             });
         }
 
-        let control = ControlMessage::DemonitorP {
+        let control = ControlMessage::UnlinkIdAck {
+            id: unlink_id,
             from_pid: OwnedTerm::Pid(from_pid.clone()),
-            to_proc: OwnedTerm::Pid(to_proc.clone()),
-            reference: OwnedTerm::Reference(reference.clone()),
+            to_pid: OwnedTerm::Pid(to_pid.clone()),
         };
 
         self.send_control_message(control, None).await
     }
 
-    #[doc(hidden)]
-    pub fn decode_complete_fragment(
-        complete_data: &[u8],
-        atom_cache: &mut AtomCache,
-    ) -> Result<(ControlMessage, Option<OwnedTerm>)> {
-        let (control_term, message) = if complete_data.len() >= 2
-            && complete_data[0] == VERSION_TAG
-            && complete_data[1] == DIST_HEADER
-        {
-            decoder::decode_with_atom_cache(complete_data, atom_cache)?
-        } else {
-            (decoder::decode(complete_data)?, None)
-        };
-
-        let control = ControlMessage::from_term(&control_term)?;
-        Ok((control, message))
+    pub async fn receive_message(&mut self) -> Result<(ControlMessage, Option<OwnedTerm>)> {
+        self.receive_envelope()
+            .await
+            .map(ReceivedMessage::into_parts)
     }
 
-    pub async fn receive_message(&mut self) -> Result<(ControlMessage, Option<OwnedTerm>)> {
+    /// Like [`Connection::receive_message`] but also returns where and when the message
+    /// was received and its encoded size.
+    ///
+    /// Errors carry the connection's id, see [`Error::connection_id`].
+    pub async fn receive_envelope(&mut self) -> Result<ReceivedMessage> {
         if !self.is_connected() {
             return Err(Error::InvalidState {
                 state: self.state(),
             });
         }
 
+        let id = self.id;
+        let span = self.span.clone();
+        self.receive_accepted()
+            .instrument(span)
+            .await
+            .map_err(|e| e.with_connection_id(id))
+    }
+
+    /// Receives the next message the link protocol does not discard, acknowledging `UNLINK_ID`s.
+    async fn receive_accepted(&mut self) -> Result<ReceivedMessage> {
+        loop {
+            let received = self.receive_next().await?;
+            if !self.unlinks.accept(&received.control) {
+                trace!(
+                    "Ignoring a link exit signal received while unlinking: {:?}",
+                    received.control
+                );
+                continue;
+            }
+            if self.config.unlink_acks
+                && let ControlMessage::UnlinkId {
+                    id,
+                    from_pid: OwnedTerm::Pid(from),
+                    to_pid: OwnedTerm::Pid(to),
+                } = &received.control
+            {
+                self.unlink_ack(*id, to, from).await?;
+            }
+            return Ok(received);
+        }
+    }
+
+    async fn receive_next(&mut self) -> Result<ReceivedMessage> {
+        self.decode_accounting.wait_for_capacity().await;
         loop {
             let data = self.read_message().await?;
 
             if data.is_empty() {
                 trace!("Received tick (heartbeat), continuing...");
+                self.metrics.record_tick_received();
                 continue;
             }
+            self.metrics.record_received();
 
             trace!(
                 "Decoding message (first 20 bytes): {:02x?}",
                 &data[..data.len().min(20)]
             );
 
-            if data.len() >= 2 && data[0] == VERSION_TAG && data[1] == DIST_FRAG_HEADER {
+            if data.len() >= 2 && data[0] == VERSION && data[1] == DIST_FRAG_HEADER {
                 trace!("DIST_FRAG_HEADER detected");
                 let (header, remaining) = decoder::decode_fragment_header(&data)?;
                 trace!(
@@ -496,11 +1925,11 @@ impl Connection {
                     remaining[payload_start..].to_vec(),
                 ) {
                     trace!("Fragment sequence complete, processing");
-                    return Self::decode_complete_fragment(&complete_data, &mut self.atom_cache);
+                    return self.decode_envelope(complete_data).await;
                 } else {
                     continue;
                 }
-            } else if data.len() >= 2 && data[0] == VERSION_TAG && data[1] == DIST_FRAG_CONT {
+            } else if data.len() >= 2 && data[0] == VERSION && data[1] == DIST_FRAG_CONT {
                 trace!("DIST_FRAG_CONT detected");
                 let ((sequence_id, fragment_id), remaining) = decoder::decode_fragment_cont(&data)?;
                 trace!(
@@ -514,41 +1943,108 @@ impl Connection {
                     remaining.to_vec(),
                 ) {
                     trace!("Fragment sequence complete, processing");
-                    return Self::decode_complete_fragment(&complete_data, &mut self.atom_cache);
+                    return self.decode_envelope(complete_data).await;
                 } else {
                     continue;
                 }
             }
 
-            let (control_term, message) = if !data.is_empty() && data[0] == PASS_THROUGH {
-                trace!("Pass-through message detected");
-                let (control, remaining) = decoder::decode_with_trailing(&data[1..])?;
-                trace!(
-                    "Decoded control term from pass-through message, {} bytes remaining",
-                    remaining.len()
-                );
-                let message = if !remaining.is_empty() {
-                    let (msg, _) = decoder::decode_with_trailing(remaining)?;
-                    trace!("Decoded message term from pass-through message");
-                    Some(msg)
-                } else {
-                    None
-                };
-                (control, message)
-            } else if data.len() >= 2 && data[0] == VERSION_TAG && data[1] == DIST_HEADER {
-                let (control, payload_opt) =
-                    decoder::decode_with_atom_cache(&data, &mut self.atom_cache)?;
-                (control, payload_opt)
-            } else {
-                (decoder::decode(&data)?, None)
-            };
+            return self.decode_envelope(data).await;
+        }
+    }
+
+    /// Sends a message to a remote process, mirroring `erlang:send/3`.
+    ///
+    /// Like `erlang:send/3`, a [`ConnectionState::Disconnected`] connection is connected
+    /// first, and a connection in any other state that is not connected fails with
+    /// [`Error::InvalidState`]. With `noconnect`, a disconnected connection returns
+    /// [`SendOutcome::NoConnect`] instead of connecting. With `nosuspend`, a full outbound
+    /// socket buffer returns [`SendOutcome::NoSuspend`] instead of waiting for it to drain.
+    pub async fn send_opts(
+        &mut self,
+        to: ExternalPid,
+        message: OwnedTerm,
+        opts: SendOpts,
+    ) -> Result<SendOutcome> {
+        if !self.is_connected() {
+            if opts.noconnect {
+                return Ok(SendOutcome::NoConnect);
+            }
+            if self.state() != ConnectionState::Disconnected {
+                return Err(Error::InvalidState {
+                    state: self.state(),
+                });
+            }
+            self.connect().await?;
+        }
+        if !self.check_stale_pid(&to)? {
+            return Ok(SendOutcome::Ok);
+        }
 
-            let control = ControlMessage::from_term(&control_term)?;
+        let control = ControlMessage::Send {
+            cookie: OwnedTerm::Atom(Atom::new("")),
+            to_pid: OwnedTerm::Pid(to),
+        };
+        let frame = self.frame_control_message(&control, Some(&message))?;
+
+        if opts.nosuspend {
+            if !self.try_write_frame(&frame).await? {
+                // The frame may have announced atoms the peer will now never see
+                self.codec.discard_unsent();
+                trace!("Outbound buffer is full, not sending: {:?}", control);
+                return Ok(SendOutcome::NoSuspend);
+            }
+        } else {
+            self.write_frame(&frame).await?;
+        }
 
-            trace!("Received control message: {:?}", control);
+        trace!("Sent control message: {:?}", control);
+        Ok(SendOutcome::Ok)
+    }
 
-            return Ok((control, message));
+    /// Sends `message` to `to` as part of the logical stream named by `key`, returning
+    /// the message's sequence number within that stream.
+    ///
+    /// Messages with the same key are delivered in the order they were sent, like
+    /// messages between a pair of Erlang processes. Messages with different keys
+    /// may be interleaved with each other, so a sender that spreads traffic over
+    /// several outbound lanes only has to keep each key on one lane.
+    ///
+    /// Sequence numbers start at 1 for every key and are only assigned to messages
+    /// that were written or dropped by [`StalePidPolicy::Drop`], so a gap means
+    /// a message was dropped. They are not sent to the peer.
+    pub async fn send_ordered(
+        &mut self,
+        key: impl Into<ChannelKey>,
+        to: ExternalPid,
+        message: OwnedTerm,
+    ) -> Result<SequenceId> {
+        if !self.is_connected() {
+            return Err(Error::InvalidState {
+                state: self.state(),
+            });
         }
+        let key = key.into();
+        let sequence = self
+            .ordered_sequence(&key)
+            .map_or(SequenceId::FIRST, |last| last.next());
+
+        if self.check_stale_pid(&to)? {
+            let control = ControlMessage::Send {
+                cookie: OwnedTerm::Atom(Atom::new("")),
+                to_pid: OwnedTerm::Pid(to),
+            };
+            self.send_control_message(control, Some(message)).await?;
+        }
+
+        trace!("Sent message {} on channel {}", sequence.value(), key);
+        self.ordered_sequences.insert(key, sequence);
+        Ok(sequence)
+    }
+
+    /// Returns the sequence number [`Connection::send_ordered`] last assigned to `key`.
+    pub fn ordered_sequence(&self, key: &ChannelKey) -> Option<SequenceId> {
+        self.ordered_sequences.get(key).copied()
     }
 
     async fn send_control_message(
@@ -556,186 +2052,228 @@ impl Connection {
         control: ControlMessage,
         message: Option<OwnedTerm>,
     ) -> Result<()> {
-        let control_term = control.to_term();
-
-        let mut buf = BytesMut::new();
-
-        let use_pass_through = self
-            .negotiated_flags()
-            .as_ref()
-            .map(|f| !f.has(DistributionFlags::DIST_HDR_ATOM_CACHE))
-            .unwrap_or(true);
+        let frame = self.frame_control_message(&control, message.as_ref())?;
+        self.write_frame(&frame).await?;
 
-        if use_pass_through {
-            let control_encoded = erltf::encode(&control_term)?;
-
-            if let Some(msg) = message {
-                let msg_encoded = erltf::encode(&msg)?;
-                let total_len = 1 + control_encoded.len() + msg_encoded.len();
-                trace!(
-                    "Sending pass-through message: control_len={}, msg_len={}, total_len={}",
-                    control_encoded.len(),
-                    msg_encoded.len(),
-                    total_len
-                );
-
-                let stream = self
-                    .transport
-                    .write_half_mut()
-                    .ok_or_else(|| Error::InvalidStateMessage("no active stream".to_string()))?;
-
-                stream.write_u32(total_len as u32).await?;
-                stream.write_u8(PASS_THROUGH).await?;
-                stream.write_all(&control_encoded).await?;
-                stream.write_all(&msg_encoded).await?;
-                stream.flush().await?;
-            } else {
-                let total_len = 1 + control_encoded.len();
-                trace!(
-                    "Sending pass-through control: control_len={}, total_len={}",
-                    control_encoded.len(),
-                    total_len
-                );
+        trace!("Sent control message: {:?}", control);
 
-                let stream = self
-                    .transport
-                    .write_half_mut()
-                    .ok_or_else(|| Error::InvalidStateMessage("no active stream".to_string()))?;
+        Ok(())
+    }
 
-                stream.write_u32(total_len as u32).await?;
-                stream.write_u8(PASS_THROUGH).await?;
-                stream.write_all(&control_encoded).await?;
-                stream.flush().await?;
-            }
+    fn frame_control_message(
+        &mut self,
+        control: &ControlMessage,
+        message: Option<&OwnedTerm>,
+    ) -> Result<BytesMut> {
+        control.validate()?;
+        self.frame_control_term(
+            &control.to_term(),
+            message,
+            control.message_type(),
+            control.registered_name(),
+        )
+    }
 
-            trace!("Sent control message: {:?}", control);
-            return Ok(());
+    /// Encodes and compresses a frame, recording its size under `message_type` and `target`.
+    fn frame_control_term(
+        &mut self,
+        control_term: &OwnedTerm,
+        message: Option<&OwnedTerm>,
+        message_type: Option<ControlMessageType>,
+        target: Option<&Atom>,
+    ) -> Result<BytesMut> {
+        let span = self.span.clone();
+        let _entered = span.enter();
+        let frame = self.encode_control_frame(control_term, message)?;
+        self.metrics.record_message(
+            MessageDirection::Outbound,
+            message_type,
+            target,
+            frame.len() - 4,
+        );
+        #[cfg(feature = "zstd")]
+        if let Some(compression) = &self.config.zstd_compression {
+            return Ok(compression.compress_frame(frame)?);
         }
+        Ok(frame)
+    }
 
-        if let Some(msg) = message {
-            let encoded = erltf::encode_with_dist_header_multi(&[&control_term, &msg])?;
-            buf.put_u32(encoded.len() as u32);
-            buf.put_slice(&encoded);
-
-            trace!("Sending DIST_HEADER message: total_len={}", encoded.len());
-            trace!(
-                "Encoded bytes (hex, first 100): {:02x?}",
-                &encoded[..encoded.len().min(100)]
-            );
-        } else {
-            let encoded = erltf::encode_with_dist_header(&control_term)?;
-            buf.put_u32(encoded.len() as u32);
-            buf.put_slice(&encoded);
+    fn encode_control_frame(
+        &mut self,
+        control_term: &OwnedTerm,
+        message: Option<&OwnedTerm>,
+    ) -> Result<BytesMut> {
+        let context = EncodeContext {
+            flags: self.negotiated_flags(),
+            config: self.config.encode_config.with_mode(self.encode_mode()),
+        };
+        let body = self.codec.encode(control_term, message, &context)?;
+        trace!(
+            "Encoded frame (hex, first 100): {:02x?}",
+            &body[..body.len().min(100)]
+        );
 
-            trace!("Sending DIST_HEADER control: total_len={}", encoded.len());
-        }
+        let mut buf = BytesMut::with_capacity(4 + body.len());
+        buf.put_u32(body.len() as u32);
+        buf.put_slice(&body);
+        Ok(buf)
+    }
 
+    async fn write_frame(&mut self, frame: &[u8]) -> Result<()> {
+        self.last_sent = Instant::now();
         let stream = self
             .transport
             .write_half_mut()
             .ok_or_else(|| Error::InvalidStateMessage("no active stream".to_string()))?;
 
-        tokio::time::timeout(self.config.timeout, stream.write_all(&buf))
+        time::timeout(self.config.timeout, stream.write_all(frame))
             .await
             .map_err(|_| Error::Timeout(self.config.timeout))??;
 
-        tokio::time::timeout(self.config.timeout, stream.flush())
+        time::timeout(self.config.timeout, stream.flush())
             .await
             .map_err(|_| Error::Timeout(self.config.timeout))??;
 
-        trace!("Sent control message: {:?}", control);
-
         Ok(())
     }
 
-    pub fn take_read_half(&mut self) -> Option<OwnedReadHalf> {
-        self.transport.take_read_half()
-    }
-
-    #[must_use]
-    pub fn timeout(&self) -> Duration {
-        self.config.timeout
-    }
+    /// Returns `false` without writing anything if the socket cannot accept
+    /// more data right now. A partially accepted frame is always completed,
+    /// since abandoning it would corrupt the stream.
+    async fn try_write_frame(&mut self, frame: &[u8]) -> Result<bool> {
+        let stream = self
+            .transport
+            .write_half_mut()
+            .ok_or_else(|| Error::InvalidStateMessage("no active stream".to_string()))?;
 
-    pub async fn receive_message_from_read_half(
-        read_half: &mut OwnedReadHalf,
-        timeout: Duration,
-    ) -> Result<(ControlMessage, Option<OwnedTerm>)> {
-        loop {
-            let len = {
-                trace!("Attempting to read message length (4 bytes, distribution protocol)...");
-                let mut len_bytes = [0u8; 4];
-                tokio::time::timeout(timeout, read_half.read_exact(&mut len_bytes))
-                    .await
-                    .map_err(|_| Error::Timeout(timeout))??;
-                let len = u32::from_be_bytes(len_bytes);
-                trace!(
-                    "Read message length: {} bytes (raw bytes: {:02x?})",
-                    len, len_bytes
-                );
-                len as usize
-            };
+        let written = match stream.try_write(frame) {
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(false),
+            Err(e) => return Err(Error::Io(e)),
+        };
+        self.last_sent = Instant::now();
 
-            if len == 0 {
-                trace!("Received tick (heartbeat), continuing...");
-                continue;
-            }
+        if written < frame.len() {
+            self.write_frame(&frame[written..]).await?;
+        }
 
-            if len > MAX_MESSAGE_SIZE {
-                return Err(Error::MessageTooLarge {
-                    size: len,
-                    max: MAX_MESSAGE_SIZE,
-                });
-            }
+        Ok(true)
+    }
 
-            let mut buf = vec![0u8; len];
-            trace!("Reading {} bytes of message data...", len);
-            tokio::time::timeout(timeout, read_half.read_exact(&mut buf))
+    /// Decodes a complete message and wraps it in a [`ReceivedMessage`].
+    async fn decode_envelope(&mut self, data: Vec<u8>) -> Result<ReceivedMessage> {
+        let byte_size = data.len();
+        let data = Bytes::from(data);
+        let decoded =
+            self.decode_frame(&data)
                 .await
-                .map_err(|_| Error::Timeout(timeout))??;
-
-            trace!("Read message data (hex): {:02x?}", buf);
+                .and_then(|(control_term, payload, raw_payload)| {
+                    let control = self.accept_control(ControlMessage::from_term(&control_term)?)?;
+                    let heap_size = payload.as_ref().map_or(0, OwnedTerm::heap_size);
+                    self.decode_accounting.charge(heap_size)?;
+                    Ok((control, payload, raw_payload, heap_size))
+                });
+        let (control, payload, raw_payload, heap_size) =
+            decoded.map_err(|e| self.config.decode_error_policy.apply(e, data))?;
+        trace!("Received control message: {:?}", control);
+        self.metrics
+            .record_control_message(MessageDirection::Inbound, &control, byte_size);
+
+        Ok(ReceivedMessage {
+            control,
+            payload,
+            raw_payload,
+            from_node: Atom::new(&self.config.remote_node_name),
+            received_at: SystemTime::now(),
+            byte_size,
+            heap_size,
+        })
+    }
 
-            if buf.is_empty() {
-                return Err(Error::InvalidStateMessage(
-                    "Empty message received".to_string(),
-                ));
+    /// Decodes a complete message with the codec, failing before it is decoded
+    /// if the codec estimates it exceeds [`DecodeBudget::max_message_size`].
+    async fn decode_frame(&mut self, data: &Bytes) -> Result<DecodedFrame> {
+        if self.decode_accounting.budget().max_message_size.is_some()
+            && let Some(estimated_size) = self.codec.estimate_heap_size(data)?
+        {
+            self.decode_accounting.check(estimated_size)?;
+        }
+        match self.config.decode_offload {
+            Some(offload) if offload.applies_to(data.len()) => {
+                self.decode_frame_offloaded(data.clone()).await
             }
+            _ => self
+                .codec
+                .decode(data, &self.config.decode_config, self.config.raw_payloads),
+        }
+    }
 
-            let pass_through_marker = buf[0];
-            trace!("Pass-through marker: {}", pass_through_marker);
+    /// Decodes a message on the blocking thread pool with a clone of the codec. The clone
+    /// replaces the codec once it returns, so a decode that panics or a receive that is
+    /// cancelled leaves the codec as it was.
+    async fn decode_frame_offloaded(&mut self, data: Bytes) -> Result<DecodedFrame> {
+        trace!(
+            "Decoding a {}-byte message on the blocking pool",
+            data.len()
+        );
+        let mut codec = self.codec.clone();
+        let decode_config = self.config.decode_config.clone();
+        let raw_payloads = self.config.raw_payloads;
+        let (decoded, codec) = spawn_blocking(move || {
+            let decoded = codec.decode(&data, &decode_config, raw_payloads);
+            (decoded, codec)
+        })
+        .await
+        .map_err(io::Error::from)?;
+        self.codec = codec;
+        decoded
+    }
 
-            if pass_through_marker != PASS_THROUGH {
-                return Err(Error::Protocol(format!(
-                    "Expected pass-through marker {}, got {}",
-                    PASS_THROUGH, pass_through_marker
-                )));
-            }
+    /// Rejects [`ControlMessage::Generic`] unless the connection is permissive.
+    fn accept_control(&self, control: ControlMessage) -> Result<ControlMessage> {
+        if self.config.permissive_control_messages {
+            Ok(control)
+        } else {
+            control.deny_generic()
+        }
+    }
 
-            let control_and_payload = &buf[1..];
-            trace!(
-                "Decoding control and payload from {} bytes",
-                control_and_payload.len()
-            );
+    /// The options that make reading from the read half behave like [`Connection::receive_message`]
+    #[must_use]
+    pub fn receive_options(&self) -> ReceiveOptions {
+        let mut options = ReceiveOptions::new()
+            .with_metrics(self.metrics.clone())
+            .with_permissive_control_messages(self.config.permissive_control_messages)
+            .with_connection_id(self.id)
+            .with_span(self.span.clone())
+            .with_unlinks(self.unlinks.clone())
+            .with_unlink_acks(self.config.unlink_acks)
+            .with_decode_error_policy(self.config.decode_error_policy)
+            .with_remote_node(Atom::new(&self.config.remote_node_name));
+        options.decode_offload = self.config.decode_offload;
+        options.decode_accounting = Some(self.decode_accounting.clone());
+        options = options.with_decode_config(self.config.decode_config.clone());
+        if let Some(atom_table) = self.codec.atom_table() {
+            options = options.with_atom_table(atom_table.clone());
+        }
+        #[cfg(feature = "zstd")]
+        {
+            options.zstd_compression = self.config.zstd_compression;
+        }
+        options
+    }
 
-            let (control_term, remaining) = decoder::decode_with_trailing(control_and_payload)?;
-            trace!("Decoded control term: {:?}", control_term);
-            trace!("Remaining bytes after control: {}", remaining.len());
-
-            let control_msg = ControlMessage::from_term(&control_term)?;
-            trace!("Parsed control message: {:?}", control_msg);
-
-            let payload = if !remaining.is_empty() {
-                trace!("Decoding payload from {} bytes", remaining.len());
-                let (payload_term, _) = decoder::decode_with_trailing(remaining)?;
-                trace!("Decoded payload: {:?}", payload_term);
-                Some(payload_term)
-            } else {
-                trace!("No payload present");
-                None
-            };
+    pub fn take_read_half(&mut self) -> Option<OwnedReadHalf> {
+        self.transport.take_read_half()
+    }
 
-            return Ok((control_msg, payload));
-        }
+    #[must_use]
+    pub fn timeout(&self) -> Duration {
+        self.config.timeout
+    }
+
+    #[must_use]
+    pub fn decode_config(&self) -> &DecodeConfig {
+        &self.config.decode_config
     }
 }
@@ -19,11 +19,14 @@
 
 use crate::errors::{Error, Result};
 use erltf::OwnedTerm;
+use erltf::types::Atom;
 use std::convert::TryFrom;
 use std::mem;
+use std::result::Result as StdResult;
+use thiserror::Error;
 
 /// Control message types (first element of control tuple)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum ControlMessageType {
     Link = 1,
@@ -61,7 +64,7 @@ pub enum ControlMessageType {
 impl TryFrom<u8> for ControlMessageType {
     type Error = u8;
 
-    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+    fn try_from(value: u8) -> StdResult<Self, Self::Error> {
         match value {
             1 => Ok(Self::Link),
             2 => Ok(Self::Send),
@@ -106,6 +109,52 @@ impl ControlMessageType {
     pub fn as_u8(self) -> u8 {
         self as u8
     }
+
+    /// The name used for this message type in the protocol documentation, e.g. `REG_SEND`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Link => "LINK",
+            Self::Send => "SEND",
+            Self::Exit => "EXIT",
+            Self::Unlink => "UNLINK",
+            Self::NodeLink => "NODE_LINK",
+            Self::RegSend => "REG_SEND",
+            Self::GroupLeader => "GROUP_LEADER",
+            Self::Exit2 => "EXIT2",
+            Self::SendTt => "SEND_TT",
+            Self::ExitTt => "EXIT_TT",
+            Self::RegSendTt => "REG_SEND_TT",
+            Self::Exit2Tt => "EXIT2_TT",
+            Self::MonitorP => "MONITOR_P",
+            Self::DemonitorP => "DEMONITOR_P",
+            Self::MonitorPExit => "MONITOR_P_EXIT",
+            Self::SendSender => "SEND_SENDER",
+            Self::SendSenderTt => "SEND_SENDER_TT",
+            Self::PayloadExit => "PAYLOAD_EXIT",
+            Self::PayloadExitTt => "PAYLOAD_EXIT_TT",
+            Self::PayloadExit2 => "PAYLOAD_EXIT2",
+            Self::PayloadExit2Tt => "PAYLOAD_EXIT2_TT",
+            Self::PayloadMonitorPExit => "PAYLOAD_MONITOR_P_EXIT",
+            Self::SpawnRequest => "SPAWN_REQUEST",
+            Self::SpawnRequestTt => "SPAWN_REQUEST_TT",
+            Self::SpawnReply => "SPAWN_REPLY",
+            Self::SpawnReplyTt => "SPAWN_REPLY_TT",
+            Self::UnlinkId => "UNLINK_ID",
+            Self::UnlinkIdAck => "UNLINK_ID_ACK",
+            Self::AliasSend => "ALIAS_SEND",
+            Self::AliasSendTt => "ALIAS_SEND_TT",
+        }
+    }
+}
+
+/// A control message field that the peer would reject by closing the connection,
+/// see [`ControlMessage::validate`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{} field {field} must be {expected}", .message_type.name())]
+pub struct ValidationError {
+    pub message_type: ControlMessageType,
+    pub field: &'static str,
+    pub expected: &'static str,
 }
 
 /// Control message representation
@@ -323,7 +372,8 @@ pub enum ControlMessage {
         trace_token: OwnedTerm,
     },
 
-    /// Generic control message (for unsupported types)
+    /// Generic control message (for unsupported types and arities), only produced by
+    /// [`ControlMessage::from_term`], [`ControlMessage::from_term_strict`] rejects these
     Generic {
         message_type: u8,
         fields: Vec<OwnedTerm>,
@@ -616,6 +666,33 @@ impl ControlMessage {
         }
     }
 
+    /// Like [`ControlMessage::from_term`] but fails on unknown message types and on known
+    /// ones with an unexpected number of elements instead of returning [`ControlMessage::Generic`].
+    pub fn from_term_strict(term: &OwnedTerm) -> Result<Self> {
+        Self::from_term(term)?.deny_generic()
+    }
+
+    /// Turns a [`ControlMessage::Generic`] into the error [`ControlMessage::from_term_strict`] returns.
+    pub(crate) fn deny_generic(self) -> Result<Self> {
+        let ControlMessage::Generic {
+            message_type,
+            fields,
+        } = &self
+        else {
+            return Ok(self);
+        };
+        let (message_type, arity) = (*message_type, fields.len() + 1);
+        let term = self.into_term();
+        Err(match ControlMessageType::from_u8(message_type) {
+            Some(known) => Error::ControlMessageArityMismatch {
+                message_type: known,
+                arity,
+                term,
+            },
+            None => Error::UnknownControlMessage { message_type, term },
+        })
+    }
+
     /// Convert this control message to an Erlang term (tuple)
     pub fn to_term(&self) -> OwnedTerm {
         match self {
@@ -1266,6 +1343,194 @@ impl ControlMessage {
         }
     }
 
+    /// The type of this control message, `None` for a [`ControlMessage::Generic`] with an unknown type.
+    pub fn message_type(&self) -> Option<ControlMessageType> {
+        let message_type = match self {
+            ControlMessage::Link { .. } => ControlMessageType::Link,
+            ControlMessage::Send { .. } => ControlMessageType::Send,
+            ControlMessage::Exit { .. } => ControlMessageType::Exit,
+            ControlMessage::UnlinkId { .. } => ControlMessageType::UnlinkId,
+            ControlMessage::UnlinkIdAck { .. } => ControlMessageType::UnlinkIdAck,
+            ControlMessage::RegSend { .. } => ControlMessageType::RegSend,
+            ControlMessage::MonitorP { .. } => ControlMessageType::MonitorP,
+            ControlMessage::DemonitorP { .. } => ControlMessageType::DemonitorP,
+            ControlMessage::MonitorPExit { .. } => ControlMessageType::MonitorPExit,
+            ControlMessage::SpawnRequest { .. } => ControlMessageType::SpawnRequest,
+            ControlMessage::SpawnReply { .. } => ControlMessageType::SpawnReply,
+            ControlMessage::AliasSend { .. } => ControlMessageType::AliasSend,
+            ControlMessage::Unlink { .. } => ControlMessageType::Unlink,
+            ControlMessage::NodeLink => ControlMessageType::NodeLink,
+            ControlMessage::GroupLeader { .. } => ControlMessageType::GroupLeader,
+            ControlMessage::Exit2 { .. } => ControlMessageType::Exit2,
+            ControlMessage::SendSender { .. } => ControlMessageType::SendSender,
+            ControlMessage::PayloadExit { .. } => ControlMessageType::PayloadExit,
+            ControlMessage::PayloadExit2 { .. } => ControlMessageType::PayloadExit2,
+            ControlMessage::PayloadMonitorPExit { .. } => ControlMessageType::PayloadMonitorPExit,
+            ControlMessage::SendTt { .. } => ControlMessageType::SendTt,
+            ControlMessage::ExitTt { .. } => ControlMessageType::ExitTt,
+            ControlMessage::RegSendTt { .. } => ControlMessageType::RegSendTt,
+            ControlMessage::Exit2Tt { .. } => ControlMessageType::Exit2Tt,
+            ControlMessage::SendSenderTt { .. } => ControlMessageType::SendSenderTt,
+            ControlMessage::PayloadExitTt { .. } => ControlMessageType::PayloadExitTt,
+            ControlMessage::PayloadExit2Tt { .. } => ControlMessageType::PayloadExit2Tt,
+            ControlMessage::SpawnRequestTt { .. } => ControlMessageType::SpawnRequestTt,
+            ControlMessage::SpawnReplyTt { .. } => ControlMessageType::SpawnReplyTt,
+            ControlMessage::AliasSendTt { .. } => ControlMessageType::AliasSendTt,
+            ControlMessage::Generic { message_type, .. } => {
+                return ControlMessageType::from_u8(*message_type);
+            }
+        };
+        Some(message_type)
+    }
+
+    /// The registered name a `REG_SEND` or `REG_SEND_TT` is addressed to.
+    pub fn registered_name(&self) -> Option<&Atom> {
+        match self {
+            ControlMessage::RegSend { to_name, .. } | ControlMessage::RegSendTt { to_name, .. } => {
+                to_name.as_atom()
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks the fields that the peer relies on, e.g. that a `SEND` targets a pid and
+    /// a `REG_SEND` a name atom. The peer closes the connection on a malformed control
+    /// message instead of reporting an error, so [`crate::Connection`] validates every
+    /// control message before sending it.
+    ///
+    /// [`ControlMessage::Generic`] messages are not checked.
+    pub fn validate(&self) -> StdResult<(), ValidationError> {
+        let Some(message_type) = self.message_type() else {
+            return Ok(());
+        };
+        let check = FieldCheck(message_type);
+        match self {
+            ControlMessage::Link { from_pid, to_pid }
+            | ControlMessage::Unlink { from_pid, to_pid }
+            | ControlMessage::GroupLeader { from_pid, to_pid }
+            | ControlMessage::SendSender { from_pid, to_pid }
+            | ControlMessage::PayloadExit { from_pid, to_pid }
+            | ControlMessage::PayloadExit2 { from_pid, to_pid }
+            | ControlMessage::Exit {
+                from_pid, to_pid, ..
+            }
+            | ControlMessage::Exit2 {
+                from_pid, to_pid, ..
+            }
+            | ControlMessage::ExitTt {
+                from_pid, to_pid, ..
+            }
+            | ControlMessage::Exit2Tt {
+                from_pid, to_pid, ..
+            }
+            | ControlMessage::SendSenderTt {
+                from_pid, to_pid, ..
+            }
+            | ControlMessage::PayloadExitTt {
+                from_pid, to_pid, ..
+            }
+            | ControlMessage::PayloadExit2Tt {
+                from_pid, to_pid, ..
+            } => {
+                check.pid("from_pid", from_pid)?;
+                check.pid("to_pid", to_pid)
+            }
+            ControlMessage::Send { to_pid, .. } | ControlMessage::SendTt { to_pid, .. } => {
+                check.pid("to_pid", to_pid)
+            }
+            ControlMessage::UnlinkId {
+                id,
+                from_pid,
+                to_pid,
+            }
+            | ControlMessage::UnlinkIdAck {
+                id,
+                from_pid,
+                to_pid,
+            } => {
+                if *id == 0 {
+                    return Err(check.error("id", "non-zero"));
+                }
+                check.pid("from_pid", from_pid)?;
+                check.pid("to_pid", to_pid)
+            }
+            ControlMessage::RegSend {
+                from_pid, to_name, ..
+            }
+            | ControlMessage::RegSendTt {
+                from_pid, to_name, ..
+            } => {
+                check.pid("from_pid", from_pid)?;
+                check.atom("to_name", to_name)
+            }
+            ControlMessage::MonitorP {
+                from_pid,
+                to_proc,
+                reference,
+            }
+            | ControlMessage::DemonitorP {
+                from_pid,
+                to_proc,
+                reference,
+            } => {
+                check.pid("from_pid", from_pid)?;
+                check.pid_or_atom("to_proc", to_proc)?;
+                check.reference("reference", reference)
+            }
+            ControlMessage::MonitorPExit {
+                from_proc,
+                to_pid,
+                reference,
+                ..
+            }
+            | ControlMessage::PayloadMonitorPExit {
+                from_proc,
+                to_pid,
+                reference,
+            } => {
+                check.pid_or_atom("from_proc", from_proc)?;
+                check.pid("to_pid", to_pid)?;
+                check.reference("reference", reference)
+            }
+            ControlMessage::SpawnRequest {
+                req_id,
+                from,
+                group_leader,
+                mfa,
+                arg_list,
+                opt_list,
+            }
+            | ControlMessage::SpawnRequestTt {
+                req_id,
+                from,
+                group_leader,
+                mfa,
+                arg_list,
+                opt_list,
+                ..
+            } => {
+                check.reference("req_id", req_id)?;
+                check.pid("from", from)?;
+                check.pid("group_leader", group_leader)?;
+                check.mfa(mfa, arg_list)?;
+                check.proper_list("opt_list", opt_list)
+            }
+            ControlMessage::SpawnReply { req_id, to, .. }
+            | ControlMessage::SpawnReplyTt { req_id, to, .. } => {
+                check.reference("req_id", req_id)?;
+                check.pid("to", to)
+            }
+            ControlMessage::AliasSend { from_pid, alias }
+            | ControlMessage::AliasSendTt {
+                from_pid, alias, ..
+            } => {
+                check.pid("from_pid", from_pid)?;
+                check.reference("alias", alias)
+            }
+            ControlMessage::NodeLink | ControlMessage::Generic { .. } => Ok(()),
+        }
+    }
+
     pub fn link(from_pid: OwnedTerm, to_pid: OwnedTerm) -> Self {
         ControlMessage::Link { from_pid, to_pid }
     }
@@ -1360,3 +1625,80 @@ impl ControlMessage {
         }
     }
 }
+
+struct FieldCheck(ControlMessageType);
+
+impl FieldCheck {
+    fn error(&self, field: &'static str, expected: &'static str) -> ValidationError {
+        ValidationError {
+            message_type: self.0,
+            field,
+            expected,
+        }
+    }
+
+    fn expect(
+        &self,
+        valid: bool,
+        field: &'static str,
+        expected: &'static str,
+    ) -> StdResult<(), ValidationError> {
+        if valid {
+            Ok(())
+        } else {
+            Err(self.error(field, expected))
+        }
+    }
+
+    fn pid(&self, field: &'static str, term: &OwnedTerm) -> StdResult<(), ValidationError> {
+        self.expect(matches!(term, OwnedTerm::Pid(_)), field, "a pid")
+    }
+
+    fn atom(&self, field: &'static str, term: &OwnedTerm) -> StdResult<(), ValidationError> {
+        self.expect(matches!(term, OwnedTerm::Atom(_)), field, "an atom")
+    }
+
+    fn pid_or_atom(&self, field: &'static str, term: &OwnedTerm) -> StdResult<(), ValidationError> {
+        self.expect(
+            matches!(term, OwnedTerm::Pid(_) | OwnedTerm::Atom(_)),
+            field,
+            "a pid or an atom",
+        )
+    }
+
+    fn reference(&self, field: &'static str, term: &OwnedTerm) -> StdResult<(), ValidationError> {
+        self.expect(
+            matches!(term, OwnedTerm::Reference(_)),
+            field,
+            "a reference",
+        )
+    }
+
+    fn proper_list(&self, field: &'static str, term: &OwnedTerm) -> StdResult<(), ValidationError> {
+        self.expect(
+            matches!(term, OwnedTerm::List(_) | OwnedTerm::Nil),
+            field,
+            "a proper list",
+        )
+    }
+
+    /// `{Module, Function, Arity}` with an arity that matches the argument list
+    fn mfa(&self, mfa: &OwnedTerm, arg_list: &OwnedTerm) -> StdResult<(), ValidationError> {
+        let arity = match mfa.as_tuple() {
+            Some(
+                [
+                    OwnedTerm::Atom(_),
+                    OwnedTerm::Atom(_),
+                    OwnedTerm::Integer(arity),
+                ],
+            ) if (0..=255).contains(arity) => *arity as usize,
+            _ => return Err(self.error("mfa", "a {Module, Function, Arity} tuple")),
+        };
+        let args = match arg_list {
+            OwnedTerm::List(args) => args.len(),
+            OwnedTerm::Nil => 0,
+            _ => return Err(self.error("arg_list", "a proper list")),
+        };
+        self.expect(args == arity, "arg_list", "as long as the arity")
+    }
+}
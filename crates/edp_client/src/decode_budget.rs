@@ -0,0 +1,266 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Accounting of the memory that terms decoded from a peer take.
+//!
+//! A peer that sends giant terms makes the process hold on to a lot of memory and
+//! keeps decoding threads busy. [`DecodeBudget`] caps how much one connection may decode,
+//! by pausing reads from it, which applies TCP backpressure to that peer only.
+//! Sizes are approximations, see [`OwnedTerm::heap_size`](erltf::OwnedTerm::heap_size).
+
+use crate::errors::{Error, Result};
+use std::pin::pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::{Instant, sleep};
+use tracing::trace;
+
+/// The window [`DecodeBudget::bytes_per_sec`] is measured over
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Limits on the heap size of message payloads decoded from one peer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeBudget {
+    /// Messages estimated to decode to more than this fail with [`Error::DecodeBudgetExceeded`]
+    /// before they are decoded, see [`DecodeAccounting::check`]. Payloads that still decode
+    /// to more fail the same way
+    pub max_message_size: Option<usize>,
+    /// Reads pause once this many bytes were decoded within a second
+    pub bytes_per_sec: Option<u64>,
+    /// Reads pause while this many decoded bytes have not been released
+    /// with [`DecodeAccounting::release`]
+    pub max_outstanding: Option<usize>,
+}
+
+impl DecodeBudget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_message_size(mut self, bytes: usize) -> Self {
+        self.max_message_size = Some(bytes);
+        self
+    }
+
+    pub fn with_bytes_per_sec(mut self, bytes: u64) -> Self {
+        self.bytes_per_sec = Some(bytes);
+        self
+    }
+
+    /// The application must release every received payload, see [`DecodeAccounting::release`].
+    pub fn with_max_outstanding(mut self, bytes: usize) -> Self {
+        self.max_outstanding = Some(bytes);
+        self
+    }
+
+    #[must_use]
+    pub fn is_unlimited(&self) -> bool {
+        self.max_message_size.is_none()
+            && self.bytes_per_sec.is_none()
+            && self.max_outstanding.is_none()
+    }
+}
+
+#[derive(Debug, Default)]
+struct AccountingState {
+    messages: u64,
+    bytes: u64,
+    largest_message: usize,
+    outstanding: usize,
+    rejected: u64,
+    throttled: u64,
+    window_started: Option<Instant>,
+    window_bytes: u64,
+}
+
+impl AccountingState {
+    /// Starts a new rate window if the current one is over.
+    fn roll_window(&mut self, now: Instant) {
+        if self
+            .window_started
+            .is_none_or(|started| now.duration_since(started) >= RATE_WINDOW)
+        {
+            self.window_started = Some(now);
+            self.window_bytes = 0;
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    budget: DecodeBudget,
+    state: Mutex<AccountingState>,
+    released: Notify,
+}
+
+/// Payload sizes decoded on one connection and the [`DecodeBudget`] they are held to.
+///
+/// Clones share the same state, so a clone can be handed to the task
+/// that reads from the connection.
+#[derive(Debug, Clone, Default)]
+pub struct DecodeAccounting {
+    shared: Arc<Shared>,
+}
+
+impl DecodeAccounting {
+    pub fn new(budget: DecodeBudget) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                budget,
+                ..Shared::default()
+            }),
+        }
+    }
+
+    #[must_use]
+    pub fn budget(&self) -> DecodeBudget {
+        self.shared.budget
+    }
+
+    /// Fails if a message estimated to decode to `estimated_size` bytes is larger than
+    /// [`DecodeBudget::max_message_size`], before it is decoded.
+    pub fn check(&self, estimated_size: usize) -> Result<()> {
+        match self.shared.budget.max_message_size {
+            Some(max) if estimated_size > max => {
+                self.state().rejected += 1;
+                Err(Error::DecodeBudgetExceeded {
+                    size: estimated_size,
+                    max,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a payload that decoded to `size` bytes, which stay outstanding until released.
+    /// Fails if the payload is larger than [`DecodeBudget::max_message_size`].
+    pub fn charge(&self, size: usize) -> Result<()> {
+        let mut state = self.state();
+        if let Some(max) = self.shared.budget.max_message_size
+            && size > max
+        {
+            state.rejected += 1;
+            return Err(Error::DecodeBudgetExceeded { size, max });
+        }
+        state.messages += 1;
+        state.bytes += size as u64;
+        state.largest_message = state.largest_message.max(size);
+        state.outstanding += size;
+        state.roll_window(Instant::now());
+        state.window_bytes += size as u64;
+        Ok(())
+    }
+
+    /// Marks `size` bytes of charged payloads as consumed by the application.
+    pub fn release(&self, size: usize) {
+        {
+            let mut state = self.state();
+            state.outstanding = state.outstanding.saturating_sub(size);
+        }
+        self.shared.released.notify_waiters();
+    }
+
+    /// Waits until the budget allows reading another message.
+    pub async fn wait_for_capacity(&self) {
+        loop {
+            let released = self.shared.released.notified();
+            let mut released = pin!(released);
+            released.as_mut().enable();
+
+            let (rate_delay, over_outstanding) = {
+                let mut state = self.state();
+                let rate_delay = self.rate_delay(&mut state);
+                let over_outstanding = self
+                    .shared
+                    .budget
+                    .max_outstanding
+                    .is_some_and(|max| state.outstanding >= max);
+                if rate_delay.is_some() || over_outstanding {
+                    state.throttled += 1;
+                }
+                (rate_delay, over_outstanding)
+            };
+
+            if let Some(delay) = rate_delay {
+                trace!("Decode rate budget used up, pausing reads for {:?}", delay);
+                sleep(delay).await;
+            } else if over_outstanding {
+                trace!("Decode outstanding budget used up, pausing reads until a release");
+                released.await;
+            } else {
+                return;
+            }
+        }
+    }
+
+    /// How long until the rate window ends, if its budget is used up.
+    fn rate_delay(&self, state: &mut AccountingState) -> Option<Duration> {
+        let limit = self.shared.budget.bytes_per_sec?;
+        let now = Instant::now();
+        state.roll_window(now);
+        let started = state.window_started?;
+        (state.window_bytes >= limit)
+            .then(|| (started + RATE_WINDOW).saturating_duration_since(now))
+    }
+
+    /// The number of payloads charged
+    #[must_use]
+    pub fn messages(&self) -> u64 {
+        self.state().messages
+    }
+
+    /// The total size of the payloads charged
+    #[must_use]
+    pub fn bytes(&self) -> u64 {
+        self.state().bytes
+    }
+
+    #[must_use]
+    pub fn largest_message(&self) -> usize {
+        self.state().largest_message
+    }
+
+    /// The size of the payloads charged and not yet released
+    #[must_use]
+    pub fn outstanding(&self) -> usize {
+        self.state().outstanding
+    }
+
+    /// The size of the payloads charged in the current one-second window
+    #[must_use]
+    pub fn bytes_in_window(&self) -> u64 {
+        let mut state = self.state();
+        state.roll_window(Instant::now());
+        state.window_bytes
+    }
+
+    /// The number of payloads that exceeded [`DecodeBudget::max_message_size`]
+    #[must_use]
+    pub fn rejected(&self) -> u64 {
+        self.state().rejected
+    }
+
+    /// How many times reads were paused by the budget
+    #[must_use]
+    pub fn throttled(&self) -> u64 {
+        self.state().throttled
+    }
+
+    fn state(&self) -> MutexGuard<'_, AccountingState> {
+        self.shared
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
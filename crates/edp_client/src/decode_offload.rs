@@ -0,0 +1,244 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Decoding of large messages on tokio's blocking thread pool.
+//!
+//! Decoding a message of tens of megabytes can take hundreds of milliseconds, during which
+//! an async runtime worker thread runs no other task. [`DecodeOffload`] moves the decoding
+//! of messages above a size threshold to [`tokio::task::spawn_blocking`].
+
+use crate::connection::{Connection, ReceiveOptions, ReceivedMessage};
+use crate::control::ControlMessage;
+use crate::errors::{Error, Result};
+use erltf::OwnedTerm;
+use std::collections::BTreeMap;
+use std::io;
+use std::num::NonZeroUsize;
+use std::thread;
+use std::time::Duration;
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::sync::mpsc;
+use tokio::task::{JoinHandle, JoinSet};
+use tracing::{Instrument, Span, trace};
+
+/// Messages of at least this many bytes are decoded on the blocking pool by default
+pub const DEFAULT_OFFLOAD_THRESHOLD: usize = 1024 * 1024;
+
+/// How many decoded messages [`DecodePipeline`] holds back while an earlier one is decoded
+const REORDER_BUFFER_CAPACITY: usize = 128;
+
+type Decoded = Result<ReceivedMessage>;
+
+/// Which messages are decoded on the blocking thread pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOffload {
+    /// The encoded size from which a message is decoded on the blocking pool
+    pub threshold: usize,
+    /// How many messages [`DecodePipeline`] decodes at the same time,
+    /// the number of CPUs available to the process by default
+    pub max_in_flight: usize,
+}
+
+impl Default for DecodeOffload {
+    fn default() -> Self {
+        Self::new(DEFAULT_OFFLOAD_THRESHOLD)
+    }
+}
+
+impl DecodeOffload {
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            max_in_flight: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+        self
+    }
+
+    /// Whether a message of `size` bytes is decoded on the blocking pool.
+    pub fn applies_to(&self, size: usize) -> bool {
+        size >= self.threshold
+    }
+}
+
+/// Receives messages from a read half, decoding large ones on the blocking pool while
+/// the messages that follow are read and decoded.
+///
+/// Messages are returned in the order they were received: a message that follows
+/// a large one waits in a small reorder buffer until the large one is decoded.
+/// The read half is read by a task of its own, which stops when the pipeline is dropped.
+pub struct DecodePipeline {
+    offload: DecodeOffload,
+    options: ReceiveOptions,
+    frames: mpsc::Receiver<Result<Vec<u8>>>,
+    reader: JoinHandle<()>,
+    in_flight: JoinSet<(u64, Decoded)>,
+    /// Decoded messages that wait for an earlier one, by arrival order
+    reorder_buffer: BTreeMap<u64, Decoded>,
+    next_arrival: u64,
+    next_delivery: u64,
+    reader_done: bool,
+}
+
+impl DecodePipeline {
+    /// Reads with the same `timeout` and `options` as
+    /// [`Connection::receive_message_from_read_half_with_options`].
+    pub fn new(
+        read_half: OwnedReadHalf,
+        timeout: Duration,
+        options: ReceiveOptions,
+        offload: DecodeOffload,
+    ) -> Self {
+        let (sender, frames) = mpsc::channel(offload.max_in_flight.max(1));
+        let span = options.span.clone().unwrap_or_else(Span::none);
+        let reader =
+            tokio::spawn(read_frames(read_half, timeout, options.clone(), sender).instrument(span));
+        Self {
+            offload,
+            options,
+            frames,
+            reader,
+            in_flight: JoinSet::new(),
+            reorder_buffer: BTreeMap::new(),
+            next_arrival: 0,
+            next_delivery: 0,
+            reader_done: false,
+        }
+    }
+
+    /// Returns the next message in the order it was received.
+    ///
+    /// A read error is returned after the messages received before it, later calls
+    /// return [`Error::ConnectionClosed`]. Errors carry [`ReceiveOptions::connection_id`].
+    pub async fn receive(&mut self) -> Result<(ControlMessage, Option<OwnedTerm>)> {
+        self.receive_envelope()
+            .await
+            .map(ReceivedMessage::into_parts)
+    }
+
+    /// Like [`DecodePipeline::receive`] but returns the message with its provenance
+    /// and the size charged to [`ReceiveOptions::decode_accounting`].
+    pub async fn receive_envelope(&mut self) -> Decoded {
+        let decoded = loop {
+            match self.next_decoded().await {
+                Ok(received) if !self.options.accepts(&received.control) => {
+                    trace!(
+                        "Ignoring a link exit signal received while unlinking: {:?}",
+                        received.control
+                    );
+                }
+                decoded => break decoded,
+            }
+        };
+        match self.options.connection_id {
+            Some(id) => decoded.map_err(|e| e.with_connection_id(id)),
+            None => decoded,
+        }
+    }
+
+    async fn next_decoded(&mut self) -> Decoded {
+        loop {
+            if let Some(decoded) = self.reorder_buffer.remove(&self.next_delivery) {
+                self.next_delivery += 1;
+                return decoded;
+            }
+
+            let can_read = !self.reader_done
+                && self.in_flight.len() < self.offload.max_in_flight
+                && self.reorder_buffer.len() < REORDER_BUFFER_CAPACITY;
+            if !can_read && self.in_flight.is_empty() {
+                return Err(Error::ConnectionClosed);
+            }
+
+            tokio::select! {
+                Some(joined) = self.in_flight.join_next(), if !self.in_flight.is_empty() => {
+                    match joined {
+                        Ok((arrival, decoded)) => {
+                            self.reorder_buffer.insert(arrival, decoded);
+                        }
+                        Err(e) => {
+                            // The arrival order of the lost message is unknown
+                            self.shut_down();
+                            return Err(io::Error::from(e).into());
+                        }
+                    }
+                }
+                frame = self.frames.recv(), if can_read => match frame {
+                    Some(Ok(frame)) => self.submit(frame),
+                    Some(Err(e)) => {
+                        let arrival = self.next_arrival();
+                        self.reorder_buffer.insert(arrival, Err(e));
+                        self.reader_done = true;
+                    }
+                    None => self.reader_done = true,
+                },
+            }
+        }
+    }
+
+    /// The number of messages being decoded on the blocking pool.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    fn submit(&mut self, frame: Vec<u8>) {
+        let arrival = self.next_arrival();
+        if self.offload.applies_to(frame.len()) {
+            let options = self.options.clone();
+            self.in_flight.spawn_blocking(move || {
+                let decoded = Connection::decode_pass_through_frame(&frame, None, &options);
+                (arrival, decoded)
+            });
+        } else {
+            let decoded = Connection::decode_pass_through_frame(&frame, None, &self.options);
+            self.reorder_buffer.insert(arrival, decoded);
+        }
+    }
+
+    fn next_arrival(&mut self) -> u64 {
+        let arrival = self.next_arrival;
+        self.next_arrival += 1;
+        arrival
+    }
+
+    fn shut_down(&mut self) {
+        self.reader.abort();
+        self.reader_done = true;
+        self.in_flight.abort_all();
+        self.reorder_buffer.clear();
+    }
+}
+
+impl Drop for DecodePipeline {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+async fn read_frames(
+    mut read_half: OwnedReadHalf,
+    timeout: Duration,
+    options: ReceiveOptions,
+    frames: mpsc::Sender<Result<Vec<u8>>>,
+) {
+    loop {
+        let frame = Connection::read_pass_through_frame(&mut read_half, timeout, &options).await;
+        let failed = frame.is_err();
+        if frames.send(frame).await.is_err() || failed {
+            break;
+        }
+    }
+}
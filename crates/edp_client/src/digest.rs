@@ -15,7 +15,8 @@
 //! MD5 digest computation for distribution protocol handshake.
 
 use md5::{Digest, Md5};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use rand::TryRngCore;
+use rand::rngs::OsRng;
 use tracing::trace;
 
 pub fn compute_digest(challenge: u32, cookie: &str) -> [u8; 16] {
@@ -34,11 +35,40 @@ pub fn compute_digest(challenge: u32, cookie: &str) -> [u8; 16] {
     result.into()
 }
 
-pub fn generate_challenge() -> u32 {
-    let nanos = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_else(|_| Duration::from_secs(0))
-        .as_nanos();
+/// Produces the challenges this node sends during handshakes.
+///
+/// Closures returning a `u32` implement it, which makes handshakes deterministic in tests.
+pub trait ChallengeSource: Send + Sync {
+    fn next_challenge(&self) -> u32;
+}
+
+impl<F: Fn() -> u32 + Send + Sync> ChallengeSource for F {
+    fn next_challenge(&self) -> u32 {
+        self()
+    }
+}
+
+/// Draws challenges from the operating system's CSPRNG. The default source.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsChallengeSource;
 
-    (nanos & 0xFFFF_FFFF) as u32
+impl ChallengeSource for OsChallengeSource {
+    fn next_challenge(&self) -> u32 {
+        // The thread-local generator is a CSPRNG seeded from the OS as well
+        OsRng.try_next_u32().unwrap_or_else(|_| rand::random())
+    }
+}
+
+/// Always produces the same challenge, for byte-exact handshake tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedChallenge(pub u32);
+
+impl ChallengeSource for FixedChallenge {
+    fn next_challenge(&self) -> u32 {
+        self.0
+    }
+}
+
+pub fn generate_challenge() -> u32 {
+    OsChallengeSource.next_challenge()
 }
@@ -15,26 +15,28 @@
 //! An EPMD (Erlang Port Mapper Daemon) protocol client.
 
 use crate::errors::{Error, Result};
+use crate::protocol::{
+    EPMD_ALIVE2_REQ, EPMD_ALIVE2_RESP, EPMD_ALIVE2_X_RESP, EPMD_DUMP_REQ, EPMD_KILL_REQ,
+    EPMD_NAMES_REQ, EPMD_PORT2_REQ, EPMD_PORT2_RESP,
+};
+use crate::resolver::{Resolver, default_resolver, resolve_addresses};
 use bytes::{BufMut, BytesMut};
+use std::collections::HashMap;
+use std::result::Result as StdResult;
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
+use tokio::time::{Instant, sleep, timeout};
+use tracing::debug;
 
-/// Default EPMD port
-pub const EPMD_PORT: u16 = 4369;
+pub use crate::protocol::EPMD_PORT;
 
 /// Default connection timeout
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
 
-/// EPMD message types
-const ALIVE2_REQ: u8 = 120;
-const ALIVE2_RESP: u8 = 121;
-const ALIVE2_X_RESP: u8 = 118;
-const PORT2_REQ: u8 = 122;
-const PORT2_RESP: u8 = 119;
-const NAMES_REQ: u8 = 110;
-const DUMP_REQ: u8 = 100;
-const KILL_REQ: u8 = 107;
+/// Default delay between lookup attempts
+pub const DEFAULT_LOOKUP_RETRY_DELAY: Duration = Duration::from_millis(500);
 
 /// Node types for EPMD registration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -68,55 +70,274 @@ pub struct NodeInfo {
     pub extra: Vec<u8>,
 }
 
-/// EPMD client for node registration and lookup
-pub struct EpmdClient {
-    host: String,
-    port: u16,
-    timeout: Duration,
+/// Lookup caching and retry settings of an [`EpmdClient`].
+///
+/// By default, nothing is cached and a failed lookup is not retried.
+#[derive(Debug, Clone)]
+pub struct EpmdClientConfig {
+    pub host: String,
+    pub port: u16,
+    pub timeout: Duration,
+    /// How many times a lookup is attempted when EPMD cannot be reached, at least once
+    pub lookup_attempts: u32,
+    /// Delay between lookup attempts
+    pub lookup_retry_delay: Duration,
+    /// How long a found node's port is cached
+    pub cache_ttl: Option<Duration>,
+    /// How long EPMD's answer that a node is not registered is cached
+    pub negative_cache_ttl: Option<Duration>,
+    /// Resolves `host`, the operating system's resolver by default
+    pub resolver: Arc<dyn Resolver>,
 }
 
-impl EpmdClient {
-    /// Create a new EPMD client
+impl EpmdClientConfig {
     pub fn new(host: impl Into<String>) -> Self {
         Self {
             host: host.into(),
             port: EPMD_PORT,
             timeout: DEFAULT_TIMEOUT,
+            lookup_attempts: 1,
+            lookup_retry_delay: DEFAULT_LOOKUP_RETRY_DELAY,
+            cache_ttl: None,
+            negative_cache_ttl: None,
+            resolver: default_resolver(),
         }
     }
 
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_lookup_retries(mut self, attempts: u32, delay: Duration) -> Self {
+        self.lookup_attempts = attempts.max(1);
+        self.lookup_retry_delay = delay;
+        self
+    }
+
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    pub fn with_negative_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_cache_ttl = Some(ttl);
+        self
+    }
+
+    pub fn with_resolver(mut self, resolver: impl Resolver + 'static) -> Self {
+        self.resolver = Arc::new(resolver);
+        self
+    }
+}
+
+impl Default for EpmdClientConfig {
+    fn default() -> Self {
+        Self::new("localhost")
+    }
+}
+
+/// A cached lookup result. `None` means EPMD reported the node as not registered.
+#[derive(Debug)]
+struct CacheEntry {
+    node_info: Option<NodeInfo>,
+    registered: Vec<String>,
+    expires_at: Instant,
+}
+
+/// A node registered with EPMD, as listed by a NAMES request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredNode {
+    pub name: String,
+    pub port: u16,
+}
+
+/// Parses the `name <Name> at port <Port>` lines of a NAMES response, skipping other lines.
+pub fn parse_names(response: &str) -> Vec<RegisteredNode> {
+    response
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("name ")?;
+            let (name, port) = rest.rsplit_once(" at port ")?;
+            Some(RegisteredNode {
+                name: name.to_string(),
+                port: port.trim().parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// EPMD client for node registration and lookup.
+///
+/// Clones share the lookup cache.
+#[derive(Debug, Clone)]
+pub struct EpmdClient {
+    config: EpmdClientConfig,
+    cache: Arc<Mutex<HashMap<String, CacheEntry>>>,
+}
+
+impl EpmdClient {
+    /// Create a new EPMD client
+    pub fn new(host: impl Into<String>) -> Self {
+        Self::from_config(EpmdClientConfig::new(host))
+    }
+
     /// Create an EPMD client with custom port
     pub fn with_port(host: impl Into<String>, port: u16) -> Self {
+        Self::from_config(EpmdClientConfig::new(host).with_port(port))
+    }
+
+    pub fn from_config(config: EpmdClientConfig) -> Self {
         Self {
-            host: host.into(),
-            port,
-            timeout: DEFAULT_TIMEOUT,
+            config,
+            cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
     /// Set connection timeout
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = timeout;
+        self.config.timeout = timeout;
+        self
+    }
+
+    /// Set the resolver for the EPMD host
+    pub fn with_resolver(mut self, resolver: impl Resolver + 'static) -> Self {
+        self.config.resolver = Arc::new(resolver);
         self
     }
 
+    pub fn config(&self) -> &EpmdClientConfig {
+        &self.config
+    }
+
     async fn connect(&self) -> Result<TcpStream> {
-        let addr = format!("{}:{}", self.host, self.port);
-        tokio::time::timeout(self.timeout, TcpStream::connect(&addr))
+        let host = &self.config.host;
+        let port = self.config.port;
+        let attempt = async {
+            let addrs = resolve_addresses(self.config.resolver.as_ref(), host, port).await?;
+            TcpStream::connect(addrs.as_slice()).await
+        };
+        timeout(self.config.timeout, attempt)
             .await
-            .map_err(|_| Error::Timeout(self.timeout))?
+            .map_err(|_| Error::Timeout(self.config.timeout))?
             .map_err(|e| {
-                Error::EpmdProtocol(format!("Failed to connect to EPMD at {}: {}", addr, e))
+                Error::EpmdProtocol(format!(
+                    "Failed to connect to EPMD at {}:{}: {}",
+                    host, port, e
+                ))
             })
     }
 
-    /// Lookup a node's port by name
+    /// Lookup a node's port by name.
+    ///
+    /// Answers from the cache when it has an unexpired entry for the node. Otherwise,
+    /// queries EPMD, retrying as configured while EPMD cannot be reached.
     pub async fn lookup_node(&self, node_name: &str) -> Result<NodeInfo> {
+        if let Some(cached) = self.cached_lookup(node_name) {
+            return cached;
+        }
+
+        let mut attempt = 1;
+        let result = loop {
+            match self.query_node(node_name).await {
+                Err(e) if !is_not_found(&e) && attempt < self.config.lookup_attempts => {
+                    debug!(
+                        "EPMD lookup of {} failed (attempt {}/{}): {}",
+                        node_name, attempt, self.config.lookup_attempts, e
+                    );
+                    attempt += 1;
+                    sleep(self.config.lookup_retry_delay).await;
+                }
+                result => break result,
+            }
+        };
+
+        match &result {
+            Ok(node_info) => self.cache_lookup(node_name, Ok(node_info.clone())),
+            Err(Error::EpmdNodeNotRegistered { registered, .. }) => {
+                self.cache_lookup(node_name, Err(registered.clone()))
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Drops the cached lookup result for a node, if any.
+    pub fn invalidate(&self, node_name: &str) {
+        self.lock_cache().remove(node_name);
+    }
+
+    pub fn clear_cache(&self) {
+        self.lock_cache().clear();
+    }
+
+    fn lock_cache(&self) -> MutexGuard<'_, HashMap<String, CacheEntry>> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn cached_lookup(&self, node_name: &str) -> Option<Result<NodeInfo>> {
+        let mut cache = self.lock_cache();
+        let entry = cache.get(node_name)?;
+        if entry.expires_at <= Instant::now() {
+            cache.remove(node_name);
+            return None;
+        }
+        Some(match &entry.node_info {
+            Some(node_info) => Ok(node_info.clone()),
+            None => Err(self.not_registered(node_name, entry.registered.clone())),
+        })
+    }
+
+    fn cache_lookup(&self, node_name: &str, lookup: StdResult<NodeInfo, Vec<String>>) {
+        let (ttl, node_info, registered) = match lookup {
+            Ok(node_info) => (self.config.cache_ttl, Some(node_info), Vec::new()),
+            Err(registered) => (self.config.negative_cache_ttl, None, registered),
+        };
+        if let Some(ttl) = ttl {
+            self.lock_cache().insert(
+                node_name.to_string(),
+                CacheEntry {
+                    node_info,
+                    registered,
+                    expires_at: Instant::now() + ttl,
+                },
+            );
+        }
+    }
+
+    fn not_registered(&self, node_name: &str, registered: Vec<String>) -> Error {
+        Error::EpmdNodeNotRegistered {
+            node: node_name.to_string(),
+            epmd_host: self.config.host.clone(),
+            epmd_port: self.config.port,
+            registered,
+        }
+    }
+
+    /// Lists the registered node names to make a failed lookup self-diagnosing.
+    /// A failure to list them leaves the list empty.
+    async fn lookup_failure(&self, node_name: &str) -> Error {
+        let registered = match self.names().await {
+            Ok(nodes) => nodes.into_iter().map(|node| node.name).collect(),
+            Err(e) => {
+                debug!("Could not list the nodes registered with EPMD: {}", e);
+                Vec::new()
+            }
+        };
+        self.not_registered(node_name, registered)
+    }
+
+    async fn query_node(&self, node_name: &str) -> Result<NodeInfo> {
         let mut stream = self.connect().await?;
 
         let mut buf = BytesMut::new();
         buf.put_u16(node_name.len() as u16 + 1);
-        buf.put_u8(PORT2_REQ);
+        buf.put_u8(EPMD_PORT2_REQ);
         buf.put_slice(node_name.as_bytes());
 
         stream.write_all(&buf).await?;
@@ -125,13 +346,11 @@ impl EpmdClient {
         let response_type = stream.read_u8().await?;
 
         match response_type {
-            PORT2_RESP => {
+            EPMD_PORT2_RESP => {
                 let result = stream.read_u8().await?;
                 if result != 0 {
-                    return Err(Error::EpmdLookup {
-                        node: node_name.to_string(),
-                        reason: "Node not found".to_string(),
-                    });
+                    drop(stream);
+                    return Err(self.lookup_failure(node_name).await);
                 }
 
                 let port = stream.read_u16().await?;
@@ -188,10 +407,12 @@ impl EpmdClient {
                     extra,
                 })
             }
-            other => Err(Error::EpmdProtocol(format!(
-                "Unexpected response type: {}",
-                other
-            ))),
+            other => Err(Error::EpmdProtocolMismatch {
+                node: node_name.to_string(),
+                epmd_host: self.config.host.clone(),
+                epmd_port: self.config.port,
+                response_type: other,
+            }),
         }
     }
 
@@ -212,7 +433,7 @@ impl EpmdClient {
 
         let mut buf = BytesMut::new();
         buf.put_u16(total_len as u16);
-        buf.put_u8(ALIVE2_REQ);
+        buf.put_u8(EPMD_ALIVE2_REQ);
         buf.put_u16(port);
         buf.put_u8(node_type as u8);
         buf.put_u8(Protocol::Tcp as u8);
@@ -229,7 +450,7 @@ impl EpmdClient {
         let response_type = stream.read_u8().await?;
 
         match response_type {
-            ALIVE2_RESP => {
+            EPMD_ALIVE2_RESP => {
                 let result = stream.read_u8().await?;
                 if result != 0 {
                     return Err(Error::EpmdRegistration {
@@ -240,7 +461,7 @@ impl EpmdClient {
                 let creation = stream.read_u16().await? as u32;
                 Ok(creation)
             }
-            ALIVE2_X_RESP => {
+            EPMD_ALIVE2_X_RESP => {
                 let result = stream.read_u8().await?;
                 if result != 0 {
                     return Err(Error::EpmdRegistration {
@@ -264,7 +485,7 @@ impl EpmdClient {
 
         let mut buf = BytesMut::new();
         buf.put_u16(1);
-        buf.put_u8(NAMES_REQ);
+        buf.put_u8(EPMD_NAMES_REQ);
 
         stream.write_all(&buf).await?;
         stream.flush().await?;
@@ -278,13 +499,20 @@ impl EpmdClient {
             .map_err(|_| Error::EpmdProtocol("Invalid UTF-8 in NAMES response".to_string()))
     }
 
+    /// Query all registered nodes (NAMES_REQ) and parse the response.
+    pub async fn names(&self) -> Result<Vec<RegisteredNode>> {
+        self.list_nodes()
+            .await
+            .map(|response| parse_names(&response))
+    }
+
     /// Dump all registered nodes with details (DUMP_REQ)
     pub async fn dump_nodes(&self) -> Result<String> {
         let mut stream = self.connect().await?;
 
         let mut buf = BytesMut::new();
         buf.put_u16(1);
-        buf.put_u8(DUMP_REQ);
+        buf.put_u8(EPMD_DUMP_REQ);
 
         stream.write_all(&buf).await?;
         stream.flush().await?;
@@ -304,7 +532,7 @@ impl EpmdClient {
 
         let mut buf = BytesMut::new();
         buf.put_u16(1);
-        buf.put_u8(KILL_REQ);
+        buf.put_u8(EPMD_KILL_REQ);
 
         stream.write_all(&buf).await?;
         stream.flush().await?;
@@ -316,3 +544,8 @@ impl EpmdClient {
             .map_err(|_| Error::EpmdProtocol("Invalid UTF-8 in KILL response".to_string()))
     }
 }
+
+/// EPMD answered that the node is not registered, as opposed to EPMD being unreachable.
+fn is_not_found(error: &Error) -> bool {
+    matches!(error, Error::EpmdNodeNotRegistered { .. })
+}
@@ -11,7 +11,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::control::{ControlMessageType, ValidationError};
 use crate::state_machine::ConnectionState;
+use crate::types::ConnectionId;
+use erltf::OwnedTerm;
 use erltf::errors::{ContextualDecodeError, DecodeError, EncodeError, TermConversionError};
 use std::io;
 use std::time::Duration;
@@ -33,9 +36,38 @@ pub enum Error {
     #[error("Connection refused by peer: {reason}")]
     ConnectionRefused { reason: String },
 
+    /// The peer answered the handshake with `alive`, i.e. it still has a connection
+    /// from this node, and this node declined to replace it
+    #[error("Node '{node}' already has a connection from this node, the handshake was declined")]
+    PeerConnectionAlive { node: String },
+
     #[error("EPMD lookup failed for node '{node}': {reason}")]
     EpmdLookup { node: String, reason: String },
 
+    /// EPMD answered that no node by this name is registered
+    #[error(
+        "Node '{node}' is not registered with EPMD at {epmd_host}:{epmd_port}, registered nodes: {registered:?}"
+    )]
+    EpmdNodeNotRegistered {
+        node: String,
+        epmd_host: String,
+        epmd_port: u16,
+        /// Names EPMD does know, empty if they could not be listed
+        registered: Vec<String>,
+    },
+
+    /// EPMD answered a PORT_PLEASE2 request with something other than PORT2_RESP,
+    /// e.g. because it predates the request or is not an EPMD
+    #[error(
+        "EPMD at {epmd_host}:{epmd_port} answered the lookup of '{node}' with unexpected response type {response_type}"
+    )]
+    EpmdProtocolMismatch {
+        node: String,
+        epmd_host: String,
+        epmd_port: u16,
+        response_type: u8,
+    },
+
     #[error("EPMD registration failed: {reason}")]
     EpmdRegistration { reason: String },
 
@@ -60,12 +92,48 @@ pub enum Error {
     #[error("Invalid control message: {0}")]
     InvalidControlMessage(String),
 
+    #[error("Invalid control message: {0}")]
+    ControlMessageValidation(#[from] ValidationError),
+
+    #[error("Unknown control message type {message_type}: {term}")]
+    UnknownControlMessage { message_type: u8, term: OwnedTerm },
+
+    #[error("{} control message with an unexpected number of elements ({arity}): {term}", .message_type.name())]
+    ControlMessageArityMismatch {
+        message_type: ControlMessageType,
+        arity: usize,
+        term: OwnedTerm,
+    },
+
     #[error("Message too large: {size} bytes (max {max} bytes)")]
     MessageTooLarge { size: usize, max: usize },
 
+    /// A payload decoded to more than [`DecodeBudget::max_message_size`](crate::DecodeBudget::max_message_size)
+    #[error("Decoded payload takes about {size} bytes, more than the budget of {max} bytes")]
+    DecodeBudgetExceeded { size: usize, max: usize },
+
     #[error("Node name too long: {size} bytes (max {max} bytes)")]
     NodeNameTooLong { size: usize, max: usize },
 
+    #[error(
+        "Pid {pid} has creation {creation}, node {node} has since restarted with creation {current}"
+    )]
+    StalePid {
+        pid: String,
+        node: String,
+        creation: u32,
+        current: u32,
+    },
+
+    #[error("Invalid creation {0}: 0 is the legacy wildcard")]
+    InvalidCreation(u32),
+
+    #[error("Invalid sequence id {0}: sequence ids start at 1")]
+    InvalidSequenceId(u64),
+
+    #[error("Node {node} did not negotiate process aliases")]
+    AliasesNotSupported { node: String },
+
     #[error("Invalid node name: {0}")]
     InvalidNodeName(String),
 
@@ -101,22 +169,76 @@ pub enum Error {
 
     #[error("{0}")]
     InvalidStateMessage(String),
+
+    /// A complete frame failed to decode, the connection continues with the next one,
+    /// see [`DecodeErrorPolicy::Resume`](crate::DecodeErrorPolicy::Resume)
+    #[error("Failed to decode a {}-byte frame: {source}", .frame.len())]
+    FrameDecode {
+        /// The frame as it was received, or reassembled from fragments
+        frame: Vec<u8>,
+        #[source]
+        source: Box<Error>,
+    },
+
+    /// An error that ended or prevented the connection with this id
+    #[error("Connection {connection_id}: {source}")]
+    Connection {
+        connection_id: ConnectionId,
+        #[source]
+        source: Box<Error>,
+    },
 }
 
 impl Error {
+    /// Attaches `connection_id`, unless the error already carries one.
+    pub fn with_connection_id(self, connection_id: ConnectionId) -> Self {
+        match self {
+            Error::Connection { .. } => self,
+            source => Error::Connection {
+                connection_id,
+                source: Box::new(source),
+            },
+        }
+    }
+
+    /// The id of the connection the error occurred on, if attached.
+    pub fn connection_id(&self) -> Option<ConnectionId> {
+        match self {
+            Error::Connection { connection_id, .. } => Some(*connection_id),
+            _ => None,
+        }
+    }
+
+    /// The error without the connection id.
+    pub fn inner(&self) -> &Error {
+        match self {
+            Error::Connection { source, .. } => source,
+            _ => self,
+        }
+    }
+
+    /// Like [`Error::inner`] but takes ownership.
+    pub fn into_inner(self) -> Error {
+        match self {
+            Error::Connection { source, .. } => *source,
+            other => other,
+        }
+    }
+
     pub fn is_recoverable(&self) -> bool {
         matches!(
-            self,
+            self.inner(),
             Error::Io(_)
                 | Error::Timeout(_)
                 | Error::UnexpectedEof { .. }
                 | Error::EpmdLookup { .. }
+                | Error::EpmdNodeNotRegistered { .. }
                 | Error::ConnectionRefused { .. }
         )
     }
 
     pub fn is_connection_closed(&self) -> bool {
-        match self {
+        match self.inner() {
             Error::ConnectionClosed | Error::UnexpectedEof { .. } => true,
             Error::Io(e) => {
                 matches!(
@@ -130,8 +252,13 @@ impl Error {
         }
     }
 
+    /// Whether only the frame being received failed, see [`Error::FrameDecode`].
+    pub fn is_frame_decode(&self) -> bool {
+        matches!(self.inner(), Error::FrameDecode { .. })
+    }
+
     pub fn is_timeout(&self) -> bool {
-        match self {
+        match self.inner() {
             Error::Timeout(_) => true,
             Error::Io(e) => e.kind() == io::ErrorKind::TimedOut,
             _ => false,
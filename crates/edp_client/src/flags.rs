@@ -15,13 +15,21 @@
 //! Distribution protocol capability flags for Erlang/OTP 26+.
 
 use bitflags::bitflags;
+use bitflags::parser::{self, ParseError};
+use std::fmt;
+use std::str::FromStr;
 
 bitflags! {
     /// Distribution capability flags as u64 bitmask.
     ///
     /// These flags are negotiated during the handshake to determine which
     /// protocol features are supported by both nodes.
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    ///
+    /// With the `serde` feature, flags serialize as a `"PUBLISHED | ATOM_CACHE"`
+    /// string in human-readable formats and as a `u64` otherwise.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     pub struct DistributionFlags: u64 {
         /// Published node (not hidden)
         const PUBLISHED = 0x01;
@@ -74,6 +82,9 @@ bitflags! {
         /// Large node creation identifiers (mandatory in Erlang/OTP 26+)
         const BIG_CREATION = 0x40000;
 
+        /// SEND_SENDER control messages, which carry the sender's pid
+        const SEND_SENDER = 0x80000;
+
         /// Message fragmentation support
         const FRAGMENTS = 0x800_0000;
 
@@ -127,6 +138,7 @@ impl DistributionFlags {
             | Self::DIST_MONITOR.bits()
             | Self::DIST_MONITOR_NAME.bits()
             | Self::SMALL_ATOM_TAGS.bits()
+            | Self::SEND_SENDER.bits()
             | Self::FRAGMENTS.bits()
             | Self::SPAWN.bits()
             | Self::NAME_ME.bits()
@@ -143,6 +155,7 @@ impl DistributionFlags {
             | Self::DIST_MONITOR.bits()
             | Self::DIST_MONITOR_NAME.bits()
             | Self::SMALL_ATOM_TAGS.bits()
+            | Self::SEND_SENDER.bits()
             | Self::FRAGMENTS.bits()
             | Self::SPAWN.bits()
             | Self::NAME_ME.bits()
@@ -178,6 +191,133 @@ impl DistributionFlags {
     pub const fn as_u64(&self) -> u64 {
         self.bits()
     }
+
+    /// The optional behaviors these (typically negotiated) flags enable.
+    pub const fn compatibility_report(&self) -> CompatibilityReport {
+        CompatibilityReport {
+            flags: *self,
+            fragmentation: self.has(Self::FRAGMENTS),
+            atom_cache: self.has(Self::DIST_HDR_ATOM_CACHE),
+            big_creation: self.has(Self::BIG_CREATION),
+            v4_node_container_ids: self.has(Self::V4_NC),
+            alias: self.has(Self::ALIAS),
+            spawn: self.has(Self::SPAWN),
+            unlink_id: self.has(Self::UNLINK_ID),
+            send_sender: self.has(Self::SEND_SENDER),
+        }
+    }
+
+    /// Compares these (for example, requested) flags with `other` (for example, negotiated) flags.
+    pub const fn diff(&self, other: &Self) -> FlagsDiff {
+        FlagsDiff {
+            missing: self.difference(*other),
+            added: other.difference(*self),
+        }
+    }
+}
+
+/// The result of [`DistributionFlags::diff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FlagsDiff {
+    /// Flags set on the left side but not on the right side,
+    /// e.g. requested flags the peer did not accept
+    pub missing: DistributionFlags,
+    /// Flags set on the right side but not on the left side
+    pub added: DistributionFlags,
+}
+
+impl FlagsDiff {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.added.is_empty()
+    }
+}
+
+impl fmt::Display for FlagsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing: {}, added: {}", self.missing, self.added)
+    }
+}
+
+/// The optional distribution behaviors in effect, see [`DistributionFlags::compatibility_report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CompatibilityReport {
+    /// The flags the report was derived from
+    pub flags: DistributionFlags,
+    /// Large messages are split into fragments (`FRAGMENTS`)
+    pub fragmentation: bool,
+    /// Distribution headers reference atoms in a per-connection cache (`DIST_HDR_ATOM_CACHE`)
+    pub atom_cache: bool,
+    /// Pids, ports and references carry 32-bit creations (`BIG_CREATION`)
+    pub big_creation: bool,
+    /// Pids and ports carry 64-bit ids (`V4_NC`)
+    pub v4_node_container_ids: bool,
+    /// Messages can be sent to process aliases (`ALIAS`)
+    pub alias: bool,
+    /// Processes can be spawned with `SPAWN_REQUEST` (`SPAWN`)
+    pub spawn: bool,
+    /// Unlinking uses the `UNLINK_ID`/`UNLINK_ID_ACK` protocol (`UNLINK_ID`)
+    pub unlink_id: bool,
+    /// Messages to pids are sent with `SEND_SENDER`, which carries the sender (`SEND_SENDER`)
+    pub send_sender: bool,
+}
+
+impl CompatibilityReport {
+    /// The names of the enabled behaviors, in field order.
+    pub fn enabled(&self) -> Vec<&'static str> {
+        [
+            ("fragmentation", self.fragmentation),
+            ("atom_cache", self.atom_cache),
+            ("big_creation", self.big_creation),
+            ("v4_node_container_ids", self.v4_node_container_ids),
+            ("alias", self.alias),
+            ("spawn", self.spawn),
+            ("unlink_id", self.unlink_id),
+            ("send_sender", self.send_sender),
+        ]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect()
+    }
+}
+
+impl From<DistributionFlags> for CompatibilityReport {
+    fn from(flags: DistributionFlags) -> Self {
+        flags.compatibility_report()
+    }
+}
+
+/// Lists the enabled behaviors separated by `, `.
+impl fmt::Display for CompatibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let enabled = self.enabled();
+        if enabled.is_empty() {
+            return f.write_str("(none)");
+        }
+        f.write_str(&enabled.join(", "))
+    }
+}
+
+/// Lists flag names separated by ` | `, with unknown bits in hex.
+impl fmt::Display for DistributionFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("(none)");
+        }
+        parser::to_writer(self, f)
+    }
+}
+
+/// Parses the format produced by `Display`, e.g. `"PUBLISHED | FRAGMENTS | 0x80000000"`.
+impl FromStr for DistributionFlags {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim() == "(none)" {
+            return Ok(Self::empty());
+        }
+        parser::from_str(s)
+    }
 }
 
 impl Default for DistributionFlags {
@@ -18,8 +18,8 @@ use std::collections::{HashMap, hash_map::Entry};
 use std::time::{Duration, Instant};
 use tracing::trace;
 
-pub const DIST_FRAG_HEADER: u8 = 69;
-pub const DIST_FRAG_CONT: u8 = 70;
+pub use crate::protocol::{DIST_FRAG_CONT, DIST_FRAG_HEADER};
+
 pub const DEFAULT_FRAGMENT_TIMEOUT: Duration = Duration::from_secs(30);
 const MAX_FRAGMENTS_VEC: u64 = 100_000;
 const MAX_FRAGMENT_COUNT: u64 = 1_000_000;
@@ -322,6 +322,11 @@ impl FragmentAssembler {
         self.pending.clear();
     }
 
+    /// Releases the capacity left by reassembled messages. Incomplete ones are kept.
+    pub fn shrink_to_fit(&mut self) {
+        self.pending.shrink_to_fit();
+    }
+
     pub fn pending_count(&self) -> usize {
         self.pending.len()
     }
@@ -19,9 +19,12 @@ use tracing::trace;
 
 const MAX_MESSAGE_SIZE: usize = 256 * 1024 * 1024;
 
+/// How messages are length-prefixed on a distribution connection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrameMode {
+    /// A 2-byte length, used until the handshake completes
     Handshake,
+    /// A 4-byte length, used once the handshake completes
     Distribution,
 }
 
@@ -43,6 +46,10 @@ impl MessageFramer {
         Self { mode }
     }
 
+    pub fn mode(&self) -> FrameMode {
+        self.mode
+    }
+
     pub fn set_mode(&mut self, mode: FrameMode) {
         self.mode = mode;
     }
@@ -103,6 +110,10 @@ impl MessageDeframer {
         Self { mode }
     }
 
+    pub fn mode(&self) -> FrameMode {
+        self.mode
+    }
+
     pub fn set_mode(&mut self, mode: FrameMode) {
         self.mode = mode;
     }
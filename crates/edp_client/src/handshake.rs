@@ -17,21 +17,46 @@
 use crate::digest;
 use crate::errors::{Error, Result};
 use crate::flags::DistributionFlags;
+use crate::protocol::{CHALLENGE_ACK_TAG, CHALLENGE_REPLY_TAG, NAME_TAG, NAME_TAG_V5, STATUS_TAG};
 use bytes::{Buf, BufMut, BytesMut};
 use std::fmt;
 use std::str;
 
-/// Handshake protocol version (version 6 was introduced in Erlang/OTP 23.0)
-pub const PROTOCOL_VERSION: u16 = 6;
+pub use crate::protocol::{PROTOCOL_VERSION, PROTOCOL_VERSION_5};
 
-/// Old protocol version 5 (for compatibility)
-pub const PROTOCOL_VERSION_5: u16 = 5;
+/// Handshake protocol version used with a particular peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HandshakeVersion {
+    /// Pre-OTP 23 handshake: 32-bit flags, no creation in the challenge and no complement message.
+    /// Requires the `legacy-handshake` feature.
+    V5,
+    #[default]
+    V6,
+}
 
-/// Handshake message tags
-const HANDSHAKE_TAG_N: u8 = b'N'; // New format (v6)
-const HANDSHAKE_TAG_N_OLD: u8 = b'n'; // Old format (v5)
-const HANDSHAKE_TAG_S: u8 = b's';
-const HANDSHAKE_TAG_A: u8 = b'a';
+impl HandshakeVersion {
+    /// Picks the version to use with a peer whose EPMD registration advertises `lowest..=highest`.
+    pub fn select(lowest: u16, highest: u16) -> Result<Self> {
+        if (lowest..=highest).contains(&PROTOCOL_VERSION) {
+            return Ok(HandshakeVersion::V6);
+        }
+        #[cfg(feature = "legacy-handshake")]
+        if (lowest..=highest).contains(&PROTOCOL_VERSION_5) {
+            return Ok(HandshakeVersion::V5);
+        }
+        Err(Error::IncompatibleVersion {
+            got: highest,
+            expected: PROTOCOL_VERSION,
+        })
+    }
+
+    pub fn as_u16(&self) -> u16 {
+        match self {
+            HandshakeVersion::V5 => PROTOCOL_VERSION_5,
+            HandshakeVersion::V6 => PROTOCOL_VERSION,
+        }
+    }
+}
 
 /// Handshake status codes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -103,7 +128,7 @@ impl SendName {
 
         let message_len = 1 + 8 + 4 + 2 + name_bytes.len();
         buf.put_u16(message_len as u16);
-        buf.put_u8(HANDSHAKE_TAG_N);
+        buf.put_u8(NAME_TAG);
         buf.put_u64(self.flags.as_u64());
         buf.put_u32(self.creation);
         buf.put_u16(name_bytes.len() as u16);
@@ -125,7 +150,7 @@ impl SendName {
 
         let message_len = 1 + 2 + 4 + name_bytes.len();
         buf.put_u16(message_len as u16);
-        buf.put_u8(HANDSHAKE_TAG_N_OLD);
+        buf.put_u8(NAME_TAG_V5);
         buf.put_u16(PROTOCOL_VERSION_5);
         buf.put_u32(self.flags.as_u64() as u32);
         buf.put_slice(name_bytes);
@@ -143,10 +168,10 @@ impl SendName {
         }
 
         let tag = buf.get_u8();
-        if tag != HANDSHAKE_TAG_N {
+        if tag != NAME_TAG {
             return Err(Error::InvalidHandshakeMessage(format!(
                 "Expected tag 'N' ({}), got {}",
-                HANDSHAKE_TAG_N, tag
+                NAME_TAG, tag
             )));
         }
 
@@ -179,6 +204,51 @@ impl SendName {
             name,
         })
     }
+
+    /// Decodes a protocol version 5 send name message (tag: 'n'), which carries 32-bit flags
+    /// and no creation. The creation is set to 0.
+    pub fn decode_old(data: &[u8]) -> Result<Self> {
+        let mut buf = data;
+
+        if buf.remaining() < 1 {
+            return Err(Error::InvalidHandshakeMessage(
+                "Insufficient data for tag".to_string(),
+            ));
+        }
+
+        let tag = buf.get_u8();
+        if tag != NAME_TAG_V5 {
+            return Err(Error::InvalidHandshakeMessage(format!(
+                "Expected tag 'n', got {}",
+                tag
+            )));
+        }
+
+        if buf.remaining() < 2 + 4 {
+            return Err(Error::InvalidHandshakeMessage(
+                "Insufficient data for version and flags".to_string(),
+            ));
+        }
+
+        let version = buf.get_u16();
+        if version != PROTOCOL_VERSION_5 {
+            return Err(Error::IncompatibleVersion {
+                got: version,
+                expected: PROTOCOL_VERSION_5,
+            });
+        }
+        let flags = DistributionFlags::new(u64::from(buf.get_u32()));
+
+        let name = str::from_utf8(buf)
+            .map_err(|_| Error::InvalidHandshakeMessage("Invalid UTF-8 in node name".to_string()))?
+            .to_owned();
+
+        Ok(Self {
+            flags,
+            creation: 0,
+            name,
+        })
+    }
 }
 
 /// Status message (tag: 's')
@@ -193,10 +263,11 @@ impl StatusMessage {
     }
 
     pub fn encode(&self) -> Vec<u8> {
-        let mut buf = BytesMut::new();
-        buf.put_u16(3);
-        buf.put_u8(HANDSHAKE_TAG_S);
-        buf.put_u16(self.status as u16);
+        let status = self.status.to_string();
+        let mut buf = BytesMut::with_capacity(3 + status.len());
+        buf.put_u16(1 + status.len() as u16);
+        buf.put_u8(STATUS_TAG);
+        buf.put_slice(status.as_bytes());
         buf.to_vec()
     }
 
@@ -210,10 +281,10 @@ impl StatusMessage {
         }
 
         let tag = buf.get_u8();
-        if tag != HANDSHAKE_TAG_S {
+        if tag != STATUS_TAG {
             return Err(Error::InvalidHandshakeMessage(format!(
                 "Expected tag 's' ({}), got {}",
-                HANDSHAKE_TAG_S, tag
+                STATUS_TAG, tag
             )));
         }
 
@@ -236,6 +307,21 @@ impl StatusMessage {
 
         Ok(Self { status })
     }
+
+    /// The initiator's answer to an `alive` status: `true` asks the peer to drop its
+    /// connection from this node and continue, `false` ends the handshake.
+    pub fn encode_alive_answer(continue_handshake: bool) -> Vec<u8> {
+        let answer: &[u8] = if continue_handshake {
+            b"true"
+        } else {
+            b"false"
+        };
+        let mut buf = BytesMut::with_capacity(3 + answer.len());
+        buf.put_u16(1 + answer.len() as u16);
+        buf.put_u8(STATUS_TAG);
+        buf.put_slice(answer);
+        buf.to_vec()
+    }
 }
 
 /// Challenge message
@@ -275,7 +361,7 @@ impl Challenge {
 
         let message_len = 1 + 8 + 4 + 4 + 2 + name_bytes.len();
         buf.put_u16(message_len as u16);
-        buf.put_u8(HANDSHAKE_TAG_N);
+        buf.put_u8(NAME_TAG);
         buf.put_u64(self.flags.as_u64());
         buf.put_u32(self.challenge);
         buf.put_u32(self.creation);
@@ -295,7 +381,7 @@ impl Challenge {
         }
 
         let tag = buf.get_u8();
-        if tag != HANDSHAKE_TAG_N {
+        if tag != NAME_TAG {
             return Err(Error::InvalidHandshakeMessage(format!(
                 "Expected tag 'N', got {}",
                 tag
@@ -335,6 +421,78 @@ impl Challenge {
     }
 }
 
+#[cfg(feature = "legacy-handshake")]
+impl Challenge {
+    /// Encodes a protocol version 5 challenge (tag: 'n'), which carries no creation.
+    pub fn encode_old(&self) -> Result<Vec<u8>> {
+        let name_bytes = self.name.as_bytes();
+        if name_bytes.len() > 255 {
+            return Err(Error::NodeNameTooLong {
+                size: name_bytes.len(),
+                max: 255,
+            });
+        }
+
+        let mut buf = BytesMut::new();
+
+        let message_len = 1 + 2 + 4 + 4 + name_bytes.len();
+        buf.put_u16(message_len as u16);
+        buf.put_u8(NAME_TAG_V5);
+        buf.put_u16(PROTOCOL_VERSION_5);
+        buf.put_u32(self.flags.as_u64() as u32);
+        buf.put_u32(self.challenge);
+        buf.put_slice(name_bytes);
+
+        Ok(buf.to_vec())
+    }
+
+    /// Decodes a protocol version 5 challenge (tag: 'n'). The creation is set to 0.
+    pub fn decode_old(data: &[u8]) -> Result<Self> {
+        let mut buf = data;
+
+        if buf.remaining() < 1 {
+            return Err(Error::InvalidHandshakeMessage(
+                "Insufficient data for tag".to_string(),
+            ));
+        }
+
+        let tag = buf.get_u8();
+        if tag != NAME_TAG_V5 {
+            return Err(Error::InvalidHandshakeMessage(format!(
+                "Expected tag 'n', got {}",
+                tag
+            )));
+        }
+
+        if buf.remaining() < 2 + 4 + 4 {
+            return Err(Error::InvalidHandshakeMessage(
+                "Insufficient data for version, flags and challenge".to_string(),
+            ));
+        }
+
+        let version = buf.get_u16();
+        if version != PROTOCOL_VERSION_5 {
+            return Err(Error::IncompatibleVersion {
+                got: version,
+                expected: PROTOCOL_VERSION_5,
+            });
+        }
+        let flags = DistributionFlags::new(u64::from(buf.get_u32()));
+        let challenge = buf.get_u32();
+
+        let name = str::from_utf8(buf)
+            .map_err(|_| Error::InvalidHandshakeMessage("Invalid UTF-8 in node name".to_string()))?
+            .to_owned();
+
+        Ok(Self {
+            flags,
+            challenge,
+            creation: 0,
+            name,
+        })
+    }
+}
+
 /// Challenge Reply message (tag: 'r')
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ChallengeReply {
@@ -354,7 +512,7 @@ impl ChallengeReply {
     pub fn encode(&self) -> Vec<u8> {
         let mut buf = BytesMut::new();
         buf.put_u16(21);
-        buf.put_u8(b'r');
+        buf.put_u8(CHALLENGE_REPLY_TAG);
         buf.put_u32(self.challenge);
         buf.put_slice(&self.digest);
         buf.to_vec()
@@ -370,7 +528,7 @@ impl ChallengeReply {
         }
 
         let tag = buf.get_u8();
-        if tag != b'r' {
+        if tag != CHALLENGE_REPLY_TAG {
             return Err(Error::InvalidHandshakeMessage(format!(
                 "Expected tag 'r', got {}",
                 tag
@@ -411,7 +569,7 @@ impl ChallengeAck {
     pub fn encode(&self) -> Vec<u8> {
         let mut buf = BytesMut::new();
         buf.put_u16(17);
-        buf.put_u8(HANDSHAKE_TAG_A);
+        buf.put_u8(CHALLENGE_ACK_TAG);
         buf.put_slice(&self.digest);
         buf.to_vec()
     }
@@ -426,7 +584,7 @@ impl ChallengeAck {
         }
 
         let tag = buf.get_u8();
-        if tag != HANDSHAKE_TAG_A {
+        if tag != CHALLENGE_ACK_TAG {
             return Err(Error::InvalidHandshakeMessage(format!(
                 "Expected tag 'a', got {}",
                 tag
@@ -0,0 +1,152 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Happy Eyeballs (RFC 8305) connection establishment.
+//!
+//! When a host resolves to several addresses, connection attempts are started one
+//! after another with a short delay between them, without waiting for earlier attempts
+//! to fail, and the first one to succeed wins. This keeps connect latency low on
+//! dual-stack and multi-homed hosts where some addresses are unreachable.
+
+use crate::errors::{Error, Result};
+use crate::resolver::{Resolver, SystemResolver, resolve_addresses};
+use std::future::{Future, poll_fn};
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::time::{Instant, sleep};
+
+/// The delay between connection attempts recommended by RFC 8305
+pub const DEFAULT_CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+type Attempt = Pin<Box<dyn Future<Output = (SocketAddr, io::Result<TcpStream>)> + Send>>;
+
+enum Event {
+    Finished(usize, SocketAddr, io::Result<TcpStream>),
+    DelayElapsed,
+}
+
+/// Resolves `host` and connects to the first address that accepts, returning the
+/// stream and the address that won.
+pub async fn connect(
+    host: &str,
+    port: u16,
+    attempt_delay: Duration,
+) -> Result<(TcpStream, SocketAddr)> {
+    connect_with_resolver(&SystemResolver, host, port, attempt_delay).await
+}
+
+/// Like [`connect`] but resolves `host` with `resolver`.
+pub async fn connect_with_resolver(
+    resolver: &dyn Resolver,
+    host: &str,
+    port: u16,
+    attempt_delay: Duration,
+) -> Result<(TcpStream, SocketAddr)> {
+    let addrs = resolve_addresses(resolver, host, port).await?;
+    connect_to_addresses(interleave_addresses(addrs), attempt_delay).await
+}
+
+/// Races connection attempts to `addrs` in order, starting a new attempt every
+/// `attempt_delay` or as soon as the previous one fails.
+pub async fn connect_to_addresses(
+    addrs: Vec<SocketAddr>,
+    attempt_delay: Duration,
+) -> Result<(TcpStream, SocketAddr)> {
+    let mut remaining = addrs.into_iter();
+    let mut attempts: Vec<Attempt> = Vec::new();
+    let mut last_error = None;
+    let delay = sleep(attempt_delay);
+    tokio::pin!(delay);
+
+    loop {
+        if attempts.is_empty() {
+            match remaining.next() {
+                Some(addr) => {
+                    attempts.push(start_attempt(addr));
+                    delay.as_mut().reset(Instant::now() + attempt_delay);
+                }
+                None => {
+                    return Err(Error::Io(last_error.unwrap_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidInput, "no addresses to connect to")
+                    })));
+                }
+            }
+        }
+
+        let has_more = remaining.len() > 0;
+        let event = poll_fn(|cx| {
+            for (i, attempt) in attempts.iter_mut().enumerate() {
+                if let Poll::Ready((addr, result)) = attempt.as_mut().poll(cx) {
+                    return Poll::Ready(Event::Finished(i, addr, result));
+                }
+            }
+            if has_more && delay.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(Event::DelayElapsed);
+            }
+            Poll::Pending
+        })
+        .await;
+
+        match event {
+            // Returning drops and thereby cancels the other attempts
+            Event::Finished(_, addr, Ok(stream)) => return Ok((stream, addr)),
+            Event::Finished(i, addr, Err(e)) => {
+                tracing::debug!("Connection attempt to {} failed: {}", addr, e);
+                drop(attempts.swap_remove(i));
+                last_error = Some(e);
+                if let Some(next) = remaining.next() {
+                    attempts.push(start_attempt(next));
+                    delay.as_mut().reset(Instant::now() + attempt_delay);
+                }
+            }
+            Event::DelayElapsed => {
+                if let Some(next) = remaining.next() {
+                    attempts.push(start_attempt(next));
+                }
+                delay.as_mut().reset(Instant::now() + attempt_delay);
+            }
+        }
+    }
+}
+
+/// Orders addresses by alternating between address families, starting with the
+/// family of the first (most preferred) address, as described in RFC 8305 section 4.
+pub fn interleave_addresses(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let Some(first) = addrs.first() else {
+        return addrs;
+    };
+    let preferred_v6 = first.is_ipv6();
+    let (preferred, other): (Vec<_>, Vec<_>) = addrs
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == preferred_v6);
+    let mut ordered = Vec::with_capacity(preferred.len() + other.len());
+    let mut preferred = preferred.into_iter();
+    let mut other = other.into_iter();
+    loop {
+        match (preferred.next(), other.next()) {
+            (None, None) => break,
+            (a, b) => ordered.extend(a.into_iter().chain(b)),
+        }
+    }
+    ordered
+}
+
+fn start_attempt(addr: SocketAddr) -> Attempt {
+    tracing::debug!("Starting connection attempt to {}", addr);
+    Box::pin(async move { (addr, TcpStream::connect(addr).await) })
+}
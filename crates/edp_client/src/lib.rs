@@ -21,6 +21,7 @@
 //! # Features
 //!
 //! - Full protocol version 6 (OTP 23+) support
+//! - Protocol version 5 handshake for older peers, behind the `legacy-handshake` feature
 //! - Mandatory OTP 26+ capability flags
 //! - EPMD (Erlang Port Mapper Daemon) client
 //! - Async I/O using Tokio
@@ -33,8 +34,14 @@
 //! - Isolate distribution traffic on dedicated networks
 //! - Do not expose EPMD or distribution ports publicly
 
+pub mod analysis;
+pub mod codec;
+#[cfg(feature = "zstd")]
+pub mod compression;
 pub mod connection;
 pub mod control;
+pub mod decode_budget;
+pub mod decode_offload;
 pub mod digest;
 pub mod epmd_client;
 pub mod errors;
@@ -42,17 +49,45 @@ pub mod flags;
 pub mod fragmentation;
 pub mod framing;
 pub mod handshake;
+pub mod happy_eyeballs;
+pub mod metrics;
 pub mod pid_allocator;
+pub mod protocol;
+pub mod resolver;
+pub mod socket_options;
 pub mod state_machine;
 pub mod term_helpers;
+#[cfg(feature = "test-support")]
+pub mod test_peer;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 pub mod transport;
 pub mod types;
+pub mod unlinking;
 
-pub use connection::{Connection, ConnectionConfig};
+pub use codec::{Codec, EncodeContext, EtfCodec};
+#[cfg(feature = "zstd")]
+pub use compression::ZstdCompression;
+pub use connection::{
+    Connection, ConnectionConfig, DecodeErrorPolicy, LARGE_EXIT_REASON_SIZE, ReceiveOptions,
+    ReceivedMessage, SendOpts, SendOutcome, StalePidPolicy,
+};
+pub use decode_budget::{DecodeAccounting, DecodeBudget};
+pub use decode_offload::{DecodeOffload, DecodePipeline};
 pub use errors::{Error, Result};
-pub use flags::DistributionFlags;
-pub use pid_allocator::PidAllocator;
-pub use state_machine::ConnectionState;
+pub use flags::{CompatibilityReport, DistributionFlags, FlagsDiff};
+pub use framing::FrameMode;
+pub use metrics::{
+    ConnectionMetrics, MessageDirection, RttCallback, RttSummary, SizeHistogram, TopTalker,
+};
+pub use pid_allocator::{PidAllocator, PidAllocatorState, PidRange};
+#[cfg(feature = "hickory")]
+pub use resolver::HickoryResolver;
+pub use resolver::{CachingResolver, Resolver, StaticResolver, SystemResolver};
+pub use socket_options::{SocketOptions, TcpKeepalive};
+pub use state_machine::{AliveCallback, ConnectionState};
 pub use term_helpers::nil;
 pub use tokio::net::tcp::OwnedReadHalf;
-pub use types::{Creation, SequenceId};
+pub use transport::FramedTransport;
+pub use types::{ChannelKey, ConnectionId, Creation, SequenceId, SequenceIdAllocator};
+pub use unlinking::UnlinkTracker;
@@ -0,0 +1,505 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-connection metrics: round-trip times, tick counters and message sizes.
+//!
+//! Distribution ticks are one-way (the peer does not echo them), so RTT samples
+//! come from explicit pings, see [`ConnectionMetrics::record_rtt`]. Ticks are
+//! counted, which tells a quiet link from a dead one.
+//!
+//! Message sizes go into a [`SizeHistogram`] per direction, and the bytes of each
+//! control message type and registered name into a bounded top-K table, see
+//! [`ConnectionMetrics::top_talkers`]. Together they show which messages dominate
+//! the bandwidth of a link.
+
+use crate::control::{ControlMessage, ControlMessageType};
+use erltf::types::Atom;
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// How many RTT samples are kept for percentile calculations by default
+pub const DEFAULT_RTT_WINDOW: usize = 128;
+
+/// How many (control message type, registered name) pairs are tracked per direction by default
+pub const DEFAULT_TOP_TALKERS: usize = 32;
+
+/// Upper bounds of the [`SizeHistogram`] buckets: powers of two from 64 bytes to 64 MiB.
+/// Larger messages fall into a final, unbounded bucket.
+pub const SIZE_BUCKET_BOUNDS: [usize; 21] = {
+    let mut bounds = [0; 21];
+    let mut i = 0;
+    while i < bounds.len() {
+        bounds[i] = 64 << i;
+        i += 1;
+    }
+    bounds
+};
+
+/// Invoked with the sample when a recorded RTT exceeds the configured threshold
+pub type RttCallback = Arc<dyn Fn(Duration) + Send + Sync>;
+
+/// A snapshot of the RTT samples currently in the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RttSummary {
+    pub samples: usize,
+    pub min: Duration,
+    pub max: Duration,
+    pub last: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+}
+
+/// Whether a message was received from or sent to the peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageDirection {
+    Inbound,
+    Outbound,
+}
+
+/// Message counts by encoded size, with fixed buckets so that it never grows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeHistogram {
+    counts: [u64; SIZE_BUCKET_BOUNDS.len() + 1],
+    messages: u64,
+    bytes: u64,
+    max: usize,
+}
+
+impl Default for SizeHistogram {
+    fn default() -> Self {
+        Self {
+            counts: [0; SIZE_BUCKET_BOUNDS.len() + 1],
+            messages: 0,
+            bytes: 0,
+            max: 0,
+        }
+    }
+}
+
+impl SizeHistogram {
+    pub fn record(&mut self, size: usize) {
+        let bucket = SIZE_BUCKET_BOUNDS.partition_point(|bound| *bound < size);
+        self.counts[bucket] += 1;
+        self.messages += 1;
+        self.bytes += size as u64;
+        self.max = self.max.max(size);
+    }
+
+    pub fn messages(&self) -> u64 {
+        self.messages
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// The largest recorded size, 0 when nothing was recorded
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.messages > 0).then(|| self.bytes as f64 / self.messages as f64)
+    }
+
+    /// Pairs of bucket upper bound (`None` for the last bucket) and message count,
+    /// smallest sizes first.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<usize>, u64)> + '_ {
+        SIZE_BUCKET_BOUNDS
+            .iter()
+            .map(|bound| Some(*bound))
+            .chain([None])
+            .zip(self.counts.iter().copied())
+    }
+
+    /// The upper bound of the bucket that holds the nearest-rank percentile,
+    /// `p` is clamped to `0.0..=100.0`. Sizes beyond the last bound report the largest size seen.
+    pub fn percentile(&self, p: f64) -> Option<usize> {
+        if self.messages == 0 {
+            return None;
+        }
+        let p = p.clamp(0.0, 100.0);
+        let rank = (((p / 100.0) * self.messages as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bound, count) in self.buckets() {
+            seen += count;
+            if seen >= rank {
+                return Some(bound.map_or(self.max, |bound| bound.min(self.max)));
+            }
+        }
+        Some(self.max)
+    }
+}
+
+/// Traffic of one (control message type, registered name) pair, see [`ConnectionMetrics::top_talkers`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopTalker {
+    /// `None` for control messages of an unknown type
+    pub message_type: Option<ControlMessageType>,
+    /// The registered name of `REG_SEND` messages, `None` for other messages
+    pub target: Option<Atom>,
+    pub messages: u64,
+    pub bytes: u64,
+    /// How many of `bytes` may belong to pairs this one replaced in the table
+    pub overcount: u64,
+}
+
+type TalkerKey = (Option<ControlMessageType>, Option<Atom>);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct TalkerCounts {
+    messages: u64,
+    bytes: u64,
+    overcount: u64,
+}
+
+/// Bytes per (control message type, registered name) with the space-saving
+/// algorithm: when the table is full, a new pair replaces the one with the
+/// fewest bytes and inherits its counts, so heavy pairs are never evicted.
+#[derive(Debug)]
+struct TopTalkers {
+    capacity: usize,
+    counts: HashMap<TalkerKey, TalkerCounts>,
+}
+
+impl TopTalkers {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            counts: HashMap::new(),
+        }
+    }
+
+    fn record(&mut self, key: TalkerKey, size: usize) {
+        if self.capacity == 0 {
+            return;
+        }
+        let size = size as u64;
+        if let Some(counts) = self.counts.get_mut(&key) {
+            counts.messages += 1;
+            counts.bytes += size;
+            return;
+        }
+
+        let mut counts = TalkerCounts::default();
+        if self.counts.len() >= self.capacity
+            && let Some(evicted) = self
+                .counts
+                .iter()
+                .min_by_key(|(_, counts)| counts.bytes)
+                .map(|(key, _)| key.clone())
+            && let Some(inherited) = self.counts.remove(&evicted)
+        {
+            counts = TalkerCounts {
+                overcount: inherited.bytes,
+                ..inherited
+            };
+        }
+        counts.messages += 1;
+        counts.bytes += size;
+        self.counts.insert(key, counts);
+    }
+
+    fn top(&self, limit: usize) -> Vec<TopTalker> {
+        let mut talkers: Vec<TopTalker> = self
+            .counts
+            .iter()
+            .map(|((message_type, target), counts)| TopTalker {
+                message_type: *message_type,
+                target: target.clone(),
+                messages: counts.messages,
+                bytes: counts.bytes,
+                overcount: counts.overcount,
+            })
+            .collect();
+        talkers.sort_by_key(|talker| Reverse(talker.bytes));
+        talkers.truncate(limit);
+        talkers
+    }
+}
+
+/// Message sizes and top talkers of one direction
+#[derive(Debug)]
+struct TrafficStats {
+    sizes: SizeHistogram,
+    talkers: TopTalkers,
+}
+
+impl TrafficStats {
+    fn new(top_talkers: usize) -> Self {
+        Self {
+            sizes: SizeHistogram::default(),
+            talkers: TopTalkers::new(top_talkers),
+        }
+    }
+}
+
+struct RttAlert {
+    threshold: Duration,
+    callback: RttCallback,
+}
+
+struct MetricsState {
+    window: usize,
+    samples: VecDeque<Duration>,
+    alert: Option<RttAlert>,
+    ticks_sent: u64,
+    ticks_received: u64,
+    last_received: Option<Instant>,
+    last_message_received: Option<Instant>,
+    inbound: TrafficStats,
+    outbound: TrafficStats,
+}
+
+impl MetricsState {
+    fn traffic(&mut self, direction: MessageDirection) -> &mut TrafficStats {
+        match direction {
+            MessageDirection::Inbound => &mut self.inbound,
+            MessageDirection::Outbound => &mut self.outbound,
+        }
+    }
+}
+
+/// Rolling RTT samples, tick counters and message sizes for one connection.
+///
+/// Clones share the same state, so a clone can be handed to the task
+/// that reads from the connection.
+#[derive(Clone)]
+pub struct ConnectionMetrics {
+    state: Arc<Mutex<MetricsState>>,
+}
+
+impl ConnectionMetrics {
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            state: Arc::new(Mutex::new(MetricsState {
+                window,
+                samples: VecDeque::with_capacity(window),
+                alert: None,
+                ticks_sent: 0,
+                ticks_received: 0,
+                last_received: None,
+                last_message_received: None,
+                inbound: TrafficStats::new(DEFAULT_TOP_TALKERS),
+                outbound: TrafficStats::new(DEFAULT_TOP_TALKERS),
+            })),
+        }
+    }
+
+    /// Tracks up to `capacity` (control message type, registered name) pairs per direction,
+    /// 0 disables the tracking. Pairs recorded so far are dropped.
+    pub fn with_top_talkers(self, capacity: usize) -> Self {
+        {
+            let mut state = self.lock();
+            state.inbound.talkers = TopTalkers::new(capacity);
+            state.outbound.talkers = TopTalkers::new(capacity);
+        }
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MetricsState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Invokes `callback` for every recorded RTT above `threshold`.
+    pub fn set_rtt_alert(&self, threshold: Duration, callback: RttCallback) {
+        self.lock().alert = Some(RttAlert {
+            threshold,
+            callback,
+        });
+    }
+
+    pub fn clear_rtt_alert(&self) {
+        self.lock().alert = None;
+    }
+
+    /// Adds a sample, evicting the oldest one when the window is full.
+    pub fn record_rtt(&self, rtt: Duration) {
+        let callback = {
+            let mut state = self.lock();
+            if state.samples.len() == state.window {
+                state.samples.pop_front();
+            }
+            state.samples.push_back(rtt);
+            state
+                .alert
+                .as_ref()
+                .filter(|alert| rtt > alert.threshold)
+                .map(|alert| Arc::clone(&alert.callback))
+        };
+        // Called outside of the lock so that the callback can read the metrics
+        if let Some(callback) = callback {
+            callback(rtt);
+        }
+    }
+
+    pub fn rtt_samples(&self) -> usize {
+        self.lock().samples.len()
+    }
+
+    pub fn last_rtt(&self) -> Option<Duration> {
+        self.lock().samples.back().copied()
+    }
+
+    /// The nearest-rank percentile of the samples in the window, `p` is clamped to `0.0..=100.0`.
+    pub fn rtt_percentile(&self, p: f64) -> Option<Duration> {
+        let mut sorted: Vec<Duration> = self.lock().samples.iter().copied().collect();
+        sorted.sort_unstable();
+        percentile(&sorted, p)
+    }
+
+    pub fn rtt_summary(&self) -> Option<RttSummary> {
+        let (mut sorted, last) = {
+            let state = self.lock();
+            let last = *state.samples.back()?;
+            (state.samples.iter().copied().collect::<Vec<_>>(), last)
+        };
+        sorted.sort_unstable();
+        Some(RttSummary {
+            samples: sorted.len(),
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            last,
+            p50: percentile(&sorted, 50.0)?,
+            p90: percentile(&sorted, 90.0)?,
+            p99: percentile(&sorted, 99.0)?,
+        })
+    }
+
+    pub fn record_tick_sent(&self) {
+        self.lock().ticks_sent += 1;
+    }
+
+    pub fn record_tick_received(&self) {
+        let mut state = self.lock();
+        state.ticks_received += 1;
+        state.last_received = Some(Instant::now());
+    }
+
+    /// Records that a non-tick frame arrived.
+    pub fn record_received(&self) {
+        let mut state = self.lock();
+        let now = Instant::now();
+        state.last_received = Some(now);
+        state.last_message_received = Some(now);
+    }
+
+    pub fn ticks_sent(&self) -> u64 {
+        self.lock().ticks_sent
+    }
+
+    pub fn ticks_received(&self) -> u64 {
+        self.lock().ticks_received
+    }
+
+    /// When the last frame, tick or otherwise, arrived from the peer
+    pub fn last_received(&self) -> Option<Instant> {
+        self.lock().last_received
+    }
+
+    pub fn since_last_received(&self) -> Option<Duration> {
+        self.last_received().map(|at| at.elapsed())
+    }
+
+    /// When the last frame other than a tick arrived from the peer
+    pub fn last_message_received(&self) -> Option<Instant> {
+        self.lock().last_message_received
+    }
+
+    /// Records the encoded size of a message, without the 4-byte length prefix
+    /// and before compression.
+    pub fn record_message(
+        &self,
+        direction: MessageDirection,
+        message_type: Option<ControlMessageType>,
+        target: Option<&Atom>,
+        size: usize,
+    ) {
+        let mut state = self.lock();
+        let traffic = state.traffic(direction);
+        traffic.sizes.record(size);
+        traffic
+            .talkers
+            .record((message_type, target.cloned()), size);
+    }
+
+    pub(crate) fn record_control_message(
+        &self,
+        direction: MessageDirection,
+        control: &ControlMessage,
+        size: usize,
+    ) {
+        self.record_message(
+            direction,
+            control.message_type(),
+            control.registered_name(),
+            size,
+        );
+    }
+
+    pub fn message_sizes(&self, direction: MessageDirection) -> SizeHistogram {
+        self.lock().traffic(direction).sizes.clone()
+    }
+
+    /// Up to `limit` (control message type, registered name) pairs with the most bytes, most first.
+    pub fn top_talkers(&self, direction: MessageDirection, limit: usize) -> Vec<TopTalker> {
+        self.lock().traffic(direction).talkers.top(limit)
+    }
+
+    /// Clears the message size histograms and top talkers of both directions.
+    pub fn reset_message_stats(&self) {
+        let mut state = self.lock();
+        for direction in [MessageDirection::Inbound, MessageDirection::Outbound] {
+            let traffic = state.traffic(direction);
+            traffic.sizes = SizeHistogram::default();
+            traffic.talkers.counts.clear();
+        }
+    }
+}
+
+impl Default for ConnectionMetrics {
+    fn default() -> Self {
+        Self::new(DEFAULT_RTT_WINDOW)
+    }
+}
+
+impl fmt::Debug for ConnectionMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("ConnectionMetrics")
+            .field("window", &state.window)
+            .field("rtt_samples", &state.samples.len())
+            .field("ticks_sent", &state.ticks_sent)
+            .field("ticks_received", &state.ticks_received)
+            .field("last_received", &state.last_received)
+            .field("last_message_received", &state.last_message_received)
+            .field("messages_received", &state.inbound.sizes.messages())
+            .field("messages_sent", &state.outbound.sizes.messages())
+            .finish()
+    }
+}
+
+fn percentile(sorted: &[Duration], p: f64) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let p = p.clamp(0.0, 100.0);
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
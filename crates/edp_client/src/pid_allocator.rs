@@ -17,11 +17,20 @@
 use crate::errors::{Error, Result};
 use crate::types::Creation;
 use erltf::types::{Atom, ExternalPid};
-use std::sync::Mutex;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
 
 const MAX_PROCESSES_PER_NODE: u32 = 1_048_576;
 
+/// Allocates pids for local processes. Share it between tasks behind an `Arc`.
+///
+/// Ids run from 1 to 1,048,576. When they run out, ids start over from 1
+/// with the next serial, so a pid is not reused until the serial wraps around.
 #[derive(Debug)]
 pub struct PidAllocator {
     node_name: Atom,
@@ -42,10 +51,19 @@ impl PidAllocator {
         }
     }
 
+    /// Like [`PidAllocator::new`] but continues from `state`, see [`PidAllocator::restore`].
+    pub fn with_state<C: Into<Creation>>(
+        node_name: Atom,
+        creation: C,
+        state: PidAllocatorState,
+    ) -> Self {
+        let allocator = Self::new(node_name, creation);
+        allocator.restore(state);
+        allocator
+    }
+
     pub fn allocate(&self) -> Result<ExternalPid> {
-        let _guard = self.wrap_lock.lock().map_err(|e| {
-            Error::InvalidStateMessage(format!("PID allocator lock poisoned: {}", e))
-        })?;
+        let _guard = self.lock()?;
 
         let id = self.next_id.load(Ordering::Relaxed);
         let serial_u64 = self.next_serial.load(Ordering::Relaxed);
@@ -75,6 +93,86 @@ impl PidAllocator {
         }
     }
 
+    /// Reserves `count` consecutive ids with the same serial, e.g. for a subsystem
+    /// that allocates its own pids. [`PidAllocator::allocate`] never returns them.
+    ///
+    /// Starts over from id 1 with the next serial when fewer than `count` ids are left.
+    pub fn reserve(&self, count: u32) -> Result<PidRange> {
+        if count == 0 || count > MAX_PROCESSES_PER_NODE {
+            return Err(Error::InvalidStateMessage(format!(
+                "cannot reserve {} pids, the range must hold 1 to {} pids",
+                count, MAX_PROCESSES_PER_NODE
+            )));
+        }
+        let _guard = self.lock()?;
+
+        let mut first_id = self.next_id.load(Ordering::Relaxed);
+        if first_id > MAX_PROCESSES_PER_NODE - count + 1 {
+            first_id = 1;
+            self.next_serial.fetch_add(1, Ordering::Relaxed);
+        }
+        let serial = (self.next_serial.load(Ordering::Relaxed) % (u32::MAX as u64 + 1)) as u32;
+        let last_id = first_id + (count - 1);
+
+        if last_id == MAX_PROCESSES_PER_NODE {
+            self.next_id.store(1, Ordering::Relaxed);
+            self.next_serial.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.next_id.store(last_id + 1, Ordering::Relaxed);
+        }
+
+        Ok(PidRange {
+            node_name: self.node_name.clone(),
+            creation: self.creation.load(Ordering::Relaxed),
+            serial,
+            first_id,
+            last_id,
+            next_id: AtomicU32::new(first_id),
+        })
+    }
+
+    /// The counters to persist so that a restarted node does not reuse pids,
+    /// see [`PidAllocator::restore`].
+    pub fn snapshot(&self) -> Result<PidAllocatorState> {
+        let _guard = self.lock()?;
+        Ok(PidAllocatorState {
+            creation: self.creation.load(Ordering::Relaxed),
+            next_id: self.next_id.load(Ordering::Relaxed),
+            next_serial: self.next_serial.load(Ordering::Relaxed),
+        })
+    }
+
+    /// Continues allocating after the pids recorded in `state`.
+    ///
+    /// Pids of a different creation cannot clash with the current ones, so `state` is
+    /// ignored when the creation has been bumped since it was taken. Never moves the
+    /// counters backwards. Returns whether `state` was applied.
+    pub fn restore(&self, state: PidAllocatorState) -> bool {
+        let Ok(_guard) = self.lock() else {
+            return false;
+        };
+        if state.creation != self.creation.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        let current = (
+            self.next_serial.load(Ordering::Relaxed),
+            self.next_id.load(Ordering::Relaxed),
+        );
+        let restored = (state.next_serial, state.next_id.max(1));
+        if restored > current {
+            self.next_serial.store(restored.0, Ordering::Relaxed);
+            self.next_id.store(restored.1, Ordering::Relaxed);
+        }
+        true
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, ()>> {
+        self.wrap_lock
+            .lock()
+            .map_err(|e| Error::InvalidStateMessage(format!("PID allocator lock poisoned: {}", e)))
+    }
+
     pub fn node_name(&self) -> &Atom {
         &self.node_name
     }
@@ -105,3 +203,121 @@ impl PidAllocator {
         &self.next_serial
     }
 }
+
+/// A block of consecutive pid ids, see [`PidAllocator::reserve`].
+#[derive(Debug)]
+pub struct PidRange {
+    node_name: Atom,
+    creation: u32,
+    serial: u32,
+    first_id: u32,
+    last_id: u32,
+    next_id: AtomicU32,
+}
+
+impl PidRange {
+    /// The next pid of the range, `None` once all of them have been allocated.
+    pub fn allocate(&self) -> Option<ExternalPid> {
+        let id = self
+            .next_id
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| {
+                (id <= self.last_id).then_some(id + 1)
+            })
+            .ok()?;
+        Some(ExternalPid::new(
+            self.node_name.clone(),
+            id,
+            self.serial,
+            self.creation,
+        ))
+    }
+
+    pub fn contains(&self, pid: &ExternalPid) -> bool {
+        pid.node == self.node_name
+            && pid.creation == self.creation
+            && pid.serial == self.serial
+            && (self.first_id..=self.last_id).contains(&pid.id)
+    }
+
+    pub fn first_id(&self) -> u32 {
+        self.first_id
+    }
+
+    pub fn last_id(&self) -> u32 {
+        self.last_id
+    }
+
+    pub fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    pub fn len(&self) -> u32 {
+        self.last_id - self.first_id + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// How many pids of the range have not been allocated yet.
+    pub fn remaining(&self) -> u32 {
+        (self.last_id + 1).saturating_sub(self.next_id.load(Ordering::Relaxed))
+    }
+}
+
+/// The counters of a [`PidAllocator`], persisted as `creation next_id next_serial`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PidAllocatorState {
+    pub creation: u32,
+    pub next_id: u32,
+    pub next_serial: u64,
+}
+
+impl PidAllocatorState {
+    /// Reads a state written by [`PidAllocatorState::save`], `None` if `path` does not exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => contents.parse().map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the state to a temporary file next to `path`, then renames it into place.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, format!("{}\n", self))?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for PidAllocatorState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.creation, self.next_id, self.next_serial)
+    }
+}
+
+impl FromStr for PidAllocatorState {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid =
+            || Error::InvalidStateMessage(format!("invalid PID allocator state: {:?}", s));
+        let mut fields = s.split_whitespace();
+        let mut next = || fields.next().ok_or_else(invalid);
+        let creation = next()?.parse().map_err(|_| invalid())?;
+        let next_id = next()?.parse().map_err(|_| invalid())?;
+        let next_serial = next()?.parse().map_err(|_| invalid())?;
+        if fields.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            creation,
+            next_id,
+            next_serial,
+        })
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Distribution protocol constants: handshake message tags, frame tags and EPMD request codes.
+//!
+//! This module is the single source of these values for the rest of the crate and for tools
+//! that inspect or proxy distribution traffic. It only defines constants and `const fn`s.
+//! Control message codes are [`ControlMessageType`], capability flags are [`DistributionFlags`].
+//!
+//! See: <https://www.erlang.org/doc/apps/erts/erl_dist_protocol>
+
+pub use crate::control::ControlMessageType;
+pub use crate::flags::DistributionFlags;
+pub use erltf::tags::{DIST_FRAG_CONT, DIST_FRAG_HEADER, DIST_HEADER, VERSION};
+
+/// Handshake protocol version (version 6 was introduced in Erlang/OTP 23.0)
+pub const PROTOCOL_VERSION: u16 = 6;
+
+/// Old protocol version 5 (for compatibility)
+pub const PROTOCOL_VERSION_5: u16 = 5;
+
+// Handshake message tags
+
+/// `send_name` and `challenge` in protocol version 6
+pub const NAME_TAG: u8 = b'N';
+/// `send_name` and `challenge` in protocol version 5
+pub const NAME_TAG_V5: u8 = b'n';
+pub const STATUS_TAG: u8 = b's';
+pub const COMPLEMENT_TAG: u8 = b'c';
+pub const CHALLENGE_REPLY_TAG: u8 = b'r';
+pub const CHALLENGE_ACK_TAG: u8 = b'a';
+
+/// First byte of a frame without a distribution header, followed by the control message
+pub const PASS_THROUGH: u8 = 112;
+
+// EPMD
+
+/// Default EPMD port
+pub const EPMD_PORT: u16 = 4369;
+
+pub const EPMD_ALIVE2_REQ: u8 = 120;
+pub const EPMD_ALIVE2_RESP: u8 = 121;
+pub const EPMD_ALIVE2_X_RESP: u8 = 118;
+pub const EPMD_PORT2_REQ: u8 = 122;
+pub const EPMD_PORT2_RESP: u8 = 119;
+pub const EPMD_NAMES_REQ: u8 = 110;
+pub const EPMD_DUMP_REQ: u8 = 100;
+pub const EPMD_KILL_REQ: u8 = 107;
+
+/// Returns the name of a handshake message tag, e.g. `"CHALLENGE_REPLY"` for `b'r'`.
+pub const fn handshake_tag_name(tag: u8) -> Option<&'static str> {
+    match tag {
+        NAME_TAG => Some("SEND_NAME/CHALLENGE"),
+        NAME_TAG_V5 => Some("SEND_NAME/CHALLENGE (v5)"),
+        STATUS_TAG => Some("STATUS"),
+        COMPLEMENT_TAG => Some("COMPLEMENT"),
+        CHALLENGE_REPLY_TAG => Some("CHALLENGE_REPLY"),
+        CHALLENGE_ACK_TAG => Some("CHALLENGE_ACK"),
+        _ => None,
+    }
+}
+
+/// Returns the name of the tag that follows the version byte of a frame,
+/// e.g. `"DIST_FRAG_CONT"` for 70.
+pub const fn frame_tag_name(tag: u8) -> Option<&'static str> {
+    match tag {
+        DIST_HEADER => Some("DIST_HEADER"),
+        DIST_FRAG_HEADER => Some("DIST_FRAG_HEADER"),
+        DIST_FRAG_CONT => Some("DIST_FRAG_CONT"),
+        _ => None,
+    }
+}
+
+/// Returns the name of an EPMD request or response code, e.g. `"PORT2_REQ"` for 122.
+pub const fn epmd_code_name(code: u8) -> Option<&'static str> {
+    match code {
+        EPMD_ALIVE2_REQ => Some("ALIVE2_REQ"),
+        EPMD_ALIVE2_RESP => Some("ALIVE2_RESP"),
+        EPMD_ALIVE2_X_RESP => Some("ALIVE2_X_RESP"),
+        EPMD_PORT2_REQ => Some("PORT2_REQ"),
+        EPMD_PORT2_RESP => Some("PORT2_RESP"),
+        EPMD_NAMES_REQ => Some("NAMES_REQ"),
+        EPMD_DUMP_REQ => Some("DUMP_REQ"),
+        EPMD_KILL_REQ => Some("KILL_REQ"),
+        _ => None,
+    }
+}
+
+/// Returns the name of a control message code, e.g. `"REG_SEND"` for 6.
+pub fn control_message_name(code: u8) -> Option<&'static str> {
+    ControlMessageType::try_from(code)
+        .ok()
+        .map(ControlMessageType::name)
+}
@@ -0,0 +1,229 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Host name resolution for EPMD and distribution connections.
+//!
+//! [`EpmdClient`](crate::epmd_client::EpmdClient) and [`Connection`](crate::Connection)
+//! resolve host names with a [`Resolver`], [`SystemResolver`] by default. A custom
+//! implementation can consult a service discovery system (Consul, Kubernetes DNS),
+//! cache answers or record lookups.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::{self, Future};
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::net::lookup_host;
+use tokio::time::Instant;
+
+#[cfg(feature = "hickory")]
+use hickory_resolver::TokioResolver;
+
+/// The future returned by [`Resolver::resolve`]
+pub type ResolveFuture<'a> = Pin<Box<dyn Future<Output = io::Result<Vec<SocketAddr>>> + Send + 'a>>;
+
+type AddressCache = HashMap<(String, u16), (Vec<SocketAddr>, Instant)>;
+
+/// Resolves a host name and port to socket addresses.
+///
+/// Addresses are returned in order of preference.
+pub trait Resolver: fmt::Debug + Send + Sync {
+    fn resolve<'a>(&'a self, host: &'a str, port: u16) -> ResolveFuture<'a>;
+}
+
+impl<R: Resolver + ?Sized> Resolver for Arc<R> {
+    fn resolve<'a>(&'a self, host: &'a str, port: u16) -> ResolveFuture<'a> {
+        (**self).resolve(host, port)
+    }
+}
+
+/// Returns the resolver used when none is configured.
+pub fn default_resolver() -> Arc<dyn Resolver> {
+    Arc::new(SystemResolver)
+}
+
+/// Resolves host names with the operating system's resolver.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemResolver;
+
+impl Resolver for SystemResolver {
+    fn resolve<'a>(&'a self, host: &'a str, port: u16) -> ResolveFuture<'a> {
+        Box::pin(async move { Ok(lookup_host((host, port)).await?.collect()) })
+    }
+}
+
+/// Resolves host names from a fixed table, e.g. one populated from a service
+/// discovery system. IP address literals resolve to themselves.
+#[derive(Debug, Clone, Default)]
+pub struct StaticResolver {
+    hosts: HashMap<String, Vec<IpAddr>>,
+}
+
+impl StaticResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_host(
+        mut self,
+        host: impl Into<String>,
+        addrs: impl IntoIterator<Item = IpAddr>,
+    ) -> Self {
+        self.hosts.insert(host.into(), addrs.into_iter().collect());
+        self
+    }
+}
+
+impl Resolver for StaticResolver {
+    fn resolve<'a>(&'a self, host: &'a str, port: u16) -> ResolveFuture<'a> {
+        let result = match (self.hosts.get(host), host.parse::<IpAddr>()) {
+            (Some(addrs), _) => Ok(addrs.iter().map(|ip| SocketAddr::new(*ip, port)).collect()),
+            (None, Ok(ip)) => Ok(vec![SocketAddr::new(ip, port)]),
+            (None, Err(_)) => Err(not_found(host)),
+        };
+        Box::pin(future::ready(result))
+    }
+}
+
+/// Caches the addresses another resolver returns for `ttl`.
+///
+/// Failed lookups are not cached. Clones share the cache.
+#[derive(Debug, Clone)]
+pub struct CachingResolver<R> {
+    inner: R,
+    ttl: Duration,
+    cache: Arc<Mutex<AddressCache>>,
+}
+
+impl<R: Resolver> CachingResolver<R> {
+    pub fn new(inner: R, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Drops the cached addresses of a host, if any.
+    pub fn invalidate(&self, host: &str) {
+        self.lock_cache()
+            .retain(|(cached_host, _), _| cached_host != host);
+    }
+
+    pub fn clear_cache(&self) {
+        self.lock_cache().clear();
+    }
+
+    fn lock_cache(&self) -> MutexGuard<'_, AddressCache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn cached(&self, host: &str, port: u16) -> Option<Vec<SocketAddr>> {
+        let mut cache = self.lock_cache();
+        let key = (host.to_string(), port);
+        let (addrs, expires_at) = cache.get(&key)?;
+        if *expires_at <= Instant::now() {
+            cache.remove(&key);
+            return None;
+        }
+        Some(addrs.clone())
+    }
+}
+
+impl<R: Resolver> Resolver for CachingResolver<R> {
+    fn resolve<'a>(&'a self, host: &'a str, port: u16) -> ResolveFuture<'a> {
+        Box::pin(async move {
+            if let Some(addrs) = self.cached(host, port) {
+                return Ok(addrs);
+            }
+            let addrs = self.inner.resolve(host, port).await?;
+            self.lock_cache().insert(
+                (host.to_string(), port),
+                (addrs.clone(), Instant::now() + self.ttl),
+            );
+            Ok(addrs)
+        })
+    }
+}
+
+/// Resolves host names with the [hickory](https://docs.rs/hickory-resolver) DNS resolver,
+/// which queries name servers directly and caches answers according to their TTLs.
+#[cfg(feature = "hickory")]
+#[derive(Clone)]
+pub struct HickoryResolver {
+    resolver: TokioResolver,
+}
+
+#[cfg(feature = "hickory")]
+impl HickoryResolver {
+    /// Uses the system's name server configuration, `/etc/resolv.conf` on Unix.
+    pub fn from_system_conf() -> io::Result<Self> {
+        let resolver = TokioResolver::builder_tokio()
+            .and_then(|builder| builder.build())
+            .map_err(io::Error::other)?;
+        Ok(Self { resolver })
+    }
+
+    pub fn from_resolver(resolver: TokioResolver) -> Self {
+        Self { resolver }
+    }
+}
+
+#[cfg(feature = "hickory")]
+impl fmt::Debug for HickoryResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HickoryResolver").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "hickory")]
+impl Resolver for HickoryResolver {
+    fn resolve<'a>(&'a self, host: &'a str, port: u16) -> ResolveFuture<'a> {
+        Box::pin(async move {
+            if let Ok(ip) = host.parse::<IpAddr>() {
+                return Ok(vec![SocketAddr::new(ip, port)]);
+            }
+            let lookup = self
+                .resolver
+                .lookup_ip(host)
+                .await
+                .map_err(io::Error::other)?;
+            Ok(lookup.iter().map(|ip| SocketAddr::new(ip, port)).collect())
+        })
+    }
+}
+
+/// Resolves `host` with `resolver`, failing when it returns no addresses.
+pub(crate) async fn resolve_addresses(
+    resolver: &dyn Resolver,
+    host: &str,
+    port: u16,
+) -> io::Result<Vec<SocketAddr>> {
+    let addrs = resolver.resolve(host, port).await?;
+    tracing::debug!("Resolved {}:{} to {:?}", host, port, addrs);
+    if addrs.is_empty() {
+        return Err(not_found(host));
+    }
+    Ok(addrs)
+}
+
+fn not_found(host: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{host} did not resolve to any address"),
+    )
+}
@@ -12,14 +12,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::digest;
+use crate::digest::{ChallengeSource, OsChallengeSource};
 use crate::errors::{Error, Result};
 use crate::flags::DistributionFlags;
-use crate::handshake::{Challenge, ChallengeAck, ChallengeReply, SendName, StatusMessage};
+use crate::handshake::{
+    Challenge, ChallengeAck, ChallengeReply, HandshakeVersion, PROTOCOL_VERSION,
+    PROTOCOL_VERSION_5, SendName, Status, StatusMessage,
+};
+use crate::protocol::{COMPLEMENT_TAG, NAME_TAG_V5};
 use crate::types::Creation;
-use bytes::{BufMut, BytesMut};
+use bytes::{Buf, BufMut, BytesMut};
 use std::fmt;
+use std::sync::Arc;
 
+/// Decides whether a handshake continues when the peer answers `alive`, i.e. it still
+/// has a connection from this node. Receives the peer's node name, `true` makes the peer
+/// drop that connection.
+pub type AliveCallback = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Which side of the handshake a [`HandshakeStateMachine`] plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandshakeRole {
+    /// Initiates the connection and sends its name first
+    Client,
+    /// Accepts the connection and learns the peer's name from its send name message
+    Server,
+}
+
+/// The handshake states. `AwaitingName`, `AwaitingComplement`, `SendingChallenge`
+/// and `AwaitingChallengeReply` are only used by the [`HandshakeRole::Server`] role.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConnectionState {
     Disconnected,
@@ -29,6 +50,10 @@ pub enum ConnectionState {
     AwaitingChallenge,
     SendingChallengeReply,
     AwaitingChallengeAck,
+    AwaitingName,
+    AwaitingComplement,
+    SendingChallenge,
+    AwaitingChallengeReply,
     Connected,
     Failed,
 }
@@ -43,6 +68,10 @@ impl ConnectionState {
             ConnectionState::AwaitingChallenge => "awaiting_challenge",
             ConnectionState::SendingChallengeReply => "sending_challenge_reply",
             ConnectionState::AwaitingChallengeAck => "awaiting_challenge_ack",
+            ConnectionState::AwaitingName => "awaiting_name",
+            ConnectionState::AwaitingComplement => "awaiting_complement",
+            ConnectionState::SendingChallenge => "sending_challenge",
+            ConnectionState::AwaitingChallengeReply => "awaiting_challenge_reply",
             ConnectionState::Connected => "connected",
             ConnectionState::Failed => "failed",
         }
@@ -55,10 +84,37 @@ impl fmt::Display for ConnectionState {
     }
 }
 
+/// An input to [`HandshakeStateMachine::on_event`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandshakeEvent {
+    /// The transport to the peer is open and the handshake starts
+    Connect,
+    /// A connection from the peer was accepted and the handshake starts
+    Accept,
+    /// A handshake message arrived, without its 2-byte length prefix
+    Received(Vec<u8>),
+    /// The transport was closed
+    Disconnect,
+}
+
+/// What the caller of [`HandshakeStateMachine::on_event`] has to do next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandshakeAction {
+    /// Write a message to the peer, it includes its 2-byte length prefix
+    Send(Vec<u8>),
+    /// The handshake has completed, see [`HandshakeStateMachine::negotiated_flags`]
+    Complete,
+}
+
+/// The distribution handshake, driven by events and free of I/O.
+///
+/// [`HandshakeStateMachine::new`] creates the client role, [`HandshakeStateMachine::accepting`]
+/// the server role. [`HandshakeStateMachine::on_event`] performs complete transitions.
+/// The `prepare_*` and `handle_*` methods are the individual steps it is built from.
 pub struct HandshakeStateMachine {
+    role: HandshakeRole,
     state: ConnectionState,
     local_node_name: String,
-    #[allow(dead_code)]
     remote_node_name: String,
     cookie: String,
     flags: DistributionFlags,
@@ -66,6 +122,13 @@ pub struct HandshakeStateMachine {
     our_challenge: Option<u32>,
     their_challenge: Option<u32>,
     negotiated_flags: Option<DistributionFlags>,
+    peer_flags: Option<DistributionFlags>,
+    peer_creation: Option<u32>,
+    required_flags: DistributionFlags,
+    version: HandshakeVersion,
+    challenge_source: Arc<dyn ChallengeSource>,
+    alive_answer: Option<AliveCallback>,
+    peer_status: Option<Status>,
 }
 
 impl HandshakeStateMachine {
@@ -77,6 +140,7 @@ impl HandshakeStateMachine {
         creation: C,
     ) -> Self {
         Self {
+            role: HandshakeRole::Client,
             state: ConnectionState::Disconnected,
             local_node_name,
             remote_node_name,
@@ -86,19 +150,194 @@ impl HandshakeStateMachine {
             our_challenge: None,
             their_challenge: None,
             negotiated_flags: None,
+            peer_flags: None,
+            peer_creation: None,
+            required_flags: DistributionFlags::empty(),
+            version: HandshakeVersion::V6,
+            challenge_source: Arc::new(OsChallengeSource),
+            alive_answer: None,
+            peer_status: None,
+        }
+    }
+
+    /// The server role, for a connection accepted from a peer. It answers the peer's
+    /// name with `ok` and learns the peer's name, flags and creation from the handshake.
+    pub fn accepting<C: Into<Creation>>(
+        local_node_name: String,
+        cookie: String,
+        flags: DistributionFlags,
+        creation: C,
+    ) -> Self {
+        Self {
+            role: HandshakeRole::Server,
+            ..Self::new(local_node_name, String::new(), cookie, flags, creation)
         }
     }
 
+    /// Fails the handshake if any of these flags are not negotiated with the peer.
+    pub fn with_required_flags(mut self, flags: DistributionFlags) -> Self {
+        self.required_flags = flags;
+        self
+    }
+
+    pub fn with_handshake_version(mut self, version: HandshakeVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Draws our challenges from `source` instead of the OS CSPRNG.
+    pub fn with_challenge_source(mut self, source: Arc<dyn ChallengeSource>) -> Self {
+        self.challenge_source = source;
+        self
+    }
+
+    /// Consults `callback` when the peer answers `alive`. Without one the handshake continues,
+    /// since a connection being established means that the peer's one is stale.
+    pub fn with_alive_answer(mut self, callback: AliveCallback) -> Self {
+        self.alive_answer = Some(callback);
+        self
+    }
+
+    /// Picks the handshake version from the range the peer advertises via EPMD.
+    pub fn select_handshake_version(&mut self, lowest: u16, highest: u16) -> Result<()> {
+        self.version = HandshakeVersion::select(lowest, highest)?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn handshake_version(&self) -> HandshakeVersion {
+        self.version
+    }
+
+    /// Version 6 peers expect a complement message with the high flags and creation.
+    #[must_use]
+    pub fn needs_complement(&self) -> bool {
+        self.version == HandshakeVersion::V6
+    }
+
     #[must_use]
     pub fn state(&self) -> ConnectionState {
         self.state
     }
 
+    #[must_use]
+    pub fn role(&self) -> HandshakeRole {
+        self.role
+    }
+
+    /// The peer's node name. The server role learns it from the send name message.
+    #[must_use]
+    pub fn remote_node_name(&self) -> &str {
+        &self.remote_node_name
+    }
+
     #[must_use]
     pub fn negotiated_flags(&self) -> Option<DistributionFlags> {
         self.negotiated_flags
     }
 
+    /// The creation the peer sent in its challenge. Version 5 challenges do not carry one.
+    #[must_use]
+    pub fn peer_creation(&self) -> Option<u32> {
+        self.peer_creation
+    }
+
+    /// The status the peer answered our name with.
+    #[must_use]
+    pub fn peer_status(&self) -> Option<Status> {
+        self.peer_status
+    }
+
+    #[must_use]
+    pub fn requested_flags(&self) -> DistributionFlags {
+        self.flags
+    }
+
+    /// Advances the handshake and returns the actions to perform along with the new state.
+    ///
+    /// A failed step moves the machine to [`ConnectionState::Failed`], from which only
+    /// [`HandshakeEvent::Disconnect`] is accepted. Declining an `alive` status returns
+    /// the answer to send along with [`ConnectionState::Failed`].
+    pub fn on_event(
+        &mut self,
+        event: HandshakeEvent,
+    ) -> Result<(Vec<HandshakeAction>, ConnectionState)> {
+        match self.transition(event) {
+            Ok(actions) => Ok((actions, self.state)),
+            Err(e) => {
+                self.state = ConnectionState::Failed;
+                Err(e)
+            }
+        }
+    }
+
+    fn transition(&mut self, event: HandshakeEvent) -> Result<Vec<HandshakeAction>> {
+        match (self.state, event) {
+            (_, HandshakeEvent::Disconnect) => {
+                self.disconnect();
+                Ok(vec![])
+            }
+            (
+                ConnectionState::Disconnected | ConnectionState::Connecting,
+                HandshakeEvent::Connect,
+            ) if self.role == HandshakeRole::Client => {
+                if self.state == ConnectionState::Disconnected {
+                    self.begin_connect()?;
+                }
+                Ok(vec![HandshakeAction::Send(self.prepare_send_name()?)])
+            }
+            (ConnectionState::AwaitingStatus, HandshakeEvent::Received(data)) => {
+                self.handle_status(&data)?;
+                let mut actions = Vec::new();
+                if let Some(answer) = self.prepare_alive_answer() {
+                    actions.push(HandshakeAction::Send(answer));
+                }
+                if self.state == ConnectionState::AwaitingChallenge && self.needs_complement() {
+                    actions.push(HandshakeAction::Send(self.prepare_complement()?));
+                }
+                Ok(actions)
+            }
+            (ConnectionState::AwaitingChallenge, HandshakeEvent::Received(data)) => {
+                self.handle_challenge(&data)?;
+                Ok(vec![HandshakeAction::Send(self.prepare_challenge_reply()?)])
+            }
+            (ConnectionState::AwaitingChallengeAck, HandshakeEvent::Received(data)) => {
+                self.handle_challenge_ack(&data)?;
+                Ok(vec![HandshakeAction::Complete])
+            }
+            (ConnectionState::Disconnected, HandshakeEvent::Accept)
+                if self.role == HandshakeRole::Server =>
+            {
+                self.begin_accept()?;
+                Ok(vec![])
+            }
+            (ConnectionState::AwaitingName, HandshakeEvent::Received(data)) => {
+                self.handle_send_name(&data)?;
+                let mut actions = vec![HandshakeAction::Send(self.prepare_status())];
+                if self.state == ConnectionState::SendingChallenge {
+                    actions.push(HandshakeAction::Send(self.prepare_challenge()?));
+                }
+                Ok(actions)
+            }
+            (ConnectionState::AwaitingComplement, HandshakeEvent::Received(data)) => {
+                self.handle_complement(&data)?;
+                Ok(vec![HandshakeAction::Send(self.prepare_challenge()?)])
+            }
+            (ConnectionState::AwaitingChallengeReply, HandshakeEvent::Received(data)) => {
+                self.handle_challenge_reply(&data)?;
+                Ok(vec![
+                    HandshakeAction::Send(self.prepare_challenge_ack()?),
+                    HandshakeAction::Complete,
+                ])
+            }
+            (state, event) => Err(Error::InvalidStateMessage(format!(
+                "unexpected handshake event {} in state {}",
+                event_name(&event),
+                state
+            ))),
+        }
+    }
+
     pub fn begin_connect(&mut self) -> Result<()> {
         if self.state != ConnectionState::Disconnected {
             return Err(Error::InvalidStateTransition {
@@ -112,7 +351,7 @@ impl HandshakeStateMachine {
 
     pub fn prepare_send_name(&mut self) -> Result<Vec<u8>> {
         self.state = ConnectionState::SendingName;
-        let send_name = SendName::new(self.flags, self.creation.0, &self.local_node_name);
+        let send_name = SendName::new(self.offered_flags(), self.creation.0, &self.local_node_name);
         let data = send_name.encode_old()?;
         self.state = ConnectionState::AwaitingStatus;
         Ok(data)
@@ -120,21 +359,47 @@ impl HandshakeStateMachine {
 
     pub fn handle_status(&mut self, data: &[u8]) -> Result<()> {
         let status_msg = StatusMessage::decode(data)?;
-        if !status_msg.status.is_ok() {
-            return Err(Error::ConnectionRefused {
-                reason: format!("Status: {}", status_msg.status),
-            });
+        self.peer_status = Some(status_msg.status);
+        match status_msg.status {
+            Status::Ok | Status::OkSimultaneous => {
+                self.state = ConnectionState::AwaitingChallenge;
+            }
+            Status::Alive => {
+                self.state = if self.continues_when_alive() {
+                    ConnectionState::AwaitingChallenge
+                } else {
+                    ConnectionState::Failed
+                };
+            }
+            status => {
+                return Err(Error::ConnectionRefused {
+                    reason: format!("Status: {}", status),
+                });
+            }
         }
         Ok(())
     }
 
+    /// The answer to an `alive` status, `None` if the peer answered something else.
+    pub fn prepare_alive_answer(&self) -> Option<Vec<u8>> {
+        (self.peer_status == Some(Status::Alive)).then(|| {
+            StatusMessage::encode_alive_answer(self.state == ConnectionState::AwaitingChallenge)
+        })
+    }
+
+    fn continues_when_alive(&self) -> bool {
+        self.alive_answer
+            .as_ref()
+            .is_none_or(|callback| callback(&self.remote_node_name))
+    }
+
     pub fn prepare_complement(&mut self) -> Result<Vec<u8>> {
         let flags_u64 = self.flags.as_u64();
         let high_flags = (flags_u64 >> 32) as u32;
 
         let mut buf = BytesMut::new();
         buf.put_u16(9);
-        buf.put_u8(b'c');
+        buf.put_u8(COMPLEMENT_TAG);
         buf.put_u32(high_flags);
         buf.put_u32(self.creation.0);
         Ok(buf.to_vec())
@@ -142,14 +407,17 @@ impl HandshakeStateMachine {
 
     pub fn handle_challenge(&mut self, data: &[u8]) -> Result<()> {
         self.state = ConnectionState::AwaitingChallenge;
-        let challenge = Challenge::decode(data)?;
+        let challenge = match self.version {
+            #[cfg(feature = "legacy-handshake")]
+            HandshakeVersion::V5 => Challenge::decode_old(data)?,
+            _ => Challenge::decode(data)?,
+        };
 
-        self.negotiated_flags = Some(DistributionFlags::new(
-            challenge.flags.as_u64() & self.flags.as_u64(),
-        ));
+        self.negotiate(challenge.flags)?;
+        self.peer_creation = (challenge.creation != 0).then_some(challenge.creation);
 
         self.their_challenge = Some(challenge.challenge);
-        self.our_challenge = Some(digest::generate_challenge());
+        self.our_challenge = Some(self.challenge_source.next_challenge());
         Ok(())
     }
 
@@ -185,10 +453,187 @@ impl HandshakeStateMachine {
         Ok(())
     }
 
+    pub fn begin_accept(&mut self) -> Result<()> {
+        if self.state != ConnectionState::Disconnected {
+            return Err(Error::InvalidStateTransition {
+                from: self.state,
+                to: ConnectionState::AwaitingName,
+            });
+        }
+        self.state = ConnectionState::AwaitingName;
+        Ok(())
+    }
+
+    /// Reads the peer's name, flags and handshake version. A version 5 send name from
+    /// a peer with `DFLAG_HANDSHAKE_23` is followed by a complement message.
+    pub fn handle_send_name(&mut self, data: &[u8]) -> Result<()> {
+        let is_old = data.first() == Some(&NAME_TAG_V5);
+        let send_name = if is_old {
+            SendName::decode_old(data)?
+        } else {
+            SendName::decode(data)?
+        };
+        let expects_complement =
+            is_old && send_name.flags.contains(DistributionFlags::HANDSHAKE_23);
+        self.version = if !is_old || expects_complement {
+            HandshakeVersion::V6
+        } else {
+            legacy_version()?
+        };
+
+        self.remote_node_name = send_name.name;
+        self.peer_flags = Some(send_name.flags);
+        self.peer_creation = (send_name.creation != 0).then_some(send_name.creation);
+        self.state = if expects_complement {
+            ConnectionState::AwaitingComplement
+        } else {
+            ConnectionState::SendingChallenge
+        };
+        Ok(())
+    }
+
+    pub fn prepare_status(&self) -> Vec<u8> {
+        StatusMessage::new(Status::Ok).encode()
+    }
+
+    /// Reads the high 32 bits of the peer's flags and its creation.
+    pub fn handle_complement(&mut self, data: &[u8]) -> Result<()> {
+        let mut buf = data;
+        if buf.remaining() < 1 + 4 + 4 {
+            return Err(Error::InvalidHandshakeMessage(
+                "Insufficient data for complement".to_string(),
+            ));
+        }
+
+        let tag = buf.get_u8();
+        if tag != COMPLEMENT_TAG {
+            return Err(Error::InvalidHandshakeMessage(format!(
+                "Expected tag 'c', got {}",
+                tag
+            )));
+        }
+
+        let high_flags = u64::from(buf.get_u32()) << 32;
+        let creation = buf.get_u32();
+        let low_flags = self
+            .peer_flags
+            .map_or(0, |flags| flags.as_u64() & 0xFFFF_FFFF);
+        self.peer_flags = Some(DistributionFlags::new(high_flags | low_flags));
+        self.peer_creation = (creation != 0).then_some(creation);
+        self.state = ConnectionState::SendingChallenge;
+        Ok(())
+    }
+
+    pub fn prepare_challenge(&mut self) -> Result<Vec<u8>> {
+        self.state = ConnectionState::SendingChallenge;
+
+        let peer_flags = self
+            .peer_flags
+            .ok_or_else(|| Error::InvalidStateMessage("no peer flags set".to_string()))?;
+        self.negotiate(peer_flags)?;
+
+        let our_challenge = self.challenge_source.next_challenge();
+        self.our_challenge = Some(our_challenge);
+        let challenge = Challenge::new(
+            self.offered_flags(),
+            our_challenge,
+            self.creation.0,
+            &self.local_node_name,
+        );
+        let data = match self.version {
+            #[cfg(feature = "legacy-handshake")]
+            HandshakeVersion::V5 => challenge.encode_old()?,
+            _ => challenge.encode()?,
+        };
+        self.state = ConnectionState::AwaitingChallengeReply;
+        Ok(data)
+    }
+
+    pub fn handle_challenge_reply(&mut self, data: &[u8]) -> Result<()> {
+        let reply = ChallengeReply::decode(data)?;
+
+        let our_challenge = self
+            .our_challenge
+            .ok_or_else(|| Error::InvalidStateMessage("no our_challenge set".to_string()))?;
+
+        if !reply.verify(our_challenge, &self.cookie) {
+            return Err(Error::AuthenticationFailed);
+        }
+
+        self.their_challenge = Some(reply.challenge);
+        Ok(())
+    }
+
+    pub fn prepare_challenge_ack(&mut self) -> Result<Vec<u8>> {
+        let their_challenge = self
+            .their_challenge
+            .ok_or_else(|| Error::InvalidStateMessage("no their_challenge set".to_string()))?;
+
+        let data = ChallengeAck::new(their_challenge, &self.cookie).encode();
+        self.state = ConnectionState::Connected;
+        Ok(data)
+    }
+
+    fn negotiate(&mut self, peer_flags: DistributionFlags) -> Result<()> {
+        let negotiated =
+            DistributionFlags::new(peer_flags.as_u64() & self.offered_flags().as_u64());
+        let missing = self.required_flags.difference(negotiated);
+        if !missing.is_empty() {
+            return Err(Error::MissingMandatoryFlags {
+                missing: missing
+                    .iter_names()
+                    .map(|(name, _)| name.to_string())
+                    .collect(),
+            });
+        }
+        self.negotiated_flags = Some(negotiated);
+        Ok(())
+    }
+
+    /// Version 5 peers predate `DFLAG_HANDSHAKE_23` and only see the low 32 bits of the flags.
+    fn offered_flags(&self) -> DistributionFlags {
+        match self.version {
+            HandshakeVersion::V5 => DistributionFlags::new(
+                self.flags
+                    .difference(DistributionFlags::HANDSHAKE_23)
+                    .as_u64()
+                    & 0xFFFF_FFFF,
+            ),
+            HandshakeVersion::V6 => self.flags,
+        }
+    }
+
     pub fn disconnect(&mut self) {
         self.state = ConnectionState::Disconnected;
         self.our_challenge = None;
         self.their_challenge = None;
         self.negotiated_flags = None;
+        self.peer_flags = None;
+        self.peer_creation = None;
+        self.peer_status = None;
+        if self.role == HandshakeRole::Server {
+            self.remote_node_name.clear();
+        }
+    }
+}
+
+/// The handshake version of a peer that only speaks version 5.
+fn legacy_version() -> Result<HandshakeVersion> {
+    if cfg!(feature = "legacy-handshake") {
+        Ok(HandshakeVersion::V5)
+    } else {
+        Err(Error::IncompatibleVersion {
+            got: PROTOCOL_VERSION_5,
+            expected: PROTOCOL_VERSION,
+        })
+    }
+}
+
+fn event_name(event: &HandshakeEvent) -> &'static str {
+    match event {
+        HandshakeEvent::Connect => "connect",
+        HandshakeEvent::Accept => "accept",
+        HandshakeEvent::Received(_) => "received",
+        HandshakeEvent::Disconnect => "disconnect",
     }
 }
@@ -0,0 +1,134 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A fake peer for tests that drive a connection against scripted distribution traffic,
+//! enabled by the `test-support` feature.
+//!
+//! [`TestPeer`] accepts a connection and completes the handshake as the node the
+//! connection expects, after which the test reads and writes frames on the stream.
+
+use crate::flags::DistributionFlags;
+use crate::handshake::{Challenge, ChallengeAck, ChallengeReply};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// The challenge a [`TestPeer`] sends unless another one is configured.
+pub const TEST_PEER_CHALLENGE: u32 = 42;
+
+/// The server side of a handshake, for a connection to `name` with `cookie`.
+///
+/// By default the peer answers with the `ok` status, has creation 1 and offers the
+/// default flags without `DIST_HDR_ATOM_CACHE`, so that frames are pass-through.
+#[derive(Debug, Clone)]
+pub struct TestPeer {
+    name: String,
+    cookie: String,
+    flags: DistributionFlags,
+    creation: u32,
+    challenge: u32,
+    status: String,
+}
+
+impl TestPeer {
+    pub fn new(name: impl Into<String>, cookie: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            cookie: cookie.into(),
+            flags: DistributionFlags::default().difference(DistributionFlags::DIST_HDR_ATOM_CACHE),
+            creation: 1,
+            challenge: TEST_PEER_CHALLENGE,
+            status: "ok".to_string(),
+        }
+    }
+
+    pub fn with_flags(mut self, flags: DistributionFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    pub fn with_creation(mut self, creation: u32) -> Self {
+        self.creation = creation;
+        self
+    }
+
+    pub fn with_challenge(mut self, challenge: u32) -> Self {
+        self.challenge = challenge;
+        self
+    }
+
+    /// The status the peer answers the connecting node's name with, e.g. `alive`.
+    /// After `alive`, the peer expects to be told to continue with `true`.
+    pub fn with_status(mut self, status: impl Into<String>) -> Self {
+        self.status = status.into();
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Accepts a connection on `listener` and completes its handshake.
+    pub async fn accept(&self, listener: &TcpListener) -> TcpStream {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        self.handshake(&mut stream).await;
+        stream
+    }
+
+    /// Completes the handshake of an accepted connection.
+    pub async fn handshake(&self, stream: &mut TcpStream) {
+        read_handshake_message(stream).await;
+        write_status(stream, &self.status).await;
+        if self.status == "alive" {
+            assert_eq!(read_handshake_message(stream).await, b"strue");
+        }
+        self.complete_handshake(stream).await;
+    }
+
+    /// Completes a handshake after the status exchange: sends the challenge,
+    /// checks the connecting node's digest against the cookie and acknowledges it.
+    pub async fn complete_handshake(&self, stream: &mut TcpStream) {
+        read_handshake_message(stream).await;
+
+        let challenge = Challenge::new(self.flags, self.challenge, self.creation, &self.name);
+        stream
+            .write_all(&challenge.encode().unwrap())
+            .await
+            .unwrap();
+        let reply = ChallengeReply::decode(&read_handshake_message(stream).await).unwrap();
+        assert!(
+            reply.verify(self.challenge, &self.cookie),
+            "the connecting node used a different cookie"
+        );
+        stream
+            .write_all(&ChallengeAck::new(reply.challenge, &self.cookie).encode())
+            .await
+            .unwrap();
+    }
+}
+
+/// Reads a 2-byte length prefixed handshake message.
+pub async fn read_handshake_message(stream: &mut TcpStream) -> Vec<u8> {
+    let len = stream.read_u16().await.unwrap();
+    let mut data = vec![0u8; len as usize];
+    stream.read_exact(&mut data).await.unwrap();
+    data
+}
+
+/// Writes a status message, e.g. `ok` or `alive`.
+pub async fn write_status(stream: &mut TcpStream, status: &str) {
+    let mut message = Vec::with_capacity(3 + status.len());
+    message.extend_from_slice(&(1 + status.len() as u16).to_be_bytes());
+    message.push(b's');
+    message.extend_from_slice(status.as_bytes());
+    stream.write_all(&message).await.unwrap();
+}
@@ -0,0 +1,385 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Helpers for building control messages in tests, enabled by the `test-support` feature.
+//!
+//! ```
+//! use edp_client::control::ControlMessage;
+//! use edp_client::{ctrl, pid};
+//! use erltf::OwnedTerm;
+//!
+//! let (msg, payload) = ctrl!(RegSend from pid!(1, 2), to "rex", payload OwnedTerm::atom("ping"));
+//! assert!(matches!(msg, ControlMessage::RegSend { .. }));
+//! assert_eq!(payload, OwnedTerm::atom("ping"));
+//! ```
+
+use crate::control::{ControlMessage, ControlMessageType};
+use erltf::OwnedTerm;
+use std::collections::BTreeMap;
+
+#[doc(hidden)]
+pub use erltf;
+
+/// The node of pids and references created by [`pid!`](crate::pid) and [`reference!`](crate::reference).
+pub const TEST_NODE: &str = "test@localhost";
+
+/// Creates a pid term, on [`TEST_NODE`] unless a node is given.
+///
+/// `pid!(id, serial)`, `pid!(node, id, serial)` or `pid!(node, id, serial, creation)`.
+#[macro_export]
+macro_rules! pid {
+    ($id:expr, $serial:expr) => {
+        $crate::pid!($crate::test_support::TEST_NODE, $id, $serial, 1)
+    };
+    ($node:expr, $id:expr, $serial:expr) => {
+        $crate::pid!($node, $id, $serial, 1)
+    };
+    ($node:expr, $id:expr, $serial:expr, $creation:expr) => {
+        $crate::test_support::erltf::OwnedTerm::Pid($crate::test_support::erltf::ExternalPid::new(
+            $crate::test_support::erltf::Atom::new($node),
+            $id,
+            $serial,
+            $creation,
+        ))
+    };
+}
+
+/// Creates a reference term on [`TEST_NODE`] from its ids, e.g. `reference!(1, 2, 3)`.
+#[macro_export]
+macro_rules! reference {
+    ($($id:expr),+ $(,)?) => {
+        $crate::test_support::erltf::OwnedTerm::Reference($crate::test_support::erltf::ExternalReference::new(
+            $crate::test_support::erltf::Atom::new($crate::test_support::TEST_NODE),
+            1,
+            vec![$($id),+],
+        ))
+    };
+}
+
+/// Builds a control message from a message type and named fields,
+/// e.g. `ctrl!(Link from pid!(1, 0), to pid!(2, 0))`.
+///
+/// Field names are the methods of [`CtrlBuilder`]. Missing fields and fields the message type
+/// does not have panic. With a `payload` field, the result is a `(ControlMessage, OwnedTerm)` pair.
+#[macro_export]
+macro_rules! ctrl {
+    ($kind:ident $($field:ident $value:expr),* $(,)?) => {
+        $crate::test_support::CtrlBuilder::new($crate::control::ControlMessageType::$kind)
+            $(.$field($value))*
+            .build()
+    };
+}
+
+/// Collects the fields of a control message for [`ctrl!`](crate::ctrl).
+///
+/// Strings passed to `from` and `to` become atoms (registered names).
+/// `cookie` defaults to `''`.
+#[derive(Debug, Clone)]
+pub struct CtrlBuilder<P = ()> {
+    message_type: ControlMessageType,
+    fields: BTreeMap<&'static str, OwnedTerm>,
+    payload: P,
+}
+
+impl CtrlBuilder {
+    pub fn new(message_type: ControlMessageType) -> Self {
+        Self {
+            message_type,
+            fields: BTreeMap::new(),
+            payload: (),
+        }
+    }
+
+    pub fn build(self) -> ControlMessage {
+        self.build_message()
+    }
+}
+
+impl CtrlBuilder<OwnedTerm> {
+    pub fn build(self) -> (ControlMessage, OwnedTerm) {
+        let payload = self.payload.clone();
+        (self.build_message(), payload)
+    }
+}
+
+impl<P> CtrlBuilder<P> {
+    pub fn payload(self, payload: impl Into<OwnedTerm>) -> CtrlBuilder<OwnedTerm> {
+        CtrlBuilder {
+            message_type: self.message_type,
+            fields: self.fields,
+            payload: payload.into(),
+        }
+    }
+
+    pub fn from(self, from: impl Into<OwnedTerm>) -> Self {
+        self.set("from", name_or_term(from.into()))
+    }
+
+    pub fn to(self, to: impl Into<OwnedTerm>) -> Self {
+        self.set("to", name_or_term(to.into()))
+    }
+
+    pub fn cookie(self, cookie: impl Into<OwnedTerm>) -> Self {
+        self.set("cookie", cookie.into())
+    }
+
+    pub fn reason(self, reason: impl Into<OwnedTerm>) -> Self {
+        self.set("reason", reason.into())
+    }
+
+    pub fn reference(self, reference: impl Into<OwnedTerm>) -> Self {
+        self.set("reference", reference.into())
+    }
+
+    pub fn token(self, trace_token: impl Into<OwnedTerm>) -> Self {
+        self.set("token", trace_token.into())
+    }
+
+    pub fn id(self, id: u64) -> Self {
+        self.set("id", OwnedTerm::Integer(id as i64))
+    }
+
+    pub fn alias(self, alias: impl Into<OwnedTerm>) -> Self {
+        self.set("alias", alias.into())
+    }
+
+    pub fn req_id(self, req_id: impl Into<OwnedTerm>) -> Self {
+        self.set("req_id", req_id.into())
+    }
+
+    pub fn group_leader(self, group_leader: impl Into<OwnedTerm>) -> Self {
+        self.set("group_leader", group_leader.into())
+    }
+
+    pub fn mfa(self, mfa: impl Into<OwnedTerm>) -> Self {
+        self.set("mfa", mfa.into())
+    }
+
+    pub fn args(self, arg_list: impl Into<OwnedTerm>) -> Self {
+        self.set("args", arg_list.into())
+    }
+
+    pub fn opts(self, opt_list: impl Into<OwnedTerm>) -> Self {
+        self.set("opts", opt_list.into())
+    }
+
+    pub fn flags(self, flags: impl Into<OwnedTerm>) -> Self {
+        self.set("flags", flags.into())
+    }
+
+    pub fn result(self, result: impl Into<OwnedTerm>) -> Self {
+        self.set("result", result.into())
+    }
+
+    fn set(mut self, field: &'static str, value: OwnedTerm) -> Self {
+        if self.fields.insert(field, value).is_some() {
+            panic!(
+                "ctrl!({}): `{field}` is given more than once",
+                self.message_type.name()
+            );
+        }
+        self
+    }
+
+    fn build_message(mut self) -> ControlMessage {
+        let msg = match self.message_type {
+            ControlMessageType::Link => ControlMessage::Link {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+            },
+            ControlMessageType::Send => ControlMessage::Send {
+                cookie: self.take_cookie(),
+                to_pid: self.take("to"),
+            },
+            ControlMessageType::Exit => ControlMessage::Exit {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+                reason: self.take("reason"),
+            },
+            ControlMessageType::Unlink => ControlMessage::Unlink {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+            },
+            ControlMessageType::NodeLink => ControlMessage::NodeLink,
+            ControlMessageType::RegSend => ControlMessage::RegSend {
+                from_pid: self.take("from"),
+                cookie: self.take_cookie(),
+                to_name: self.take("to"),
+            },
+            ControlMessageType::GroupLeader => ControlMessage::GroupLeader {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+            },
+            ControlMessageType::Exit2 => ControlMessage::Exit2 {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+                reason: self.take("reason"),
+            },
+            ControlMessageType::SendTt => ControlMessage::SendTt {
+                cookie: self.take_cookie(),
+                to_pid: self.take("to"),
+                trace_token: self.take("token"),
+            },
+            ControlMessageType::ExitTt => ControlMessage::ExitTt {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+                trace_token: self.take("token"),
+                reason: self.take("reason"),
+            },
+            ControlMessageType::RegSendTt => ControlMessage::RegSendTt {
+                from_pid: self.take("from"),
+                cookie: self.take_cookie(),
+                to_name: self.take("to"),
+                trace_token: self.take("token"),
+            },
+            ControlMessageType::Exit2Tt => ControlMessage::Exit2Tt {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+                trace_token: self.take("token"),
+                reason: self.take("reason"),
+            },
+            ControlMessageType::MonitorP => ControlMessage::MonitorP {
+                from_pid: self.take("from"),
+                to_proc: self.take("to"),
+                reference: self.take("reference"),
+            },
+            ControlMessageType::DemonitorP => ControlMessage::DemonitorP {
+                from_pid: self.take("from"),
+                to_proc: self.take("to"),
+                reference: self.take("reference"),
+            },
+            ControlMessageType::MonitorPExit => ControlMessage::MonitorPExit {
+                from_proc: self.take("from"),
+                to_pid: self.take("to"),
+                reference: self.take("reference"),
+                reason: self.take("reason"),
+            },
+            ControlMessageType::SendSender => ControlMessage::SendSender {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+            },
+            ControlMessageType::SendSenderTt => ControlMessage::SendSenderTt {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+                trace_token: self.take("token"),
+            },
+            ControlMessageType::PayloadExit => ControlMessage::PayloadExit {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+            },
+            ControlMessageType::PayloadExitTt => ControlMessage::PayloadExitTt {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+                trace_token: self.take("token"),
+            },
+            ControlMessageType::PayloadExit2 => ControlMessage::PayloadExit2 {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+            },
+            ControlMessageType::PayloadExit2Tt => ControlMessage::PayloadExit2Tt {
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+                trace_token: self.take("token"),
+            },
+            ControlMessageType::PayloadMonitorPExit => ControlMessage::PayloadMonitorPExit {
+                from_proc: self.take("from"),
+                to_pid: self.take("to"),
+                reference: self.take("reference"),
+            },
+            ControlMessageType::SpawnRequest => ControlMessage::SpawnRequest {
+                req_id: self.take("req_id"),
+                from: self.take("from"),
+                group_leader: self.take("group_leader"),
+                mfa: self.take("mfa"),
+                arg_list: self.take("args"),
+                opt_list: self.take("opts"),
+            },
+            ControlMessageType::SpawnRequestTt => ControlMessage::SpawnRequestTt {
+                req_id: self.take("req_id"),
+                from: self.take("from"),
+                group_leader: self.take("group_leader"),
+                mfa: self.take("mfa"),
+                arg_list: self.take("args"),
+                opt_list: self.take("opts"),
+                trace_token: self.take("token"),
+            },
+            ControlMessageType::SpawnReply => ControlMessage::SpawnReply {
+                req_id: self.take("req_id"),
+                to: self.take("to"),
+                flags: self.take("flags"),
+                result: self.take("result"),
+            },
+            ControlMessageType::SpawnReplyTt => ControlMessage::SpawnReplyTt {
+                req_id: self.take("req_id"),
+                to: self.take("to"),
+                flags: self.take("flags"),
+                result: self.take("result"),
+                trace_token: self.take("token"),
+            },
+            ControlMessageType::UnlinkId => ControlMessage::UnlinkId {
+                id: self.take_id(),
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+            },
+            ControlMessageType::UnlinkIdAck => ControlMessage::UnlinkIdAck {
+                id: self.take_id(),
+                from_pid: self.take("from"),
+                to_pid: self.take("to"),
+            },
+            ControlMessageType::AliasSend => ControlMessage::AliasSend {
+                from_pid: self.take("from"),
+                alias: self.take("alias"),
+            },
+            ControlMessageType::AliasSendTt => ControlMessage::AliasSendTt {
+                from_pid: self.take("from"),
+                alias: self.take("alias"),
+                trace_token: self.take("token"),
+            },
+        };
+
+        if let Some(field) = self.fields.keys().next() {
+            panic!(
+                "ctrl!({}) does not take `{field}`",
+                self.message_type.name()
+            );
+        }
+        msg
+    }
+
+    fn take(&mut self, field: &'static str) -> OwnedTerm {
+        match self.fields.remove(field) {
+            Some(value) => value,
+            None => panic!("ctrl!({}) requires `{field}`", self.message_type.name()),
+        }
+    }
+
+    fn take_cookie(&mut self) -> OwnedTerm {
+        self.fields
+            .remove("cookie")
+            .unwrap_or_else(|| OwnedTerm::atom(""))
+    }
+
+    fn take_id(&mut self) -> u64 {
+        match self.take("id") {
+            OwnedTerm::Integer(id) => id as u64,
+            _ => unreachable!("`id` is always set from a u64"),
+        }
+    }
+}
+
+fn name_or_term(term: OwnedTerm) -> OwnedTerm {
+    match term {
+        OwnedTerm::String(name) => OwnedTerm::atom(&name),
+        other => other,
+    }
+}
@@ -12,19 +12,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "zstd")]
+use crate::compression::{self, ZstdCompression};
+#[cfg(feature = "zstd")]
+use crate::connection::MAX_MESSAGE_SIZE;
 use crate::errors::{Error, Result};
 use crate::framing::{FrameMode, MessageDeframer, MessageFramer};
+use std::net::SocketAddr;
 use std::time::Duration;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::time::timeout;
 
+/// A TCP stream split into halves, with length-prefixed reads and writes.
+///
+/// [`FramedTransport::read`] and [`FramedTransport::write`] use the current [`FrameMode`].
+/// [`FramedTransport::read_raw`] and [`FramedTransport::write_raw`] bypass framing, so an
+/// embedder can exchange other data before or during the handshake, e.g. a proxy
+/// protocol preamble, and then pass the transport to
+/// [`Connection::connect_over`](crate::Connection::connect_over) or
+/// [`Connection::adopt_handshake`](crate::Connection::adopt_handshake).
 pub struct FramedTransport {
     read_half: Option<OwnedReadHalf>,
     write_half: Option<OwnedWriteHalf>,
     framer: MessageFramer,
     deframer: MessageDeframer,
     timeout: Duration,
+    /// Applied to distribution frames once the handshake completes
+    #[cfg(feature = "zstd")]
+    compression: Option<ZstdCompression>,
 }
 
 impl FramedTransport {
@@ -35,6 +52,8 @@ impl FramedTransport {
             framer: MessageFramer::new(FrameMode::Handshake),
             deframer: MessageDeframer::new(FrameMode::Handshake),
             timeout,
+            #[cfg(feature = "zstd")]
+            compression: None,
         }
     }
 
@@ -42,6 +61,10 @@ impl FramedTransport {
         let (read_half, write_half) = stream.into_split();
         self.read_half = Some(read_half);
         self.write_half = Some(write_half);
+        // A new stream always starts with the handshake
+        self.set_frame_mode(FrameMode::Handshake);
+        #[cfg(feature = "zstd")]
+        self.set_compression(None);
     }
 
     pub fn set_frame_mode(&mut self, mode: FrameMode) {
@@ -49,25 +72,58 @@ impl FramedTransport {
         self.deframer.set_mode(mode);
     }
 
+    pub fn frame_mode(&self) -> FrameMode {
+        self.framer.mode()
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.write_half
+            .as_ref()
+            .and_then(|write_half| write_half.peer_addr().ok())
+    }
+
+    /// Compresses written frames and decompresses read ones, see [`crate::compression`].
+    #[cfg(feature = "zstd")]
+    pub fn set_compression(&mut self, compression: Option<ZstdCompression>) {
+        self.compression = compression;
+    }
+
     pub async fn read(&mut self) -> Result<Vec<u8>> {
         let stream = self
             .read_half
             .as_mut()
             .ok_or_else(|| Error::InvalidStateMessage("no active stream".to_string()))?;
 
-        tokio::time::timeout(self.timeout, self.deframer.read_framed(stream))
+        let data = timeout(self.timeout, self.deframer.read_framed(stream))
             .await
             .map_err(|_| Error::Timeout(self.timeout))?
-            .map_err(Error::Io)
+            .map_err(Error::Io)?;
+        #[cfg(feature = "zstd")]
+        if self.compression.is_some() {
+            return Ok(compression::decompress(data, MAX_MESSAGE_SIZE)?);
+        }
+        Ok(data)
     }
 
     pub async fn write(&mut self, data: &[u8]) -> Result<()> {
+        #[cfg(feature = "zstd")]
+        let compressed = match &self.compression {
+            Some(compression) => compression.compress(data)?,
+            None => None,
+        };
+        #[cfg(feature = "zstd")]
+        let data = compressed.as_deref().unwrap_or(data);
+
         let stream = self
             .write_half
             .as_mut()
             .ok_or_else(|| Error::InvalidStateMessage("no active stream".to_string()))?;
 
-        tokio::time::timeout(self.timeout, self.framer.write_framed(stream, data))
+        timeout(self.timeout, self.framer.write_framed(stream, data))
             .await
             .map_err(|_| Error::Timeout(self.timeout))?
             .map_err(Error::Io)
@@ -90,13 +146,28 @@ impl FramedTransport {
         self.read_half.take()
     }
 
+    /// Reads exactly `len` bytes that are not length-prefixed.
+    pub async fn read_raw(&mut self, len: usize) -> Result<Vec<u8>> {
+        let stream = self
+            .read_half
+            .as_mut()
+            .ok_or_else(|| Error::InvalidStateMessage("no active stream".to_string()))?;
+
+        let mut buf = vec![0u8; len];
+        timeout(self.timeout, stream.read_exact(&mut buf))
+            .await
+            .map_err(|_| Error::Timeout(self.timeout))??;
+        Ok(buf)
+    }
+
+    /// Writes `data` as is, without a length prefix.
     pub async fn write_raw(&mut self, data: &[u8]) -> Result<()> {
         let stream = self
             .write_half
             .as_mut()
             .ok_or_else(|| Error::InvalidStateMessage("no active stream".to_string()))?;
 
-        tokio::time::timeout(self.timeout, async {
+        timeout(self.timeout, async {
             stream.write_all(data).await?;
             stream.flush().await
         })
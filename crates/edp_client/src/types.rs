@@ -12,17 +12,50 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::errors::{Error, Result};
+use std::fmt;
+use std::str;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Distinguishes incarnations of a node with the same name.
+///
+/// Creation 0 predates OTP 23 and acts as a wildcard: pids, ports and references
+/// with it match any incarnation of their node, see [`Creation::matches`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Creation(pub u32);
 
 impl Creation {
+    /// The legacy creation that matches any other.
+    pub const WILDCARD: Creation = Creation(0);
+
     pub fn new(value: u32) -> Self {
         Self(value)
     }
 
+    /// Like [`Creation::new`] but rejects the wildcard, which a running node never has.
+    pub fn try_new(value: u32) -> Result<Self> {
+        if value == Self::WILDCARD.0 {
+            return Err(Error::InvalidCreation(value));
+        }
+        Ok(Self(value))
+    }
+
     pub fn value(&self) -> u32 {
         self.0
     }
+
+    pub fn is_wildcard(&self) -> bool {
+        *self == Self::WILDCARD
+    }
+
+    /// Returns true if both creations are equal or either one is the wildcard.
+    pub fn matches(&self, other: impl Into<Creation>) -> bool {
+        let other = other.into();
+        self.is_wildcard() || other.is_wildcard() || *self == other
+    }
 }
 
 impl From<u32> for Creation {
@@ -43,17 +76,86 @@ impl Default for Creation {
     }
 }
 
+/// Identifies a fragmented message or a position in an ordered channel.
+///
+/// Allocated sequence ids start at 1 and skip 0 when they wrap around.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct SequenceId(pub u64);
 
 impl SequenceId {
+    pub const FIRST: SequenceId = SequenceId(1);
+
     pub fn new(value: u64) -> Self {
         Self(value)
     }
 
+    /// Like [`SequenceId::new`] but rejects 0, which is never allocated.
+    pub fn try_new(value: u64) -> Result<Self> {
+        if value == 0 {
+            return Err(Error::InvalidSequenceId(value));
+        }
+        Ok(Self(value))
+    }
+
     pub fn value(&self) -> u64 {
         self.0
     }
+
+    /// The id after this one, wrapping from `u64::MAX` to [`SequenceId::FIRST`].
+    #[must_use]
+    pub fn next(&self) -> Self {
+        match self.0.wrapping_add(1) {
+            0 => Self::FIRST,
+            value => Self(value),
+        }
+    }
+}
+
+/// Hands out [`SequenceId`]s from multiple tasks without locking.
+#[derive(Debug)]
+pub struct SequenceIdAllocator {
+    next: AtomicU64,
+}
+
+impl SequenceIdAllocator {
+    pub fn new() -> Self {
+        Self::starting_at(SequenceId::FIRST)
+    }
+
+    pub fn starting_at(first: SequenceId) -> Self {
+        Self {
+            next: AtomicU64::new(first.0),
+        }
+    }
+
+    pub fn allocate(&self) -> SequenceId {
+        let previous = self
+            .next
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                Some(SequenceId(current).next().0)
+            })
+            .unwrap_or_else(|current| current);
+        match previous {
+            0 => SequenceId::FIRST,
+            value => SequenceId(value),
+        }
+    }
+
+    /// The id the next [`SequenceIdAllocator::allocate`] call returns.
+    pub fn peek(&self) -> SequenceId {
+        match self.next.load(Ordering::Relaxed) {
+            0 => SequenceId::FIRST,
+            value => SequenceId(value),
+        }
+    }
+}
+
+impl Default for SequenceIdAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl From<u64> for SequenceId {
@@ -67,3 +169,98 @@ impl From<SequenceId> for u64 {
         seq_id.0
     }
 }
+
+/// Names a logical stream of messages for [`Connection::send_ordered`](crate::Connection::send_ordered).
+///
+/// Messages sent with the same key are delivered in the order they were sent.
+/// Messages with different keys carry no ordering guarantee relative to each other.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ChannelKey {
+    Name(String),
+    Id(u64),
+}
+
+impl fmt::Display for ChannelKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChannelKey::Name(name) => f.write_str(name),
+            ChannelKey::Id(id) => write!(f, "{id}"),
+        }
+    }
+}
+
+impl From<&str> for ChannelKey {
+    fn from(name: &str) -> Self {
+        ChannelKey::Name(name.to_string())
+    }
+}
+
+impl From<String> for ChannelKey {
+    fn from(name: String) -> Self {
+        ChannelKey::Name(name)
+    }
+}
+
+impl From<u64> for ChannelKey {
+    fn from(id: u64) -> Self {
+        ChannelKey::Id(id)
+    }
+}
+
+const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Identifies a [`Connection`](crate::Connection) in logs, tracing spans and errors.
+///
+/// Laid out like a ULID: the upper 48 bits are the creation time in milliseconds
+/// since the Unix epoch and the lower 80 bits are random, so ids sort by creation
+/// time. Displayed as 26 Crockford base32 characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ConnectionId(pub u128);
+
+impl ConnectionId {
+    pub fn new() -> Self {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        let random = rand::random::<u128>() & ((1 << 80) - 1);
+        ConnectionId((u128::from(millis & 0xFFFF_FFFF_FFFF) << 80) | random)
+    }
+
+    pub fn value(&self) -> u128 {
+        self.0
+    }
+
+    /// Milliseconds since the Unix epoch at which the id was created
+    pub fn timestamp_millis(&self) -> u64 {
+        (self.0 >> 80) as u64
+    }
+}
+
+impl Default for ConnectionId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ConnectionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut encoded = [0u8; 26];
+        for (i, c) in encoded.iter_mut().enumerate() {
+            let shift = 5 * (25 - i);
+            *c = CROCKFORD_ALPHABET[((self.0 >> shift) & 0x1F) as usize];
+        }
+        f.write_str(str::from_utf8(&encoded).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl From<u128> for ConnectionId {
+    fn from(value: u128) -> Self {
+        ConnectionId(value)
+    }
+}
+
+impl From<ConnectionId> for u128 {
+    fn from(id: ConnectionId) -> Self {
+        id.0
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The unlinking state of the link protocol introduced in OTP 23.
+//!
+//! A process that unlinks sends `UNLINK_ID` and stays unlinking until the peer answers
+//! with an `UNLINK_ID_ACK` that carries the same id. A link exit signal the other process
+//! sent in the meantime was sent before it saw the unlink, and is ignored.
+
+use crate::control::ControlMessage;
+use erltf::types::ExternalPid;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Links being removed with `UNLINK_ID`, keyed by the unlinking process and the other
+/// process. Clones share their state, so a read half can consult the unlinks of its connection.
+#[derive(Debug, Clone, Default)]
+pub struct UnlinkTracker {
+    unlinking: Arc<Mutex<HashMap<(ExternalPid, ExternalPid), u64>>>,
+}
+
+impl UnlinkTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `from` sent an `UNLINK_ID` with `id` to `to`.
+    pub fn begin(&self, from: &ExternalPid, to: &ExternalPid, id: u64) {
+        self.lock().insert((from.clone(), to.clone()), id);
+    }
+
+    /// Forgets an unlink, e.g. one whose `UNLINK_ID` could not be sent.
+    pub fn forget(&self, from: &ExternalPid, to: &ExternalPid) {
+        self.lock().remove(&(from.clone(), to.clone()));
+    }
+
+    pub fn is_unlinking(&self, from: &ExternalPid, to: &ExternalPid) -> bool {
+        self.lock().contains_key(&(from.clone(), to.clone()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Applies a received control message. An `UNLINK_ID_ACK` completes the unlink it
+    /// acknowledges. Returns `false` for a link exit signal that must be ignored
+    /// because its receiver is unlinking from its sender.
+    pub fn accept(&self, control: &ControlMessage) -> bool {
+        match control {
+            ControlMessage::UnlinkIdAck {
+                id,
+                from_pid,
+                to_pid,
+            } => {
+                if let (Some(from), Some(to)) = (from_pid.as_pid(), to_pid.as_pid()) {
+                    let key = (to.clone(), from.clone());
+                    let mut unlinking = self.lock();
+                    if unlinking.get(&key) == Some(id) {
+                        unlinking.remove(&key);
+                    }
+                }
+                true
+            }
+            ControlMessage::Exit {
+                from_pid, to_pid, ..
+            }
+            | ControlMessage::ExitTt {
+                from_pid, to_pid, ..
+            }
+            | ControlMessage::PayloadExit { from_pid, to_pid }
+            | ControlMessage::PayloadExitTt {
+                from_pid, to_pid, ..
+            } => match (from_pid.as_pid(), to_pid.as_pid()) {
+                (Some(from), Some(to)) => !self.is_unlinking(to, from),
+                _ => true,
+            },
+            _ => true,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<(ExternalPid, ExternalPid), u64>> {
+        self.unlinking.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, DistributionFlags, Error};
+use erltf::OwnedTerm;
+use erltf::decoder::decode_with_trailing;
+use erltf::types::{Atom, ExternalPid, ExternalReference};
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "alias_cookie";
+const PEER: &str = "caller@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const ALIAS_SEND: i64 = 33;
+
+async fn accept_handshake(listener: &TcpListener, flags: DistributionFlags) -> TcpStream {
+    TestPeer::new(PEER, COOKIE)
+        .with_flags(flags)
+        .accept(listener)
+        .await
+}
+
+/// Collects the non-tick frames the client sends until it disconnects.
+async fn read_frames(mut stream: TcpStream) -> Vec<Vec<u8>> {
+    let mut frames = Vec::new();
+    while let Ok(len) = stream.read_u32().await {
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        frames.push(data);
+    }
+    frames
+}
+
+fn peer_flags() -> DistributionFlags {
+    DistributionFlags::default().difference(DistributionFlags::DIST_HDR_ATOM_CACHE)
+}
+
+fn config(port: u16) -> ConnectionConfig {
+    ConnectionConfig::new("rust@localhost", PEER, COOKIE).with_remote_port(port)
+}
+
+fn from_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)
+}
+
+fn alias() -> ExternalReference {
+    ExternalReference::new(Atom::new(PEER), 1, vec![7, 8, 9])
+}
+
+#[tokio::test]
+async fn test_send_to_alias_emits_alias_send() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer =
+        tokio::spawn(
+            async move { read_frames(accept_handshake(&listener, peer_flags()).await).await },
+        );
+
+    let mut conn = Connection::new(config(port));
+    conn.connect().await.unwrap();
+    let reply = OwnedTerm::tuple(vec![OwnedTerm::atom("tag"), OwnedTerm::atom("pong")]);
+    conn.send_to_alias(from_pid(), alias(), reply.clone())
+        .await
+        .unwrap();
+    conn.close().await.unwrap();
+
+    let frames = peer.await.unwrap();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0][0], PASS_THROUGH);
+    let (control, rest) = decode_with_trailing(&frames[0][1..]).unwrap();
+    assert_eq!(
+        control,
+        OwnedTerm::tuple(vec![
+            OwnedTerm::integer(ALIAS_SEND),
+            OwnedTerm::Pid(from_pid()),
+            OwnedTerm::Reference(alias()),
+        ])
+    );
+    let (message, rest) = decode_with_trailing(rest).unwrap();
+    assert_eq!(message, reply);
+    assert!(rest.is_empty());
+}
+
+#[tokio::test]
+async fn test_send_to_alias_requires_negotiated_aliases() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let flags = peer_flags().difference(DistributionFlags::ALIAS);
+        read_frames(accept_handshake(&listener, flags).await).await
+    });
+
+    let mut conn = Connection::new(config(port));
+    conn.connect().await.unwrap();
+    let err = conn
+        .send_to_alias(from_pid(), alias(), OwnedTerm::atom("dropped"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::AliasesNotSupported { ref node } if node == PEER));
+    conn.close().await.unwrap();
+
+    assert!(peer.await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_send_to_alias_requires_a_connection() {
+    let mut conn = Connection::new(config(1));
+    let result = conn
+        .send_to_alias(from_pid(), alias(), OwnedTerm::atom("dropped"))
+        .await;
+
+    assert!(matches!(result, Err(Error::InvalidState { .. })));
+}
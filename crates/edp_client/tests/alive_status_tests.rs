@@ -0,0 +1,76 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::test_peer::{TestPeer, read_handshake_message, write_status};
+use edp_client::{Connection, ConnectionConfig, Error};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+const COOKIE: &str = "alive_status_cookie";
+const PEER: &str = "peer@127.0.0.1";
+
+/// Answers the name with `alive` and continues the handshake if the answer is `true`.
+/// Returns the answer.
+async fn accept_with_alive(listener: &TcpListener) -> Vec<u8> {
+    let (mut stream, _) = listener.accept().await.unwrap();
+    read_handshake_message(&mut stream).await;
+    write_status(&mut stream, "alive").await;
+    let answer = read_handshake_message(&mut stream).await;
+    if answer == b"strue" {
+        TestPeer::new(PEER, COOKIE)
+            .complete_handshake(&mut stream)
+            .await;
+    }
+    answer
+}
+
+async fn connect_to_alive_peer(
+    config: ConnectionConfig,
+) -> (Connection, Result<(), Error>, Vec<u8>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_with_alive(&listener).await });
+    let mut conn = Connection::new(
+        config
+            .with_remote_port(port)
+            .with_timeout(Duration::from_secs(5)),
+    );
+    let result = conn.connect().await;
+    (conn, result, peer.await.unwrap())
+}
+
+#[tokio::test]
+async fn test_alive_peer_is_asked_to_replace_its_connection() {
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE);
+    let (conn, result, answer) = connect_to_alive_peer(config).await;
+
+    result.unwrap();
+    assert!(conn.is_connected());
+    assert_eq!(answer, b"strue");
+}
+
+#[tokio::test]
+async fn test_declining_an_alive_peer_fails_the_connect() {
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_alive_answer(|node| node != PEER);
+    let (conn, result, answer) = connect_to_alive_peer(config).await;
+
+    let err = result.unwrap_err();
+    assert!(
+        matches!(err.inner(), Error::PeerConnectionAlive { node } if node == PEER),
+        "{err:?}"
+    );
+    assert!(!err.is_recoverable());
+    assert!(!conn.is_connected());
+    assert_eq!(answer, b"sfalse");
+}
@@ -0,0 +1,150 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::analysis::{CapturedMessage, SequenceDiagram};
+use edp_client::control::{ControlMessage, ControlMessageType};
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid};
+use std::time::Duration;
+
+const RUST_NODE: &str = "rust@localhost";
+const ERLANG_NODE: &str = "erlang@localhost";
+
+fn pid(node: &str, id: u32) -> OwnedTerm {
+    OwnedTerm::Pid(ExternalPid::new(Atom::new(node), id, 0, 1))
+}
+
+fn rpc_exchange() -> SequenceDiagram {
+    let mut diagram = SequenceDiagram::new().with_title("rpc call");
+    diagram.push(
+        CapturedMessage::new(
+            RUST_NODE,
+            ERLANG_NODE,
+            ControlMessage::reg_send(
+                pid(RUST_NODE, 1),
+                OwnedTerm::Atom(Atom::new("")),
+                OwnedTerm::Atom(Atom::new("rex")),
+            ),
+            Some(OwnedTerm::Atom(Atom::new("ping"))),
+        )
+        .with_timestamp(Duration::from_micros(0)),
+    );
+    diagram.push(
+        CapturedMessage::new(
+            ERLANG_NODE,
+            RUST_NODE,
+            ControlMessage::send(OwnedTerm::Atom(Atom::new("")), pid(RUST_NODE, 1)),
+            Some(OwnedTerm::Atom(Atom::new("pong"))),
+        )
+        .with_timestamp(Duration::from_micros(1500)),
+    );
+    diagram
+}
+
+#[test]
+fn test_control_message_type_names() {
+    assert_eq!(ControlMessageType::RegSend.name(), "REG_SEND");
+    assert_eq!(ControlMessageType::MonitorPExit.name(), "MONITOR_P_EXIT");
+    assert_eq!(ControlMessageType::AliasSendTt.name(), "ALIAS_SEND_TT");
+}
+
+#[test]
+fn test_captured_message_participants() {
+    let diagram = rpc_exchange();
+    let messages = diagram.messages();
+
+    assert_eq!(messages[0].message_type(), "REG_SEND");
+    assert_eq!(messages[0].sender(), "rust@localhost <1.0.1>");
+    assert_eq!(messages[0].receiver(), "rex (erlang@localhost)");
+
+    // SEND does not carry the sender pid
+    assert_eq!(messages[1].message_type(), "SEND");
+    assert_eq!(messages[1].sender(), ERLANG_NODE);
+    assert_eq!(messages[1].receiver(), "rust@localhost <1.0.1>");
+
+    assert_eq!(
+        diagram.participants(),
+        vec![
+            "rust@localhost <1.0.1>".to_string(),
+            "rex (erlang@localhost)".to_string(),
+            ERLANG_NODE.to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_render_mermaid() {
+    let expected = "\
+---
+title: rpc call
+---
+sequenceDiagram
+    participant P0 as rust@localhost <1.0.1>
+    participant P1 as rex (erlang@localhost)
+    participant P2 as erlang@localhost
+    P0->>P1: [+0.000 ms] REG_SEND ping
+    P2->>P0: [+1.500 ms] SEND pong
+";
+    assert_eq!(rpc_exchange().to_mermaid(), expected);
+}
+
+#[test]
+fn test_render_plantuml() {
+    let expected = "\
+@startuml
+title rpc call
+participant \"rust@localhost <1.0.1>\" as P0
+participant \"rex (erlang@localhost)\" as P1
+participant \"erlang@localhost\" as P2
+P0 -> P1 : [+0.000 ms] REG_SEND ping
+P2 -> P0 : [+1.500 ms] SEND pong
+@enduml
+";
+    assert_eq!(rpc_exchange().to_plantuml(), expected);
+}
+
+#[test]
+fn test_payloads_can_be_hidden_or_truncated() {
+    let mut diagram = SequenceDiagram::new().with_max_payload_len(5);
+    diagram.push(CapturedMessage::new(
+        RUST_NODE,
+        ERLANG_NODE,
+        ControlMessage::link(pid(RUST_NODE, 1), pid(ERLANG_NODE, 2)),
+        Some(OwnedTerm::Atom(Atom::new("a_long_payload"))),
+    ));
+    assert!(diagram.to_mermaid().contains("P0->>P1: LINK a_lon...\n"));
+
+    let diagram = diagram.with_payloads(false);
+    assert!(diagram.to_mermaid().contains("P0->>P1: LINK\n"));
+}
+
+#[test]
+fn test_mermaid_escapes_special_characters() {
+    let mut diagram = SequenceDiagram::new();
+    diagram.push(CapturedMessage::new(
+        RUST_NODE,
+        ERLANG_NODE,
+        ControlMessage::link(pid(RUST_NODE, 1), pid(ERLANG_NODE, 2)),
+        Some(OwnedTerm::Atom(Atom::new("a;b#c"))),
+    ));
+    assert!(diagram.to_mermaid().contains("LINK a#59;b#35;c\n"));
+}
+
+#[test]
+fn test_empty_diagram() {
+    let diagram = SequenceDiagram::new();
+    assert!(diagram.is_empty());
+    assert_eq!(diagram.to_mermaid(), "sequenceDiagram\n");
+    assert_eq!(diagram.to_plantuml(), "@startuml\n@enduml\n");
+}
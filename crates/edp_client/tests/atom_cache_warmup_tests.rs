@@ -0,0 +1,128 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, DistributionFlags};
+use erltf::decoder::AtomCache;
+use erltf::tags::DIST_HEADER;
+use erltf::types::{Atom, ExternalPid};
+use erltf::{OwnedTerm, decode_with_atom_cache};
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "warmup_cookie";
+
+fn flags() -> DistributionFlags {
+    DistributionFlags::default() | DistributionFlags::DIST_HDR_ATOM_CACHE
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new("peer@127.0.0.1", COOKIE)
+        .with_flags(flags())
+        .accept(listener)
+        .await
+}
+
+async fn read_frame(stream: &mut TcpStream) -> Vec<u8> {
+    loop {
+        let len = stream.read_u32().await.unwrap();
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        return data;
+    }
+}
+
+fn header_refs(frame: &[u8]) -> u8 {
+    assert_eq!(frame[1], DIST_HEADER);
+    frame[2]
+}
+
+fn message() -> OwnedTerm {
+    OwnedTerm::Tuple(vec![
+        OwnedTerm::Atom(Atom::new("telemetry_event")),
+        OwnedTerm::Atom(Atom::new("http_request_completed")),
+        OwnedTerm::Integer(200),
+    ])
+}
+
+#[tokio::test]
+async fn test_frames_reuse_atom_cache_and_warm_up_after_reconnect() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let to = ExternalPid::new(Atom::new("peer@127.0.0.1"), 1, 0, 1);
+
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        let mut cache = AtomCache::new();
+        let mut frames = Vec::new();
+        for _ in 0..2 {
+            let frame = read_frame(&mut stream).await;
+            let (_, payload) = decode_with_atom_cache(&frame, &mut cache).unwrap();
+            assert_eq!(payload, Some(message()));
+            frames.push(frame);
+        }
+        drop(stream);
+
+        // The second connection starts with an empty cache on the peer
+        let mut stream = accept_handshake(&listener).await;
+        let frame = read_frame(&mut stream).await;
+        let mut cache = AtomCache::new();
+        decode_with_atom_cache(&frame, &mut cache).unwrap();
+        frames.push(frame);
+        (frames, cache.entries_len())
+    });
+
+    let config = ConnectionConfig::new("rust@localhost", "peer@127.0.0.1", COOKIE)
+        .with_remote_port(port)
+        .with_flags(flags())
+        .with_atom_cache_seed([Atom::new("seeded_atom")])
+        .with_atom_cache_warmup(16);
+    let mut conn = Connection::new(config);
+    conn.connect().await.unwrap();
+    let negotiated = conn.negotiated_flags().unwrap();
+    assert!(negotiated.has(DistributionFlags::DIST_HDR_ATOM_CACHE));
+
+    let from = ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1);
+    conn.send_message(from.clone(), to.clone(), message())
+        .await
+        .unwrap();
+    conn.send_message(from.clone(), to.clone(), message())
+        .await
+        .unwrap();
+    assert!(conn.outgoing_atom_cache().contains("seeded_atom"));
+    let hot = conn.hot_atoms(16);
+    assert!(hot.contains(&Atom::new("telemetry_event")));
+    assert!(!hot.contains(&Atom::new("seeded_atom")));
+
+    conn.close().await.unwrap();
+    conn.connect().await.unwrap();
+    conn.send_opts(
+        to.clone(),
+        OwnedTerm::Integer(1),
+        edp_client::SendOpts::new(),
+    )
+    .await
+    .unwrap();
+
+    let (frames, entries_after_reconnect) = peer.await.unwrap();
+    // The seed rides along with the first frame
+    assert_eq!(header_refs(&frames[0]), hot.len() as u8 + 1);
+    assert_eq!(header_refs(&frames[1]), hot.len() as u8);
+    assert!(frames[1].len() < frames[0].len());
+    // The warmup re-announces the hot atoms, though the frame does not use them
+    assert!(entries_after_reconnect > hot.len());
+}
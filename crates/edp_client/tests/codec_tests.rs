@@ -0,0 +1,367 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use bytes::Bytes;
+use edp_client::codec::DecodedFrame;
+use edp_client::control::ControlMessage;
+use edp_client::ctrl;
+use edp_client::errors::Result;
+use edp_client::test_peer::TestPeer;
+use edp_client::{
+    Codec, Connection, ConnectionConfig, DecodeOffload, DistributionFlags, EncodeContext, EtfCodec,
+};
+use erltf::decoder::decode_with_trailing;
+use erltf::types::{Atom, ExternalPid};
+use erltf::{DecodeConfig, EncodeConfig, FloatPolicy, OwnedTerm};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "codec_cookie";
+const PEER: &str = "codec_peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+
+/// Counts what goes through the ETF codec it wraps.
+#[derive(Clone, Default)]
+struct CountingCodec {
+    inner: EtfCodec,
+    encoded: Arc<AtomicUsize>,
+    decoded: Arc<AtomicUsize>,
+    resets: Arc<AtomicUsize>,
+}
+
+impl Codec for CountingCodec {
+    fn encode(
+        &mut self,
+        control: &OwnedTerm,
+        payload: Option<&OwnedTerm>,
+        context: &EncodeContext,
+    ) -> Result<Vec<u8>> {
+        self.encoded.fetch_add(1, Ordering::Relaxed);
+        self.inner.encode(control, payload, context)
+    }
+
+    fn decode(
+        &mut self,
+        data: &Bytes,
+        config: &DecodeConfig,
+        raw_payloads: bool,
+    ) -> Result<DecodedFrame> {
+        self.decoded.fetch_add(1, Ordering::Relaxed);
+        self.inner.decode(data, config, raw_payloads)
+    }
+
+    fn reset(&mut self, config: &ConnectionConfig) {
+        self.resets.fetch_add(1, Ordering::Relaxed);
+        self.inner.reset(config);
+    }
+}
+
+/// Panics on frames that mention `boom`, otherwise decodes like the ETF codec it wraps.
+#[derive(Clone, Default)]
+struct PanickingCodec {
+    inner: EtfCodec,
+}
+
+impl Codec for PanickingCodec {
+    fn encode(
+        &mut self,
+        control: &OwnedTerm,
+        payload: Option<&OwnedTerm>,
+        context: &EncodeContext,
+    ) -> Result<Vec<u8>> {
+        self.inner.encode(control, payload, context)
+    }
+
+    fn decode(
+        &mut self,
+        data: &Bytes,
+        config: &DecodeConfig,
+        raw_payloads: bool,
+    ) -> Result<DecodedFrame> {
+        let decoded = self.inner.decode(data, config, raw_payloads);
+        assert!(!data.windows(4).any(|w| w == b"boom"), "boom");
+        decoded
+    }
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+fn local_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)
+}
+
+fn send_control() -> OwnedTerm {
+    ctrl!(Send to OwnedTerm::Pid(local_pid())).to_term()
+}
+
+fn send_frame(payload: &str) -> Vec<u8> {
+    let mut body = EtfCodec::new()
+        .encode(
+            &send_control(),
+            Some(&OwnedTerm::atom(payload)),
+            &current(None),
+        )
+        .unwrap();
+    let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+    frame.append(&mut body);
+    frame
+}
+
+fn current(flags: Option<DistributionFlags>) -> EncodeContext {
+    EncodeContext {
+        flags,
+        config: EncodeConfig::new(),
+    }
+}
+
+#[test]
+fn test_etf_codec_round_trips_pass_through_frames() {
+    let mut codec = EtfCodec::new();
+    let payload = OwnedTerm::tuple(vec![OwnedTerm::atom("hello"), OwnedTerm::integer(1)]);
+    let body = Bytes::from(
+        codec
+            .encode(&send_control(), Some(&payload), &current(None))
+            .unwrap(),
+    );
+    assert_eq!(body[0], PASS_THROUGH);
+
+    let config = DecodeConfig::default().atoms_as_binaries_after(1_000);
+    let (control, decoded, raw) = codec.decode(&body, &config, false).unwrap();
+    assert_eq!(control, send_control());
+    assert_eq!(decoded, Some(payload));
+    assert_eq!(raw, None);
+    assert!(codec.atom_table().contains("hello"));
+}
+
+#[test]
+fn test_etf_codec_records_atoms_only_under_a_limit() {
+    let mut codec = EtfCodec::new();
+    let body = Bytes::from(send_frame("unlimited")[4..].to_vec());
+
+    codec
+        .decode(&body, &DecodeConfig::default(), false)
+        .unwrap();
+    assert_eq!(codec.atom_table().distinct_count(), 0);
+    assert_eq!(codec.atom_table().total_decoded(), 3);
+
+    let config = DecodeConfig::default().atoms_as_binaries_after(1_000);
+    codec.decode(&body, &config, false).unwrap();
+    assert!(codec.atom_table().contains("unlimited"));
+
+    codec.reset(&ConnectionConfig::new("rust@localhost", PEER, COOKIE));
+    assert_eq!(codec.atom_table().distinct_count(), 0);
+    assert!(!codec.atom_table().contains("unlimited"));
+}
+
+#[test]
+fn test_etf_codec_uses_the_atom_cache_when_negotiated() {
+    let mut codec = EtfCodec::new();
+    let flags = DistributionFlags::default() | DistributionFlags::DIST_HDR_ATOM_CACHE;
+    let payload = OwnedTerm::atom("cached_atom");
+
+    let body = Bytes::from(
+        codec
+            .encode(&send_control(), Some(&payload), &current(Some(flags)))
+            .unwrap(),
+    );
+    assert_ne!(body[0], PASS_THROUGH);
+    assert!(!codec.outgoing_atom_cache().is_empty());
+
+    let mut peer = EtfCodec::new();
+    let (control, decoded, _) = peer.decode(&body, &DecodeConfig::default(), false).unwrap();
+    assert_eq!(control, send_control());
+    assert_eq!(decoded, Some(payload));
+
+    codec.discard_unsent();
+    assert!(codec.outgoing_atom_cache().is_empty());
+}
+
+#[test]
+fn test_etf_codec_applies_the_encode_config() {
+    let flags = DistributionFlags::default() | DistributionFlags::DIST_HDR_ATOM_CACHE;
+    let payload = OwnedTerm::Float(f64::NEG_INFINITY);
+    let context = |flags| EncodeContext {
+        flags,
+        config: EncodeConfig::new().with_float_policy(FloatPolicy::SaturateToMax),
+    };
+
+    for flags in [None, Some(flags)] {
+        let mut codec = EtfCodec::new();
+        let body = Bytes::from(
+            codec
+                .encode(&send_control(), Some(&payload), &context(flags))
+                .unwrap(),
+        );
+        let (_, decoded, _) = EtfCodec::new()
+            .decode(&body, &DecodeConfig::default(), false)
+            .unwrap();
+        assert_eq!(decoded, Some(OwnedTerm::Float(f64::MIN)));
+        assert!(
+            codec
+                .encode(&send_control(), Some(&payload), &current(flags))
+                .is_err()
+        );
+    }
+}
+
+#[test]
+fn test_etf_codec_shares_large_payload_binaries_with_the_frame() {
+    let payload = OwnedTerm::tuple(vec![
+        OwnedTerm::binary(vec![7; 4096]),
+        OwnedTerm::binary(vec![1, 2, 3]),
+    ]);
+    let config = DecodeConfig::default().with_shared_binaries(1024);
+    let cached = DistributionFlags::default() | DistributionFlags::DIST_HDR_ATOM_CACHE;
+
+    for flags in [None, Some(cached)] {
+        let mut codec = EtfCodec::new();
+        let body = Bytes::from(
+            codec
+                .encode(&send_control(), Some(&payload), &current(flags))
+                .unwrap(),
+        );
+        let (_, decoded, _) = EtfCodec::new().decode(&body, &config, false).unwrap();
+        let decoded = decoded.unwrap();
+
+        let shared = decoded[0].as_shared_binary().unwrap();
+        assert_eq!(shared.as_ref(), &[7; 4096][..]);
+        let frame = body.as_ptr_range();
+        assert!(frame.contains(&shared.as_ptr()));
+        assert_eq!(decoded[1], OwnedTerm::binary(vec![1, 2, 3]));
+    }
+}
+
+#[test]
+fn test_etf_codec_keeps_undecodable_payloads_when_asked() {
+    let mut codec = EtfCodec::new();
+    let mut body = codec.encode(&send_control(), None, &current(None)).unwrap();
+    body.extend([131, 255]);
+    let body = Bytes::from(body);
+
+    assert!(
+        codec
+            .decode(&body, &DecodeConfig::default(), false)
+            .is_err()
+    );
+    let (_, payload, raw) = codec.decode(&body, &DecodeConfig::default(), true).unwrap();
+    assert_eq!(payload, None);
+    assert_eq!(raw, Some(vec![131, 255]));
+}
+
+#[tokio::test]
+async fn test_connection_encodes_and_decodes_with_its_codec() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener).await });
+
+    let codec = CountingCodec::default();
+    let (encoded, decoded, resets) = (
+        Arc::clone(&codec.encoded),
+        Arc::clone(&codec.decoded),
+        Arc::clone(&codec.resets),
+    );
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(Duration::from_secs(5))
+        .with_decode_config(DecodeConfig::new().atoms_as_binaries_after(1_000));
+    let mut conn = Connection::with_codec(config, codec);
+    conn.connect().await.unwrap();
+    let mut stream = peer.await.unwrap();
+    assert_eq!(resets.load(Ordering::Relaxed), 1);
+
+    let remote = ExternalPid::new(Atom::new(PEER), 7, 0, 1);
+    conn.send_message(local_pid(), remote, OwnedTerm::atom("ping"))
+        .await
+        .unwrap();
+    assert_eq!(encoded.load(Ordering::Relaxed), 1);
+
+    let len = stream.read_u32().await.unwrap();
+    let mut data = vec![0u8; len as usize];
+    stream.read_exact(&mut data).await.unwrap();
+    assert_eq!(data[0], PASS_THROUGH);
+    let (_, payload) = decode_with_trailing(&data[1..]).unwrap();
+    assert_eq!(
+        decode_with_trailing(payload).unwrap().0,
+        OwnedTerm::atom("ping")
+    );
+
+    stream.write_all(&send_frame("pong")).await.unwrap();
+    let (control, payload) = conn.receive_message().await.unwrap();
+    assert!(matches!(control, ControlMessage::Send { .. }));
+    assert_eq!(payload, Some(OwnedTerm::atom("pong")));
+    assert_eq!(decoded.load(Ordering::Relaxed), 1);
+    assert!(conn.codec().inner.atom_table().contains("pong"));
+}
+
+#[tokio::test]
+async fn test_connection_encodes_with_its_encode_config() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener).await });
+
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(Duration::from_secs(5))
+        .with_encode_config(EncodeConfig::new().with_float_policy(FloatPolicy::EncodeAsAtomTag));
+    let mut conn = Connection::new(config);
+    conn.connect().await.unwrap();
+    let mut stream = peer.await.unwrap();
+
+    let remote = ExternalPid::new(Atom::new(PEER), 7, 0, 1);
+    conn.send_message(local_pid(), remote, OwnedTerm::Float(f64::NAN))
+        .await
+        .unwrap();
+
+    let len = stream.read_u32().await.unwrap();
+    let mut data = vec![0u8; len as usize];
+    stream.read_exact(&mut data).await.unwrap();
+    let (_, payload, _) = EtfCodec::new()
+        .decode(&Bytes::from(data), &DecodeConfig::default(), false)
+        .unwrap();
+    assert_eq!(payload, Some(OwnedTerm::atom("nan")));
+}
+
+#[tokio::test]
+async fn test_offloaded_decodes_that_panic_leave_the_codec_as_it_was() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener).await });
+
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(Duration::from_secs(5))
+        .with_decode_offload(DecodeOffload::new(0))
+        .with_decode_config(DecodeConfig::new().atoms_as_binaries_after(1_000));
+    let mut conn = Connection::with_codec(config, PanickingCodec::default());
+    conn.connect().await.unwrap();
+    let mut stream = peer.await.unwrap();
+
+    for payload in ["before", "boom", "after"] {
+        stream.write_all(&send_frame(payload)).await.unwrap();
+    }
+    let (_, payload) = conn.receive_message().await.unwrap();
+    assert_eq!(payload, Some(OwnedTerm::atom("before")));
+    assert!(conn.receive_message().await.is_err());
+    let (_, payload) = conn.receive_message().await.unwrap();
+    assert_eq!(payload, Some(OwnedTerm::atom("after")));
+
+    let atoms = conn.codec().inner.atom_table();
+    assert!(atoms.contains("before"));
+    assert!(!atoms.contains("boom"));
+    assert!(atoms.contains("after"));
+}
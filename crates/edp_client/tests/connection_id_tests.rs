@@ -0,0 +1,169 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, ConnectionId, DistributionFlags, Error};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "connection_id_cookie";
+const PEER: &str = "connection_id_peer@127.0.0.1";
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE)
+        .with_flags(DistributionFlags::default())
+        .accept(listener)
+        .await
+}
+
+fn config(port: u16) -> ConnectionConfig {
+    ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(Duration::from_secs(5))
+}
+
+/// A port nothing listens on.
+async fn closed_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    listener.local_addr().unwrap().port()
+}
+
+#[test]
+fn test_connection_id_display() {
+    assert_eq!(ConnectionId(0).to_string(), "00000000000000000000000000");
+    assert_eq!(
+        ConnectionId(u128::MAX).to_string(),
+        "7ZZZZZZZZZZZZZZZZZZZZZZZZZ"
+    );
+    assert_eq!(ConnectionId(33).to_string(), "00000000000000000000000011");
+
+    let id = ConnectionId::new().to_string();
+    assert_eq!(id.len(), 26);
+    assert!(
+        id.chars()
+            .all(|c| c.is_ascii_digit() || "ABCDEFGHJKMNPQRSTVWXYZ".contains(c))
+    );
+}
+
+#[test]
+fn test_connection_ids_are_unique_and_carry_their_creation_time() {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    let a = ConnectionId::new();
+    let b = ConnectionId::new();
+    assert_ne!(a, b);
+    assert!(a.timestamp_millis() >= now);
+    assert!(a.timestamp_millis() - now < 60_000);
+
+    let earlier = ConnectionId((u128::from(now - 1) << 80) | u128::MAX >> 48);
+    assert!(earlier < a);
+    assert_eq!(u128::from(ConnectionId::from(7u128)), 7);
+}
+
+#[test]
+fn test_connections_get_an_id_unless_configured() {
+    let a = Connection::new(config(1));
+    let b = Connection::new(config(1));
+    assert_ne!(a.id(), b.id());
+
+    let id = ConnectionId::new();
+    let conn = Connection::new(config(1).with_connection_id(id));
+    assert_eq!(conn.id(), id);
+    assert_eq!(conn.receive_options().connection_id, Some(id));
+    assert!(conn.receive_options().span.is_some());
+}
+
+#[test]
+fn test_error_with_connection_id() {
+    let id = ConnectionId::new();
+    let err = Error::Timeout(Duration::from_secs(1)).with_connection_id(id);
+    assert_eq!(err.connection_id(), Some(id));
+    assert!(matches!(err.inner(), Error::Timeout(_)));
+    assert!(err.is_timeout());
+    assert!(err.is_recoverable());
+    assert!(err.to_string().contains(&id.to_string()));
+
+    let rewrapped = err.with_connection_id(ConnectionId::new());
+    assert_eq!(rewrapped.connection_id(), Some(id));
+    assert!(matches!(rewrapped.into_inner(), Error::Timeout(_)));
+
+    assert_eq!(Error::ConnectionClosed.connection_id(), None);
+    assert!(matches!(
+        Error::ConnectionClosed.inner(),
+        Error::ConnectionClosed
+    ));
+}
+
+#[tokio::test]
+async fn test_connect_errors_carry_the_connection_id() {
+    let mut conn = Connection::new(config(closed_port().await));
+    let err = conn.connect().await.unwrap_err();
+    assert_eq!(err.connection_id(), Some(conn.id()));
+    assert!(err.is_recoverable());
+}
+
+#[tokio::test]
+async fn test_connection_id_survives_reconnects() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let first = accept_handshake(&listener).await;
+        drop(first);
+        accept_handshake(&listener).await
+    });
+
+    let mut conn = Connection::new(config(port));
+    let id = conn.id();
+    conn.connect().await.unwrap();
+
+    let err = conn.receive_message().await.unwrap_err();
+    assert_eq!(err.connection_id(), Some(id));
+    assert!(err.is_connection_closed());
+
+    conn.close().await.unwrap();
+    conn.connect().await.unwrap();
+    assert_eq!(conn.id(), id);
+    let _second = peer.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_read_half_errors_carry_the_connection_id() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        drop(accept_handshake(&listener).await);
+    });
+
+    let mut conn = Connection::new(config(port));
+    conn.connect().await.unwrap();
+    peer.await.unwrap();
+    let mut read_half = conn.take_read_half().unwrap();
+    let options = conn.receive_options();
+
+    let err = Connection::receive_message_from_read_half_with_options(
+        &mut read_half,
+        conn.timeout(),
+        &options,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.connection_id(), Some(conn.id()));
+
+    let err = Connection::receive_message_from_read_half(&mut read_half, conn.timeout())
+        .await
+        .unwrap_err();
+    assert_eq!(err.connection_id(), None);
+}
@@ -0,0 +1,437 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::control::ControlMessageType;
+use edp_client::test_peer::TestPeer;
+use edp_client::{
+    Connection, ConnectionConfig, ConnectionMetrics, MessageDirection, SizeHistogram,
+};
+use edp_client::{ctrl, pid};
+use erltf::OwnedTerm;
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "metrics_cookie";
+const PEER: &str = "metrics_peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+fn send_frame(message: &OwnedTerm) -> Vec<u8> {
+    let control = ctrl!(Send to pid!("rust@localhost", 1, 0));
+    pass_through_frame(&control.to_term(), message)
+}
+
+fn reg_send_frame(to_name: &str, message: &OwnedTerm) -> Vec<u8> {
+    let control = ctrl!(RegSend from pid!(PEER, 1, 0), to to_name);
+    pass_through_frame(&control.to_term(), message)
+}
+
+fn pass_through_frame(control: &OwnedTerm, message: &OwnedTerm) -> Vec<u8> {
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(control).unwrap());
+    body.extend(encode(message).unwrap());
+    let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+    frame.extend(body);
+    frame
+}
+
+#[test]
+fn test_percentiles_use_nearest_rank() {
+    let metrics = ConnectionMetrics::new(100);
+    assert_eq!(metrics.rtt_percentile(50.0), None);
+    assert!(metrics.rtt_summary().is_none());
+
+    for n in (1..=100).rev() {
+        metrics.record_rtt(ms(n));
+    }
+    assert_eq!(metrics.rtt_samples(), 100);
+    assert_eq!(metrics.rtt_percentile(0.0), Some(ms(1)));
+    assert_eq!(metrics.rtt_percentile(50.0), Some(ms(50)));
+    assert_eq!(metrics.rtt_percentile(99.0), Some(ms(99)));
+    assert_eq!(metrics.rtt_percentile(250.0), Some(ms(100)));
+
+    let summary = metrics.rtt_summary().unwrap();
+    assert_eq!(summary.samples, 100);
+    assert_eq!(summary.min, ms(1));
+    assert_eq!(summary.max, ms(100));
+    assert_eq!(summary.last, ms(1));
+    assert_eq!(summary.p50, ms(50));
+    assert_eq!(summary.p90, ms(90));
+    assert_eq!(summary.p99, ms(99));
+}
+
+#[test]
+fn test_window_evicts_oldest_samples() {
+    let metrics = ConnectionMetrics::new(3);
+    for n in [100, 1, 2, 3] {
+        metrics.record_rtt(ms(n));
+    }
+    assert_eq!(metrics.rtt_samples(), 3);
+    assert_eq!(metrics.last_rtt(), Some(ms(3)));
+    assert_eq!(metrics.rtt_summary().unwrap().max, ms(3));
+}
+
+#[test]
+fn test_rtt_alert_fires_above_threshold() {
+    let metrics = ConnectionMetrics::default();
+    let alerts = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&alerts);
+    let observed = metrics.clone();
+    metrics.set_rtt_alert(
+        ms(50),
+        Arc::new(move |rtt| {
+            assert!(rtt > ms(50));
+            // The callback can read the metrics it was registered on
+            assert_eq!(observed.last_rtt(), Some(rtt));
+            counter.fetch_add(1, Ordering::SeqCst);
+        }),
+    );
+
+    metrics.record_rtt(ms(10));
+    metrics.record_rtt(ms(50));
+    metrics.record_rtt(ms(51));
+    assert_eq!(alerts.load(Ordering::SeqCst), 1);
+
+    metrics.clear_rtt_alert();
+    metrics.record_rtt(ms(500));
+    assert_eq!(alerts.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_config_rtt_alert_applies_to_connection_metrics() {
+    let alerts = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&alerts);
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_rtt_window(2)
+        .with_rtt_alert(ms(5), move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+    let conn = Connection::new(config);
+
+    for n in [1, 10, 20] {
+        conn.metrics().record_rtt(ms(n));
+    }
+    assert_eq!(conn.metrics().rtt_samples(), 2);
+    assert_eq!(alerts.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_received_ticks_are_counted() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let message = OwnedTerm::atom("after_ticks");
+    let frame = send_frame(&message);
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        for _ in 0..3 {
+            stream.write_all(&0u32.to_be_bytes()).await.unwrap();
+        }
+        stream.write_all(&frame).await.unwrap();
+        stream
+    });
+
+    let mut conn = Connection::new(
+        ConnectionConfig::new("rust@localhost", PEER, COOKIE).with_remote_port(port),
+    );
+    conn.connect().await.unwrap();
+    assert!(conn.metrics().last_received().is_none());
+
+    let (_, payload) = conn.receive_message().await.unwrap();
+    assert_eq!(payload, Some(message));
+    assert_eq!(conn.metrics().ticks_received(), 3);
+    assert!(conn.metrics().since_last_received().unwrap() < Duration::from_secs(5));
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_send_tick_writes_an_empty_frame() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        stream.read_u32().await.unwrap()
+    });
+
+    let mut conn = Connection::new(
+        ConnectionConfig::new("rust@localhost", PEER, COOKIE).with_remote_port(port),
+    );
+    conn.connect().await.unwrap();
+    conn.send_tick().await.unwrap();
+    assert_eq!(conn.metrics().ticks_sent(), 1);
+
+    assert_eq!(peer.await.unwrap(), 0);
+    conn.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_send_tick_requires_a_connection() {
+    let mut conn = Connection::new(ConnectionConfig::new("rust@localhost", PEER, COOKIE));
+    assert!(conn.send_tick().await.is_err());
+    assert_eq!(conn.metrics().ticks_sent(), 0);
+}
+
+#[tokio::test]
+async fn test_receiving_from_read_half_records_ticks() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let message = OwnedTerm::atom("via_read_half");
+    let frame = send_frame(&message);
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        stream.write_all(&0u32.to_be_bytes()).await.unwrap();
+        stream.write_all(&frame).await.unwrap();
+        stream
+    });
+
+    let mut conn = Connection::new(
+        ConnectionConfig::new("rust@localhost", PEER, COOKIE).with_remote_port(port),
+    );
+    conn.connect().await.unwrap();
+    let options = conn.receive_options();
+    let mut read_half = conn.take_read_half().unwrap();
+
+    let (_, payload) = Connection::receive_message_from_read_half_with_options(
+        &mut read_half,
+        conn.timeout(),
+        &options,
+    )
+    .await
+    .unwrap();
+    assert_eq!(payload, Some(message));
+    assert_eq!(conn.metrics().ticks_received(), 1);
+    assert!(conn.metrics().last_received().is_some());
+    drop(peer.await.unwrap());
+}
+
+#[test]
+fn test_size_histogram_buckets_and_percentiles() {
+    let mut histogram = SizeHistogram::default();
+    assert_eq!(histogram.percentile(50.0), None);
+    assert_eq!(histogram.mean(), None);
+
+    for size in [10, 64, 65, 1000, 100 << 20] {
+        histogram.record(size);
+    }
+    assert_eq!(histogram.messages(), 5);
+    assert_eq!(histogram.bytes(), 10 + 64 + 65 + 1000 + (100 << 20));
+    assert_eq!(histogram.max(), 100 << 20);
+
+    let buckets: Vec<_> = histogram.buckets().filter(|(_, n)| *n > 0).collect();
+    assert_eq!(
+        buckets,
+        vec![(Some(64), 2), (Some(128), 1), (Some(1024), 1), (None, 1)]
+    );
+    assert_eq!(histogram.percentile(0.0), Some(64));
+    assert_eq!(histogram.percentile(60.0), Some(128));
+    assert_eq!(histogram.percentile(80.0), Some(1024));
+    assert_eq!(histogram.percentile(100.0), Some(100 << 20));
+}
+
+#[test]
+fn test_top_talkers_rank_pairs_by_bytes() {
+    let metrics = ConnectionMetrics::default();
+    let rex = Atom::new("rex");
+    let logger = Atom::new("logger");
+    for _ in 0..3 {
+        metrics.record_message(
+            MessageDirection::Inbound,
+            Some(ControlMessageType::RegSend),
+            Some(&rex),
+            100,
+        );
+    }
+    metrics.record_message(
+        MessageDirection::Inbound,
+        Some(ControlMessageType::RegSend),
+        Some(&logger),
+        1000,
+    );
+    metrics.record_message(
+        MessageDirection::Inbound,
+        Some(ControlMessageType::Send),
+        None,
+        10,
+    );
+
+    let top = metrics.top_talkers(MessageDirection::Inbound, 2);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0].target, Some(logger));
+    assert_eq!((top[0].messages, top[0].bytes), (1, 1000));
+    assert_eq!(top[1].target, Some(rex));
+    assert_eq!(top[1].message_type, Some(ControlMessageType::RegSend));
+    assert_eq!((top[1].messages, top[1].bytes), (3, 300));
+    assert!(
+        metrics
+            .top_talkers(MessageDirection::Outbound, 10)
+            .is_empty()
+    );
+    assert_eq!(
+        metrics.message_sizes(MessageDirection::Inbound).messages(),
+        5
+    );
+
+    metrics.reset_message_stats();
+    assert!(
+        metrics
+            .top_talkers(MessageDirection::Inbound, 10)
+            .is_empty()
+    );
+    assert_eq!(
+        metrics.message_sizes(MessageDirection::Inbound).messages(),
+        0
+    );
+}
+
+#[test]
+fn test_top_talkers_keep_heavy_pairs_when_full() {
+    let metrics = ConnectionMetrics::default().with_top_talkers(2);
+    let heavy = Atom::new("heavy");
+    metrics.record_message(
+        MessageDirection::Outbound,
+        Some(ControlMessageType::RegSend),
+        Some(&heavy),
+        10_000,
+    );
+    for n in 0..50 {
+        let name = Atom::new(format!("light_{n}"));
+        metrics.record_message(
+            MessageDirection::Outbound,
+            Some(ControlMessageType::RegSend),
+            Some(&name),
+            10,
+        );
+    }
+
+    let top = metrics.top_talkers(MessageDirection::Outbound, 10);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0].target, Some(heavy));
+    assert_eq!(top[0].bytes, 10_000);
+    assert_eq!(top[0].overcount, 0);
+    // The last light pair inherited the counts of the ones it replaced
+    assert_eq!(top[1].target, Some(Atom::new("light_49")));
+    assert_eq!(top[1].bytes, 500);
+    assert_eq!(top[1].overcount, 490);
+    // Sizes are still recorded for every message
+    assert_eq!(
+        metrics.message_sizes(MessageDirection::Outbound).messages(),
+        51
+    );
+}
+
+#[test]
+fn test_zero_top_talkers_disables_tracking() {
+    let metrics = ConnectionMetrics::default().with_top_talkers(0);
+    metrics.record_message(MessageDirection::Inbound, None, None, 42);
+    assert!(
+        metrics
+            .top_talkers(MessageDirection::Inbound, 10)
+            .is_empty()
+    );
+    assert_eq!(metrics.message_sizes(MessageDirection::Inbound).bytes(), 42);
+}
+
+#[tokio::test]
+async fn test_connection_records_message_sizes_per_direction() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let large = OwnedTerm::binary(vec![0u8; 4096]);
+    let inbound = [
+        reg_send_frame("rex", &large),
+        reg_send_frame("rex", &large),
+        send_frame(&OwnedTerm::atom("small")),
+    ];
+    let expected_rex_bytes = 2 * (inbound[0].len() - 4) as u64;
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        for frame in &inbound {
+            stream.write_all(frame).await.unwrap();
+        }
+        let len = stream.read_u32().await.unwrap();
+        let mut sent = vec![0u8; len as usize];
+        stream.read_exact(&mut sent).await.unwrap();
+        (stream, len)
+    });
+
+    let mut conn = Connection::new(
+        ConnectionConfig::new("rust@localhost", PEER, COOKIE).with_remote_port(port),
+    );
+    conn.connect().await.unwrap();
+    for _ in 0..3 {
+        conn.receive_message().await.unwrap();
+    }
+    let from = ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1);
+    conn.send_to_name(from, Atom::new("logger"), OwnedTerm::atom("hello"))
+        .await
+        .unwrap();
+    let (_stream, sent_len) = peer.await.unwrap();
+
+    let metrics = conn.metrics();
+    let inbound_sizes = metrics.message_sizes(MessageDirection::Inbound);
+    assert_eq!(inbound_sizes.messages(), 3);
+    assert_eq!(inbound_sizes.percentile(100.0), Some(inbound_sizes.max()));
+    let top = metrics.top_talkers(MessageDirection::Inbound, 1);
+    assert_eq!(top[0].message_type, Some(ControlMessageType::RegSend));
+    assert_eq!(top[0].target, Some(Atom::new("rex")));
+    assert_eq!((top[0].messages, top[0].bytes), (2, expected_rex_bytes));
+
+    let outbound = metrics.top_talkers(MessageDirection::Outbound, 10);
+    assert_eq!(outbound.len(), 1);
+    assert_eq!(outbound[0].target, Some(Atom::new("logger")));
+    assert_eq!(outbound[0].bytes, sent_len as u64);
+}
+
+#[tokio::test]
+async fn test_receiving_from_read_half_records_message_sizes() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let frame = reg_send_frame("rex", &OwnedTerm::atom("via_read_half"));
+    let size = (frame.len() - 4) as u64;
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        stream.write_all(&frame).await.unwrap();
+        stream
+    });
+
+    let mut conn = Connection::new(
+        ConnectionConfig::new("rust@localhost", PEER, COOKIE).with_remote_port(port),
+    );
+    conn.connect().await.unwrap();
+    let options = conn.receive_options();
+    let mut read_half = conn.take_read_half().unwrap();
+    Connection::receive_message_from_read_half_with_options(
+        &mut read_half,
+        conn.timeout(),
+        &options,
+    )
+    .await
+    .unwrap();
+
+    let top = conn.metrics().top_talkers(MessageDirection::Inbound, 10);
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0].target, Some(Atom::new("rex")));
+    assert_eq!(top[0].bytes, size);
+    drop(peer.await.unwrap());
+}
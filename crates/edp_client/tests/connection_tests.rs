@@ -12,7 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use edp_client::{Connection, ConnectionConfig, ConnectionState, Creation};
+use edp_client::test_peer::TestPeer;
+use edp_client::{
+    Connection, ConnectionConfig, ConnectionState, Creation, DistributionFlags, Error, SendOpts,
+    SendOutcome,
+};
+use erltf::types::{Atom, ExternalPid};
+use erltf::{DecodeConfig, OwnedTerm};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
 
 #[test]
 fn test_connection_initial_state() {
@@ -22,6 +30,7 @@ fn test_connection_initial_state() {
     assert_eq!(conn.state(), ConnectionState::Disconnected);
     assert!(!conn.is_connected());
     assert!(conn.negotiated_flags().is_none());
+    assert!(conn.compatibility_report().is_none());
 }
 
 #[test]
@@ -60,3 +69,150 @@ fn test_connection_state_as_str() {
     assert_eq!(ConnectionState::Connected.as_str(), "connected");
     assert_eq!(ConnectionState::Failed.as_str(), "failed");
 }
+
+#[test]
+fn test_send_opts_default_and_builder() {
+    let opts = SendOpts::default();
+    assert!(!opts.noconnect);
+    assert!(!opts.nosuspend);
+
+    let opts = SendOpts::new().with_noconnect(true).with_nosuspend(true);
+    assert!(opts.noconnect);
+    assert!(opts.nosuspend);
+}
+
+#[tokio::test]
+async fn test_send_opts_noconnect_on_disconnected_connection() {
+    let config = ConnectionConfig::new("node1@localhost", "node2@localhost", "secret");
+    let mut conn = Connection::new(config);
+    let to = ExternalPid::new(Atom::new("node2@localhost"), 1, 0, 1);
+
+    let outcome = conn
+        .send_opts(
+            to,
+            OwnedTerm::atom("hello"),
+            SendOpts::new().with_noconnect(true),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(outcome, SendOutcome::NoConnect);
+    assert_eq!(conn.state(), ConnectionState::Disconnected);
+}
+
+#[tokio::test]
+async fn test_send_opts_without_noconnect_attempts_to_connect() {
+    let config = ConnectionConfig::new("node1@localhost", "invalid", "secret");
+    let mut conn = Connection::new(config);
+    let to = ExternalPid::new(Atom::new("invalid"), 1, 0, 1);
+
+    let result = conn
+        .send_opts(to, OwnedTerm::atom("hello"), SendOpts::new())
+        .await;
+
+    assert!(matches!(
+        result.as_ref().map_err(Error::inner),
+        Err(Error::InvalidNodeName(_))
+    ));
+}
+
+#[test]
+fn test_connection_decode_config_and_atom_table() {
+    let config = ConnectionConfig::new("node1@localhost", "node2@localhost", "secret")
+        .with_decode_config(DecodeConfig::new().atoms_as_binaries_after(1000));
+    let conn = Connection::new(config);
+
+    assert_eq!(conn.decode_config().atom_limit(), Some(1000));
+    assert_eq!(conn.atom_table().distinct_count(), 0);
+    assert_eq!(conn.atom_table().total_decoded(), 0);
+}
+
+#[test]
+fn test_connection_config_safe_mode() {
+    let config = ConnectionConfig::new("node1@localhost", "node2@localhost", "secret");
+    assert!(!config.decode_config.is_safe());
+
+    let config = config.with_safe_mode(true);
+    assert!(config.decode_config.is_safe());
+}
+
+#[test]
+fn test_connection_config_require_flags() {
+    let config = ConnectionConfig::new("rust@localhost", "erlang@localhost", "cookie")
+        .with_flags(DistributionFlags::MANDATORY_OTP26)
+        .require_flags(DistributionFlags::FRAGMENTS);
+
+    assert_eq!(config.required_flags, DistributionFlags::FRAGMENTS);
+    assert!(config.flags.contains(DistributionFlags::FRAGMENTS));
+    assert!(config.flags.has_mandatory_otp26());
+}
+
+#[test]
+fn test_connection_config_remote_port() {
+    let config = ConnectionConfig::new("rust@localhost", "erlang@localhost", "cookie");
+    assert_eq!(config.remote_port, None);
+
+    let config = config.with_remote_port(25672);
+    assert_eq!(config.remote_port, Some(25672));
+}
+
+#[tokio::test]
+async fn test_connect_with_remote_port_bypasses_epmd() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let peer = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let len = stream.read_u16().await.unwrap();
+        let mut send_name = vec![0u8; len as usize];
+        stream.read_exact(&mut send_name).await.unwrap();
+        stream
+            .write_all(&[0, 4, b's', b'n', b'o', b'k'])
+            .await
+            .unwrap();
+        send_name
+    });
+
+    // An unusable EPMD address, so a lookup would fail
+    let config = ConnectionConfig::new("rust@localhost", "erlang@127.0.0.1", "cookie")
+        .with_epmd_host("127.0.0.1:1")
+        .with_remote_port(port);
+    let mut conn = Connection::new(config);
+    let result = conn.connect().await;
+
+    assert!(matches!(
+        result.as_ref().map_err(Error::inner),
+        Err(Error::ConnectionRefused { .. })
+    ));
+    let send_name = peer.await.unwrap();
+    assert_eq!(send_name[0], b'n');
+    assert!(send_name.ends_with(b"rust@localhost"));
+}
+
+#[tokio::test]
+async fn test_compatibility_report_reflects_negotiated_flags() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer_flags = DistributionFlags::default()
+        .difference(DistributionFlags::FRAGMENTS | DistributionFlags::ALIAS);
+
+    let peer = tokio::spawn(async move {
+        TestPeer::new("erlang@127.0.0.1", "cookie")
+            .with_flags(peer_flags)
+            .accept(&listener)
+            .await
+    });
+
+    let config = ConnectionConfig::new("rust@localhost", "erlang@127.0.0.1", "cookie")
+        .with_remote_port(port);
+    let mut conn = Connection::new(config);
+    conn.connect().await.unwrap();
+
+    let report = conn.compatibility_report().unwrap();
+    assert_eq!(report.flags, conn.negotiated_flags().unwrap());
+    assert!(!report.fragmentation);
+    assert!(!report.alias);
+    assert!(report.spawn);
+    assert!(report.unlink_id);
+    drop(peer.await.unwrap());
+}
@@ -12,9 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use edp_client::control::ControlMessage;
-use erltf::OwnedTerm;
+use edp_client::Error;
+use edp_client::control::{ControlMessage, ControlMessageType};
 use erltf::types::{Atom, ExternalPid, ExternalReference};
+use erltf::{OwnedTerm, encode};
+use std::fs;
+use std::path::Path;
+
+/// When set, `test_write_fuzz_seeds` writes encoded control messages there as `cargo fuzz` seeds
+const FUZZ_SEEDS_ENV_VAR: &str = "EDP_FUZZ_SEEDS_DIR";
 
 fn make_pid(id: u32, serial: u32, creation: u32) -> OwnedTerm {
     OwnedTerm::Pid(ExternalPid::new(
@@ -746,3 +752,103 @@ fn test_spawn_request_tt_into_term_matches_to_term() {
         "to_term() and into_term() must produce identical output"
     );
 }
+
+#[test]
+fn test_control_message_strict_rejects_unknown_type() {
+    let term = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(99),
+        OwnedTerm::integer(1),
+        OwnedTerm::integer(2),
+    ]);
+
+    let err = ControlMessage::from_term_strict(&term).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::UnknownControlMessage { message_type: 99, term: ref t } if *t == term
+    ));
+    assert!(err.to_string().contains("99"));
+}
+
+#[test]
+fn test_control_message_strict_rejects_arity_mismatch() {
+    let term = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(2),
+        OwnedTerm::atom(""),
+        make_pid(1, 0, 1),
+        OwnedTerm::atom("extra"),
+    ]);
+
+    let err = ControlMessage::from_term_strict(&term).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ControlMessageArityMismatch {
+            message_type: ControlMessageType::Send,
+            arity: 4,
+            ..
+        }
+    ));
+    assert!(err.to_string().starts_with("SEND control message"));
+}
+
+#[test]
+fn test_control_message_strict_accepts_known_messages() {
+    let message = ControlMessage::link(make_pid(1, 0, 1), make_pid(2, 0, 1));
+    assert_eq!(
+        ControlMessage::from_term_strict(&message.to_term()).unwrap(),
+        message
+    );
+}
+
+#[test]
+fn test_write_fuzz_seeds() {
+    let Some(dir) = std::env::var_os(FUZZ_SEEDS_ENV_VAR) else {
+        return;
+    };
+    let dir = Path::new(&dir).join("control_message");
+    fs::create_dir_all(&dir).unwrap();
+
+    let from = make_pid(1, 0, 1);
+    let to = make_pid(2, 0, 1);
+    let reason = OwnedTerm::atom("normal");
+    let seeds = [
+        ("link", ControlMessage::link(from.clone(), to.clone())),
+        (
+            "send",
+            ControlMessage::send(OwnedTerm::atom(""), to.clone()),
+        ),
+        (
+            "exit",
+            ControlMessage::exit(from.clone(), to.clone(), reason.clone()),
+        ),
+        (
+            "reg_send",
+            ControlMessage::reg_send(from.clone(), OwnedTerm::atom(""), OwnedTerm::atom("rex")),
+        ),
+        (
+            "monitor_p",
+            ControlMessage::monitor_p(from.clone(), to.clone(), make_reference()),
+        ),
+        (
+            "monitor_p_exit",
+            ControlMessage::monitor_p_exit(to.clone(), from.clone(), make_reference(), reason),
+        ),
+        (
+            "unlink_id",
+            ControlMessage::UnlinkId {
+                id: 42,
+                from_pid: from.clone(),
+                to_pid: to.clone(),
+            },
+        ),
+        (
+            "alias_send",
+            ControlMessage::AliasSend {
+                from_pid: from,
+                alias: make_reference(),
+            },
+        ),
+    ];
+    for (name, message) in seeds {
+        fs::write(dir.join(name), encode(&message.to_term()).unwrap()).unwrap();
+    }
+}
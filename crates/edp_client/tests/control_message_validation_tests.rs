@@ -0,0 +1,208 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::control::{ControlMessage, ControlMessageType, ValidationError};
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, DistributionFlags, Error};
+use edp_client::{ctrl, pid, reference};
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid};
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpListener;
+
+const COOKIE: &str = "validation_cookie";
+const PEER: &str = "validation_peer@127.0.0.1";
+
+fn mfa(arity: i64) -> OwnedTerm {
+    OwnedTerm::tuple(vec![
+        OwnedTerm::atom("lists"),
+        OwnedTerm::atom("reverse"),
+        OwnedTerm::integer(arity),
+    ])
+}
+
+fn spawn_request(mfa: OwnedTerm, arg_list: OwnedTerm) -> ControlMessage {
+    ctrl!(
+        SpawnRequest
+        req_id reference!(1, 2, 3),
+        from pid!(1, 0),
+        group_leader pid!(2, 0),
+        mfa mfa,
+        args arg_list,
+        opts OwnedTerm::Nil,
+    )
+}
+
+fn invalid(message: &ControlMessage) -> ValidationError {
+    message.validate().unwrap_err()
+}
+
+#[test]
+fn test_well_formed_messages_pass() {
+    let messages = [
+        ControlMessage::link(pid!(1, 0), pid!(2, 0)),
+        ControlMessage::send(OwnedTerm::atom(""), pid!(2, 0)),
+        ControlMessage::reg_send(pid!(1, 0), OwnedTerm::atom(""), OwnedTerm::atom("rex")),
+        ControlMessage::exit2(pid!(1, 0), pid!(2, 0), OwnedTerm::atom("kill")),
+        ControlMessage::monitor_p(
+            pid!(1, 0),
+            OwnedTerm::atom("registered"),
+            reference!(1, 2, 3),
+        ),
+        ctrl!(UnlinkId id 1, from pid!(1, 0), to pid!(2, 0)),
+        spawn_request(mfa(1), OwnedTerm::List(vec![OwnedTerm::Nil])),
+        spawn_request(mfa(0), OwnedTerm::Nil),
+        ControlMessage::NodeLink,
+        ControlMessage::Generic {
+            message_type: 99,
+            fields: vec![OwnedTerm::atom("anything")],
+        },
+    ];
+    for message in messages {
+        assert_eq!(message.validate(), Ok(()), "{:?}", message);
+    }
+}
+
+#[test]
+fn test_send_requires_a_pid_target() {
+    let err = invalid(&ControlMessage::send(
+        OwnedTerm::atom(""),
+        OwnedTerm::atom("rex"),
+    ));
+    assert_eq!(
+        err,
+        ValidationError {
+            message_type: ControlMessageType::Send,
+            field: "to_pid",
+            expected: "a pid",
+        }
+    );
+    assert_eq!(err.to_string(), "SEND field to_pid must be a pid");
+}
+
+#[test]
+fn test_reg_send_requires_a_name_atom() {
+    let by_string = ControlMessage::reg_send(
+        pid!(1, 0),
+        OwnedTerm::atom(""),
+        OwnedTerm::Binary(b"rex".to_vec()),
+    );
+    assert_eq!(invalid(&by_string).field, "to_name");
+
+    let by_charlist = ControlMessage::reg_send(
+        pid!(1, 0),
+        OwnedTerm::atom(""),
+        OwnedTerm::ImproperList {
+            elements: vec![OwnedTerm::integer(114)],
+            tail: Box::new(OwnedTerm::integer(120)),
+        },
+    );
+    assert_eq!(invalid(&by_charlist).expected, "an atom");
+}
+
+#[test]
+fn test_unlink_ids_must_be_non_zero() {
+    let err = invalid(&ctrl!(UnlinkIdAck id 0, from pid!(1, 0), to pid!(2, 0)));
+    assert_eq!(err.message_type, ControlMessageType::UnlinkIdAck);
+    assert_eq!(err.field, "id");
+}
+
+#[test]
+fn test_spawn_request_requires_a_well_formed_mfa() {
+    let args = OwnedTerm::List(vec![OwnedTerm::Nil]);
+    let not_a_tuple = spawn_request(OwnedTerm::atom("lists"), args.clone());
+    assert_eq!(invalid(&not_a_tuple).field, "mfa");
+
+    let string_module = spawn_request(
+        OwnedTerm::tuple(vec![
+            OwnedTerm::Binary(b"lists".to_vec()),
+            OwnedTerm::atom("reverse"),
+            OwnedTerm::integer(1),
+        ]),
+        args.clone(),
+    );
+    assert_eq!(invalid(&string_module).field, "mfa");
+
+    assert_eq!(invalid(&spawn_request(mfa(256), args.clone())).field, "mfa");
+    assert_eq!(invalid(&spawn_request(mfa(2), args)).field, "arg_list");
+}
+
+#[test]
+fn test_monitor_requires_a_reference() {
+    let err = invalid(&ControlMessage::monitor_p(
+        pid!(1, 0),
+        pid!(2, 0),
+        OwnedTerm::integer(1),
+    ));
+    assert_eq!(err.field, "reference");
+}
+
+#[test]
+fn test_message_type_of_generic_messages() {
+    assert_eq!(
+        ControlMessage::NodeLink.message_type(),
+        Some(ControlMessageType::NodeLink)
+    );
+    let known = ControlMessage::Generic {
+        message_type: 2,
+        fields: vec![],
+    };
+    assert_eq!(known.message_type(), Some(ControlMessageType::Send));
+    let unknown = ControlMessage::Generic {
+        message_type: 99,
+        fields: vec![],
+    };
+    assert_eq!(unknown.message_type(), None);
+}
+
+/// Completes the handshake, then collects the non-tick frames until the client disconnects.
+async fn accept_and_read_frames(listener: TcpListener) -> Vec<Vec<u8>> {
+    let mut stream = TestPeer::new(PEER, COOKIE)
+        .with_flags(DistributionFlags::default())
+        .accept(&listener)
+        .await;
+
+    let mut frames = Vec::new();
+    while let Ok(len) = stream.read_u32().await {
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        frames.push(data);
+    }
+    frames
+}
+
+#[tokio::test]
+async fn test_connection_rejects_invalid_messages_before_sending() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(accept_and_read_frames(listener));
+
+    let mut conn = Connection::new(
+        ConnectionConfig::new("rust@localhost", PEER, COOKIE).with_remote_port(port),
+    );
+    conn.connect().await.unwrap();
+    let from = ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1);
+    let to = ExternalPid::new(Atom::new(PEER), 2, 0, 1);
+    let err = conn.unlink(&from, &to, 0).await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ControlMessageValidation(ValidationError { field: "id", .. })
+    ));
+    conn.close().await.unwrap();
+
+    assert!(peer.await.unwrap().is_empty());
+}
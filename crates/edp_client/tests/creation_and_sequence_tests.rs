@@ -0,0 +1,76 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::{Creation, Error, SequenceId, SequenceIdAllocator};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+#[test]
+fn test_creation_wildcard_matches_any_creation() {
+    assert!(Creation::WILDCARD.is_wildcard());
+    assert!(Creation::WILDCARD.matches(7));
+    assert!(Creation::new(7).matches(Creation::WILDCARD));
+    assert!(Creation::new(7).matches(7));
+    assert!(!Creation::new(7).matches(8));
+}
+
+#[test]
+fn test_creation_try_new_rejects_the_wildcard() {
+    assert_eq!(Creation::try_new(42).unwrap(), Creation::new(42));
+    assert!(matches!(
+        Creation::try_new(0),
+        Err(Error::InvalidCreation(0))
+    ));
+}
+
+#[test]
+fn test_sequence_id_next_wraps_around_past_zero() {
+    assert_eq!(SequenceId::new(1).next(), SequenceId::new(2));
+    assert_eq!(SequenceId::new(u64::MAX).next(), SequenceId::FIRST);
+    assert!(matches!(
+        SequenceId::try_new(0),
+        Err(Error::InvalidSequenceId(0))
+    ));
+    assert_eq!(SequenceId::try_new(5).unwrap(), SequenceId::new(5));
+}
+
+#[test]
+fn test_sequence_id_allocator_wraps_around() {
+    let allocator = SequenceIdAllocator::starting_at(SequenceId::new(u64::MAX - 1));
+    assert_eq!(allocator.allocate(), SequenceId::new(u64::MAX - 1));
+    assert_eq!(allocator.allocate(), SequenceId::new(u64::MAX));
+    assert_eq!(allocator.peek(), SequenceId::FIRST);
+    assert_eq!(allocator.allocate(), SequenceId::FIRST);
+    assert_eq!(allocator.allocate(), SequenceId::new(2));
+}
+
+#[test]
+fn test_sequence_id_allocator_hands_out_unique_ids_across_threads() {
+    let allocator = Arc::new(SequenceIdAllocator::new());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let allocator = Arc::clone(&allocator);
+            std::thread::spawn(move || (0..1000).map(|_| allocator.allocate()).collect::<Vec<_>>())
+        })
+        .collect();
+
+    let mut seen = HashSet::new();
+    for handle in handles {
+        for id in handle.join().unwrap() {
+            assert!(seen.insert(id));
+        }
+    }
+    assert_eq!(seen.len(), 4000);
+    assert!(!seen.contains(&SequenceId::new(0)));
+}
@@ -0,0 +1,120 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::control::ControlMessage;
+use edp_client::test_support::TEST_NODE;
+use edp_client::{ctrl, pid, reference};
+use erltf::types::{Atom, ExternalPid, ExternalReference};
+use erltf::{OwnedTerm, erl_atom, erl_int, erl_list, erl_tuple};
+
+#[test]
+fn pid_macro() {
+    assert_eq!(
+        pid!(1, 2),
+        OwnedTerm::Pid(ExternalPid::new(Atom::new(TEST_NODE), 1, 2, 1))
+    );
+    assert_eq!(
+        pid!("a@host", 3, 0, 7),
+        OwnedTerm::Pid(ExternalPid::new(Atom::new("a@host"), 3, 0, 7))
+    );
+}
+
+#[test]
+fn reference_macro() {
+    assert_eq!(
+        reference!(1, 2, 3),
+        OwnedTerm::Reference(ExternalReference::new(
+            Atom::new(TEST_NODE),
+            1,
+            vec![1, 2, 3]
+        ))
+    );
+}
+
+#[test]
+fn ctrl_link() {
+    let msg = ctrl!(Link from pid!(1, 0), to pid!(2, 0));
+    assert_eq!(msg, ControlMessage::link(pid!(1, 0), pid!(2, 0)));
+    assert_eq!(
+        msg.to_term(),
+        erl_tuple![erl_int!(1), pid!(1, 0), pid!(2, 0)]
+    );
+}
+
+#[test]
+fn ctrl_reg_send_with_payload() {
+    let (msg, payload) = ctrl!(RegSend from pid!(1, 2), to "rex", payload erl_atom!("ping"));
+    assert_eq!(
+        msg.to_term(),
+        erl_tuple![erl_int!(6), pid!(1, 2), erl_atom!(""), erl_atom!("rex")]
+    );
+    assert_eq!(payload, erl_atom!("ping"));
+}
+
+#[test]
+fn ctrl_send_with_an_explicit_cookie() {
+    let msg = ctrl!(Send cookie erl_atom!("secret"), to pid!(5, 0));
+    assert_eq!(msg, ControlMessage::send(erl_atom!("secret"), pid!(5, 0)));
+}
+
+#[test]
+fn ctrl_monitor_a_registered_name() {
+    let msg = ctrl!(MonitorP from pid!(1, 0), to "logger", reference reference!(7));
+    assert_eq!(
+        msg,
+        ControlMessage::monitor_p(pid!(1, 0), erl_atom!("logger"), reference!(7))
+    );
+}
+
+#[test]
+fn ctrl_unlink_id_and_spawn_request() {
+    let msg = ctrl!(UnlinkId id 42, from pid!(1, 0), to pid!(2, 0));
+    assert_eq!(
+        msg.to_term(),
+        erl_tuple![erl_int!(35), erl_int!(42), pid!(1, 0), pid!(2, 0)]
+    );
+
+    let msg = ctrl!(
+        SpawnRequest
+        req_id reference!(1),
+        from pid!(1, 0),
+        group_leader pid!(2, 0),
+        mfa erl_tuple![erl_atom!("erlang"), erl_atom!("apply"), 2],
+        args erl_list![],
+        opts erl_list![],
+    );
+    assert!(ControlMessage::from_term_strict(&msg.to_term()).is_ok());
+}
+
+#[test]
+fn ctrl_node_link() {
+    assert_eq!(ctrl!(NodeLink), ControlMessage::NodeLink);
+}
+
+#[test]
+#[should_panic(expected = "ctrl!(EXIT) requires `reason`")]
+fn ctrl_panics_on_a_missing_field() {
+    let _ = ctrl!(Exit from pid!(1, 0), to pid!(2, 0));
+}
+
+#[test]
+#[should_panic(expected = "ctrl!(LINK) does not take `reason`")]
+fn ctrl_panics_on_an_extra_field() {
+    let _ = ctrl!(Link from pid!(1, 0), to pid!(2, 0), reason erl_atom!("normal"));
+}
+
+#[test]
+#[should_panic(expected = "`to` is given more than once")]
+fn ctrl_panics_on_a_repeated_field() {
+    let _ = ctrl!(Link from pid!(1, 0), to pid!(2, 0), to pid!(3, 0));
+}
@@ -0,0 +1,325 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::errors::Error;
+use edp_client::test_peer::TestPeer;
+use edp_client::{
+    Connection, ConnectionConfig, DecodeAccounting, DecodeBudget, DecodeErrorPolicy, DecodeOffload,
+    DecodePipeline,
+};
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use erltf::{DecodeConfig, OwnedTerm};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, timeout};
+
+const COOKIE: &str = "budget_cookie";
+const PEER: &str = "budget_peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+fn send_frame(message: &OwnedTerm) -> Vec<u8> {
+    let control = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(SEND),
+        OwnedTerm::atom(""),
+        OwnedTerm::Pid(ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)),
+    ]);
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    body.extend(encode(message).unwrap());
+    let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+    frame.extend(body);
+    frame
+}
+
+fn blob(size: usize) -> OwnedTerm {
+    OwnedTerm::Binary(vec![0; size])
+}
+
+async fn start_peer(frames: Vec<Vec<u8>>) -> (u16, JoinHandle<TcpStream>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        for frame in frames {
+            stream.write_all(&frame).await.unwrap();
+        }
+        stream
+    });
+    (port, peer)
+}
+
+fn config(port: u16, budget: DecodeBudget) -> ConnectionConfig {
+    ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(TIMEOUT)
+        .with_decode_budget(budget)
+}
+
+async fn connect(port: u16, budget: DecodeBudget) -> Connection {
+    let mut conn = Connection::new(config(port, budget));
+    conn.connect().await.unwrap();
+    conn
+}
+
+/// A payload over a 1000-byte budget, with an atom that is only seen if it is decoded
+fn oversized() -> OwnedTerm {
+    OwnedTerm::tuple(vec![blob(10_000), OwnedTerm::atom("budget_unseen_atom")])
+}
+
+#[test]
+fn test_decode_budget_builders() {
+    assert!(DecodeBudget::default().is_unlimited());
+    let budget = DecodeBudget::new()
+        .with_max_message_size(10)
+        .with_bytes_per_sec(20)
+        .with_max_outstanding(30);
+    assert!(!budget.is_unlimited());
+    assert_eq!(budget.max_message_size, Some(10));
+    assert_eq!(budget.bytes_per_sec, Some(20));
+    assert_eq!(budget.max_outstanding, Some(30));
+}
+
+#[test]
+fn test_accounting_tracks_charges_and_releases() {
+    let accounting = DecodeAccounting::new(DecodeBudget::new().with_max_message_size(100));
+    accounting.charge(40).unwrap();
+    accounting.clone().charge(60).unwrap();
+
+    assert_eq!(accounting.messages(), 2);
+    assert_eq!(accounting.bytes(), 100);
+    assert_eq!(accounting.largest_message(), 60);
+    assert_eq!(accounting.outstanding(), 100);
+    assert_eq!(accounting.bytes_in_window(), 100);
+
+    let err = accounting.charge(101).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::DecodeBudgetExceeded {
+            size: 101,
+            max: 100
+        }
+    ));
+    assert_eq!(accounting.rejected(), 1);
+    assert_eq!(accounting.messages(), 2);
+
+    accounting.release(40);
+    assert_eq!(accounting.outstanding(), 60);
+    accounting.release(1000);
+    assert_eq!(accounting.outstanding(), 0);
+}
+
+#[test]
+fn test_accounting_checks_estimates_before_decoding() {
+    let accounting = DecodeAccounting::new(DecodeBudget::new().with_max_message_size(100));
+    accounting.check(100).unwrap();
+    let err = accounting.check(101).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::DecodeBudgetExceeded {
+            size: 101,
+            max: 100
+        }
+    ));
+    assert_eq!(accounting.rejected(), 1);
+    assert_eq!(accounting.messages(), 0);
+
+    DecodeAccounting::new(DecodeBudget::default())
+        .check(usize::MAX)
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_outstanding_budget_waits_for_a_release() {
+    let accounting = DecodeAccounting::new(DecodeBudget::new().with_max_outstanding(100));
+    accounting.charge(100).unwrap();
+
+    let waiter = {
+        let accounting = accounting.clone();
+        tokio::spawn(async move { accounting.wait_for_capacity().await })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!waiter.is_finished());
+
+    accounting.release(50);
+    timeout(Duration::from_secs(1), waiter)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(accounting.throttled() >= 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_budget_waits_for_the_next_window() {
+    let accounting = DecodeAccounting::new(DecodeBudget::new().with_bytes_per_sec(100));
+    accounting.wait_for_capacity().await;
+    accounting.charge(150).unwrap();
+
+    let started = Instant::now();
+    accounting.wait_for_capacity().await;
+    assert!(started.elapsed() >= Duration::from_millis(900));
+    assert_eq!(accounting.bytes_in_window(), 0);
+    assert_eq!(accounting.throttled(), 1);
+}
+
+#[tokio::test]
+async fn test_receive_envelope_reports_the_payload_heap_size() {
+    let payload = blob(10_000);
+    let (port, _peer) = start_peer(vec![send_frame(&payload)]).await;
+    let mut conn = connect(port, DecodeBudget::default()).await;
+
+    let received = conn.receive_envelope().await.unwrap();
+    assert_eq!(received.heap_size, payload.heap_size());
+
+    let accounting = conn.decode_accounting();
+    assert_eq!(accounting.messages(), 1);
+    assert_eq!(accounting.outstanding(), received.heap_size);
+    accounting.release(received.heap_size);
+    assert_eq!(accounting.outstanding(), 0);
+}
+
+#[tokio::test]
+async fn test_oversized_payloads_fail_to_receive() {
+    let (port, _peer) = start_peer(vec![send_frame(&blob(10_000))]).await;
+    let mut conn = connect(port, DecodeBudget::new().with_max_message_size(1_000)).await;
+
+    let err = conn.receive_envelope().await.unwrap_err();
+    assert!(
+        matches!(err.inner(), Error::DecodeBudgetExceeded { max: 1_000, .. }),
+        "{err:?}"
+    );
+    assert_eq!(conn.decode_accounting().rejected(), 1);
+}
+
+#[tokio::test]
+async fn test_receive_pauses_until_outstanding_payloads_are_released() {
+    let frames = vec![send_frame(&blob(1_000)), send_frame(&blob(1_000))];
+    let (port, _peer) = start_peer(frames).await;
+    let mut conn = connect(port, DecodeBudget::new().with_max_outstanding(500)).await;
+
+    let first = conn.receive_envelope().await.unwrap();
+    assert!(
+        timeout(Duration::from_millis(100), conn.receive_envelope())
+            .await
+            .is_err(),
+        "the second message is held back"
+    );
+
+    conn.decode_accounting().release(first.heap_size);
+    let second = timeout(TIMEOUT, conn.receive_envelope())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(second.payload, Some(blob(1_000)));
+}
+
+#[tokio::test]
+async fn test_read_half_receives_are_charged() {
+    let (port, _peer) = start_peer(vec![send_frame(&blob(1_000))]).await;
+    let mut conn = connect(port, DecodeBudget::default()).await;
+    let read_half = conn.take_read_half().unwrap();
+    let options = conn.receive_options();
+    let mut pipeline = DecodePipeline::new(
+        read_half,
+        conn.timeout(),
+        options,
+        DecodeOffload::new(usize::MAX),
+    );
+
+    let (_, payload) = pipeline.receive().await.unwrap();
+    assert_eq!(payload, Some(blob(1_000)));
+    assert_eq!(conn.decode_accounting().messages(), 1);
+    assert_eq!(
+        conn.decode_accounting().outstanding(),
+        blob(1_000).heap_size()
+    );
+}
+
+#[tokio::test]
+async fn test_oversized_payloads_fail_before_they_are_decoded() {
+    let (port, _peer) = start_peer(vec![send_frame(&oversized())]).await;
+    let mut conn = connect(port, DecodeBudget::new().with_max_message_size(1_000)).await;
+
+    let err = conn.receive_envelope().await.unwrap_err();
+    assert!(
+        matches!(err.inner(), Error::DecodeBudgetExceeded { size, max: 1_000 } if *size > 10_000),
+        "{err:?}"
+    );
+    assert!(!conn.atom_table().contains("budget_unseen_atom"));
+    assert_eq!(conn.decode_accounting().messages(), 0);
+}
+
+#[tokio::test]
+async fn test_oversized_read_half_payloads_fail_before_they_are_decoded() {
+    let (port, _peer) = start_peer(vec![send_frame(&oversized())]).await;
+    let budget = DecodeBudget::new().with_max_message_size(1_000);
+    let decode_config = DecodeConfig::new().atoms_error_after(1_000);
+    let mut conn = Connection::new(config(port, budget).with_decode_config(decode_config));
+    conn.connect().await.unwrap();
+    let options = conn.receive_options();
+    let mut read_half = conn.take_read_half().unwrap();
+
+    let err =
+        Connection::receive_envelope_from_read_half_with_options(&mut read_half, TIMEOUT, &options)
+            .await
+            .unwrap_err();
+    assert!(matches!(err.inner(), Error::DecodeBudgetExceeded { .. }));
+    let atom_table = options.atom_table.unwrap();
+    assert!(!atom_table.lock().unwrap().contains("budget_unseen_atom"));
+    assert_eq!(conn.decode_accounting().rejected(), 1);
+}
+
+#[tokio::test]
+async fn test_resumed_connections_receive_the_message_after_an_oversized_one() {
+    let frames = vec![send_frame(&oversized()), send_frame(&blob(100))];
+    let (port, _peer) = start_peer(frames).await;
+    let budget = DecodeBudget::new().with_max_message_size(1_000);
+    let config = config(port, budget).with_decode_error_policy(DecodeErrorPolicy::Resume);
+    let mut conn = Connection::new(config);
+    conn.connect().await.unwrap();
+
+    let err = conn.receive_envelope().await.unwrap_err();
+    assert!(err.is_frame_decode());
+    let Error::FrameDecode { source, .. } = err.inner() else {
+        unreachable!()
+    };
+    assert!(matches!(**source, Error::DecodeBudgetExceeded { .. }));
+
+    let received = conn.receive_envelope().await.unwrap();
+    assert_eq!(received.payload, Some(blob(100)));
+}
+
+#[tokio::test]
+async fn test_read_half_envelopes_report_the_charged_size() {
+    let (port, _peer) = start_peer(vec![send_frame(&blob(1_000))]).await;
+    let mut conn = connect(port, DecodeBudget::default()).await;
+    let options = conn.receive_options();
+    let mut read_half = conn.take_read_half().unwrap();
+
+    let received =
+        Connection::receive_envelope_from_read_half_with_options(&mut read_half, TIMEOUT, &options)
+            .await
+            .unwrap();
+    assert_eq!(received.payload, Some(blob(1_000)));
+    assert_eq!(received.heap_size, blob(1_000).heap_size());
+    assert_eq!(received.from_node, Atom::new(PEER));
+    assert_eq!(conn.decode_accounting().outstanding(), received.heap_size);
+}
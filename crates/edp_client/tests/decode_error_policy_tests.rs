@@ -0,0 +1,157 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::control::ControlMessage;
+use edp_client::test_peer::TestPeer;
+use edp_client::{
+    Connection, ConnectionConfig, DecodeErrorPolicy, DecodeOffload, DecodePipeline, Error,
+};
+use erltf::OwnedTerm;
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "decode_error_cookie";
+const PEER: &str = "decode_error_peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+
+/// A pass-through frame whose control message ends after the version byte
+const UNDECODABLE: [u8; 2] = [PASS_THROUGH, 131];
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+async fn write_frame(stream: &mut TcpStream, body: &[u8]) {
+    stream
+        .write_all(&(body.len() as u32).to_be_bytes())
+        .await
+        .unwrap();
+    stream.write_all(body).await.unwrap();
+}
+
+fn send_body() -> Vec<u8> {
+    let control = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(SEND),
+        OwnedTerm::atom(""),
+        OwnedTerm::Pid(ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)),
+    ]);
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    body.extend(encode(&OwnedTerm::atom("after")).unwrap());
+    body
+}
+
+/// Connects to a peer that sends an undecodable frame followed by a regular message.
+async fn connect(config: ConnectionConfig) -> Connection {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        write_frame(&mut stream, &UNDECODABLE).await;
+        write_frame(&mut stream, &send_body()).await;
+        // Keeps the stream open while the client reads
+        std::future::pending::<()>().await
+    });
+
+    let mut conn = Connection::new(config.with_remote_port(port));
+    conn.connect().await.unwrap();
+    conn
+}
+
+fn config() -> ConnectionConfig {
+    ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+}
+
+fn assert_resumable(err: &Error) {
+    assert!(err.is_frame_decode(), "unexpected error: {err}");
+    let Error::FrameDecode { frame, source } = err.inner() else {
+        unreachable!()
+    };
+    assert_eq!(frame.as_slice(), UNDECODABLE.as_slice());
+    assert!(!source.is_frame_decode());
+}
+
+fn assert_after(control: &ControlMessage, payload: Option<OwnedTerm>) {
+    assert!(matches!(control, ControlMessage::Send { .. }));
+    assert_eq!(payload, Some(OwnedTerm::atom("after")));
+}
+
+#[tokio::test]
+async fn test_decode_errors_are_not_resumable_by_default() {
+    let mut conn = connect(config()).await;
+    let err = conn.receive_message().await.unwrap_err();
+    assert!(!err.is_frame_decode());
+    assert!(matches!(err.inner(), Error::Decode(_)));
+}
+
+#[tokio::test]
+async fn test_resumed_connections_receive_the_next_frame() {
+    let mut conn = connect(config().with_decode_error_policy(DecodeErrorPolicy::Resume)).await;
+    assert_resumable(&conn.receive_message().await.unwrap_err());
+    let (control, payload) = conn.receive_message().await.unwrap();
+    assert_after(&control, payload);
+}
+
+#[tokio::test]
+async fn test_resumed_offloaded_decodes_receive_the_next_frame() {
+    let config = config()
+        .with_decode_error_policy(DecodeErrorPolicy::Resume)
+        .with_decode_offload(DecodeOffload::new(0));
+    let mut conn = connect(config).await;
+    assert_resumable(&conn.receive_message().await.unwrap_err());
+    let (control, payload) = conn.receive_message().await.unwrap();
+    assert_after(&control, payload);
+}
+
+#[tokio::test]
+async fn test_resumed_read_halves_receive_the_next_frame() {
+    let mut conn = connect(config().with_decode_error_policy(DecodeErrorPolicy::Resume)).await;
+    let options = conn.receive_options();
+    assert_eq!(options.decode_error_policy, DecodeErrorPolicy::Resume);
+    let mut read_half = conn.take_read_half().unwrap();
+
+    let err = Connection::receive_message_from_read_half_with_options(
+        &mut read_half,
+        conn.timeout(),
+        &options,
+    )
+    .await
+    .unwrap_err();
+    assert_resumable(&err);
+    assert_eq!(err.connection_id(), Some(conn.id()));
+
+    let (control, payload) = Connection::receive_message_from_read_half_with_options(
+        &mut read_half,
+        conn.timeout(),
+        &options,
+    )
+    .await
+    .unwrap();
+    assert_after(&control, payload);
+}
+
+#[tokio::test]
+async fn test_resumed_decode_pipelines_receive_the_next_frame() {
+    let mut conn = connect(config().with_decode_error_policy(DecodeErrorPolicy::Resume)).await;
+    let options = conn.receive_options();
+    let read_half = conn.take_read_half().unwrap();
+    let mut pipeline =
+        DecodePipeline::new(read_half, conn.timeout(), options, DecodeOffload::new(0));
+
+    assert_resumable(&pipeline.receive().await.unwrap_err());
+    let (control, payload) = pipeline.receive().await.unwrap();
+    assert_after(&control, payload);
+}
@@ -0,0 +1,189 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::control::ControlMessage;
+use edp_client::decode_offload::DEFAULT_OFFLOAD_THRESHOLD;
+use edp_client::errors::Error;
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, DecodeOffload, DecodePipeline};
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use erltf::{DecodeConfig, OwnedTerm};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+const COOKIE: &str = "offload_cookie";
+const PEER: &str = "offload_peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+const THRESHOLD: usize = 1024;
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+fn send_frame(message: &OwnedTerm) -> Vec<u8> {
+    let control = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(SEND),
+        OwnedTerm::atom(""),
+        OwnedTerm::Pid(ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)),
+    ]);
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    body.extend(encode(message).unwrap());
+    let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+    frame.extend(body);
+    frame
+}
+
+/// `{Index, Padding}`, above [`THRESHOLD`] when `large` is set.
+fn message(index: i64, large: bool) -> OwnedTerm {
+    let padding = if large { 4 * THRESHOLD } else { 8 };
+    OwnedTerm::tuple(vec![
+        OwnedTerm::integer(index),
+        OwnedTerm::Binary(vec![0; padding]),
+    ])
+}
+
+fn index_of(payload: Option<OwnedTerm>) -> i64 {
+    let payload = payload.expect("a payload");
+    payload.as_tuple().unwrap()[0].as_integer().unwrap()
+}
+
+/// Completes the handshake, writes `frames` and closes the stream when `close` is set.
+async fn start_peer(frames: Vec<Vec<u8>>, close: bool) -> (u16, JoinHandle<Option<TcpStream>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        for frame in frames {
+            stream.write_all(&frame).await.unwrap();
+        }
+        (!close).then_some(stream)
+    });
+    (port, peer)
+}
+
+async fn connect(port: u16, offload: DecodeOffload) -> Connection {
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(TIMEOUT)
+        .with_decode_offload(offload)
+        .with_decode_config(DecodeConfig::new().atoms_as_binaries_after(1_000));
+    let mut conn = Connection::new(config);
+    conn.connect().await.unwrap();
+    conn
+}
+
+fn pipeline(conn: &mut Connection) -> DecodePipeline {
+    let read_half = conn.take_read_half().unwrap();
+    let options = conn.receive_options();
+    DecodePipeline::new(
+        read_half,
+        conn.timeout(),
+        options.clone(),
+        options.decode_offload.unwrap(),
+    )
+}
+
+#[test]
+fn test_decode_offload_defaults() {
+    let offload = DecodeOffload::default();
+    assert_eq!(offload.threshold, DEFAULT_OFFLOAD_THRESHOLD);
+    assert!(offload.max_in_flight >= 1);
+    assert!(offload.applies_to(DEFAULT_OFFLOAD_THRESHOLD));
+    assert!(!offload.applies_to(DEFAULT_OFFLOAD_THRESHOLD - 1));
+    assert_eq!(offload.with_max_in_flight(0).max_in_flight, 1);
+}
+
+#[tokio::test]
+async fn test_receive_envelope_decodes_large_messages_on_the_blocking_pool() {
+    let large = OwnedTerm::tuple(vec![
+        OwnedTerm::atom("offloaded_atom"),
+        OwnedTerm::Binary(vec![1; 4 * THRESHOLD]),
+    ]);
+    let frames = vec![send_frame(&large), send_frame(&message(1, false))];
+    let (port, peer) = start_peer(frames, false).await;
+
+    let mut conn = connect(port, DecodeOffload::new(THRESHOLD)).await;
+    let first = conn.receive_envelope().await.unwrap();
+    let second = conn.receive_envelope().await.unwrap();
+
+    assert!(matches!(first.control, ControlMessage::Send { .. }));
+    assert_eq!(first.payload, Some(large));
+    assert!(first.byte_size >= THRESHOLD);
+    assert_eq!(index_of(second.payload), 1);
+    // The atom table comes back from the decoding thread
+    assert!(conn.atom_table().contains("offloaded_atom"));
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_pipeline_preserves_the_order_of_mixed_sizes() {
+    let frames = (0..20)
+        .map(|i| send_frame(&message(i, i % 3 == 0)))
+        .collect();
+    let (port, peer) = start_peer(frames, false).await;
+
+    let offload = DecodeOffload::new(THRESHOLD).with_max_in_flight(2);
+    let mut conn = connect(port, offload).await;
+    let mut pipeline = pipeline(&mut conn);
+    for i in 0..20 {
+        let (control, payload) = pipeline.receive().await.unwrap();
+        assert!(matches!(control, ControlMessage::Send { .. }));
+        assert_eq!(index_of(payload), i);
+    }
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_pipeline_preserves_the_order_when_every_message_is_offloaded() {
+    let frames = (0..50)
+        .map(|i| send_frame(&message(i, i % 2 == 0)))
+        .collect();
+    let (port, peer) = start_peer(frames, false).await;
+
+    let offload = DecodeOffload::new(0).with_max_in_flight(4);
+    let mut conn = connect(port, offload).await;
+    let mut pipeline = pipeline(&mut conn);
+    for i in 0..50 {
+        let (_, payload) = pipeline.receive().await.unwrap();
+        assert_eq!(index_of(payload), i);
+    }
+    assert!(pipeline.in_flight() <= 4);
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_pipeline_returns_read_errors_after_earlier_messages() {
+    let frames = vec![
+        send_frame(&message(0, true)),
+        send_frame(&message(1, false)),
+    ];
+    let (port, peer) = start_peer(frames, true).await;
+
+    let mut conn = connect(port, DecodeOffload::new(THRESHOLD)).await;
+    let mut pipeline = pipeline(&mut conn);
+    assert!(peer.await.unwrap().is_none());
+
+    assert_eq!(index_of(pipeline.receive().await.unwrap().1), 0);
+    assert_eq!(index_of(pipeline.receive().await.unwrap().1), 1);
+    assert!(pipeline.receive().await.is_err());
+    assert!(matches!(
+        pipeline.receive().await.map_err(Error::into_inner),
+        Err(Error::ConnectionClosed)
+    ));
+}
@@ -12,7 +12,66 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use edp_client::epmd_client::{NodeType, Protocol};
+use edp_client::Error;
+use edp_client::epmd_client::{
+    EpmdClient, EpmdClientConfig, NodeType, Protocol, RegisteredNode, parse_names,
+};
+use edp_client::resolver::StaticResolver;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::time::sleep;
+
+const NAMES_REQ: u8 = 110;
+const PORT2_REQ: u8 = 122;
+const PORT2_RESP: u8 = 119;
+
+/// Serves PORT2 requests: `known` is registered on port 4370, every other node is not.
+/// NAMES requests list `known`. Only PORT2 requests are counted.
+async fn serve_port2(listener: TcpListener, known: &'static str, requests: Arc<AtomicUsize>) {
+    loop {
+        let Ok((mut stream, _)) = listener.accept().await else {
+            return;
+        };
+        let len = stream.read_u16().await.unwrap();
+        let mut request = vec![0u8; len as usize];
+        stream.read_exact(&mut request).await.unwrap();
+        if request[0] == NAMES_REQ {
+            let mut response = 4369u32.to_be_bytes().to_vec();
+            response.extend(format!("name {} at port 4370\n", known).as_bytes());
+            stream.write_all(&response).await.unwrap();
+            continue;
+        }
+        assert_eq!(request[0], PORT2_REQ);
+        requests.fetch_add(1, Ordering::SeqCst);
+
+        let name = &request[1..];
+        if name == known.as_bytes() {
+            let mut response = vec![PORT2_RESP, 0];
+            response.extend(4370u16.to_be_bytes());
+            response.extend([77, 0]);
+            response.extend(6u16.to_be_bytes());
+            response.extend(6u16.to_be_bytes());
+            response.extend((name.len() as u16).to_be_bytes());
+            response.extend(name);
+            response.extend(0u16.to_be_bytes());
+            stream.write_all(&response).await.unwrap();
+        } else {
+            stream.write_all(&[PORT2_RESP, 1]).await.unwrap();
+        }
+    }
+}
+
+async fn start_epmd(known: &'static str) -> (u16, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let requests = Arc::new(AtomicUsize::new(0));
+    tokio::spawn(serve_port2(listener, known, requests.clone()));
+    (port, requests)
+}
 
 #[test]
 fn test_node_type_values() {
@@ -32,3 +91,205 @@ fn test_node_type_discriminants() {
     assert_ne!(NodeType::Normal as u8, NodeType::R3Hidden as u8);
     assert_ne!(NodeType::Hidden as u8, NodeType::R3Hidden as u8);
 }
+
+#[test]
+fn test_epmd_client_config_defaults() {
+    let config = EpmdClientConfig::default();
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 4369);
+    assert_eq!(config.lookup_attempts, 1);
+    assert!(config.cache_ttl.is_none());
+    assert!(config.negative_cache_ttl.is_none());
+
+    let config = config.with_lookup_retries(0, Duration::from_millis(10));
+    assert_eq!(config.lookup_attempts, 1);
+}
+
+#[tokio::test]
+async fn test_lookup_without_cache_queries_epmd_every_time() {
+    let (port, requests) = start_epmd("rabbit").await;
+    let client = EpmdClient::with_port("127.0.0.1", port);
+
+    for _ in 0..3 {
+        assert_eq!(client.lookup_node("rabbit").await.unwrap().port, 4370);
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_lookup_is_cached_until_the_ttl_expires() {
+    let (port, requests) = start_epmd("rabbit").await;
+    let config = EpmdClientConfig::new("127.0.0.1")
+        .with_port(port)
+        .with_cache_ttl(Duration::from_millis(200));
+    let client = EpmdClient::from_config(config);
+
+    let info = client.lookup_node("rabbit").await.unwrap();
+    assert_eq!(info.node_name, "rabbit");
+    assert_eq!(client.clone().lookup_node("rabbit").await.unwrap(), info);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    sleep(Duration::from_millis(250)).await;
+    client.lookup_node("rabbit").await.unwrap();
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+    client.invalidate("rabbit");
+    client.lookup_node("rabbit").await.unwrap();
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_not_found_is_negatively_cached() {
+    let (port, requests) = start_epmd("rabbit").await;
+    let config = EpmdClientConfig::new("127.0.0.1")
+        .with_port(port)
+        .with_lookup_retries(3, Duration::from_millis(10))
+        .with_negative_cache_ttl(Duration::from_secs(60));
+    let client = EpmdClient::from_config(config);
+
+    for _ in 0..3 {
+        let err = client.lookup_node("missing").await.unwrap_err();
+        assert!(
+            matches!(err, Error::EpmdNodeNotRegistered { ref node, ref registered, .. }
+                if node == "missing" && registered == &["rabbit"])
+        );
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    client.clear_cache();
+    client.lookup_node("missing").await.unwrap_err();
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_not_found_is_not_cached_without_a_negative_ttl() {
+    let (port, requests) = start_epmd("rabbit").await;
+    let config = EpmdClientConfig::new("127.0.0.1")
+        .with_port(port)
+        .with_cache_ttl(Duration::from_secs(60));
+    let client = EpmdClient::from_config(config);
+
+    client.lookup_node("missing").await.unwrap_err();
+    client.lookup_node("missing").await.unwrap_err();
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_lookup_retries_until_epmd_is_back() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let requests = Arc::new(AtomicUsize::new(0));
+    let restarted = requests.clone();
+    tokio::spawn(async move {
+        sleep(Duration::from_millis(150)).await;
+        let listener = TcpListener::bind(("127.0.0.1", port)).await.unwrap();
+        serve_port2(listener, "rabbit", restarted).await;
+    });
+
+    let config = EpmdClientConfig::new("127.0.0.1")
+        .with_port(port)
+        .with_lookup_retries(20, Duration::from_millis(50));
+    let client = EpmdClient::from_config(config);
+
+    assert_eq!(client.lookup_node("rabbit").await.unwrap().port, 4370);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_lookup_gives_up_after_the_configured_attempts() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let config = EpmdClientConfig::new("127.0.0.1")
+        .with_port(port)
+        .with_lookup_retries(2, Duration::from_millis(10));
+    let client = EpmdClient::from_config(config);
+
+    let err = client.lookup_node("rabbit").await.unwrap_err();
+    assert!(matches!(err, Error::EpmdProtocol(_)));
+}
+
+#[tokio::test]
+async fn test_lookup_resolves_the_epmd_host_with_the_configured_resolver() {
+    let (port, requests) = start_epmd("rabbit").await;
+    let resolver =
+        StaticResolver::new().with_host("epmd.service.consul", [Ipv4Addr::LOCALHOST.into()]);
+    let client = EpmdClient::with_port("epmd.service.consul", port).with_resolver(resolver);
+
+    assert_eq!(client.lookup_node("rabbit").await.unwrap().port, 4370);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_lookup_fails_when_the_epmd_host_does_not_resolve() {
+    let client = EpmdClient::new("epmd.service.consul").with_resolver(StaticResolver::new());
+    let err = client.lookup_node("rabbit").await.unwrap_err();
+    assert!(matches!(err, Error::EpmdProtocol(_)));
+}
+
+#[test]
+fn test_parse_names_skips_unrelated_lines() {
+    let response =
+        "name rabbit at port 25672\nname a b at port 4370\ngarbage\nname bad at port x\n";
+    assert_eq!(
+        parse_names(response),
+        vec![
+            RegisteredNode {
+                name: "rabbit".to_string(),
+                port: 25672,
+            },
+            RegisteredNode {
+                name: "a b".to_string(),
+                port: 4370,
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_not_registered_errors_name_the_epmd_and_its_nodes() {
+    let (port, _) = start_epmd("rabbit").await;
+    let client = EpmdClient::with_port("127.0.0.1", port);
+
+    let err = client.lookup_node("rabit").await.unwrap_err();
+    let Error::EpmdNodeNotRegistered {
+        node,
+        epmd_host,
+        epmd_port,
+        registered,
+    } = &err
+    else {
+        panic!("unexpected error: {err}");
+    };
+    assert_eq!(node, "rabit");
+    assert_eq!(epmd_host, "127.0.0.1");
+    assert_eq!(*epmd_port, port);
+    assert_eq!(registered, &["rabbit"]);
+    assert!(err.to_string().contains("registered nodes: [\"rabbit\"]"));
+    assert!(err.is_recoverable());
+}
+
+#[tokio::test]
+async fn test_unexpected_response_types_are_protocol_mismatches() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let len = stream.read_u16().await.unwrap();
+        let mut request = vec![0u8; len as usize];
+        stream.read_exact(&mut request).await.unwrap();
+        // Not a PORT2_RESP
+        stream.write_all(&[b'p', 0]).await.unwrap();
+    });
+    let client = EpmdClient::with_port("127.0.0.1", port);
+
+    let err = client.lookup_node("rabbit").await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::EpmdProtocolMismatch { ref node, epmd_port, response_type: b'p', .. }
+            if node == "rabbit" && epmd_port == port
+    ));
+}
@@ -67,6 +67,11 @@ fn test_connection_state_display() {
         ConnectionState::AwaitingChallengeAck.to_string(),
         "awaiting_challenge_ack"
     );
+    assert_eq!(ConnectionState::AwaitingName.to_string(), "awaiting_name");
+    assert_eq!(
+        ConnectionState::AwaitingChallengeReply.to_string(),
+        "awaiting_challenge_reply"
+    );
     assert_eq!(ConnectionState::Connected.to_string(), "connected");
     assert_eq!(ConnectionState::Failed.to_string(), "failed");
 }
@@ -0,0 +1,135 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, Error, LARGE_EXIT_REASON_SIZE};
+use erltf::OwnedTerm;
+use erltf::decoder::decode_with_trailing;
+use erltf::types::{Atom, ExternalPid};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "exit2_cookie";
+const PEER: &str = "peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const EXIT2: i64 = 8;
+const PAYLOAD_EXIT2: i64 = 26;
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+async fn read_frame(stream: &mut TcpStream) -> (Vec<OwnedTerm>, Option<OwnedTerm>) {
+    loop {
+        let len = stream.read_u32().await.unwrap();
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        assert_eq!(data[0], PASS_THROUGH);
+        let (control, rest) = decode_with_trailing(&data[1..]).unwrap();
+        let payload = (!rest.is_empty()).then(|| decode_with_trailing(rest).unwrap().0);
+        let OwnedTerm::Tuple(control) = control else {
+            panic!("unexpected control message: {:?}", control);
+        };
+        return (control, payload);
+    }
+}
+
+fn local_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)
+}
+
+fn remote_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new(PEER), 7, 0, 1)
+}
+
+async fn connect() -> (Connection, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener).await });
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(Duration::from_secs(5));
+    let mut conn = Connection::new(config);
+    conn.connect().await.unwrap();
+    (conn, peer.await.unwrap())
+}
+
+#[tokio::test]
+async fn test_exit_sends_exit2_with_the_reason() {
+    let (mut conn, mut stream) = connect().await;
+
+    conn.exit(&local_pid(), &remote_pid(), OwnedTerm::atom("shutdown"))
+        .await
+        .unwrap();
+
+    let (control, payload) = read_frame(&mut stream).await;
+    assert_eq!(
+        control,
+        vec![
+            OwnedTerm::integer(EXIT2),
+            OwnedTerm::Pid(local_pid()),
+            OwnedTerm::Pid(remote_pid()),
+            OwnedTerm::atom("shutdown"),
+        ]
+    );
+    assert_eq!(payload, None);
+}
+
+#[tokio::test]
+async fn test_kill_sends_exit2_with_kill() {
+    let (mut conn, mut stream) = connect().await;
+
+    conn.kill(&local_pid(), &remote_pid()).await.unwrap();
+
+    let (control, _) = read_frame(&mut stream).await;
+    assert_eq!(control[0], OwnedTerm::integer(EXIT2));
+    assert_eq!(control[3], OwnedTerm::atom("kill"));
+}
+
+#[tokio::test]
+async fn test_large_exit_reasons_are_sent_as_payload() {
+    let (mut conn, mut stream) = connect().await;
+    let reason = OwnedTerm::tuple(vec![
+        OwnedTerm::atom("badarg"),
+        OwnedTerm::binary(vec![0u8; LARGE_EXIT_REASON_SIZE]),
+    ]);
+
+    conn.exit(&local_pid(), &remote_pid(), reason.clone())
+        .await
+        .unwrap();
+
+    let (control, payload) = read_frame(&mut stream).await;
+    assert_eq!(
+        control,
+        vec![
+            OwnedTerm::integer(PAYLOAD_EXIT2),
+            OwnedTerm::Pid(local_pid()),
+            OwnedTerm::Pid(remote_pid()),
+        ]
+    );
+    assert_eq!(payload, Some(reason));
+}
+
+#[tokio::test]
+async fn test_kill_requires_a_connection() {
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE);
+    let mut conn = Connection::new(config);
+
+    let err = conn.kill(&local_pid(), &remote_pid()).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidState { .. }));
+}
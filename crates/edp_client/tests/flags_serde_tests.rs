@@ -0,0 +1,27 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "serde")]
+
+use edp_client::flags::DistributionFlags;
+
+#[test]
+fn test_flags_serialize_as_names_in_human_readable_formats() {
+    let flags = DistributionFlags::PUBLISHED | DistributionFlags::UNLINK_ID;
+    let json = serde_json::to_string(&flags).unwrap();
+    assert_eq!(json, "\"PUBLISHED | UNLINK_ID\"");
+
+    let parsed: DistributionFlags = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, flags);
+}
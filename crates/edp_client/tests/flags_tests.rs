@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use edp_client::flags::DistributionFlags;
+use edp_client::flags::{CompatibilityReport, DistributionFlags, FlagsDiff};
 
 #[test]
 fn test_mandatory_flags() {
@@ -54,3 +54,93 @@ fn test_default_hidden_not_published() {
     assert!(flags.has(DistributionFlags::FRAGMENTS));
     assert!(!flags.has(DistributionFlags::PUBLISHED));
 }
+
+#[test]
+fn test_display_lists_flag_names() {
+    let flags = DistributionFlags::PUBLISHED | DistributionFlags::FRAGMENTS;
+    assert_eq!(flags.to_string(), "PUBLISHED | FRAGMENTS");
+    assert_eq!(DistributionFlags::empty().to_string(), "(none)");
+
+    let with_unknown = DistributionFlags::new(DistributionFlags::PUBLISHED.bits() | 0x1000);
+    assert_eq!(with_unknown.to_string(), "PUBLISHED | 0x1000");
+    assert!(format!("{:?}", flags).contains("FRAGMENTS"));
+}
+
+#[test]
+fn test_from_str_round_trips_display() {
+    let flags = DistributionFlags::default_otp26();
+    let parsed: DistributionFlags = flags.to_string().parse().unwrap();
+    assert_eq!(parsed, flags);
+
+    let parsed: DistributionFlags = "(none)".parse().unwrap();
+    assert!(parsed.is_empty());
+    assert!("NOT_A_FLAG".parse::<DistributionFlags>().is_err());
+}
+
+#[test]
+fn test_diff_reports_rejected_flags() {
+    let requested = DistributionFlags::default_otp26();
+    let negotiated = requested - DistributionFlags::FRAGMENTS - DistributionFlags::ALIAS;
+
+    let diff = requested.diff(&negotiated);
+    assert_eq!(
+        diff,
+        FlagsDiff {
+            missing: DistributionFlags::FRAGMENTS | DistributionFlags::ALIAS,
+            added: DistributionFlags::empty(),
+        }
+    );
+    assert!(!diff.is_empty());
+    assert_eq!(
+        diff.to_string(),
+        "missing: FRAGMENTS | ALIAS, added: (none)"
+    );
+
+    let reverse = negotiated.diff(&requested);
+    assert_eq!(reverse.added, diff.missing);
+    assert!(requested.diff(&requested).is_empty());
+}
+
+#[test]
+fn test_compatibility_report_of_default_flags() {
+    let report = DistributionFlags::default().compatibility_report();
+    assert_eq!(report.flags, DistributionFlags::default());
+    assert!(report.fragmentation);
+    assert!(!report.atom_cache);
+    assert!(report.big_creation);
+    assert!(report.v4_node_container_ids);
+    assert!(report.alias);
+    assert!(report.spawn);
+    assert!(report.unlink_id);
+    assert!(report.send_sender);
+    assert_eq!(
+        report.to_string(),
+        "fragmentation, big_creation, v4_node_container_ids, alias, spawn, unlink_id, send_sender"
+    );
+}
+
+#[test]
+fn test_compatibility_report_of_partial_flags() {
+    let flags = DistributionFlags::MANDATORY_OTP26 | DistributionFlags::SPAWN;
+    let report = CompatibilityReport::from(flags);
+    assert!(!report.fragmentation);
+    assert!(!report.atom_cache);
+    assert!(!report.alias);
+    assert!(report.spawn);
+    assert!(report.unlink_id);
+    assert!(report.v4_node_container_ids);
+    assert!(report.enabled().contains(&"spawn"));
+    assert!(!report.enabled().contains(&"alias"));
+}
+
+#[test]
+fn test_compatibility_report_of_no_flags() {
+    let report = DistributionFlags::empty().compatibility_report();
+    assert!(
+        (DistributionFlags::DIST_HDR_ATOM_CACHE)
+            .compatibility_report()
+            .atom_cache
+    );
+    assert!(report.enabled().is_empty());
+    assert_eq!(report.to_string(), "(none)");
+}
@@ -0,0 +1,125 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, Error};
+use erltf::OwnedTerm;
+use erltf::decoder::decode_with_trailing;
+use erltf::types::{Atom, ExternalPid, ExternalReference};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "group_leader_cookie";
+const PEER: &str = "peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const GROUP_LEADER: i64 = 7;
+const SPAWN_REQUEST: i64 = 29;
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+async fn read_frame(stream: &mut TcpStream) -> (Vec<OwnedTerm>, Option<OwnedTerm>) {
+    loop {
+        let len = stream.read_u32().await.unwrap();
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        assert_eq!(data[0], PASS_THROUGH);
+        let (control, rest) = decode_with_trailing(&data[1..]).unwrap();
+        let payload = (!rest.is_empty()).then(|| decode_with_trailing(rest).unwrap().0);
+        let OwnedTerm::Tuple(control) = control else {
+            panic!("unexpected control message: {:?}", control);
+        };
+        return (control, payload);
+    }
+}
+
+fn local_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)
+}
+
+fn remote_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new(PEER), 7, 0, 1)
+}
+
+async fn connect() -> (Connection, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener).await });
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(Duration::from_secs(5));
+    let mut conn = Connection::new(config);
+    conn.connect().await.unwrap();
+    (conn, peer.await.unwrap())
+}
+
+#[tokio::test]
+async fn test_set_group_leader_sends_group_leader() {
+    let (mut conn, mut stream) = connect().await;
+
+    conn.set_group_leader(&remote_pid(), &local_pid())
+        .await
+        .unwrap();
+
+    let (control, payload) = read_frame(&mut stream).await;
+    assert_eq!(
+        control,
+        vec![
+            OwnedTerm::integer(GROUP_LEADER),
+            OwnedTerm::Pid(local_pid()),
+            OwnedTerm::Pid(remote_pid()),
+        ]
+    );
+    assert_eq!(payload, None);
+}
+
+#[tokio::test]
+async fn test_spawn_request_carries_a_local_group_leader() {
+    let (mut conn, mut stream) = connect().await;
+    let req_id = ExternalReference::new(Atom::new("rust@localhost"), 1, vec![1, 2, 3]);
+    let io_server = ExternalPid::new(Atom::new("rust@localhost"), 2, 0, 1);
+
+    conn.spawn_request(
+        &req_id,
+        &local_pid(),
+        &io_server,
+        Atom::new("io"),
+        Atom::new("format"),
+        vec![OwnedTerm::binary(b"hello~n".to_vec())],
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let (control, _) = read_frame(&mut stream).await;
+    assert_eq!(control[0], OwnedTerm::integer(SPAWN_REQUEST));
+    assert_eq!(control[3], OwnedTerm::Pid(io_server));
+}
+
+#[tokio::test]
+async fn test_set_group_leader_requires_a_connection() {
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE);
+    let mut conn = Connection::new(config);
+
+    let err = conn
+        .set_group_leader(&remote_pid(), &local_pid())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidState { .. }));
+}
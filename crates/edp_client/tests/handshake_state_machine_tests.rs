@@ -0,0 +1,437 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::ConnectionState;
+use edp_client::digest::{ChallengeSource, FixedChallenge};
+use edp_client::errors::Error;
+use edp_client::flags::DistributionFlags;
+use edp_client::handshake::{Challenge, ChallengeAck, ChallengeReply, SendName, Status};
+use edp_client::state_machine::{
+    HandshakeAction, HandshakeEvent, HandshakeRole, HandshakeStateMachine,
+};
+use std::mem;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+const COOKIE: &str = "cookie";
+
+fn state_machine() -> HandshakeStateMachine {
+    HandshakeStateMachine::new(
+        "rust@localhost".to_string(),
+        "erlang@localhost".to_string(),
+        COOKIE.to_string(),
+        DistributionFlags::default_otp26(),
+        1,
+    )
+}
+
+fn accepting() -> HandshakeStateMachine {
+    HandshakeStateMachine::accepting(
+        "erlang@localhost".to_string(),
+        COOKIE.to_string(),
+        DistributionFlags::default_otp26(),
+        7,
+    )
+}
+
+fn received(data: &[u8]) -> HandshakeEvent {
+    HandshakeEvent::Received(data.to_vec())
+}
+
+fn sent(actions: &[HandshakeAction]) -> &[u8] {
+    match actions {
+        [HandshakeAction::Send(data)] => data,
+        other => panic!("expected a single send, got {other:?}"),
+    }
+}
+
+fn challenge(flags: DistributionFlags, challenge: u32) -> Vec<u8> {
+    Challenge::new(flags, challenge, 7, "erlang@localhost")
+        .encode()
+        .unwrap()[2..]
+        .to_vec()
+}
+
+/// Runs the handshake up to the challenge ack and returns the peer's view of the reply.
+fn run_until_challenge_ack(sm: &mut HandshakeStateMachine) -> ChallengeReply {
+    let (actions, state) = sm.on_event(HandshakeEvent::Connect).unwrap();
+    assert_eq!(state, ConnectionState::AwaitingStatus);
+    let send_name = sent(&actions);
+    assert_eq!(send_name[2], b'n');
+    assert!(send_name.ends_with(b"rust@localhost"));
+
+    let (actions, state) = sm.on_event(received(b"sok")).unwrap();
+    assert_eq!(state, ConnectionState::AwaitingChallenge);
+    let complement = sent(&actions);
+    assert_eq!(complement[2], b'c');
+
+    let (actions, state) = sm
+        .on_event(received(&challenge(DistributionFlags::default_otp26(), 42)))
+        .unwrap();
+    assert_eq!(state, ConnectionState::AwaitingChallengeAck);
+    let reply = ChallengeReply::decode(&sent(&actions)[2..]).unwrap();
+    assert!(reply.verify(42, COOKIE));
+    reply
+}
+
+#[test]
+fn test_on_event_completes_the_handshake() {
+    let mut sm = state_machine();
+    let reply = run_until_challenge_ack(&mut sm);
+
+    let ack = ChallengeAck::new(reply.challenge, COOKIE).encode();
+    let (actions, state) = sm.on_event(received(&ack[2..])).unwrap();
+    assert_eq!(actions, vec![HandshakeAction::Complete]);
+    assert_eq!(state, ConnectionState::Connected);
+    assert_eq!(sm.state(), ConnectionState::Connected);
+    assert_eq!(sm.peer_creation(), Some(7));
+    assert!(sm.negotiated_flags().is_some());
+}
+
+#[test]
+fn test_fixed_challenge_source_makes_the_handshake_byte_exact() {
+    let mut sm = state_machine().with_challenge_source(Arc::new(FixedChallenge(0xDEAD_BEEF)));
+    sm.on_event(HandshakeEvent::Connect).unwrap();
+    sm.on_event(received(b"sok")).unwrap();
+
+    let (actions, _) = sm
+        .on_event(received(&challenge(DistributionFlags::default_otp26(), 42)))
+        .unwrap();
+    let golden = ChallengeReply::new(0xDEAD_BEEF, 42, COOKIE).encode();
+    assert_eq!(sent(&actions), golden.as_slice());
+    assert_eq!(
+        &golden[..7],
+        &[0x00, 0x15, b'r', 0xDE, 0xAD, 0xBE, 0xEF],
+        "length prefix, tag and our challenge"
+    );
+
+    let ack = ChallengeAck::new(0xDEAD_BEEF, COOKIE).encode();
+    let (actions, state) = sm.on_event(received(&ack[2..])).unwrap();
+    assert_eq!(actions, vec![HandshakeAction::Complete]);
+    assert_eq!(state, ConnectionState::Connected);
+}
+
+#[test]
+fn test_closures_are_challenge_sources() {
+    let counter = Arc::new(AtomicU32::new(100));
+    let source = {
+        let counter = Arc::clone(&counter);
+        move || counter.fetch_add(1, Ordering::Relaxed)
+    };
+    assert_eq!(source.next_challenge(), 100);
+
+    let mut sm = state_machine().with_challenge_source(Arc::new(source));
+    let reply = run_until_challenge_ack(&mut sm);
+    assert_eq!(reply.challenge, 101);
+    assert_eq!(counter.load(Ordering::Relaxed), 102);
+}
+
+#[test]
+fn test_connect_is_accepted_after_begin_connect() {
+    let mut sm = state_machine();
+    sm.begin_connect().unwrap();
+    let (actions, state) = sm.on_event(HandshakeEvent::Connect).unwrap();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(state, ConnectionState::AwaitingStatus);
+}
+
+#[test]
+fn test_refused_status_fails_the_handshake() {
+    let mut sm = state_machine();
+    sm.on_event(HandshakeEvent::Connect).unwrap();
+
+    let err = sm.on_event(received(b"snot_allowed")).unwrap_err();
+    assert!(matches!(err, Error::ConnectionRefused { .. }));
+    assert_eq!(sm.state(), ConnectionState::Failed);
+}
+
+#[test]
+fn test_alive_status_is_answered_with_true_by_default() {
+    let mut sm = state_machine();
+    sm.on_event(HandshakeEvent::Connect).unwrap();
+
+    let (actions, state) = sm.on_event(received(b"salive")).unwrap();
+    assert_eq!(state, ConnectionState::AwaitingChallenge);
+    assert_eq!(sm.peer_status(), Some(Status::Alive));
+    assert_eq!(actions.len(), 2);
+    assert_eq!(actions[0], HandshakeAction::Send(b"\x00\x05strue".to_vec()));
+    assert!(matches!(&actions[1], HandshakeAction::Send(data) if data[2] == b'c'));
+
+    let (_, state) = sm
+        .on_event(received(&challenge(DistributionFlags::default_otp26(), 42)))
+        .unwrap();
+    assert_eq!(state, ConnectionState::AwaitingChallengeAck);
+}
+
+#[test]
+fn test_alive_answer_callback_receives_the_peer_name() {
+    let asked = Arc::new(Mutex::new(Vec::new()));
+    let callback = {
+        let asked = Arc::clone(&asked);
+        move |node: &str| {
+            asked.lock().unwrap().push(node.to_string());
+            false
+        }
+    };
+    let mut sm = state_machine().with_alive_answer(Arc::new(callback));
+    sm.on_event(HandshakeEvent::Connect).unwrap();
+
+    let (actions, state) = sm.on_event(received(b"salive")).unwrap();
+    assert_eq!(state, ConnectionState::Failed);
+    assert_eq!(sent(&actions), b"\x00\x06sfalse");
+    assert_eq!(*asked.lock().unwrap(), vec!["erlang@localhost".to_string()]);
+
+    assert!(
+        sm.on_event(received(&challenge(DistributionFlags::default_otp26(), 42)))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_ok_simultaneous_status_continues_the_handshake() {
+    let mut sm = state_machine().with_alive_answer(Arc::new(|_: &str| false));
+    sm.on_event(HandshakeEvent::Connect).unwrap();
+
+    let (actions, state) = sm.on_event(received(b"sok_simultaneous")).unwrap();
+    assert_eq!(state, ConnectionState::AwaitingChallenge);
+    assert_eq!(sm.peer_status(), Some(Status::OkSimultaneous));
+    assert_eq!(sent(&actions)[2], b'c');
+    assert_eq!(sm.prepare_alive_answer(), None);
+}
+
+#[test]
+fn test_wrong_cookie_in_ack_fails_the_handshake() {
+    let mut sm = state_machine();
+    let reply = run_until_challenge_ack(&mut sm);
+
+    let ack = ChallengeAck::new(reply.challenge, "another cookie").encode();
+    let err = sm.on_event(received(&ack[2..])).unwrap_err();
+    assert!(matches!(err, Error::AuthenticationFailed));
+    assert_eq!(sm.state(), ConnectionState::Failed);
+}
+
+#[test]
+fn test_unexpected_events_fail_the_handshake() {
+    let mut sm = state_machine();
+    let err = sm.on_event(received(b"sok")).unwrap_err();
+    assert!(matches!(err, Error::InvalidStateMessage(_)));
+    assert_eq!(sm.state(), ConnectionState::Failed);
+
+    assert!(sm.on_event(HandshakeEvent::Connect).is_err());
+    assert_eq!(sm.state(), ConnectionState::Failed);
+
+    let mut sm = state_machine();
+    sm.on_event(HandshakeEvent::Connect).unwrap();
+    assert!(sm.on_event(HandshakeEvent::Connect).is_err());
+}
+
+#[test]
+fn test_disconnect_resets_from_any_state() {
+    let mut sm = state_machine();
+    run_until_challenge_ack(&mut sm);
+
+    let (actions, state) = sm.on_event(HandshakeEvent::Disconnect).unwrap();
+    assert!(actions.is_empty());
+    assert_eq!(state, ConnectionState::Disconnected);
+    assert!(sm.negotiated_flags().is_none());
+
+    sm.on_event(received(b"sok")).unwrap_err();
+    sm.on_event(HandshakeEvent::Disconnect).unwrap();
+    let reply = run_until_challenge_ack(&mut sm);
+    let ack = ChallengeAck::new(reply.challenge, COOKIE).encode();
+    let (_, state) = sm.on_event(received(&ack[2..])).unwrap();
+    assert_eq!(state, ConnectionState::Connected);
+}
+
+/// Delivers the messages each side sends to the other until neither has anything left to send.
+fn run_against_each_other(client: &mut HandshakeStateMachine, server: &mut HandshakeStateMachine) {
+    server.on_event(HandshakeEvent::Accept).unwrap();
+    let (mut to_server, _) = client.on_event(HandshakeEvent::Connect).unwrap();
+    let mut to_client = Vec::new();
+    while !to_server.is_empty() || !to_client.is_empty() {
+        for action in mem::take(&mut to_server) {
+            if let HandshakeAction::Send(data) = action {
+                to_client.extend(server.on_event(received(&data[2..])).unwrap().0);
+            }
+        }
+        for action in mem::take(&mut to_client) {
+            if let HandshakeAction::Send(data) = action {
+                to_server.extend(client.on_event(received(&data[2..])).unwrap().0);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_server_role_completes_a_handshake_with_the_client_role() {
+    let mut client = state_machine();
+    let mut server = accepting();
+    assert_eq!(server.role(), HandshakeRole::Server);
+    assert_eq!(server.remote_node_name(), "");
+
+    run_against_each_other(&mut client, &mut server);
+
+    assert_eq!(client.state(), ConnectionState::Connected);
+    assert_eq!(server.state(), ConnectionState::Connected);
+    assert_eq!(server.remote_node_name(), "rust@localhost");
+    assert_eq!(server.peer_creation(), Some(1));
+    assert_eq!(client.peer_creation(), Some(7));
+    assert_eq!(server.negotiated_flags(), client.negotiated_flags());
+    assert!(
+        server
+            .negotiated_flags()
+            .unwrap()
+            .contains(DistributionFlags::HANDSHAKE_23)
+    );
+}
+
+#[test]
+fn test_server_role_answers_a_send_name_with_status_and_challenge() {
+    let mut sm = accepting().with_challenge_source(Arc::new(FixedChallenge(42)));
+    let (actions, state) = sm.on_event(HandshakeEvent::Accept).unwrap();
+    assert!(actions.is_empty());
+    assert_eq!(state, ConnectionState::AwaitingName);
+
+    let send_name = SendName::new(DistributionFlags::default_otp26(), 3, "rust@localhost")
+        .encode()
+        .unwrap();
+    let (actions, state) = sm.on_event(received(&send_name[2..])).unwrap();
+    assert_eq!(state, ConnectionState::AwaitingChallengeReply);
+    assert_eq!(actions[0], HandshakeAction::Send(b"\x00\x03sok".to_vec()));
+    let HandshakeAction::Send(challenge) = &actions[1] else {
+        panic!("expected a challenge, got {:?}", actions[1]);
+    };
+    let challenge = Challenge::decode(&challenge[2..]).unwrap();
+    assert_eq!(challenge.challenge, 42);
+    assert_eq!(challenge.creation, 7);
+    assert_eq!(challenge.name, "erlang@localhost");
+    assert_eq!(sm.peer_creation(), Some(3));
+
+    let reply = ChallengeReply::new(99, 42, COOKIE).encode();
+    let (actions, state) = sm.on_event(received(&reply[2..])).unwrap();
+    assert_eq!(state, ConnectionState::Connected);
+    assert_eq!(actions.len(), 2);
+    let HandshakeAction::Send(ack) = &actions[0] else {
+        panic!("expected an ack, got {:?}", actions[0]);
+    };
+    assert!(ChallengeAck::decode(&ack[2..]).unwrap().verify(99, COOKIE));
+    assert_eq!(actions[1], HandshakeAction::Complete);
+}
+
+#[test]
+fn test_server_role_waits_for_the_complement_of_an_old_send_name() {
+    let mut sm = accepting();
+    sm.on_event(HandshakeEvent::Accept).unwrap();
+
+    let send_name = SendName::new(DistributionFlags::default_otp26(), 3, "rust@localhost")
+        .encode_old()
+        .unwrap();
+    let (actions, state) = sm.on_event(received(&send_name[2..])).unwrap();
+    assert_eq!(state, ConnectionState::AwaitingComplement);
+    assert_eq!(sent(&actions), b"\x00\x03sok");
+    assert_eq!(sm.peer_creation(), None);
+
+    let high_flags = (DistributionFlags::default_otp26().as_u64() >> 32) as u32;
+    let mut complement = vec![b'c'];
+    complement.extend(high_flags.to_be_bytes());
+    complement.extend(3u32.to_be_bytes());
+    let (actions, state) = sm.on_event(received(&complement)).unwrap();
+    assert_eq!(state, ConnectionState::AwaitingChallengeReply);
+    assert_eq!(sent(&actions)[2], b'N');
+    assert_eq!(sm.peer_creation(), Some(3));
+    assert_eq!(
+        sm.negotiated_flags(),
+        Some(DistributionFlags::default_otp26())
+    );
+}
+
+#[test]
+fn test_server_role_rejects_a_reply_with_the_wrong_cookie() {
+    let mut sm = accepting();
+    sm.on_event(HandshakeEvent::Accept).unwrap();
+
+    let send_name = SendName::new(DistributionFlags::default_otp26(), 3, "rust@localhost")
+        .encode()
+        .unwrap();
+    let (actions, _) = sm.on_event(received(&send_name[2..])).unwrap();
+    let HandshakeAction::Send(challenge) = &actions[1] else {
+        panic!("expected a challenge, got {:?}", actions[1]);
+    };
+    let challenge = Challenge::decode(&challenge[2..]).unwrap();
+
+    let reply = ChallengeReply::new(1, challenge.challenge, "another cookie").encode();
+    let err = sm.on_event(received(&reply[2..])).unwrap_err();
+    assert!(matches!(err, Error::AuthenticationFailed));
+    assert_eq!(sm.state(), ConnectionState::Failed);
+}
+
+#[test]
+fn test_server_role_requires_mandatory_flags() {
+    let mut sm = accepting().with_required_flags(DistributionFlags::FRAGMENTS);
+    sm.on_event(HandshakeEvent::Accept).unwrap();
+
+    let flags = DistributionFlags::default_otp26().difference(DistributionFlags::FRAGMENTS);
+    let send_name = SendName::new(flags, 3, "rust@localhost").encode().unwrap();
+    let err = sm.on_event(received(&send_name[2..])).unwrap_err();
+    assert!(matches!(err, Error::MissingMandatoryFlags { .. }));
+    assert_eq!(sm.state(), ConnectionState::Failed);
+}
+
+#[cfg(not(feature = "legacy-handshake"))]
+#[test]
+fn test_server_role_rejects_version_5_peers_without_legacy_handshake() {
+    let mut sm = accepting();
+    sm.on_event(HandshakeEvent::Accept).unwrap();
+
+    let flags = DistributionFlags::default_otp26().difference(DistributionFlags::HANDSHAKE_23);
+    let send_name = SendName::new(flags, 0, "rust@localhost")
+        .encode_old()
+        .unwrap();
+    let err = sm.on_event(received(&send_name[2..])).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::IncompatibleVersion {
+            got: 5,
+            expected: 6
+        }
+    ));
+}
+
+#[test]
+fn test_roles_reject_each_others_start_events() {
+    let mut server = accepting();
+    assert!(server.on_event(HandshakeEvent::Connect).is_err());
+    assert_eq!(server.state(), ConnectionState::Failed);
+
+    let mut client = state_machine();
+    assert_eq!(client.role(), HandshakeRole::Client);
+    assert!(client.on_event(HandshakeEvent::Accept).is_err());
+    assert_eq!(client.state(), ConnectionState::Failed);
+}
+
+#[test]
+fn test_disconnect_forgets_the_accepted_peer() {
+    let mut client = state_machine();
+    let mut server = accepting();
+    run_against_each_other(&mut client, &mut server);
+
+    let (_, state) = server.on_event(HandshakeEvent::Disconnect).unwrap();
+    assert_eq!(state, ConnectionState::Disconnected);
+    assert_eq!(server.remote_node_name(), "");
+    assert!(server.negotiated_flags().is_none());
+
+    client.on_event(HandshakeEvent::Disconnect).unwrap();
+    run_against_each_other(&mut client, &mut server);
+    assert_eq!(server.state(), ConnectionState::Connected);
+}
@@ -12,8 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use edp_client::errors::Error;
 use edp_client::flags::DistributionFlags;
-use edp_client::handshake::{ChallengeAck, ChallengeReply, SendName, Status};
+use edp_client::handshake::{
+    Challenge, ChallengeAck, ChallengeReply, HandshakeVersion, SendName, Status, StatusMessage,
+};
+use edp_client::state_machine::HandshakeStateMachine;
+use std::fs;
+use std::path::Path;
+
+/// When set, `test_write_fuzz_seeds` writes handshake messages there as `cargo fuzz` seeds
+const FUZZ_SEEDS_ENV_VAR: &str = "EDP_FUZZ_SEEDS_DIR";
 
 //
 // SendName Message
@@ -29,6 +38,18 @@ fn test_send_name_encode_decode() {
     assert_eq!(original, decoded);
 }
 
+#[test]
+fn test_old_send_name_encode_decode() {
+    let flags = DistributionFlags::new(DistributionFlags::default_otp26().as_u64() & 0xFFFF_FFFF);
+    let original = SendName::new(flags, 0, "test@localhost");
+
+    let encoded = original.encode_old().unwrap();
+    assert_eq!(encoded[2], b'n');
+    let decoded = SendName::decode_old(&encoded[2..]).unwrap();
+
+    assert_eq!(original, decoded);
+}
+
 #[test]
 fn test_send_name_node_name_too_long() {
     let long_name = "a".repeat(300);
@@ -42,6 +63,27 @@ fn test_send_name_node_name_too_long() {
 // Status Handling
 //
 
+#[test]
+fn test_status_message_encode_decode() {
+    assert_eq!(
+        StatusMessage::new(Status::Ok).encode(),
+        b"\x00\x03sok".to_vec()
+    );
+    for status in [
+        Status::Ok,
+        Status::OkSimultaneous,
+        Status::Nok,
+        Status::NotAllowed,
+        Status::Alive,
+    ] {
+        let encoded = StatusMessage::new(status).encode();
+        assert_eq!(
+            StatusMessage::decode(&encoded[2..]).unwrap(),
+            StatusMessage::new(status)
+        );
+    }
+}
+
 #[test]
 fn test_status_is_ok() {
     assert!(Status::Ok.is_ok());
@@ -102,3 +144,136 @@ fn test_challenge_ack_roundtrip() {
 
     assert_eq!(ack.digest, decoded.digest);
 }
+
+//
+// Required Flags
+//
+
+fn state_machine(required: DistributionFlags) -> HandshakeStateMachine {
+    HandshakeStateMachine::new(
+        "rust@localhost".to_string(),
+        "erlang@localhost".to_string(),
+        "cookie".to_string(),
+        DistributionFlags::default_otp26(),
+        1,
+    )
+    .with_required_flags(required)
+}
+
+fn challenge_from_peer(flags: DistributionFlags) -> Vec<u8> {
+    let encoded = Challenge::new(flags, 42, 1, "erlang@localhost")
+        .encode()
+        .unwrap();
+    encoded[2..].to_vec()
+}
+
+#[test]
+fn test_required_flags_accepted_when_peer_offers_them() {
+    let mut sm = state_machine(DistributionFlags::FRAGMENTS | DistributionFlags::V4_NC);
+    sm.handle_challenge(&challenge_from_peer(DistributionFlags::default_otp26()))
+        .unwrap();
+    assert!(
+        sm.negotiated_flags()
+            .unwrap()
+            .contains(DistributionFlags::FRAGMENTS)
+    );
+}
+
+#[test]
+fn test_required_flags_fail_handshake_when_peer_lacks_them() {
+    let mut sm = state_machine(DistributionFlags::FRAGMENTS | DistributionFlags::UNLINK_ID);
+    let peer = DistributionFlags::default_otp26() - DistributionFlags::FRAGMENTS;
+
+    let err = sm.handle_challenge(&challenge_from_peer(peer)).unwrap_err();
+    match err {
+        Error::MissingMandatoryFlags { missing } => assert_eq!(missing, vec!["FRAGMENTS"]),
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(sm.negotiated_flags().is_none());
+}
+
+//
+// Handshake Version Selection
+//
+
+#[test]
+fn test_handshake_version_selects_v6_when_advertised() {
+    assert_eq!(
+        HandshakeVersion::select(5, 6).unwrap(),
+        HandshakeVersion::V6
+    );
+    assert_eq!(
+        HandshakeVersion::select(6, 6).unwrap(),
+        HandshakeVersion::V6
+    );
+}
+
+#[test]
+fn test_handshake_version_rejects_unknown_range() {
+    assert!(matches!(
+        HandshakeVersion::select(7, 8),
+        Err(Error::IncompatibleVersion { got: 8, .. })
+    ));
+}
+
+#[cfg(not(feature = "legacy-handshake"))]
+#[test]
+fn test_handshake_version_rejects_v5_peers_without_legacy_feature() {
+    assert!(matches!(
+        HandshakeVersion::select(5, 5),
+        Err(Error::IncompatibleVersion {
+            got: 5,
+            expected: 6
+        })
+    ));
+}
+
+#[test]
+fn test_alive_answer_encoding() {
+    assert_eq!(
+        StatusMessage::encode_alive_answer(true),
+        b"\x00\x05strue".to_vec()
+    );
+    assert_eq!(
+        StatusMessage::encode_alive_answer(false),
+        b"\x00\x06sfalse".to_vec()
+    );
+}
+
+#[test]
+fn test_v6_handshake_needs_complement() {
+    let mut sm = state_machine(DistributionFlags::empty());
+    sm.select_handshake_version(5, 6).unwrap();
+    assert!(sm.needs_complement());
+}
+
+#[test]
+fn test_write_fuzz_seeds() {
+    let Some(dir) = std::env::var_os(FUZZ_SEEDS_ENV_VAR) else {
+        return;
+    };
+    let dir = Path::new(&dir).join("handshake");
+    fs::create_dir_all(&dir).unwrap();
+
+    let flags = DistributionFlags::default_otp26();
+    let seeds = [
+        (
+            "send_name",
+            SendName::new(flags, 1, "a@host").encode().unwrap(),
+        ),
+        ("status_ok", StatusMessage::new(Status::Ok).encode()),
+        (
+            "challenge",
+            Challenge::new(flags, 42, 1, "b@host").encode().unwrap(),
+        ),
+        (
+            "challenge_reply",
+            ChallengeReply::new(7, 42, "cookie").encode(),
+        ),
+        ("challenge_ack", ChallengeAck::new(7, "cookie").encode()),
+    ];
+    // The parsers receive messages without the 2-byte length prefix
+    for (name, message) in seeds {
+        fs::write(dir.join(name), &message[2..]).unwrap();
+    }
+}
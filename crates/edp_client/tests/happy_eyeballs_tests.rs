@@ -0,0 +1,109 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::Error;
+use edp_client::happy_eyeballs::{connect, connect_to_addresses, interleave_addresses};
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+
+fn addr(s: &str) -> SocketAddr {
+    s.parse().unwrap()
+}
+
+/// Returns an address on which nothing listens.
+async fn closed_addr() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    listener.local_addr().unwrap()
+}
+
+#[test]
+fn test_interleave_addresses_alternates_families() {
+    let addrs = vec![
+        addr("[::1]:1"),
+        addr("[::2]:1"),
+        addr("[::3]:1"),
+        addr("10.0.0.1:1"),
+        addr("10.0.0.2:1"),
+    ];
+
+    assert_eq!(
+        interleave_addresses(addrs),
+        vec![
+            addr("[::1]:1"),
+            addr("10.0.0.1:1"),
+            addr("[::2]:1"),
+            addr("10.0.0.2:1"),
+            addr("[::3]:1"),
+        ]
+    );
+}
+
+#[test]
+fn test_interleave_addresses_starts_with_first_family() {
+    let addrs = vec![addr("10.0.0.1:1"), addr("10.0.0.2:1"), addr("[::1]:1")];
+
+    assert_eq!(
+        interleave_addresses(addrs),
+        vec![addr("10.0.0.1:1"), addr("[::1]:1"), addr("10.0.0.2:1")]
+    );
+}
+
+#[test]
+fn test_interleave_addresses_empty() {
+    assert!(interleave_addresses(Vec::new()).is_empty());
+}
+
+#[tokio::test]
+async fn test_connect_to_addresses_skips_failed_addresses() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let open = listener.local_addr().unwrap();
+    let closed = closed_addr().await;
+
+    let started = Instant::now();
+    let (_stream, winner) = connect_to_addresses(vec![closed, open], Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    assert_eq!(winner, open);
+    // A refused attempt starts the next one right away instead of waiting for the delay
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_connect_to_addresses_fails_when_all_fail() {
+    let result = connect_to_addresses(
+        vec![closed_addr().await, closed_addr().await],
+        Duration::ZERO,
+    )
+    .await;
+    assert!(matches!(result, Err(Error::Io(_))));
+}
+
+#[tokio::test]
+async fn test_connect_to_addresses_without_addresses() {
+    let result = connect_to_addresses(Vec::new(), Duration::ZERO).await;
+    assert!(matches!(result, Err(Error::Io(_))));
+}
+
+#[tokio::test]
+async fn test_connect_resolves_host() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let (_stream, winner) = connect("127.0.0.1", port, Duration::from_millis(50))
+        .await
+        .unwrap();
+    assert_eq!(winner, listener.local_addr().unwrap());
+}
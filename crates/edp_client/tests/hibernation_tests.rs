@@ -0,0 +1,188 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, DistributionFlags};
+use erltf::OwnedTerm;
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::sleep;
+
+const COOKIE: &str = "hibernation_cookie";
+const PEER: &str = "hibernation_peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+
+async fn accept_handshake(listener: &TcpListener, flags: DistributionFlags) -> TcpStream {
+    TestPeer::new(PEER, COOKIE)
+        .with_flags(flags)
+        .accept(listener)
+        .await
+}
+
+async fn read_frame(stream: &mut TcpStream) -> Vec<u8> {
+    let len = stream.read_u32().await.unwrap();
+    let mut body = vec![0u8; len as usize];
+    stream.read_exact(&mut body).await.unwrap();
+    body
+}
+
+fn local_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)
+}
+
+fn peer_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new(PEER), 1, 0, 1)
+}
+
+fn send_frame(message: &OwnedTerm) -> Vec<u8> {
+    let control = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(SEND),
+        OwnedTerm::atom(""),
+        OwnedTerm::Pid(local_pid()),
+    ]);
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    body.extend(encode(message).unwrap());
+    let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+    frame.extend(body);
+    frame
+}
+
+fn pass_through_flags() -> DistributionFlags {
+    DistributionFlags::default().difference(DistributionFlags::DIST_HDR_ATOM_CACHE)
+}
+
+fn atom_cache_flags() -> DistributionFlags {
+    DistributionFlags::default() | DistributionFlags::DIST_HDR_ATOM_CACHE
+}
+
+async fn connect(config: ConnectionConfig, flags: DistributionFlags) -> (Connection, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener, flags).await });
+    let mut conn = Connection::new(config.with_remote_port(port));
+    conn.connect().await.unwrap();
+    (conn, peer.await.unwrap())
+}
+
+fn config() -> ConnectionConfig {
+    ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+}
+
+#[tokio::test]
+async fn test_hibernated_connection_stays_usable() {
+    let (mut conn, mut peer) = connect(config(), pass_through_flags()).await;
+    assert!(!conn.is_hibernating());
+
+    conn.hibernate();
+    assert!(conn.is_hibernating());
+    assert!(conn.is_connected());
+
+    let message = OwnedTerm::atom("after_hibernation");
+    conn.send_message(local_pid(), peer_pid(), message.clone())
+        .await
+        .unwrap();
+    assert!(!conn.is_hibernating());
+    assert_eq!(read_frame(&mut peer).await[0], PASS_THROUGH);
+
+    conn.hibernate();
+    peer.write_all(&send_frame(&message)).await.unwrap();
+    let (_, payload) = conn.receive_message().await.unwrap();
+    assert_eq!(payload, Some(message));
+    assert!(!conn.is_hibernating());
+}
+
+#[tokio::test]
+async fn test_hibernate_keeps_the_outgoing_atom_cache_by_default() {
+    let config = config().with_flags(atom_cache_flags());
+    let (mut conn, _peer) = connect(config, atom_cache_flags()).await;
+    conn.send_message(local_pid(), peer_pid(), OwnedTerm::atom("cached"))
+        .await
+        .unwrap();
+    let cached = conn.outgoing_atom_cache().len();
+    assert!(cached > 0);
+
+    conn.hibernate();
+    assert_eq!(conn.outgoing_atom_cache().len(), cached);
+}
+
+#[tokio::test]
+async fn test_hibernate_can_reset_the_outgoing_atom_cache() {
+    let config = config()
+        .with_flags(atom_cache_flags())
+        .with_hibernation_atom_cache_reset(true);
+    let (mut conn, mut peer) = connect(config, atom_cache_flags()).await;
+    conn.send_message(local_pid(), peer_pid(), OwnedTerm::atom("cached"))
+        .await
+        .unwrap();
+    let first = read_frame(&mut peer).await;
+    assert!(!conn.outgoing_atom_cache().is_empty());
+
+    conn.hibernate();
+    assert!(conn.outgoing_atom_cache().is_empty());
+
+    // The atoms are announced again, so the frame has the same size as the first one
+    conn.send_message(local_pid(), peer_pid(), OwnedTerm::atom("cached"))
+        .await
+        .unwrap();
+    assert_eq!(read_frame(&mut peer).await.len(), first.len());
+    assert!(!conn.outgoing_atom_cache().is_empty());
+}
+
+#[tokio::test]
+async fn test_hibernate_if_idle_waits_for_the_idle_period() {
+    let config = config().with_idle_hibernation(Duration::from_millis(50));
+    let (mut conn, _peer) = connect(config, pass_through_flags()).await;
+    assert!(!conn.hibernate_if_idle());
+
+    sleep(Duration::from_millis(80)).await;
+    assert!(conn.idle_for() >= Duration::from_millis(50));
+    assert!(conn.hibernate_if_idle());
+    assert!(conn.is_hibernating());
+    assert!(!conn.hibernate_if_idle());
+}
+
+#[tokio::test]
+async fn test_hibernate_if_idle_is_off_by_default() {
+    let (mut conn, _peer) = connect(config(), pass_through_flags()).await;
+    sleep(Duration::from_millis(20)).await;
+    assert!(!conn.hibernate_if_idle());
+    assert!(!conn.is_hibernating());
+}
+
+#[tokio::test]
+async fn test_messages_from_the_read_half_end_hibernation_but_ticks_do_not() {
+    let (mut conn, mut peer) = connect(config(), pass_through_flags()).await;
+    let mut read_half = conn.take_read_half().unwrap();
+    let options = conn.receive_options();
+    conn.hibernate();
+
+    let message = OwnedTerm::atom("from_read_half");
+    peer.write_all(&[0, 0, 0, 0]).await.unwrap();
+    peer.write_all(&send_frame(&message)).await.unwrap();
+    let before = conn.metrics().ticks_received();
+    let (_, payload) = Connection::receive_message_from_read_half_with_options(
+        &mut read_half,
+        conn.timeout(),
+        &options,
+    )
+    .await
+    .unwrap();
+    assert_eq!(payload, Some(message));
+    assert_eq!(conn.metrics().ticks_received(), before + 1);
+    assert!(!conn.is_hibernating());
+}
@@ -0,0 +1,24 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![cfg(feature = "hickory")]
+
+use edp_client::resolver::{HickoryResolver, Resolver};
+use std::net::SocketAddr;
+
+#[tokio::test]
+async fn test_hickory_resolver_passes_ip_literals_through() {
+    let resolver = HickoryResolver::from_system_conf().unwrap();
+    let addrs = resolver.resolve("127.0.0.1", 4369).await.unwrap();
+    assert_eq!(addrs, vec!["127.0.0.1:4369".parse::<SocketAddr>().unwrap()]);
+}
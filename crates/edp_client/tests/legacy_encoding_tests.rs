@@ -0,0 +1,88 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, DistributionFlags};
+use erltf::types::{Atom, ExternalPid};
+use erltf::{EncodeMode, OwnedTerm, encode_with_mode};
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "legacy_cookie";
+const PEER: &str = "cnode@127.0.0.1";
+
+async fn accept_handshake(listener: &TcpListener, flags: DistributionFlags) -> TcpStream {
+    TestPeer::new(PEER, COOKIE)
+        .with_flags(flags)
+        .accept(listener)
+        .await
+}
+
+async fn read_frame(stream: &mut TcpStream) -> Vec<u8> {
+    loop {
+        let len = stream.read_u32().await.unwrap();
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        return data;
+    }
+}
+
+async fn send_pid_to_peer(peer_flags: DistributionFlags) -> (EncodeMode, Vec<u8>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener, peer_flags).await;
+        read_frame(&mut stream).await
+    });
+
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE).with_remote_port(port);
+    let mut conn = Connection::new(config);
+    assert_eq!(conn.encode_mode(), EncodeMode::Current);
+    conn.connect().await.unwrap();
+    let mode = conn.encode_mode();
+
+    let to = ExternalPid::new(Atom::new(PEER), 1, 0, 1);
+    conn.send_message(from_pid(), to, OwnedTerm::Pid(from_pid()))
+        .await
+        .unwrap();
+    let frame = peer.await.unwrap();
+    conn.close().await.unwrap();
+    (mode, frame)
+}
+
+fn from_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("rust@localhost"), 5, 0, 1)
+}
+
+#[tokio::test]
+async fn test_peer_without_big_creation_gets_legacy_tags() {
+    let flags = DistributionFlags::default().difference(DistributionFlags::BIG_CREATION);
+    let (mode, frame) = send_pid_to_peer(flags).await;
+
+    assert_eq!(mode, EncodeMode::Legacy);
+    let expected = encode_with_mode(&OwnedTerm::Pid(from_pid()), EncodeMode::Legacy).unwrap();
+    assert!(frame.ends_with(&expected));
+}
+
+#[tokio::test]
+async fn test_peer_with_big_creation_gets_current_tags() {
+    let (mode, frame) = send_pid_to_peer(DistributionFlags::default()).await;
+
+    assert_eq!(mode, EncodeMode::Current);
+    let expected = encode_with_mode(&OwnedTerm::Pid(from_pid()), EncodeMode::Current).unwrap();
+    assert!(frame.ends_with(&expected));
+}
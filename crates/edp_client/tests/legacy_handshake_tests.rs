@@ -0,0 +1,154 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "legacy-handshake")]
+
+use edp_client::errors::Error;
+use edp_client::flags::DistributionFlags;
+use edp_client::handshake::{Challenge, ChallengeAck, ChallengeReply, HandshakeVersion};
+use edp_client::state_machine::{HandshakeAction, HandshakeEvent, HandshakeStateMachine};
+
+fn legacy_state_machine() -> HandshakeStateMachine {
+    let mut sm = HandshakeStateMachine::new(
+        "rust@localhost".to_string(),
+        "erlang@localhost".to_string(),
+        "cookie".to_string(),
+        DistributionFlags::default_otp26(),
+        1,
+    );
+    sm.select_handshake_version(5, 5).unwrap();
+    sm
+}
+
+#[test]
+fn test_handshake_version_selects_v5_for_legacy_peers() {
+    assert_eq!(
+        HandshakeVersion::select(5, 5).unwrap(),
+        HandshakeVersion::V5
+    );
+    assert_eq!(
+        HandshakeVersion::select(5, 6).unwrap(),
+        HandshakeVersion::V6
+    );
+}
+
+#[test]
+fn test_legacy_challenge_roundtrip() {
+    let flags = DistributionFlags::EXTENDED_REFERENCES | DistributionFlags::BIG_CREATION;
+    let challenge = Challenge::new(flags, 0xCAFE, 0, "legacy@localhost");
+
+    let encoded = challenge.encode_old().unwrap();
+    assert_eq!(encoded[2], b'n');
+    assert_eq!(&encoded[3..5], &[0, 5]);
+
+    let decoded = Challenge::decode_old(&encoded[2..]).unwrap();
+    assert_eq!(decoded, challenge);
+}
+
+#[test]
+fn test_legacy_challenge_rejects_other_versions() {
+    let mut encoded = Challenge::new(DistributionFlags::empty(), 1, 0, "n@h")
+        .encode_old()
+        .unwrap();
+    encoded[4] = 6;
+
+    assert!(matches!(
+        Challenge::decode_old(&encoded[2..]),
+        Err(Error::IncompatibleVersion {
+            got: 6,
+            expected: 5
+        })
+    ));
+}
+
+#[test]
+fn test_legacy_send_name_omits_handshake_23() {
+    let mut sm = legacy_state_machine();
+    let data = sm.prepare_send_name().unwrap();
+
+    assert_eq!(data[2], b'n');
+    let flags = u32::from_be_bytes(data[5..9].try_into().unwrap());
+    assert_eq!(flags & DistributionFlags::HANDSHAKE_23.as_u64() as u32, 0);
+    assert!(!sm.needs_complement());
+}
+
+#[test]
+fn test_legacy_handshake_completes() {
+    let mut sm = legacy_state_machine();
+    sm.prepare_send_name().unwrap();
+    sm.handle_status(b"sok").unwrap();
+
+    let peer_flags = DistributionFlags::default_otp26();
+    let challenge = Challenge::new(peer_flags, 777, 0, "erlang@localhost")
+        .encode_old()
+        .unwrap();
+    sm.handle_challenge(&challenge[2..]).unwrap();
+
+    let negotiated = sm.negotiated_flags().unwrap();
+    assert!(!negotiated.contains(DistributionFlags::HANDSHAKE_23));
+    assert!(negotiated.contains(DistributionFlags::EXTENDED_REFERENCES));
+
+    let reply = sm.prepare_challenge_reply().unwrap();
+    let reply = ChallengeReply::decode(&reply[2..]).unwrap();
+    assert!(reply.verify(777, "cookie"));
+
+    let ack = ChallengeAck::new(reply.challenge, "cookie").encode();
+    sm.handle_challenge_ack(&ack[2..]).unwrap();
+    assert_eq!(sm.state(), edp_client::ConnectionState::Connected);
+}
+
+#[test]
+fn test_server_role_completes_a_legacy_handshake() {
+    let mut client = legacy_state_machine();
+    let mut server = HandshakeStateMachine::accepting(
+        "erlang@localhost".to_string(),
+        "cookie".to_string(),
+        DistributionFlags::default_otp26(),
+        7,
+    );
+    server.on_event(HandshakeEvent::Accept).unwrap();
+
+    let send_name = client.prepare_send_name().unwrap();
+    let (actions, _) = server
+        .on_event(HandshakeEvent::Received(send_name[2..].to_vec()))
+        .unwrap();
+    assert_eq!(server.handshake_version(), HandshakeVersion::V5);
+    let [
+        HandshakeAction::Send(status),
+        HandshakeAction::Send(challenge),
+    ] = actions.as_slice()
+    else {
+        panic!("expected a status and a challenge, got {actions:?}");
+    };
+    assert_eq!(challenge[2], b'n');
+    client.handle_status(&status[2..]).unwrap();
+    client.handle_challenge(&challenge[2..]).unwrap();
+
+    let reply = client.prepare_challenge_reply().unwrap();
+    let (actions, _) = server
+        .on_event(HandshakeEvent::Received(reply[2..].to_vec()))
+        .unwrap();
+    let [HandshakeAction::Send(ack), HandshakeAction::Complete] = actions.as_slice() else {
+        panic!("expected an ack, got {actions:?}");
+    };
+    client.handle_challenge_ack(&ack[2..]).unwrap();
+    assert_eq!(client.state(), edp_client::ConnectionState::Connected);
+    assert_eq!(server.state(), edp_client::ConnectionState::Connected);
+    assert!(
+        !server
+            .negotiated_flags()
+            .unwrap()
+            .contains(DistributionFlags::HANDSHAKE_23)
+    );
+}
@@ -0,0 +1,150 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::test_peer::TestPeer;
+use edp_client::{ChannelKey, Connection, ConnectionConfig, Error, SequenceId, StalePidPolicy};
+use erltf::OwnedTerm;
+use erltf::decoder::decode_with_trailing;
+use erltf::types::{Atom, ExternalPid};
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "ordered_cookie";
+const PEER: &str = "consumer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const PEER_CREATION: u32 = 3;
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE)
+        .with_creation(PEER_CREATION)
+        .accept(listener)
+        .await
+}
+
+/// Collects the payloads of the messages the client sends until it disconnects.
+async fn read_payloads(mut stream: TcpStream) -> Vec<OwnedTerm> {
+    let mut payloads = Vec::new();
+    while let Ok(len) = stream.read_u32().await {
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        assert_eq!(data[0], PASS_THROUGH);
+        let (_control, rest) = decode_with_trailing(&data[1..]).unwrap();
+        let (payload, _) = decode_with_trailing(rest).unwrap();
+        payloads.push(payload);
+    }
+    payloads
+}
+
+fn config(port: u16) -> ConnectionConfig {
+    ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_stale_pid_policy(StalePidPolicy::Drop)
+}
+
+fn peer_pid(creation: u32) -> ExternalPid {
+    ExternalPid::new(Atom::new(PEER), 100, 0, creation)
+}
+
+fn msg(stream: &str, n: i64) -> OwnedTerm {
+    OwnedTerm::tuple(vec![OwnedTerm::atom(stream), OwnedTerm::integer(n)])
+}
+
+#[tokio::test]
+async fn test_send_ordered_numbers_each_channel_independently() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { read_payloads(accept_handshake(&listener).await).await });
+
+    let mut conn = Connection::new(config(port));
+    conn.connect().await.unwrap();
+    let to = peer_pid(PEER_CREATION);
+    let sends = [
+        ("orders", 1),
+        ("audit", 1),
+        ("orders", 2),
+        ("orders", 3),
+        ("audit", 2),
+    ];
+    for (stream, n) in sends {
+        let seq = conn
+            .send_ordered(stream, to.clone(), msg(stream, n))
+            .await
+            .unwrap();
+        assert_eq!(seq, SequenceId::new(n as u64));
+    }
+    assert_eq!(
+        conn.ordered_sequence(&ChannelKey::from("orders")),
+        Some(SequenceId::new(3))
+    );
+    assert_eq!(
+        conn.ordered_sequence(&ChannelKey::from("audit")),
+        Some(SequenceId::new(2))
+    );
+    assert_eq!(conn.ordered_sequence(&ChannelKey::from(7u64)), None);
+    conn.close().await.unwrap();
+
+    let expected: Vec<_> = sends.iter().map(|(stream, n)| msg(stream, *n)).collect();
+    assert_eq!(peer.await.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn test_send_ordered_counts_dropped_messages() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { read_payloads(accept_handshake(&listener).await).await });
+
+    let mut conn = Connection::new(config(port));
+    conn.connect().await.unwrap();
+    let stale = peer_pid(PEER_CREATION - 1);
+    let current = peer_pid(PEER_CREATION);
+    assert_eq!(
+        conn.send_ordered(1u64, stale, msg("stale", 1))
+            .await
+            .unwrap(),
+        SequenceId::new(1)
+    );
+    assert_eq!(
+        conn.send_ordered(1u64, current, msg("current", 2))
+            .await
+            .unwrap(),
+        SequenceId::new(2)
+    );
+    conn.close().await.unwrap();
+
+    assert_eq!(peer.await.unwrap(), vec![msg("current", 2)]);
+}
+
+#[tokio::test]
+async fn test_send_ordered_requires_a_connection() {
+    let mut conn = Connection::new(config(1));
+    let result = conn
+        .send_ordered(
+            "orders",
+            peer_pid(PEER_CREATION),
+            OwnedTerm::atom("dropped"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(Error::InvalidState { .. })));
+    assert_eq!(conn.ordered_sequence(&ChannelKey::from("orders")), None);
+}
+
+#[test]
+fn test_channel_key_display() {
+    assert_eq!(ChannelKey::from("orders").to_string(), "orders");
+    assert_eq!(ChannelKey::from(String::from("audit")).to_string(), "audit");
+    assert_eq!(ChannelKey::from(42u64).to_string(), "42");
+}
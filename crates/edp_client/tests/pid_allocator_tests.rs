@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use edp_client::PidAllocator;
+use edp_client::{PidAllocator, PidAllocatorState};
 use erltf::types::Atom;
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -223,3 +223,182 @@ fn test_pid_serial_overflow_wraps_correctly() {
         );
     }
 }
+
+//
+// Reservation Tests
+//
+
+#[test]
+fn test_reserve_skips_the_range_in_regular_allocation() {
+    let allocator = PidAllocator::new(Atom::new("node@host"), 1);
+    allocator.allocate().unwrap();
+
+    let range = allocator.reserve(10).unwrap();
+    assert_eq!(range.first_id(), 2);
+    assert_eq!(range.last_id(), 11);
+    assert_eq!(range.len(), 10);
+
+    let pid = allocator.allocate().unwrap();
+    assert_eq!(pid.id, 12);
+    assert!(!range.contains(&pid));
+}
+
+#[test]
+fn test_reserved_range_allocates_until_exhausted() {
+    let allocator = PidAllocator::new(Atom::new("node@host"), 3);
+    let range = allocator.reserve(3).unwrap();
+
+    let pids: Vec<_> = std::iter::from_fn(|| range.allocate()).collect();
+
+    assert_eq!(pids.iter().map(|p| p.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert!(pids.iter().all(|p| range.contains(p) && p.creation == 3));
+    assert_eq!(range.remaining(), 0);
+    assert!(range.allocate().is_none());
+}
+
+#[test]
+fn test_reserve_wraps_when_too_few_ids_are_left() {
+    let allocator = PidAllocator::new(Atom::new("node@host"), 1);
+    allocator
+        .next_id_test_only()
+        .store(MAX_PROCESSES_PER_NODE - 5, Ordering::Relaxed);
+
+    let range = allocator.reserve(10).unwrap();
+    assert_eq!(range.first_id(), 1);
+    assert_eq!(range.serial(), 1);
+
+    let pid = allocator.allocate().unwrap();
+    assert_eq!((pid.id, pid.serial), (11, 1));
+}
+
+#[test]
+fn test_reserve_up_to_the_last_id_moves_to_the_next_serial() {
+    let allocator = PidAllocator::new(Atom::new("node@host"), 1);
+    allocator
+        .next_id_test_only()
+        .store(MAX_PROCESSES_PER_NODE - 9, Ordering::Relaxed);
+
+    let range = allocator.reserve(10).unwrap();
+    assert_eq!(range.last_id(), MAX_PROCESSES_PER_NODE);
+    assert_eq!(range.serial(), 0);
+
+    let pid = allocator.allocate().unwrap();
+    assert_eq!((pid.id, pid.serial), (1, 1));
+}
+
+#[test]
+fn test_reserve_rejects_invalid_sizes() {
+    let allocator = PidAllocator::new(Atom::new("node@host"), 1);
+
+    assert!(allocator.reserve(0).is_err());
+    assert!(allocator.reserve(MAX_PROCESSES_PER_NODE + 1).is_err());
+}
+
+#[tokio::test]
+async fn test_concurrent_allocation_and_reservation_do_not_overlap() {
+    let allocator = Arc::new(PidAllocator::new(Atom::new("test@localhost"), 1));
+    let mut handles = vec![];
+
+    for _ in 0..8 {
+        let allocator = allocator.clone();
+        handles.push(tokio::spawn(async move {
+            let mut pids = Vec::new();
+            for _ in 0..50 {
+                pids.push(allocator.allocate().unwrap());
+                let range = allocator.reserve(4).unwrap();
+                pids.extend(std::iter::from_fn(|| range.allocate()));
+            }
+            pids
+        }));
+    }
+
+    let mut all_pids = HashSet::new();
+    for handle in handles {
+        for pid in handle.await.unwrap() {
+            assert!(
+                all_pids.insert((pid.id, pid.serial)),
+                "Duplicate PID: {:?}",
+                pid
+            );
+        }
+    }
+    assert_eq!(all_pids.len(), 8 * 50 * 5);
+}
+
+//
+// Persistence Tests
+//
+
+#[test]
+fn test_restore_continues_after_the_snapshot() {
+    let before_restart = PidAllocator::new(Atom::new("node@host"), 7);
+    for _ in 0..5 {
+        before_restart.allocate().unwrap();
+    }
+    let state = before_restart.snapshot().unwrap();
+
+    let after_restart = PidAllocator::with_state(Atom::new("node@host"), 7, state);
+
+    assert_eq!(after_restart.allocate().unwrap().id, 6);
+}
+
+#[test]
+fn test_restore_ignores_state_of_another_creation() {
+    let allocator = PidAllocator::new(Atom::new("node@host"), 8);
+    let state = PidAllocatorState {
+        creation: 7,
+        next_id: 500,
+        next_serial: 2,
+    };
+
+    assert!(!allocator.restore(state));
+    let pid = allocator.allocate().unwrap();
+    assert_eq!((pid.id, pid.serial), (1, 0));
+}
+
+#[test]
+fn test_restore_never_moves_backwards() {
+    let allocator = PidAllocator::new(Atom::new("node@host"), 1);
+    for _ in 0..10 {
+        allocator.allocate().unwrap();
+    }
+
+    assert!(allocator.restore(PidAllocatorState {
+        creation: 1,
+        next_id: 3,
+        next_serial: 0,
+    }));
+    assert_eq!(allocator.allocate().unwrap().id, 11);
+}
+
+#[test]
+fn test_state_round_trips_through_a_file() {
+    let path = std::env::temp_dir().join(format!("edp_pid_state_{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(PidAllocatorState::load(&path).unwrap(), None);
+
+    let state = PidAllocatorState {
+        creation: 42,
+        next_id: 1234,
+        next_serial: 5,
+    };
+    state.save(&path).unwrap();
+
+    assert_eq!(PidAllocatorState::load(&path).unwrap(), Some(state));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_state_parse_rejects_malformed_input() {
+    assert!("1 2".parse::<PidAllocatorState>().is_err());
+    assert!("1 2 3 4".parse::<PidAllocatorState>().is_err());
+    assert!("a 2 3".parse::<PidAllocatorState>().is_err());
+    assert_eq!(
+        "1 2 3".parse::<PidAllocatorState>().unwrap(),
+        PidAllocatorState {
+            creation: 1,
+            next_id: 2,
+            next_serial: 3,
+        }
+    );
+}
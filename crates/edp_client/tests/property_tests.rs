@@ -15,7 +15,9 @@
 use edp_client::digest;
 use edp_client::flags::DistributionFlags;
 use edp_client::handshake::{Challenge, ChallengeAck, ChallengeReply, SendName};
+use edp_client::state_machine::HandshakeStateMachine;
 use proptest::prelude::*;
+use std::collections::HashSet;
 
 //
 // Handshake Message Roundtrip Properties
@@ -234,4 +236,34 @@ proptest! {
         df.remove(flag_to_clear);
         prop_assert_eq!(df.bits() & flag_bit, 0);
     }
+
+    #[test]
+    fn test_handshake_challenges_are_unique_across_connections(
+        connections in 2usize..32,
+        their_challenge in any::<u32>()
+    ) {
+        let peer_challenge = Challenge::new(
+            DistributionFlags::default_otp26(),
+            their_challenge,
+            1,
+            "erlang@localhost",
+        )
+        .encode()
+        .unwrap();
+
+        let mut ours = HashSet::new();
+        for _ in 0..connections {
+            let mut sm = HandshakeStateMachine::new(
+                "rust@localhost".to_string(),
+                "erlang@localhost".to_string(),
+                "cookie".to_string(),
+                DistributionFlags::default_otp26(),
+                1,
+            );
+            sm.handle_challenge(&peer_challenge[2..]).unwrap();
+            let reply = ChallengeReply::decode(&sm.prepare_challenge_reply().unwrap()[2..]).unwrap();
+            ours.insert(reply.challenge);
+        }
+        prop_assert_eq!(ours.len(), connections);
+    }
 }
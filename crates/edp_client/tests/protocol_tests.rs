@@ -0,0 +1,71 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::DistributionFlags;
+use edp_client::control::ControlMessageType;
+use edp_client::handshake::{ChallengeAck, SendName};
+use edp_client::protocol::{
+    self, CHALLENGE_ACK_TAG, DIST_FRAG_CONT, DIST_FRAG_HEADER, DIST_HEADER, EPMD_PORT,
+    EPMD_PORT2_REQ, NAME_TAG, NAME_TAG_V5, PASS_THROUGH, VERSION,
+};
+use erltf::decoder::decode_fragment_cont;
+
+#[test]
+fn test_protocol_constants_match_otp_values() {
+    assert_eq!(VERSION, 131);
+    assert_eq!(DIST_HEADER, 68);
+    assert_eq!(DIST_FRAG_HEADER, 69);
+    assert_eq!(DIST_FRAG_CONT, 70);
+    assert_eq!(PASS_THROUGH, 112);
+    assert_eq!(EPMD_PORT, 4369);
+    assert_eq!(EPMD_PORT2_REQ, 122);
+}
+
+#[test]
+fn test_handshake_messages_use_the_protocol_tags() {
+    let send_name = SendName::new(DistributionFlags::default(), 1, "rust@localhost");
+    assert_eq!(send_name.encode().unwrap()[2], NAME_TAG);
+    assert_eq!(send_name.encode_old().unwrap()[2], NAME_TAG_V5);
+
+    let ack = ChallengeAck::new(42, "cookie").encode();
+    assert_eq!(ack[2], CHALLENGE_ACK_TAG);
+}
+
+#[test]
+fn test_tag_names() {
+    assert_eq!(protocol::handshake_tag_name(b'r'), Some("CHALLENGE_REPLY"));
+    assert_eq!(protocol::handshake_tag_name(b'x'), None);
+    assert_eq!(protocol::frame_tag_name(70), Some("DIST_FRAG_CONT"));
+    assert_eq!(protocol::frame_tag_name(131), None);
+    assert_eq!(protocol::epmd_code_name(122), Some("PORT2_REQ"));
+    assert_eq!(protocol::control_message_name(6), Some("REG_SEND"));
+    assert_eq!(protocol::control_message_name(200), None);
+    assert_eq!(
+        ControlMessageType::try_from(6).unwrap().name(),
+        protocol::control_message_name(6).unwrap()
+    );
+}
+
+#[test]
+fn test_fragment_continuation_is_decoded_by_its_protocol_tag() {
+    let mut frame = vec![VERSION, DIST_FRAG_CONT];
+    frame.extend(7u64.to_be_bytes());
+    frame.extend(2u64.to_be_bytes());
+    frame.push(1);
+
+    let ((sequence_id, fragment_id), rest) = decode_fragment_cont(&frame).unwrap();
+
+    assert_eq!((sequence_id, fragment_id), (7, 2));
+    assert_eq!(rest, &[1]);
+}
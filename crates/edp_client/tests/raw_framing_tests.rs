@@ -0,0 +1,189 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::state_machine::{HandshakeAction, HandshakeEvent};
+use edp_client::test_peer::TestPeer;
+use edp_client::{
+    Connection, ConnectionConfig, ConnectionState, Error, FrameMode, FramedTransport,
+};
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "raw_framing_cookie";
+const PEER: &str = "raw_framing_peer@127.0.0.1";
+const PREAMBLE: &[u8] = b"PROXY TCP4 10.0.0.1 10.0.0.2 4370 4370\r\n";
+const PREAMBLE_ACK: &[u8] = b"OK";
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reads one distribution frame, the first one after the handshake.
+async fn read_frame(stream: &mut TcpStream) -> Vec<u8> {
+    let len = stream.read_u32().await.unwrap();
+    let mut body = vec![0u8; len as usize];
+    stream.read_exact(&mut body).await.unwrap();
+    body
+}
+
+async fn connected_transport(listener: &TcpListener) -> FramedTransport {
+    let stream = TcpStream::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+    let mut transport = FramedTransport::new(TIMEOUT);
+    transport.connect(stream);
+    transport
+}
+
+fn config() -> ConnectionConfig {
+    ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+}
+
+async fn send_hello(conn: &mut Connection) {
+    let to = ExternalPid::new(Atom::new(PEER), 1, 0, 1);
+    let from = ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1);
+    conn.send_message(from, to, OwnedTerm::atom("hello"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_frame_mode_switches_the_length_prefix() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let mut transport = connected_transport(&listener).await;
+    let (mut stream, _) = listener.accept().await.unwrap();
+    assert_eq!(transport.frame_mode(), FrameMode::Handshake);
+    assert_eq!(transport.peer_addr(), Some(listener.local_addr().unwrap()));
+
+    transport.write(b"abc").await.unwrap();
+    transport.set_frame_mode(FrameMode::Distribution);
+    assert_eq!(transport.frame_mode(), FrameMode::Distribution);
+    transport.write(b"def").await.unwrap();
+    transport.write_raw(b"gh").await.unwrap();
+    let mut written = [0u8; 2 + 3 + 4 + 3 + 2];
+    stream.read_exact(&mut written).await.unwrap();
+    assert_eq!(&written, b"\0\x03abc\0\0\0\x03defgh");
+
+    stream.write_all(b"raw\0\0\0\x02hi").await.unwrap();
+    assert_eq!(transport.read_raw(3).await.unwrap(), b"raw");
+    assert_eq!(transport.read().await.unwrap(), b"hi");
+}
+
+#[tokio::test]
+async fn test_read_raw_times_out() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+    let _peer = listener.accept().await.unwrap();
+    let mut transport = FramedTransport::new(Duration::from_millis(50));
+    transport.connect(stream);
+
+    assert!(matches!(
+        transport.read_raw(1).await,
+        Err(Error::Timeout(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_connect_over_a_transport_with_a_preamble() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let mut transport = connected_transport(&listener).await;
+    let peer = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut preamble = vec![0u8; PREAMBLE.len()];
+        stream.read_exact(&mut preamble).await.unwrap();
+        assert_eq!(preamble, PREAMBLE);
+        stream.write_all(PREAMBLE_ACK).await.unwrap();
+        TestPeer::new(PEER, COOKIE).handshake(&mut stream).await;
+        read_frame(&mut stream).await
+    });
+
+    transport.write_raw(PREAMBLE).await.unwrap();
+    assert_eq!(
+        transport.read_raw(PREAMBLE_ACK.len()).await.unwrap(),
+        PREAMBLE_ACK
+    );
+    let mut conn = Connection::new(config());
+    conn.connect_over(transport).await.unwrap();
+    assert!(conn.is_connected());
+    assert!(conn.peer_addr().is_some());
+
+    send_hello(&mut conn).await;
+    let frame = peer.await.unwrap();
+    assert!(frame.windows(b"hello".len()).any(|w| w == b"hello"));
+}
+
+#[tokio::test]
+async fn test_connect_over_requires_a_connected_transport() {
+    let mut conn = Connection::new(config());
+    let result = conn.connect_over(FramedTransport::new(TIMEOUT)).await;
+    assert!(matches!(result, Err(Error::InvalidStateMessage(_))));
+    assert_eq!(conn.state(), ConnectionState::Disconnected);
+}
+
+#[tokio::test]
+async fn test_adopt_a_handshake_driven_by_the_embedder() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let mut transport = connected_transport(&listener).await;
+    let peer = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        TestPeer::new(PEER, COOKIE).handshake(&mut stream).await;
+        read_frame(&mut stream).await
+    });
+
+    let config = config();
+    let mut handshake = config.handshake_state_machine();
+    handshake.begin_connect().unwrap();
+    let (mut actions, _) = handshake.on_event(HandshakeEvent::Connect).unwrap();
+    'handshake: loop {
+        for action in actions {
+            match action {
+                HandshakeAction::Send(data) => transport.write_raw(&data).await.unwrap(),
+                HandshakeAction::Complete => break 'handshake,
+            }
+        }
+        let data = transport.read().await.unwrap();
+        (actions, _) = handshake.on_event(HandshakeEvent::Received(data)).unwrap();
+    }
+    // The embedder switches to distribution framing, adopting it again is harmless
+    transport.set_frame_mode(FrameMode::Distribution);
+
+    let mut conn = Connection::new(config);
+    conn.adopt_handshake(transport, handshake).unwrap();
+    assert!(conn.is_connected());
+    assert!(conn.negotiated_flags().is_some());
+    assert_eq!(conn.peer_creation(), Some(1));
+
+    send_hello(&mut conn).await;
+    let frame = peer.await.unwrap();
+    assert!(frame.windows(b"hello".len()).any(|w| w == b"hello"));
+}
+
+#[tokio::test]
+async fn test_adopt_handshake_rejects_an_incomplete_handshake() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let transport = connected_transport(&listener).await;
+    let config = config();
+    let handshake = config.handshake_state_machine();
+
+    let mut conn = Connection::new(config);
+    let result = conn.adopt_handshake(transport, handshake);
+    assert!(matches!(
+        result,
+        Err(Error::InvalidState {
+            state: ConnectionState::Disconnected
+        })
+    ));
+    assert!(!conn.is_connected());
+}
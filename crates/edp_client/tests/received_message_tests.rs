@@ -0,0 +1,183 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::control::ControlMessage;
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig};
+use erltf::OwnedTerm;
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use std::time::SystemTime;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+const COOKIE: &str = "envelope_cookie";
+const PEER: &str = "envelope_peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+/// A versioned term with a tag no OTP release uses
+const UNKNOWN_TAG_PAYLOAD: &[u8] = &[131, 200, 1, 2, 3];
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+fn recipient() -> ExternalPid {
+    ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)
+}
+
+/// Returns a SEND frame and the size of its body.
+fn send_frame(message: &OwnedTerm) -> (Vec<u8>, usize) {
+    send_frame_with_payload(&encode(message).unwrap())
+}
+
+/// Like [`send_frame`] but with an already encoded payload.
+fn send_frame_with_payload(payload: &[u8]) -> (Vec<u8>, usize) {
+    let control = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(SEND),
+        OwnedTerm::atom(""),
+        OwnedTerm::Pid(recipient()),
+    ]);
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    body.extend(payload);
+    let size = body.len();
+    let mut frame = (size as u32).to_be_bytes().to_vec();
+    frame.extend(body);
+    (frame, size)
+}
+
+fn config(port: u16) -> ConnectionConfig {
+    ConnectionConfig::new("rust@localhost", PEER, COOKIE).with_remote_port(port)
+}
+
+async fn connect(port: u16) -> Connection {
+    connect_with(config(port)).await
+}
+
+async fn connect_with(config: ConnectionConfig) -> Connection {
+    let mut conn = Connection::new(config);
+    conn.connect().await.unwrap();
+    conn
+}
+
+/// Completes the handshake, then writes `frames` and keeps the stream open.
+async fn start_peer(frames: Vec<Vec<u8>>) -> (u16, JoinHandle<TcpStream>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        for frame in frames {
+            stream.write_all(&frame).await.unwrap();
+        }
+        stream
+    });
+    (port, peer)
+}
+
+#[tokio::test]
+async fn test_receive_envelope_carries_provenance() {
+    let message = OwnedTerm::tuple(vec![OwnedTerm::atom("hello"), OwnedTerm::integer(1)]);
+    let (frame, size) = send_frame(&message);
+    let (port, peer) = start_peer(vec![vec![0, 0, 0, 0], frame]).await;
+
+    let before = SystemTime::now();
+    let mut conn = connect(port).await;
+    let envelope = conn.receive_envelope().await.unwrap();
+
+    assert_eq!(envelope.from_node, Atom::new(PEER));
+    assert_eq!(envelope.byte_size, size);
+    assert!(envelope.received_at >= before);
+    assert!(envelope.received_at <= SystemTime::now());
+    assert_eq!(envelope.payload, Some(message));
+    assert!(matches!(
+        envelope.control,
+        ControlMessage::Send { to_pid: OwnedTerm::Pid(ref to), .. } if *to == recipient()
+    ));
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_receive_envelope_sizes_each_message() {
+    let small = OwnedTerm::atom("a");
+    let large = OwnedTerm::Binary(vec![7; 1024]);
+    let (small_frame, small_size) = send_frame(&small);
+    let (large_frame, large_size) = send_frame(&large);
+    let (port, peer) = start_peer(vec![small_frame, large_frame]).await;
+
+    let mut conn = connect(port).await;
+    let first = conn.receive_envelope().await.unwrap();
+    let second = conn.receive_envelope().await.unwrap();
+
+    assert_eq!(first.byte_size, small_size);
+    assert_eq!(second.byte_size, large_size);
+    assert!(second.received_at >= first.received_at);
+    assert_eq!(second.into_parts().1, Some(large));
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_receive_message_returns_the_envelope_parts() {
+    let message = OwnedTerm::atom("plain");
+    let (frame, _) = send_frame(&message);
+    let (port, peer) = start_peer(vec![frame]).await;
+
+    let mut conn = connect(port).await;
+    let (control, payload) = conn.receive_message().await.unwrap();
+
+    assert!(matches!(control, ControlMessage::Send { .. }));
+    assert_eq!(payload, Some(message));
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_receive_envelope_keeps_undecodable_payload_bytes() {
+    let payload = UNKNOWN_TAG_PAYLOAD.to_vec();
+    let (frame, _) = send_frame_with_payload(&payload);
+    let (port, peer) = start_peer(vec![frame]).await;
+
+    let mut conn = connect_with(config(port).with_raw_payloads(true)).await;
+    let envelope = conn.receive_envelope().await.unwrap();
+
+    assert!(matches!(envelope.control, ControlMessage::Send { .. }));
+    assert_eq!(envelope.payload, None);
+    assert_eq!(envelope.raw_payload, Some(payload));
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_receive_envelope_fails_on_undecodable_payload_by_default() {
+    let (frame, _) = send_frame_with_payload(UNKNOWN_TAG_PAYLOAD);
+    let (port, peer) = start_peer(vec![frame]).await;
+
+    let mut conn = connect(port).await;
+
+    assert!(conn.receive_envelope().await.is_err());
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_raw_payloads_decode_valid_payloads() {
+    let message = OwnedTerm::atom("decodable");
+    let (frame, _) = send_frame(&message);
+    let (port, peer) = start_peer(vec![frame]).await;
+
+    let mut conn = connect_with(config(port).with_raw_payloads(true)).await;
+    let envelope = conn.receive_envelope().await.unwrap();
+
+    assert_eq!(envelope.payload, Some(message));
+    assert_eq!(envelope.raw_payload, None);
+    drop(peer.await.unwrap());
+}
@@ -0,0 +1,126 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::happy_eyeballs::connect_with_resolver;
+use edp_client::resolver::{CachingResolver, ResolveFuture, Resolver, StaticResolver};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::time::sleep;
+
+const LOCALHOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+/// Counts lookups and resolves every host to the loopback address.
+#[derive(Debug, Default)]
+struct CountingResolver {
+    lookups: AtomicUsize,
+}
+
+impl Resolver for CountingResolver {
+    fn resolve<'a>(&'a self, _host: &'a str, port: u16) -> ResolveFuture<'a> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async move { Ok(vec![SocketAddr::new(LOCALHOST, port)]) })
+    }
+}
+
+#[tokio::test]
+async fn test_static_resolver_resolves_known_hosts() {
+    let resolver = StaticResolver::new().with_host(
+        "rabbit.service.consul",
+        [LOCALHOST, IpAddr::V6(Ipv6Addr::LOCALHOST)],
+    );
+
+    let addrs = resolver
+        .resolve("rabbit.service.consul", 4369)
+        .await
+        .unwrap();
+    assert_eq!(
+        addrs,
+        vec![
+            "127.0.0.1:4369".parse::<SocketAddr>().unwrap(),
+            "[::1]:4369".parse().unwrap(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_static_resolver_passes_ip_literals_through() {
+    let resolver = StaticResolver::new();
+    let addrs = resolver.resolve("10.0.0.7", 25672).await.unwrap();
+    assert_eq!(addrs, vec!["10.0.0.7:25672".parse::<SocketAddr>().unwrap()]);
+}
+
+#[tokio::test]
+async fn test_static_resolver_fails_for_unknown_hosts() {
+    let resolver = StaticResolver::new();
+    let err = resolver
+        .resolve("unknown.internal", 4369)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
+
+#[tokio::test]
+async fn test_caching_resolver_caches_until_the_ttl_expires() {
+    let inner = Arc::new(CountingResolver::default());
+    let resolver = CachingResolver::new(inner.clone(), Duration::from_millis(200));
+
+    resolver.resolve("rabbit", 4369).await.unwrap();
+    resolver.clone().resolve("rabbit", 4369).await.unwrap();
+    assert_eq!(inner.lookups.load(Ordering::SeqCst), 1);
+
+    resolver.resolve("rabbit", 25672).await.unwrap();
+    assert_eq!(inner.lookups.load(Ordering::SeqCst), 2);
+
+    sleep(Duration::from_millis(250)).await;
+    resolver.resolve("rabbit", 4369).await.unwrap();
+    assert_eq!(inner.lookups.load(Ordering::SeqCst), 3);
+
+    resolver.invalidate("rabbit");
+    resolver.resolve("rabbit", 4369).await.unwrap();
+    assert_eq!(inner.lookups.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn test_connect_with_resolver_uses_the_resolved_addresses() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let resolver = StaticResolver::new().with_host("rabbit.service.consul", [LOCALHOST]);
+
+    let (_stream, addr) = connect_with_resolver(
+        &resolver,
+        "rabbit.service.consul",
+        port,
+        Duration::from_millis(50),
+    )
+    .await
+    .unwrap();
+    assert_eq!(addr, SocketAddr::new(LOCALHOST, port));
+}
+
+#[tokio::test]
+async fn test_connect_with_resolver_fails_when_nothing_resolves() {
+    let resolver = StaticResolver::new().with_host("empty.service.consul", []);
+    let err = connect_with_resolver(
+        &resolver,
+        "empty.service.consul",
+        4369,
+        Duration::from_millis(50),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, edp_client::Error::Io(e) if e.kind() == io::ErrorKind::NotFound));
+}
@@ -0,0 +1,128 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, DistributionFlags};
+use erltf::OwnedTerm;
+use erltf::decoder::decode_with_trailing;
+use erltf::types::{Atom, ExternalPid};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "send_sender_cookie";
+const PEER: &str = "peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+const SEND_SENDER: i64 = 22;
+
+async fn accept_handshake(listener: &TcpListener, flags: DistributionFlags) -> TcpStream {
+    TestPeer::new(PEER, COOKIE)
+        .with_flags(flags)
+        .accept(listener)
+        .await
+}
+
+async fn read_frame(stream: &mut TcpStream) -> (Vec<OwnedTerm>, OwnedTerm) {
+    loop {
+        let len = stream.read_u32().await.unwrap();
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        assert_eq!(data[0], PASS_THROUGH);
+        let (control, rest) = decode_with_trailing(&data[1..]).unwrap();
+        let (payload, _) = decode_with_trailing(rest).unwrap();
+        let OwnedTerm::Tuple(control) = control else {
+            panic!("unexpected control message: {:?}", control);
+        };
+        return (control, payload);
+    }
+}
+
+fn local_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)
+}
+
+fn remote_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new(PEER), 7, 0, 1)
+}
+
+async fn connect(peer_flags: DistributionFlags) -> (Connection, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener, peer_flags).await });
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(Duration::from_secs(5));
+    let mut conn = Connection::new(config);
+    conn.connect().await.unwrap();
+    (conn, peer.await.unwrap())
+}
+
+#[test]
+fn test_send_sender_is_advertised_by_default() {
+    assert!(DistributionFlags::default().contains(DistributionFlags::SEND_SENDER));
+    assert!(DistributionFlags::DEFAULT_HIDDEN.contains(DistributionFlags::SEND_SENDER));
+}
+
+#[tokio::test]
+async fn test_send_message_uses_send_sender_when_negotiated() {
+    let flags = DistributionFlags::default().difference(DistributionFlags::DIST_HDR_ATOM_CACHE);
+    let (mut conn, mut stream) = connect(flags).await;
+    assert!(
+        conn.negotiated_flags()
+            .unwrap()
+            .contains(DistributionFlags::SEND_SENDER)
+    );
+
+    conn.send_message(local_pid(), remote_pid(), OwnedTerm::atom("hello"))
+        .await
+        .unwrap();
+
+    let (control, payload) = read_frame(&mut stream).await;
+    assert_eq!(
+        control,
+        vec![
+            OwnedTerm::integer(SEND_SENDER),
+            OwnedTerm::Pid(local_pid()),
+            OwnedTerm::Pid(remote_pid()),
+        ]
+    );
+    assert_eq!(payload, OwnedTerm::atom("hello"));
+}
+
+#[tokio::test]
+async fn test_send_message_falls_back_to_send_without_the_flag() {
+    let flags = DistributionFlags::default()
+        .difference(DistributionFlags::DIST_HDR_ATOM_CACHE)
+        .difference(DistributionFlags::SEND_SENDER);
+    let (mut conn, mut stream) = connect(flags).await;
+
+    conn.send_message(local_pid(), remote_pid(), OwnedTerm::atom("hello"))
+        .await
+        .unwrap();
+
+    let (control, payload) = read_frame(&mut stream).await;
+    assert_eq!(
+        control,
+        vec![
+            OwnedTerm::integer(SEND),
+            OwnedTerm::atom(""),
+            OwnedTerm::Pid(remote_pid()),
+        ]
+    );
+    assert_eq!(payload, OwnedTerm::atom("hello"));
+}
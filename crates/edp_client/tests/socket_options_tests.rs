@@ -0,0 +1,139 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::{ConnectionConfig, SocketOptions, TcpKeepalive};
+use socket2::SockRef;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+
+async fn connected_stream() -> (TcpStream, TcpListener) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+    (stream, listener)
+}
+
+#[test]
+fn test_default_socket_options_are_empty() {
+    let options = SocketOptions::default();
+    assert!(options.is_empty());
+    assert!(!options.with_nodelay(true).is_empty());
+
+    let config = ConnectionConfig::new("a@localhost", "b@localhost", "cookie");
+    assert!(config.socket_options.is_empty());
+}
+
+#[test]
+fn test_socket_options_builders() {
+    let keepalive = TcpKeepalive::new(Duration::from_secs(30))
+        .with_interval(Duration::from_secs(5))
+        .with_retries(4);
+    let options = SocketOptions::new()
+        .with_nodelay(true)
+        .with_keepalive(keepalive)
+        .with_recv_buffer_size(128 * 1024)
+        .with_send_buffer_size(64 * 1024)
+        .with_dscp(46);
+
+    assert_eq!(options.nodelay, Some(true));
+    assert_eq!(options.keepalive, Some(keepalive));
+    assert_eq!(keepalive.interval, Some(Duration::from_secs(5)));
+    assert_eq!(keepalive.retries, Some(4));
+    assert_eq!(options.recv_buffer_size, Some(128 * 1024));
+    assert_eq!(options.send_buffer_size, Some(64 * 1024));
+    assert_eq!(options.tos, Some(0xB8));
+
+    let config =
+        ConnectionConfig::new("a@localhost", "b@localhost", "cookie").with_socket_options(options);
+    assert_eq!(config.socket_options, options);
+}
+
+#[test]
+fn test_dscp_is_masked_to_six_bits() {
+    assert_eq!(SocketOptions::new().with_dscp(0xFF).tos, Some(0xFC));
+}
+
+#[tokio::test]
+async fn test_apply_sets_nodelay_and_keepalive() {
+    let (stream, _listener) = connected_stream().await;
+    let options = SocketOptions::new()
+        .with_nodelay(true)
+        .with_keepalive(TcpKeepalive::new(Duration::from_secs(45)));
+    options.apply(&stream).unwrap();
+
+    let socket = SockRef::from(&stream);
+    assert!(socket.tcp_nodelay().unwrap());
+    assert!(socket.keepalive().unwrap());
+    #[cfg(target_os = "linux")]
+    assert_eq!(
+        socket.tcp_keepalive_time().unwrap(),
+        Duration::from_secs(45)
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_apply_sets_keepalive_interval_and_retries() {
+    let (stream, _listener) = connected_stream().await;
+    let keepalive = TcpKeepalive::new(Duration::from_secs(60))
+        .with_interval(Duration::from_secs(7))
+        .with_retries(3);
+    SocketOptions::new()
+        .with_keepalive(keepalive)
+        .apply(&stream)
+        .unwrap();
+
+    let socket = SockRef::from(&stream);
+    assert_eq!(
+        socket.tcp_keepalive_interval().unwrap(),
+        Duration::from_secs(7)
+    );
+    assert_eq!(socket.tcp_keepalive_retries().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_apply_sets_buffer_sizes() {
+    let (stream, _listener) = connected_stream().await;
+    let size = 256 * 1024;
+    SocketOptions::new()
+        .with_recv_buffer_size(size)
+        .with_send_buffer_size(size)
+        .apply(&stream)
+        .unwrap();
+
+    // The kernel may double or clamp the requested size
+    let socket = SockRef::from(&stream);
+    assert!(socket.recv_buffer_size().unwrap() > 0);
+    assert!(socket.send_buffer_size().unwrap() > 0);
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_apply_sets_tos() {
+    let (stream, _listener) = connected_stream().await;
+    SocketOptions::new().with_dscp(10).apply(&stream).unwrap();
+
+    let socket = SockRef::from(&stream);
+    assert_eq!(socket.tos_v4().unwrap(), 40);
+}
+
+#[tokio::test]
+async fn test_empty_options_leave_socket_untouched() {
+    let (stream, _listener) = connected_stream().await;
+    let socket = SockRef::from(&stream);
+    let nodelay = socket.tcp_nodelay().unwrap();
+    SocketOptions::default().apply(&stream).unwrap();
+    assert_eq!(socket.tcp_nodelay().unwrap(), nodelay);
+}
@@ -0,0 +1,173 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_client::{
+    Connection, ConnectionConfig, DistributionFlags, Error, SendOpts, SendOutcome, StalePidPolicy,
+};
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "stale_cookie";
+const PEER: &str = "peer@127.0.0.1";
+
+async fn accept_handshake(listener: &TcpListener, creation: u32) -> TcpStream {
+    TestPeer::new(PEER, COOKIE)
+        .with_flags(DistributionFlags::default())
+        .with_creation(creation)
+        .accept(listener)
+        .await
+}
+
+/// Collects the non-tick frames the client sends until it disconnects.
+async fn read_frames(mut stream: TcpStream) -> Vec<Vec<u8>> {
+    let mut frames = Vec::new();
+    while let Ok(len) = stream.read_u32().await {
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        frames.push(data);
+    }
+    frames
+}
+
+fn config(port: u16, policy: StalePidPolicy) -> ConnectionConfig {
+    ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(Duration::from_secs(5))
+        .with_stale_pid_policy(policy)
+}
+
+fn peer_pid(creation: u32) -> ExternalPid {
+    ExternalPid::new(Atom::new(PEER), 100, 0, creation)
+}
+
+fn from_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)
+}
+
+#[tokio::test]
+async fn test_is_stale_tracks_peer_creation_across_restarts() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        drop(accept_handshake(&listener, 7).await);
+        drop(accept_handshake(&listener, 8).await);
+    });
+
+    let mut conn = Connection::new(config(port, StalePidPolicy::Deliver));
+    assert_eq!(conn.peer_creation(), None);
+    assert!(!conn.is_stale(&peer_pid(7)));
+
+    conn.connect().await.unwrap();
+    assert_eq!(conn.peer_creation(), Some(7));
+    assert!(!conn.is_stale(&peer_pid(7)));
+    assert!(conn.is_stale(&peer_pid(6)));
+
+    conn.close().await.unwrap();
+    assert_eq!(conn.peer_creation(), Some(7));
+
+    conn.connect().await.unwrap();
+    assert_eq!(conn.peer_creation(), Some(8));
+    assert!(conn.is_stale(&peer_pid(7)));
+    assert!(!conn.is_stale(&peer_pid(8)));
+    // Legacy pids with creation 0 match any incarnation
+    assert!(!conn.is_stale(&peer_pid(0)));
+    // Pids of other nodes are not judged by the peer's creation
+    let other = ExternalPid::new(Atom::new("other@127.0.0.1"), 100, 0, 7);
+    assert!(!conn.is_stale(&other));
+
+    conn.close().await.unwrap();
+    peer.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_deliver_policy_sends_to_stale_pids() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { read_frames(accept_handshake(&listener, 3).await).await });
+
+    let mut conn = Connection::new(config(port, StalePidPolicy::Deliver));
+    conn.connect().await.unwrap();
+    conn.send_message(from_pid(), peer_pid(2), OwnedTerm::atom("hello"))
+        .await
+        .unwrap();
+    conn.close().await.unwrap();
+
+    assert_eq!(peer.await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_drop_policy_skips_stale_pids() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { read_frames(accept_handshake(&listener, 3).await).await });
+
+    let mut conn = Connection::new(config(port, StalePidPolicy::Drop));
+    conn.connect().await.unwrap();
+    conn.send_message(from_pid(), peer_pid(2), OwnedTerm::atom("dropped"))
+        .await
+        .unwrap();
+    let outcome = conn
+        .send_opts(peer_pid(2), OwnedTerm::atom("dropped"), SendOpts::new())
+        .await
+        .unwrap();
+    assert_eq!(outcome, SendOutcome::Ok);
+    conn.send_message(from_pid(), peer_pid(3), OwnedTerm::atom("delivered"))
+        .await
+        .unwrap();
+    conn.close().await.unwrap();
+
+    assert_eq!(peer.await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_reject_policy_fails_sends_to_stale_pids() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { read_frames(accept_handshake(&listener, 3).await).await });
+
+    let mut conn = Connection::new(config(port, StalePidPolicy::Reject));
+    conn.connect().await.unwrap();
+    let err = conn
+        .send_message(from_pid(), peer_pid(2), OwnedTerm::atom("rejected"))
+        .await
+        .unwrap_err();
+    assert!(!err.is_recoverable());
+    match err {
+        Error::StalePid {
+            node,
+            creation,
+            current,
+            ..
+        } => {
+            assert_eq!(node, PEER);
+            assert_eq!(creation, 2);
+            assert_eq!(current, 3);
+        }
+        other => panic!("expected StalePid, got {other:?}"),
+    }
+    let result = conn
+        .send_opts(peer_pid(2), OwnedTerm::atom("rejected"), SendOpts::new())
+        .await;
+    assert!(matches!(result, Err(Error::StalePid { .. })));
+    conn.close().await.unwrap();
+
+    assert!(peer.await.unwrap().is_empty());
+}
@@ -0,0 +1,133 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::control::ControlMessage;
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, Error};
+use erltf::OwnedTerm;
+use erltf::encoder::encode;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "strict_cookie";
+const PEER: &str = "strict_peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+
+/// Completes the handshake and sends `control` as a pass-through frame.
+async fn send_control(listener: TcpListener, control: OwnedTerm) -> TcpStream {
+    let mut stream = TestPeer::new(PEER, COOKIE).accept(&listener).await;
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    stream
+        .write_all(&(body.len() as u32).to_be_bytes())
+        .await
+        .unwrap();
+    stream.write_all(&body).await.unwrap();
+    stream
+}
+
+fn unknown_control() -> OwnedTerm {
+    OwnedTerm::tuple(vec![OwnedTerm::integer(99), OwnedTerm::atom("future")])
+}
+
+async fn connect(config: ConnectionConfig, control: OwnedTerm) -> Connection {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _stream = send_control(listener, control).await;
+        // Keeps the stream open while the client reads
+        std::future::pending::<()>().await
+    });
+
+    let mut conn = Connection::new(config.with_remote_port(port));
+    conn.connect().await.unwrap();
+    conn
+}
+
+fn config() -> ConnectionConfig {
+    ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+}
+
+#[tokio::test]
+async fn test_unknown_control_messages_are_rejected_by_default() {
+    let mut conn = connect(config(), unknown_control()).await;
+    let err = conn.receive_message().await.unwrap_err();
+    assert!(
+        matches!(err.inner(), Error::UnknownControlMessage { message_type: 99, term } if *term == unknown_control())
+    );
+}
+
+#[tokio::test]
+async fn test_arity_mismatches_are_rejected_by_default() {
+    let link = OwnedTerm::tuple(vec![OwnedTerm::integer(1), OwnedTerm::atom("only_one")]);
+    let mut conn = connect(config(), link).await;
+    let err = conn.receive_message().await.unwrap_err();
+    assert!(matches!(
+        err.inner(),
+        Error::ControlMessageArityMismatch { arity: 2, .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_permissive_connections_receive_generic_messages() {
+    let config = config().with_permissive_control_messages(true);
+    let mut conn = connect(config, unknown_control()).await;
+    let (control, payload) = conn.receive_message().await.unwrap();
+    assert_eq!(
+        control,
+        ControlMessage::Generic {
+            message_type: 99,
+            fields: vec![OwnedTerm::atom("future")],
+        }
+    );
+    assert_eq!(payload, None);
+}
+
+#[tokio::test]
+async fn test_read_half_follows_the_connection_options() {
+    let mut strict = connect(config(), unknown_control()).await;
+    let options = strict.receive_options();
+    let mut read_half = strict.take_read_half().unwrap();
+    let err = Connection::receive_message_from_read_half_with_options(
+        &mut read_half,
+        strict.timeout(),
+        &options,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err.inner(), Error::UnknownControlMessage { .. }));
+
+    let mut permissive = connect(
+        config().with_permissive_control_messages(true),
+        unknown_control(),
+    )
+    .await;
+    let options = permissive.receive_options();
+    assert!(options.permissive_control_messages);
+    let mut read_half = permissive.take_read_half().unwrap();
+    let (control, _) = Connection::receive_message_from_read_half_with_options(
+        &mut read_half,
+        permissive.timeout(),
+        &options,
+    )
+    .await
+    .unwrap();
+    assert!(matches!(
+        control,
+        ControlMessage::Generic {
+            message_type: 99,
+            ..
+        }
+    ));
+}
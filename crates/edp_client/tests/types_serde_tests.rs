@@ -0,0 +1,33 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "serde")]
+
+use edp_client::{Creation, SequenceId};
+
+#[test]
+fn test_creation_serializes_as_a_number() {
+    let json = serde_json::to_string(&Creation::new(42)).unwrap();
+    assert_eq!(json, "42");
+    let parsed: Creation = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, Creation::new(42));
+}
+
+#[test]
+fn test_sequence_id_serializes_as_a_number() {
+    let json = serde_json::to_string(&SequenceId::new(u64::MAX)).unwrap();
+    assert_eq!(json, u64::MAX.to_string());
+    let parsed: SequenceId = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, SequenceId::new(u64::MAX));
+}
@@ -0,0 +1,260 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::control::ControlMessage;
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, UnlinkTracker};
+use erltf::OwnedTerm;
+use erltf::decoder::decode_with_trailing;
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "unlink_cookie";
+const PEER: &str = "peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+const SEND_SENDER: i64 = 22;
+const EXIT: i64 = 3;
+const EXIT2: i64 = 8;
+const UNLINK_ID: i64 = 35;
+const UNLINK_ID_ACK: i64 = 36;
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+async fn write_control(
+    stream: &mut TcpStream,
+    control: Vec<OwnedTerm>,
+    payload: Option<OwnedTerm>,
+) {
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&OwnedTerm::tuple(control)).unwrap());
+    if let Some(payload) = payload {
+        body.extend(encode(&payload).unwrap());
+    }
+    stream
+        .write_all(&(body.len() as u32).to_be_bytes())
+        .await
+        .unwrap();
+    stream.write_all(&body).await.unwrap();
+}
+
+async fn read_control(stream: &mut TcpStream) -> Vec<OwnedTerm> {
+    loop {
+        let len = stream.read_u32().await.unwrap();
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        assert_eq!(data[0], PASS_THROUGH);
+        let (control, _) = decode_with_trailing(&data[1..]).unwrap();
+        let OwnedTerm::Tuple(control) = control else {
+            panic!("unexpected control message: {:?}", control);
+        };
+        return control;
+    }
+}
+
+fn local_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)
+}
+
+fn remote_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new(PEER), 100, 0, 1)
+}
+
+fn unlink_id(id: i64, from: &ExternalPid, to: &ExternalPid) -> Vec<OwnedTerm> {
+    vec![
+        OwnedTerm::integer(UNLINK_ID),
+        OwnedTerm::integer(id),
+        OwnedTerm::Pid(from.clone()),
+        OwnedTerm::Pid(to.clone()),
+    ]
+}
+
+fn unlink_id_ack(id: i64, from: &ExternalPid, to: &ExternalPid) -> Vec<OwnedTerm> {
+    vec![
+        OwnedTerm::integer(UNLINK_ID_ACK),
+        OwnedTerm::integer(id),
+        OwnedTerm::Pid(from.clone()),
+        OwnedTerm::Pid(to.clone()),
+    ]
+}
+
+fn exit(tag: i64, from: &ExternalPid, to: &ExternalPid, reason: &str) -> Vec<OwnedTerm> {
+    vec![
+        OwnedTerm::integer(tag),
+        OwnedTerm::Pid(from.clone()),
+        OwnedTerm::Pid(to.clone()),
+        OwnedTerm::atom(reason),
+    ]
+}
+
+fn send_to_local() -> Vec<OwnedTerm> {
+    vec![
+        OwnedTerm::integer(SEND),
+        OwnedTerm::atom(""),
+        OwnedTerm::Pid(local_pid()),
+    ]
+}
+
+fn control(elements: Vec<OwnedTerm>) -> ControlMessage {
+    ControlMessage::from_term(&OwnedTerm::tuple(elements)).unwrap()
+}
+
+async fn connect(listener: TcpListener, unlink_acks: bool) -> (Connection, TcpStream) {
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener).await });
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(Duration::from_secs(5))
+        .with_unlink_acks(unlink_acks);
+    let mut conn = Connection::new(config);
+    conn.connect().await.unwrap();
+    (conn, peer.await.unwrap())
+}
+
+#[test]
+fn test_tracker_ignores_link_exits_from_the_process_being_unlinked() {
+    let tracker = UnlinkTracker::new();
+    let (local, remote) = (local_pid(), remote_pid());
+    tracker.begin(&local, &remote, 7);
+    assert!(tracker.is_unlinking(&local, &remote));
+    assert!(!tracker.is_unlinking(&remote, &local));
+
+    assert!(!tracker.accept(&control(exit(EXIT, &remote, &local, "late"))));
+    // exit/2 signals are not link exits
+    assert!(tracker.accept(&control(exit(EXIT2, &remote, &local, "kill"))));
+    let other = ExternalPid::new(Atom::new(PEER), 101, 0, 1);
+    assert!(tracker.accept(&control(exit(EXIT, &other, &local, "late"))));
+
+    // an acknowledgement of an earlier unlink does not complete this one
+    assert!(tracker.accept(&control(unlink_id_ack(6, &remote, &local))));
+    assert!(tracker.is_unlinking(&local, &remote));
+    assert!(tracker.accept(&control(unlink_id_ack(7, &remote, &local))));
+    assert!(tracker.is_empty());
+    assert!(tracker.accept(&control(exit(EXIT, &remote, &local, "later"))));
+}
+
+#[test]
+fn test_forgotten_unlinks_stop_filtering() {
+    let tracker = UnlinkTracker::new();
+    tracker.begin(&local_pid(), &remote_pid(), 7);
+    assert_eq!(tracker.len(), 1);
+    tracker.forget(&local_pid(), &remote_pid());
+    assert!(tracker.is_empty());
+    assert!(tracker.accept(&control(exit(EXIT, &remote_pid(), &local_pid(), "late"))));
+}
+
+#[tokio::test]
+async fn test_unlink_ids_are_acknowledged_with_the_same_id() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let (mut conn, mut stream) = connect(listener, true).await;
+
+    // the link was never set up on this side
+    write_control(
+        &mut stream,
+        unlink_id(42, &remote_pid(), &local_pid()),
+        None,
+    )
+    .await;
+    let (received, _) = conn.receive_message().await.unwrap();
+    assert_eq!(
+        received,
+        control(unlink_id(42, &remote_pid(), &local_pid()))
+    );
+
+    assert_eq!(
+        read_control(&mut stream).await,
+        unlink_id_ack(42, &local_pid(), &remote_pid())
+    );
+}
+
+#[tokio::test]
+async fn test_unlink_acks_can_be_disabled() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let (mut conn, mut stream) = connect(listener, false).await;
+
+    write_control(
+        &mut stream,
+        unlink_id(42, &remote_pid(), &local_pid()),
+        None,
+    )
+    .await;
+    conn.receive_message().await.unwrap();
+    conn.send_message(local_pid(), remote_pid(), OwnedTerm::atom("next"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        read_control(&mut stream).await[0],
+        OwnedTerm::integer(SEND_SENDER)
+    );
+}
+
+#[tokio::test]
+async fn test_exits_received_while_unlinking_are_ignored() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let (mut conn, mut stream) = connect(listener, true).await;
+    let (local, remote) = (local_pid(), remote_pid());
+
+    conn.unlink(&local, &remote, 7).await.unwrap();
+    assert!(conn.unlinks().is_unlinking(&local, &remote));
+    assert_eq!(
+        read_control(&mut stream).await,
+        unlink_id(7, &local, &remote)
+    );
+
+    // sent by the peer before it saw the unlink
+    write_control(&mut stream, exit(EXIT, &remote, &local, "late"), None).await;
+    write_control(&mut stream, unlink_id_ack(7, &remote, &local), None).await;
+    write_control(&mut stream, send_to_local(), Some(OwnedTerm::atom("after"))).await;
+
+    let (received, _) = conn.receive_message().await.unwrap();
+    assert_eq!(received, control(unlink_id_ack(7, &remote, &local)));
+    assert!(conn.unlinks().is_empty());
+    let (received, payload) = conn.receive_message().await.unwrap();
+    assert_eq!(received, control(send_to_local()));
+    assert_eq!(payload, Some(OwnedTerm::atom("after")));
+}
+
+#[tokio::test]
+async fn test_read_halves_ignore_exits_received_while_unlinking() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let (mut conn, mut stream) = connect(listener, true).await;
+    let (local, remote) = (local_pid(), remote_pid());
+    let options = conn.receive_options();
+    assert!(options.unlink_acks);
+    let mut read_half = conn.take_read_half().unwrap();
+
+    conn.unlink(&local, &remote, 7).await.unwrap();
+    read_control(&mut stream).await;
+    write_control(&mut stream, exit(EXIT, &remote, &local, "late"), None).await;
+    write_control(&mut stream, unlink_id_ack(7, &remote, &local), None).await;
+
+    let (received, _) = Connection::receive_message_from_read_half_with_options(
+        &mut read_half,
+        Duration::from_secs(5),
+        &options,
+    )
+    .await
+    .unwrap();
+    assert_eq!(received, control(unlink_id_ack(7, &remote, &local)));
+    assert!(conn.unlinks().is_empty());
+}
@@ -0,0 +1,206 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![cfg(feature = "zstd")]
+
+use edp_client::compression::{ZSTD_MAGIC, decompress, is_compressed};
+use edp_client::control::ControlMessage;
+use edp_client::test_peer::TestPeer;
+use edp_client::{Connection, ConnectionConfig, ReceiveOptions, ZstdCompression};
+use erltf::OwnedTerm;
+use erltf::decoder::decode_all;
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+const COOKIE: &str = "zstd_cookie";
+const PEER: &str = "zstd_peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+const MAX_SIZE: usize = 64 * 1024 * 1024;
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+async fn read_frame_body(stream: &mut TcpStream) -> Vec<u8> {
+    let len = stream.read_u32().await.unwrap();
+    let mut body = vec![0u8; len as usize];
+    stream.read_exact(&mut body).await.unwrap();
+    body
+}
+
+fn local_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("rust@localhost"), 1, 0, 1)
+}
+
+fn peer_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new(PEER), 1, 0, 1)
+}
+
+fn send_body(message: &OwnedTerm) -> Vec<u8> {
+    let control = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(SEND),
+        OwnedTerm::atom(""),
+        OwnedTerm::Pid(local_pid()),
+    ]);
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    body.extend(encode(message).unwrap());
+    body
+}
+
+fn frame(body: &[u8]) -> Vec<u8> {
+    let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+    frame.extend(body);
+    frame
+}
+
+fn compressible() -> OwnedTerm {
+    OwnedTerm::Binary(b"compress me ".repeat(1024))
+}
+
+/// Completes the handshake, writes `frames` and returns the stream.
+async fn start_peer(frames: Vec<Vec<u8>>) -> (u16, JoinHandle<TcpStream>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        for frame in frames {
+            stream.write_all(&frame).await.unwrap();
+        }
+        stream
+    });
+    (port, peer)
+}
+
+async fn connect(port: u16) -> Connection {
+    let config = ConnectionConfig::new("rust@localhost", PEER, COOKIE)
+        .with_remote_port(port)
+        .with_timeout(Duration::from_secs(5))
+        .with_zstd_compression(ZstdCompression::new());
+    let mut conn = Connection::new(config);
+    conn.connect().await.unwrap();
+    conn
+}
+
+//
+// Compression
+//
+
+#[test]
+fn test_compress_skips_small_and_incompressible_bodies() {
+    let compression = ZstdCompression::new().with_min_size(64);
+    assert_eq!(compression.compress(&[131; 63]).unwrap(), None);
+    assert_eq!(compression.compress(&[]).unwrap(), None);
+
+    let random: Vec<u8> = (0..4096).map(|_| rand::random()).collect();
+    assert_eq!(compression.compress(&random).unwrap(), None);
+
+    let body = send_body(&compressible());
+    let compressed = compression.compress(&body).unwrap().unwrap();
+    assert!(compressed.len() < body.len());
+    assert!(is_compressed(&compressed));
+    assert_eq!(decompress(compressed, MAX_SIZE).unwrap(), body);
+}
+
+#[test]
+fn test_decompress_returns_uncompressed_bodies_as_is() {
+    let body = send_body(&OwnedTerm::atom("plain"));
+    assert!(!is_compressed(&body));
+    assert_eq!(decompress(body.clone(), MAX_SIZE).unwrap(), body);
+}
+
+#[test]
+fn test_decompress_enforces_the_size_limit() {
+    let body = vec![131; 64 * 1024];
+    let compressed = ZstdCompression::new().compress(&body).unwrap().unwrap();
+    assert!(compressed.starts_with(&ZSTD_MAGIC));
+    assert!(decompress(compressed.clone(), body.len() - 1).is_err());
+    assert_eq!(decompress(compressed, body.len()).unwrap(), body);
+}
+
+//
+// Connections
+//
+
+#[tokio::test]
+async fn test_sent_frames_are_compressed_after_the_handshake() {
+    let (port, peer) = start_peer(Vec::new()).await;
+    let mut conn = connect(port).await;
+    let mut stream = peer.await.unwrap();
+
+    conn.send_message(local_pid(), peer_pid(), compressible())
+        .await
+        .unwrap();
+    conn.send_message(local_pid(), peer_pid(), OwnedTerm::atom("small"))
+        .await
+        .unwrap();
+
+    let large = read_frame_body(&mut stream).await;
+    assert!(is_compressed(&large));
+    let large = decompress(large, MAX_SIZE).unwrap();
+    assert_eq!(large[0], PASS_THROUGH);
+    let terms: Vec<OwnedTerm> = decode_all(&large[1..]).map(Result::unwrap).collect();
+    assert_eq!(terms[1], compressible());
+
+    let small = read_frame_body(&mut stream).await;
+    assert_eq!(small[0], PASS_THROUGH);
+}
+
+#[tokio::test]
+async fn test_received_frames_are_decompressed() {
+    let body = send_body(&compressible());
+    let compressed = ZstdCompression::new().compress(&body).unwrap().unwrap();
+    let plain = send_body(&OwnedTerm::atom("plain"));
+    let frames = vec![frame(&compressed), vec![0, 0, 0, 0], frame(&plain)];
+    let (port, peer) = start_peer(frames).await;
+
+    let mut conn = connect(port).await;
+    let first = conn.receive_envelope().await.unwrap();
+    let second = conn.receive_envelope().await.unwrap();
+
+    assert!(matches!(first.control, ControlMessage::Send { .. }));
+    assert_eq!(first.payload, Some(compressible()));
+    assert_eq!(first.byte_size, body.len());
+    assert_eq!(second.payload, Some(OwnedTerm::atom("plain")));
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_read_half_decompresses_with_the_connection_options() {
+    let compressed = ZstdCompression::new()
+        .compress(&send_body(&compressible()))
+        .unwrap()
+        .unwrap();
+    let (port, peer) = start_peer(vec![frame(&compressed)]).await;
+
+    let mut conn = connect(port).await;
+    let mut read_half = conn.take_read_half().unwrap();
+    let options = conn.receive_options();
+    assert!(options.zstd_compression.is_some());
+
+    let (_, payload) = Connection::receive_message_from_read_half_with_options(
+        &mut read_half,
+        conn.timeout(),
+        &options,
+    )
+    .await
+    .unwrap();
+    assert_eq!(payload, Some(compressible()));
+    assert!(ReceiveOptions::new().zstd_compression.is_none());
+    drop(peer.await.unwrap());
+}
@@ -197,6 +197,44 @@ impl AtomKeyMapBuilder {
         self
     }
 
+    /// Inserts a nested atom-keyed map built by `f`.
+    ///
+    /// ```
+    /// use edp_elixir_terms::AtomKeyMapBuilder;
+    ///
+    /// let map = AtomKeyMapBuilder::new()
+    ///     .insert_map("opts", |b| b.insert("timeout", 5000))
+    ///     .build();
+    ///
+    /// assert!(map.is_map());
+    /// ```
+    pub fn insert_map<F>(self, key: &str, f: F) -> Self
+    where
+        F: FnOnce(AtomKeyMapBuilder) -> AtomKeyMapBuilder,
+    {
+        let nested = f(AtomKeyMapBuilder::new()).build();
+        self.insert_term(key, nested)
+    }
+
+    /// Inserts a nested keyword list built by `f`.
+    pub fn insert_keyword<F>(self, key: &str, f: F) -> Self
+    where
+        F: FnOnce(KeywordListBuilder) -> KeywordListBuilder,
+    {
+        let nested = f(KeywordListBuilder::new()).build();
+        self.insert_term(key, nested)
+    }
+
+    /// Inserts a list of values.
+    pub fn insert_list<I, V>(self, key: &str, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<OwnedTerm>,
+    {
+        let list = OwnedTerm::List(values.into_iter().map(Into::into).collect());
+        self.insert_term(key, list)
+    }
+
     /// Conditionally inserts a key-value pair.
     pub fn insert_if<V: Into<OwnedTerm>>(self, condition: bool, key: &str, value: V) -> Self {
         if condition {
@@ -14,10 +14,111 @@
 
 //! Elixir Date, Time, and DateTime type support.
 
+#[cfg(feature = "tz")]
+use chrono::{Datelike, Offset, TimeZone, Timelike};
+#[cfg(feature = "tz")]
+use chrono_tz::{OffsetComponents, OffsetName};
 use erltf::{Atom, OwnedTerm};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+const SECONDS_PER_DAY: i64 = 86_400;
+
+const ISO_CALENDAR: &str = "Elixir.Calendar.ISO";
+
+const DATE_FIELDS: &[&str] = &["__struct__", "calendar", "year", "month", "day"];
+const TIME_FIELDS: &[&str] = &[
+    "__struct__",
+    "calendar",
+    "hour",
+    "minute",
+    "second",
+    "microsecond",
+];
+const NAIVE_DATE_TIME_FIELDS: &[&str] = &[
+    "__struct__",
+    "calendar",
+    "year",
+    "month",
+    "day",
+    "hour",
+    "minute",
+    "second",
+    "microsecond",
+];
+const DATE_TIME_FIELDS: &[&str] = &[
+    "__struct__",
+    "calendar",
+    "year",
+    "month",
+    "day",
+    "hour",
+    "minute",
+    "second",
+    "microsecond",
+    "time_zone",
+    "zone_abbr",
+    "utc_offset",
+    "std_offset",
+];
+
+/// Only the ISO calendar is supported. Structs without a `calendar` key are assumed to use it.
+fn has_iso_calendar(map: &BTreeMap<OwnedTerm, OwnedTerm>) -> bool {
+    match map.get(&OwnedTerm::Atom(Atom::new("calendar"))) {
+        Some(calendar) => calendar.is_atom_with_name(ISO_CALENDAR),
+        None => true,
+    }
+}
+
+fn unknown_fields(
+    map: &BTreeMap<OwnedTerm, OwnedTerm>,
+    known: &[&str],
+) -> BTreeMap<OwnedTerm, OwnedTerm> {
+    map.iter()
+        .filter(|(key, _)| !key.atom_name().is_some_and(|name| known.contains(&name)))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+// Howard Hinnant's days_from_civil and civil_from_days, see
+// https://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let month = month as i64;
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> Option<(i64, u8, u8)> {
+    let days = days.checked_add(719_468)?;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = era * 400 + yoe + i64::from(month <= 2);
+    Some((year, month, day))
+}
+
+fn day_of_week(days: i64) -> u8 {
+    // 1970-01-01 was a Thursday
+    ((days + 3).rem_euclid(7) + 1) as u8
+}
+
+fn iso_week(days: i64) -> (i32, u8) {
+    // the ISO year of a week is the year of its Thursday
+    let thursday = days - day_of_week(days) as i64 + 4;
+    let (year, _, _) = civil_from_days(thursday).unwrap_or((i64::MAX, 1, 1));
+    let week = (thursday - days_from_civil(year, 1, 1)) / 7 + 1;
+    (i32::try_from(year).unwrap_or(i32::MAX), week as u8)
+}
+
 /// Represents an Elixir Date (`~D[2025-12-25]`).
 ///
 /// # Example
@@ -32,18 +133,27 @@ use std::collections::BTreeMap;
 /// assert!(term.is_elixir_struct());
 /// assert_eq!(term.elixir_struct_module(), Some("Elixir.Date"));
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ElixirDate {
     pub year: i32,
     pub month: u8,
     pub day: u8,
+    /// Keys other than the known fields, such as fields added by newer Elixir versions.
+    /// They are kept so that decoding and re-encoding a struct is lossless.
+    #[serde(skip)]
+    pub extra_fields: BTreeMap<OwnedTerm, OwnedTerm>,
 }
 
 impl ElixirDate {
     /// Creates a new Date without validation. Use `try_new` for validation.
     #[must_use]
     pub fn new(year: i32, month: u8, day: u8) -> Self {
-        Self { year, month, day }
+        Self {
+            year,
+            month,
+            day,
+            extra_fields: BTreeMap::new(),
+        }
     }
 
     /// Creates a new Date with validation.
@@ -70,7 +180,12 @@ impl ElixirDate {
             return None;
         }
 
-        Some(Self { year, month, day })
+        Some(Self {
+            year,
+            month,
+            day,
+            extra_fields: BTreeMap::new(),
+        })
     }
 
     /// Returns true if the given year is a leap year.
@@ -79,6 +194,49 @@ impl ElixirDate {
         (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
     }
 
+    /// Returns the number of days since 1970-01-01 in the proleptic ISO calendar.
+    #[must_use]
+    pub fn to_days_since_epoch(&self) -> i64 {
+        days_from_civil(self.year as i64, self.month, self.day)
+    }
+
+    /// Converts a number of days since 1970-01-01 to a Date.
+    /// Returns `None` if the year does not fit an `i32`.
+    #[must_use]
+    pub fn from_days_since_epoch(days: i64) -> Option<Self> {
+        let (year, month, day) = civil_from_days(days)?;
+        Some(Self {
+            year: i32::try_from(year).ok()?,
+            month,
+            day,
+            extra_fields: BTreeMap::new(),
+        })
+    }
+
+    /// Adds (or subtracts, if negative) a number of days, like `Date.add/2`.
+    #[must_use]
+    pub fn add_days(&self, days: i64) -> Option<Self> {
+        Self::from_days_since_epoch(self.to_days_since_epoch().checked_add(days)?)
+    }
+
+    /// Returns the number of days from `other` to `self`, like `Date.diff/2`.
+    #[must_use]
+    pub fn diff_days(&self, other: &Self) -> i64 {
+        self.to_days_since_epoch() - other.to_days_since_epoch()
+    }
+
+    /// Returns the ISO day of the week, from 1 (Monday) to 7 (Sunday), like `Date.day_of_week/1`.
+    #[must_use]
+    pub fn day_of_week(&self) -> u8 {
+        day_of_week(self.to_days_since_epoch())
+    }
+
+    /// Returns the ISO week-numbering year and week, like `:calendar.iso_week_number/1`.
+    #[must_use]
+    pub fn iso_week(&self) -> (i32, u8) {
+        iso_week(self.to_days_since_epoch())
+    }
+
     /// Parses an OwnedTerm as a Date struct.
     #[must_use]
     pub fn from_term(term: &OwnedTerm) -> Option<Self> {
@@ -87,19 +245,27 @@ impl ElixirDate {
         }
 
         let map = term.as_map()?;
+        if !has_iso_calendar(map) {
+            return None;
+        }
         let year = map.get(&OwnedTerm::Atom(Atom::new("year")))?.as_integer()? as i32;
         let month = map
             .get(&OwnedTerm::Atom(Atom::new("month")))?
             .as_integer()? as u8;
         let day = map.get(&OwnedTerm::Atom(Atom::new("day")))?.as_integer()? as u8;
 
-        Some(Self { year, month, day })
+        Some(Self {
+            year,
+            month,
+            day,
+            extra_fields: unknown_fields(map, DATE_FIELDS),
+        })
     }
 }
 
 impl From<ElixirDate> for OwnedTerm {
     fn from(date: ElixirDate) -> Self {
-        let mut map = BTreeMap::new();
+        let mut map = date.extra_fields;
         map.insert(
             OwnedTerm::Atom(Atom::new("__struct__")),
             OwnedTerm::Atom(Atom::new("Elixir.Date")),
@@ -118,7 +284,7 @@ impl From<ElixirDate> for OwnedTerm {
         );
         map.insert(
             OwnedTerm::Atom(Atom::new("calendar")),
-            OwnedTerm::Atom(Atom::new("Elixir.Calendar.ISO")),
+            OwnedTerm::Atom(Atom::new(ISO_CALENDAR)),
         );
         OwnedTerm::Map(map)
     }
@@ -144,7 +310,7 @@ impl std::fmt::Display for ElixirDate {
 /// assert!(term.is_elixir_struct());
 /// assert_eq!(term.elixir_struct_module(), Some("Elixir.Time"));
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ElixirTime {
     pub hour: u8,
     pub minute: u8,
@@ -153,6 +319,10 @@ pub struct ElixirTime {
     pub microsecond_value: u32,
     /// Precision (0-6)
     pub microsecond_precision: u8,
+    /// Keys other than the known fields, such as fields added by newer Elixir versions.
+    /// They are kept so that decoding and re-encoding a struct is lossless.
+    #[serde(skip)]
+    pub extra_fields: BTreeMap<OwnedTerm, OwnedTerm>,
 }
 
 impl ElixirTime {
@@ -165,6 +335,7 @@ impl ElixirTime {
             second,
             microsecond_value: microsecond,
             microsecond_precision: precision.min(6),
+            extra_fields: BTreeMap::new(),
         }
     }
 
@@ -192,6 +363,7 @@ impl ElixirTime {
             second,
             microsecond_value: microsecond,
             microsecond_precision: precision,
+            extra_fields: BTreeMap::new(),
         })
     }
 
@@ -215,6 +387,9 @@ impl ElixirTime {
         }
 
         let map = term.as_map()?;
+        if !has_iso_calendar(map) {
+            return None;
+        }
         let hour = map.get(&OwnedTerm::Atom(Atom::new("hour")))?.as_integer()? as u8;
         let minute = map
             .get(&OwnedTerm::Atom(Atom::new("minute")))?
@@ -240,13 +415,14 @@ impl ElixirTime {
             second,
             microsecond_value,
             microsecond_precision,
+            extra_fields: unknown_fields(map, TIME_FIELDS),
         })
     }
 }
 
 impl From<ElixirTime> for OwnedTerm {
     fn from(time: ElixirTime) -> Self {
-        let mut map = BTreeMap::new();
+        let mut map = time.extra_fields;
         map.insert(
             OwnedTerm::Atom(Atom::new("__struct__")),
             OwnedTerm::Atom(Atom::new("Elixir.Time")),
@@ -272,7 +448,7 @@ impl From<ElixirTime> for OwnedTerm {
         );
         map.insert(
             OwnedTerm::Atom(Atom::new("calendar")),
-            OwnedTerm::Atom(Atom::new("Elixir.Calendar.ISO")),
+            OwnedTerm::Atom(Atom::new(ISO_CALENDAR)),
         );
         OwnedTerm::Map(map)
     }
@@ -303,7 +479,7 @@ impl std::fmt::Display for ElixirTime {
 }
 
 /// Represents an Elixir NaiveDateTime (`~N[2025-12-25 14:30:00]`).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ElixirNaiveDateTime {
     pub year: i32,
     pub month: u8,
@@ -313,6 +489,10 @@ pub struct ElixirNaiveDateTime {
     pub second: u8,
     pub microsecond_value: u32,
     pub microsecond_precision: u8,
+    /// Keys other than the known fields, such as fields added by newer Elixir versions.
+    /// They are kept so that decoding and re-encoding a struct is lossless.
+    #[serde(skip)]
+    pub extra_fields: BTreeMap<OwnedTerm, OwnedTerm>,
 }
 
 impl ElixirNaiveDateTime {
@@ -338,6 +518,7 @@ impl ElixirNaiveDateTime {
             second,
             microsecond_value: microsecond,
             microsecond_precision: precision.min(6),
+            extra_fields: BTreeMap::new(),
         }
     }
 
@@ -365,6 +546,7 @@ impl ElixirNaiveDateTime {
             second,
             microsecond_value: microsecond,
             microsecond_precision: precision,
+            extra_fields: BTreeMap::new(),
         })
     }
 
@@ -380,6 +562,7 @@ impl ElixirNaiveDateTime {
             second: time.second,
             microsecond_value: time.microsecond_value,
             microsecond_precision: time.microsecond_precision,
+            extra_fields: BTreeMap::new(),
         }
     }
 
@@ -401,6 +584,71 @@ impl ElixirNaiveDateTime {
         )
     }
 
+    /// Adds (or subtracts, if negative) a number of days, keeping the time of day,
+    /// like `NaiveDateTime.add(ndt, days, :day)`.
+    #[must_use]
+    pub fn add_days(&self, days: i64) -> Option<Self> {
+        let date = self.to_date().add_days(days)?;
+        Some(Self::from_date_time(date, self.to_time()))
+    }
+
+    /// Adds (or subtracts, if negative) a number of seconds, like `NaiveDateTime.add/2`.
+    #[must_use]
+    pub fn add_seconds(&self, seconds: i64) -> Option<Self> {
+        let mut result = Self::from_unix(self.to_unix().checked_add(seconds)?)?;
+        result.microsecond_value = self.microsecond_value;
+        result.microsecond_precision = self.microsecond_precision;
+        Some(result)
+    }
+
+    /// Returns the number of whole days from `other` to `self`, truncated towards zero,
+    /// like `NaiveDateTime.diff(self, other, :day)`.
+    #[must_use]
+    pub fn diff_days(&self, other: &Self) -> i64 {
+        let micros = self.unix_microseconds() - other.unix_microseconds();
+        (micros / (SECONDS_PER_DAY as i128 * 1_000_000)) as i64
+    }
+
+    /// Returns the ISO day of the week, from 1 (Monday) to 7 (Sunday).
+    #[must_use]
+    pub fn day_of_week(&self) -> u8 {
+        self.to_date().day_of_week()
+    }
+
+    /// Returns the ISO week-numbering year and week.
+    #[must_use]
+    pub fn iso_week(&self) -> (i32, u8) {
+        self.to_date().iso_week()
+    }
+
+    /// Returns the number of seconds since the Unix epoch, treating this value as UTC.
+    /// Microseconds are discarded.
+    #[must_use]
+    pub fn to_unix(&self) -> i64 {
+        self.to_date().to_days_since_epoch() * SECONDS_PER_DAY
+            + self.hour as i64 * 3600
+            + self.minute as i64 * 60
+            + self.second as i64
+    }
+
+    /// Converts a number of seconds since the Unix epoch to a NaiveDateTime in UTC.
+    /// Returns `None` if the year does not fit an `i32`.
+    #[must_use]
+    pub fn from_unix(seconds: i64) -> Option<Self> {
+        let date = ElixirDate::from_days_since_epoch(seconds.div_euclid(SECONDS_PER_DAY))?;
+        let secs = seconds.rem_euclid(SECONDS_PER_DAY);
+        let time = ElixirTime::hms(
+            (secs / 3600) as u8,
+            (secs % 3600 / 60) as u8,
+            (secs % 60) as u8,
+        );
+        Some(Self::from_date_time(date, time))
+    }
+
+    fn unix_microseconds(&self) -> i128 {
+        self.to_unix() as i128 * 1_000_000 + self.microsecond_value as i128
+    }
+
     /// Parses an OwnedTerm as a NaiveDateTime struct.
     #[must_use]
     pub fn from_term(term: &OwnedTerm) -> Option<Self> {
@@ -409,6 +657,9 @@ impl ElixirNaiveDateTime {
         }
 
         let map = term.as_map()?;
+        if !has_iso_calendar(map) {
+            return None;
+        }
         let year = map.get(&OwnedTerm::Atom(Atom::new("year")))?.as_integer()? as i32;
         let month = map
             .get(&OwnedTerm::Atom(Atom::new("month")))?
@@ -442,6 +693,7 @@ impl ElixirNaiveDateTime {
             second,
             microsecond_value,
             microsecond_precision,
+            extra_fields: unknown_fields(map, NAIVE_DATE_TIME_FIELDS),
         })
     }
 }
@@ -475,7 +727,7 @@ impl std::fmt::Display for ElixirNaiveDateTime {
 
 impl From<ElixirNaiveDateTime> for OwnedTerm {
     fn from(dt: ElixirNaiveDateTime) -> Self {
-        let mut map = BTreeMap::new();
+        let mut map = dt.extra_fields;
         map.insert(
             OwnedTerm::Atom(Atom::new("__struct__")),
             OwnedTerm::Atom(Atom::new("Elixir.NaiveDateTime")),
@@ -513,7 +765,7 @@ impl From<ElixirNaiveDateTime> for OwnedTerm {
         );
         map.insert(
             OwnedTerm::Atom(Atom::new("calendar")),
-            OwnedTerm::Atom(Atom::new("Elixir.Calendar.ISO")),
+            OwnedTerm::Atom(Atom::new(ISO_CALENDAR)),
         );
         OwnedTerm::Map(map)
     }
@@ -534,6 +786,10 @@ pub struct ElixirDateTime {
     pub zone_abbr: String,
     pub utc_offset: i32,
     pub std_offset: i32,
+    /// Keys other than the known fields, such as fields added by newer Elixir versions.
+    /// They are kept so that decoding and re-encoding a struct is lossless.
+    #[serde(skip)]
+    pub extra_fields: BTreeMap<OwnedTerm, OwnedTerm>,
 }
 
 impl ElixirDateTime {
@@ -563,6 +819,7 @@ impl ElixirDateTime {
             zone_abbr: "UTC".to_string(),
             utc_offset: 0,
             std_offset: 0,
+            extra_fields: BTreeMap::new(),
         }
     }
 
@@ -594,6 +851,7 @@ impl ElixirDateTime {
             zone_abbr: "UTC".to_string(),
             utc_offset: 0,
             std_offset: 0,
+            extra_fields: BTreeMap::new(),
         })
     }
 
@@ -627,6 +885,7 @@ impl ElixirDateTime {
             zone_abbr: zone_abbr.to_string(),
             utc_offset,
             std_offset,
+            extra_fields: BTreeMap::new(),
         }
     }
 
@@ -663,6 +922,29 @@ impl ElixirDateTime {
         )
     }
 
+    /// Returns the number of seconds since the Unix epoch, like `DateTime.to_unix/1`.
+    /// The UTC and standard offsets are taken into account, microseconds are discarded.
+    #[must_use]
+    pub fn to_unix(&self) -> i64 {
+        self.to_naive().to_unix() - self.utc_offset as i64 - self.std_offset as i64
+    }
+
+    /// Converts a number of seconds since the Unix epoch to a UTC DateTime, like `DateTime.from_unix/1`.
+    #[must_use]
+    pub fn from_unix(seconds: i64) -> Option<Self> {
+        let naive = ElixirNaiveDateTime::from_unix(seconds)?;
+        Some(Self::utc(
+            naive.year,
+            naive.month,
+            naive.day,
+            naive.hour,
+            naive.minute,
+            naive.second,
+            0,
+            0,
+        ))
+    }
+
     /// Parses an OwnedTerm as a DateTime struct.
     #[must_use]
     pub fn from_term(term: &OwnedTerm) -> Option<Self> {
@@ -671,6 +953,9 @@ impl ElixirDateTime {
         }
 
         let map = term.as_map()?;
+        if !has_iso_calendar(map) {
+            return None;
+        }
         let year = map.get(&OwnedTerm::Atom(Atom::new("year")))?.as_integer()? as i32;
         let month = map
             .get(&OwnedTerm::Atom(Atom::new("month")))?
@@ -721,10 +1006,63 @@ impl ElixirDateTime {
             zone_abbr,
             utc_offset,
             std_offset,
+            extra_fields: unknown_fields(map, DATE_TIME_FIELDS),
         })
     }
 }
 
+#[cfg(feature = "tz")]
+impl ElixirDateTime {
+    /// Returns the same instant in another time zone, like `DateTime.shift_zone/2`
+    /// with the tzdata database: `utc_offset` is the standard offset of the zone
+    /// and `std_offset` the daylight saving offset in effect at that instant.
+    /// Returns `None` for unknown zones and instants `chrono` cannot represent.
+    #[must_use]
+    pub fn shift_zone(&self, time_zone: &str) -> Option<Self> {
+        let tz: chrono_tz::Tz = time_zone.parse().ok()?;
+        self.shift_to_tz(tz)
+    }
+
+    /// Like [`ElixirDateTime::shift_zone`] for a parsed [`chrono_tz::Tz`].
+    #[must_use]
+    pub fn shift_to_tz(&self, tz: chrono_tz::Tz) -> Option<Self> {
+        let utc = chrono::DateTime::from_timestamp(self.to_unix(), self.microsecond_value * 1000)?;
+        let local = tz.from_utc_datetime(&utc.naive_utc());
+        let offset = local.offset();
+        let total_offset = offset.fix().local_minus_utc();
+        // tzdata abbreviates zones without letter abbreviations as "+04" or "+0545"
+        let zone_abbr = match offset.abbreviation() {
+            Some(abbr) => abbr.to_string(),
+            None => numeric_zone_abbr(total_offset),
+        };
+        Some(Self {
+            year: local.year(),
+            month: local.month() as u8,
+            day: local.day() as u8,
+            hour: local.hour() as u8,
+            minute: local.minute() as u8,
+            second: local.second() as u8,
+            microsecond_value: self.microsecond_value,
+            microsecond_precision: self.microsecond_precision,
+            time_zone: tz.name().to_string(),
+            zone_abbr,
+            utc_offset: offset.base_utc_offset().num_seconds() as i32,
+            std_offset: offset.dst_offset().num_seconds() as i32,
+            extra_fields: BTreeMap::new(),
+        })
+    }
+}
+
+#[cfg(feature = "tz")]
+fn numeric_zone_abbr(offset_seconds: i32) -> String {
+    let sign = if offset_seconds < 0 { '-' } else { '+' };
+    let minutes = offset_seconds.unsigned_abs() / 60;
+    match minutes % 60 {
+        0 => format!("{sign}{:02}", minutes / 60),
+        m => format!("{sign}{:02}{m:02}", minutes / 60),
+    }
+}
+
 impl std::fmt::Display for ElixirDateTime {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let zone = if self.zone_abbr == "UTC" {
@@ -760,7 +1098,7 @@ impl std::fmt::Display for ElixirDateTime {
 
 impl From<ElixirDateTime> for OwnedTerm {
     fn from(dt: ElixirDateTime) -> Self {
-        let mut map = BTreeMap::new();
+        let mut map = dt.extra_fields;
         map.insert(
             OwnedTerm::Atom(Atom::new("__struct__")),
             OwnedTerm::Atom(Atom::new("Elixir.DateTime")),
@@ -814,7 +1152,7 @@ impl From<ElixirDateTime> for OwnedTerm {
         );
         map.insert(
             OwnedTerm::Atom(Atom::new("calendar")),
-            OwnedTerm::Atom(Atom::new("Elixir.Calendar.ISO")),
+            OwnedTerm::Atom(Atom::new(ISO_CALENDAR)),
         );
         OwnedTerm::Map(map)
     }
@@ -17,7 +17,17 @@
 //! Helpers for constructing and parsing GenServer message tuples.
 //! These are low-level building blocks, not a GenServer framework.
 
-use erltf::{Atom, ExternalPid, OwnedTerm};
+use erltf::{Atom, ExternalPid, ExternalReference, OwnedTerm};
+
+/// Where the reply to a gen_call must be sent, see [`GenServerTerms::reply_target`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplyTarget<'a> {
+    /// The caller used a `{pid, ref}` from tuple.
+    Pid(&'a ExternalPid),
+    /// The caller used a `{pid, [:alias | ref]}` from tuple (OTP 24+).
+    /// Replies sent to the pid instead of the alias are dropped.
+    Alias(&'a ExternalReference),
+}
 
 /// Helpers for constructing and parsing GenServer message tuples.
 pub struct GenServerTerms;
@@ -33,6 +43,23 @@ impl GenServerTerms {
         ])
     }
 
+    /// Creates a `{:'$gen_call', {pid, [:alias | alias]}, request}` message,
+    /// the form `gen_server:call/3` uses since OTP 24.
+    #[must_use]
+    pub fn gen_call_with_alias(
+        from_pid: OwnedTerm,
+        alias: OwnedTerm,
+        request: OwnedTerm,
+    ) -> OwnedTerm {
+        Self::gen_call(from_pid, Self::alias_tag(alias), request)
+    }
+
+    /// Creates the `[:alias | alias]` tag of an alias-based from tuple.
+    #[must_use]
+    pub fn alias_tag(alias: OwnedTerm) -> OwnedTerm {
+        OwnedTerm::improper_list(vec![OwnedTerm::Atom(Atom::new("alias"))], alias)
+    }
+
     /// Creates a `{:'$gen_cast', request}` message.
     #[must_use]
     pub fn gen_cast(request: OwnedTerm) -> OwnedTerm {
@@ -247,13 +274,47 @@ impl GenServerTerms {
         })
     }
 
-    /// Extracts the PID and reference from a gen_call "from" tuple.
+    /// Extracts the PID and tag from a gen_call "from" tuple.
+    ///
+    /// The tag is either a reference or, for alias-based calls, `[:alias | ref]`.
+    /// A reply must carry the tag unchanged, see [`GenServerTerms::gen_reply`].
     #[must_use]
     pub fn parse_from(from: &OwnedTerm) -> Option<(&ExternalPid, &OwnedTerm)> {
         from.as_2_tuple()
             .and_then(|(pid_term, ref_term)| pid_term.as_pid().map(|pid| (pid, ref_term)))
     }
 
+    /// Extracts the alias from a `[:alias | alias]` tag.
+    #[must_use]
+    pub fn parse_alias_tag(tag: &OwnedTerm) -> Option<&ExternalReference> {
+        match tag {
+            OwnedTerm::ImproperList { elements, tail } => match (elements.as_slice(), &**tail) {
+                ([marker], OwnedTerm::Reference(alias)) if marker.is_atom_with_name("alias") => {
+                    Some(alias)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns where the reply to a call with the given "from" tuple must be sent.
+    #[must_use]
+    pub fn reply_target(from: &OwnedTerm) -> Option<ReplyTarget<'_>> {
+        let (pid, tag) = Self::parse_from(from)?;
+        match Self::parse_alias_tag(tag) {
+            Some(alias) => Some(ReplyTarget::Alias(alias)),
+            None => Some(ReplyTarget::Pid(pid)),
+        }
+    }
+
+    /// Creates the `{tag, reply}` message `gen_server:reply/2` sends to the caller.
+    #[must_use]
+    pub fn gen_reply(from: &OwnedTerm, reply: OwnedTerm) -> Option<OwnedTerm> {
+        let (_, tag) = Self::parse_from(from)?;
+        Some(OwnedTerm::Tuple(vec![tag.clone(), reply]))
+    }
+
     /// Checks if the term is a `{:reply, ...}` response.
     #[must_use]
     pub fn is_reply(term: &OwnedTerm) -> bool {
@@ -0,0 +1,200 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Elixir keyword list support.
+
+use erltf::{Atom, OwnedTerm};
+use std::collections::HashSet;
+use std::vec;
+
+/// Represents an Elixir keyword list.
+///
+/// Follows the semantics of Elixir's `Keyword` module: entries keep their order,
+/// keys may repeat and lookups return the first match.
+///
+/// # Example
+///
+/// ```
+/// use edp_elixir_terms::KeywordList;
+/// use erltf::OwnedTerm;
+///
+/// let mut kw = KeywordList::new();
+/// kw.push("only", "a");
+/// kw.push("only", "b");
+///
+/// assert_eq!(kw.get("only"), Some(&OwnedTerm::from("a")));
+/// assert_eq!(kw.get_values("only").len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeywordList {
+    entries: Vec<(Atom, OwnedTerm)>,
+}
+
+impl KeywordList {
+    /// Creates a new empty keyword list.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Appends an entry, keeping any existing entries for the key.
+    pub fn push<V: Into<OwnedTerm>>(&mut self, key: &str, value: V) {
+        self.entries.push((Atom::new(key), value.into()));
+    }
+
+    /// Replaces all entries for the key with a single entry at the front, like `Keyword.put/3`.
+    pub fn put<V: Into<OwnedTerm>>(&mut self, key: &str, value: V) {
+        self.delete(key);
+        self.entries.insert(0, (Atom::new(key), value.into()));
+    }
+
+    /// Returns the value of the first entry for the key, like `Keyword.get/2`.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&OwnedTerm> {
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Returns the values of all entries for the key, in order.
+    #[must_use]
+    pub fn get_values(&self, key: &str) -> Vec<&OwnedTerm> {
+        self.entries
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, v)| v)
+            .collect()
+    }
+
+    /// Returns true if there is at least one entry for the key.
+    #[must_use]
+    pub fn has_key(&self, key: &str) -> bool {
+        self.entries.iter().any(|(k, _)| k == key)
+    }
+
+    /// Returns the keys in order, including duplicates.
+    pub fn keys(&self) -> impl Iterator<Item = &Atom> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Removes all entries for the key. Returns true if any were removed.
+    pub fn delete(&mut self, key: &str) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|(k, _)| k != key);
+        self.entries.len() != len
+    }
+
+    /// Merges two keyword lists, like `Keyword.merge/2`.
+    ///
+    /// Entries of `self` whose keys appear in `other` are dropped, then all entries of `other`
+    /// are appended, duplicates included.
+    #[must_use]
+    pub fn merge(mut self, other: Self) -> Self {
+        let overridden: HashSet<&Atom> = other.keys().collect();
+        self.entries.retain(|(k, _)| !overridden.contains(k));
+        self.entries.extend(other.entries);
+        self
+    }
+
+    /// Keeps only the first entry for every key, preserving order.
+    ///
+    /// [`KeywordList::get`] returns the same values before and after.
+    pub fn dedup(&mut self) {
+        let mut seen = HashSet::new();
+        self.entries.retain(|(k, _)| seen.insert(k.clone()));
+    }
+
+    /// Checks that every key is one of `allowed`, like `Keyword.validate/2`.
+    ///
+    /// Returns the unknown keys, without duplicates, in the order they first appear.
+    pub fn validate(&self, allowed: &[&str]) -> Result<(), Vec<Atom>> {
+        let mut invalid: Vec<Atom> = Vec::new();
+        for (key, _) in &self.entries {
+            if !allowed.contains(&key.as_str()) && !invalid.contains(key) {
+                invalid.push(key.clone());
+            }
+        }
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid)
+        }
+    }
+
+    /// Returns the number of entries, including duplicates.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the keyword list is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the entries, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Atom, &OwnedTerm)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    /// Parses a list of `{atom, value}` tuples.
+    ///
+    /// Returns `None` for improper lists and for elements that are not 2-tuples with an atom key.
+    #[must_use]
+    pub fn from_term(term: &OwnedTerm) -> Option<Self> {
+        let elements = match term {
+            OwnedTerm::List(elements) => elements.as_slice(),
+            OwnedTerm::Nil => &[],
+            _ => return None,
+        };
+
+        let mut entries = Vec::with_capacity(elements.len());
+        for element in elements {
+            match element.as_tuple()? {
+                [OwnedTerm::Atom(key), value] => entries.push((key.clone(), value.clone())),
+                _ => return None,
+            }
+        }
+        Some(Self { entries })
+    }
+}
+
+impl From<KeywordList> for OwnedTerm {
+    fn from(kw: KeywordList) -> Self {
+        OwnedTerm::List(
+            kw.entries
+                .into_iter()
+                .map(|(k, v)| OwnedTerm::Tuple(vec![OwnedTerm::Atom(k), v]))
+                .collect(),
+        )
+    }
+}
+
+impl FromIterator<(Atom, OwnedTerm)> for KeywordList {
+    fn from_iter<I: IntoIterator<Item = (Atom, OwnedTerm)>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for KeywordList {
+    type Item = (Atom, OwnedTerm);
+    type IntoIter = vec::IntoIter<(Atom, OwnedTerm)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
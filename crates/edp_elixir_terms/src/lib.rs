@@ -42,6 +42,7 @@ mod builders;
 mod date_time;
 mod exceptions;
 mod gen_server_terms;
+mod keyword;
 mod map_set;
 mod range;
 
@@ -52,6 +53,9 @@ pub use exceptions::{
     CondClauseError, ElixirExceptionExt, FunctionClauseError, KeyError, MatchError, RuntimeError,
     UndefinedFunctionError, WithClauseError,
 };
-pub use gen_server_terms::GenServerTerms;
+pub use gen_server_terms::{GenServerTerms, ReplyTarget};
+pub use keyword::KeywordList;
 pub use map_set::ElixirMapSet;
+#[cfg(feature = "stream")]
+pub use range::RangeStream;
 pub use range::{ElixirRange, RangeIterator};
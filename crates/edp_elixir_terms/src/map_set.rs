@@ -14,15 +14,20 @@
 
 //! Elixir MapSet type support.
 //!
-//! Uses the `:sets` v2 format (Elixir 1.17+).
+//! Encodes as `%MapSet{map: %{elem => []}, version: 2}`. Decoding also accepts
+//! the struct without `version`, whose `map` is a `:sets` v2 set.
 
 use erltf::{Atom, OwnedTerm};
+use std::collections::btree_set::{Difference, Intersection, SymmetricDifference, Union};
 use std::collections::{BTreeMap, BTreeSet};
 
+const MAP_SET_VERSION: i64 = 2;
+
 /// Represents an Elixir MapSet.
 ///
 /// MapSet is a set data structure backed by a map where each element
-/// is a key with an empty list as value.
+/// is a key with an empty list as value. The elements are kept in a
+/// [`BTreeSet`], so set operations never go through an intermediate map.
 ///
 /// # Example
 ///
@@ -102,44 +107,70 @@ impl ElixirMapSet {
         self.elements.iter()
     }
 
+    /// Keeps only the elements for which `f` returns true.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&OwnedTerm) -> bool,
+    {
+        self.elements.retain(f);
+    }
+
     /// Returns the union of this set with another.
     #[must_use]
     pub fn union(&self, other: &Self) -> Self {
-        Self {
-            elements: self.elements.union(&other.elements).cloned().collect(),
+        let (larger, smaller) = if self.len() >= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut elements = larger.elements.clone();
+        for elem in &smaller.elements {
+            if !elements.contains(elem) {
+                elements.insert(elem.clone());
+            }
         }
+        Self { elements }
     }
 
     /// Returns the intersection of this set with another.
     #[must_use]
     pub fn intersection(&self, other: &Self) -> Self {
-        Self {
-            elements: self
-                .elements
-                .intersection(&other.elements)
-                .cloned()
-                .collect(),
-        }
+        collect_cloned(self.intersection_iter(other))
     }
 
     /// Returns the difference of this set with another.
     #[must_use]
     pub fn difference(&self, other: &Self) -> Self {
-        Self {
-            elements: self.elements.difference(&other.elements).cloned().collect(),
-        }
+        collect_cloned(self.difference_iter(other))
     }
 
     /// Returns the symmetric difference of this set with another.
     #[must_use]
     pub fn symmetric_difference(&self, other: &Self) -> Self {
-        Self {
-            elements: self
-                .elements
-                .symmetric_difference(&other.elements)
-                .cloned()
-                .collect(),
-        }
+        collect_cloned(self.symmetric_difference_iter(other))
+    }
+
+    /// Lazily visits the union of this set with another, in ascending order.
+    pub fn union_iter<'a>(&'a self, other: &'a Self) -> Union<'a, OwnedTerm> {
+        self.elements.union(&other.elements)
+    }
+
+    /// Lazily visits the intersection of this set with another, in ascending order.
+    pub fn intersection_iter<'a>(&'a self, other: &'a Self) -> Intersection<'a, OwnedTerm> {
+        self.elements.intersection(&other.elements)
+    }
+
+    /// Lazily visits the difference of this set with another, in ascending order.
+    pub fn difference_iter<'a>(&'a self, other: &'a Self) -> Difference<'a, OwnedTerm> {
+        self.elements.difference(&other.elements)
+    }
+
+    /// Lazily visits the symmetric difference of this set with another, in ascending order.
+    pub fn symmetric_difference_iter<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> SymmetricDifference<'a, OwnedTerm> {
+        self.elements.symmetric_difference(&other.elements)
     }
 
     /// Returns true if this set is a subset of another.
@@ -160,9 +191,16 @@ impl ElixirMapSet {
         self.elements.is_disjoint(&other.elements)
     }
 
+    /// Returns the elements as a [`BTreeSet`].
+    #[must_use]
+    pub fn as_btree_set(&self) -> &BTreeSet<OwnedTerm> {
+        &self.elements
+    }
+
     /// Parses an OwnedTerm as a MapSet struct.
     ///
-    /// Expects `:sets` v2 format: `{:set, size, %{elem => []}}`.
+    /// Accepts `%MapSet{map: %{elem => []}}` with or without the `version` field,
+    /// as well as the `{:set, size, %{elem => []}}` tuple earlier versions of this crate emitted.
     #[must_use]
     pub fn from_term(term: &OwnedTerm) -> Option<Self> {
         if term.elixir_struct_module() != Some("Elixir.MapSet") {
@@ -170,50 +208,67 @@ impl ElixirMapSet {
         }
 
         let map = term.as_map()?;
-        let map_key = OwnedTerm::Atom(Atom::new("map"));
-        let map_value = map.get(&map_key)?;
-
-        // :sets v2 format: {:set, size, %{elem => []}}
-        let tuple = map_value.as_tuple()?;
-        if tuple.len() != 3 || tuple[0].atom_name() != Some("set") {
-            return None;
+        let map_value = map.get(&OwnedTerm::Atom(Atom::new("map")))?;
+
+        let inner_map = match map_value {
+            OwnedTerm::Map(inner_map) => inner_map,
+            OwnedTerm::Tuple(tuple) if tuple.len() == 3 && tuple[0].atom_name() == Some("set") => {
+                tuple[2].as_map()?
+            }
+            _ => return None,
+        };
+
+        let mut elements = BTreeSet::new();
+        for elem in inner_map.keys() {
+            elements.insert(elem.clone());
         }
-
-        let inner_map = tuple[2].as_map()?;
-        let elements: BTreeSet<OwnedTerm> = inner_map.keys().cloned().collect();
         Some(Self { elements })
     }
 }
 
 impl From<ElixirMapSet> for OwnedTerm {
     fn from(set: ElixirMapSet) -> Self {
-        // Elixir 1.17+ MapSet uses :sets v2:
-        // %MapSet{map: {:set, size, %{elem1 => [], elem2 => [], ...}}}
-        let size = set.elements.len() as i64;
-        let inner_map: BTreeMap<OwnedTerm, OwnedTerm> = set
-            .elements
-            .into_iter()
-            .map(|elem| (elem, OwnedTerm::List(vec![])))
-            .collect();
-
-        // Build the :sets v2 tuple: {:set, size, map}
-        let sets_tuple = OwnedTerm::Tuple(vec![
-            OwnedTerm::Atom(Atom::new("set")),
-            OwnedTerm::Integer(size),
-            OwnedTerm::Map(inner_map),
-        ]);
+        // %MapSet{map: %{elem1 => [], elem2 => [], ...}, version: 2}
+        let mut inner_map = BTreeMap::new();
+        for elem in set.elements {
+            inner_map.insert(elem, OwnedTerm::List(vec![]));
+        }
 
         let mut outer_map = BTreeMap::new();
         outer_map.insert(
             OwnedTerm::Atom(Atom::new("__struct__")),
             OwnedTerm::Atom(Atom::new("Elixir.MapSet")),
         );
-        outer_map.insert(OwnedTerm::Atom(Atom::new("map")), sets_tuple);
+        outer_map.insert(OwnedTerm::Atom(Atom::new("map")), OwnedTerm::Map(inner_map));
+        outer_map.insert(
+            OwnedTerm::Atom(Atom::new("version")),
+            OwnedTerm::Integer(MAP_SET_VERSION),
+        );
 
         OwnedTerm::Map(outer_map)
     }
 }
 
+impl From<BTreeSet<OwnedTerm>> for ElixirMapSet {
+    fn from(elements: BTreeSet<OwnedTerm>) -> Self {
+        Self { elements }
+    }
+}
+
+impl From<ElixirMapSet> for BTreeSet<OwnedTerm> {
+    fn from(set: ElixirMapSet) -> Self {
+        set.elements
+    }
+}
+
+impl<T: Into<OwnedTerm>> Extend<T> for ElixirMapSet {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.elements.insert(value.into());
+        }
+    }
+}
+
 impl FromIterator<OwnedTerm> for ElixirMapSet {
     fn from_iter<I: IntoIterator<Item = OwnedTerm>>(iter: I) -> Self {
         Self {
@@ -239,3 +294,12 @@ impl<'a> IntoIterator for &'a ElixirMapSet {
         self.elements.iter()
     }
 }
+
+fn collect_cloned<'a>(iter: impl Iterator<Item = &'a OwnedTerm>) -> ElixirMapSet {
+    // Ascending inserts extend the rightmost leaf, without the sort a `collect` would do
+    let mut elements = BTreeSet::new();
+    for elem in iter {
+        elements.insert(elem.clone());
+    }
+    ElixirMapSet { elements }
+}
@@ -15,8 +15,17 @@
 //! Elixir Range type support.
 
 use erltf::{Atom, OwnedTerm};
+#[cfg(feature = "stream")]
+use futures_core::Stream;
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+#[cfg(feature = "stream")]
+use std::pin::Pin;
+#[cfg(feature = "stream")]
+use std::task::{Context, Poll};
 
 /// Represents an Elixir Range (`first..last//step`).
 ///
@@ -73,28 +82,55 @@ impl ElixirRange {
         }
     }
 
-    /// Returns the number of elements in the range.
+    /// Returns the number of elements in the range, saturating at `usize::MAX`.
     #[must_use]
     pub fn len(&self) -> usize {
+        usize::try_from(self.len_u128()).unwrap_or(usize::MAX)
+    }
+
+    /// Returns the number of elements in the range. Unlike [`len`](Self::len),
+    /// this is exact for every range, including `i64::MIN..i64::MAX`.
+    #[must_use]
+    pub fn len_u128(&self) -> u128 {
         if self.is_empty() {
             return 0;
         }
-        let diff = (self.last - self.first).abs();
-        let step = self.step.abs();
-        ((diff / step) + 1) as usize
+        let diff = (i128::from(self.last) - i128::from(self.first)).unsigned_abs();
+        diff / u128::from(self.step.unsigned_abs()) + 1
     }
 
     /// Returns true if the range contains the given value.
     #[must_use]
     pub fn contains(&self, value: i64) -> bool {
+        self.contains_i128(i128::from(value))
+    }
+
+    /// Like [`contains`](Self::contains), for values that may not fit an `i64`,
+    /// such as big integers decoded from a term.
+    #[must_use]
+    pub fn contains_i128(&self, value: i128) -> bool {
         if self.is_empty() {
             return false;
         }
-        if self.step > 0 {
-            value >= self.first && value <= self.last && (value - self.first) % self.step == 0
+        let (first, last) = (i128::from(self.first), i128::from(self.last));
+        let (low, high) = if self.step > 0 {
+            (first, last)
         } else {
-            value <= self.first && value >= self.last && (self.first - value) % (-self.step) == 0
-        }
+            (last, first)
+        };
+        low <= value && value <= high && (value - first) % i128::from(self.step) == 0
+    }
+
+    /// Returns the equivalent `first..=last` range if the step is 1.
+    #[must_use]
+    pub fn to_std_range(&self) -> Option<RangeInclusive<i64>> {
+        (self.step == 1).then_some(self.first..=self.last)
+    }
+
+    /// Returns the element at `index`, computed without intermediate overflow.
+    fn nth_element(&self, index: u128) -> i64 {
+        let offset = index as i128 * i128::from(self.step);
+        (i128::from(self.first) + offset) as i64
     }
 
     /// Parses an OwnedTerm as a Range struct.
@@ -140,82 +176,112 @@ impl From<ElixirRange> for OwnedTerm {
     }
 }
 
+impl From<RangeInclusive<i64>> for ElixirRange {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        Self::ascending(*range.start(), *range.end())
+    }
+}
+
 impl IntoIterator for ElixirRange {
     type Item = i64;
     type IntoIter = RangeIterator;
 
     fn into_iter(self) -> Self::IntoIter {
         RangeIterator {
-            current: self.first,
+            next: 0,
+            len: self.len_u128(),
             range: self,
-            done: false,
         }
     }
 }
 
 /// Iterator over an ElixirRange.
 pub struct RangeIterator {
-    current: i64,
+    next: u128,
+    len: u128,
     range: ElixirRange,
-    done: bool,
 }
 
 impl Iterator for RangeIterator {
     type Item = i64;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.done || self.range.is_empty() {
+        if self.next >= self.len {
             return None;
         }
-
-        let value = self.current;
-
-        if self.range.step > 0 {
-            if value > self.range.last {
-                self.done = true;
-                return None;
-            }
-            if value == self.range.last {
-                self.done = true;
-            } else {
-                self.current = self.current.saturating_add(self.range.step);
-            }
-        } else {
-            if value < self.range.last {
-                self.done = true;
-                return None;
-            }
-            if value == self.range.last {
-                self.done = true;
-            } else {
-                self.current = self.current.saturating_add(self.range.step);
-            }
-        }
-
+        let value = self.range.nth_element(self.next);
+        self.next += 1;
         Some(value)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        if self.done || self.range.is_empty() {
-            return (0, Some(0));
-        }
-        let remaining = if self.range.step > 0 {
-            if self.current > self.range.last {
-                0
-            } else {
-                (((self.range.last - self.current) / self.range.step) + 1) as usize
-            }
-        } else if self.current < self.range.last {
-            0
-        } else {
-            (((self.current - self.range.last) / (-self.range.step)) + 1) as usize
-        };
+        let remaining = usize::try_from(self.len - self.next).unwrap_or(usize::MAX);
         (remaining, Some(remaining))
     }
 }
 
+impl DoubleEndedIterator for RangeIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.next >= self.len {
+            return None;
+        }
+        self.len -= 1;
+        Some(self.range.nth_element(self.len))
+    }
+}
+
 impl ExactSizeIterator for RangeIterator {}
 
+#[cfg(feature = "stream")]
+impl ElixirRange {
+    /// Returns a [`Stream`](futures_core::Stream) of the elements, for ranges used as
+    /// work generators in async code. Every element is immediately ready.
+    pub fn into_stream(self) -> RangeStream {
+        RangeStream {
+            iter: self.into_iter(),
+        }
+    }
+}
+
+/// A [`Stream`](futures_core::Stream) over an ElixirRange, see [`ElixirRange::into_stream`].
+#[cfg(feature = "stream")]
+pub struct RangeStream {
+    iter: RangeIterator,
+}
+
+#[cfg(feature = "stream")]
+impl Stream for RangeStream {
+    type Item = i64;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl ElixirRange {
+    /// Returns a parallel iterator over the elements. The range is split in halves
+    /// between rayon workers, so it works for ranges of any length.
+    pub fn into_par_iter(self) -> impl rayon::iter::ParallelIterator<Item = i64> {
+        rayon::iter::split(self, ElixirRange::split_in_half).flat_map_iter(IntoIterator::into_iter)
+    }
+
+    fn split_in_half(self) -> (Self, Option<Self>) {
+        let len = self.len_u128();
+        if len < 2 {
+            return (self, None);
+        }
+        let mid = len / 2;
+        let left = Self::new(self.first, self.nth_element(mid - 1), self.step);
+        let right = Self::new(self.nth_element(mid), self.last, self.step);
+        (left, Some(right))
+    }
+}
+
 impl std::fmt::Display for ElixirRange {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.step == 1 {
@@ -14,11 +14,11 @@
 
 use edp_elixir_terms::{
     ArgumentError, AtomKeyMapBuilder, ElixirDate, ElixirDateTime, ElixirExceptionExt, ElixirMapSet,
-    ElixirNaiveDateTime, ElixirRange, ElixirTime, GenServerTerms, KeyError, KeywordListBuilder,
-    MatchError, RuntimeError, UndefinedFunctionError,
+    ElixirNaiveDateTime, ElixirRange, ElixirTime, GenServerTerms, KeyError, KeywordList,
+    KeywordListBuilder, MatchError, ReplyTarget, RuntimeError, UndefinedFunctionError,
 };
-use erltf::{Atom, ExternalPid, OwnedTerm};
-use std::collections::BTreeMap;
+use erltf::{Atom, ExternalPid, ExternalReference, OwnedTerm};
+use std::collections::{BTreeMap, BTreeSet};
 
 #[test]
 fn keyword_list_basic() {
@@ -43,6 +43,114 @@ fn keyword_list_with_flags() {
     assert_eq!(kw.len(), 2);
 }
 
+fn keyword(pairs: &[(&str, i64)]) -> KeywordList {
+    let mut kw = KeywordList::new();
+    for (key, value) in pairs {
+        kw.push(key, *value);
+    }
+    kw
+}
+
+#[test]
+fn keyword_list_get_returns_first_match() {
+    let kw = keyword(&[("a", 1), ("b", 2), ("a", 3)]);
+
+    assert_eq!(kw.get("a"), Some(&OwnedTerm::integer(1)));
+    assert_eq!(kw.get("c"), None);
+    assert_eq!(
+        kw.get_values("a"),
+        vec![&OwnedTerm::integer(1), &OwnedTerm::integer(3)]
+    );
+    assert!(kw.get_values("c").is_empty());
+    assert!(kw.has_key("b"));
+}
+
+#[test]
+fn keyword_list_put_replaces_all_entries_at_the_front() {
+    let mut kw = keyword(&[("a", 1), ("b", 2), ("a", 3)]);
+    kw.put("a", 4i64);
+
+    assert_eq!(kw, keyword(&[("a", 4), ("b", 2)]));
+}
+
+#[test]
+fn keyword_list_delete_removes_duplicates() {
+    let mut kw = keyword(&[("a", 1), ("b", 2), ("a", 3)]);
+
+    assert!(kw.delete("a"));
+    assert!(!kw.delete("a"));
+    assert_eq!(kw, keyword(&[("b", 2)]));
+}
+
+#[test]
+fn keyword_list_merge_gives_precedence_to_the_right() {
+    let left = keyword(&[("a", 1), ("b", 2), ("a", 3), ("c", 4)]);
+    let right = keyword(&[("a", 5), ("d", 6), ("a", 7)]);
+
+    assert_eq!(
+        left.merge(right),
+        keyword(&[("b", 2), ("c", 4), ("a", 5), ("d", 6), ("a", 7)])
+    );
+}
+
+#[test]
+fn keyword_list_dedup_keeps_first_entries() {
+    let mut kw = keyword(&[("a", 1), ("b", 2), ("a", 3), ("b", 4), ("c", 5)]);
+    kw.dedup();
+
+    assert_eq!(kw, keyword(&[("a", 1), ("b", 2), ("c", 5)]));
+}
+
+#[test]
+fn keyword_list_validate() {
+    let kw = keyword(&[("timeout", 1), ("bogus", 2), ("retries", 3), ("bogus", 4)]);
+
+    assert_eq!(kw.validate(&["timeout", "retries", "bogus"]), Ok(()));
+    assert_eq!(
+        kw.validate(&["timeout", "retries"]),
+        Err(vec![Atom::new("bogus")])
+    );
+    assert_eq!(KeywordList::new().validate(&[]), Ok(()));
+}
+
+#[test]
+fn keyword_list_term_roundtrip_preserves_order_and_duplicates() {
+    let kw = keyword(&[("b", 1), ("a", 2), ("b", 3)]);
+    let term: OwnedTerm = kw.clone().into();
+
+    assert!(term.is_proplist());
+    assert_eq!(KeywordList::from_term(&term), Some(kw));
+}
+
+#[test]
+fn keyword_list_from_builder_term() {
+    let term = KeywordListBuilder::new()
+        .put("name", "worker")
+        .put_flag("verbose")
+        .build();
+    let kw = KeywordList::from_term(&term).unwrap();
+
+    assert_eq!(kw.len(), 2);
+    assert_eq!(kw.get("verbose"), Some(&OwnedTerm::boolean(true)));
+}
+
+#[test]
+fn keyword_list_from_term_rejects_non_keyword_lists() {
+    assert_eq!(
+        KeywordList::from_term(&OwnedTerm::Nil),
+        Some(KeywordList::new())
+    );
+    assert!(KeywordList::from_term(&OwnedTerm::atom("a")).is_none());
+    assert!(
+        KeywordList::from_term(&OwnedTerm::list(vec![OwnedTerm::tuple(vec![
+            OwnedTerm::binary(b"a".to_vec()),
+            OwnedTerm::integer(1),
+        ])]))
+        .is_none()
+    );
+    assert!(KeywordList::from_term(&OwnedTerm::list(vec![OwnedTerm::atom("flag")])).is_none());
+}
+
 #[test]
 fn atom_key_map_basic() {
     let map = AtomKeyMapBuilder::new()
@@ -291,6 +399,118 @@ fn mapset_disjoint() {
     assert!(!set1.is_disjoint(&set3));
 }
 
+#[test]
+fn mapset_to_term_uses_map_with_version() {
+    let term: OwnedTerm = ElixirMapSet::from_values([1i64, 2]).into();
+
+    let inner = term.map_get(&OwnedTerm::atom("map")).unwrap();
+    assert_eq!(
+        inner,
+        &OwnedTerm::map(BTreeMap::from([
+            (OwnedTerm::integer(1), OwnedTerm::List(vec![])),
+            (OwnedTerm::integer(2), OwnedTerm::List(vec![])),
+        ]))
+    );
+    assert_eq!(
+        term.map_get(&OwnedTerm::atom("version")),
+        Some(&OwnedTerm::integer(2))
+    );
+}
+
+#[test]
+fn mapset_from_term_without_version() {
+    let term = OwnedTerm::map(BTreeMap::from([
+        (
+            OwnedTerm::atom("__struct__"),
+            OwnedTerm::atom("Elixir.MapSet"),
+        ),
+        (
+            OwnedTerm::atom("map"),
+            OwnedTerm::map(BTreeMap::from([(
+                OwnedTerm::atom("a"),
+                OwnedTerm::List(vec![]),
+            )])),
+        ),
+    ]));
+
+    let set = ElixirMapSet::from_term(&term).unwrap();
+    assert_eq!(set.len(), 1);
+    assert!(set.contains(&OwnedTerm::atom("a")));
+}
+
+#[test]
+fn mapset_from_term_with_sets_tuple() {
+    let term = OwnedTerm::map(BTreeMap::from([
+        (
+            OwnedTerm::atom("__struct__"),
+            OwnedTerm::atom("Elixir.MapSet"),
+        ),
+        (
+            OwnedTerm::atom("map"),
+            OwnedTerm::tuple(vec![
+                OwnedTerm::atom("set"),
+                OwnedTerm::integer(1),
+                OwnedTerm::map(BTreeMap::from([(
+                    OwnedTerm::integer(7),
+                    OwnedTerm::List(vec![]),
+                )])),
+            ]),
+        ),
+    ]));
+
+    let set = ElixirMapSet::from_term(&term).unwrap();
+    assert_eq!(set, ElixirMapSet::from_values([7i64]));
+}
+
+#[test]
+fn mapset_lazy_set_operations() {
+    let set1 = ElixirMapSet::from_values([1i64, 2, 3]);
+    let set2 = ElixirMapSet::from_values([2i64, 3, 4]);
+
+    let union: Vec<_> = set1.union_iter(&set2).cloned().collect();
+    assert_eq!(union, (1..=4).map(OwnedTerm::integer).collect::<Vec<_>>());
+    assert_eq!(set1.intersection_iter(&set2).count(), 2);
+    assert_eq!(
+        set1.difference_iter(&set2).collect::<Vec<_>>(),
+        vec![&OwnedTerm::integer(1)]
+    );
+    assert_eq!(set1.symmetric_difference_iter(&set2).count(), 2);
+}
+
+#[test]
+fn mapset_union_is_symmetric() {
+    let small = ElixirMapSet::from_values([5i64]);
+    let large = ElixirMapSet::from_values([1i64, 2, 3]);
+
+    assert_eq!(small.union(&large), large.union(&small));
+    assert_eq!(small.union(&large).len(), 4);
+}
+
+#[test]
+fn mapset_retain() {
+    let mut set = ElixirMapSet::from_values([1i64, 2, 3, 4]);
+    set.retain(|t| t.as_integer().is_some_and(|i| i % 2 == 0));
+
+    assert_eq!(set, ElixirMapSet::from_values([2i64, 4]));
+}
+
+#[test]
+fn mapset_extend() {
+    let mut set = ElixirMapSet::from_values([1i64]);
+    set.extend([1i64, 2, 3]);
+
+    assert_eq!(set.len(), 3);
+}
+
+#[test]
+fn mapset_btree_set_conversions() {
+    let elements = BTreeSet::from([OwnedTerm::atom("a"), OwnedTerm::atom("b")]);
+    let set = ElixirMapSet::from(elements.clone());
+
+    assert_eq!(set.as_btree_set(), &elements);
+    assert_eq!(BTreeSet::from(set), elements);
+}
+
 #[test]
 fn mapset_clear() {
     let mut set = ElixirMapSet::from_values([1i64, 2, 3]);
@@ -579,6 +799,91 @@ fn parse_gen_call_from() {
     assert!(ref_term.is_atom_with_name("ref123"));
 }
 
+fn test_alias() -> ExternalReference {
+    ExternalReference::new(Atom::new("test@localhost"), 0, vec![1, 2, 3])
+}
+
+#[test]
+fn gen_call_with_alias_message() {
+    let alias = OwnedTerm::Reference(test_alias());
+    let msg = GenServerTerms::gen_call_with_alias(
+        OwnedTerm::Pid(test_pid()),
+        alias.clone(),
+        OwnedTerm::atom("ping"),
+    );
+    assert!(GenServerTerms::is_gen_call(&msg));
+
+    let (from, request) = GenServerTerms::parse_gen_call(&msg).unwrap();
+    assert!(request.is_atom_with_name("ping"));
+    let (pid, tag) = GenServerTerms::parse_from(from).unwrap();
+    assert_eq!(pid, &test_pid());
+    assert_eq!(
+        tag,
+        &OwnedTerm::improper_list(vec![OwnedTerm::atom("alias")], alias)
+    );
+    assert_eq!(GenServerTerms::parse_alias_tag(tag), Some(&test_alias()));
+}
+
+#[test]
+fn parse_alias_tag_rejects_other_tags() {
+    let reference = OwnedTerm::Reference(test_alias());
+    assert!(GenServerTerms::parse_alias_tag(&reference).is_none());
+
+    let wrong_marker = OwnedTerm::improper_list(vec![OwnedTerm::atom("other")], reference.clone());
+    assert!(GenServerTerms::parse_alias_tag(&wrong_marker).is_none());
+
+    let not_a_reference = GenServerTerms::alias_tag(OwnedTerm::atom("ref"));
+    assert!(GenServerTerms::parse_alias_tag(&not_a_reference).is_none());
+}
+
+#[test]
+fn reply_target_for_pid_and_alias_callers() {
+    let msg = GenServerTerms::gen_call(
+        OwnedTerm::Pid(test_pid()),
+        OwnedTerm::Reference(test_alias()),
+        OwnedTerm::atom("ping"),
+    );
+    let (from, _) = GenServerTerms::parse_gen_call(&msg).unwrap();
+    let pid = test_pid();
+    assert_eq!(
+        GenServerTerms::reply_target(from),
+        Some(ReplyTarget::Pid(&pid))
+    );
+
+    let msg = GenServerTerms::gen_call_with_alias(
+        OwnedTerm::Pid(test_pid()),
+        OwnedTerm::Reference(test_alias()),
+        OwnedTerm::atom("ping"),
+    );
+    let (from, _) = GenServerTerms::parse_gen_call(&msg).unwrap();
+    let alias = test_alias();
+    assert_eq!(
+        GenServerTerms::reply_target(from),
+        Some(ReplyTarget::Alias(&alias))
+    );
+
+    assert!(GenServerTerms::reply_target(&OwnedTerm::atom("nope")).is_none());
+}
+
+#[test]
+fn gen_reply_keeps_the_alias_tag() {
+    let msg = GenServerTerms::gen_call_with_alias(
+        OwnedTerm::Pid(test_pid()),
+        OwnedTerm::Reference(test_alias()),
+        OwnedTerm::atom("ping"),
+    );
+    let (from, _) = GenServerTerms::parse_gen_call(&msg).unwrap();
+
+    let reply = GenServerTerms::gen_reply(from, OwnedTerm::atom("pong")).unwrap();
+    assert_eq!(
+        reply,
+        OwnedTerm::tuple(vec![
+            GenServerTerms::alias_tag(OwnedTerm::Reference(test_alias())),
+            OwnedTerm::atom("pong"),
+        ])
+    );
+}
+
 #[test]
 fn date_validation_rejects_invalid() {
     assert!(ElixirDate::try_new(2025, 0, 15).is_none());
@@ -798,6 +1103,62 @@ fn atom_key_map_extend() {
     assert_eq!(map.len(), 2);
 }
 
+#[test]
+fn atom_key_map_insert_map() {
+    let map = AtomKeyMapBuilder::new()
+        .insert("name", "worker")
+        .insert_map("opts", |b| {
+            b.insert("timeout", 5000i64)
+                .insert_map("retry", |b| b.insert("max", 3i64))
+        })
+        .build();
+
+    let opts = map.map_get(&OwnedTerm::atom("opts")).unwrap();
+    assert_eq!(
+        opts.map_get(&OwnedTerm::atom("timeout")),
+        Some(&OwnedTerm::integer(5000))
+    );
+    let retry = opts.map_get(&OwnedTerm::atom("retry")).unwrap();
+    assert_eq!(
+        retry.map_get(&OwnedTerm::atom("max")),
+        Some(&OwnedTerm::integer(3))
+    );
+}
+
+#[test]
+fn atom_key_map_insert_list() {
+    let map = AtomKeyMapBuilder::new()
+        .insert_list("tags", ["a", "b"])
+        .insert_list("empty", Vec::<i64>::new())
+        .build();
+
+    assert_eq!(
+        map.map_get(&OwnedTerm::atom("tags")),
+        Some(&OwnedTerm::List(vec![
+            OwnedTerm::from("a"),
+            OwnedTerm::from("b")
+        ]))
+    );
+    assert_eq!(
+        map.map_get(&OwnedTerm::atom("empty")),
+        Some(&OwnedTerm::List(vec![]))
+    );
+}
+
+#[test]
+fn atom_key_map_insert_keyword() {
+    let map = AtomKeyMapBuilder::new()
+        .insert_keyword("opts", |kw| kw.put("timeout", 5000i64).put_flag("verbose"))
+        .build_struct("Worker");
+
+    let opts = map.map_get(&OwnedTerm::atom("opts")).unwrap();
+    assert!(opts.is_proplist());
+    assert_eq!(
+        KeywordList::from_term(opts).unwrap().get("verbose"),
+        Some(&OwnedTerm::boolean(true))
+    );
+}
+
 #[test]
 fn keyword_list_is_empty() {
     let builder = KeywordListBuilder::new();
@@ -1182,7 +1543,7 @@ fn datetime_with_timezone_roundtrip() {
 #[test]
 fn date_negative_year() {
     let date = ElixirDate::new(-500, 3, 15);
-    let term: OwnedTerm = date.into();
+    let term: OwnedTerm = date.clone().into();
     let parsed = ElixirDate::from_term(&term).unwrap();
 
     assert_eq!(parsed.year, -500);
@@ -1252,3 +1613,291 @@ fn datetime_to_naive() {
     assert_eq!(naive.hour, 14);
     assert_eq!(naive.microsecond_value, 123456);
 }
+
+#[test]
+fn range_len_and_contains_do_not_overflow() {
+    let full = ElixirRange::new(i64::MIN, i64::MAX, 1);
+    assert_eq!(full.len_u128(), 1u128 << 64);
+    assert_eq!(full.len(), usize::MAX);
+    assert!(full.contains(i64::MIN));
+    assert!(full.contains(i64::MAX));
+    assert!(!full.contains_i128(i128::from(i64::MAX) + 1));
+
+    let wide_step = ElixirRange::new(i64::MAX, i64::MIN, i64::MIN);
+    assert_eq!(wide_step.len(), 2);
+    assert!(wide_step.contains(i64::MAX));
+    assert!(wide_step.contains(-1));
+    assert!(!wide_step.contains(i64::MIN));
+}
+
+#[test]
+fn range_iterator_does_not_overshoot_an_unaligned_last() {
+    let range = ElixirRange::new(i64::MAX - 3, i64::MAX, 2);
+    let values: Vec<i64> = range.into_iter().collect();
+    assert_eq!(values, vec![i64::MAX - 3, i64::MAX - 1]);
+}
+
+#[test]
+fn range_iterator_is_double_ended() {
+    let range = ElixirRange::new(1, 10, 3);
+    let values: Vec<i64> = range.into_iter().rev().collect();
+    assert_eq!(values, vec![10, 7, 4, 1]);
+
+    let mut iter = ElixirRange::ascending(1, 4).into_iter();
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(4));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.collect::<Vec<_>>(), vec![2, 3]);
+}
+
+#[test]
+fn range_to_std_range() {
+    assert_eq!(ElixirRange::ascending(1, 5).to_std_range(), Some(1..=5));
+    assert_eq!(ElixirRange::new(1, 5, 2).to_std_range(), None);
+    assert_eq!(ElixirRange::descending(5, 1).to_std_range(), None);
+    assert_eq!(ElixirRange::from(3..=7), ElixirRange::ascending(3, 7));
+}
+
+#[test]
+fn date_add_days() {
+    let date = ElixirDate::new(2000, 1, 3);
+    assert_eq!(date.add_days(-2), Some(ElixirDate::new(2000, 1, 1)));
+    assert_eq!(date.add_days(0), Some(date.clone()));
+    assert_eq!(
+        ElixirDate::new(2024, 2, 28).add_days(1),
+        Some(ElixirDate::new(2024, 2, 29))
+    );
+    assert_eq!(
+        ElixirDate::new(2023, 12, 31).add_days(366),
+        Some(ElixirDate::new(2024, 12, 31))
+    );
+    assert_eq!(
+        ElixirDate::new(0, 1, 1).add_days(-1),
+        Some(ElixirDate::new(-1, 12, 31))
+    );
+    assert_eq!(ElixirDate::new(i32::MAX, 12, 31).add_days(1), None);
+    assert_eq!(date.add_days(i64::MAX), None);
+}
+
+#[test]
+fn date_diff_days() {
+    let a = ElixirDate::new(2000, 1, 3);
+    let b = ElixirDate::new(2000, 1, 1);
+    assert_eq!(a.diff_days(&b), 2);
+    assert_eq!(b.diff_days(&a), -2);
+    assert_eq!(
+        ElixirDate::new(2025, 1, 1).diff_days(&ElixirDate::new(1970, 1, 1)),
+        20089
+    );
+}
+
+#[test]
+fn date_days_since_epoch_roundtrip() {
+    assert_eq!(ElixirDate::new(1970, 1, 1).to_days_since_epoch(), 0);
+    assert_eq!(ElixirDate::new(1969, 12, 31).to_days_since_epoch(), -1);
+    for days in (-800_000..800_000).step_by(97) {
+        let date = ElixirDate::from_days_since_epoch(days).unwrap();
+        assert!(ElixirDate::try_new(date.year, date.month, date.day).is_some());
+        assert_eq!(date.to_days_since_epoch(), days);
+    }
+}
+
+#[test]
+fn date_day_of_week() {
+    assert_eq!(ElixirDate::new(2016, 10, 31).day_of_week(), 1);
+    assert_eq!(ElixirDate::new(2016, 11, 1).day_of_week(), 2);
+    assert_eq!(ElixirDate::new(1970, 1, 1).day_of_week(), 4);
+    assert_eq!(ElixirDate::new(2016, 11, 6).day_of_week(), 7);
+    assert_eq!(ElixirDate::new(-1, 12, 31).day_of_week(), 5);
+}
+
+#[test]
+fn date_iso_week() {
+    assert_eq!(ElixirDate::new(2005, 1, 1).iso_week(), (2004, 53));
+    assert_eq!(ElixirDate::new(2007, 12, 31).iso_week(), (2008, 1));
+    assert_eq!(ElixirDate::new(2008, 12, 29).iso_week(), (2009, 1));
+    assert_eq!(ElixirDate::new(2010, 1, 3).iso_week(), (2009, 53));
+    assert_eq!(ElixirDate::new(2025, 6, 15).iso_week(), (2025, 24));
+}
+
+#[test]
+fn naive_datetime_calendar_arithmetic() {
+    let ndt = ElixirNaiveDateTime::new(2024, 2, 28, 23, 30, 15, 500_000, 6);
+    let next = ndt.add_days(1).unwrap();
+    assert_eq!(
+        next,
+        ElixirNaiveDateTime::new(2024, 2, 29, 23, 30, 15, 500_000, 6)
+    );
+    assert_eq!(
+        ndt.add_seconds(3600).unwrap(),
+        ElixirNaiveDateTime::new(2024, 2, 29, 0, 30, 15, 500_000, 6)
+    );
+    assert_eq!(next.day_of_week(), 4);
+    assert_eq!(next.iso_week(), (2024, 9));
+
+    let later = ElixirNaiveDateTime::new(2014, 10, 4, 0, 0, 0, 0, 0);
+    let earlier = ElixirNaiveDateTime::new(2014, 10, 2, 0, 0, 1, 0, 0);
+    assert_eq!(later.diff_days(&earlier), 1);
+    assert_eq!(earlier.diff_days(&later), -1);
+}
+
+#[test]
+fn naive_datetime_unix_roundtrip() {
+    let ndt = ElixirNaiveDateTime::new(2016, 5, 24, 13, 26, 8, 0, 0);
+    assert_eq!(ndt.to_unix(), 1_464_096_368);
+    assert_eq!(ElixirNaiveDateTime::from_unix(1_464_096_368), Some(ndt));
+
+    let before_epoch = ElixirNaiveDateTime::from_unix(-1).unwrap();
+    assert_eq!(
+        before_epoch,
+        ElixirNaiveDateTime::new(1969, 12, 31, 23, 59, 59, 0, 0)
+    );
+    assert_eq!(before_epoch.to_unix(), -1);
+    assert_eq!(ElixirNaiveDateTime::from_unix(i64::MAX), None);
+}
+
+#[test]
+fn datetime_unix_roundtrip() {
+    let utc = ElixirDateTime::utc(2016, 5, 24, 13, 26, 8, 3000, 6);
+    assert_eq!(utc.to_unix(), 1_464_096_368);
+    assert_eq!(
+        ElixirDateTime::from_unix(1_464_096_368),
+        Some(ElixirDateTime::utc(2016, 5, 24, 13, 26, 8, 0, 0))
+    );
+
+    let warsaw =
+        ElixirDateTime::with_timezone(2000, 2, 29, 23, 0, 7, 0, 0, "Europe/Warsaw", "CET", 3600, 0);
+    assert_eq!(warsaw.to_unix(), 951_861_607);
+}
+
+fn struct_term(module: &str, fields: &[(&str, OwnedTerm)]) -> OwnedTerm {
+    let mut map = BTreeMap::new();
+    map.insert(
+        OwnedTerm::Atom(Atom::new("__struct__")),
+        OwnedTerm::Atom(Atom::new(module)),
+    );
+    for (key, value) in fields {
+        map.insert(OwnedTerm::Atom(Atom::new(key)), value.clone());
+    }
+    OwnedTerm::Map(map)
+}
+
+#[test]
+fn date_preserves_unknown_fields() {
+    let term = struct_term(
+        "Elixir.Date",
+        &[
+            ("year", OwnedTerm::Integer(2025)),
+            ("month", OwnedTerm::Integer(12)),
+            ("day", OwnedTerm::Integer(25)),
+            (
+                "calendar",
+                OwnedTerm::Atom(Atom::new("Elixir.Calendar.ISO")),
+            ),
+            ("era", OwnedTerm::Atom(Atom::new("ce"))),
+        ],
+    );
+
+    let date = ElixirDate::from_term(&term).unwrap();
+    assert_eq!(date.extra_fields.len(), 1);
+    assert_eq!(
+        date.extra_fields.get(&OwnedTerm::Atom(Atom::new("era"))),
+        Some(&OwnedTerm::Atom(Atom::new("ce")))
+    );
+
+    let reencoded: OwnedTerm = date.into();
+    assert_eq!(reencoded, term);
+}
+
+#[test]
+fn datetime_preserves_unknown_fields() {
+    let original = ElixirDateTime::utc(2025, 6, 15, 12, 30, 0, 0, 0);
+    let mut term: OwnedTerm = original.clone().into();
+    if let OwnedTerm::Map(map) = &mut term {
+        map.insert(
+            OwnedTerm::Atom(Atom::new("leap_second")),
+            OwnedTerm::Atom(Atom::new("false")),
+        );
+        map.insert(
+            OwnedTerm::Binary(b"binary key".to_vec()),
+            OwnedTerm::Integer(1),
+        );
+    }
+
+    let decoded = ElixirDateTime::from_term(&term).unwrap();
+    assert_eq!(decoded.extra_fields.len(), 2);
+    assert_eq!(decoded.to_naive(), original.to_naive());
+
+    let reencoded: OwnedTerm = decoded.into();
+    assert_eq!(reencoded, term);
+}
+
+#[test]
+fn time_and_naive_datetime_preserve_unknown_fields() {
+    let mut time_term: OwnedTerm = ElixirTime::hms(10, 0, 0).into();
+    let mut naive_term: OwnedTerm = ElixirNaiveDateTime::new(2025, 1, 1, 10, 0, 0, 0, 0).into();
+    for term in [&mut time_term, &mut naive_term] {
+        if let OwnedTerm::Map(map) = term {
+            map.insert(
+                OwnedTerm::Atom(Atom::new("future_field")),
+                OwnedTerm::Integer(42),
+            );
+        }
+    }
+
+    let time = ElixirTime::from_term(&time_term).unwrap();
+    assert_eq!(time.extra_fields.len(), 1);
+    assert_eq!(OwnedTerm::from(time), time_term);
+
+    let naive = ElixirNaiveDateTime::from_term(&naive_term).unwrap();
+    assert_eq!(naive.extra_fields.len(), 1);
+    assert_eq!(OwnedTerm::from(naive), naive_term);
+}
+
+#[test]
+fn date_time_structs_without_unknown_fields_have_none() {
+    let term: OwnedTerm = ElixirDate::new(2025, 12, 25).into();
+    assert!(
+        ElixirDate::from_term(&term)
+            .unwrap()
+            .extra_fields
+            .is_empty()
+    );
+
+    let term: OwnedTerm = ElixirDateTime::utc(2025, 6, 15, 12, 30, 0, 0, 0).into();
+    assert!(
+        ElixirDateTime::from_term(&term)
+            .unwrap()
+            .extra_fields
+            .is_empty()
+    );
+}
+
+#[test]
+fn date_time_structs_reject_other_calendars() {
+    let fields = [
+        ("year", OwnedTerm::Integer(2025)),
+        ("month", OwnedTerm::Integer(12)),
+        ("day", OwnedTerm::Integer(25)),
+        (
+            "calendar",
+            OwnedTerm::Atom(Atom::new("Elixir.Calendar.Holocene")),
+        ),
+    ];
+    assert!(ElixirDate::from_term(&struct_term("Elixir.Date", &fields)).is_none());
+
+    let mut time: OwnedTerm = ElixirTime::hms(10, 0, 0).into();
+    if let OwnedTerm::Map(map) = &mut time {
+        map.insert(
+            OwnedTerm::Atom(Atom::new("calendar")),
+            OwnedTerm::Atom(Atom::new("Elixir.Calendar.Holocene")),
+        );
+    }
+    assert!(ElixirTime::from_term(&time).is_none());
+
+    let without_calendar = struct_term("Elixir.Date", &fields[..3]);
+    assert_eq!(
+        ElixirDate::from_term(&without_calendar),
+        Some(ElixirDate::new(2025, 12, 25))
+    );
+}
@@ -0,0 +1,48 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "rayon")]
+
+use edp_elixir_terms::ElixirRange;
+use rayon::iter::ParallelIterator;
+
+#[test]
+fn range_par_iter_yields_every_element() {
+    let range = ElixirRange::new(-1_000, 100_000, 7);
+    let mut values: Vec<i64> = range.into_par_iter().collect();
+    values.sort_unstable();
+    assert_eq!(values, range.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn range_par_iter_sum() {
+    let range = ElixirRange::descending(1_000_000, 1);
+    assert_eq!(range.into_par_iter().sum::<i64>(), 500_000_500_000);
+}
+
+#[test]
+fn range_par_iter_near_the_bounds() {
+    let range = ElixirRange::new(i64::MAX - 10_000, i64::MAX, 3);
+    let mut values: Vec<i64> = range.into_par_iter().collect();
+    values.sort_unstable();
+    assert_eq!(values, range.into_iter().collect::<Vec<_>>());
+
+    let range = ElixirRange::new(i64::MIN + 10_000, i64::MIN, -3);
+    assert_eq!(range.into_par_iter().count(), range.len());
+}
+
+#[test]
+fn range_par_iter_of_an_empty_range() {
+    assert_eq!(ElixirRange::new(1, 10, 0).into_par_iter().count(), 0);
+}
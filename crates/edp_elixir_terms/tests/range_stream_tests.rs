@@ -0,0 +1,41 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "stream")]
+
+use edp_elixir_terms::ElixirRange;
+use futures_core::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+fn collect<S: Stream<Item = i64> + Unpin>(mut stream: S) -> Vec<i64> {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut values = Vec::new();
+    while let Poll::Ready(Some(value)) = Pin::new(&mut stream).poll_next(&mut cx) {
+        values.push(value);
+    }
+    values
+}
+
+#[test]
+fn range_stream_yields_every_element() {
+    let stream = ElixirRange::new(1, 10, 3).into_stream();
+    assert_eq!(stream.size_hint(), (4, Some(4)));
+    assert_eq!(collect(stream), vec![1, 4, 7, 10]);
+}
+
+#[test]
+fn range_stream_of_an_empty_range() {
+    assert!(collect(ElixirRange::new(10, 1, 1).into_stream()).is_empty());
+}
@@ -0,0 +1,108 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![cfg(feature = "tz")]
+
+use edp_elixir_terms::ElixirDateTime;
+
+#[test]
+fn shift_zone_to_daylight_saving_time() {
+    let utc = ElixirDateTime::utc(2018, 7, 16, 10, 0, 0, 0, 0);
+    let shifted = utc.shift_zone("America/Los_Angeles").unwrap();
+
+    assert_eq!(
+        shifted,
+        ElixirDateTime::with_timezone(
+            2018,
+            7,
+            16,
+            3,
+            0,
+            0,
+            0,
+            0,
+            "America/Los_Angeles",
+            "PDT",
+            -28800,
+            3600
+        )
+    );
+    assert_eq!(shifted.to_unix(), utc.to_unix());
+}
+
+#[test]
+fn shift_zone_to_standard_time() {
+    let utc = ElixirDateTime::utc(2000, 2, 29, 22, 0, 7, 123_456, 6);
+    let shifted = utc.shift_zone("Europe/Warsaw").unwrap();
+
+    assert_eq!(
+        shifted,
+        ElixirDateTime::with_timezone(
+            2000,
+            2,
+            29,
+            23,
+            0,
+            7,
+            123_456,
+            6,
+            "Europe/Warsaw",
+            "CET",
+            3600,
+            0
+        )
+    );
+}
+
+#[test]
+fn shift_zone_across_a_date_boundary_and_back_to_utc() {
+    let tokyo = ElixirDateTime::utc(2025, 12, 31, 20, 30, 0, 0, 0)
+        .shift_zone("Asia/Tokyo")
+        .unwrap();
+    assert_eq!(
+        (tokyo.year, tokyo.month, tokyo.day, tokyo.hour),
+        (2026, 1, 1, 5)
+    );
+    assert_eq!(
+        (tokyo.zone_abbr.as_str(), tokyo.utc_offset, tokyo.std_offset),
+        ("JST", 32400, 0)
+    );
+
+    let utc = tokyo.shift_zone("Etc/UTC").unwrap();
+    assert_eq!(utc, ElixirDateTime::utc(2025, 12, 31, 20, 30, 0, 0, 0));
+}
+
+#[test]
+fn shift_zone_rejects_unknown_zones() {
+    let utc = ElixirDateTime::utc(2025, 6, 15, 12, 0, 0, 0, 0);
+    assert_eq!(utc.shift_zone("Mars/Olympus_Mons"), None);
+}
+
+#[test]
+fn shift_zone_to_a_zone_with_numeric_abbreviations() {
+    let dubai = ElixirDateTime::utc(2025, 6, 15, 12, 0, 0, 0, 0)
+        .shift_zone("Asia/Dubai")
+        .unwrap();
+    assert_eq!(dubai.zone_abbr, "+04");
+    let kathmandu = ElixirDateTime::utc(2025, 6, 15, 12, 0, 0, 0, 0)
+        .shift_zone("Asia/Kathmandu")
+        .unwrap();
+    assert_eq!(kathmandu.zone_abbr, "+0545");
+    let sao_paulo = ElixirDateTime::utc(2025, 6, 15, 12, 0, 0, 0, 0)
+        .shift_zone("America/Sao_Paulo")
+        .unwrap();
+    assert_eq!(
+        (sao_paulo.zone_abbr.as_str(), sao_paulo.utc_offset),
+        ("-03", -10800)
+    );
+}
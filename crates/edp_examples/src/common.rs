@@ -15,9 +15,12 @@
 //! Common utilities shared across examples.
 
 use anyhow::{Context, Result};
+use edp_node::{Message, Process};
+use erltf::OwnedTerm;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
+use tokio::sync::mpsc;
 
 /// Reads the Erlang cookie from ~/.erlang.cookie
 pub fn get_erlang_cookie() -> Result<String> {
@@ -52,3 +55,24 @@ pub fn build_client_node_name(prefix: &str, remote_host: &str) -> String {
     let hostname = get_hostname(use_long_names).unwrap_or_else(|_| "localhost".to_string());
     format!("{}@{}", prefix, hostname)
 }
+
+/// A process that forwards the body of every regular message it receives to a channel.
+pub struct Forwarder {
+    tx: mpsc::UnboundedSender<OwnedTerm>,
+}
+
+impl Forwarder {
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<OwnedTerm>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self { tx }, rx)
+    }
+}
+
+impl Process for Forwarder {
+    async fn handle_message(&mut self, msg: Message) -> edp_node::Result<()> {
+        if let Message::Regular { body, .. } = msg {
+            let _ = self.tx.send(body);
+        }
+        Ok(())
+    }
+}
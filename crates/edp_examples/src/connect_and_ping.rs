@@ -0,0 +1,54 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Connects to a node and pings it, like `Node.ping/1` in Elixir.
+//!
+//! Without arguments, the example talks to an in-process loopback peer. To use a real node,
+//! start one and pass its name:
+//!
+//! ```shell
+//! iex --sname demo --cookie monster
+//! ERLANG_COOKIE=monster cargo run --package edp_examples --bin example_connect_and_ping demo@$(hostname -s)
+//! ```
+
+use anyhow::{Context, Result};
+use edp_examples::loopback::Peer;
+
+const PINGS: usize = 3;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let peer = Peer::from_args().await?;
+    let node = peer.start_node("rust_ping").await?;
+    peer.connect(&node).await?;
+    println!("Connected to {}", peer.name());
+
+    for _ in 0..PINGS {
+        let rtt = node.ping(peer.name()).await.context("Ping failed")?;
+        println!("pong from {} in {:?}", peer.name(), rtt);
+    }
+
+    let metrics = node
+        .connection_metrics(peer.name())
+        .await
+        .context("The connection is gone")?;
+    if let Some(summary) = metrics.rtt_summary() {
+        println!(
+            "RTT over {} samples: min {:?}, p50 {:?}, max {:?}",
+            summary.samples, summary.min, summary.p50, summary.max
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Calls a registered `GenServer` on another node, like `GenServer.call({:counter, node}, :increment)`.
+//!
+//! Without arguments, the example talks to an in-process loopback peer. To use a real node,
+//! start one, define and start the counter, then pass the node name:
+//!
+//! ```elixir
+//! # iex --sname demo --cookie monster
+//! defmodule Counter do
+//!   use GenServer
+//!
+//!   def start_link(n), do: GenServer.start_link(__MODULE__, n, name: :counter)
+//!
+//!   @impl true
+//!   def init(n), do: {:ok, n}
+//!
+//!   @impl true
+//!   def handle_call(:increment, _from, n), do: {:reply, n + 1, n + 1}
+//!   def handle_call(:get, _from, n), do: {:reply, n, n}
+//! end
+//!
+//! Counter.start_link(0)
+//! ```
+//!
+//! ```shell
+//! ERLANG_COOKIE=monster cargo run --package edp_examples --bin example_genserver_call demo@$(hostname -s)
+//! ```
+
+use anyhow::{Context, Result};
+use edp_examples::loopback::Peer;
+use edp_node::{CallTable, Message, Node, Process};
+use erltf::types::{Atom, ExternalPid};
+use erltf::OwnedTerm;
+use std::time::Duration;
+
+const CALL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Hands every reply to the call table.
+struct Caller {
+    calls: CallTable,
+}
+
+impl Process for Caller {
+    async fn handle_message(&mut self, msg: Message) -> edp_node::Result<()> {
+        if let Some(other) = self.calls.dispatch(msg) {
+            println!("Unexpected message: {:?}", other);
+        }
+        Ok(())
+    }
+}
+
+/// Sends `{'$gen_call', {Caller, Ref}, Request}` to a registered name and waits for the reply.
+async fn call(
+    node: &Node,
+    calls: &CallTable,
+    caller: &ExternalPid,
+    peer: &str,
+    name: &str,
+    request: OwnedTerm,
+) -> Result<OwnedTerm> {
+    let reference = node.make_reference();
+    let pending = calls.register(reference.clone(), CALL_TIMEOUT);
+    let message = OwnedTerm::tuple(vec![
+        OwnedTerm::atom("$gen_call"),
+        OwnedTerm::tuple(vec![
+            OwnedTerm::Pid(caller.clone()),
+            OwnedTerm::Reference(reference),
+        ]),
+        request,
+    ]);
+
+    let conn = node
+        .connections()
+        .get(peer)
+        .map(|entry| entry.value().clone())
+        .context("Not connected")?;
+    conn.lock()
+        .await
+        .send_to_name(caller.clone(), Atom::new(name), message)
+        .await?;
+
+    Ok(pending.wait().await?)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let peer = Peer::from_args().await?;
+    let node = peer.start_node("rust_genserver_call").await?;
+    peer.connect(&node).await?;
+    println!("Connected to {}", peer.name());
+
+    let calls = CallTable::new();
+    let caller = node
+        .spawn(Caller {
+            calls: calls.clone(),
+        })
+        .await?;
+
+    for _ in 0..3 {
+        let reply = call(
+            &node,
+            &calls,
+            &caller,
+            peer.name(),
+            "counter",
+            OwnedTerm::atom("increment"),
+        )
+        .await?;
+        println!("GenServer.call(:counter, :increment) => {}", reply);
+    }
+
+    let reply = call(
+        &node,
+        &calls,
+        &caller,
+        peer.name(),
+        "counter",
+        OwnedTerm::atom("get"),
+    )
+    .await?;
+    println!("GenServer.call(:counter, :get) => {}", reply);
+
+    Ok(())
+}
@@ -15,3 +15,4 @@
 //! Common utilities and test helpers for examples.
 
 pub mod common;
+pub mod loopback;
@@ -0,0 +1,354 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An in-process stand-in for an Erlang node, so that the examples run without one.
+//!
+//! A [`LoopbackPeer`] accepts one connection, completes the handshake and plays the
+//! processes the examples talk to: `net_kernel` (`is_auth` calls), a `counter` gen_server,
+//! a `pubsub` process and `rex` (`erlang:spawn/3` of an echo process and `init:stop/0`).
+
+use crate::common::{build_client_node_name, get_erlang_cookie};
+use anyhow::{bail, Context, Result};
+use edp_client::control::ControlMessage;
+use edp_client::handshake::{Challenge, ChallengeAck, ChallengeReply};
+use edp_client::protocol::PASS_THROUGH;
+use edp_client::DistributionFlags;
+use edp_node::Node;
+use erltf::decoder::decode_all;
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use erltf::OwnedTerm;
+use std::collections::HashSet;
+use std::env;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+pub const LOOPBACK_NODE: &str = "loopback@localhost";
+pub const LOOPBACK_COOKIE: &str = "loopback_cookie";
+/// The number of events the loopback `pubsub` process publishes to a new subscriber.
+pub const LOOPBACK_PUBLISHED_EVENTS: i64 = 3;
+
+const LOOPBACK_CREATION: u32 = 1;
+
+pub struct LoopbackPeer {
+    port: u16,
+    task: JoinHandle<()>,
+}
+
+impl LoopbackPeer {
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .context("Failed to bind the loopback peer")?;
+        let port = listener.local_addr()?.port();
+        let task = tokio::spawn(async move {
+            if let Err(e) = serve(listener).await {
+                tracing::debug!("Loopback peer stopped: {}", e);
+            }
+        });
+        Ok(Self { port, task })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl Drop for LoopbackPeer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// The node an example talks to.
+pub enum Peer {
+    Remote { name: String, cookie: String },
+    Loopback(LoopbackPeer),
+}
+
+impl Peer {
+    /// Uses the node named by the first command line argument, or a loopback peer
+    /// when there is none. The cookie of a named node is taken from `ERLANG_COOKIE`
+    /// or `~/.erlang.cookie`.
+    pub async fn from_args() -> Result<Self> {
+        match env::args().nth(1) {
+            Some(name) => {
+                let cookie = match env::var("ERLANG_COOKIE") {
+                    Ok(cookie) => cookie,
+                    Err(_) => get_erlang_cookie()?,
+                };
+                Ok(Peer::Remote { name, cookie })
+            }
+            None => Ok(Peer::Loopback(LoopbackPeer::start().await?)),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Peer::Remote { name, .. } => name,
+            Peer::Loopback(_) => LOOPBACK_NODE,
+        }
+    }
+
+    pub fn cookie(&self) -> &str {
+        match self {
+            Peer::Remote { cookie, .. } => cookie,
+            Peer::Loopback(_) => LOOPBACK_COOKIE,
+        }
+    }
+
+    pub fn is_loopback(&self) -> bool {
+        matches!(self, Peer::Loopback(_))
+    }
+
+    /// A local node name that shares the peer's host name style.
+    pub fn local_node_name(&self, prefix: &str) -> String {
+        match self {
+            Peer::Remote { name, .. } => {
+                build_client_node_name(prefix, name.split('@').nth(1).unwrap_or("localhost"))
+            }
+            Peer::Loopback(_) => format!("{}@localhost", prefix),
+        }
+    }
+
+    /// Creates and starts a local node to connect to the peer with.
+    pub async fn start_node(&self, prefix: &str) -> Result<Node> {
+        let mut node = Node::new(self.local_node_name(prefix), self.cookie());
+        node.start(0)
+            .await
+            .context("Failed to start the local node")?;
+        Ok(node)
+    }
+
+    pub async fn connect(&self, node: &Node) -> Result<()> {
+        match self {
+            Peer::Remote { name, .. } => node
+                .connect(name.as_str())
+                .await
+                .with_context(|| format!("Failed to connect to {}", name)),
+            Peer::Loopback(loopback) => node
+                .connect_to_port(LOOPBACK_NODE, loopback.port())
+                .await
+                .context("Failed to connect to the loopback peer"),
+        }
+    }
+}
+
+struct LoopbackState {
+    counter: i64,
+    echo_processes: HashSet<ExternalPid>,
+    next_pid_id: u32,
+}
+
+async fn serve(listener: TcpListener) -> Result<()> {
+    let (mut stream, _) = listener.accept().await?;
+    accept_handshake(&mut stream).await?;
+
+    let mut state = LoopbackState {
+        counter: 0,
+        echo_processes: HashSet::new(),
+        next_pid_id: 100,
+    };
+    loop {
+        let frame = read_frame(&mut stream).await?;
+        if frame.first() != Some(&PASS_THROUGH) {
+            bail!("Unexpected frame type: {:?}", frame.first());
+        }
+        let mut terms = decode_all(&frame[1..]);
+        let control = terms
+            .next()
+            .context("A frame without a control message")??;
+        let payload = terms.next().transpose()?;
+        let control = ControlMessage::from_term(&control)?;
+        if !handle(&mut stream, &mut state, control, payload).await? {
+            return Ok(());
+        }
+    }
+}
+
+/// Handles one message, returns `false` once the peer should shut down.
+async fn handle(
+    stream: &mut TcpStream,
+    state: &mut LoopbackState,
+    control: ControlMessage,
+    payload: Option<OwnedTerm>,
+) -> Result<bool> {
+    let Some(payload) = payload else {
+        return Ok(true);
+    };
+    match control {
+        ControlMessage::RegSend {
+            to_name: OwnedTerm::Atom(name),
+            ..
+        } => match name.as_str() {
+            "net_kernel" => {
+                if let Some((from, tag, _)) = parse_gen_call(&payload) {
+                    send(stream, from, reply(tag, OwnedTerm::atom("yes"))).await?;
+                }
+            }
+            "counter" => {
+                if let Some((from, tag, request)) = parse_gen_call(&payload) {
+                    if request.is_atom_with_name("increment") {
+                        state.counter += 1;
+                    }
+                    let value = OwnedTerm::integer(state.counter);
+                    send(stream, from, reply(tag, value)).await?;
+                }
+            }
+            "pubsub" => {
+                if let Some([tag, OwnedTerm::Pid(subscriber)]) = payload.as_tuple() {
+                    if tag.is_atom_with_name("subscribe") {
+                        for n in 1..=LOOPBACK_PUBLISHED_EVENTS {
+                            let event = OwnedTerm::tuple(vec![
+                                OwnedTerm::atom("event"),
+                                OwnedTerm::integer(n),
+                            ]);
+                            send(stream, subscriber, event).await?;
+                        }
+                    }
+                }
+            }
+            "rex" => return handle_rpc(stream, state, &payload).await,
+            _ => {}
+        },
+        ControlMessage::Send {
+            to_pid: OwnedTerm::Pid(to),
+            ..
+        }
+        | ControlMessage::SendSender {
+            to_pid: OwnedTerm::Pid(to),
+            ..
+        } if state.echo_processes.contains(&to) => {
+            if let Some([OwnedTerm::Pid(from), msg]) = payload.as_tuple() {
+                let echo = OwnedTerm::tuple(vec![OwnedTerm::Pid(to.clone()), msg.clone()]);
+                send(stream, from, echo).await?;
+            }
+        }
+        _ => {}
+    }
+    Ok(true)
+}
+
+async fn handle_rpc(
+    stream: &mut TcpStream,
+    state: &mut LoopbackState,
+    payload: &OwnedTerm,
+) -> Result<bool> {
+    let Some([OwnedTerm::Pid(from), call]) = payload.as_tuple() else {
+        return Ok(true);
+    };
+    let Some([_, OwnedTerm::Atom(module), OwnedTerm::Atom(function), _, _]) = call.as_tuple()
+    else {
+        return Ok(true);
+    };
+
+    match (module.as_str(), function.as_str()) {
+        ("erlang", "spawn") => {
+            let pid = ExternalPid::new(
+                Atom::new(LOOPBACK_NODE),
+                state.next_pid_id,
+                0,
+                LOOPBACK_CREATION,
+            );
+            state.next_pid_id += 1;
+            state.echo_processes.insert(pid.clone());
+            send(stream, from, rex_reply(OwnedTerm::Pid(pid))).await?;
+            Ok(true)
+        }
+        ("init", "stop") => {
+            send(stream, from, rex_reply(OwnedTerm::atom("ok"))).await?;
+            Ok(false)
+        }
+        _ => {
+            let undef = OwnedTerm::tuple(vec![
+                OwnedTerm::atom("badrpc"),
+                OwnedTerm::tuple(vec![OwnedTerm::atom("EXIT"), OwnedTerm::atom("undef")]),
+            ]);
+            send(stream, from, rex_reply(undef)).await?;
+            Ok(true)
+        }
+    }
+}
+
+/// Splits `{'$gen_call', {From, Tag}, Request}`.
+fn parse_gen_call(payload: &OwnedTerm) -> Option<(&ExternalPid, &OwnedTerm, &OwnedTerm)> {
+    let [call_tag, from, request] = payload.as_tuple()? else {
+        return None;
+    };
+    if !call_tag.is_atom_with_name("$gen_call") {
+        return None;
+    }
+    let [OwnedTerm::Pid(pid), tag] = from.as_tuple()? else {
+        return None;
+    };
+    Some((pid, tag, request))
+}
+
+fn reply(tag: &OwnedTerm, value: OwnedTerm) -> OwnedTerm {
+    OwnedTerm::tuple(vec![tag.clone(), value])
+}
+
+fn rex_reply(value: OwnedTerm) -> OwnedTerm {
+    OwnedTerm::tuple(vec![OwnedTerm::atom("rex"), value])
+}
+
+async fn send(stream: &mut TcpStream, to: &ExternalPid, message: OwnedTerm) -> Result<()> {
+    let control = ControlMessage::Send {
+        cookie: OwnedTerm::atom(""),
+        to_pid: OwnedTerm::Pid(to.clone()),
+    };
+    let mut frame = vec![0, 0, 0, 0, PASS_THROUGH];
+    frame.extend(encode(&control.to_term())?);
+    frame.extend(encode(&message)?);
+    let len = (frame.len() - 4) as u32;
+    frame[..4].copy_from_slice(&len.to_be_bytes());
+    stream.write_all(&frame).await?;
+    Ok(())
+}
+
+async fn accept_handshake(stream: &mut TcpStream) -> Result<()> {
+    read_handshake_message(stream).await?;
+    stream.write_all(&[0, 3, b's', b'o', b'k']).await?;
+    read_handshake_message(stream).await?;
+
+    let flags = DistributionFlags::default().difference(DistributionFlags::DIST_HDR_ATOM_CACHE);
+    let challenge = Challenge::new(flags, 42, LOOPBACK_CREATION, LOOPBACK_NODE);
+    stream.write_all(&challenge.encode()?).await?;
+    let reply = ChallengeReply::decode(&read_handshake_message(stream).await?)?;
+    stream
+        .write_all(&ChallengeAck::new(reply.challenge, LOOPBACK_COOKIE).encode())
+        .await?;
+    Ok(())
+}
+
+async fn read_handshake_message(stream: &mut TcpStream) -> Result<Vec<u8>> {
+    let len = stream.read_u16().await?;
+    let mut data = vec![0u8; len as usize];
+    stream.read_exact(&mut data).await?;
+    Ok(data)
+}
+
+/// Reads the next frame, skipping ticks.
+async fn read_frame(stream: &mut TcpStream) -> Result<Vec<u8>> {
+    loop {
+        let len = stream.read_u32().await?;
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await?;
+        return Ok(data);
+    }
+}
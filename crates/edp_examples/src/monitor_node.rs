@@ -0,0 +1,63 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Watches a node come up and go down, like `:net_kernel.monitor_nodes(true, [:nodedown_reason])`.
+//!
+//! The example stops the peer with `init:stop/0` over RPC to trigger the nodedown event.
+//! Without arguments, it talks to an in-process loopback peer. To use a real node that
+//! can be stopped, start one and pass its name:
+//!
+//! ```shell
+//! iex --sname demo --cookie monster
+//! ERLANG_COOKIE=monster cargo run --package edp_examples --bin example_monitor_node demo@$(hostname -s)
+//! ```
+
+use anyhow::{Context, Result};
+use edp_examples::loopback::Peer;
+use edp_node::NodeEvent;
+use std::time::Duration;
+use tokio::time::timeout;
+
+const EVENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let peer = Peer::from_args().await?;
+    let node = peer.start_node("rust_node_monitor").await?;
+    let mut events = node.monitor_nodes();
+    peer.connect(&node).await?;
+
+    println!("Connected to {}, stopping it", peer.name());
+    let result = node
+        .rpc_call(peer.name(), "init", "stop", vec![])
+        .await
+        .context("RPC to init:stop/0 failed")?;
+    println!("init:stop() => {}", result);
+
+    loop {
+        let event = timeout(EVENT_TIMEOUT, events.recv())
+            .await
+            .context("Timed out waiting for the nodedown event")?
+            .context("The node monitor has stopped")?;
+        match event {
+            NodeEvent::NodeUp { node } => println!("nodeup: {}", node),
+            NodeEvent::NodeDown { node, reason } => {
+                println!("nodedown: {} ({})", node, reason.as_str());
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Spawns a process on another node with `erlang:spawn/3` over RPC, then exchanges
+//! a message with it.
+//!
+//! Without arguments, the example talks to an in-process loopback peer. To use a real node,
+//! start one, define the echo loop, then pass the node name:
+//!
+//! ```elixir
+//! # iex --sname demo --cookie monster
+//! defmodule Echo do
+//!   def loop do
+//!     receive do
+//!       {from, msg} ->
+//!         send(from, {self(), msg})
+//!         loop()
+//!     end
+//!   end
+//! end
+//! ```
+//!
+//! ```shell
+//! ERLANG_COOKIE=monster cargo run --package edp_examples --bin example_remote_spawn demo@$(hostname -s)
+//! ```
+
+use anyhow::{bail, Context, Result};
+use edp_examples::common::Forwarder;
+use edp_examples::loopback::Peer;
+use erltf::OwnedTerm;
+use std::time::Duration;
+use tokio::time::timeout;
+
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let peer = Peer::from_args().await?;
+    let node = peer.start_node("rust_remote_spawn").await?;
+    peer.connect(&node).await?;
+    println!("Connected to {}", peer.name());
+
+    let spawned = node
+        .rpc_call(
+            peer.name(),
+            "erlang",
+            "spawn",
+            vec![
+                OwnedTerm::atom("Elixir.Echo"),
+                OwnedTerm::atom("loop"),
+                OwnedTerm::Nil,
+            ],
+        )
+        .await
+        .context("RPC to erlang:spawn/3 failed")?;
+    let OwnedTerm::Pid(echo) = spawned else {
+        bail!("erlang:spawn/3 returned {}", spawned);
+    };
+    println!("Spawned {} on {}", echo, peer.name());
+
+    let (forwarder, mut replies) = Forwarder::new();
+    let me = node.spawn(forwarder).await?;
+    node.send(
+        &echo,
+        OwnedTerm::tuple(vec![OwnedTerm::Pid(me), OwnedTerm::atom("hello")]),
+    )
+    .await?;
+
+    let reply = timeout(REPLY_TIMEOUT, replies.recv())
+        .await
+        .context("Timed out waiting for the echo")?
+        .context("The local process has stopped")?;
+    println!("Echo replied with {}", reply);
+
+    Ok(())
+}
@@ -101,7 +101,7 @@ async fn main() -> Result<()> {
 
     tracing::info!("Spawning counter server...");
     let counter = CounterServer::new();
-    let counter_process = GenServerProcess::new(counter, node.registry());
+    let counter_process = GenServerProcess::new(counter, &node);
     let counter_pid = node.spawn(counter_process).await?;
     tracing::info!("Counter server PID: {:?}", counter_pid);
 
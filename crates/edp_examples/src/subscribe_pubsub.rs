@@ -0,0 +1,102 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Subscribes a local process to a publisher on another node and receives the events
+//! it publishes.
+//!
+//! Without arguments, the example talks to an in-process loopback peer, which publishes
+//! three events right away. To use a real node, start one, define and start the publisher,
+//! run the example with the node name, then publish three events:
+//!
+//! ```elixir
+//! # iex --sname demo --cookie monster
+//! defmodule PubSub do
+//!   use GenServer
+//!
+//!   def start_link(_), do: GenServer.start_link(__MODULE__, [], name: :pubsub)
+//!   def publish(n), do: GenServer.cast(:pubsub, {:publish, n})
+//!
+//!   @impl true
+//!   def init(subscribers), do: {:ok, subscribers}
+//!
+//!   @impl true
+//!   def handle_info({:subscribe, pid}, subscribers), do: {:noreply, [pid | subscribers]}
+//!
+//!   @impl true
+//!   def handle_cast({:publish, n}, subscribers) do
+//!     Enum.each(subscribers, &send(&1, {:event, n}))
+//!     {:noreply, subscribers}
+//!   end
+//! end
+//!
+//! PubSub.start_link([])
+//! # once the example has subscribed
+//! Enum.each(1..3, &PubSub.publish/1)
+//! ```
+//!
+//! ```shell
+//! ERLANG_COOKIE=monster cargo run --package edp_examples --bin example_subscribe_pubsub demo@$(hostname -s)
+//! ```
+
+use anyhow::{Context, Result};
+use edp_examples::common::Forwarder;
+use edp_examples::loopback::{Peer, LOOPBACK_PUBLISHED_EVENTS};
+use erltf::types::Atom;
+use erltf::OwnedTerm;
+use std::time::Duration;
+use tokio::time::timeout;
+
+const LOOPBACK_EVENT_TIMEOUT: Duration = Duration::from_secs(5);
+const REMOTE_EVENT_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let peer = Peer::from_args().await?;
+    let node = peer.start_node("rust_subscriber").await?;
+    peer.connect(&node).await?;
+    println!("Connected to {}", peer.name());
+
+    let (forwarder, mut events) = Forwarder::new();
+    let subscriber = node.spawn(forwarder).await?;
+
+    let conn = node
+        .connections()
+        .get(peer.name())
+        .map(|entry| entry.value().clone())
+        .context("Not connected")?;
+    let subscribe = OwnedTerm::tuple(vec![
+        OwnedTerm::atom("subscribe"),
+        OwnedTerm::Pid(subscriber.clone()),
+    ]);
+    conn.lock()
+        .await
+        .send_to_name(subscriber, Atom::new("pubsub"), subscribe)
+        .await?;
+    println!("Subscribed to pubsub on {}", peer.name());
+
+    let event_timeout = if peer.is_loopback() {
+        LOOPBACK_EVENT_TIMEOUT
+    } else {
+        REMOTE_EVENT_TIMEOUT
+    };
+    for _ in 0..LOOPBACK_PUBLISHED_EVENTS {
+        let event = timeout(event_timeout, events.recv())
+            .await
+            .context("Timed out waiting for an event")?
+            .context("The subscriber has stopped")?;
+        println!("Received {}", event);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runs the examples against their loopback peer.
+
+use std::process::Command;
+
+fn run_example(binary: &str) -> String {
+    let output = Command::new(binary)
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .expect("failed to run the example");
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert!(
+        output.status.success(),
+        "{} failed: {}\n{}",
+        binary,
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    stdout
+}
+
+#[test]
+fn test_connect_and_ping() {
+    let stdout = run_example(env!("CARGO_BIN_EXE_example_connect_and_ping"));
+    assert_eq!(stdout.matches("pong from loopback@localhost").count(), 3);
+    assert!(stdout.contains("RTT over 3 samples"));
+}
+
+#[test]
+fn test_genserver_call() {
+    let stdout = run_example(env!("CARGO_BIN_EXE_example_genserver_call"));
+    assert!(stdout.contains("GenServer.call(:counter, :increment) => 3"));
+    assert!(stdout.contains("GenServer.call(:counter, :get) => 3"));
+}
+
+#[test]
+fn test_remote_spawn() {
+    let stdout = run_example(env!("CARGO_BIN_EXE_example_remote_spawn"));
+    assert!(stdout.contains("Spawned <100.0.1> on loopback@localhost"));
+    assert!(stdout.contains("Echo replied with {<100.0.1>, hello}"));
+}
+
+#[test]
+fn test_subscribe_pubsub() {
+    let stdout = run_example(env!("CARGO_BIN_EXE_example_subscribe_pubsub"));
+    for n in 1..=3 {
+        assert!(stdout.contains(&format!("Received {{event, {}}}", n)));
+    }
+}
+
+#[test]
+fn test_monitor_node() {
+    let stdout = run_example(env!("CARGO_BIN_EXE_example_monitor_node"));
+    assert!(stdout.contains("nodeup: loopback@localhost"));
+    assert!(stdout.contains("nodedown: loopback@localhost (connection_closed)"));
+}
@@ -78,10 +78,9 @@ async fn setup_client(test_name: &str, elixir_node: &str) -> Result<Node> {
         if let Ok(OwnedTerm::Atom(a)) = node
             .rpc_call(elixir_node, "Elixir.TestEchoServer", "echo", probe.clone())
             .await
+            && a.as_str() == "ping"
         {
-            if a.as_str() == "ping" {
-                return Ok(node);
-            }
+            return Ok(node);
         }
         tokio::time::sleep(Duration::from_millis(500)).await;
     }
@@ -0,0 +1,240 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Correlation of outbound calls with their replies.
+//!
+//! A [`CallTable`] keeps pending calls keyed by reference. A `{Ref, Reply}` message,
+//! a `{[alias | Ref], Reply}` message or a monitor `DOWN` for the same reference completes
+//! the call. A call that sees neither before its deadline fails with [`Error::CallTimeout`].
+//! Entries are removed when a call completes, times out or its [`PendingCall`] is dropped.
+//!
+//! Calls can also be keyed by something else, e.g. [`Node`](crate::Node) keys its RPC calls
+//! by the pid the reply is sent to and completes them with [`CallTable::complete`].
+
+use crate::errors::{Error, Result};
+use crate::mailbox::Message;
+use dashmap::DashMap;
+use erltf::OwnedTerm;
+use erltf::types::ExternalReference;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::time::{Instant, timeout_at};
+
+enum CallOutcome {
+    Reply(OwnedTerm),
+    Down(OwnedTerm),
+}
+
+struct CallEntry {
+    deadline: Instant,
+    sender: oneshot::Sender<CallOutcome>,
+}
+
+type PendingTable<K> = DashMap<K, CallEntry>;
+
+pub struct CallTable<K = ExternalReference> {
+    pending: Arc<PendingTable<K>>,
+}
+
+impl<K> Clone for CallTable<K> {
+    fn clone(&self) -> Self {
+        Self {
+            pending: Arc::clone(&self.pending),
+        }
+    }
+}
+
+impl<K: Eq + Hash> Default for CallTable<K> {
+    fn default() -> Self {
+        Self {
+            pending: Arc::new(DashMap::new()),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone> CallTable<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a call that expires after `timeout`. The reference is typically the one
+    /// of a monitor on the callee, so that its `DOWN` also completes the call.
+    pub fn register(&self, reference: K, timeout: Duration) -> PendingCall<K> {
+        let (sender, receiver) = oneshot::channel();
+        let deadline = Instant::now() + timeout;
+        self.pending
+            .insert(reference.clone(), CallEntry { deadline, sender });
+
+        PendingCall {
+            reference,
+            timeout,
+            deadline,
+            receiver: Some(receiver),
+            pending: self.pending.clone(),
+        }
+    }
+
+    /// Completes a call with a reply. Returns `false` if no such call is pending.
+    pub fn complete(&self, reference: &K, reply: OwnedTerm) -> bool {
+        self.resolve(reference, CallOutcome::Reply(reply))
+    }
+
+    /// Fails a call because the callee went down. Returns `false` if no such call is pending.
+    pub fn fail(&self, reference: &K, reason: OwnedTerm) -> bool {
+        self.resolve(reference, CallOutcome::Down(reason))
+    }
+
+    /// Cancels a pending call. Its waiter fails with [`Error::CallTimeout`].
+    pub fn cancel(&self, reference: &K) -> bool {
+        self.pending.remove(reference).is_some()
+    }
+
+    /// Removes the calls whose deadline has passed and returns how many there were.
+    pub fn expire(&self) -> usize {
+        let now = Instant::now();
+        let before = self.pending.len();
+        self.pending.retain(|_, entry| entry.deadline > now);
+        before - self.pending.len()
+    }
+
+    pub fn contains(&self, reference: &K) -> bool {
+        self.pending.contains_key(reference)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    fn resolve(&self, reference: &K, outcome: CallOutcome) -> bool {
+        match self.pending.remove(reference) {
+            Some((_, entry)) => {
+                let _ = entry.sender.send(outcome);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl CallTable {
+    /// Completes the call a message is for, if any.
+    ///
+    /// Returns the message back if it is not a reply to or a `DOWN` for a pending call,
+    /// so that a process can hand every message to the table first.
+    pub fn dispatch(&self, msg: Message) -> Option<Message> {
+        match msg {
+            Message::Regular { from, body } => {
+                let reference = reply_reference(&body)
+                    .filter(|reference| self.pending.contains_key(*reference))
+                    .cloned();
+                match (reference, body) {
+                    (Some(reference), OwnedTerm::Tuple(mut elements)) => {
+                        self.complete(&reference, elements.swap_remove(1));
+                        None
+                    }
+                    (_, body) => Some(Message::Regular { from, body }),
+                }
+            }
+            Message::MonitorExit {
+                monitored,
+                reference,
+                reason,
+            } => {
+                if self.pending.contains_key(&reference) {
+                    self.fail(&reference, reason);
+                    None
+                } else {
+                    Some(Message::MonitorExit {
+                        monitored,
+                        reference,
+                        reason,
+                    })
+                }
+            }
+            other => Some(other),
+        }
+    }
+}
+
+/// A call registered with a [`CallTable`]. Dropping it removes the call from the table.
+pub struct PendingCall<K: Eq + Hash = ExternalReference> {
+    reference: K,
+    timeout: Duration,
+    deadline: Instant,
+    receiver: Option<oneshot::Receiver<CallOutcome>>,
+    pending: Arc<PendingTable<K>>,
+}
+
+impl<K: Eq + Hash> PendingCall<K> {
+    pub fn reference(&self) -> &K {
+        &self.reference
+    }
+
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Waits for the reply until the deadline.
+    ///
+    /// Fails with [`Error::CallTargetDown`] if the callee went down first and with
+    /// [`Error::CallTimeout`] if the deadline passed or the call was cancelled.
+    pub async fn wait(mut self) -> Result<OwnedTerm> {
+        let receiver = self
+            .receiver
+            .take()
+            .expect("a pending call is only awaited once");
+        match timeout_at(self.deadline, receiver).await {
+            Ok(Ok(CallOutcome::Reply(reply))) => Ok(reply),
+            Ok(Ok(CallOutcome::Down(reason))) => Err(Error::CallTargetDown(reason)),
+            Ok(Err(_)) | Err(_) => Err(Error::CallTimeout(self.timeout)),
+        }
+    }
+}
+
+impl<K: Eq + Hash> Drop for PendingCall<K> {
+    fn drop(&mut self) {
+        self.pending.remove(&self.reference);
+    }
+}
+
+impl<K: Eq + Hash + fmt::Debug> fmt::Debug for PendingCall<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingCall")
+            .field("reference", &self.reference)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// Returns the reference a `{Ref, Reply}` or `{[alias | Ref], Reply}` message replies to.
+fn reply_reference(body: &OwnedTerm) -> Option<&ExternalReference> {
+    let (tag, _) = body.as_2_tuple()?;
+    match tag {
+        OwnedTerm::Reference(reference) => Some(reference),
+        OwnedTerm::ImproperList { elements, tail } => match (elements.as_slice(), &**tail) {
+            ([marker], OwnedTerm::Reference(reference)) if marker.is_atom_with_name("alias") => {
+                Some(reference)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
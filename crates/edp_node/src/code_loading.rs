@@ -0,0 +1,123 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loading Erlang modules onto remote nodes from embedded source.
+//!
+//! Each form is scanned and parsed with `erl_scan` and `erl_parse` on the remote
+//! node, compiled with `compile:forms/2` and loaded with `code:load_binary/3`.
+//! The preprocessor is not run, so forms cannot use macros or includes.
+
+use crate::errors::{Error, Result};
+use crate::node::Node;
+use erltf::OwnedTerm;
+use erltf::types::Atom;
+
+impl Node {
+    /// Loads `module` on `remote_node` from `forms` unless it is already loaded.
+    pub async fn ensure_module_loaded(
+        &self,
+        remote_node: &str,
+        module: &str,
+        forms: &[&str],
+    ) -> Result<()> {
+        let loaded = self
+            .rpc_call(
+                remote_node,
+                "code",
+                "is_loaded",
+                vec![OwnedTerm::Atom(Atom::new(module))],
+            )
+            .await?;
+        if matches!(loaded, OwnedTerm::Tuple(_)) {
+            return Ok(());
+        }
+        self.load_module_from_forms(remote_node, module, forms)
+            .await
+    }
+
+    /// Compiles `forms`, one form per string, and loads the result as `module`
+    /// on `remote_node`, replacing any loaded version.
+    pub async fn load_module_from_forms(
+        &self,
+        remote_node: &str,
+        module: &str,
+        forms: &[&str],
+    ) -> Result<()> {
+        let mut parsed_forms = Vec::with_capacity(forms.len());
+        for source in forms {
+            let scanned = self
+                .rpc_call(
+                    remote_node,
+                    "erl_scan",
+                    "string",
+                    vec![OwnedTerm::charlist(source)],
+                )
+                .await?;
+            let tokens = load_step("erl_scan:string/1", scanned, 3)?.swap_remove(1);
+
+            let parsed = self
+                .rpc_call(remote_node, "erl_parse", "parse_form", vec![tokens])
+                .await?;
+            parsed_forms.push(load_step("erl_parse:parse_form/1", parsed, 2)?.swap_remove(1));
+        }
+
+        let compiled = self
+            .rpc_call(
+                remote_node,
+                "compile",
+                "forms",
+                vec![
+                    OwnedTerm::List(parsed_forms),
+                    OwnedTerm::List(vec![
+                        OwnedTerm::Atom(Atom::new("binary")),
+                        OwnedTerm::Atom(Atom::new("return_errors")),
+                    ]),
+                ],
+            )
+            .await?;
+        let binary = load_step("compile:forms/2", compiled, 3)?.swap_remove(2);
+
+        let result = self
+            .rpc_call(
+                remote_node,
+                "code",
+                "load_binary",
+                vec![
+                    OwnedTerm::Atom(Atom::new(module)),
+                    OwnedTerm::charlist(format!("{module}.erl")),
+                    binary,
+                ],
+            )
+            .await?;
+        match result {
+            OwnedTerm::Tuple(ref elements)
+                if elements.len() == 2 && elements[0].is_atom_with_name("module") =>
+            {
+                Ok(())
+            }
+            other => Err(Error::ModuleLoadFailed(format!(
+                "code:load_binary/3 failed: {other}"
+            ))),
+        }
+    }
+}
+
+fn load_step(step: &str, result: OwnedTerm, arity: usize) -> Result<Vec<OwnedTerm>> {
+    match result {
+        OwnedTerm::Tuple(elements) if elements.len() == arity && elements[0] == OwnedTerm::ok() => {
+            Ok(elements)
+        }
+        other => Err(Error::ModuleLoadFailed(format!("{step} failed: {other}"))),
+    }
+}
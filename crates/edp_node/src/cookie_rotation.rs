@@ -0,0 +1,157 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cookie rotation for environments that treat the Erlang cookie as a credential.
+
+use crate::errors::Error;
+use crate::node::{DEFAULT_CONNECT_RETRY_ATTEMPTS, DEFAULT_CONNECT_RETRY_DELAY, Node};
+use crate::node_monitor::{NodeDownReason, NodeEvent};
+use erltf::types::Atom;
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// Default pause between two connections re-established by [`Node::rotate_cookie`]
+pub const DEFAULT_ROTATION_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// How [`Node::rotate_cookie`] treats the connections made with the previous cookie.
+///
+/// By default they are left alone: the peers authenticated them already and
+/// only new connections use the new cookie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieRotation {
+    /// Whether existing connections are closed and re-established with the new cookie
+    pub reconnect: bool,
+    /// Pause after each re-established connection, so that peers are not all
+    /// reconnected at once
+    pub reconnect_delay: Duration,
+    /// Total number of attempts to re-establish each connection, including the first one
+    pub max_attempts: u32,
+    /// Delay between attempts to re-establish a connection
+    pub retry_delay: Duration,
+}
+
+impl Default for CookieRotation {
+    fn default() -> Self {
+        Self {
+            reconnect: false,
+            reconnect_delay: DEFAULT_ROTATION_RECONNECT_DELAY,
+            max_attempts: DEFAULT_CONNECT_RETRY_ATTEMPTS,
+            retry_delay: DEFAULT_CONNECT_RETRY_DELAY,
+        }
+    }
+}
+
+impl CookieRotation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Re-establishes existing connections one at a time, pausing for `delay` after each.
+    pub fn with_reconnect(mut self, delay: Duration) -> Self {
+        self.reconnect = true;
+        self.reconnect_delay = delay;
+        self
+    }
+
+    pub fn with_retries(mut self, max_attempts: u32, retry_delay: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.retry_delay = retry_delay;
+        self
+    }
+}
+
+/// The outcome of [`Node::rotate_cookie`] for the connections that existed before it.
+#[derive(Debug, Default)]
+pub struct CookieRotationReport {
+    /// Nodes that were reconnected with the new cookie
+    pub reconnected: Vec<Atom>,
+    /// Nodes that could not be reconnected, with the error of the last attempt
+    pub failed: Vec<(Atom, Error)>,
+}
+
+impl CookieRotationReport {
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+impl Node {
+    /// Makes future handshakes use `new_cookie`.
+    ///
+    /// The peers must accept the new cookie before it is used, e.g. after
+    /// `erlang:set_cookie/2` on their side. With [`CookieRotation::with_reconnect`],
+    /// existing connections are then closed and re-established one at a time:
+    /// subscribers of [`Node::monitor_nodes`] see a nodedown followed by a nodeup for each.
+    pub async fn rotate_cookie(
+        &self,
+        new_cookie: impl Into<String>,
+        rotation: &CookieRotation,
+    ) -> CookieRotationReport {
+        self.set_cookie(new_cookie.into());
+        let mut report = CookieRotationReport::default();
+        if !rotation.reconnect {
+            return report;
+        }
+
+        let mut nodes: Vec<String> = self
+            .connections()
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect();
+        nodes.sort();
+
+        for (i, node) in nodes.iter().enumerate() {
+            if i > 0 {
+                sleep(rotation.reconnect_delay).await;
+            }
+            let Some((_, conn)) = self.connections().remove(node) else {
+                continue;
+            };
+            // The receiver task sees that the connection was replaced and stays quiet
+            let port = {
+                let mut conn = conn.lock().await;
+                if let Err(e) = conn.close().await {
+                    tracing::debug!("Failed to close the connection to {}: {}", node, e);
+                }
+                conn.config().remote_port
+            };
+            self.emit_node_event(NodeEvent::NodeDown {
+                node: Atom::new(node),
+                reason: NodeDownReason::ConnectionClosed,
+            });
+
+            tracing::debug!("Reconnecting to {} with the rotated cookie", node);
+            match self
+                .connect_with_port_and_retries(
+                    node.clone(),
+                    port,
+                    rotation.max_attempts,
+                    rotation.retry_delay,
+                )
+                .await
+            {
+                Ok(()) => report.reconnected.push(Atom::new(node)),
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to reconnect to {} after a cookie rotation: {}",
+                        node,
+                        e
+                    );
+                    report.failed.push((Atom::new(node), e));
+                }
+            }
+        }
+        report
+    }
+}
@@ -13,9 +13,9 @@
 // limitations under the License.
 
 use edp_client::Error as ClientError;
-use erltf::EncodeError;
 use erltf::errors::TermConversionError;
 use erltf::types::{Atom, ExternalPid};
+use erltf::{EncodeError, OwnedTerm};
 use std::time::Duration;
 use thiserror::Error;
 
@@ -47,9 +47,15 @@ pub enum Error {
     #[error("Call timeout after {0:?}")]
     CallTimeout(Duration),
 
+    #[error("Call target went down: {0}")]
+    CallTargetDown(OwnedTerm),
+
     #[error("Mailbox closed")]
     MailboxClosed,
 
+    #[error("Mailbox full")]
+    MailboxFull,
+
     #[error("Spawn failed: {0}")]
     SpawnFailed(String),
 
@@ -70,6 +76,15 @@ pub enum Error {
 
     #[error("RPC cancelled")]
     RpcCancelled,
+
+    #[error("Streamed RPC failed: {0}")]
+    RpcStreamFailed(String),
+
+    #[error("Failed to load module on remote node: {0}")]
+    ModuleLoadFailed(String),
+
+    #[error("Delivery not acknowledged after {0} attempts")]
+    DeliveryNotAcknowledged(u32),
 }
 
 impl Error {
@@ -79,4 +94,8 @@ impl Error {
             _ => false,
         }
     }
+
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Error::RpcTimeout(_) | Error::CallTimeout(_))
+    }
 }
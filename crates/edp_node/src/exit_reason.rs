@@ -0,0 +1,126 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Classification of the exit reasons carried by exit signals and `DOWN` messages.
+
+use erltf::OwnedTerm;
+use erltf::types::Atom;
+use std::fmt;
+
+const KILLED: &str = "killed";
+const NOPROC: &str = "noproc";
+const NOCONNECTION: &str = "noconnection";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExitReason {
+    /// `normal`
+    Normal,
+    /// `shutdown` (`None`) or `{shutdown, Term}` (`Some(Term)`)
+    Shutdown(Option<OwnedTerm>),
+    /// `killed`, the reason linked processes see after an untrappable `kill`
+    Killed,
+    /// `noproc`, the monitored or linked process did not exist
+    Noproc,
+    /// `noconnection`, the connection to the process' node went down
+    Noconnection,
+    /// Any other reason
+    Custom(OwnedTerm),
+}
+
+impl ExitReason {
+    pub fn from_term(term: &OwnedTerm) -> Self {
+        if let Some(atom) = term.as_atom() {
+            return match atom.as_str() {
+                Atom::NORMAL => ExitReason::Normal,
+                Atom::SHUTDOWN => ExitReason::Shutdown(None),
+                KILLED => ExitReason::Killed,
+                NOPROC => ExitReason::Noproc,
+                NOCONNECTION => ExitReason::Noconnection,
+                _ => ExitReason::Custom(term.clone()),
+            };
+        }
+        if let Some([tag, detail]) = term.as_tuple()
+            && tag.is_atom_with_name(Atom::SHUTDOWN)
+        {
+            return ExitReason::Shutdown(Some(detail.clone()));
+        }
+        ExitReason::Custom(term.clone())
+    }
+
+    pub fn to_term(&self) -> OwnedTerm {
+        match self {
+            ExitReason::Normal => OwnedTerm::Atom(Atom::new(Atom::NORMAL)),
+            ExitReason::Shutdown(None) => OwnedTerm::Atom(Atom::new(Atom::SHUTDOWN)),
+            ExitReason::Shutdown(Some(detail)) => OwnedTerm::Tuple(vec![
+                OwnedTerm::Atom(Atom::new(Atom::SHUTDOWN)),
+                detail.clone(),
+            ]),
+            ExitReason::Killed => OwnedTerm::Atom(Atom::new(KILLED)),
+            ExitReason::Noproc => OwnedTerm::Atom(Atom::new(NOPROC)),
+            ExitReason::Noconnection => OwnedTerm::Atom(Atom::new(NOCONNECTION)),
+            ExitReason::Custom(term) => term.clone(),
+        }
+    }
+
+    pub fn is_normal(&self) -> bool {
+        matches!(self, ExitReason::Normal)
+    }
+
+    /// True for both `shutdown` and `{shutdown, Term}`.
+    pub fn is_shutdown(&self) -> bool {
+        matches!(self, ExitReason::Shutdown(_))
+    }
+
+    /// True for reasons an OTP supervisor would report as a crash,
+    /// that is, anything but `normal`, `shutdown` and `{shutdown, Term}`.
+    pub fn is_abnormal(&self) -> bool {
+        !self.is_normal() && !self.is_shutdown()
+    }
+
+    pub fn is_killed(&self) -> bool {
+        matches!(self, ExitReason::Killed)
+    }
+
+    pub fn is_noproc(&self) -> bool {
+        matches!(self, ExitReason::Noproc)
+    }
+
+    pub fn is_noconnection(&self) -> bool {
+        matches!(self, ExitReason::Noconnection)
+    }
+}
+
+impl From<&OwnedTerm> for ExitReason {
+    fn from(term: &OwnedTerm) -> Self {
+        ExitReason::from_term(term)
+    }
+}
+
+impl From<OwnedTerm> for ExitReason {
+    fn from(term: OwnedTerm) -> Self {
+        ExitReason::from_term(&term)
+    }
+}
+
+impl From<ExitReason> for OwnedTerm {
+    fn from(reason: ExitReason) -> Self {
+        reason.to_term()
+    }
+}
+
+impl fmt::Display for ExitReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_term())
+    }
+}
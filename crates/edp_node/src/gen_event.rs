@@ -41,6 +41,9 @@ pub trait GenEventHandler: Send + 'static {
         args: OwnedTerm,
     ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
 
+    /// Every handler gets a clone of one [`OwnedTerm::share`]d event, so events that
+    /// carry lists, tuples, maps or binaries arrive as [`OwnedTerm::Shared`]: match on
+    /// [`OwnedTerm::resolve`] to look through it.
     fn handle_event<'a>(
         &'a mut self,
         event: OwnedTerm,
@@ -127,6 +130,7 @@ impl GenEventManager {
     }
 
     async fn notify(&mut self, event: OwnedTerm) -> Result<()> {
+        let event = event.share();
         let mut to_remove = Vec::new();
 
         for (key, entry) in &mut self.handlers {
@@ -297,6 +301,7 @@ impl Process for GenEventManager {
                     }
                 }
 
+                let body = body.share();
                 for entry in self.handlers.values_mut() {
                     if let Err(e) = entry.handler.handle_info(body.clone()).await {
                         tracing::error!("Handler info failed: {}", e);
@@ -307,6 +312,7 @@ impl Process for GenEventManager {
             }
             Message::Control { .. } => Ok(()),
             Message::Exit { reason, .. } => {
+                let reason = reason.share();
                 for entry in self.handlers.values_mut() {
                     entry.handler.terminate(reason.clone()).await;
                 }
@@ -12,18 +12,60 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::errors::Result;
+use crate::errors::{Error, Result};
 use crate::mailbox::Message;
-use crate::process::Process;
+use crate::node::{Node, ReferenceAllocator};
+use crate::process::{Process, connection_to};
 use crate::registry::ProcessRegistry;
+use dashmap::DashMap;
+use edp_client::Connection;
 use erltf::OwnedTerm;
 use erltf::types::{Atom, ExternalPid, ExternalReference};
+use std::collections::{HashMap, VecDeque};
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+/// The timeout of [`CallResult::call`], the same as the one of `gen_server:call/2`.
+pub const DEFAULT_GEN_CALL_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub enum CallResult {
     Reply(OwnedTerm),
     NoReply,
+    /// Calls the gen_server `to`, on this node or a connected one, without blocking the server. The reply
+    /// is passed to [`GenServer::handle_call_reply`] along with the original caller.
+    Call {
+        to: ExternalPid,
+        request: OwnedTerm,
+        timeout: Duration,
+    },
+}
+
+impl CallResult {
+    /// A [`CallResult::Call`] with [`DEFAULT_GEN_CALL_TIMEOUT`].
+    pub fn call(to: ExternalPid, request: OwnedTerm) -> Self {
+        CallResult::Call {
+            to,
+            request,
+            timeout: DEFAULT_GEN_CALL_TIMEOUT,
+        }
+    }
+}
+
+/// How a [`GenServerProcess`] treats messages that arrive while a call it made
+/// with [`CallResult::Call`] awaits its reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Reentrancy {
+    /// Like a `gen_server` blocked in `gen_server:call/3`: other messages are deferred
+    /// until the reply arrives or the call times out. Two servers that call each other
+    /// are stuck until one of the calls times out.
+    #[default]
+    Disabled,
+    /// Calls, casts and other messages are handled while calls are outstanding,
+    /// so servers can call each other from `handle_call`.
+    Enabled,
 }
 
 pub trait GenServer: Send + 'static {
@@ -35,6 +77,18 @@ pub trait GenServer: Send + 'static {
         from: ExternalPid,
     ) -> impl Future<Output = Result<CallResult>> + Send + '_;
 
+    /// Handles the reply to a [`CallResult::Call`] made while handling a call from `from`.
+    ///
+    /// By default the reply is passed on to `from`, and a failed call, e.g. one that
+    /// timed out, stops the server.
+    fn handle_call_reply(
+        &mut self,
+        reply: Result<OwnedTerm>,
+        _from: ExternalPid,
+    ) -> impl Future<Output = Result<CallResult>> + Send + '_ {
+        async move { reply.map(CallResult::Reply) }
+    }
+
     fn handle_cast(&mut self, msg: OwnedTerm) -> impl Future<Output = Result<()>> + Send + '_;
 
     fn handle_info(&mut self, msg: OwnedTerm) -> impl Future<Output = Result<()>> + Send + '_;
@@ -44,23 +98,106 @@ pub trait GenServer: Send + 'static {
     }
 }
 
+/// The process a call came from and the reference its reply is tagged with.
+struct Caller {
+    pid: ExternalPid,
+    reference: ExternalReference,
+}
+
+/// Delivers the messages of a [`GenServerProcess`], to local processes via their
+/// mailboxes and to remote ones over the connection to their node.
+struct Delivery {
+    registry: Arc<ProcessRegistry>,
+    connections: Arc<DashMap<String, Arc<Mutex<Connection>>>>,
+}
+
+impl Delivery {
+    async fn send(&self, from: &ExternalPid, to: &ExternalPid, body: OwnedTerm) -> Result<()> {
+        if to.node == from.node {
+            let handle = self
+                .registry
+                .get(to)
+                .await
+                .ok_or_else(|| Error::ProcessNotFound(to.clone()))?;
+            handle
+                .send(Message::Regular {
+                    from: Some(from.clone()),
+                    body,
+                })
+                .await
+        } else {
+            let conn = connection_to(&self.connections, to)
+                .ok_or_else(|| Error::NodeNotConnected(to.node.to_string()))?;
+            conn.lock()
+                .await
+                .send_message(from.clone(), to.clone(), body)
+                .await?;
+            Ok(())
+        }
+    }
+
+    /// Replies to `caller`. Like `gen_server:reply/2`, a caller that is gone is ignored.
+    async fn reply(&self, from: &ExternalPid, caller: &Caller, reply: OwnedTerm) -> Result<()> {
+        let reply_msg =
+            OwnedTerm::Tuple(vec![OwnedTerm::Reference(caller.reference.clone()), reply]);
+
+        match self.send(from, &caller.pid, reply_msg).await {
+            Err(Error::ProcessNotFound(_) | Error::NodeNotConnected(_)) => Ok(()),
+            result => result,
+        }
+    }
+}
+
+/// A call made with [`CallResult::Call`] on behalf of a caller.
+struct OutstandingCall {
+    caller: Caller,
+    timeout: Duration,
+}
+
 pub struct GenServerProcess<T: GenServer> {
     server: T,
     call_tag: Atom,
     cast_tag: Atom,
-    registry: Arc<ProcessRegistry>,
+    timeout_tag: Atom,
+    delivery: Delivery,
+    references: ReferenceAllocator,
+    reentrancy: Reentrancy,
+    pid: Option<ExternalPid>,
+    outstanding: HashMap<ExternalReference, OutstandingCall>,
+    deferred: VecDeque<(Option<ExternalPid>, OwnedTerm)>,
 }
 
 impl<T: GenServer> GenServerProcess<T> {
-    pub fn new(server: T, registry: Arc<ProcessRegistry>) -> Self {
+    /// A gen_server to spawn on `node`. Calls and replies to processes on other nodes
+    /// go over the node's connections.
+    pub fn new(server: T, node: &Node) -> Self {
         Self {
             server,
             call_tag: Atom::new("$gen_call"),
             cast_tag: Atom::new("$gen_cast"),
-            registry,
+            timeout_tag: Atom::new("$gen_call_timeout"),
+            delivery: Delivery {
+                registry: node.registry(),
+                connections: node.connections(),
+            },
+            references: node.reference_allocator(),
+            reentrancy: Reentrancy::default(),
+            pid: None,
+            outstanding: HashMap::new(),
+            deferred: VecDeque::new(),
         }
     }
 
+    pub fn with_reentrancy(mut self, reentrancy: Reentrancy) -> Self {
+        self.reentrancy = reentrancy;
+        self
+    }
+
+    /// The number of calls made with [`CallResult::Call`] that await their reply.
+    pub fn outstanding_calls(&self) -> usize {
+        self.outstanding.len()
+    }
+
     async fn handle_gen_call(
         &mut self,
         from_pid: ExternalPid,
@@ -68,56 +205,174 @@ impl<T: GenServer> GenServerProcess<T> {
         request: OwnedTerm,
     ) -> Result<()> {
         let result = self.server.handle_call(request, from_pid.clone()).await?;
+        let caller = Caller {
+            pid: from_pid,
+            reference,
+        };
+        self.complete(caller, result).await
+    }
 
-        match result {
-            CallResult::Reply(reply) => {
-                let reply_msg = OwnedTerm::Tuple(vec![OwnedTerm::Reference(reference), reply]);
-
-                if let Some(handle) = self.registry.get(&from_pid).await {
-                    handle
-                        .send(Message::Regular {
-                            from: None,
-                            body: reply_msg,
-                        })
-                        .await?;
+    async fn complete(&mut self, caller: Caller, mut result: CallResult) -> Result<()> {
+        loop {
+            match result {
+                CallResult::Reply(reply) => {
+                    let Some(pid) = &self.pid else {
+                        return Ok(());
+                    };
+                    return self.delivery.reply(pid, &caller, reply).await;
                 }
+                CallResult::NoReply => return Ok(()),
+                CallResult::Call {
+                    to,
+                    request,
+                    timeout,
+                } => match self.send_call(&to, request, timeout).await {
+                    Ok(reference) => {
+                        self.outstanding
+                            .insert(reference, OutstandingCall { caller, timeout });
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        result = self
+                            .server
+                            .handle_call_reply(Err(e), caller.pid.clone())
+                            .await?;
+                    }
+                },
+            }
+        }
+    }
 
-                Ok(())
+    /// Sends a `$gen_call` to `to` and schedules its timeout. Returns the call's reference.
+    async fn send_call(
+        &mut self,
+        to: &ExternalPid,
+        request: OwnedTerm,
+        timeout: Duration,
+    ) -> Result<ExternalReference> {
+        let pid = self.pid.clone().ok_or_else(|| {
+            Error::InvalidMessage("calls can only be made by a spawned gen_server".to_string())
+        })?;
+        let reference = self.references.allocate();
+        let body = OwnedTerm::Tuple(vec![
+            OwnedTerm::Atom(self.call_tag.clone()),
+            OwnedTerm::Tuple(vec![
+                OwnedTerm::Pid(pid.clone()),
+                OwnedTerm::Reference(reference.clone()),
+            ]),
+            request,
+        ]);
+        self.delivery.send(&pid, to, body).await?;
+
+        let registry = self.delivery.registry.clone();
+        let timeout_msg = OwnedTerm::Tuple(vec![
+            OwnedTerm::Atom(self.timeout_tag.clone()),
+            OwnedTerm::Reference(reference.clone()),
+        ]);
+        tokio::spawn(async move {
+            sleep(timeout).await;
+            if let Some(handle) = registry.get(&pid).await {
+                let _ = handle
+                    .send(Message::Regular {
+                        from: None,
+                        body: timeout_msg,
+                    })
+                    .await;
             }
-            CallResult::NoReply => Ok(()),
+        });
+
+        Ok(reference)
+    }
+
+    /// Matches replies to and timeouts of outstanding calls.
+    fn take_outstanding(
+        &mut self,
+        body: &OwnedTerm,
+    ) -> Option<(OutstandingCall, Result<OwnedTerm>)> {
+        let (tag, value) = body.as_2_tuple()?;
+        match (tag, value) {
+            (OwnedTerm::Reference(reference), reply) => {
+                let call = self.outstanding.remove(reference)?;
+                Some((call, Ok(reply.clone())))
+            }
+            (OwnedTerm::Atom(tag), OwnedTerm::Reference(reference)) if tag == &self.timeout_tag => {
+                let call = self.outstanding.remove(reference)?;
+                let timeout = call.timeout;
+                Some((call, Err(Error::CallTimeout(timeout))))
+            }
+            _ => None,
         }
     }
 
+    fn is_call_timeout(&self, body: &OwnedTerm) -> bool {
+        matches!(body.as_2_tuple(), Some((OwnedTerm::Atom(tag), OwnedTerm::Reference(_))) if tag == &self.timeout_tag)
+    }
+
     async fn handle_gen_cast(&mut self, request: OwnedTerm) -> Result<()> {
         self.server.handle_cast(request).await
     }
+
+    async fn handle_regular(&mut self, body: OwnedTerm) -> Result<()> {
+        if let OwnedTerm::Tuple(elements) = &body
+            && elements.len() >= 2
+            && let OwnedTerm::Atom(tag) = &elements[0]
+        {
+            if tag == &self.call_tag && elements.len() == 3 {
+                if let OwnedTerm::Tuple(from_tuple) = &elements[1]
+                    && from_tuple.len() == 2
+                    && let OwnedTerm::Pid(from_pid) = &from_tuple[0]
+                    && let OwnedTerm::Reference(reference) = &from_tuple[1]
+                {
+                    let request = elements[2].clone();
+                    return self
+                        .handle_gen_call(from_pid.clone(), reference.clone(), request)
+                        .await;
+                }
+            } else if tag == &self.cast_tag && elements.len() == 2 {
+                return self.handle_gen_cast(elements[1].clone()).await;
+            }
+        }
+
+        self.server.handle_info(body).await
+    }
+
+    /// Handles the messages deferred while a call was outstanding, until the next call is made.
+    async fn handle_deferred(&mut self) -> Result<()> {
+        while self.outstanding.is_empty()
+            && let Some((_, body)) = self.deferred.pop_front()
+        {
+            self.handle_regular(body).await?;
+        }
+        Ok(())
+    }
 }
 
 impl<T: GenServer> Process for GenServerProcess<T> {
+    fn spawned(&mut self, pid: &ExternalPid) {
+        self.pid = Some(pid.clone());
+    }
+
     async fn handle_message(&mut self, msg: Message) -> Result<()> {
         match msg {
-            Message::Regular { from: _, body } => {
-                if let OwnedTerm::Tuple(elements) = &body
-                    && elements.len() >= 2
-                    && let OwnedTerm::Atom(tag) = &elements[0]
-                {
-                    if tag == &self.call_tag && elements.len() == 3 {
-                        if let OwnedTerm::Tuple(from_tuple) = &elements[1]
-                            && from_tuple.len() == 2
-                            && let OwnedTerm::Pid(from_pid) = &from_tuple[0]
-                            && let OwnedTerm::Reference(reference) = &from_tuple[1]
-                        {
-                            let request = elements[2].clone();
-                            return self
-                                .handle_gen_call(from_pid.clone(), reference.clone(), request)
-                                .await;
-                        }
-                    } else if tag == &self.cast_tag && elements.len() == 2 {
-                        return self.handle_gen_cast(elements[1].clone()).await;
-                    }
+            Message::Regular { from, body } => {
+                if let Some((call, reply)) = self.take_outstanding(&body) {
+                    let result = self
+                        .server
+                        .handle_call_reply(reply, call.caller.pid.clone())
+                        .await?;
+                    self.complete(call.caller, result).await?;
+                    return self.handle_deferred().await;
+                }
+                // Timeouts of calls that have been replied to
+                if self.is_call_timeout(&body) {
+                    return Ok(());
+                }
+                if self.reentrancy == Reentrancy::Disabled && !self.outstanding.is_empty() {
+                    self.deferred.push_back((from, body));
+                    return Ok(());
                 }
 
-                self.server.handle_info(body).await
+                self.handle_regular(body).await
             }
             Message::Control { .. } => Ok(()),
             Message::Exit { reason, .. } => {
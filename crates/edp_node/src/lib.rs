@@ -24,6 +24,7 @@
 //! - Message routing to local and remote processes
 //! - GenServer behavior pattern
 //! - Process linking and monitoring
+//! - Timers (`send_after`, `start_timer`)
 //!
 //! # Example
 //!
@@ -41,26 +42,58 @@
 //! }
 //! ```
 
+pub mod call_table;
+pub mod code_loading;
+pub mod cookie_rotation;
 pub mod erlang_mod_fns;
 pub mod errors;
+pub mod exit_reason;
 pub mod gen_event;
 pub mod gen_server;
 pub mod mailbox;
 pub mod node;
+pub mod node_info;
+pub mod node_monitor;
 pub mod process;
 pub mod registry;
+pub mod reliable;
+pub mod routing;
+pub mod rpc_pool;
+pub mod rpc_result;
+pub mod rpc_stream;
+pub mod timers;
+pub mod tracer;
 
+pub use call_table::{CallTable, PendingCall};
+pub use cookie_rotation::{CookieRotation, CookieRotationReport, DEFAULT_ROTATION_RECONNECT_DELAY};
 pub use errors::{Error, Result};
+pub use exit_reason::ExitReason;
 pub use gen_event::{
     CallResult as GenEventCallResult, EventResult, GenEventHandler, GenEventManager,
 };
-pub use gen_server::{CallResult, GenServer, GenServerProcess};
-pub use mailbox::{Mailbox, Message};
+pub use gen_server::{
+    CallResult, DEFAULT_GEN_CALL_TIMEOUT, GenServer, GenServerProcess, Reentrancy,
+};
+pub use mailbox::{Mailbox, MailboxSender, MailboxStats, Message, OverflowPolicy};
 pub use node::{
-    DEFAULT_CONNECT_RETRY_ATTEMPTS, DEFAULT_CONNECT_RETRY_DELAY, DEFAULT_RPC_TIMEOUT, Node,
+    DEFAULT_CONNECT_RETRY_ATTEMPTS, DEFAULT_CONNECT_RETRY_DELAY, DEFAULT_DEADLINE_GRACE,
+    DEFAULT_RPC_TIMEOUT, Node, is_erpc_timeout,
 };
+pub use node_info::{ProcessInfo, SnapshotOptions};
+pub use node_monitor::{NodeDownReason, NodeEvent, NodeMonitor};
 pub use process::{Process, ProcessHandle};
 pub use registry::ProcessRegistry;
+pub use reliable::{DeliveryReceipt, ReliableSender, ReliableTarget, RetryPolicy};
+pub use routing::{Destination, NamePattern, RouteHandler, RoutedMessage, Router, RouterStats};
+pub use rpc_pool::{
+    DEFAULT_RPC_POOL_SIZE, PoolMemberMetrics, RpcPool, RpcPoolConfig, RpcPoolMetrics,
+};
+pub use rpc_result::{RpcError, RpcResult, rpc_result};
+pub use rpc_stream::{DEFAULT_STREAM_CHUNK_SIZE, RpcStream};
+pub use timers::{TimerHandle, Timers, parse_timeout_message, timeout_message};
+pub use tracer::{
+    GcPhase, TraceEvent, TraceEventKind, TraceFlag, TracePattern, TraceTarget, Tracer,
+};
 
 pub use erltf::{
     Atom, ExternalPid, Mfa, OwnedTerm, erl_atom, erl_atoms, erl_int, erl_list, erl_map, erl_tuple,
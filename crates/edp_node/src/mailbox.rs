@@ -13,12 +13,16 @@
 // limitations under the License.
 
 use crate::errors::{Error, Result};
+use crate::exit_reason::ExitReason;
 use edp_client::control::ControlMessage;
 use erltf::OwnedTerm;
 use erltf::types::ExternalPid;
-use tokio::sync::mpsc;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex as StdMutex, MutexGuard};
+use tokio::sync::Notify;
 
-const DEFAULT_MAILBOX_CAPACITY: usize = 1000;
+pub const DEFAULT_MAILBOX_CAPACITY: usize = 1000;
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -56,39 +60,194 @@ pub enum Message {
     },
 }
 
+impl Message {
+    /// The classified reason of an exit signal or a `DOWN` message,
+    /// `None` for every other kind of message.
+    pub fn exit_reason(&self) -> Option<ExitReason> {
+        match self {
+            Message::Exit { reason, .. } | Message::MonitorExit { reason, .. } => {
+                Some(ExitReason::from_term(reason))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// What a mailbox does with a message that arrives when it is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Senders wait until there is room
+    #[default]
+    Block,
+    /// The oldest queued message is discarded to make room
+    DropOldest,
+    /// The incoming message is discarded
+    DropNewest,
+    /// The incoming message is rejected with [`Error::MailboxFull`]. Messages routed from
+    /// remote nodes make the node send an exit signal to the sender.
+    Exit,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MailboxStats {
+    pub capacity: usize,
+    pub len: usize,
+    /// Largest queue length observed
+    pub peak_len: usize,
+    pub enqueued: u64,
+    pub dequeued: u64,
+    /// Messages discarded by the `DropOldest` and `DropNewest` policies
+    pub dropped: u64,
+    /// Messages refused with [`Error::MailboxFull`]
+    pub rejected: u64,
+}
+
+struct State {
+    queue: VecDeque<Message>,
+    closed: bool,
+    stats: MailboxStats,
+}
+
+struct Shared {
+    state: StdMutex<State>,
+    policy: OverflowPolicy,
+    message_available: Notify,
+    space_available: Notify,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the message back when the mailbox is full and the policy is `Block`.
+    fn push(&self, msg: Message) -> Result<Option<Message>> {
+        let mut state = self.lock();
+        if state.closed {
+            return Err(Error::MailboxClosed);
+        }
+        if state.queue.len() >= state.stats.capacity {
+            match self.policy {
+                OverflowPolicy::Block => return Ok(Some(msg)),
+                OverflowPolicy::DropNewest => {
+                    state.stats.dropped += 1;
+                    return Ok(None);
+                }
+                OverflowPolicy::DropOldest => {
+                    state.queue.pop_front();
+                    state.stats.dropped += 1;
+                }
+                OverflowPolicy::Exit => {
+                    state.stats.rejected += 1;
+                    return Err(Error::MailboxFull);
+                }
+            }
+        }
+        state.queue.push_back(msg);
+        state.stats.enqueued += 1;
+        state.stats.peak_len = state.stats.peak_len.max(state.queue.len());
+        drop(state);
+        self.message_available.notify_one();
+        Ok(None)
+    }
+
+    fn pop(&self) -> Option<Message> {
+        let mut state = self.lock();
+        let msg = state.queue.pop_front()?;
+        state.stats.dequeued += 1;
+        drop(state);
+        self.space_available.notify_one();
+        Some(msg)
+    }
+
+    fn stats(&self) -> MailboxStats {
+        let state = self.lock();
+        MailboxStats {
+            len: state.queue.len(),
+            ..state.stats
+        }
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.message_available.notify_waiters();
+        self.space_available.notify_waiters();
+    }
+}
+
+/// A bounded, per-process message queue.
 pub struct Mailbox {
-    sender: mpsc::Sender<Message>,
-    receiver: mpsc::Receiver<Message>,
+    shared: Arc<Shared>,
 }
 
 impl Mailbox {
     pub fn new() -> Self {
-        let (sender, receiver) = mpsc::channel(DEFAULT_MAILBOX_CAPACITY);
-        Self { sender, receiver }
+        Self::with_capacity(DEFAULT_MAILBOX_CAPACITY)
     }
 
     pub fn with_capacity(capacity: usize) -> Self {
-        let (sender, receiver) = mpsc::channel(capacity);
-        Self { sender, receiver }
+        Self::bounded(capacity, OverflowPolicy::default())
+    }
+
+    /// Panics if `capacity` is zero.
+    pub fn bounded(capacity: usize, policy: OverflowPolicy) -> Self {
+        assert!(capacity > 0, "mailbox capacity must be greater than zero");
+        let state = State {
+            queue: VecDeque::new(),
+            closed: false,
+            stats: MailboxStats {
+                capacity,
+                ..MailboxStats::default()
+            },
+        };
+        Self {
+            shared: Arc::new(Shared {
+                state: StdMutex::new(state),
+                policy,
+                message_available: Notify::new(),
+                space_available: Notify::new(),
+            }),
+        }
+    }
+
+    pub fn sender(&self) -> MailboxSender {
+        MailboxSender {
+            shared: self.shared.clone(),
+        }
     }
 
-    pub fn sender(&self) -> mpsc::Sender<Message> {
-        self.sender.clone()
+    pub fn policy(&self) -> OverflowPolicy {
+        self.shared.policy
+    }
+
+    pub fn stats(&self) -> MailboxStats {
+        self.shared.stats()
     }
 
     pub async fn recv(&mut self) -> Result<Message> {
-        self.receiver.recv().await.ok_or(Error::MailboxClosed)
+        loop {
+            let notified = self.shared.message_available.notified();
+            if let Some(msg) = self.shared.pop() {
+                return Ok(msg);
+            }
+            if self.shared.lock().closed {
+                return Err(Error::MailboxClosed);
+            }
+            notified.await;
+        }
     }
 
     pub fn try_recv(&mut self) -> Result<Message> {
-        self.receiver.try_recv().map_err(|_| Error::MailboxClosed)
+        self.shared.pop().ok_or(Error::MailboxClosed)
     }
 
     pub async fn send(&self, msg: Message) -> Result<()> {
-        self.sender
-            .send(msg)
-            .await
-            .map_err(|_| Error::MailboxClosed)
+        self.sender().send(msg).await
+    }
+
+    /// Rejects further messages. Queued messages can still be received.
+    pub fn close(&self) {
+        self.shared.close();
     }
 }
 
@@ -97,3 +256,67 @@ impl Default for Mailbox {
         Self::new()
     }
 }
+
+impl Drop for Mailbox {
+    fn drop(&mut self) {
+        self.shared.close();
+    }
+}
+
+#[derive(Clone)]
+pub struct MailboxSender {
+    shared: Arc<Shared>,
+}
+
+impl MailboxSender {
+    /// Enqueues a message, applying the mailbox's overflow policy when it is full.
+    pub async fn send(&self, msg: Message) -> Result<()> {
+        let mut msg = msg;
+        loop {
+            let notified = self.shared.space_available.notified();
+            match self.shared.push(msg)? {
+                None => return Ok(()),
+                Some(returned) => msg = returned,
+            }
+            notified.await;
+        }
+    }
+
+    /// Like [`MailboxSender::send`] but fails with [`Error::MailboxFull`] instead of waiting.
+    pub fn try_send(&self, msg: Message) -> Result<()> {
+        match self.shared.push(msg)? {
+            None => Ok(()),
+            Some(_) => {
+                self.shared.lock().stats.rejected += 1;
+                Err(Error::MailboxFull)
+            }
+        }
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.shared.policy
+    }
+
+    pub fn stats(&self) -> MailboxStats {
+        self.shared.stats()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.shared.lock().closed
+    }
+
+    /// Rejects further messages. A process receiving through this mailbox
+    /// exits with `normal` once it has handled the queued ones.
+    pub fn close(&self) {
+        self.shared.close();
+    }
+}
+
+impl fmt::Debug for MailboxSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MailboxSender")
+            .field("policy", &self.shared.policy)
+            .field("stats", &self.shared.stats())
+            .finish()
+    }
+}
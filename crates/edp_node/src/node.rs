@@ -12,38 +12,93 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::call_table::{CallTable, PendingCall};
 use crate::errors::{Error, Result};
-use crate::mailbox::{Mailbox, Message};
-use crate::process::{Process, spawn_process};
+use crate::mailbox::{Mailbox, MailboxStats, Message};
+use crate::node_monitor::{NODE_EVENT_BUFFER_SIZE, NodeDownReason, NodeEvent, NodeMonitor};
+use crate::process::{Process, ProcessHandle, connection_to, spawn_process};
 use crate::registry::ProcessRegistry;
+use crate::routing::{Destination, RoutedMessage, Router};
+use crate::rpc_result::{RpcResult, rpc_result};
+use crate::timers::{TimerHandle, Timers};
 use dashmap::DashMap;
 use edp_client::control::ControlMessage;
 use edp_client::epmd_client::{EpmdClient, NodeType};
-use edp_client::{Connection, ConnectionConfig, PidAllocator};
-use erltf::OwnedTerm;
+use edp_client::{
+    Connection, ConnectionConfig, ConnectionId, ConnectionMetrics, DecodeBudget, DecodeErrorPolicy,
+    DecodeOffload, DecodePipeline, OwnedReadHalf, PidAllocator, ReceiveOptions, ReceivedMessage,
+};
 use erltf::types::{Atom, ExternalPid, ExternalReference};
-use std::sync::Arc;
+use erltf::{DecodeConfig, OwnedTerm};
+use std::ptr;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::time::Duration;
-use tokio::sync::{Mutex, oneshot};
+use std::sync::{Arc, RwLock, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, broadcast, mpsc};
 use tokio::time::sleep;
+use tracing::{Instrument, Span};
 
 pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(10);
 pub const DEFAULT_CONNECT_RETRY_ATTEMPTS: u32 = 10;
 pub const DEFAULT_CONNECT_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// Extra time to wait for the remote `{erpc, timeout}` reply after a deadline passes.
+pub const DEFAULT_DEADLINE_GRACE: Duration = Duration::from_millis(500);
+/// The shortest interval between two idleness checks of a connection
+const MIN_HIBERNATION_CHECK_PERIOD: Duration = Duration::from_millis(10);
+
+pub(crate) fn new_reference(
+    node: &Atom,
+    creation: &AtomicU32,
+    reference_counter: &AtomicU32,
+) -> ExternalReference {
+    let id0 = reference_counter.fetch_add(1, Ordering::SeqCst);
+    let id1 = reference_counter.fetch_add(1, Ordering::SeqCst);
+    let id2 = reference_counter.fetch_add(1, Ordering::SeqCst);
+    ExternalReference::new(
+        node.clone(),
+        creation.load(Ordering::SeqCst),
+        vec![id0, id1, id2],
+    )
+}
+
+/// Allocates references the way [`Node::make_reference`] does, for the node's processes.
+#[derive(Clone)]
+pub(crate) struct ReferenceAllocator {
+    node: Atom,
+    creation: Arc<AtomicU32>,
+    reference_counter: Arc<AtomicU32>,
+}
+
+impl ReferenceAllocator {
+    pub(crate) fn allocate(&self) -> ExternalReference {
+        new_reference(&self.node, &self.creation, &self.reference_counter)
+    }
+}
 
 pub struct Node {
     name: Atom,
-    cookie: String,
+    cookie: RwLock<String>,
     creation: Arc<AtomicU32>,
     pid_allocator: Arc<PidAllocator>,
     reference_counter: Arc<AtomicU32>,
     registry: Arc<ProcessRegistry>,
+    router: Arc<Router>,
+    timers: Timers,
+    node_events: broadcast::Sender<NodeEvent>,
     connections: Arc<DashMap<String, Arc<Mutex<Connection>>>>,
-    pending_rpcs: Arc<DashMap<String, oneshot::Sender<OwnedTerm>>>,
+    /// The id of the last connection to each node, reused when reconnecting
+    connection_ids: DashMap<String, ConnectionId>,
+    /// RPC calls keyed by the pid their reply is sent to
+    pending_rpcs: CallTable<ExternalPid>,
+    pending_streams: Arc<DashMap<String, mpsc::UnboundedSender<OwnedTerm>>>,
     started: Arc<AtomicBool>,
     listen_port: Option<u16>,
     hidden: bool,
+    decode_offload: Option<DecodeOffload>,
+    decode_budget: DecodeBudget,
+    decode_config: DecodeConfig,
+    idle_hibernation: Option<Duration>,
+    unlink_acks: bool,
 }
 
 impl Node {
@@ -139,19 +194,37 @@ impl Node {
         let creation = 1;
         let pid_allocator = Arc::new(PidAllocator::new(name_atom.clone(), creation));
         let creation = Arc::new(AtomicU32::new(creation));
+        let reference_counter = Arc::new(AtomicU32::new(0));
+        let registry = Arc::new(ProcessRegistry::new());
+        let timers = Timers::new(
+            name_atom.clone(),
+            creation.clone(),
+            reference_counter.clone(),
+            registry.clone(),
+        );
 
         Self {
             name: name_atom,
-            cookie: cookie.into(),
+            cookie: RwLock::new(cookie.into()),
             creation,
             pid_allocator,
-            reference_counter: Arc::new(AtomicU32::new(0)),
-            registry: Arc::new(ProcessRegistry::new()),
+            reference_counter,
+            registry,
+            router: Arc::new(Router::new()),
+            timers,
+            node_events: broadcast::channel(NODE_EVENT_BUFFER_SIZE).0,
             connections: Arc::new(DashMap::new()),
-            pending_rpcs: Arc::new(DashMap::new()),
+            connection_ids: DashMap::new(),
+            pending_rpcs: CallTable::new(),
+            pending_streams: Arc::new(DashMap::new()),
             started: Arc::new(AtomicBool::new(false)),
             listen_port: None,
             hidden,
+            decode_offload: None,
+            decode_budget: DecodeBudget::default(),
+            decode_config: DecodeConfig::default(),
+            idle_hibernation: None,
+            unlink_acks: true,
         }
     }
 
@@ -159,6 +232,53 @@ impl Node {
         self.registry.clone()
     }
 
+    /// Dispatches received messages that no local process is registered for,
+    /// see [`Router`].
+    pub fn router(&self) -> Arc<Router> {
+        self.router.clone()
+    }
+
+    /// Allocates the pids of this node's processes, e.g. to reserve a range
+    /// or to persist its state across restarts.
+    pub fn pid_allocator(&self) -> Arc<PidAllocator> {
+        self.pid_allocator.clone()
+    }
+
+    /// Decodes messages above the offload threshold on tokio's blocking thread pool,
+    /// for the connections this node makes. Messages are still routed in the order they arrive.
+    pub fn with_decode_offload(mut self, offload: DecodeOffload) -> Self {
+        self.decode_offload = Some(offload);
+        self
+    }
+
+    /// Holds what each connection this node makes decodes to `budget`. Payloads count as
+    /// outstanding until they are routed to a process, see [`ConnectionConfig::with_decode_budget`].
+    pub fn with_decode_budget(mut self, budget: DecodeBudget) -> Self {
+        self.decode_budget = budget;
+        self
+    }
+
+    /// Decodes what the connections this node makes receive with `config`, e.g. in safe
+    /// mode or with an atom limit, see [`ConnectionConfig::with_decode_config`].
+    pub fn with_decode_config(mut self, config: DecodeConfig) -> Self {
+        self.decode_config = config;
+        self
+    }
+
+    /// Hibernates connections that go without messages for `after`, see
+    /// [`Connection::hibernate`]. Ticks keep flowing and the connections stay up.
+    pub fn with_idle_hibernation(mut self, after: Duration) -> Self {
+        self.idle_hibernation = Some(after);
+        self
+    }
+
+    /// Whether received `UNLINK_ID`s are answered with `UNLINK_ID_ACK`, as the link
+    /// protocol requires. Enabled by default, see [`ConnectionConfig::with_unlink_acks`].
+    pub fn with_unlink_acks(mut self, unlink_acks: bool) -> Self {
+        self.unlink_acks = unlink_acks;
+        self
+    }
+
     pub async fn start(&mut self, port: u16) -> Result<()> {
         if self.started.swap(true, Ordering::SeqCst) {
             return Err(Error::NodeAlreadyStarted);
@@ -189,20 +309,56 @@ impl Node {
     }
 
     pub async fn connect(&self, remote_node: impl Into<String>) -> Result<()> {
-        let remote_node = remote_node.into();
+        self.connect_with_port(remote_node.into(), None).await
+    }
 
+    /// Connects to `remote_node` on a known distribution port, without querying EPMD.
+    pub async fn connect_to_port(&self, remote_node: impl Into<String>, port: u16) -> Result<()> {
+        self.connect_with_port(remote_node.into(), Some(port)).await
+    }
+
+    async fn connect_with_port(&self, remote_node: String, port: Option<u16>) -> Result<()> {
         if self.connections.contains_key(&remote_node) {
             return Ok(());
         }
 
-        let config = if self.hidden {
-            ConnectionConfig::new_hidden(self.name.as_str(), &remote_node, &self.cookie)
+        let cookie = self.cookie();
+        let mut config = if self.hidden {
+            ConnectionConfig::new_hidden(self.name.as_str(), &remote_node, &cookie)
         } else {
-            ConnectionConfig::new(self.name.as_str(), &remote_node, &self.cookie)
+            ConnectionConfig::new(self.name.as_str(), &remote_node, &cookie)
         };
+        if let Some(port) = port {
+            config = config.with_remote_port(port);
+        }
+        if let Some(offload) = self.decode_offload {
+            config = config.with_decode_offload(offload);
+        }
+        if let Some(after) = self.idle_hibernation {
+            config = config.with_idle_hibernation(after);
+        }
+        if let Some(id) = self.connection_id(&remote_node) {
+            config = config.with_connection_id(id);
+        }
+        config = config
+            .with_unlink_acks(self.unlink_acks)
+            .with_decode_budget(self.decode_budget)
+            .with_decode_config(self.decode_config.clone())
+            .with_decode_error_policy(DecodeErrorPolicy::Resume);
+        let connections = Arc::clone(&self.connections);
+        let node_events = self.node_events.clone();
+        config = config
+            .with_alive_answer(move |node| Self::answer_alive(&connections, &node_events, node));
 
         let mut conn = Connection::new(config);
-        conn.connect().await?;
+        let connection_id = conn.id();
+        if let Err(e) = conn.connect().await {
+            self.emit_node_event(NodeEvent::NodeDown {
+                node: Atom::new(&remote_node),
+                reason: NodeDownReason::ConnectionSetupFailed,
+            });
+            return Err(e.into());
+        }
 
         let read_half = conn.take_read_half().ok_or_else(|| {
             edp_client::Error::InvalidStateMessage(
@@ -211,13 +367,28 @@ impl Node {
         })?;
 
         let timeout = conn.timeout();
+        let receive_options = conn.receive_options();
+
+        let conn = Arc::new(Mutex::new(conn));
+        self.connections.insert(remote_node.clone(), conn.clone());
+        self.connection_ids
+            .insert(remote_node.clone(), connection_id);
+
+        self.spawn_receiver_task(
+            remote_node.clone(),
+            Arc::downgrade(&conn),
+            read_half,
+            timeout,
+            receive_options,
+        );
+        if let Some(after) = self.idle_hibernation {
+            Self::spawn_hibernation_task(Arc::downgrade(&conn), after);
+        }
+        self.emit_node_event(NodeEvent::NodeUp {
+            node: Atom::new(&remote_node),
+        });
 
-        self.connections
-            .insert(remote_node.clone(), Arc::new(Mutex::new(conn)));
-
-        self.spawn_receiver_task(remote_node.clone(), read_half, timeout);
-
-        tracing::debug!("Connected to {}", remote_node);
+        tracing::debug!(%connection_id, "Connected to {}", remote_node);
         Ok(())
     }
 
@@ -227,11 +398,21 @@ impl Node {
         max_attempts: u32,
         retry_delay: Duration,
     ) -> Result<()> {
-        let remote_node = remote_node.into();
+        self.connect_with_port_and_retries(remote_node.into(), None, max_attempts, retry_delay)
+            .await
+    }
+
+    pub(crate) async fn connect_with_port_and_retries(
+        &self,
+        remote_node: String,
+        port: Option<u16>,
+        max_attempts: u32,
+        retry_delay: Duration,
+    ) -> Result<()> {
         let mut last_err = None;
 
         for attempt in 1..=max_attempts {
-            match self.connect(&remote_node).await {
+            match self.connect_with_port(remote_node.clone(), port).await {
                 Ok(()) => return Ok(()),
                 Err(e) if e.is_recoverable() => {
                     tracing::debug!(
@@ -253,94 +434,220 @@ impl Node {
         Err(last_err.expect("at least one attempt must have been made"))
     }
 
+    /// Answers a peer that still has a connection from this node. Like OTP, the new handshake
+    /// always wins: the existing connection to `node` is torn down and the peer drops its end.
+    fn answer_alive(
+        connections: &DashMap<String, Arc<Mutex<Connection>>>,
+        node_events: &broadcast::Sender<NodeEvent>,
+        node: &str,
+    ) -> bool {
+        // The receiver task sees that the connection was replaced and stays quiet
+        if let Some((_, conn)) = connections.remove(node) {
+            tracing::debug!("Replacing the connection to {} the peer still has", node);
+            tokio::spawn(async move {
+                let _ = conn.lock().await.close().await;
+            });
+            let _ = node_events.send(NodeEvent::NodeDown {
+                node: Atom::new(node),
+                reason: NodeDownReason::ConnectionClosed,
+            });
+        }
+        true
+    }
+
+    /// Checks the connection for idleness until it is dropped.
+    fn spawn_hibernation_task(connection: Weak<Mutex<Connection>>, after: Duration) {
+        let period = (after / 2).max(MIN_HIBERNATION_CHECK_PERIOD);
+        tokio::spawn(async move {
+            loop {
+                sleep(period).await;
+                let Some(conn) = connection.upgrade() else {
+                    break;
+                };
+                conn.lock().await.hibernate_if_idle();
+            }
+        });
+    }
+
     fn spawn_receiver_task(
         &self,
         remote_node: String,
-        mut read_half: edp_client::OwnedReadHalf,
-        timeout: std::time::Duration,
+        connection: Weak<Mutex<Connection>>,
+        read_half: OwnedReadHalf,
+        timeout: Duration,
+        receive_options: ReceiveOptions,
     ) {
         let registry = self.registry.clone();
+        let router = self.router.clone();
         let pending_rpcs = self.pending_rpcs.clone();
+        let pending_streams = self.pending_streams.clone();
         let connections = self.connections.clone();
+        let node_events = self.node_events.clone();
         let remote_node_clone = remote_node.clone();
-
-        tokio::spawn(async move {
-            loop {
-                let result =
-                    edp_client::Connection::receive_message_from_read_half(&mut read_half, timeout)
-                        .await;
-
-                match result {
-                    Ok((control_msg, payload)) => {
-                        let payload_len = payload.as_ref().map(|p| p.len()).unwrap_or(0);
-                        tracing::debug!(
-                            "Received control message from {}, payload size: {} bytes",
-                            remote_node,
-                            payload_len
-                        );
-                        tracing::debug!(
-                            "Control message details: {:?}, payload: {:?}",
-                            control_msg,
-                            payload
-                        );
-                        if let Err(e) =
-                            Self::route_message(&registry, &pending_rpcs, control_msg, payload)
-                                .await
-                        {
-                            tracing::error!("Failed to route message: {}", e);
-                        }
-                    }
-                    Err(e) => {
-                        if e.to_string().contains("Decode error") {
-                            tracing::warn!(
-                                "Failed to decode message from {} (likely unsupported message type): {}",
+        let span = receive_options.span.clone().unwrap_or_else(Span::none);
+        let unlink_acks = receive_options.unlink_acks;
+        let decode_accounting = receive_options.decode_accounting.clone();
+        let mut source = MessageSource::new(read_half, timeout, receive_options);
+
+        tokio::spawn(
+            async move {
+                let reason = loop {
+                    let result = source.receive().await;
+
+                    match result {
+                        Ok(ReceivedMessage {
+                            control: control_msg,
+                            payload,
+                            heap_size,
+                            ..
+                        }) => {
+                            let payload_len = payload.as_ref().map(|p| p.len()).unwrap_or(0);
+                            tracing::debug!(
+                                "Received control message from {}, payload size: {} bytes",
                                 remote_node,
-                                e
+                                payload_len
+                            );
+                            tracing::debug!(
+                                "Control message details: {:?}, payload: {:?}",
+                                control_msg,
+                                payload
                             );
-                            continue;
+                            if unlink_acks
+                                && let ControlMessage::UnlinkId {
+                                    id,
+                                    from_pid: OwnedTerm::Pid(from),
+                                    to_pid: OwnedTerm::Pid(to),
+                                } = &control_msg
+                                && let Some(conn) = connection.upgrade()
+                                && let Err(e) = conn.lock().await.unlink_ack(*id, to, from).await
+                            {
+                                tracing::warn!(
+                                    "Failed to acknowledge an unlink from {}: {}",
+                                    remote_node,
+                                    e
+                                );
+                            }
+                            if let Err(e) = Self::route_message(
+                                &registry,
+                                &router,
+                                &connections,
+                                &pending_rpcs,
+                                &pending_streams,
+                                control_msg,
+                                payload,
+                            )
+                            .await
+                            {
+                                tracing::error!("Failed to route message: {}", e);
+                            }
+                            if let Some(accounting) = &decode_accounting {
+                                accounting.release(heap_size);
+                            }
+                        }
+                        Err(e) => {
+                            if e.is_frame_decode() {
+                                tracing::warn!(
+                                    "Dropping a message from {} that failed to decode: {}",
+                                    remote_node,
+                                    e
+                                );
+                                continue;
+                            }
+                            tracing::error!("Error receiving message from {}: {}", remote_node, e);
+                            break NodeDownReason::from_error(&e);
                         }
-                        tracing::error!("Error receiving message from {}: {}", remote_node, e);
-                        break;
                     }
+                };
+
+                // The connection may have been replaced, e.g. by a cookie rotation
+                let removed = connections
+                    .remove_if(&remote_node_clone, |_, conn| {
+                        ptr::eq(Arc::as_ptr(conn), connection.as_ptr())
+                    })
+                    .is_some();
+                if removed {
+                    let _ = node_events.send(NodeEvent::NodeDown {
+                        node: Atom::new(&remote_node_clone),
+                        reason,
+                    });
                 }
+                tracing::debug!(
+                    "Receiver task for {} terminated, connection removed",
+                    remote_node
+                );
             }
-
-            connections.remove(&remote_node_clone);
-            tracing::debug!(
-                "Receiver task for {} terminated, connection removed",
-                remote_node
-            );
-        });
+            .instrument(span),
+        );
     }
 
     async fn route_message(
         registry: &ProcessRegistry,
-        pending_rpcs: &DashMap<String, oneshot::Sender<OwnedTerm>>,
+        router: &Router,
+        connections: &DashMap<String, Arc<Mutex<Connection>>>,
+        pending_rpcs: &CallTable<ExternalPid>,
+        pending_streams: &DashMap<String, mpsc::UnboundedSender<OwnedTerm>>,
         control_msg: ControlMessage,
         payload: Option<OwnedTerm>,
     ) -> Result<()> {
         match control_msg {
             ControlMessage::Send { to_pid, .. } => {
-                if let Some(body) = payload
-                    && let OwnedTerm::Pid(pid) = to_pid
-                {
-                    if let Some(handle) = registry.get(&pid).await {
-                        handle.send(Message::Regular { from: None, body }).await?;
-                    } else {
-                        let pid_str = format!("{}.{}.{}", pid.id, pid.serial, pid.creation);
-                        if let Some((_key, sender)) = pending_rpcs.remove(&pid_str) {
-                            let _ = sender.send(body);
-                        }
-                    }
-                }
+                Self::deliver(
+                    registry,
+                    router,
+                    pending_rpcs,
+                    pending_streams,
+                    None,
+                    to_pid,
+                    payload,
+                )
+                .await?;
             }
-            ControlMessage::RegSend { to_name, .. } => {
+            ControlMessage::SendSender { from_pid, to_pid } => {
+                let from = match from_pid {
+                    OwnedTerm::Pid(from) => Some(from),
+                    _ => None,
+                };
+                Self::deliver(
+                    registry,
+                    router,
+                    pending_rpcs,
+                    pending_streams,
+                    from,
+                    to_pid,
+                    payload,
+                )
+                .await?;
+            }
+            ControlMessage::RegSend {
+                from_pid, to_name, ..
+            } => {
                 if let Some(body) = payload
                     && let OwnedTerm::Atom(name) = to_name
-                    && let Some(pid) = registry.whereis(&name).await
-                    && let Some(handle) = registry.get(&pid).await
                 {
-                    handle.send(Message::Regular { from: None, body }).await?;
+                    let from = match from_pid {
+                        OwnedTerm::Pid(from) => Some(from),
+                        _ => None,
+                    };
+                    let handle = match registry.whereis(&name).await {
+                        Some(pid) => registry.get(&pid).await.map(|handle| (pid, handle)),
+                        None => None,
+                    };
+                    match handle {
+                        Some((pid, handle)) => {
+                            let result = handle.send(Message::Regular { from: None, body }).await;
+                            if let Err(Error::MailboxFull) = result
+                                && let Some(from) = &from
+                            {
+                                Self::signal_mailbox_full(connections, &pid, from).await?;
+                            }
+                            result?;
+                        }
+                        None => router.dispatch(RoutedMessage {
+                            from,
+                            to: Destination::Name(name),
+                            body,
+                        }),
+                    }
                 }
             }
             ControlMessage::Exit {
@@ -364,15 +671,97 @@ impl Node {
                 if let OwnedTerm::Pid(from) = from_proc
                     && let OwnedTerm::Pid(to) = to_pid
                     && let OwnedTerm::Reference(ref_val) = reference
-                    && let Some(handle) = registry.get(&to).await
                 {
-                    handle
-                        .send(Message::MonitorExit {
-                            monitored: from,
-                            reference: ref_val,
+                    if let Some(handle) = registry.get(&to).await {
+                        handle
+                            .send(Message::MonitorExit {
+                                monitored: from,
+                                reference: ref_val,
+                                reason,
+                            })
+                            .await?;
+                    } else if let Some(sender) = pending_streams.get(&pid_key(&to)) {
+                        let _ = sender.send(OwnedTerm::Tuple(vec![
+                            OwnedTerm::Atom(Atom::new("DOWN")),
+                            OwnedTerm::Reference(ref_val),
+                            OwnedTerm::Atom(Atom::new("process")),
+                            OwnedTerm::Pid(from),
                             reason,
-                        })
-                        .await?;
+                        ]));
+                    }
+                }
+            }
+            ControlMessage::Link { from_pid, to_pid } => {
+                if let OwnedTerm::Pid(from) = from_pid
+                    && let OwnedTerm::Pid(to) = to_pid
+                {
+                    if let Some(handle) = registry.get(&to).await {
+                        handle.add_link(from).await;
+                    } else if let Some(conn) = connection_to(connections, &from) {
+                        conn.lock()
+                            .await
+                            .link_exit(&to, &from, OwnedTerm::Atom(Atom::new("noproc")))
+                            .await?;
+                    }
+                }
+            }
+            ControlMessage::Unlink { from_pid, to_pid }
+            | ControlMessage::UnlinkId {
+                from_pid, to_pid, ..
+            } => {
+                if let OwnedTerm::Pid(from) = from_pid
+                    && let OwnedTerm::Pid(to) = to_pid
+                    && let Some(handle) = registry.get(&to).await
+                {
+                    handle.remove_link(&from).await;
+                }
+            }
+            ControlMessage::MonitorP {
+                from_pid,
+                to_proc,
+                reference,
+            } => {
+                if let OwnedTerm::Pid(from) = from_pid
+                    && let OwnedTerm::Reference(reference) = reference
+                {
+                    match Self::resolve_process(registry, &to_proc).await {
+                        Some(handle) => handle.add_monitor(from, reference).await,
+                        None => {
+                            if let Some(conn) = connection_to(connections, &from) {
+                                conn.lock()
+                                    .await
+                                    .monitor_exit(
+                                        to_proc,
+                                        &from,
+                                        &reference,
+                                        OwnedTerm::Atom(Atom::new("noproc")),
+                                    )
+                                    .await?;
+                            }
+                        }
+                    }
+                }
+            }
+            ControlMessage::DemonitorP {
+                to_proc, reference, ..
+            } => {
+                if let OwnedTerm::Reference(reference) = reference
+                    && let Some(handle) = Self::resolve_process(registry, &to_proc).await
+                {
+                    handle.remove_monitor(&reference).await;
+                }
+            }
+            ControlMessage::SpawnReply {
+                req_id, to, result, ..
+            } => {
+                if let OwnedTerm::Pid(to) = to
+                    && let Some(sender) = pending_streams.get(&pid_key(&to))
+                {
+                    let _ = sender.send(OwnedTerm::Tuple(vec![
+                        OwnedTerm::Atom(Atom::new("spawn_reply")),
+                        req_id,
+                        result,
+                    ]));
                 }
             }
             _ => {}
@@ -381,18 +770,95 @@ impl Node {
         Ok(())
     }
 
+    /// Delivers a message sent to a pid with `SEND` or `SEND_SENDER`.
+    async fn deliver(
+        registry: &ProcessRegistry,
+        router: &Router,
+        pending_rpcs: &CallTable<ExternalPid>,
+        pending_streams: &DashMap<String, mpsc::UnboundedSender<OwnedTerm>>,
+        from: Option<ExternalPid>,
+        to_pid: OwnedTerm,
+        payload: Option<OwnedTerm>,
+    ) -> Result<()> {
+        if let Some(body) = payload
+            && let OwnedTerm::Pid(pid) = to_pid
+        {
+            if let Some(handle) = registry.get(&pid).await {
+                handle.send(Message::Regular { from, body }).await?;
+            } else if pending_rpcs.contains(&pid) {
+                pending_rpcs.complete(&pid, body);
+            } else if let Some(sender) = pending_streams.get(&pid_key(&pid)) {
+                let _ = sender.send(body);
+            } else {
+                router.dispatch(RoutedMessage {
+                    from,
+                    to: Destination::Pid(pid),
+                    body,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Looks up a local process by pid or registered name.
+    async fn resolve_process(
+        registry: &ProcessRegistry,
+        proc: &OwnedTerm,
+    ) -> Option<ProcessHandle> {
+        let pid = match proc {
+            OwnedTerm::Pid(pid) => pid.clone(),
+            OwnedTerm::Atom(name) => registry.whereis(name).await?,
+            _ => return None,
+        };
+        registry.get(&pid).await
+    }
+
+    /// Sends a `mailbox_full` exit signal to a remote process whose message was rejected.
+    async fn signal_mailbox_full(
+        connections: &DashMap<String, Arc<Mutex<Connection>>>,
+        receiver: &ExternalPid,
+        sender: &ExternalPid,
+    ) -> Result<()> {
+        let conn = connections
+            .get(sender.node.as_str())
+            .map(|entry| entry.value().clone());
+        if let Some(conn) = conn {
+            conn.lock()
+                .await
+                .exit(receiver, sender, OwnedTerm::Atom(Atom::new("mailbox_full")))
+                .await?;
+        }
+        Ok(())
+    }
+
     pub async fn spawn<P: Process>(&self, process: P) -> Result<ExternalPid> {
+        self.spawn_with_mailbox(process, Mailbox::new()).await
+    }
+
+    /// Spawns a process that receives through `mailbox`, e.g. one created with
+    /// [`Mailbox::bounded`] and a non-default overflow policy.
+    pub async fn spawn_with_mailbox<P: Process>(
+        &self,
+        process: P,
+        mailbox: Mailbox,
+    ) -> Result<ExternalPid> {
         if !self.started.load(Ordering::SeqCst) {
             return Err(Error::NodeNotStarted);
         }
 
-        let mailbox = Mailbox::new();
         let pid = self
             .pid_allocator
             .allocate()
             .expect("PID allocator lock poisoned");
 
-        let handle = spawn_process(process, mailbox, self.registry.clone(), pid.clone()).await;
+        let handle = spawn_process(
+            process,
+            mailbox,
+            self.registry.clone(),
+            self.connections.clone(),
+            pid.clone(),
+        )
+        .await;
 
         self.registry.insert(pid.clone(), handle).await;
 
@@ -400,6 +866,13 @@ impl Node {
         Ok(pid)
     }
 
+    pub async fn mailbox_stats(&self, pid: &ExternalPid) -> Option<MailboxStats> {
+        self.registry
+            .get(pid)
+            .await
+            .map(|handle| handle.mailbox_stats())
+    }
+
     pub async fn register(&self, name: Atom, pid: ExternalPid) -> Result<()> {
         self.registry.register(name, pid).await
     }
@@ -432,6 +905,26 @@ impl Node {
         self.send(&pid, message).await
     }
 
+    /// Sends `message` to a process alias on a connected node, e.g. to reply to
+    /// a `gen_server:call` whose from tuple carries an `[alias | Ref]` tag.
+    pub async fn send_to_alias(&self, alias: &ExternalReference, message: OwnedTerm) -> Result<()> {
+        let node_name = alias.node.as_str();
+
+        if let Some(conn) = self.connections.get(node_name) {
+            let from = self
+                .pid_allocator
+                .allocate()
+                .expect("PID allocator lock poisoned");
+            let mut conn_guard = conn.lock().await;
+            conn_guard
+                .send_to_alias(from, alias.clone(), message)
+                .await?;
+            Ok(())
+        } else {
+            Err(Error::NodeNotConnected(node_name.to_string()))
+        }
+    }
+
     async fn send_local(&self, to: &ExternalPid, message: OwnedTerm) -> Result<()> {
         if let Some(handle) = self.registry.get(to).await {
             handle
@@ -509,15 +1002,147 @@ impl Node {
         }
     }
 
+    /// Makes `leader` the group leader of the remote process `target`, for example
+    /// to direct the I/O of a remotely spawned helper to a local process.
+    pub async fn set_group_leader(&self, target: &ExternalPid, leader: &ExternalPid) -> Result<()> {
+        let node_name = target.node.as_str();
+        if let Some(conn) = self.connections.get(node_name) {
+            let mut conn_guard = conn.lock().await;
+            conn_guard.set_group_leader(target, leader).await?;
+            Ok(())
+        } else {
+            Err(Error::NodeNotConnected(node_name.to_string()))
+        }
+    }
+
+    /// Like `net_adm:ping/1`: connects to `remote_node` if needed, then makes an
+    /// `is_auth` call to its `net_kernel` and returns the round-trip time.
+    ///
+    /// The RTT is recorded in the connection's [`ConnectionMetrics`].
+    pub async fn ping(&self, remote_node: &str) -> Result<Duration> {
+        self.ping_with_timeout(remote_node, DEFAULT_RPC_TIMEOUT)
+            .await
+    }
+
+    pub async fn ping_with_timeout(
+        &self,
+        remote_node: &str,
+        timeout: Duration,
+    ) -> Result<Duration> {
+        self.connect(remote_node).await?;
+        let conn = self
+            .connections
+            .get(remote_node)
+            .map(|entry| Arc::clone(entry.value()))
+            .ok_or_else(|| Error::NodeNotConnected(remote_node.to_string()))?;
+
+        let (reply_to_pid, pending) = self.expect_reply(timeout);
+        let reference = self.make_reference();
+        let request = OwnedTerm::Tuple(vec![
+            OwnedTerm::Atom(Atom::new("$gen_call")),
+            OwnedTerm::Tuple(vec![
+                OwnedTerm::Pid(reply_to_pid.clone()),
+                OwnedTerm::Reference(reference.clone()),
+            ]),
+            OwnedTerm::Tuple(vec![
+                OwnedTerm::Atom(Atom::new("is_auth")),
+                OwnedTerm::Atom(self.name.clone()),
+            ]),
+        ]);
+
+        let started = Instant::now();
+        let metrics = {
+            let mut conn_guard = conn.lock().await;
+            conn_guard
+                .send_to_name(reply_to_pid, Atom::new("net_kernel"), request)
+                .await?;
+            conn_guard.metrics().clone()
+        };
+
+        let reply = self.await_rpc_reply(pending).await?;
+        let rtt = started.elapsed();
+        match reply {
+            OwnedTerm::Tuple(ref elements)
+                if elements.len() == 2
+                    && elements[0] == OwnedTerm::Reference(reference)
+                    && elements[1] == OwnedTerm::Atom(Atom::new("yes")) =>
+            {
+                metrics.record_rtt(rtt);
+                Ok(rtt)
+            }
+            other => Err(Error::InvalidMessage(format!(
+                "unexpected ping reply from {}: {:?}",
+                remote_node, other
+            ))),
+        }
+    }
+
+    /// RTT samples and tick counters of the connection to `remote_node`, if connected.
+    pub async fn connection_metrics(&self, remote_node: &str) -> Option<ConnectionMetrics> {
+        let conn = self
+            .connections
+            .get(remote_node)
+            .map(|entry| Arc::clone(entry.value()))?;
+        let conn_guard = conn.lock().await;
+        Some(conn_guard.metrics().clone())
+    }
+
     pub fn make_reference(&self) -> ExternalReference {
-        let id0 = self.reference_counter.fetch_add(1, Ordering::SeqCst);
-        let id1 = self.reference_counter.fetch_add(1, Ordering::SeqCst);
-        let id2 = self.reference_counter.fetch_add(1, Ordering::SeqCst);
-        ExternalReference::new(
-            self.name.clone(),
-            self.creation.load(Ordering::SeqCst),
-            vec![id0, id1, id2],
-        )
+        new_reference(&self.name, &self.creation, &self.reference_counter)
+    }
+
+    pub(crate) fn reference_allocator(&self) -> ReferenceAllocator {
+        ReferenceAllocator {
+            node: self.name.clone(),
+            creation: Arc::clone(&self.creation),
+            reference_counter: Arc::clone(&self.reference_counter),
+        }
+    }
+
+    /// Subscribes to nodeup and nodedown events, like
+    /// `net_kernel:monitor_nodes(true, [nodedown_reason])`.
+    ///
+    /// A failed connection attempt is reported as a nodedown with `connection_setup_failed`.
+    pub fn monitor_nodes(&self) -> NodeMonitor {
+        NodeMonitor::new(self.node_events.subscribe())
+    }
+
+    pub(crate) fn emit_node_event(&self, event: NodeEvent) {
+        tracing::debug!("Node event: {:?}", event);
+        // Sending only fails when there are no subscribers
+        let _ = self.node_events.send(event);
+    }
+
+    pub fn timers(&self) -> Timers {
+        self.timers.clone()
+    }
+
+    /// See [`Timers::send_after`].
+    pub fn send_after(
+        &self,
+        after: Duration,
+        to: &ExternalPid,
+        message: OwnedTerm,
+    ) -> Result<TimerHandle> {
+        self.timers.send_after(after, to, message)
+    }
+
+    /// See [`Timers::start_timer`].
+    pub fn start_timer(
+        &self,
+        after: Duration,
+        to: &ExternalPid,
+        message: OwnedTerm,
+    ) -> Result<TimerHandle> {
+        self.timers.start_timer(after, to, message)
+    }
+
+    pub fn cancel_timer(&self, reference: &ExternalReference) -> Option<Duration> {
+        self.timers.cancel_timer(reference)
+    }
+
+    pub fn read_timer(&self, reference: &ExternalReference) -> Option<Duration> {
+        self.timers.read_timer(reference)
     }
 
     pub async fn monitor(&self, from: &ExternalPid, to: &ExternalPid) -> Result<ExternalReference> {
@@ -565,6 +1190,24 @@ impl Node {
         }
     }
 
+    pub(crate) fn allocate_pid(&self) -> ExternalPid {
+        self.pid_allocator
+            .allocate()
+            .expect("PID allocator lock poisoned")
+    }
+
+    pub(crate) fn register_stream(
+        &self,
+        pid: &ExternalPid,
+        sender: mpsc::UnboundedSender<OwnedTerm>,
+    ) {
+        self.pending_streams.insert(pid_key(pid), sender);
+    }
+
+    pub(crate) fn pending_streams(&self) -> Arc<DashMap<String, mpsc::UnboundedSender<OwnedTerm>>> {
+        self.pending_streams.clone()
+    }
+
     pub fn name(&self) -> &Atom {
         &self.name
     }
@@ -581,8 +1224,22 @@ impl Node {
         self.connections.clone()
     }
 
-    pub fn cookie(&self) -> &str {
-        &self.cookie
+    /// The id of the connection to `remote_node`. Reconnects, e.g. after a cookie
+    /// rotation, keep the id, which stays known after a disconnect.
+    pub fn connection_id(&self, remote_node: &str) -> Option<ConnectionId> {
+        self.connection_ids.get(remote_node).map(|id| *id)
+    }
+
+    /// The cookie used for new connections, see [`Node::rotate_cookie`].
+    pub fn cookie(&self) -> String {
+        self.cookie
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn set_cookie(&self, cookie: String) {
+        *self.cookie.write().unwrap_or_else(|e| e.into_inner()) = cookie;
     }
 
     pub async fn rpc_call(
@@ -610,6 +1267,100 @@ impl Node {
         response.into_rex_response().map_err(Error::from)
     }
 
+    /// Like [`Node::rpc_call`] but returns `{badrpc, ...}` replies as [`RpcError`](crate::RpcError)s.
+    pub async fn rpc_call_result(
+        &self,
+        remote_node: &str,
+        module: &str,
+        function: &str,
+        args: Vec<OwnedTerm>,
+    ) -> RpcResult {
+        rpc_result(self.rpc_call(remote_node, module, function, args).await)
+    }
+
+    /// Like [`Node::rpc_call_with_timeout`] but returns `{badrpc, ...}` replies as
+    /// [`RpcError`](crate::RpcError)s.
+    pub async fn rpc_call_result_with_timeout(
+        &self,
+        remote_node: &str,
+        module: &str,
+        function: &str,
+        args: Vec<OwnedTerm>,
+        timeout: Duration,
+    ) -> RpcResult {
+        rpc_result(
+            self.rpc_call_with_timeout(remote_node, module, function, args, timeout)
+                .await,
+        )
+    }
+
+    /// Like [`Node::rpc_call_with_deadline`] with a deadline `timeout` from now.
+    pub async fn rpc_call_with_remote_timeout(
+        &self,
+        remote_node: &str,
+        module: &str,
+        function: &str,
+        args: Vec<OwnedTerm>,
+        timeout: Duration,
+    ) -> Result<OwnedTerm> {
+        self.rpc_call_with_deadline(
+            remote_node,
+            module,
+            function,
+            args,
+            Instant::now() + timeout,
+        )
+        .await
+    }
+
+    /// Performs an RPC call that is bounded by `deadline` on both sides.
+    ///
+    /// The call is executed via `erpc:call/5` with the remaining time, so the
+    /// remote node terminates the work once the deadline passes instead of
+    /// leaving it running. Remote `{erpc, timeout}` errors are returned as
+    /// [`Error::RpcTimeout`].
+    pub async fn rpc_call_with_deadline(
+        &self,
+        remote_node: &str,
+        module: &str,
+        function: &str,
+        args: Vec<OwnedTerm>,
+        deadline: Instant,
+    ) -> Result<OwnedTerm> {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(Error::RpcTimeout(remaining));
+        }
+
+        let erpc_args = vec![
+            OwnedTerm::Atom(Atom::new(remote_node)),
+            OwnedTerm::Atom(Atom::new(module)),
+            OwnedTerm::Atom(Atom::new(function)),
+            OwnedTerm::List(args),
+            OwnedTerm::Integer(remaining.as_millis().min(u32::MAX as u128) as i64),
+        ];
+
+        let response = self
+            .rpc_call_raw_with_timeout(
+                remote_node,
+                "erpc",
+                "call",
+                erpc_args,
+                remaining + DEFAULT_DEADLINE_GRACE,
+            )
+            .await
+            .map_err(|e| match e {
+                Error::RpcTimeout(_) => Error::RpcTimeout(remaining),
+                other => other,
+            })?
+            .into_rex_response()?;
+
+        if is_erpc_timeout(&response) {
+            return Err(Error::RpcTimeout(remaining));
+        }
+        Ok(response)
+    }
+
     pub async fn rpc_call_raw(
         &self,
         remote_node: &str,
@@ -629,10 +1380,24 @@ impl Node {
         args: Vec<OwnedTerm>,
         timeout: Duration,
     ) -> Result<OwnedTerm> {
-        let reply_to_pid = self
-            .pid_allocator
-            .allocate()
-            .expect("PID allocator lock poisoned");
+        let pending = self
+            .send_rpc_request(remote_node, module, function, args, timeout)
+            .await?;
+        self.await_rpc_reply(pending).await
+    }
+
+    /// Sends an RPC request to `rex` without waiting for the reply, so that
+    /// several requests can be in flight on one connection. The reply is awaited
+    /// for up to `timeout` after the request is sent.
+    pub(crate) async fn send_rpc_request(
+        &self,
+        remote_node: &str,
+        module: &str,
+        function: &str,
+        args: Vec<OwnedTerm>,
+        timeout: Duration,
+    ) -> Result<PendingRpc> {
+        let (reply_to_pid, pending) = self.expect_reply(timeout);
 
         let call_request = OwnedTerm::Tuple(vec![
             OwnedTerm::Pid(reply_to_pid.clone()),
@@ -645,13 +1410,6 @@ impl Node {
             ]),
         ]);
 
-        let (tx, rx) = oneshot::channel();
-        let pid_str = format!(
-            "{}.{}.{}",
-            reply_to_pid.id, reply_to_pid.serial, reply_to_pid.creation
-        );
-        self.pending_rpcs.insert(pid_str.clone(), tx);
-
         tracing::debug!("RPC call_request: {:?}", call_request);
         tracing::debug!("RPC reply_to_pid: {:?}", reply_to_pid);
 
@@ -665,20 +1423,95 @@ impl Node {
             tracing::trace!("Message sent to rex");
         } else {
             tracing::error!("No connection found for node: {}", remote_node);
-            self.pending_rpcs.remove(&pid_str);
             return Err(Error::NodeNotConnected(remote_node.to_string()));
         }
 
-        let response = tokio::time::timeout(timeout, rx).await;
+        Ok(pending)
+    }
+
+    /// Allocates a pid whose first incoming message, if it arrives within `timeout`,
+    /// completes the returned [`PendingRpc`].
+    pub(crate) fn expect_reply(&self, timeout: Duration) -> (ExternalPid, PendingRpc) {
+        let pid = self.allocate_pid();
+        let pending = self.pending_rpcs.register(pid.clone(), timeout);
+        (pid, pending)
+    }
+
+    pub(crate) async fn await_rpc_reply(&self, pending: PendingRpc) -> Result<OwnedTerm> {
+        pending.wait().await.map_err(|e| match e {
+            Error::CallTimeout(timeout) => Error::RpcTimeout(timeout),
+            other => other,
+        })
+    }
+}
 
-        if response.is_err() {
-            self.pending_rpcs.remove(&pid_str);
+/// An RPC request that has been sent and is waiting for its `{rex, Result}` reply.
+pub(crate) type PendingRpc = PendingCall<ExternalPid>;
+
+pub(crate) fn pid_key(pid: &ExternalPid) -> String {
+    format!("{}.{}.{}", pid.id, pid.serial, pid.creation)
+}
+
+/// Returns `true` if `result` is an RPC result that reports an `{erpc, timeout}` error.
+///
+/// Handles both the bare error term and the `{badrpc, {'EXIT', {Reason, Stack}}}`
+/// wrapping `rex` applies to exceptions.
+pub fn is_erpc_timeout(result: &OwnedTerm) -> bool {
+    match result {
+        OwnedTerm::Tuple(elements) if elements.len() == 2 => {
+            let (tag, reason) = (&elements[0], &elements[1]);
+            if tag.is_atom_with_name("erpc") {
+                return reason.is_atom_with_name("timeout");
+            }
+            if tag.is_atom_with_name("badrpc") {
+                return reason.is_atom_with_name("timeout") || is_erpc_timeout(reason);
+            }
+            if tag.is_atom_with_name("EXIT") {
+                return is_erpc_timeout(reason);
+            }
+            is_erpc_timeout(tag)
         }
+        _ => false,
+    }
+}
+
+/// Where a receiver task reads messages from.
+enum MessageSource {
+    ReadHalf {
+        read_half: OwnedReadHalf,
+        timeout: Duration,
+        options: ReceiveOptions,
+    },
+    Pipeline(DecodePipeline),
+}
 
-        let response = response
-            .map_err(|_| Error::RpcTimeout(timeout))?
-            .map_err(|_| Error::RpcCancelled)?;
+impl MessageSource {
+    fn new(read_half: OwnedReadHalf, timeout: Duration, options: ReceiveOptions) -> Self {
+        match options.decode_offload {
+            Some(offload) => {
+                Self::Pipeline(DecodePipeline::new(read_half, timeout, options, offload))
+            }
+            None => Self::ReadHalf {
+                read_half,
+                timeout,
+                options,
+            },
+        }
+    }
 
-        Ok(response)
+    async fn receive(&mut self) -> edp_client::Result<ReceivedMessage> {
+        match self {
+            Self::ReadHalf {
+                read_half,
+                timeout,
+                options,
+            } => {
+                Connection::receive_envelope_from_read_half_with_options(
+                    read_half, *timeout, options,
+                )
+                .await
+            }
+            Self::Pipeline(pipeline) => pipeline.receive_envelope().await,
+        }
     }
 }
@@ -0,0 +1,207 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Snapshots of the processes running on a remote node.
+//!
+//! [`process_snapshot`] lists the processes with `erlang:processes/0` and then
+//! requests `process_info/2` for them in batches. Several batches are kept in
+//! flight on the connection at once, so collection does not pay a full round trip
+//! per process on nodes with many processes.
+
+use crate::errors::{Error, Result};
+use crate::node::{DEFAULT_RPC_TIMEOUT, Node, PendingRpc};
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalFun, ExternalPid, Mfa};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+pub const DEFAULT_SNAPSHOT_BATCH_SIZE: usize = 500;
+pub const DEFAULT_SNAPSHOT_PIPELINE_DEPTH: usize = 8;
+
+/// The `process_info/2` items collected for every process.
+pub const PROCESS_INFO_ITEMS: &[&str] = &[
+    "registered_name",
+    "current_function",
+    "initial_call",
+    "status",
+    "memory",
+    "message_queue_len",
+    "reductions",
+    "heap_size",
+    "total_heap_size",
+    "stack_size",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotOptions {
+    /// Number of processes per `process_info/2` request.
+    pub batch_size: usize,
+    /// Number of batch requests in flight at once.
+    pub pipeline_depth: usize,
+    /// Timeout for each individual request.
+    pub timeout: Duration,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_SNAPSHOT_BATCH_SIZE,
+            pipeline_depth: DEFAULT_SNAPSHOT_PIPELINE_DEPTH,
+            timeout: DEFAULT_RPC_TIMEOUT,
+        }
+    }
+}
+
+impl SnapshotOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_pipeline_depth(mut self, pipeline_depth: usize) -> Self {
+        self.pipeline_depth = pipeline_depth.max(1);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub pid: ExternalPid,
+    pub registered_name: Option<Atom>,
+    pub current_function: Option<Mfa>,
+    pub initial_call: Option<Mfa>,
+    pub status: Option<Atom>,
+    pub memory: u64,
+    pub message_queue_len: u64,
+    pub reductions: u64,
+    pub heap_size: u64,
+    pub total_heap_size: u64,
+    pub stack_size: u64,
+}
+
+impl ProcessInfo {
+    /// Builds a `ProcessInfo` from a `process_info/2` result.
+    ///
+    /// Returns `None` for `undefined`, which `process_info/2` returns for
+    /// processes that exited after being listed.
+    pub fn from_proplist(pid: ExternalPid, info: &OwnedTerm) -> Option<Self> {
+        if !info.is_list() {
+            return None;
+        }
+        let count = |key: &str| info.proplist_get_i64(key).unwrap_or(0).max(0) as u64;
+
+        Some(Self {
+            pid,
+            // a process without a registered name reports []
+            registered_name: info.proplist_get_atom("registered_name").cloned(),
+            current_function: info
+                .proplist_get_atom_key("current_function")
+                .and_then(Mfa::try_from_term),
+            initial_call: info
+                .proplist_get_atom_key("initial_call")
+                .and_then(Mfa::try_from_term),
+            status: info.proplist_get_atom("status").cloned(),
+            memory: count("memory"),
+            message_queue_len: count("message_queue_len"),
+            reductions: count("reductions"),
+            heap_size: count("heap_size"),
+            total_heap_size: count("total_heap_size"),
+            stack_size: count("stack_size"),
+        })
+    }
+}
+
+/// Collects [`ProcessInfo`] for every process on `remote_node`.
+///
+/// Processes that exit while the snapshot is being taken are left out.
+pub async fn process_snapshot(
+    node: &Node,
+    remote_node: &str,
+    opts: &SnapshotOptions,
+) -> Result<Vec<ProcessInfo>> {
+    let processes = node
+        .rpc_call_with_timeout(remote_node, "erlang", "processes", vec![], opts.timeout)
+        .await?;
+    let pids: Vec<ExternalPid> = processes
+        .as_list()
+        .ok_or_else(|| Error::InvalidMessage(format!("erlang:processes/0 returned {processes}")))?
+        .iter()
+        .filter_map(|p| p.as_pid().cloned())
+        .collect();
+
+    let items = OwnedTerm::List(
+        PROCESS_INFO_ITEMS
+            .iter()
+            .map(|item| OwnedTerm::Atom(Atom::new(*item)))
+            .collect(),
+    );
+    let mut batches = pids.chunks(opts.batch_size.max(1));
+    let mut in_flight: VecDeque<(&[ExternalPid], PendingRpc)> = VecDeque::new();
+    let mut snapshot = Vec::with_capacity(pids.len());
+
+    loop {
+        while in_flight.len() < opts.pipeline_depth.max(1)
+            && let Some(batch) = batches.next()
+        {
+            let pending = node
+                .send_rpc_request(
+                    remote_node,
+                    "lists",
+                    "zipwith",
+                    batch_args(batch, &items),
+                    opts.timeout,
+                )
+                .await?;
+            in_flight.push_back((batch, pending));
+        }
+
+        let Some((batch, pending)) = in_flight.pop_front() else {
+            break;
+        };
+        let reply = node.await_rpc_reply(pending).await?.into_rex_response()?;
+        let infos = reply.as_list().ok_or_else(|| {
+            Error::InvalidMessage(format!("process_info/2 batch returned {reply}"))
+        })?;
+        snapshot.extend(
+            batch
+                .iter()
+                .zip(infos)
+                .filter_map(|(pid, info)| ProcessInfo::from_proplist(pid.clone(), info)),
+        );
+    }
+
+    Ok(snapshot)
+}
+
+// lists:zipwith(fun erlang:process_info/2, Pids, [Items, Items, ...])
+fn batch_args(batch: &[ExternalPid], items: &OwnedTerm) -> Vec<OwnedTerm> {
+    vec![
+        OwnedTerm::ExternalFun(ExternalFun::new(
+            Atom::new("erlang"),
+            Atom::new("process_info"),
+            2,
+        )),
+        OwnedTerm::List(batch.iter().cloned().map(OwnedTerm::Pid).collect()),
+        OwnedTerm::List(vec![items.clone(); batch.len()]),
+    ]
+}
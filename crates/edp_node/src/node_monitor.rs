@@ -0,0 +1,128 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cluster membership events, the equivalent of
+//! `net_kernel:monitor_nodes(true, [nodedown_reason])`.
+
+use edp_client::Error as ClientError;
+use erltf::OwnedTerm;
+use erltf::types::Atom;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+/// Events buffered per subscriber. A subscriber that falls further behind skips the oldest events.
+pub const NODE_EVENT_BUFFER_SIZE: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeDownReason {
+    /// The connection could not be established, e.g. the handshake failed
+    ConnectionSetupFailed,
+    /// The peer closed the connection or the socket failed
+    ConnectionClosed,
+    /// Nothing, not even a tick, was received from the peer within the connection timeout
+    NetTickTimeout,
+}
+
+impl NodeDownReason {
+    pub fn from_error(error: &ClientError) -> Self {
+        if error.is_timeout() {
+            NodeDownReason::NetTickTimeout
+        } else {
+            NodeDownReason::ConnectionClosed
+        }
+    }
+
+    /// The reason as reported by `net_kernel`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeDownReason::ConnectionSetupFailed => "connection_setup_failed",
+            NodeDownReason::ConnectionClosed => "connection_closed",
+            NodeDownReason::NetTickTimeout => "net_tick_timeout",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeEvent {
+    NodeUp { node: Atom },
+    NodeDown { node: Atom, reason: NodeDownReason },
+}
+
+impl NodeEvent {
+    pub fn node(&self) -> &Atom {
+        match self {
+            NodeEvent::NodeUp { node } | NodeEvent::NodeDown { node, .. } => node,
+        }
+    }
+
+    pub fn is_up(&self) -> bool {
+        matches!(self, NodeEvent::NodeUp { .. })
+    }
+
+    /// Returns the message `net_kernel` delivers: `{nodeup, Node, []}` or
+    /// `{nodedown, Node, [{nodedown_reason, Reason}]}`.
+    pub fn to_term(&self) -> OwnedTerm {
+        match self {
+            NodeEvent::NodeUp { node } => OwnedTerm::Tuple(vec![
+                OwnedTerm::Atom(Atom::new("nodeup")),
+                OwnedTerm::Atom(node.clone()),
+                OwnedTerm::Nil,
+            ]),
+            NodeEvent::NodeDown { node, reason } => OwnedTerm::Tuple(vec![
+                OwnedTerm::Atom(Atom::new("nodedown")),
+                OwnedTerm::Atom(node.clone()),
+                OwnedTerm::List(vec![OwnedTerm::Tuple(vec![
+                    OwnedTerm::Atom(Atom::new("nodedown_reason")),
+                    OwnedTerm::Atom(Atom::new(reason.as_str())),
+                ])]),
+            ]),
+        }
+    }
+}
+
+/// A subscription to [`NodeEvent`]s, created with [`crate::Node::monitor_nodes`].
+pub struct NodeMonitor {
+    receiver: broadcast::Receiver<NodeEvent>,
+}
+
+impl NodeMonitor {
+    pub(crate) fn new(receiver: broadcast::Receiver<NodeEvent>) -> Self {
+        Self { receiver }
+    }
+
+    /// Waits for the next event. Returns `None` once the node is dropped.
+    pub async fn recv(&mut self) -> Option<NodeEvent> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Node monitor lagged behind, {} events skipped", skipped);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    pub fn try_recv(&mut self) -> Option<NodeEvent> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) => return Some(event),
+                Err(TryRecvError::Lagged(skipped)) => {
+                    tracing::warn!("Node monitor lagged behind, {} events skipped", skipped);
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => return None,
+            }
+        }
+    }
+}
@@ -13,16 +13,24 @@
 // limitations under the License.
 
 use crate::errors::Result;
-use crate::mailbox::{Mailbox, Message};
+use crate::mailbox::{Mailbox, MailboxSender, MailboxStats, Message};
 use crate::registry::ProcessRegistry;
+use dashmap::DashMap;
+use edp_client::Connection;
 use erltf::OwnedTerm;
 use erltf::types::{Atom, ExternalPid, ExternalReference};
+use std::any::Any;
 use std::collections::HashSet;
 use std::future::Future;
 use std::sync::Arc;
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::{Mutex, RwLock};
+
+const PANIC: &str = "panic";
 
 pub trait Process: Send + 'static {
+    /// Called with the process' pid before it handles its first message.
+    fn spawned(&mut self, _pid: &ExternalPid) {}
+
     fn handle_message(&mut self, msg: Message) -> impl Future<Output = Result<()>> + Send + '_;
 
     fn terminate(&mut self) -> impl Future<Output = ()> + Send + '_ {
@@ -33,13 +41,13 @@ pub trait Process: Send + 'static {
 #[derive(Clone)]
 pub struct ProcessHandle {
     pub pid: ExternalPid,
-    pub mailbox_sender: mpsc::Sender<Message>,
+    pub mailbox_sender: MailboxSender,
     links: Arc<RwLock<HashSet<ExternalPid>>>,
     monitors: Arc<RwLock<HashSet<(ExternalPid, ExternalReference)>>>,
 }
 
 impl ProcessHandle {
-    pub fn new(pid: ExternalPid, mailbox_sender: mpsc::Sender<Message>) -> Self {
+    pub fn new(pid: ExternalPid, mailbox_sender: MailboxSender) -> Self {
         Self {
             pid,
             mailbox_sender,
@@ -49,10 +57,11 @@ impl ProcessHandle {
     }
 
     pub async fn send(&self, msg: Message) -> Result<()> {
-        self.mailbox_sender
-            .send(msg)
-            .await
-            .map_err(|_| crate::errors::Error::MailboxClosed)
+        self.mailbox_sender.send(msg).await
+    }
+
+    pub fn mailbox_stats(&self) -> MailboxStats {
+        self.mailbox_sender.stats()
     }
 
     pub async fn add_link(&self, other_pid: ExternalPid) {
@@ -84,9 +93,10 @@ impl ProcessHandle {
 }
 
 pub async fn spawn_process<P: Process>(
-    mut process: P,
-    mut mailbox: Mailbox,
+    process: P,
+    mailbox: Mailbox,
     registry: Arc<ProcessRegistry>,
+    connections: Arc<DashMap<String, Arc<Mutex<Connection>>>>,
     pid: ExternalPid,
 ) -> ProcessHandle {
     let sender = mailbox.sender();
@@ -94,23 +104,20 @@ pub async fn spawn_process<P: Process>(
     let handle_clone = handle.clone();
 
     tokio::spawn(async move {
-        let exit_reason = loop {
-            match mailbox.recv().await {
-                Ok(msg) => {
-                    if let Err(e) = process.handle_message(msg).await {
-                        tracing::error!("Process {} error: {}", pid, e);
-                        break OwnedTerm::Atom(Atom::new("error"));
-                    }
-                }
-                Err(_) => {
-                    break OwnedTerm::Atom(Atom::new("normal"));
-                }
+        // Running the process in its own task turns a panic into an exit reason
+        let exit_reason = match tokio::spawn(run_process(process, mailbox, pid.clone())).await {
+            Ok(reason) => reason,
+            Err(e) if e.is_panic() => {
+                let reason = panic_reason(e.into_panic());
+                tracing::error!("Process {} panicked: {}", pid, reason);
+                reason
             }
+            Err(_) => OwnedTerm::Atom(Atom::new("killed")),
         };
 
-        process.terminate().await;
-
-        if let Err(e) = propagate_exit_signals(&handle_clone, &registry, exit_reason).await {
+        if let Err(e) =
+            propagate_exit_signals(&handle_clone, &registry, &connections, exit_reason).await
+        {
             tracing::error!("Failed to propagate exit signals for {}: {}", pid, e);
         }
 
@@ -120,35 +127,105 @@ pub async fn spawn_process<P: Process>(
     handle
 }
 
+async fn run_process<P: Process>(
+    mut process: P,
+    mut mailbox: Mailbox,
+    pid: ExternalPid,
+) -> OwnedTerm {
+    process.spawned(&pid);
+    let exit_reason = loop {
+        match mailbox.recv().await {
+            Ok(msg) => {
+                if let Err(e) = process.handle_message(msg).await {
+                    tracing::error!("Process {} error: {}", pid, e);
+                    break OwnedTerm::Atom(Atom::new("error"));
+                }
+            }
+            Err(_) => {
+                break OwnedTerm::Atom(Atom::new("normal"));
+            }
+        }
+    };
+
+    process.terminate().await;
+    exit_reason
+}
+
+/// The exit reason of a panicked process: `{panic, Message}`, with the panic message as a binary.
+fn panic_reason(payload: Box<dyn Any + Send>) -> OwnedTerm {
+    let message = match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .unwrap_or_default(),
+    };
+    OwnedTerm::Tuple(vec![
+        OwnedTerm::Atom(Atom::new(PANIC)),
+        OwnedTerm::Binary(message.into_bytes()),
+    ])
+}
+
+/// Delivers exit signals to linked processes and `DOWN` messages to monitoring processes,
+/// local ones via their mailboxes and remote ones over the connection to their node.
 async fn propagate_exit_signals(
     handle: &ProcessHandle,
     registry: &ProcessRegistry,
+    connections: &DashMap<String, Arc<Mutex<Connection>>>,
     reason: OwnedTerm,
 ) -> Result<()> {
     let links = handle.get_links().await;
     for linked_pid in links {
-        if let Some(linked_handle) = registry.get(&linked_pid).await {
-            let _ = linked_handle
-                .send(Message::Exit {
-                    from: handle.pid.clone(),
-                    reason: reason.clone(),
-                })
-                .await;
+        if linked_pid.node == handle.pid.node {
+            if let Some(linked_handle) = registry.get(&linked_pid).await {
+                let _ = linked_handle
+                    .send(Message::Exit {
+                        from: handle.pid.clone(),
+                        reason: reason.clone(),
+                    })
+                    .await;
+            }
+        } else if let Some(conn) = connection_to(connections, &linked_pid) {
+            conn.lock()
+                .await
+                .link_exit(&handle.pid, &linked_pid, reason.clone())
+                .await?;
         }
     }
 
     let monitors = handle.get_monitors().await;
     for (monitoring_pid, reference) in monitors {
-        if let Some(monitoring_handle) = registry.get(&monitoring_pid).await {
-            let _ = monitoring_handle
-                .send(Message::MonitorExit {
-                    monitored: handle.pid.clone(),
-                    reference,
-                    reason: reason.clone(),
-                })
-                .await;
+        if monitoring_pid.node == handle.pid.node {
+            if let Some(monitoring_handle) = registry.get(&monitoring_pid).await {
+                let _ = monitoring_handle
+                    .send(Message::MonitorExit {
+                        monitored: handle.pid.clone(),
+                        reference,
+                        reason: reason.clone(),
+                    })
+                    .await;
+            }
+        } else if let Some(conn) = connection_to(connections, &monitoring_pid) {
+            conn.lock()
+                .await
+                .monitor_exit(
+                    OwnedTerm::Pid(handle.pid.clone()),
+                    &monitoring_pid,
+                    &reference,
+                    reason.clone(),
+                )
+                .await?;
         }
     }
 
     Ok(())
 }
+
+pub(crate) fn connection_to(
+    connections: &DashMap<String, Arc<Mutex<Connection>>>,
+    pid: &ExternalPid,
+) -> Option<Arc<Mutex<Connection>>> {
+    connections
+        .get(pid.node.as_str())
+        .map(|entry| entry.value().clone())
+}
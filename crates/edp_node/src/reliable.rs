@@ -0,0 +1,341 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sends with delivery receipts.
+//!
+//! A plain send gives no indication of whether the message reached its destination,
+//! in particular around reconnects. [`ReliableSender`] routes messages through a
+//! helper process on the remote node which delivers them and replies with a receipt.
+//! Sends that are not acknowledged in time are retried with the same idempotency key,
+//! and the helper does not deliver a message with a key it has already seen.
+
+use crate::errors::{Error, Result};
+use crate::node::Node;
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid};
+use std::time::Duration;
+use tokio::time::sleep;
+
+pub const RELIABLE_HELPER_MODULE: &str = "edp_reliable";
+
+pub const DEFAULT_RELIABLE_MAX_ATTEMPTS: u32 = 3;
+pub const DEFAULT_RELIABLE_ACK_TIMEOUT: Duration = Duration::from_secs(5);
+pub const DEFAULT_RELIABLE_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// Source of the helper module, one form per string.
+///
+/// The helper remembers the last 10000 idempotency keys.
+pub const RELIABLE_HELPER_FORMS: &[&str] = &[
+    "-module(edp_reliable).",
+    "-export([ensure_started/0, init/0]).",
+    "ensure_started() ->
+        case whereis(edp_reliable) of
+            undefined ->
+                Pid = spawn(edp_reliable, init, []),
+                try register(edp_reliable, Pid) of
+                    true -> Pid
+                catch
+                    error:badarg ->
+                        exit(Pid, kill),
+                        whereis(edp_reliable)
+                end;
+            Pid ->
+                Pid
+        end.",
+    "init() -> loop(#{}, queue:new()).",
+    "loop(Seen, Order) ->
+        receive
+            {deliver, ReplyTo, Key, To, Msg} ->
+                case maps:find(Key, Seen) of
+                    {ok, Status} ->
+                        ReplyTo ! {edp_reliable_ack, Key, Status, duplicate},
+                        loop(Seen, Order);
+                    error ->
+                        Status = deliver(To, Msg),
+                        ReplyTo ! {edp_reliable_ack, Key, Status, new},
+                        {Seen1, Order1} = remember(Key, Status, Seen, Order),
+                        loop(Seen1, Order1)
+                end;
+            _ ->
+                loop(Seen, Order)
+        end.",
+    "deliver(To, Msg) when is_pid(To) ->
+        case is_process_alive(To) of
+            true -> To ! Msg, delivered;
+            false -> noproc
+        end;
+    deliver(To, Msg) when is_atom(To) ->
+        case whereis(To) of
+            undefined -> noproc;
+            Pid -> Pid ! Msg, delivered
+        end.",
+    "remember(Key, Status, Seen, Order) ->
+        Seen1 = maps:put(Key, Status, Seen),
+        Order1 = queue:in(Key, Order),
+        case queue:len(Order1) > 10000 of
+            true ->
+                {{value, Oldest}, Order2} = queue:out(Order1),
+                {maps:remove(Oldest, Seen1), Order2};
+            false ->
+                {Seen1, Order1}
+        end.",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// How long to wait for a receipt before retrying.
+    pub ack_timeout: Duration,
+    /// Delay between attempts.
+    pub retry_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_RELIABLE_MAX_ATTEMPTS,
+            ack_timeout: DEFAULT_RELIABLE_ACK_TIMEOUT,
+            retry_delay: DEFAULT_RELIABLE_RETRY_DELAY,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_ack_timeout(mut self, ack_timeout: Duration) -> Self {
+        self.ack_timeout = ack_timeout;
+        self
+    }
+
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+}
+
+/// The recipient of a reliable send, a process or a registered name on the remote node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReliableTarget {
+    Pid(ExternalPid),
+    Name(Atom),
+}
+
+impl ReliableTarget {
+    fn to_term(&self) -> OwnedTerm {
+        match self {
+            ReliableTarget::Pid(pid) => OwnedTerm::Pid(pid.clone()),
+            ReliableTarget::Name(name) => OwnedTerm::Atom(name.clone()),
+        }
+    }
+}
+
+impl From<ExternalPid> for ReliableTarget {
+    fn from(pid: ExternalPid) -> Self {
+        ReliableTarget::Pid(pid)
+    }
+}
+
+impl From<Atom> for ReliableTarget {
+    fn from(name: Atom) -> Self {
+        ReliableTarget::Name(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeliveryReceipt {
+    pub idempotency_key: OwnedTerm,
+    /// Number of attempts it took to get the receipt.
+    pub attempts: u32,
+    /// `true` if the message had already been delivered by an earlier attempt
+    /// (or an earlier send with the same key) and was not delivered again.
+    pub duplicate: bool,
+}
+
+/// Sends messages to processes on one remote node and waits for delivery receipts.
+pub struct ReliableSender {
+    remote_node: String,
+    helper: ExternalPid,
+    policy: RetryPolicy,
+}
+
+impl ReliableSender {
+    /// Loads the helper module on `remote_node` and starts the helper process
+    /// unless it is already running.
+    pub async fn start(node: &Node, remote_node: &str) -> Result<Self> {
+        let helper = start_helper(node, remote_node).await?;
+        Ok(Self {
+            remote_node: remote_node.to_string(),
+            helper,
+            policy: RetryPolicy::default(),
+        })
+    }
+
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn remote_node(&self) -> &str {
+        &self.remote_node
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    /// Sends `message` with a freshly generated idempotency key.
+    pub async fn send_reliable(
+        &mut self,
+        node: &Node,
+        to: impl Into<ReliableTarget>,
+        message: OwnedTerm,
+    ) -> Result<DeliveryReceipt> {
+        let key = OwnedTerm::Reference(node.make_reference());
+        self.send_reliable_with_key(node, to, message, key).await
+    }
+
+    /// Sends `message` with the given idempotency key. A message is delivered at most
+    /// once per key, so retrying a failed call with the same key is safe.
+    pub async fn send_reliable_with_key(
+        &mut self,
+        node: &Node,
+        to: impl Into<ReliableTarget>,
+        message: OwnedTerm,
+        idempotency_key: OwnedTerm,
+    ) -> Result<DeliveryReceipt> {
+        let to = to.into();
+        if let ReliableTarget::Pid(pid) = &to
+            && pid.node.as_str() != self.remote_node
+        {
+            return Err(Error::InvalidMessage(format!(
+                "{} is not a process on {}",
+                pid, self.remote_node
+            )));
+        }
+
+        let mut last_error = None;
+        for attempt in 1..=self.policy.max_attempts {
+            if attempt > 1 {
+                sleep(self.policy.retry_delay).await;
+                if let Err(e) = self.reconnect(node).await {
+                    tracing::debug!("Reconnecting to {} failed: {}", self.remote_node, e);
+                    last_error = Some(e);
+                    continue;
+                }
+            }
+
+            match self
+                .attempt(node, &to, message.clone(), idempotency_key.clone())
+                .await
+            {
+                Ok((status, duplicate)) => {
+                    return match status.atom_name() {
+                        Some("delivered") => Ok(DeliveryReceipt {
+                            idempotency_key,
+                            attempts: attempt,
+                            duplicate,
+                        }),
+                        _ => Err(match to {
+                            ReliableTarget::Pid(pid) => Error::ProcessNotFound(pid),
+                            ReliableTarget::Name(name) => Error::NameNotRegistered(name),
+                        }),
+                    };
+                }
+                Err(e) => {
+                    tracing::debug!(
+                        "Reliable send attempt {} to {} failed: {}",
+                        attempt,
+                        self.remote_node,
+                        e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        tracing::warn!(
+            "Reliable send to {} not acknowledged: {:?}",
+            self.remote_node,
+            last_error
+        );
+        Err(Error::DeliveryNotAcknowledged(self.policy.max_attempts))
+    }
+
+    async fn attempt(
+        &self,
+        node: &Node,
+        to: &ReliableTarget,
+        message: OwnedTerm,
+        key: OwnedTerm,
+    ) -> Result<(OwnedTerm, bool)> {
+        let (reply_to, pending) = node.expect_reply(self.policy.ack_timeout);
+        let request = OwnedTerm::Tuple(vec![
+            OwnedTerm::Atom(Atom::new("deliver")),
+            OwnedTerm::Pid(reply_to),
+            key,
+            to.to_term(),
+            message,
+        ]);
+        node.send(&self.helper, request).await?;
+
+        match node.await_rpc_reply(pending).await? {
+            OwnedTerm::Tuple(mut elements)
+                if elements.len() == 4 && elements[0].is_atom_with_name("edp_reliable_ack") =>
+            {
+                let duplicate = elements[3].is_atom_with_name("duplicate");
+                Ok((elements.swap_remove(2), duplicate))
+            }
+            other => Err(Error::InvalidMessage(format!(
+                "unexpected delivery receipt: {other}"
+            ))),
+        }
+    }
+
+    // The helper outlives the connection, but it may have been restarted since
+    async fn reconnect(&mut self, node: &Node) -> Result<()> {
+        if !node.connections().contains_key(&self.remote_node) {
+            node.connect(self.remote_node.clone()).await?;
+            self.helper = start_helper(node, &self.remote_node).await?;
+        }
+        Ok(())
+    }
+}
+
+async fn start_helper(node: &Node, remote_node: &str) -> Result<ExternalPid> {
+    node.ensure_module_loaded(remote_node, RELIABLE_HELPER_MODULE, RELIABLE_HELPER_FORMS)
+        .await?;
+    let helper = node
+        .rpc_call(
+            remote_node,
+            RELIABLE_HELPER_MODULE,
+            "ensure_started",
+            vec![],
+        )
+        .await?;
+    match helper {
+        OwnedTerm::Pid(pid) => Ok(pid),
+        other => Err(Error::SpawnFailed(format!(
+            "failed to start delivery helper: {other}"
+        ))),
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dispatch of received messages that no local process is registered for.
+//!
+//! A [`Router`] hands a message sent to a registered name to the handler of the most
+//! specific matching [`NamePattern`]: the exact name, then the longest prefix, then
+//! the fallback. Processes registered with [`crate::Node::register`] take precedence.
+//! Messages to unknown pids and to names no pattern matches are dead letters: they go
+//! to the dead-letter handler, which logs them unless replaced.
+
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Invoked on the connection's receiver task, so it should hand the message
+/// off, e.g. to a channel, rather than block.
+pub type RouteHandler = Arc<dyn Fn(RoutedMessage) + Send + Sync>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NamePattern {
+    /// Matches this name only
+    Exact(Atom),
+    /// Matches names that start with this prefix
+    Prefix(String),
+    /// Matches any name
+    Fallback,
+}
+
+impl NamePattern {
+    pub fn exact(name: impl AsRef<str>) -> Self {
+        NamePattern::Exact(Atom::new(name))
+    }
+
+    pub fn prefix(prefix: impl Into<String>) -> Self {
+        NamePattern::Prefix(prefix.into())
+    }
+
+    pub fn matches(&self, name: &Atom) -> bool {
+        match self {
+            NamePattern::Exact(exact) => exact == name,
+            NamePattern::Prefix(prefix) => name.as_str().starts_with(prefix.as_str()),
+            NamePattern::Fallback => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Destination {
+    Pid(ExternalPid),
+    Name(Atom),
+}
+
+impl fmt::Display for Destination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Destination::Pid(pid) => write!(f, "{}", pid),
+            Destination::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutedMessage {
+    /// The sender, known for messages sent to a name
+    pub from: Option<ExternalPid>,
+    pub to: Destination,
+    pub body: OwnedTerm,
+}
+
+/// How many messages a [`Router`] dispatched, by kind of route.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RouterStats {
+    pub exact: u64,
+    pub prefix: u64,
+    pub fallback: u64,
+    pub dead_letters: u64,
+}
+
+#[derive(Default)]
+struct Routes {
+    exact: HashMap<Atom, RouteHandler>,
+    /// Longest prefix first
+    prefixes: Vec<(String, RouteHandler)>,
+    fallback: Option<RouteHandler>,
+    dead_letter: Option<RouteHandler>,
+}
+
+#[derive(Default)]
+pub struct Router {
+    routes: RwLock<Routes>,
+    exact: AtomicU64,
+    prefix: AtomicU64,
+    fallback: AtomicU64,
+    dead_letters: AtomicU64,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Routes names matching `pattern` to `handler`, replacing the handler
+    /// previously registered for the same pattern.
+    pub fn register<F>(&self, pattern: NamePattern, handler: F)
+    where
+        F: Fn(RoutedMessage) + Send + Sync + 'static,
+    {
+        let handler: RouteHandler = Arc::new(handler);
+        let mut routes = self.routes.write().expect("router lock poisoned");
+        match pattern {
+            NamePattern::Exact(name) => {
+                routes.exact.insert(name, handler);
+            }
+            NamePattern::Prefix(prefix) => {
+                routes.prefixes.retain(|(existing, _)| *existing != prefix);
+                let at = routes
+                    .prefixes
+                    .partition_point(|(existing, _)| existing.len() >= prefix.len());
+                routes.prefixes.insert(at, (prefix, handler));
+            }
+            NamePattern::Fallback => routes.fallback = Some(handler),
+        }
+    }
+
+    /// Returns false if no handler was registered for `pattern`.
+    pub fn unregister(&self, pattern: &NamePattern) -> bool {
+        let mut routes = self.routes.write().expect("router lock poisoned");
+        match pattern {
+            NamePattern::Exact(name) => routes.exact.remove(name).is_some(),
+            NamePattern::Prefix(prefix) => {
+                let before = routes.prefixes.len();
+                routes.prefixes.retain(|(existing, _)| existing != prefix);
+                routes.prefixes.len() != before
+            }
+            NamePattern::Fallback => routes.fallback.take().is_some(),
+        }
+    }
+
+    /// Replaces the default dead-letter handler, which logs dead letters at debug level.
+    pub fn set_dead_letter_handler<F>(&self, handler: F)
+    where
+        F: Fn(RoutedMessage) + Send + Sync + 'static,
+    {
+        self.routes
+            .write()
+            .expect("router lock poisoned")
+            .dead_letter = Some(Arc::new(handler));
+    }
+
+    /// Restores the default dead-letter handler.
+    pub fn clear_dead_letter_handler(&self) {
+        self.routes
+            .write()
+            .expect("router lock poisoned")
+            .dead_letter = None;
+    }
+
+    /// Hands `message` to the handler of the most specific pattern that matches
+    /// its destination name. Messages to pids and unmatched names are dead letters.
+    pub fn dispatch(&self, message: RoutedMessage) {
+        let handler = {
+            let routes = self.routes.read().expect("router lock poisoned");
+            let matched = match &message.to {
+                Destination::Name(name) => Self::find(&routes, name),
+                Destination::Pid(_) => None,
+            };
+            match matched {
+                Some((handler, counter)) => {
+                    self.counter(counter).fetch_add(1, Ordering::Relaxed);
+                    Some(handler)
+                }
+                None => {
+                    self.dead_letters.fetch_add(1, Ordering::Relaxed);
+                    routes.dead_letter.clone()
+                }
+            }
+        };
+        match handler {
+            Some(handler) => handler(message),
+            None => tracing::debug!("Dead letter to {}: {:?}", message.to, message.body),
+        }
+    }
+
+    pub fn stats(&self) -> RouterStats {
+        RouterStats {
+            exact: self.exact.load(Ordering::Relaxed),
+            prefix: self.prefix.load(Ordering::Relaxed),
+            fallback: self.fallback.load(Ordering::Relaxed),
+            dead_letters: self.dead_letters.load(Ordering::Relaxed),
+        }
+    }
+
+    fn find(routes: &Routes, name: &Atom) -> Option<(RouteHandler, RouteKind)> {
+        if let Some(handler) = routes.exact.get(name) {
+            return Some((Arc::clone(handler), RouteKind::Exact));
+        }
+        if let Some((_, handler)) = routes
+            .prefixes
+            .iter()
+            .find(|(prefix, _)| name.as_str().starts_with(prefix.as_str()))
+        {
+            return Some((Arc::clone(handler), RouteKind::Prefix));
+        }
+        routes
+            .fallback
+            .as_ref()
+            .map(|handler| (Arc::clone(handler), RouteKind::Fallback))
+    }
+
+    fn counter(&self, kind: RouteKind) -> &AtomicU64 {
+        match kind {
+            RouteKind::Exact => &self.exact,
+            RouteKind::Prefix => &self.prefix,
+            RouteKind::Fallback => &self.fallback,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum RouteKind {
+    Exact,
+    Prefix,
+    Fallback,
+}
@@ -0,0 +1,337 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A pool of connections to one remote node for RPC-heavy workloads.
+//!
+//! The distribution protocol allows a single connection between two node names,
+//! so every pool member is a separate local node named after the pool's base name
+//! with a numeric suffix: `rpc@host` becomes `rpc_0@host`, `rpc_1@host` and so on.
+
+use crate::errors::{Error, Result};
+use crate::node::{DEFAULT_RPC_TIMEOUT, Node};
+use erltf::OwnedTerm;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+pub const DEFAULT_RPC_POOL_SIZE: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct RpcPoolConfig {
+    pub local_node_name: String,
+    pub remote_node_name: String,
+    pub cookie: String,
+    pub size: usize,
+    pub timeout: Duration,
+    pub hidden: bool,
+}
+
+impl RpcPoolConfig {
+    pub fn new(
+        local_node_name: impl Into<String>,
+        remote_node_name: impl Into<String>,
+        cookie: impl Into<String>,
+    ) -> Self {
+        Self {
+            local_node_name: local_node_name.into(),
+            remote_node_name: remote_node_name.into(),
+            cookie: cookie.into(),
+            size: DEFAULT_RPC_POOL_SIZE,
+            timeout: DEFAULT_RPC_TIMEOUT,
+            hidden: true,
+        }
+    }
+
+    /// Sets the number of connections. A pool always has at least one.
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size.max(1);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Returns the local node name used by the pool member at `index`.
+    pub fn member_node_name(&self, index: usize) -> String {
+        match self.local_node_name.split_once('@') {
+            Some((name, host)) => format!("{name}_{index}@{host}"),
+            None => format!("{}_{index}", self.local_node_name),
+        }
+    }
+}
+
+struct PoolMember {
+    node: Node,
+    in_flight: AtomicUsize,
+    calls: AtomicU64,
+    failures: AtomicU64,
+    reconnects: AtomicU64,
+    connect_lock: Mutex<()>,
+}
+
+impl PoolMember {
+    fn is_connected(&self, remote_node: &str) -> bool {
+        self.node.connections().contains_key(remote_node)
+    }
+}
+
+/// Decrements the member's in-flight counter when the call completes.
+struct Checkout {
+    member: Arc<PoolMember>,
+}
+
+impl Checkout {
+    fn new(member: Arc<PoolMember>) -> Self {
+        member.in_flight.fetch_add(1, Ordering::SeqCst);
+        Self { member }
+    }
+}
+
+impl Drop for Checkout {
+    fn drop(&mut self) {
+        self.member.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolMemberMetrics {
+    pub node_name: String,
+    pub connected: bool,
+    pub in_flight: usize,
+    pub calls: u64,
+    pub failures: u64,
+    pub reconnects: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcPoolMetrics {
+    pub size: usize,
+    pub connected: usize,
+    pub in_flight: usize,
+    pub calls: u64,
+    pub failures: u64,
+    pub reconnects: u64,
+    pub members: Vec<PoolMemberMetrics>,
+}
+
+/// Spreads RPC calls to one remote node over several connections.
+///
+/// Each call goes to the connected member with the fewest calls in flight.
+/// Broken connections are detected when a call fails or the connection
+/// closes, and are re-established on a later checkout.
+pub struct RpcPool {
+    config: RpcPoolConfig,
+    members: Vec<Arc<PoolMember>>,
+    next: AtomicUsize,
+}
+
+impl RpcPool {
+    /// Starts the member nodes and connects them to the remote node.
+    ///
+    /// Fails only if no member can connect; the others reconnect on demand.
+    pub async fn start(config: RpcPoolConfig) -> Result<Self> {
+        let mut members = Vec::with_capacity(config.size);
+        for index in 0..config.size {
+            let name = config.member_node_name(index);
+            let mut node = if config.hidden {
+                Node::new_hidden(name, config.cookie.clone())
+            } else {
+                Node::new(name, config.cookie.clone())
+            };
+            node.start(0).await?;
+            members.push(Arc::new(PoolMember {
+                node,
+                in_flight: AtomicUsize::new(0),
+                calls: AtomicU64::new(0),
+                failures: AtomicU64::new(0),
+                reconnects: AtomicU64::new(0),
+                connect_lock: Mutex::new(()),
+            }));
+        }
+
+        let pool = Self {
+            config,
+            members,
+            next: AtomicUsize::new(0),
+        };
+
+        let mut last_err = None;
+        for member in &pool.members {
+            if let Err(e) = member.node.connect(&pool.config.remote_node_name).await {
+                tracing::debug!(
+                    "Pool member {} failed to connect to {}: {}",
+                    member.node.name(),
+                    pool.config.remote_node_name,
+                    e
+                );
+                last_err = Some(e);
+            }
+        }
+
+        match last_err {
+            Some(e) if pool.connected_count() == 0 => Err(e),
+            _ => Ok(pool),
+        }
+    }
+
+    pub fn config(&self) -> &RpcPoolConfig {
+        &self.config
+    }
+
+    pub fn remote_node(&self) -> &str {
+        &self.config.remote_node_name
+    }
+
+    pub fn size(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn connected_count(&self) -> usize {
+        self.members
+            .iter()
+            .filter(|m| m.is_connected(&self.config.remote_node_name))
+            .count()
+    }
+
+    pub async fn rpc_call(
+        &self,
+        module: &str,
+        function: &str,
+        args: Vec<OwnedTerm>,
+    ) -> Result<OwnedTerm> {
+        self.rpc_call_with_timeout(module, function, args, self.config.timeout)
+            .await
+    }
+
+    pub async fn rpc_call_with_timeout(
+        &self,
+        module: &str,
+        function: &str,
+        args: Vec<OwnedTerm>,
+        timeout: Duration,
+    ) -> Result<OwnedTerm> {
+        let checkout = self.checkout().await?;
+        let member = &checkout.member;
+        member.calls.fetch_add(1, Ordering::Relaxed);
+
+        let result = member
+            .node
+            .rpc_call_with_timeout(self.remote_node(), module, function, args, timeout)
+            .await;
+
+        self.record_result(member, &result);
+        result
+    }
+
+    /// See [`Node::rpc_call_with_deadline`].
+    pub async fn rpc_call_with_deadline(
+        &self,
+        module: &str,
+        function: &str,
+        args: Vec<OwnedTerm>,
+        deadline: Instant,
+    ) -> Result<OwnedTerm> {
+        let checkout = self.checkout().await?;
+        let member = &checkout.member;
+        member.calls.fetch_add(1, Ordering::Relaxed);
+
+        let result = member
+            .node
+            .rpc_call_with_deadline(self.remote_node(), module, function, args, deadline)
+            .await;
+
+        self.record_result(member, &result);
+        result
+    }
+
+    fn record_result(&self, member: &PoolMember, result: &Result<OwnedTerm>) {
+        if let Err(e) = result {
+            member.failures.fetch_add(1, Ordering::Relaxed);
+            if matches!(e, Error::Client(_) | Error::NodeNotConnected(_)) {
+                member.node.connections().remove(self.remote_node());
+            }
+        }
+    }
+
+    pub fn metrics(&self) -> RpcPoolMetrics {
+        let members: Vec<PoolMemberMetrics> = self
+            .members
+            .iter()
+            .map(|m| PoolMemberMetrics {
+                node_name: m.node.name().to_string(),
+                connected: m.is_connected(self.remote_node()),
+                in_flight: m.in_flight.load(Ordering::SeqCst),
+                calls: m.calls.load(Ordering::Relaxed),
+                failures: m.failures.load(Ordering::Relaxed),
+                reconnects: m.reconnects.load(Ordering::Relaxed),
+            })
+            .collect();
+
+        RpcPoolMetrics {
+            size: members.len(),
+            connected: members.iter().filter(|m| m.connected).count(),
+            in_flight: members.iter().map(|m| m.in_flight).sum(),
+            calls: members.iter().map(|m| m.calls).sum(),
+            failures: members.iter().map(|m| m.failures).sum(),
+            reconnects: members.iter().map(|m| m.reconnects).sum(),
+            members,
+        }
+    }
+
+    async fn checkout(&self) -> Result<Checkout> {
+        let len = self.members.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % len;
+        let order = (0..len).map(|i| &self.members[(start + i) % len]);
+
+        let least_loaded = order
+            .clone()
+            .filter(|m| m.is_connected(self.remote_node()))
+            .min_by_key(|m| m.in_flight.load(Ordering::SeqCst));
+        if let Some(member) = least_loaded {
+            return Ok(Checkout::new(member.clone()));
+        }
+
+        let mut last_err = None;
+        for member in order {
+            match self.reconnect(member).await {
+                Ok(()) => return Ok(Checkout::new(member.clone())),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| Error::NodeNotConnected(self.remote_node().to_string())))
+    }
+
+    async fn reconnect(&self, member: &PoolMember) -> Result<()> {
+        let _guard = member.connect_lock.lock().await;
+        if member.is_connected(self.remote_node()) {
+            return Ok(());
+        }
+        member.node.connect(self.remote_node()).await?;
+        member.reconnects.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(
+            "Pool member {} reconnected to {}",
+            member.node.name(),
+            self.remote_node()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed RPC results: `{badrpc, ...}` replies classified into [`RpcError`] variants.
+//!
+//! `rex` reports a failed call as `{badrpc, Reason}`, and an exception raised by the
+//! called function as `{badrpc, {'EXIT', {Reason, Stack}}}` (errors) or
+//! `{badrpc, {'EXIT', Reason}}` (exits). Calls made through `erpc:call` wrap the
+//! remote exception once more, as `{exception, Reason, Stack}` or `{exception, Reason}`.
+//!
+//! Note that `rex` returns the value of a `throw` as a regular result, so
+//! [`RpcError::Throw`] only covers throws that escaped as `{nocatch, Value}` errors.
+
+use crate::errors::{Error, Result};
+use erltf::OwnedTerm;
+use std::result::Result as StdResult;
+use thiserror::Error;
+
+const BADRPC: &str = "badrpc";
+const EXIT: &str = "EXIT";
+const EXCEPTION: &str = "exception";
+const NOCATCH: &str = "nocatch";
+
+/// The result of an RPC call with failures classified, see [`rpc_result`].
+pub type RpcResult = StdResult<OwnedTerm, RpcError>;
+
+/// A failed RPC call. Every variant but [`RpcError::Transport`] keeps the
+/// `{badrpc, ...}` term it was parsed from in `term`. Terms are boxed to keep
+/// the error small.
+#[derive(Error, Debug)]
+pub enum RpcError {
+    /// `{badrpc, Reason}` that does not carry an exception, e.g. `nodedown` or `timeout`
+    #[error("RPC failed: {reason}")]
+    BadRpc {
+        reason: Box<OwnedTerm>,
+        term: Box<OwnedTerm>,
+    },
+
+    /// The called function exited with `reason`
+    #[error("RPC call exited: {reason}")]
+    Exit {
+        reason: Box<OwnedTerm>,
+        term: Box<OwnedTerm>,
+    },
+
+    /// The called function threw `value` and nothing caught it
+    #[error("RPC call threw: {value}")]
+    Throw {
+        value: Box<OwnedTerm>,
+        term: Box<OwnedTerm>,
+    },
+
+    /// The called function raised an error
+    #[error("RPC call raised an error: {reason}")]
+    Error {
+        reason: Box<OwnedTerm>,
+        stacktrace: Box<OwnedTerm>,
+        term: Box<OwnedTerm>,
+    },
+
+    /// The call could not be made or its reply did not arrive
+    #[error(transparent)]
+    Transport(#[from] Error),
+}
+
+impl RpcError {
+    /// The `{badrpc, ...}` reply this error was parsed from, `None` for transport errors.
+    pub fn term(&self) -> Option<&OwnedTerm> {
+        match self {
+            RpcError::BadRpc { term, .. }
+            | RpcError::Exit { term, .. }
+            | RpcError::Throw { term, .. }
+            | RpcError::Error { term, .. } => Some(term),
+            RpcError::Transport(_) => None,
+        }
+    }
+
+    pub fn is_transport(&self) -> bool {
+        matches!(self, RpcError::Transport(_))
+    }
+
+    pub fn is_exception(&self) -> bool {
+        matches!(
+            self,
+            RpcError::Exit { .. } | RpcError::Throw { .. } | RpcError::Error { .. }
+        )
+    }
+}
+
+/// Classifies the result of an RPC call such as [`Node::rpc_call`](crate::Node::rpc_call).
+///
+/// `{badrpc, ...}` replies become [`RpcError`]s, every other reply is returned as is.
+pub fn rpc_result(response: Result<OwnedTerm>) -> RpcResult {
+    let term = response?;
+    match term.as_tuple() {
+        Some([tag, reason]) if tag.is_atom_with_name(BADRPC) => {
+            let reason = reason.clone();
+            Err(classify_badrpc(reason, Box::new(term)))
+        }
+        _ => Ok(term),
+    }
+}
+
+fn classify_badrpc(reason: OwnedTerm, term: Box<OwnedTerm>) -> RpcError {
+    let Some([tag, exit_reason]) = reason.as_tuple() else {
+        return RpcError::BadRpc {
+            reason: Box::new(reason),
+            term,
+        };
+    };
+    if !tag.is_atom_with_name(EXIT) {
+        return RpcError::BadRpc {
+            reason: Box::new(reason),
+            term,
+        };
+    }
+
+    match exit_reason.as_tuple() {
+        // A remote error re-raised by erpc:call
+        Some([exception, _local_stack])
+            if let Some([tag, reason, stacktrace]) = exception.as_tuple()
+                && tag.is_atom_with_name(EXCEPTION) =>
+        {
+            RpcError::Error {
+                reason: Box::new(reason.clone()),
+                stacktrace: Box::new(stacktrace.clone()),
+                term,
+            }
+        }
+        // A remote exit re-raised by erpc:call
+        Some([tag, reason]) if tag.is_atom_with_name(EXCEPTION) => RpcError::Exit {
+            reason: Box::new(reason.clone()),
+            term,
+        },
+        Some([reason, stacktrace])
+            if is_stacktrace(stacktrace)
+                && let Some([tag, value]) = reason.as_tuple()
+                && tag.is_atom_with_name(NOCATCH) =>
+        {
+            RpcError::Throw {
+                value: Box::new(value.clone()),
+                term,
+            }
+        }
+        Some([reason, stacktrace]) if is_stacktrace(stacktrace) => RpcError::Error {
+            reason: Box::new(reason.clone()),
+            stacktrace: Box::new(stacktrace.clone()),
+            term,
+        },
+        _ => RpcError::Exit {
+            reason: Box::new(exit_reason.clone()),
+            term,
+        },
+    }
+}
+
+/// A non-empty list of `{Module, Function, Arity | Args, Location}` entries.
+fn is_stacktrace(term: &OwnedTerm) -> bool {
+    term.as_list().is_some_and(|frames| {
+        !frames.is_empty()
+            && frames
+                .iter()
+                .all(|frame| frame.as_tuple().is_some_and(|e| e.len() == 4))
+    })
+}
@@ -0,0 +1,286 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Streamed RPC for results too large to send as a single message.
+//!
+//! The call runs in a helper process spawned on the remote node with
+//! `spawn_request`. The helper serialises the result with `term_to_binary/1`
+//! and sends it back in bounded chunks, which [`RpcStream`] reassembles.
+
+use crate::errors::{Error, Result};
+use crate::node::{DEFAULT_RPC_TIMEOUT, Node, pid_key};
+use bytes::Bytes;
+use dashmap::DashMap;
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid, ExternalReference};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+pub const DEFAULT_STREAM_CHUNK_SIZE: usize = 1024 * 1024;
+
+pub const STREAM_SHIM_MODULE: &str = "edp_rpc_stream";
+
+/// Source of the helper module, one form per string.
+pub const STREAM_SHIM_FORMS: &[&str] = &[
+    "-module(edp_rpc_stream).",
+    "-export([run/5]).",
+    "run(ReplyTo, M, F, A, ChunkSize) ->
+        case whereis(user) of
+            undefined -> ok;
+            User -> group_leader(User, self())
+        end,
+        try apply(M, F, A) of
+            Value ->
+                Bin = term_to_binary(Value),
+                send_chunks(ReplyTo, Bin, ChunkSize, 0),
+                ReplyTo ! {edp_stream_done, self(), byte_size(Bin)}
+        catch
+            Class:Reason ->
+                ReplyTo ! {edp_stream_error, self(), {Class, Reason}}
+        end.",
+    "send_chunks(ReplyTo, Bin, ChunkSize, Seq) when byte_size(Bin) > ChunkSize ->
+        <<Chunk:ChunkSize/binary, Rest/binary>> = Bin,
+        ReplyTo ! {edp_stream_chunk, self(), Seq, Chunk},
+        send_chunks(ReplyTo, Rest, ChunkSize, Seq + 1);
+    send_chunks(ReplyTo, Bin, _ChunkSize, Seq) ->
+        ReplyTo ! {edp_stream_chunk, self(), Seq, Bin}.",
+];
+
+impl Node {
+    /// Compiles and loads the streaming helper module on `remote_node`
+    /// unless it is already loaded.
+    pub async fn ensure_stream_shim_loaded(&self, remote_node: &str) -> Result<()> {
+        self.ensure_module_loaded(remote_node, STREAM_SHIM_MODULE, STREAM_SHIM_FORMS)
+            .await
+    }
+
+    pub async fn rpc_call_streamed(
+        &self,
+        remote_node: &str,
+        module: &str,
+        function: &str,
+        args: Vec<OwnedTerm>,
+    ) -> Result<RpcStream> {
+        self.rpc_call_streamed_with_chunk_size(
+            remote_node,
+            module,
+            function,
+            args,
+            DEFAULT_STREAM_CHUNK_SIZE,
+        )
+        .await
+    }
+
+    /// Runs `module:function(args...)` in a helper process on `remote_node` and
+    /// returns a stream of the serialised result in chunks of at most `chunk_size` bytes.
+    pub async fn rpc_call_streamed_with_chunk_size(
+        &self,
+        remote_node: &str,
+        module: &str,
+        function: &str,
+        args: Vec<OwnedTerm>,
+        chunk_size: usize,
+    ) -> Result<RpcStream> {
+        let conn = self
+            .connections()
+            .get(remote_node)
+            .map(|c| c.clone())
+            .ok_or_else(|| Error::NodeNotConnected(remote_node.to_string()))?;
+
+        self.ensure_stream_shim_loaded(remote_node).await?;
+
+        let reply_to_pid = self.allocate_pid();
+        let req_id = self.make_reference();
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.register_stream(&reply_to_pid, tx);
+
+        let stream = RpcStream {
+            rx,
+            key: pid_key(&reply_to_pid),
+            pending_streams: self.pending_streams(),
+            req_id: req_id.clone(),
+            helper: None,
+            next_seq: 0,
+            received_bytes: 0,
+            finished: false,
+            idle_timeout: DEFAULT_RPC_TIMEOUT,
+        };
+
+        let helper_args = vec![
+            OwnedTerm::Pid(reply_to_pid.clone()),
+            OwnedTerm::Atom(Atom::new(module)),
+            OwnedTerm::Atom(Atom::new(function)),
+            OwnedTerm::List(args),
+            OwnedTerm::Integer(chunk_size.max(1) as i64),
+        ];
+        conn.lock()
+            .await
+            .spawn_request(
+                &req_id,
+                &reply_to_pid,
+                &reply_to_pid,
+                Atom::new(STREAM_SHIM_MODULE),
+                Atom::new("run"),
+                helper_args,
+                vec![OwnedTerm::Atom(Atom::new("monitor"))],
+            )
+            .await?;
+
+        Ok(stream)
+    }
+}
+
+/// Chunks of a streamed RPC result, in order.
+///
+/// Dropping the stream stops routing further chunks to it.
+pub struct RpcStream {
+    rx: mpsc::UnboundedReceiver<OwnedTerm>,
+    key: String,
+    pending_streams: Arc<DashMap<String, mpsc::UnboundedSender<OwnedTerm>>>,
+    req_id: ExternalReference,
+    helper: Option<ExternalPid>,
+    next_seq: i64,
+    received_bytes: usize,
+    finished: bool,
+    idle_timeout: Duration,
+}
+
+impl RpcStream {
+    /// Sets how long to wait for the next message from the helper.
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    pub fn helper_pid(&self) -> Option<&ExternalPid> {
+        self.helper.as_ref()
+    }
+
+    pub fn received_bytes(&self) -> usize {
+        self.received_bytes
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the next chunk, or `None` once the whole result has been received.
+    /// Chunks decoded as shared binaries are returned without copying.
+    pub async fn next_chunk(&mut self) -> Result<Option<Bytes>> {
+        while !self.finished {
+            let message = timeout(self.idle_timeout, self.rx.recv())
+                .await
+                .map_err(|_| Error::RpcTimeout(self.idle_timeout))?
+                .ok_or(Error::RpcCancelled)?;
+            if let Some(chunk) = self.handle(message)? {
+                return Ok(Some(chunk));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Receives all remaining chunks and decodes the reassembled result.
+    pub async fn collect(mut self) -> Result<OwnedTerm> {
+        let mut buf = Vec::new();
+        while let Some(chunk) = self.next_chunk().await? {
+            buf.extend_from_slice(&chunk);
+        }
+        erltf::decode(&buf).map_err(|e| Error::RpcStreamFailed(e.to_string()))
+    }
+
+    fn handle(&mut self, message: OwnedTerm) -> Result<Option<Bytes>> {
+        let OwnedTerm::Tuple(mut elements) = message else {
+            return Err(Error::InvalidMessage(format!(
+                "unexpected stream message: {message}"
+            )));
+        };
+        let tag = elements.first().and_then(|t| t.atom_name()).unwrap_or("");
+
+        match (tag, elements.len()) {
+            ("spawn_reply", 3) => {
+                if elements[1] != OwnedTerm::Reference(self.req_id.clone()) {
+                    return Ok(None);
+                }
+                match elements.swap_remove(2) {
+                    OwnedTerm::Pid(pid) => {
+                        self.helper = Some(pid);
+                        Ok(None)
+                    }
+                    reason => self.fail(format!("spawn_request failed: {reason}")),
+                }
+            }
+            ("edp_stream_chunk", 4) => {
+                let seq = elements[2].as_integer().unwrap_or(-1);
+                if seq != self.next_seq {
+                    return self.fail(format!(
+                        "chunk {seq} arrived out of order, expected {}",
+                        self.next_seq
+                    ));
+                }
+                let chunk = match elements.swap_remove(3) {
+                    OwnedTerm::Binary(chunk) => Bytes::from(chunk),
+                    OwnedTerm::SharedBinary(chunk) => chunk,
+                    _ => return self.fail("chunk is not a binary".to_string()),
+                };
+                self.next_seq += 1;
+                self.received_bytes += chunk.len();
+                Ok(Some(chunk))
+            }
+            ("edp_stream_done", 3) => {
+                let expected = elements[2].as_integer().unwrap_or(-1);
+                self.finish();
+                if expected != self.received_bytes as i64 {
+                    return Err(Error::RpcStreamFailed(format!(
+                        "received {} bytes, expected {expected}",
+                        self.received_bytes
+                    )));
+                }
+                Ok(None)
+            }
+            ("edp_stream_error", 3) => {
+                let reason = elements.swap_remove(2);
+                self.fail(format!("remote call raised: {reason}"))
+            }
+            ("DOWN", 5) => {
+                if elements[1] != OwnedTerm::Reference(self.req_id.clone()) {
+                    return Ok(None);
+                }
+                let reason = elements.swap_remove(4);
+                self.fail(format!("helper process exited: {reason}"))
+            }
+            _ => Err(Error::InvalidMessage(format!(
+                "unexpected stream message: {}",
+                OwnedTerm::Tuple(elements)
+            ))),
+        }
+    }
+
+    fn fail(&mut self, reason: String) -> Result<Option<Bytes>> {
+        self.finish();
+        Err(Error::RpcStreamFailed(reason))
+    }
+
+    fn finish(&mut self) {
+        self.finished = true;
+        self.pending_streams.remove(&self.key);
+    }
+}
+
+impl Drop for RpcStream {
+    fn drop(&mut self) {
+        self.pending_streams.remove(&self.key);
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Timers with the semantics of `erlang:send_after/3` and `erlang:start_timer/3`.
+//!
+//! [`Timers::send_after`] delivers the message as is, [`Timers::start_timer`] delivers
+//! `{timeout, TimerRef, Msg}`. Both return a [`TimerHandle`] that can cancel the timer
+//! or report the time left. Timers only target processes on the local node, and a
+//! message for a process that has exited by the time the timer fires is discarded.
+
+use crate::errors::{Error, Result};
+use crate::mailbox::Message;
+use crate::node::new_reference;
+use crate::registry::ProcessRegistry;
+use dashmap::DashMap;
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid, ExternalReference};
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU32;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, sleep_until};
+
+pub const TIMEOUT_TAG: &str = "timeout";
+
+struct TimerEntry {
+    deadline: Instant,
+    task: JoinHandle<()>,
+}
+
+type TimerTable = DashMap<ExternalReference, TimerEntry>;
+
+#[derive(Clone)]
+pub struct Timers {
+    node: Atom,
+    creation: Arc<AtomicU32>,
+    reference_counter: Arc<AtomicU32>,
+    registry: Arc<ProcessRegistry>,
+    active: Arc<TimerTable>,
+}
+
+impl Timers {
+    pub(crate) fn new(
+        node: Atom,
+        creation: Arc<AtomicU32>,
+        reference_counter: Arc<AtomicU32>,
+        registry: Arc<ProcessRegistry>,
+    ) -> Self {
+        Self {
+            node,
+            creation,
+            reference_counter,
+            registry,
+            active: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Sends `msg` to `to` after `after`, like `erlang:send_after/3`.
+    pub fn send_after(
+        &self,
+        after: Duration,
+        to: &ExternalPid,
+        msg: OwnedTerm,
+    ) -> Result<TimerHandle> {
+        self.schedule(after, to, |_| msg)
+    }
+
+    /// Sends `{timeout, TimerRef, Msg}` to `to` after `after`, like `erlang:start_timer/3`.
+    pub fn start_timer(
+        &self,
+        after: Duration,
+        to: &ExternalPid,
+        msg: OwnedTerm,
+    ) -> Result<TimerHandle> {
+        self.schedule(after, to, |reference| timeout_message(reference, msg))
+    }
+
+    /// Cancels a timer and returns the time that was left, or `None` if it already
+    /// fired or was cancelled.
+    pub fn cancel_timer(&self, reference: &ExternalReference) -> Option<Duration> {
+        cancel(&self.active, reference)
+    }
+
+    /// Returns the time left before a timer fires.
+    pub fn read_timer(&self, reference: &ExternalReference) -> Option<Duration> {
+        remaining(&self.active, reference)
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.active.len()
+    }
+
+    fn schedule(
+        &self,
+        after: Duration,
+        to: &ExternalPid,
+        make_message: impl FnOnce(&ExternalReference) -> OwnedTerm,
+    ) -> Result<TimerHandle> {
+        if to.node != self.node {
+            return Err(Error::ProcessNotFound(to.clone()));
+        }
+
+        let reference = new_reference(&self.node, &self.creation, &self.reference_counter);
+        let body = make_message(&reference);
+        let deadline = Instant::now() + after;
+
+        let registry = self.registry.clone();
+        let active = self.active.clone();
+        let to = to.clone();
+        let task_reference = reference.clone();
+        // Holding the entry keeps the task from removing it before it has been inserted
+        let entry = self.active.entry(reference.clone());
+        let task = tokio::spawn(async move {
+            sleep_until(deadline).await;
+            if active.remove(&task_reference).is_none() {
+                return;
+            }
+            if let Some(handle) = registry.get(&to).await {
+                let _ = handle.send(Message::Regular { from: None, body }).await;
+            }
+        });
+        entry.insert(TimerEntry { deadline, task });
+
+        Ok(TimerHandle {
+            reference,
+            active: self.active.clone(),
+        })
+    }
+}
+
+/// A running timer. Dropping the handle does not cancel the timer.
+#[derive(Clone)]
+pub struct TimerHandle {
+    reference: ExternalReference,
+    active: Arc<TimerTable>,
+}
+
+impl TimerHandle {
+    pub fn reference(&self) -> &ExternalReference {
+        &self.reference
+    }
+
+    pub fn cancel(&self) -> Option<Duration> {
+        cancel(&self.active, &self.reference)
+    }
+
+    pub fn remaining(&self) -> Option<Duration> {
+        remaining(&self.active, &self.reference)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.contains_key(&self.reference)
+    }
+}
+
+impl fmt::Debug for TimerHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimerHandle")
+            .field("reference", &self.reference)
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+/// Builds `{timeout, TimerRef, Msg}`.
+pub fn timeout_message(reference: &ExternalReference, msg: OwnedTerm) -> OwnedTerm {
+    OwnedTerm::Tuple(vec![
+        OwnedTerm::Atom(Atom::new(TIMEOUT_TAG)),
+        OwnedTerm::Reference(reference.clone()),
+        msg,
+    ])
+}
+
+/// Splits a `{timeout, TimerRef, Msg}` message into the timer reference and the message.
+pub fn parse_timeout_message(term: &OwnedTerm) -> Option<(&ExternalReference, &OwnedTerm)> {
+    match term {
+        OwnedTerm::Tuple(elements) => match elements.as_slice() {
+            [OwnedTerm::Atom(tag), OwnedTerm::Reference(reference), msg]
+                if tag.as_str() == TIMEOUT_TAG =>
+            {
+                Some((reference, msg))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn cancel(active: &TimerTable, reference: &ExternalReference) -> Option<Duration> {
+    let (_, entry) = active.remove(reference)?;
+    entry.task.abort();
+    Some(entry.deadline.saturating_duration_since(Instant::now()))
+}
+
+fn remaining(active: &TimerTable, reference: &ExternalReference) -> Option<Duration> {
+    active
+        .get(reference)
+        .map(|entry| entry.deadline.saturating_duration_since(Instant::now()))
+}
@@ -0,0 +1,505 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracing processes on a remote node.
+//!
+//! `erlang:trace/3` only accepts a local process as the tracer, so [`Tracer::start`]
+//! loads a small relay module on the remote node and spawns a relay process that
+//! forwards every trace message to this node. The relay exits when the connection
+//! goes down, which also stops the tracing it was set up for.
+
+use crate::errors::{Error, Result};
+use crate::node::{Node, pid_key};
+use dashmap::DashMap;
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid, Mfa};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time;
+
+pub const TRACE_RELAY_MODULE: &str = "edp_trace_relay";
+
+/// Source of the relay module, one form per string.
+pub const TRACE_RELAY_FORMS: &[&str] = &[
+    "-module(edp_trace_relay).",
+    "-export([start/1, init/1]).",
+    "start(Receiver) -> spawn(edp_trace_relay, init, [Receiver]).",
+    "init(Receiver) ->
+        erlang:monitor(process, Receiver),
+        loop(Receiver).",
+    "loop(Receiver) ->
+        receive
+            {'DOWN', _, process, Receiver, _} -> exit(normal);
+            {edp_trace_relay, stop} -> exit(normal);
+            Msg ->
+                Receiver ! Msg,
+                loop(Receiver)
+        end.",
+];
+
+/// Which processes `erlang:trace/3` applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceTarget {
+    All,
+    Existing,
+    New,
+    Pid(ExternalPid),
+}
+
+impl TraceTarget {
+    fn to_term(&self) -> OwnedTerm {
+        match self {
+            TraceTarget::All => OwnedTerm::Atom(Atom::new("all")),
+            TraceTarget::Existing => OwnedTerm::Atom(Atom::new("existing")),
+            TraceTarget::New => OwnedTerm::Atom(Atom::new("new")),
+            TraceTarget::Pid(pid) => OwnedTerm::Pid(pid.clone()),
+        }
+    }
+}
+
+/// `erlang:trace/3` flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TraceFlag {
+    Call,
+    Arity,
+    Send,
+    Receive,
+    Procs,
+    GarbageCollection,
+    Timestamp,
+}
+
+impl TraceFlag {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TraceFlag::Call => "call",
+            TraceFlag::Arity => "arity",
+            TraceFlag::Send => "send",
+            TraceFlag::Receive => "receive",
+            TraceFlag::Procs => "procs",
+            TraceFlag::GarbageCollection => "garbage_collection",
+            TraceFlag::Timestamp => "timestamp",
+        }
+    }
+}
+
+/// A call trace pattern for `erlang:trace_pattern/3`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TracePattern {
+    pub module: Atom,
+    /// `None` matches every function in the module.
+    pub function: Option<Atom>,
+    /// `None` matches every arity.
+    pub arity: Option<u8>,
+    pub match_spec: OwnedTerm,
+    /// Trace local calls as well as global (exported) ones.
+    pub local: bool,
+}
+
+impl TracePattern {
+    pub fn new(module: impl Into<Atom>) -> Self {
+        Self {
+            module: module.into(),
+            function: None,
+            arity: None,
+            match_spec: OwnedTerm::Atom(Atom::new("true")),
+            local: false,
+        }
+    }
+
+    pub fn with_function(mut self, function: impl Into<Atom>) -> Self {
+        self.function = Some(function.into());
+        self
+    }
+
+    pub fn with_arity(mut self, arity: u8) -> Self {
+        self.arity = Some(arity);
+        self
+    }
+
+    pub fn with_match_spec(mut self, match_spec: OwnedTerm) -> Self {
+        self.match_spec = match_spec;
+        self
+    }
+
+    /// Uses the `[{'_', [], [{return_trace}]}]` match spec, which produces
+    /// `return_from` and `exception_from` events.
+    pub fn with_return_trace(self) -> Self {
+        self.with_match_spec(OwnedTerm::List(vec![OwnedTerm::Tuple(vec![
+            OwnedTerm::Atom(Atom::new("_")),
+            OwnedTerm::List(vec![]),
+            OwnedTerm::List(vec![OwnedTerm::Tuple(vec![OwnedTerm::Atom(Atom::new(
+                "return_trace",
+            ))])]),
+        ])]))
+    }
+
+    pub fn with_local(mut self, local: bool) -> Self {
+        self.local = local;
+        self
+    }
+
+    fn mfa_term(&self) -> OwnedTerm {
+        let wildcard = || OwnedTerm::Atom(Atom::new("_"));
+        OwnedTerm::Tuple(vec![
+            OwnedTerm::Atom(self.module.clone()),
+            self.function.clone().map_or_else(wildcard, OwnedTerm::Atom),
+            self.arity
+                .map_or_else(wildcard, |a| OwnedTerm::Integer(a as i64)),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcPhase {
+    MinorStart,
+    MinorEnd,
+    MajorStart,
+    MajorEnd,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceEventKind {
+    /// `args` is `None` when the `arity` flag is set.
+    Call {
+        module: Atom,
+        function: Atom,
+        arity: u8,
+        args: Option<Vec<OwnedTerm>>,
+    },
+    ReturnFrom {
+        mfa: Mfa,
+        value: OwnedTerm,
+    },
+    ExceptionFrom {
+        mfa: Mfa,
+        class: OwnedTerm,
+        reason: OwnedTerm,
+    },
+    Send {
+        message: OwnedTerm,
+        to: OwnedTerm,
+    },
+    SendToNonExistingProcess {
+        message: OwnedTerm,
+        to: OwnedTerm,
+    },
+    Receive {
+        message: OwnedTerm,
+    },
+    GarbageCollection {
+        phase: GcPhase,
+        info: OwnedTerm,
+    },
+    /// Any other trace message, e.g. from the `procs` flag.
+    Other {
+        tag: Atom,
+        data: Vec<OwnedTerm>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEvent {
+    pub pid: ExternalPid,
+    pub kind: TraceEventKind,
+    /// Set for `trace_ts` messages, i.e. with the `timestamp` flag.
+    pub timestamp: Option<OwnedTerm>,
+}
+
+impl TraceEvent {
+    /// Decodes a `{trace, Pid, Tag, ...}` or `{trace_ts, Pid, Tag, ..., Timestamp}` message.
+    pub fn from_term(term: &OwnedTerm) -> Option<Self> {
+        let OwnedTerm::Tuple(elements) = term else {
+            return None;
+        };
+        let (data, timestamp) = match elements.first()?.atom_name()? {
+            "trace" => (&elements[..], None),
+            "trace_ts" if elements.len() > 3 => {
+                let (last, rest) = elements.split_last()?;
+                (rest, Some(last.clone()))
+            }
+            _ => return None,
+        };
+        if data.len() < 3 {
+            return None;
+        }
+
+        let pid = data[1].as_pid()?.clone();
+        let tag = data[2].as_atom()?;
+        let rest = &data[3..];
+        let kind = match (tag.as_str(), rest) {
+            ("call", [mfa, ..]) => Self::call(mfa)?,
+            ("return_from", [mfa, value]) => TraceEventKind::ReturnFrom {
+                mfa: Mfa::try_from_term(mfa)?,
+                value: value.clone(),
+            },
+            ("exception_from", [mfa, OwnedTerm::Tuple(exception)]) if exception.len() == 2 => {
+                TraceEventKind::ExceptionFrom {
+                    mfa: Mfa::try_from_term(mfa)?,
+                    class: exception[0].clone(),
+                    reason: exception[1].clone(),
+                }
+            }
+            ("send", [message, to]) => TraceEventKind::Send {
+                message: message.clone(),
+                to: to.clone(),
+            },
+            ("send_to_non_existing_process", [message, to]) => {
+                TraceEventKind::SendToNonExistingProcess {
+                    message: message.clone(),
+                    to: to.clone(),
+                }
+            }
+            ("receive", [message]) => TraceEventKind::Receive {
+                message: message.clone(),
+            },
+            ("gc_minor_start", [info]) => Self::gc(GcPhase::MinorStart, info),
+            ("gc_minor_end", [info]) => Self::gc(GcPhase::MinorEnd, info),
+            ("gc_major_start", [info]) => Self::gc(GcPhase::MajorStart, info),
+            ("gc_major_end", [info]) => Self::gc(GcPhase::MajorEnd, info),
+            _ => TraceEventKind::Other {
+                tag: tag.clone(),
+                data: rest.to_vec(),
+            },
+        };
+
+        Some(Self {
+            pid,
+            kind,
+            timestamp,
+        })
+    }
+
+    fn call(mfa: &OwnedTerm) -> Option<TraceEventKind> {
+        let OwnedTerm::Tuple(elements) = mfa else {
+            return None;
+        };
+        let [module, function, args] = elements.as_slice() else {
+            return None;
+        };
+        let module = module.as_atom()?.clone();
+        let function = function.as_atom()?.clone();
+        let (arity, args) = match args {
+            OwnedTerm::Integer(arity) => (u8::try_from(*arity).ok()?, None),
+            OwnedTerm::Nil => (0, Some(Vec::new())),
+            other => {
+                let args = other.as_list()?.to_vec();
+                (u8::try_from(args.len()).ok()?, Some(args))
+            }
+        };
+        Some(TraceEventKind::Call {
+            module,
+            function,
+            arity,
+            args,
+        })
+    }
+
+    fn gc(phase: GcPhase, info: &OwnedTerm) -> TraceEventKind {
+        TraceEventKind::GarbageCollection {
+            phase,
+            info: info.clone(),
+        }
+    }
+}
+
+/// Receives trace events from a relay process on a remote node.
+pub struct Tracer {
+    remote_node: String,
+    relay: ExternalPid,
+    rx: mpsc::UnboundedReceiver<OwnedTerm>,
+    key: String,
+    pending_streams: Arc<DashMap<String, mpsc::UnboundedSender<OwnedTerm>>>,
+}
+
+impl Tracer {
+    /// Loads the relay module on `remote_node` and starts a relay process
+    /// that forwards trace messages to `node`.
+    pub async fn start(node: &Node, remote_node: &str) -> Result<Self> {
+        if !node.connections().contains_key(remote_node) {
+            return Err(Error::NodeNotConnected(remote_node.to_string()));
+        }
+        node.ensure_module_loaded(remote_node, TRACE_RELAY_MODULE, TRACE_RELAY_FORMS)
+            .await?;
+
+        let receiver = node.allocate_pid();
+        let key = pid_key(&receiver);
+        let (tx, rx) = mpsc::unbounded_channel();
+        node.register_stream(&receiver, tx);
+
+        let relay = node
+            .rpc_call(
+                remote_node,
+                TRACE_RELAY_MODULE,
+                "start",
+                vec![OwnedTerm::Pid(receiver)],
+            )
+            .await;
+        match relay {
+            Ok(OwnedTerm::Pid(relay)) => Ok(Self {
+                remote_node: remote_node.to_string(),
+                relay,
+                rx,
+                key,
+                pending_streams: node.pending_streams(),
+            }),
+            other => {
+                node.pending_streams().remove(&key);
+                match other {
+                    Err(e) => Err(e),
+                    Ok(term) => Err(Error::SpawnFailed(format!(
+                        "failed to start trace relay: {term}"
+                    ))),
+                }
+            }
+        }
+    }
+
+    pub fn remote_node(&self) -> &str {
+        &self.remote_node
+    }
+
+    /// The relay process on the remote node that acts as the tracer.
+    pub fn relay_pid(&self) -> &ExternalPid {
+        &self.relay
+    }
+
+    /// Calls `erlang:trace(Target, true, [{tracer, Relay} | Flags])` and returns
+    /// the number of matched processes.
+    pub async fn trace(
+        &self,
+        node: &Node,
+        target: TraceTarget,
+        flags: &[TraceFlag],
+    ) -> Result<i64> {
+        let mut flag_list = vec![OwnedTerm::Tuple(vec![
+            OwnedTerm::Atom(Atom::new("tracer")),
+            OwnedTerm::Pid(self.relay.clone()),
+        ])];
+        flag_list.extend(flags.iter().map(|f| OwnedTerm::Atom(Atom::new(f.as_str()))));
+
+        let result = node
+            .rpc_call(
+                &self.remote_node,
+                "erlang",
+                "trace",
+                vec![
+                    target.to_term(),
+                    OwnedTerm::Atom(Atom::new("true")),
+                    OwnedTerm::List(flag_list),
+                ],
+            )
+            .await?;
+        count_result("erlang:trace/3", result)
+    }
+
+    /// Turns off the given flags for `target`.
+    pub async fn untrace(
+        &self,
+        node: &Node,
+        target: TraceTarget,
+        flags: &[TraceFlag],
+    ) -> Result<i64> {
+        let flag_list = flags
+            .iter()
+            .map(|f| OwnedTerm::Atom(Atom::new(f.as_str())))
+            .collect();
+        let result = node
+            .rpc_call(
+                &self.remote_node,
+                "erlang",
+                "trace",
+                vec![
+                    target.to_term(),
+                    OwnedTerm::Atom(Atom::new("false")),
+                    OwnedTerm::List(flag_list),
+                ],
+            )
+            .await?;
+        count_result("erlang:trace/3", result)
+    }
+
+    /// Calls `erlang:trace_pattern/3` and returns the number of matched functions.
+    pub async fn trace_pattern(&self, node: &Node, pattern: &TracePattern) -> Result<i64> {
+        let scope = if pattern.local { "local" } else { "global" };
+        let result = node
+            .rpc_call(
+                &self.remote_node,
+                "erlang",
+                "trace_pattern",
+                vec![
+                    pattern.mfa_term(),
+                    pattern.match_spec.clone(),
+                    OwnedTerm::List(vec![OwnedTerm::Atom(Atom::new(scope))]),
+                ],
+            )
+            .await?;
+        count_result("erlang:trace_pattern/3", result)
+    }
+
+    /// Removes call trace patterns set for `pattern`'s module, function and arity.
+    pub async fn clear_trace_pattern(&self, node: &Node, pattern: &TracePattern) -> Result<i64> {
+        let cleared = pattern
+            .clone()
+            .with_match_spec(OwnedTerm::Atom(Atom::new("false")));
+        self.trace_pattern(node, &cleared).await
+    }
+
+    /// Waits for the next trace event. Returns `None` once the tracer is stopped.
+    ///
+    /// Messages that are not trace messages are skipped.
+    pub async fn next_event(&mut self) -> Option<TraceEvent> {
+        while let Some(message) = self.rx.recv().await {
+            if let Some(event) = TraceEvent::from_term(&message) {
+                return Some(event);
+            }
+            tracing::debug!("Ignoring non-trace message: {}", message);
+        }
+        None
+    }
+
+    pub async fn next_event_with_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<TraceEvent>> {
+        time::timeout(timeout, self.next_event())
+            .await
+            .map_err(|_| Error::CallTimeout(timeout))
+    }
+
+    /// Stops the relay process. The runtime stops tracing with a tracer that has exited.
+    pub async fn stop(self, node: &Node) -> Result<()> {
+        node.send(
+            &self.relay,
+            OwnedTerm::Tuple(vec![
+                OwnedTerm::Atom(Atom::new(TRACE_RELAY_MODULE)),
+                OwnedTerm::Atom(Atom::new("stop")),
+            ]),
+        )
+        .await
+    }
+}
+
+impl Drop for Tracer {
+    fn drop(&mut self) {
+        self.pending_streams.remove(&self.key);
+    }
+}
+
+fn count_result(call: &str, result: OwnedTerm) -> Result<i64> {
+    result
+        .as_integer()
+        .ok_or_else(|| Error::InvalidMessage(format!("{call} returned {result}")))
+}
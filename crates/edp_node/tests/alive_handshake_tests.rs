@@ -0,0 +1,113 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::test_peer::TestPeer;
+use edp_node::{Node, NodeEvent};
+use erltf::types::Atom;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::{sleep, timeout};
+
+const COOKIE: &str = "alive_handshake_cookie";
+const PEER: &str = "alive_handshake_peer@localhost";
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+/// A peer that still has a connection from the node: it answers the name with `alive`
+/// and expects to be asked to replace that connection.
+async fn accept_with_alive(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE)
+        .with_status("alive")
+        .accept(listener)
+        .await
+}
+
+#[tokio::test]
+async fn test_node_replaces_the_connection_an_alive_peer_still_has() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_with_alive(&listener).await });
+
+    let node = Node::new_hidden(test_node_name("alive_handshake"), COOKIE);
+    let mut monitor = node.monitor_nodes();
+    node.connect_to_port(PEER, port).await.unwrap();
+    let _stream = peer.await.unwrap();
+
+    assert!(node.connections().contains_key(PEER));
+    let event = timeout(Duration::from_secs(1), monitor.recv())
+        .await
+        .unwrap();
+    assert_eq!(
+        event,
+        Some(NodeEvent::NodeUp {
+            node: Atom::new(PEER),
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_node_replaces_an_established_connection_when_the_peer_says_alive() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let node = Node::new_hidden(test_node_name("alive_replace"), COOKIE);
+    let connections = node.connections();
+    let mut monitor = node.monitor_nodes();
+    let peer = tokio::spawn(async move {
+        let mut first = TestPeer::new(PEER, COOKIE).accept(&listener).await;
+        while !connections.contains_key(PEER) {
+            sleep(Duration::from_millis(10)).await;
+        }
+        let replaced = Arc::clone(connections.get(PEER).unwrap().value());
+        let second = accept_with_alive(&listener).await;
+        // The node tears down the connection it replaces
+        let mut buf = [0u8; 64];
+        let closed = timeout(Duration::from_secs(5), async {
+            loop {
+                match first.read(&mut buf).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => continue,
+                }
+            }
+        })
+        .await;
+        assert!(closed.is_ok(), "the replaced connection was not closed");
+        (replaced, second)
+    });
+
+    let (first, second) = tokio::join!(
+        node.connect_to_port(PEER, port),
+        node.connect_to_port(PEER, port)
+    );
+    first.unwrap();
+    second.unwrap();
+    let (replaced, _second) = peer.await.unwrap();
+
+    let current = Arc::clone(node.connections().get(PEER).unwrap().value());
+    assert!(!Arc::ptr_eq(&current, &replaced));
+    let mut events = Vec::new();
+    for _ in 0..3 {
+        let event = timeout(Duration::from_secs(1), monitor.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        events.push(event);
+    }
+    assert!(matches!(events[0], NodeEvent::NodeUp { .. }));
+    assert!(matches!(events[1], NodeEvent::NodeDown { .. }));
+    assert!(matches!(events[2], NodeEvent::NodeUp { .. }));
+}
@@ -0,0 +1,210 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_node::{CallTable, Error, Message, Node, Process, Result};
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid, ExternalReference};
+use std::time::Duration;
+use tokio::time::{advance, sleep};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+fn reference(id: u32) -> ExternalReference {
+    ExternalReference::new(Atom::new("caller@localhost"), 1, vec![id, 0, 0])
+}
+
+fn reply_message(reference: &ExternalReference, reply: OwnedTerm) -> Message {
+    Message::Regular {
+        from: None,
+        body: OwnedTerm::Tuple(vec![OwnedTerm::Reference(reference.clone()), reply]),
+    }
+}
+
+/// Hands every message to a call table first, like a process with its own request/reply protocol.
+struct CallerProcess {
+    calls: CallTable,
+}
+
+impl Process for CallerProcess {
+    async fn handle_message(&mut self, msg: Message) -> Result<()> {
+        let _ = self.calls.dispatch(msg);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_complete_wakes_the_waiter() {
+    let table = CallTable::new();
+    let call = table.register(reference(1), TIMEOUT);
+    assert!(table.contains(&reference(1)));
+
+    assert!(table.complete(&reference(1), OwnedTerm::atom("pong")));
+    assert!(!table.complete(&reference(1), OwnedTerm::atom("again")));
+
+    assert_eq!(call.wait().await.unwrap(), OwnedTerm::atom("pong"));
+    assert!(table.is_empty());
+}
+
+#[tokio::test]
+async fn test_dispatch_consumes_replies_and_returns_other_messages() {
+    let table = CallTable::new();
+    let call = table.register(reference(1), TIMEOUT);
+
+    let unrelated = reply_message(&reference(2), OwnedTerm::atom("other"));
+    assert!(matches!(
+        table.dispatch(unrelated),
+        Some(Message::Regular { .. })
+    ));
+    let plain = Message::Regular {
+        from: None,
+        body: OwnedTerm::atom("hello"),
+    };
+    assert!(table.dispatch(plain).is_some());
+    assert_eq!(table.len(), 1);
+
+    let reply = reply_message(&reference(1), OwnedTerm::atom("pong"));
+    assert!(table.dispatch(reply).is_none());
+    assert_eq!(call.wait().await.unwrap(), OwnedTerm::atom("pong"));
+}
+
+#[tokio::test]
+async fn test_dispatch_accepts_alias_tagged_replies() {
+    let table = CallTable::new();
+    let call = table.register(reference(1), TIMEOUT);
+
+    let tag = OwnedTerm::improper_list(
+        vec![OwnedTerm::atom("alias")],
+        OwnedTerm::Reference(reference(1)),
+    );
+    let reply = Message::Regular {
+        from: None,
+        body: OwnedTerm::Tuple(vec![tag, OwnedTerm::atom("pong")]),
+    };
+    assert!(table.dispatch(reply).is_none());
+    assert_eq!(call.wait().await.unwrap(), OwnedTerm::atom("pong"));
+}
+
+#[tokio::test]
+async fn test_down_fails_the_call() {
+    let table = CallTable::new();
+    let call = table.register(reference(1), TIMEOUT);
+
+    let down = Message::MonitorExit {
+        monitored: ExternalPid::new(Atom::new("callee@localhost"), 1, 0, 1),
+        reference: reference(1),
+        reason: OwnedTerm::atom("noproc"),
+    };
+    assert!(table.dispatch(down).is_none());
+
+    match call.wait().await {
+        Err(Error::CallTargetDown(reason)) => assert_eq!(reason, OwnedTerm::atom("noproc")),
+        other => panic!("expected CallTargetDown, got {other:?}"),
+    }
+    assert!(table.is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_wait_times_out_at_the_deadline() {
+    let table = CallTable::new();
+    let call = table.register(reference(1), Duration::from_millis(100));
+
+    let err = call.wait().await.unwrap_err();
+    assert!(matches!(err, Error::CallTimeout(timeout) if timeout == Duration::from_millis(100)));
+    assert!(err.is_timeout());
+    assert!(table.is_empty());
+}
+
+#[tokio::test]
+async fn test_dropping_a_pending_call_removes_it() {
+    let table = CallTable::new();
+    let call = table.register(reference(1), TIMEOUT);
+    assert_eq!(table.len(), 1);
+
+    drop(call);
+    assert!(table.is_empty());
+    assert!(!table.complete(&reference(1), OwnedTerm::atom("late")));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_expire_removes_overdue_calls() {
+    let table = CallTable::new();
+    let short = table.register(reference(1), Duration::from_millis(10));
+    let _long = table.register(reference(2), Duration::from_secs(60));
+
+    assert_eq!(table.expire(), 0);
+    advance(Duration::from_millis(20)).await;
+    assert_eq!(table.expire(), 1);
+    assert!(!table.contains(&reference(1)));
+    assert!(table.contains(&reference(2)));
+
+    assert!(matches!(short.wait().await, Err(Error::CallTimeout(_))));
+}
+
+#[tokio::test]
+async fn test_cancel_fails_the_waiter() {
+    let table = CallTable::new();
+    let call = table.register(reference(1), TIMEOUT);
+
+    assert!(table.cancel(&reference(1)));
+    assert!(!table.cancel(&reference(1)));
+    assert!(matches!(call.wait().await, Err(Error::CallTimeout(_))));
+}
+
+#[tokio::test]
+async fn test_calls_can_be_keyed_by_the_reply_pid() {
+    let table: CallTable<ExternalPid> = CallTable::new();
+    let reply_to = ExternalPid::new(Atom::new("caller@localhost"), 1, 0, 1);
+    let call = table.register(reply_to.clone(), TIMEOUT);
+    assert_eq!(call.reference(), &reply_to);
+
+    assert!(table.complete(&reply_to, OwnedTerm::atom("pong")));
+    assert_eq!(call.wait().await.unwrap(), OwnedTerm::atom("pong"));
+    assert!(table.is_empty());
+}
+
+#[tokio::test]
+async fn test_process_routes_replies_through_the_table() {
+    let mut node = Node::new(test_node_name("call_table_process"), "secret");
+    node.start(0).await.unwrap();
+
+    let calls = CallTable::new();
+    let caller = node
+        .spawn(CallerProcess {
+            calls: calls.clone(),
+        })
+        .await
+        .unwrap();
+
+    let reference = node.make_reference();
+    let call = calls.register(reference.clone(), TIMEOUT);
+    let waiter = tokio::spawn(call.wait());
+
+    sleep(Duration::from_millis(10)).await;
+    node.send(
+        &caller,
+        OwnedTerm::Tuple(vec![
+            OwnedTerm::Reference(reference),
+            OwnedTerm::atom("pong"),
+        ]),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(waiter.await.unwrap().unwrap(), OwnedTerm::atom("pong"));
+    assert!(calls.is_empty());
+}
@@ -0,0 +1,105 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::DistributionFlags;
+use edp_client::test_peer::TestPeer;
+use edp_node::{Node, NodeEvent};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+
+const COOKIE: &str = "connection_id_cookie";
+const PEER: &str = "connection_id_peer@127.0.0.1";
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    accept_handshake_as(listener, PEER).await
+}
+
+async fn accept_handshake_as(listener: &TcpListener, name: &str) -> TcpStream {
+    TestPeer::new(name, COOKIE)
+        .with_flags(DistributionFlags::default())
+        .accept(listener)
+        .await
+}
+
+#[tokio::test]
+async fn test_reconnects_keep_the_connection_id() {
+    let node = Node::new(test_node_name("connection_id"), COOKIE);
+    let mut monitor = node.monitor_nodes();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        drop(accept_handshake(&listener).await);
+        accept_handshake(&listener).await
+    });
+    assert_eq!(node.connection_id(PEER), None);
+
+    node.connect_to_port(PEER, port).await.unwrap();
+    let id = node.connection_id(PEER).unwrap();
+    let conn = node.connections().get(PEER).unwrap().clone();
+    assert_eq!(conn.lock().await.id(), id);
+
+    loop {
+        let event = timeout(Duration::from_secs(5), monitor.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        if matches!(event, NodeEvent::NodeDown { .. }) {
+            break;
+        }
+    }
+    assert_eq!(node.connection_id(PEER), Some(id));
+
+    node.connect_to_port(PEER, port).await.unwrap();
+    let conn = node.connections().get(PEER).unwrap().clone();
+    assert_eq!(conn.lock().await.id(), id);
+    let _second = peer.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_connections_to_different_nodes_get_different_ids() {
+    let node = Node::new(test_node_name("connection_ids"), COOKIE);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let first = accept_handshake_as(&listener, "first@127.0.0.1").await;
+        let second = accept_handshake_as(&listener, "second@127.0.0.1").await;
+        (first, second)
+    });
+
+    node.connect_to_port("first@127.0.0.1", port).await.unwrap();
+    node.connect_to_port("second@127.0.0.1", port)
+        .await
+        .unwrap();
+    let _streams = peer.await.unwrap();
+
+    let first = node.connection_id("first@127.0.0.1").unwrap();
+    let second = node.connection_id("second@127.0.0.1").unwrap();
+    assert_ne!(first, second);
+}
+
+#[tokio::test]
+async fn test_failed_connects_do_not_record_a_connection_id() {
+    let node = Node::new(test_node_name("connection_id_failed"), COOKIE);
+    let closed = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = closed.local_addr().unwrap().port();
+    drop(closed);
+
+    assert!(node.connect_to_port(PEER, port).await.is_err());
+    assert_eq!(node.connection_id(PEER), None);
+}
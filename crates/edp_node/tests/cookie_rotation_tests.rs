@@ -0,0 +1,133 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::DistributionFlags;
+use edp_client::test_peer::TestPeer;
+use edp_node::{CookieRotation, Node, NodeDownReason, NodeEvent};
+use erltf::types::Atom;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::{sleep, timeout};
+
+const OLD_COOKIE: &str = "old_cookie";
+const NEW_COOKIE: &str = "new_cookie";
+const PEER: &str = "peer@127.0.0.1";
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+/// Accepts one connection and completes the handshake, checking that the client used `cookie`.
+async fn accept_handshake(listener: &TcpListener, cookie: &str) -> TcpStream {
+    TestPeer::new(PEER, cookie)
+        .with_flags(DistributionFlags::default())
+        .accept(listener)
+        .await
+}
+
+/// Reads until the client closes the connection.
+async fn wait_for_close(mut stream: TcpStream) {
+    let mut buf = [0u8; 1024];
+    while let Ok(n) = stream.read(&mut buf).await {
+        if n == 0 {
+            break;
+        }
+    }
+}
+
+fn rotation() -> CookieRotation {
+    CookieRotation::new()
+        .with_reconnect(Duration::from_millis(10))
+        .with_retries(1, Duration::from_millis(10))
+}
+
+#[tokio::test]
+async fn test_rotate_cookie_without_reconnect_keeps_connections() {
+    let node = Node::new(test_node_name("rotate_keep"), OLD_COOKIE);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener, OLD_COOKIE).await });
+
+    node.connect_to_port(PEER, port).await.unwrap();
+    let _stream = peer.await.unwrap();
+
+    let report = node
+        .rotate_cookie(NEW_COOKIE, &CookieRotation::default())
+        .await;
+    assert!(report.is_complete());
+    assert!(report.reconnected.is_empty());
+    assert_eq!(node.cookie(), NEW_COOKIE);
+    assert!(node.connections().contains_key(PEER));
+}
+
+#[tokio::test]
+async fn test_rotate_cookie_reconnects_with_the_new_cookie() {
+    let node = Node::new(test_node_name("rotate_reconnect"), OLD_COOKIE);
+    let mut monitor = node.monitor_nodes();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        wait_for_close(accept_handshake(&listener, OLD_COOKIE).await).await;
+        accept_handshake(&listener, NEW_COOKIE).await
+    });
+
+    node.connect_to_port(PEER, port).await.unwrap();
+    let report = node.rotate_cookie(NEW_COOKIE, &rotation()).await;
+    let _stream = peer.await.unwrap();
+
+    assert!(report.is_complete());
+    assert_eq!(report.reconnected, vec![Atom::new(PEER)]);
+    assert!(node.connections().contains_key(PEER));
+
+    let node_up = NodeEvent::NodeUp {
+        node: Atom::new(PEER),
+    };
+    let node_down = NodeEvent::NodeDown {
+        node: Atom::new(PEER),
+        reason: NodeDownReason::ConnectionClosed,
+    };
+    for expected in [&node_up, &node_down, &node_up] {
+        let event = timeout(Duration::from_secs(1), monitor.recv())
+            .await
+            .unwrap();
+        assert_eq!(event.as_ref(), Some(expected));
+    }
+    // The receiver of the replaced connection does not report it or remove the new one
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(monitor.try_recv(), None);
+    assert!(node.connections().contains_key(PEER));
+}
+
+#[tokio::test]
+async fn test_rotate_cookie_reports_failed_reconnects() {
+    let node = Node::new(test_node_name("rotate_failed"), OLD_COOKIE);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let stream = accept_handshake(&listener, OLD_COOKIE).await;
+        drop(listener);
+        wait_for_close(stream).await;
+    });
+
+    node.connect_to_port(PEER, port).await.unwrap();
+    sleep(Duration::from_millis(20)).await;
+    let report = node.rotate_cookie(NEW_COOKIE, &rotation()).await;
+    peer.await.unwrap();
+
+    assert!(!report.is_complete());
+    assert!(report.reconnected.is_empty());
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, Atom::new(PEER));
+    assert!(!node.connections().contains_key(PEER));
+}
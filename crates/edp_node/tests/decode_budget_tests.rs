@@ -0,0 +1,137 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::DecodeBudget;
+use edp_client::test_peer::TestPeer;
+use edp_node::{Message, Node, NodeEvent, Process, Result};
+use erltf::OwnedTerm;
+use erltf::encoder::encode;
+use erltf::types::ExternalPid;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+const COOKIE: &str = "decode_budget_cookie";
+const PEER: &str = "decode_budget_peer@localhost";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+struct Recorder {
+    received: mpsc::UnboundedSender<OwnedTerm>,
+}
+
+impl Process for Recorder {
+    async fn handle_message(&mut self, msg: Message) -> Result<()> {
+        if let Message::Regular { body, .. } = msg {
+            let _ = self.received.send(body);
+        }
+        Ok(())
+    }
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+fn send_frame(to: &ExternalPid, payload: &OwnedTerm) -> Vec<u8> {
+    let control = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(SEND),
+        OwnedTerm::atom(""),
+        OwnedTerm::Pid(to.clone()),
+    ]);
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    body.extend(encode(payload).unwrap());
+    let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+    frame.extend(body);
+    frame
+}
+
+#[tokio::test]
+async fn test_routed_payloads_are_released() {
+    let mut node = Node::new(test_node_name("decode_budget"), COOKIE)
+        .with_decode_budget(DecodeBudget::new().with_max_outstanding(100_000));
+    node.start(0).await.unwrap();
+    let (received, mut received_rx) = mpsc::unbounded_channel();
+    let local = node.spawn(Recorder { received }).await.unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let payload = OwnedTerm::Binary(vec![0; 10_000]);
+    let frames = send_frame(&local, &payload).repeat(20);
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        stream.write_all(&frames).await.unwrap();
+        stream
+    });
+    node.connect_to_port(PEER, port).await.unwrap();
+    let _stream = peer.await.unwrap();
+
+    for _ in 0..20 {
+        let body = timeout(Duration::from_secs(5), received_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(body, payload);
+    }
+
+    let conn = node.connections().get(PEER).unwrap().clone();
+    let conn = conn.lock().await;
+    let accounting = conn.decode_accounting();
+    assert_eq!(accounting.messages(), 20);
+    assert_eq!(accounting.bytes(), 20 * payload.heap_size() as u64);
+    assert_eq!(accounting.outstanding(), 0);
+}
+
+#[tokio::test]
+async fn test_oversized_payloads_are_dropped_without_taking_the_link_down() {
+    let mut node = Node::new(test_node_name("decode_budget_oversized"), COOKIE)
+        .with_decode_budget(DecodeBudget::new().with_max_message_size(1_000));
+    node.start(0).await.unwrap();
+    let mut monitor = node.monitor_nodes();
+    let (received, mut received_rx) = mpsc::unbounded_channel();
+    let local = node.spawn(Recorder { received }).await.unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let mut frames = send_frame(&local, &OwnedTerm::Binary(vec![0; 10_000]));
+    frames.extend(send_frame(&local, &OwnedTerm::atom("small")));
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        stream.write_all(&frames).await.unwrap();
+        stream
+    });
+    node.connect_to_port(PEER, port).await.unwrap();
+    let _stream = peer.await.unwrap();
+
+    let body = timeout(Duration::from_secs(5), received_rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(body, OwnedTerm::atom("small"));
+
+    let conn = node.connections().get(PEER).unwrap().clone();
+    assert_eq!(conn.lock().await.decode_accounting().rejected(), 1);
+    while let Some(event) = monitor.try_recv() {
+        assert!(
+            !matches!(event, NodeEvent::NodeDown { .. }),
+            "unexpected {event:?}"
+        );
+    }
+}
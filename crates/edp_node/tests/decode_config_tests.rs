@@ -0,0 +1,164 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::DecodeOffload;
+use edp_client::test_peer::TestPeer;
+use edp_node::{Message, Node, Process, Result};
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalFun, ExternalPid};
+use erltf::{DecodeConfig, OwnedTerm};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+const COOKIE: &str = "decode_config_cookie";
+const PEER: &str = "decode_config_peer@localhost";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+/// Preallocated, so it is never past an atom limit
+const DONE: &str = "ok";
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+struct Recorder {
+    received: mpsc::UnboundedSender<OwnedTerm>,
+}
+
+impl Process for Recorder {
+    async fn handle_message(&mut self, msg: Message) -> Result<()> {
+        if let Message::Regular { body, .. } = msg {
+            let _ = self.received.send(body);
+        }
+        Ok(())
+    }
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+async fn write_send(stream: &mut TcpStream, to: &ExternalPid, message: &OwnedTerm) {
+    let control = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(SEND),
+        OwnedTerm::atom(""),
+        OwnedTerm::Pid(to.clone()),
+    ]);
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    body.extend(encode(message).unwrap());
+    stream
+        .write_all(&(body.len() as u32).to_be_bytes())
+        .await
+        .unwrap();
+    stream.write_all(&body).await.unwrap();
+}
+
+/// Sends `messages` from a peer to a process on a node built by `node`
+/// and returns what the process received.
+async fn deliver(node: Node, messages: Vec<OwnedTerm>) -> Vec<OwnedTerm> {
+    let mut node = node;
+    node.start(0).await.unwrap();
+    let (received, mut received_rx) = mpsc::unbounded_channel();
+    let local = node.spawn(Recorder { received }).await.unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        for message in &messages {
+            write_send(&mut stream, &local, message).await;
+        }
+        write_send(&mut stream, &local, &OwnedTerm::atom(DONE)).await;
+        stream
+    });
+    node.connect_to_port(PEER, port).await.unwrap();
+    let _stream = peer.await.unwrap();
+
+    let mut bodies = Vec::new();
+    loop {
+        let body = timeout(Duration::from_secs(5), received_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        if body == OwnedTerm::atom(DONE) {
+            return bodies;
+        }
+        bodies.push(body);
+    }
+}
+
+fn fun_message() -> OwnedTerm {
+    OwnedTerm::tuple(vec![
+        OwnedTerm::atom("run"),
+        OwnedTerm::ExternalFun(ExternalFun::new(Atom::new("erlang"), Atom::new("halt"), 0)),
+    ])
+}
+
+#[tokio::test]
+async fn test_safe_node_connections_drop_messages_with_funs() {
+    let node = Node::new(test_node_name("decode_config_safe"), COOKIE)
+        .with_decode_config(DecodeConfig::new().with_safe(true));
+    let received = deliver(node, vec![fun_message(), OwnedTerm::atom("after")]).await;
+    assert_eq!(received, vec![OwnedTerm::atom("after")]);
+}
+
+#[tokio::test]
+async fn test_safe_node_connections_drop_offloaded_messages_with_funs() {
+    let node = Node::new(test_node_name("decode_config_safe_offload"), COOKIE)
+        .with_decode_config(DecodeConfig::new().with_safe(true))
+        .with_decode_offload(DecodeOffload::new(0));
+    let received = deliver(node, vec![fun_message(), OwnedTerm::atom("after")]).await;
+    assert_eq!(received, vec![OwnedTerm::atom("after")]);
+}
+
+#[tokio::test]
+async fn test_node_connections_deliver_funs_by_default() {
+    let node = Node::new(test_node_name("decode_config_default"), COOKIE);
+    let received = deliver(node, vec![fun_message()]).await;
+    assert_eq!(received, vec![fun_message()]);
+}
+
+#[tokio::test]
+async fn test_node_connections_decode_atoms_past_the_limit_as_binaries() {
+    let node = Node::new(test_node_name("decode_config_atom_limit"), COOKIE)
+        .with_decode_config(DecodeConfig::new().atoms_as_binaries_after(8));
+    let atoms = (0..16)
+        .map(|i| OwnedTerm::atom(format!("decode_config_atom_{i}")))
+        .collect();
+    let received = deliver(node, vec![OwnedTerm::List(atoms)]).await;
+
+    let [OwnedTerm::List(elements)] = received.as_slice() else {
+        panic!("unexpected messages: {received:?}");
+    };
+    assert_eq!(elements.len(), 16);
+    assert!(elements.first().unwrap().is_atom());
+    assert_eq!(
+        elements.last().unwrap(),
+        &OwnedTerm::Binary(b"decode_config_atom_15".to_vec())
+    );
+}
+
+#[tokio::test]
+async fn test_node_connections_drop_messages_past_the_atom_limit() {
+    let node = Node::new(test_node_name("decode_config_atom_error"), COOKIE)
+        .with_decode_config(DecodeConfig::new().atoms_error_after(8));
+    let atoms = (0..16)
+        .map(|i| OwnedTerm::atom(format!("decode_config_atom_{i}")))
+        .collect();
+    let received = deliver(node, vec![OwnedTerm::List(atoms), OwnedTerm::atom("true")]).await;
+    assert_eq!(received, vec![OwnedTerm::atom("true")]);
+}
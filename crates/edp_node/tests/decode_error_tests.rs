@@ -0,0 +1,103 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::ctrl;
+use edp_client::test_peer::TestPeer;
+use edp_node::{Message, Node, NodeEvent, Process, Result};
+use erltf::OwnedTerm;
+use erltf::encoder::encode;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+const COOKIE: &str = "decode_error_cookie";
+const PEER: &str = "decode_error_peer@localhost";
+const PASS_THROUGH: u8 = 112;
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+struct Recorder {
+    received: mpsc::UnboundedSender<OwnedTerm>,
+}
+
+impl Process for Recorder {
+    async fn handle_message(&mut self, msg: Message) -> Result<()> {
+        if let Message::Regular { body, .. } = msg {
+            let _ = self.received.send(body);
+        }
+        Ok(())
+    }
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+async fn write_frame(stream: &mut TcpStream, body: &[u8]) {
+    stream
+        .write_all(&(body.len() as u32).to_be_bytes())
+        .await
+        .unwrap();
+    stream.write_all(body).await.unwrap();
+}
+
+fn pass_through(control: OwnedTerm, payload: Option<OwnedTerm>) -> Vec<u8> {
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    if let Some(payload) = payload {
+        body.extend(encode(&payload).unwrap());
+    }
+    body
+}
+
+#[tokio::test]
+async fn test_node_connections_survive_frames_that_fail_to_decode() {
+    let mut node = Node::new(test_node_name("decode_error"), COOKIE);
+    node.start(0).await.unwrap();
+    let mut monitor = node.monitor_nodes();
+    let (received, mut received_rx) = mpsc::unbounded_channel();
+    let local = node.spawn(Recorder { received }).await.unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        // A control message that ends after the version byte
+        write_frame(&mut stream, &[PASS_THROUGH, 131]).await;
+        let unknown = OwnedTerm::tuple(vec![OwnedTerm::integer(99), OwnedTerm::atom("future")]);
+        write_frame(&mut stream, &pass_through(unknown, None)).await;
+        let (send, payload) =
+            ctrl!(Send to OwnedTerm::Pid(local), payload OwnedTerm::atom("after"));
+        write_frame(&mut stream, &pass_through(send.to_term(), Some(payload))).await;
+        stream
+    });
+    node.connect_to_port(PEER, port).await.unwrap();
+    let _stream = peer.await.unwrap();
+
+    let body = timeout(Duration::from_secs(5), received_rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(body, OwnedTerm::atom("after"));
+    assert!(node.connections().contains_key(PEER));
+    while let Some(event) = monitor.try_recv() {
+        assert!(
+            !matches!(event, NodeEvent::NodeDown { .. }),
+            "unexpected {event:?}"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::test_peer::TestPeer;
+use edp_client::{DecodeOffload, DistributionFlags};
+use edp_node::{Node, NodeEvent};
+use erltf::OwnedTerm;
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+
+const COOKIE: &str = "offload_cookie";
+const PEER: &str = "offload_peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE)
+        .with_flags(DistributionFlags::default())
+        .accept(listener)
+        .await
+}
+
+fn send_frame(to: ExternalPid, message: &OwnedTerm) -> Vec<u8> {
+    let control = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(SEND),
+        OwnedTerm::atom(""),
+        OwnedTerm::Pid(to),
+    ]);
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    body.extend(encode(message).unwrap());
+    let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+    frame.extend(body);
+    frame
+}
+
+#[tokio::test]
+async fn test_connections_with_decode_offload_receive_until_the_peer_disconnects() {
+    let name = test_node_name("decode_offload");
+    let node = Node::new(&name, COOKIE).with_decode_offload(DecodeOffload::new(1024));
+    let mut monitor = node.monitor_nodes();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let to = ExternalPid::new(Atom::new(&name), 1, 0, 1);
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        let large = OwnedTerm::Binary(vec![0; 64 * 1024]);
+        for message in [large, OwnedTerm::atom("small")] {
+            stream
+                .write_all(&send_frame(to.clone(), &message))
+                .await
+                .unwrap();
+        }
+    });
+
+    node.connect_to_port(PEER, port).await.unwrap();
+    peer.await.unwrap();
+
+    let node_up = timeout(Duration::from_secs(1), monitor.recv())
+        .await
+        .unwrap();
+    assert!(matches!(node_up, Some(NodeEvent::NodeUp { .. })));
+    let node_down = timeout(Duration::from_secs(5), monitor.recv())
+        .await
+        .unwrap();
+    assert!(
+        matches!(node_down, Some(NodeEvent::NodeDown { ref node, .. }) if *node == Atom::new(PEER))
+    );
+    assert!(!node.connections().contains_key(PEER));
+}
@@ -0,0 +1,145 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_node::{ExitReason, Message};
+use erltf::types::{Atom, ExternalPid, ExternalReference};
+use erltf::{OwnedTerm, erl_atom, erl_int, erl_tuple};
+
+fn pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("test@localhost"), 1, 0, 1)
+}
+
+#[test]
+fn test_from_term_classifies_well_known_atoms() {
+    assert_eq!(
+        ExitReason::from_term(&erl_atom!("normal")),
+        ExitReason::Normal
+    );
+    assert_eq!(
+        ExitReason::from_term(&erl_atom!("shutdown")),
+        ExitReason::Shutdown(None)
+    );
+    assert_eq!(
+        ExitReason::from_term(&erl_atom!("killed")),
+        ExitReason::Killed
+    );
+    assert_eq!(
+        ExitReason::from_term(&erl_atom!("noproc")),
+        ExitReason::Noproc
+    );
+    assert_eq!(
+        ExitReason::from_term(&erl_atom!("noconnection")),
+        ExitReason::Noconnection
+    );
+}
+
+#[test]
+fn test_from_term_shutdown_tuple() {
+    let term = erl_tuple![erl_atom!("shutdown"), erl_atom!("maintenance")];
+    let reason = ExitReason::from_term(&term);
+
+    assert_eq!(reason, ExitReason::Shutdown(Some(erl_atom!("maintenance"))));
+    assert!(reason.is_shutdown());
+    assert!(!reason.is_abnormal());
+}
+
+#[test]
+fn test_from_term_custom_reasons() {
+    let atom = erl_atom!("badarg");
+    assert_eq!(
+        ExitReason::from_term(&atom),
+        ExitReason::Custom(atom.clone())
+    );
+
+    let tuple = erl_tuple![erl_atom!("badmatch"), erl_int!(1)];
+    assert_eq!(
+        ExitReason::from_term(&tuple),
+        ExitReason::Custom(tuple.clone())
+    );
+
+    let not_shutdown = erl_tuple![erl_atom!("shutdown"), erl_int!(1), erl_int!(2)];
+    assert!(matches!(
+        ExitReason::from_term(&not_shutdown),
+        ExitReason::Custom(_)
+    ));
+}
+
+#[test]
+fn test_predicates() {
+    assert!(ExitReason::Normal.is_normal());
+    assert!(!ExitReason::Normal.is_abnormal());
+    assert!(ExitReason::Shutdown(None).is_shutdown());
+    assert!(!ExitReason::Shutdown(None).is_abnormal());
+    assert!(ExitReason::Killed.is_killed());
+    assert!(ExitReason::Killed.is_abnormal());
+    assert!(ExitReason::Noproc.is_noproc());
+    assert!(ExitReason::Noproc.is_abnormal());
+    assert!(ExitReason::Noconnection.is_noconnection());
+    assert!(ExitReason::Noconnection.is_abnormal());
+    assert!(ExitReason::Custom(erl_atom!("badarg")).is_abnormal());
+}
+
+#[test]
+fn test_to_term_round_trip() {
+    let terms = vec![
+        erl_atom!("normal"),
+        erl_atom!("shutdown"),
+        erl_tuple![erl_atom!("shutdown"), erl_atom!("restart")],
+        erl_atom!("killed"),
+        erl_atom!("noproc"),
+        erl_atom!("noconnection"),
+        erl_tuple![erl_atom!("badmatch"), erl_int!(42)],
+    ];
+
+    for term in terms {
+        let reason = ExitReason::from(&term);
+        assert_eq!(OwnedTerm::from(reason), term);
+    }
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(ExitReason::Normal.to_string(), "normal");
+    assert_eq!(ExitReason::Noconnection.to_string(), "noconnection");
+    assert_eq!(
+        ExitReason::Shutdown(Some(erl_atom!("restart"))).to_string(),
+        "{shutdown, restart}"
+    );
+    assert_eq!(
+        ExitReason::Custom(erl_tuple![erl_atom!("badmatch"), erl_int!(42)]).to_string(),
+        "{badmatch, 42}"
+    );
+}
+
+#[test]
+fn test_message_exit_reason() {
+    let exit = Message::Exit {
+        from: pid(),
+        reason: erl_atom!("killed"),
+    };
+    assert_eq!(exit.exit_reason(), Some(ExitReason::Killed));
+
+    let down = Message::MonitorExit {
+        monitored: pid(),
+        reference: ExternalReference::new(Atom::new("test@localhost"), 1, vec![1, 2, 3]),
+        reason: erl_atom!("noproc"),
+    };
+    assert_eq!(down.exit_reason(), Some(ExitReason::Noproc));
+
+    let regular = Message::Regular {
+        from: None,
+        body: erl_atom!("hello"),
+    };
+    assert_eq!(regular.exit_reason(), None);
+}
@@ -0,0 +1,295 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_node::{ExitReason, Mailbox, Message, Node, Process, Result};
+use erltf::OwnedTerm;
+use erltf::decoder::decode_with_trailing;
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid, ExternalReference};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+const COOKIE: &str = "exit_signal_cookie";
+const PEER: &str = "exit_signal_peer@localhost";
+const PASS_THROUGH: u8 = 112;
+const LINK: i64 = 1;
+const SEND: i64 = 2;
+const EXIT: i64 = 3;
+const MONITOR_P: i64 = 19;
+const MONITOR_P_EXIT: i64 = 21;
+const UNLINK_ID: i64 = 35;
+const UNLINK_ID_ACK: i64 = 36;
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+/// Reports `ready` messages to the test and panics on `boom`.
+struct ScriptedProcess {
+    ready: mpsc::UnboundedSender<()>,
+}
+
+impl Process for ScriptedProcess {
+    async fn handle_message(&mut self, msg: Message) -> Result<()> {
+        if let Message::Regular { body, .. } = msg {
+            if body == OwnedTerm::atom("boom") {
+                panic!("boom");
+            }
+            let _ = self.ready.send(());
+        }
+        Ok(())
+    }
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+async fn write_control(
+    stream: &mut TcpStream,
+    control: Vec<OwnedTerm>,
+    payload: Option<OwnedTerm>,
+) {
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&OwnedTerm::tuple(control)).unwrap());
+    if let Some(payload) = payload {
+        body.extend(encode(&payload).unwrap());
+    }
+    stream
+        .write_all(&(body.len() as u32).to_be_bytes())
+        .await
+        .unwrap();
+    stream.write_all(&body).await.unwrap();
+}
+
+async fn read_control(stream: &mut TcpStream) -> Vec<OwnedTerm> {
+    loop {
+        let len = timeout(Duration::from_secs(5), stream.read_u32())
+            .await
+            .unwrap()
+            .unwrap();
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        assert_eq!(data[0], PASS_THROUGH);
+        let (control, _) = decode_with_trailing(&data[1..]).unwrap();
+        let OwnedTerm::Tuple(control) = control else {
+            panic!("unexpected control message: {:?}", control);
+        };
+        return control;
+    }
+}
+
+fn remote_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new(PEER), 77, 0, 1)
+}
+
+fn remote_reference() -> ExternalReference {
+    ExternalReference::new(Atom::new(PEER), 1, vec![7, 8, 9])
+}
+
+/// Connects `node` to a fake peer that links to and monitors `local`, then sends it `message`.
+async fn link_monitor_and_send(node: &Node, local: &ExternalPid, message: &str) -> TcpStream {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (local, message) = (local.clone(), OwnedTerm::atom(message));
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        let remote = OwnedTerm::Pid(remote_pid());
+        let local = OwnedTerm::Pid(local);
+        write_control(
+            &mut stream,
+            vec![OwnedTerm::integer(LINK), remote.clone(), local.clone()],
+            None,
+        )
+        .await;
+        write_control(
+            &mut stream,
+            vec![
+                OwnedTerm::integer(MONITOR_P),
+                remote,
+                local.clone(),
+                OwnedTerm::Reference(remote_reference()),
+            ],
+            None,
+        )
+        .await;
+        write_control(
+            &mut stream,
+            vec![OwnedTerm::integer(SEND), OwnedTerm::atom(""), local],
+            Some(message),
+        )
+        .await;
+        stream
+    });
+    node.connect_to_port(PEER, port).await.unwrap();
+    peer.await.unwrap()
+}
+
+async fn assert_exit_signals(stream: &mut TcpStream, local: &ExternalPid, reason: &OwnedTerm) {
+    let exit = read_control(stream).await;
+    assert_eq!(
+        exit,
+        vec![
+            OwnedTerm::integer(EXIT),
+            OwnedTerm::Pid(local.clone()),
+            OwnedTerm::Pid(remote_pid()),
+            reason.clone(),
+        ]
+    );
+    let down = read_control(stream).await;
+    assert_eq!(
+        down,
+        vec![
+            OwnedTerm::integer(MONITOR_P_EXIT),
+            OwnedTerm::Pid(local.clone()),
+            OwnedTerm::Pid(remote_pid()),
+            OwnedTerm::Reference(remote_reference()),
+            reason.clone(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_panic_is_signalled_to_remote_links_and_monitors() {
+    let mut node = Node::new(test_node_name("exit_signal_panic"), COOKIE);
+    node.start(0).await.unwrap();
+    let (ready, _ready_rx) = mpsc::unbounded_channel();
+    let local = node.spawn(ScriptedProcess { ready }).await.unwrap();
+
+    let mut stream = link_monitor_and_send(&node, &local, "boom").await;
+
+    let reason = OwnedTerm::tuple(vec![
+        OwnedTerm::atom("panic"),
+        OwnedTerm::Binary(b"boom".to_vec()),
+    ]);
+    assert!(ExitReason::from_term(&reason).is_abnormal());
+    assert_exit_signals(&mut stream, &local, &reason).await;
+}
+
+#[tokio::test]
+async fn test_normal_exit_is_signalled_to_remote_links_and_monitors() {
+    let mut node = Node::new(test_node_name("exit_signal_normal"), COOKIE);
+    node.start(0).await.unwrap();
+    let (ready, mut ready_rx) = mpsc::unbounded_channel();
+    let mailbox = Mailbox::new();
+    let sender = mailbox.sender();
+    let local = node
+        .spawn_with_mailbox(ScriptedProcess { ready }, mailbox)
+        .await
+        .unwrap();
+
+    let mut stream = link_monitor_and_send(&node, &local, "ready").await;
+    timeout(Duration::from_secs(5), ready_rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    sender.close();
+
+    assert_exit_signals(&mut stream, &local, &OwnedTerm::atom("normal")).await;
+}
+
+#[tokio::test]
+async fn test_links_and_monitors_of_unknown_processes_get_noproc() {
+    let mut node = Node::new(test_node_name("exit_signal_noproc"), COOKIE);
+    node.start(0).await.unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener).await });
+    node.connect_to_port(PEER, port).await.unwrap();
+    let mut stream = peer.await.unwrap();
+
+    let missing = ExternalPid::new(node.name().clone(), 4096, 0, node.creation());
+    write_control(
+        &mut stream,
+        vec![
+            OwnedTerm::integer(LINK),
+            OwnedTerm::Pid(remote_pid()),
+            OwnedTerm::Pid(missing.clone()),
+        ],
+        None,
+    )
+    .await;
+    write_control(
+        &mut stream,
+        vec![
+            OwnedTerm::integer(MONITOR_P),
+            OwnedTerm::Pid(remote_pid()),
+            OwnedTerm::atom("nobody"),
+            OwnedTerm::Reference(remote_reference()),
+        ],
+        None,
+    )
+    .await;
+
+    assert_eq!(
+        read_control(&mut stream).await,
+        vec![
+            OwnedTerm::integer(EXIT),
+            OwnedTerm::Pid(missing),
+            OwnedTerm::Pid(remote_pid()),
+            OwnedTerm::atom("noproc"),
+        ]
+    );
+    assert_eq!(
+        read_control(&mut stream).await,
+        vec![
+            OwnedTerm::integer(MONITOR_P_EXIT),
+            OwnedTerm::atom("nobody"),
+            OwnedTerm::Pid(remote_pid()),
+            OwnedTerm::Reference(remote_reference()),
+            OwnedTerm::atom("noproc"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_unlink_ids_of_unknown_links_are_acknowledged() {
+    let node = Node::new(test_node_name("exit_signal_unlink_ack"), COOKIE);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener).await });
+    node.connect_to_port(PEER, port).await.unwrap();
+    let mut stream = peer.await.unwrap();
+
+    let local = ExternalPid::new(node.name().clone(), 4096, 0, node.creation());
+    write_control(
+        &mut stream,
+        vec![
+            OwnedTerm::integer(UNLINK_ID),
+            OwnedTerm::integer(9),
+            OwnedTerm::Pid(remote_pid()),
+            OwnedTerm::Pid(local.clone()),
+        ],
+        None,
+    )
+    .await;
+
+    assert_eq!(
+        read_control(&mut stream).await,
+        vec![
+            OwnedTerm::integer(UNLINK_ID_ACK),
+            OwnedTerm::integer(9),
+            OwnedTerm::Pid(local),
+            OwnedTerm::Pid(remote_pid()),
+        ]
+    );
+}
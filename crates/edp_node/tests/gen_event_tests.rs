@@ -193,6 +193,53 @@ async fn test_gen_event_multiple_handlers() {
     assert_eq!(collected2[0], OwnedTerm::Integer(42));
 }
 
+#[tokio::test]
+async fn test_gen_event_handlers_share_one_event() {
+    let mut node = Node::new(test_node_name("test_genevent_shared"), "secret");
+    node.start(0).await.unwrap();
+
+    let events1 = Arc::new(Mutex::new(Vec::new()));
+    let events2 = Arc::new(Mutex::new(Vec::new()));
+
+    let handler1 = LoggerHandler::new(OwnedTerm::Atom(Atom::new("logger1")), events1.clone());
+    let handler2 = LoggerHandler::new(OwnedTerm::Atom(Atom::new("logger2")), events2.clone());
+
+    let mut manager = GenEventManager::new(node.registry());
+    manager
+        .add_handler(Box::new(handler1), OwnedTerm::Atom(Atom::new("ok")))
+        .await
+        .unwrap();
+    manager
+        .add_handler(Box::new(handler2), OwnedTerm::Atom(Atom::new("ok")))
+        .await
+        .unwrap();
+
+    let pid = node.spawn(manager).await.unwrap();
+
+    let event = OwnedTerm::Tuple(vec![
+        OwnedTerm::Atom(Atom::new("payload")),
+        OwnedTerm::Binary(vec![0; 1024]),
+    ]);
+    let notify_msg = OwnedTerm::Tuple(vec![
+        OwnedTerm::Atom(Atom::new("$gen_notify")),
+        event.clone(),
+    ]);
+    node.send(&pid, notify_msg).await.unwrap();
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let collected1 = events1.lock().await;
+    let collected2 = events2.lock().await;
+
+    assert_eq!(collected1[0], event);
+    assert_eq!(collected2[0], event);
+    assert_eq!(collected1[0].resolve().as_tuple().unwrap().len(), 2);
+    match (&collected1[0], &collected2[0]) {
+        (OwnedTerm::Shared(a), OwnedTerm::Shared(b)) => assert!(a.ptr_eq(b)),
+        other => panic!("expected shared events, got {other:?}"),
+    }
+}
+
 //
 // Handler Management Tests
 //
@@ -78,7 +78,7 @@ async fn test_genserver_call() {
 
     let casts = Arc::new(Mutex::new(Vec::new()));
     let server = CounterServer::new(casts.clone());
-    let process = GenServerProcess::new(server, node.registry());
+    let process = GenServerProcess::new(server, &node);
 
     let pid = node.spawn(process).await.unwrap();
     node.register(Atom::new("counter"), pid.clone())
@@ -95,7 +95,7 @@ async fn test_genserver_cast() {
 
     let casts = Arc::new(Mutex::new(Vec::new()));
     let server = CounterServer::new(casts.clone());
-    let process = GenServerProcess::new(server, node.registry());
+    let process = GenServerProcess::new(server, &node);
 
     let pid = node.spawn(process).await.unwrap();
 
@@ -0,0 +1,69 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_client::DistributionFlags;
+use edp_client::test_peer::TestPeer;
+use edp_node::Node;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::sleep;
+
+const COOKIE: &str = "hibernation_cookie";
+const PEER: &str = "hibernation_peer@127.0.0.1";
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE)
+        .with_flags(DistributionFlags::default())
+        .accept(listener)
+        .await
+}
+
+async fn is_hibernating(node: &Node) -> bool {
+    let conn = node.connections().get(PEER).unwrap().clone();
+    conn.lock().await.is_hibernating()
+}
+
+#[tokio::test]
+async fn test_idle_connections_are_hibernated() {
+    let node = Node::new(test_node_name("idle_hibernation"), COOKIE)
+        .with_idle_hibernation(Duration::from_millis(30));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener).await });
+
+    node.connect_to_port(PEER, port).await.unwrap();
+    let _stream = peer.await.unwrap();
+    assert!(!is_hibernating(&node).await);
+
+    sleep(Duration::from_millis(150)).await;
+    assert!(is_hibernating(&node).await);
+    assert!(node.connections().contains_key(PEER));
+}
+
+#[tokio::test]
+async fn test_connections_are_not_hibernated_by_default() {
+    let node = Node::new(test_node_name("no_hibernation"), COOKIE);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move { accept_handshake(&listener).await });
+
+    node.connect_to_port(PEER, port).await.unwrap();
+    let _stream = peer.await.unwrap();
+
+    sleep(Duration::from_millis(50)).await;
+    assert!(!is_hibernating(&node).await);
+}
@@ -0,0 +1,200 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_node::{Error, Mailbox, Message, Node, OverflowPolicy, Process, Result};
+use erltf::OwnedTerm;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::time::timeout;
+
+fn msg(n: i64) -> Message {
+    Message::Regular {
+        from: None,
+        body: OwnedTerm::Integer(n),
+    }
+}
+
+fn body(msg: Message) -> OwnedTerm {
+    match msg {
+        Message::Regular { body, .. } => body,
+        other => panic!("unexpected message: {:?}", other),
+    }
+}
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+#[tokio::test]
+async fn test_drop_oldest_keeps_latest_messages() {
+    let mut mailbox = Mailbox::bounded(2, OverflowPolicy::DropOldest);
+    let sender = mailbox.sender();
+    for n in 1..=4 {
+        sender.send(msg(n)).await.unwrap();
+    }
+
+    assert_eq!(body(mailbox.recv().await.unwrap()), OwnedTerm::Integer(3));
+    assert_eq!(body(mailbox.recv().await.unwrap()), OwnedTerm::Integer(4));
+
+    let stats = mailbox.stats();
+    assert_eq!(stats.capacity, 2);
+    assert_eq!(stats.len, 0);
+    assert_eq!(stats.peak_len, 2);
+    assert_eq!(stats.enqueued, 4);
+    assert_eq!(stats.dequeued, 2);
+    assert_eq!(stats.dropped, 2);
+    assert_eq!(stats.rejected, 0);
+}
+
+#[tokio::test]
+async fn test_drop_newest_keeps_earliest_messages() {
+    let mut mailbox = Mailbox::bounded(2, OverflowPolicy::DropNewest);
+    for n in 1..=4 {
+        mailbox.send(msg(n)).await.unwrap();
+    }
+
+    assert_eq!(body(mailbox.recv().await.unwrap()), OwnedTerm::Integer(1));
+    assert_eq!(body(mailbox.recv().await.unwrap()), OwnedTerm::Integer(2));
+    assert!(mailbox.try_recv().is_err());
+    assert_eq!(mailbox.stats().dropped, 2);
+    assert_eq!(mailbox.stats().enqueued, 2);
+}
+
+#[tokio::test]
+async fn test_exit_policy_rejects_when_full() {
+    let mut mailbox = Mailbox::bounded(1, OverflowPolicy::Exit);
+    let sender = mailbox.sender();
+    sender.send(msg(1)).await.unwrap();
+
+    assert!(matches!(sender.send(msg(2)).await, Err(Error::MailboxFull)));
+    assert_eq!(sender.stats().rejected, 1);
+
+    mailbox.recv().await.unwrap();
+    sender.send(msg(3)).await.unwrap();
+    assert_eq!(body(mailbox.recv().await.unwrap()), OwnedTerm::Integer(3));
+}
+
+#[tokio::test]
+async fn test_block_policy_waits_for_space() {
+    let mut mailbox = Mailbox::with_capacity(1);
+    assert_eq!(mailbox.policy(), OverflowPolicy::Block);
+    let sender = mailbox.sender();
+    sender.send(msg(1)).await.unwrap();
+
+    assert!(matches!(sender.try_send(msg(2)), Err(Error::MailboxFull)));
+    assert!(
+        timeout(Duration::from_millis(50), sender.send(msg(2)))
+            .await
+            .is_err()
+    );
+
+    let blocked = sender.clone();
+    let (tx, rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let _ = tx.send(blocked.send(msg(2)).await);
+    });
+    assert_eq!(body(mailbox.recv().await.unwrap()), OwnedTerm::Integer(1));
+    timeout(Duration::from_secs(1), rx)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(body(mailbox.recv().await.unwrap()), OwnedTerm::Integer(2));
+}
+
+#[tokio::test]
+async fn test_close_releases_blocked_senders() {
+    let mut mailbox = Mailbox::with_capacity(1);
+    let sender = mailbox.sender();
+    sender.send(msg(1)).await.unwrap();
+
+    let blocked = sender.clone();
+    let task = tokio::spawn(async move { blocked.send(msg(2)).await });
+    tokio::task::yield_now().await;
+    mailbox.close();
+
+    let result = timeout(Duration::from_secs(1), task)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(result, Err(Error::MailboxClosed)));
+    assert!(sender.is_closed());
+
+    // Messages queued before closing are still delivered
+    assert_eq!(body(mailbox.recv().await.unwrap()), OwnedTerm::Integer(1));
+    assert!(matches!(mailbox.recv().await, Err(Error::MailboxClosed)));
+}
+
+#[tokio::test]
+async fn test_dropping_mailbox_closes_senders() {
+    let mailbox = Mailbox::new();
+    let sender = mailbox.sender();
+    drop(mailbox);
+    assert!(matches!(
+        sender.send(msg(1)).await,
+        Err(Error::MailboxClosed)
+    ));
+}
+
+#[test]
+#[should_panic(expected = "greater than zero")]
+fn test_zero_capacity_panics() {
+    let _ = Mailbox::bounded(0, OverflowPolicy::DropOldest);
+}
+
+struct GatedProcess {
+    gate: Option<oneshot::Receiver<()>>,
+}
+
+impl Process for GatedProcess {
+    async fn handle_message(&mut self, _msg: Message) -> Result<()> {
+        if let Some(gate) = self.gate.take() {
+            let _ = gate.await;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_spawn_with_bounded_mailbox() {
+    let mut node = Node::new(test_node_name("mailbox"), "secret");
+    node.start(0).await.unwrap();
+
+    let (open_gate, gate) = oneshot::channel();
+    let pid = node
+        .spawn_with_mailbox(
+            GatedProcess { gate: Some(gate) },
+            Mailbox::bounded(2, OverflowPolicy::Exit),
+        )
+        .await
+        .unwrap();
+
+    // The first message is taken by the process, which then waits on the gate
+    node.send(&pid, OwnedTerm::Integer(0)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    node.send(&pid, OwnedTerm::Integer(1)).await.unwrap();
+    node.send(&pid, OwnedTerm::Integer(2)).await.unwrap();
+    assert!(matches!(
+        node.send(&pid, OwnedTerm::Integer(3)).await,
+        Err(Error::MailboxFull)
+    ));
+
+    let stats = node.mailbox_stats(&pid).await.unwrap();
+    assert_eq!(stats.len, 2);
+    assert_eq!(stats.rejected, 1);
+
+    open_gate.send(()).unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(node.mailbox_stats(&pid).await.unwrap().len, 0);
+}
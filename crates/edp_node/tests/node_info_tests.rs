@@ -0,0 +1,121 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_node::node_info::{
+    DEFAULT_SNAPSHOT_BATCH_SIZE, DEFAULT_SNAPSHOT_PIPELINE_DEPTH, process_snapshot,
+};
+use edp_node::{
+    Atom, DEFAULT_RPC_TIMEOUT, Error, ExternalPid, Mfa, Node, OwnedTerm, ProcessInfo,
+    SnapshotOptions, erl_atom, erl_int, erl_list, erl_tuple,
+};
+use std::time::Duration;
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+fn remote_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new("erlang@localhost"), 42, 0, 1)
+}
+
+#[test]
+fn test_snapshot_options_defaults_and_builder() {
+    let opts = SnapshotOptions::default();
+    assert_eq!(opts.batch_size, DEFAULT_SNAPSHOT_BATCH_SIZE);
+    assert_eq!(opts.pipeline_depth, DEFAULT_SNAPSHOT_PIPELINE_DEPTH);
+    assert_eq!(opts.timeout, DEFAULT_RPC_TIMEOUT);
+
+    let opts = SnapshotOptions::new()
+        .with_batch_size(0)
+        .with_pipeline_depth(0)
+        .with_timeout(Duration::from_secs(2));
+    assert_eq!(opts.batch_size, 1);
+    assert_eq!(opts.pipeline_depth, 1);
+    assert_eq!(opts.timeout, Duration::from_secs(2));
+}
+
+#[test]
+fn test_process_info_from_proplist() {
+    let info = erl_list![
+        erl_tuple!(erl_atom!("registered_name"), erl_atom!("logger")),
+        erl_tuple!(
+            erl_atom!("current_function"),
+            erl_tuple!(erl_atom!("gen_server"), erl_atom!("loop"), erl_int!(7))
+        ),
+        erl_tuple!(
+            erl_atom!("initial_call"),
+            erl_tuple!(erl_atom!("proc_lib"), erl_atom!("init_p"), erl_int!(5))
+        ),
+        erl_tuple!(erl_atom!("status"), erl_atom!("waiting")),
+        erl_tuple!(erl_atom!("memory"), erl_int!(10_000)),
+        erl_tuple!(erl_atom!("message_queue_len"), erl_int!(3)),
+        erl_tuple!(erl_atom!("reductions"), erl_int!(123_456)),
+        erl_tuple!(erl_atom!("heap_size"), erl_int!(376)),
+        erl_tuple!(erl_atom!("total_heap_size"), erl_int!(1_000)),
+        erl_tuple!(erl_atom!("stack_size"), erl_int!(12))
+    ];
+
+    let info = ProcessInfo::from_proplist(remote_pid(), &info).unwrap();
+    assert_eq!(info.pid, remote_pid());
+    assert_eq!(info.registered_name, Some(Atom::new("logger")));
+    assert_eq!(
+        info.current_function,
+        Some(Mfa::new("gen_server", "loop", 7))
+    );
+    assert_eq!(info.initial_call, Some(Mfa::new("proc_lib", "init_p", 5)));
+    assert_eq!(info.status, Some(Atom::new("waiting")));
+    assert_eq!(info.memory, 10_000);
+    assert_eq!(info.message_queue_len, 3);
+    assert_eq!(info.reductions, 123_456);
+    assert_eq!(info.heap_size, 376);
+    assert_eq!(info.total_heap_size, 1_000);
+    assert_eq!(info.stack_size, 12);
+}
+
+#[test]
+fn test_process_info_without_registered_name() {
+    let info = erl_list![
+        erl_tuple!(erl_atom!("registered_name"), OwnedTerm::List(vec![])),
+        erl_tuple!(erl_atom!("memory"), erl_int!(2_000))
+    ];
+
+    let info = ProcessInfo::from_proplist(remote_pid(), &info).unwrap();
+    assert_eq!(info.registered_name, None);
+    assert_eq!(info.current_function, None);
+    assert_eq!(info.memory, 2_000);
+    assert_eq!(info.message_queue_len, 0);
+}
+
+#[test]
+fn test_process_info_for_exited_process() {
+    assert_eq!(
+        ProcessInfo::from_proplist(remote_pid(), &erl_atom!("undefined")),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_process_snapshot_requires_connection() {
+    let mut node = Node::new(test_node_name("node_info1"), "secret");
+    node.start(0).await.unwrap();
+
+    let result = process_snapshot(
+        &node,
+        "not_connected@localhost",
+        &SnapshotOptions::default(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(Error::NodeNotConnected(_))));
+}
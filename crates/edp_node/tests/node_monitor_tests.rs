@@ -0,0 +1,142 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::DistributionFlags;
+use edp_client::Error as ClientError;
+use edp_client::test_peer::TestPeer;
+use edp_node::{Node, NodeDownReason, NodeEvent};
+use erltf::OwnedTerm;
+use erltf::types::Atom;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+
+const COOKIE: &str = "monitor_cookie";
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+/// Accepts one connection, completes the handshake and returns the stream.
+async fn accept_handshake(listener: TcpListener) -> TcpStream {
+    TestPeer::new("peer@localhost", COOKIE)
+        .with_flags(DistributionFlags::default())
+        .accept(&listener)
+        .await
+}
+
+#[test]
+fn test_node_event_to_term() {
+    let up = NodeEvent::NodeUp {
+        node: Atom::new("a@localhost"),
+    };
+    assert!(up.is_up());
+    assert_eq!(up.node(), &Atom::new("a@localhost"));
+    assert_eq!(
+        up.to_term(),
+        OwnedTerm::Tuple(vec![
+            OwnedTerm::Atom(Atom::new("nodeup")),
+            OwnedTerm::Atom(Atom::new("a@localhost")),
+            OwnedTerm::Nil,
+        ])
+    );
+
+    let down = NodeEvent::NodeDown {
+        node: Atom::new("a@localhost"),
+        reason: NodeDownReason::NetTickTimeout,
+    };
+    assert!(!down.is_up());
+    assert_eq!(
+        down.to_term(),
+        OwnedTerm::Tuple(vec![
+            OwnedTerm::Atom(Atom::new("nodedown")),
+            OwnedTerm::Atom(Atom::new("a@localhost")),
+            OwnedTerm::List(vec![OwnedTerm::Tuple(vec![
+                OwnedTerm::Atom(Atom::new("nodedown_reason")),
+                OwnedTerm::Atom(Atom::new("net_tick_timeout")),
+            ])]),
+        ])
+    );
+}
+
+#[test]
+fn test_node_down_reason_from_error() {
+    assert_eq!(
+        NodeDownReason::from_error(&ClientError::Timeout(Duration::from_secs(60))),
+        NodeDownReason::NetTickTimeout
+    );
+    assert_eq!(
+        NodeDownReason::from_error(&ClientError::ConnectionClosed),
+        NodeDownReason::ConnectionClosed
+    );
+    assert_eq!(
+        NodeDownReason::ConnectionSetupFailed.as_str(),
+        "connection_setup_failed"
+    );
+}
+
+#[tokio::test]
+async fn test_failed_connection_reports_connection_setup_failed() {
+    let mut node = Node::new(test_node_name("monitor_setup"), COOKIE);
+    node.start(0).await.unwrap();
+    let mut monitor = node.monitor_nodes();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    assert!(node.connect_to_port("peer@127.0.0.1", port).await.is_err());
+    assert_eq!(
+        monitor.try_recv(),
+        Some(NodeEvent::NodeDown {
+            node: Atom::new("peer@127.0.0.1"),
+            reason: NodeDownReason::ConnectionSetupFailed,
+        })
+    );
+    assert_eq!(monitor.try_recv(), None);
+}
+
+#[tokio::test]
+async fn test_nodeup_and_nodedown_on_connection_closed() {
+    let mut node = Node::new(test_node_name("monitor_updown"), COOKIE);
+    node.start(0).await.unwrap();
+    let mut monitor = node.monitor_nodes();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(accept_handshake(listener));
+
+    node.connect_to_port("peer@127.0.0.1", port).await.unwrap();
+    let event = timeout(Duration::from_secs(1), monitor.recv())
+        .await
+        .unwrap();
+    assert_eq!(
+        event,
+        Some(NodeEvent::NodeUp {
+            node: Atom::new("peer@127.0.0.1"),
+        })
+    );
+
+    drop(peer.await.unwrap());
+    let event = timeout(Duration::from_secs(1), monitor.recv())
+        .await
+        .unwrap();
+    assert_eq!(
+        event,
+        Some(NodeEvent::NodeDown {
+            node: Atom::new("peer@127.0.0.1"),
+            reason: NodeDownReason::ConnectionClosed,
+        })
+    );
+}
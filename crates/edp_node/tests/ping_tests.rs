@@ -0,0 +1,163 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_node::{Error, Node};
+use erltf::OwnedTerm;
+use erltf::decoder::decode_with_trailing;
+use erltf::encoder::encode;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const COOKIE: &str = "ping_cookie";
+const PEER: &str = "ping_peer@localhost";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+const REG_SEND: i64 = 6;
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+async fn read_frame(stream: &mut TcpStream) -> Vec<u8> {
+    loop {
+        let len = stream.read_u32().await.unwrap();
+        if len == 0 {
+            continue;
+        }
+        let mut data = vec![0u8; len as usize];
+        stream.read_exact(&mut data).await.unwrap();
+        return data;
+    }
+}
+
+/// Plays `net_kernel`: answers one `is_auth` call with `answer`, returns the call request
+/// and the stream, which is kept open.
+async fn answer_is_auth(listener: TcpListener, answer: &str) -> (OwnedTerm, TcpStream) {
+    let mut stream = accept_handshake(&listener).await;
+    let frame = read_frame(&mut stream).await;
+    assert_eq!(frame[0], PASS_THROUGH);
+    let (control, rest) = decode_with_trailing(&frame[1..]).unwrap();
+    let (request, _) = decode_with_trailing(rest).unwrap();
+
+    let OwnedTerm::Tuple(control) = control else {
+        panic!("unexpected control message: {:?}", control);
+    };
+    assert_eq!(control[0], OwnedTerm::integer(REG_SEND));
+    assert_eq!(control[3], OwnedTerm::atom("net_kernel"));
+
+    let OwnedTerm::Tuple(call) = &request else {
+        panic!("unexpected request: {:?}", request);
+    };
+    let OwnedTerm::Tuple(from) = &call[1] else {
+        panic!("unexpected from: {:?}", call[1]);
+    };
+    let reply_control = OwnedTerm::tuple(vec![
+        OwnedTerm::integer(SEND),
+        OwnedTerm::atom(""),
+        from[0].clone(),
+    ]);
+    let reply = OwnedTerm::tuple(vec![from[1].clone(), OwnedTerm::atom(answer)]);
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&reply_control).unwrap());
+    body.extend(encode(&reply).unwrap());
+    stream
+        .write_all(&(body.len() as u32).to_be_bytes())
+        .await
+        .unwrap();
+    stream.write_all(&body).await.unwrap();
+    (request, stream)
+}
+
+#[tokio::test]
+async fn test_ping_records_rtt() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(answer_is_auth(listener, "yes"));
+
+    let mut node = Node::new(test_node_name("ping_rtt"), COOKIE);
+    node.start(0).await.unwrap();
+    node.connect_to_port(PEER, port).await.unwrap();
+
+    let rtt = node.ping(PEER).await.unwrap();
+    let metrics = node.connection_metrics(PEER).await.unwrap();
+    assert_eq!(metrics.rtt_samples(), 1);
+    assert_eq!(metrics.last_rtt(), Some(rtt));
+    assert!(metrics.last_received().is_some());
+
+    let (request, _stream) = peer.await.unwrap();
+    let OwnedTerm::Tuple(call) = request else {
+        panic!("unexpected request");
+    };
+    assert_eq!(call[0], OwnedTerm::atom("$gen_call"));
+    assert_eq!(
+        call[2],
+        OwnedTerm::tuple(vec![
+            OwnedTerm::atom("is_auth"),
+            OwnedTerm::Atom(node.name().clone()),
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_ping_rejects_unexpected_reply() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(answer_is_auth(listener, "no"));
+
+    let mut node = Node::new(test_node_name("ping_no"), COOKIE);
+    node.start(0).await.unwrap();
+    node.connect_to_port(PEER, port).await.unwrap();
+
+    let err = node.ping(PEER).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidMessage(_)));
+    assert_eq!(
+        node.connection_metrics(PEER).await.unwrap().rtt_samples(),
+        0
+    );
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_ping_times_out_without_reply() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        read_frame(&mut stream).await;
+        stream
+    });
+
+    let mut node = Node::new(test_node_name("ping_timeout"), COOKIE);
+    node.start(0).await.unwrap();
+    node.connect_to_port(PEER, port).await.unwrap();
+
+    let err = node
+        .ping_with_timeout(PEER, Duration::from_millis(100))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::RpcTimeout(_)));
+    drop(peer.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_connection_metrics_requires_a_connection() {
+    let node = Node::new(test_node_name("ping_metrics"), COOKIE);
+    assert!(node.connection_metrics(PEER).await.is_none());
+}
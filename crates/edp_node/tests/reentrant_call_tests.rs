@@ -0,0 +1,401 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_node::{
+    CallResult, GenServer, GenServerProcess, Message, Node, Process, Reentrancy, Result,
+};
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::copy_bidirectional;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Mutex, mpsc};
+use tokio::time::timeout;
+
+const CALL_TIMEOUT: Duration = Duration::from_millis(300);
+const COOKIE: &str = "secret";
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+/// Forwards every message it receives to the test.
+struct Collector {
+    received: mpsc::UnboundedSender<OwnedTerm>,
+}
+
+impl Process for Collector {
+    async fn handle_message(&mut self, msg: Message) -> Result<()> {
+        if let Message::Regular { body, .. } = msg {
+            let _ = self.received.send(body);
+        }
+        Ok(())
+    }
+}
+
+/// Serves `whoami` with its name and asks its peer for `whoami` when it handles `ping`,
+/// so two servers pinging each other call each other back.
+struct CrossCallingServer {
+    name: &'static str,
+    peer: Option<ExternalPid>,
+    casts: Arc<Mutex<Vec<OwnedTerm>>>,
+}
+
+impl CrossCallingServer {
+    fn new(name: &'static str, casts: Arc<Mutex<Vec<OwnedTerm>>>) -> Self {
+        Self {
+            name,
+            peer: None,
+            casts,
+        }
+    }
+}
+
+impl GenServer for CrossCallingServer {
+    async fn init(&mut self, _args: Vec<OwnedTerm>) -> Result<()> {
+        Ok(())
+    }
+
+    async fn handle_call(&mut self, msg: OwnedTerm, _from: ExternalPid) -> Result<CallResult> {
+        if let OwnedTerm::Pid(peer) = msg {
+            self.peer = Some(peer);
+            return Ok(CallResult::Reply(OwnedTerm::atom("ok")));
+        }
+        let peer = self.peer.clone().expect("peer is set first");
+        let result = match msg.as_atom().map(Atom::as_str) {
+            Some("whoami") => CallResult::Reply(OwnedTerm::atom(self.name)),
+            Some("ping") => CallResult::Call {
+                to: peer,
+                request: OwnedTerm::atom("ping_back"),
+                timeout: CALL_TIMEOUT,
+            },
+            Some("ping_back") => CallResult::Call {
+                to: peer,
+                request: OwnedTerm::atom("whoami"),
+                timeout: CALL_TIMEOUT,
+            },
+            Some("hold") => CallResult::NoReply,
+            Some("hold_peer") => CallResult::Call {
+                to: peer,
+                request: OwnedTerm::atom("hold"),
+                timeout: CALL_TIMEOUT,
+            },
+            _ => CallResult::NoReply,
+        };
+        Ok(result)
+    }
+
+    async fn handle_call_reply(
+        &mut self,
+        reply: Result<OwnedTerm>,
+        _from: ExternalPid,
+    ) -> Result<CallResult> {
+        match reply {
+            Ok(reply) => Ok(CallResult::Reply(reply)),
+            Err(e) if e.is_timeout() => Ok(CallResult::Reply(OwnedTerm::atom("timeout"))),
+            Err(_) => Ok(CallResult::Reply(OwnedTerm::atom("noproc"))),
+        }
+    }
+
+    async fn handle_cast(&mut self, msg: OwnedTerm) -> Result<()> {
+        self.casts.lock().await.push(msg);
+        Ok(())
+    }
+
+    async fn handle_info(&mut self, _msg: OwnedTerm) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct Harness {
+    node: Node,
+    collector: ExternalPid,
+    replies: mpsc::UnboundedReceiver<OwnedTerm>,
+}
+
+impl Harness {
+    async fn start(base: &str) -> Self {
+        let mut node = Node::new(test_node_name(base), COOKIE);
+        node.start(0).await.unwrap();
+        let (received, replies) = mpsc::unbounded_channel();
+        let collector = node.spawn(Collector { received }).await.unwrap();
+        Self {
+            node,
+            collector,
+            replies,
+        }
+    }
+
+    async fn spawn(&self, server: CrossCallingServer, reentrancy: Reentrancy) -> ExternalPid {
+        let process = GenServerProcess::new(server, &self.node).with_reentrancy(reentrancy);
+        self.node.spawn(process).await.unwrap()
+    }
+
+    /// Spawns two servers that know each other.
+    async fn spawn_pair(
+        &mut self,
+        reentrancy: Reentrancy,
+        casts: Arc<Mutex<Vec<OwnedTerm>>>,
+    ) -> (ExternalPid, ExternalPid) {
+        let a = self
+            .spawn(CrossCallingServer::new("a", casts.clone()), reentrancy)
+            .await;
+        let b = self
+            .spawn(CrossCallingServer::new("b", casts), reentrancy)
+            .await;
+        assert_eq!(
+            self.call(&a, OwnedTerm::Pid(b.clone())).await,
+            OwnedTerm::atom("ok")
+        );
+        assert_eq!(
+            self.call(&b, OwnedTerm::Pid(a.clone())).await,
+            OwnedTerm::atom("ok")
+        );
+        (a, b)
+    }
+
+    async fn call(&mut self, to: &ExternalPid, request: OwnedTerm) -> OwnedTerm {
+        let reference = self.node.make_reference();
+        let call = OwnedTerm::tuple(vec![
+            OwnedTerm::atom("$gen_call"),
+            OwnedTerm::tuple(vec![
+                OwnedTerm::Pid(self.collector.clone()),
+                OwnedTerm::Reference(reference.clone()),
+            ]),
+            request,
+        ]);
+        self.node.send(to, call).await.unwrap();
+
+        let reply = timeout(Duration::from_secs(5), self.replies.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let (tag, reply) = reply.as_2_tuple().unwrap();
+        assert_eq!(tag, &OwnedTerm::Reference(reference));
+        reply.clone()
+    }
+}
+
+/// Completes the handshake of a connection to `node` as that node.
+async fn accept_handshake_as(listener: &TcpListener, node: &Node) -> TcpStream {
+    TestPeer::new(node.name().as_str(), COOKIE)
+        .with_creation(node.creation())
+        .accept(listener)
+        .await
+}
+
+/// Connects two nodes through a relay that handshakes with each of them as the other
+/// node, then passes the distribution traffic through.
+async fn connect_nodes(a: &Node, b: &Node) {
+    let to_b = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let to_a = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let (b_port, a_port) = (
+        to_b.local_addr().unwrap().port(),
+        to_a.local_addr().unwrap().port(),
+    );
+
+    let (mut from_a, mut from_b) = tokio::join!(
+        async {
+            let (stream, connected) = tokio::join!(
+                accept_handshake_as(&to_b, b),
+                a.connect_to_port(b.name().as_str(), b_port)
+            );
+            connected.unwrap();
+            stream
+        },
+        async {
+            let (stream, connected) = tokio::join!(
+                accept_handshake_as(&to_a, a),
+                b.connect_to_port(a.name().as_str(), a_port)
+            );
+            connected.unwrap();
+            stream
+        }
+    );
+    tokio::spawn(async move {
+        let _ = copy_bidirectional(&mut from_a, &mut from_b).await;
+    });
+}
+
+#[tokio::test]
+async fn test_reentrant_servers_serve_calls_while_awaiting_their_own() {
+    let mut harness = Harness::start("reentrant_cross_call").await;
+    let casts = Arc::new(Mutex::new(Vec::new()));
+    let (a, b) = harness.spawn_pair(Reentrancy::Enabled, casts).await;
+
+    // a calls b, which calls a back before replying
+    assert_eq!(
+        harness.call(&a, OwnedTerm::atom("ping")).await,
+        OwnedTerm::atom("a")
+    );
+    assert_eq!(
+        harness.call(&b, OwnedTerm::atom("ping")).await,
+        OwnedTerm::atom("b")
+    );
+}
+
+#[tokio::test]
+async fn test_non_reentrant_servers_calling_each_other_time_out() {
+    let mut harness = Harness::start("non_reentrant_cross_call").await;
+    let casts = Arc::new(Mutex::new(Vec::new()));
+    let (a, _b) = harness.spawn_pair(Reentrancy::Disabled, casts).await;
+
+    // b's call back to a is deferred until a's call to b is answered
+    assert_eq!(
+        harness.call(&a, OwnedTerm::atom("ping")).await,
+        OwnedTerm::atom("timeout")
+    );
+    // a serves the deferred call afterwards and keeps working
+    assert_eq!(
+        harness.call(&a, OwnedTerm::atom("whoami")).await,
+        OwnedTerm::atom("a")
+    );
+}
+
+#[tokio::test]
+async fn test_non_reentrant_servers_defer_messages_until_the_reply() {
+    let mut harness = Harness::start("non_reentrant_defer").await;
+    let casts = Arc::new(Mutex::new(Vec::new()));
+    let (a, _b) = harness
+        .spawn_pair(Reentrancy::Disabled, casts.clone())
+        .await;
+
+    let reference = harness.node.make_reference();
+    let call = OwnedTerm::tuple(vec![
+        OwnedTerm::atom("$gen_call"),
+        OwnedTerm::tuple(vec![
+            OwnedTerm::Pid(harness.collector.clone()),
+            OwnedTerm::Reference(reference),
+        ]),
+        OwnedTerm::atom("hold_peer"),
+    ]);
+    harness.node.send(&a, call).await.unwrap();
+    let cast = OwnedTerm::tuple(vec![OwnedTerm::atom("$gen_cast"), OwnedTerm::atom("note")]);
+    harness.node.send(&a, cast).await.unwrap();
+
+    tokio::time::sleep(CALL_TIMEOUT / 3).await;
+    assert!(casts.lock().await.is_empty());
+
+    let reply = timeout(Duration::from_secs(5), harness.replies.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(reply.as_2_tuple().unwrap().1, &OwnedTerm::atom("timeout"));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(*casts.lock().await, vec![OwnedTerm::atom("note")]);
+}
+
+#[tokio::test]
+async fn test_reentrant_servers_handle_casts_while_awaiting_a_reply() {
+    let mut harness = Harness::start("reentrant_cast").await;
+    let casts = Arc::new(Mutex::new(Vec::new()));
+    let (a, _b) = harness.spawn_pair(Reentrancy::Enabled, casts.clone()).await;
+
+    let reference = harness.node.make_reference();
+    let call = OwnedTerm::tuple(vec![
+        OwnedTerm::atom("$gen_call"),
+        OwnedTerm::tuple(vec![
+            OwnedTerm::Pid(harness.collector.clone()),
+            OwnedTerm::Reference(reference),
+        ]),
+        OwnedTerm::atom("hold_peer"),
+    ]);
+    harness.node.send(&a, call).await.unwrap();
+    let cast = OwnedTerm::tuple(vec![OwnedTerm::atom("$gen_cast"), OwnedTerm::atom("note")]);
+    harness.node.send(&a, cast).await.unwrap();
+
+    tokio::time::sleep(CALL_TIMEOUT / 3).await;
+    assert_eq!(*casts.lock().await, vec![OwnedTerm::atom("note")]);
+}
+
+#[tokio::test]
+async fn test_calls_to_unknown_processes_fail_without_blocking() {
+    let mut harness = Harness::start("reentrant_noproc").await;
+    let casts = Arc::new(Mutex::new(Vec::new()));
+    let (a, b) = harness.spawn_pair(Reentrancy::Disabled, casts).await;
+
+    let unknown = ExternalPid::new(a.node.clone(), 4096, 0, a.creation);
+    assert_eq!(
+        harness.call(&b, OwnedTerm::Pid(unknown)).await,
+        OwnedTerm::atom("ok")
+    );
+    assert_eq!(
+        harness.call(&b, OwnedTerm::atom("ping_back")).await,
+        OwnedTerm::atom("noproc")
+    );
+    assert_eq!(
+        harness.call(&b, OwnedTerm::atom("whoami")).await,
+        OwnedTerm::atom("b")
+    );
+}
+
+#[tokio::test]
+async fn test_servers_on_two_nodes_call_each_other() {
+    let mut first = Harness::start("remote_cross_call_a").await;
+    let mut second = Harness::start("remote_cross_call_b").await;
+    connect_nodes(&first.node, &second.node).await;
+    let casts = Arc::new(Mutex::new(Vec::new()));
+    let a = first
+        .spawn(
+            CrossCallingServer::new("a", casts.clone()),
+            Reentrancy::Enabled,
+        )
+        .await;
+    let b = second
+        .spawn(CrossCallingServer::new("b", casts), Reentrancy::Enabled)
+        .await;
+    assert_eq!(
+        first.call(&a, OwnedTerm::Pid(b.clone())).await,
+        OwnedTerm::atom("ok")
+    );
+    assert_eq!(
+        second.call(&b, OwnedTerm::Pid(a.clone())).await,
+        OwnedTerm::atom("ok")
+    );
+
+    // a calls b on the other node, which calls a back before replying
+    assert_eq!(
+        first.call(&a, OwnedTerm::atom("ping")).await,
+        OwnedTerm::atom("a")
+    );
+    assert_eq!(
+        second.call(&b, OwnedTerm::atom("ping")).await,
+        OwnedTerm::atom("b")
+    );
+}
+
+#[tokio::test]
+async fn test_calls_to_disconnected_nodes_fail_without_blocking() {
+    let mut harness = Harness::start("remote_call_not_connected").await;
+    let casts = Arc::new(Mutex::new(Vec::new()));
+    let a = harness
+        .spawn(CrossCallingServer::new("a", casts), Reentrancy::Disabled)
+        .await;
+
+    let remote = ExternalPid::new(Atom::new("nowhere@localhost"), 77, 0, 1);
+    assert_eq!(
+        harness.call(&a, OwnedTerm::Pid(remote)).await,
+        OwnedTerm::atom("ok")
+    );
+    assert_eq!(
+        harness.call(&a, OwnedTerm::atom("ping_back")).await,
+        OwnedTerm::atom("noproc")
+    );
+    assert_eq!(
+        harness.call(&a, OwnedTerm::atom("whoami")).await,
+        OwnedTerm::atom("a")
+    );
+}
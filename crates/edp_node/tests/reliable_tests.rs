@@ -0,0 +1,82 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_node::reliable::{
+    DEFAULT_RELIABLE_ACK_TIMEOUT, DEFAULT_RELIABLE_MAX_ATTEMPTS, DEFAULT_RELIABLE_RETRY_DELAY,
+    RELIABLE_HELPER_FORMS, RELIABLE_HELPER_MODULE,
+};
+use edp_node::{Atom, Error, ExternalPid, Node, ReliableSender, ReliableTarget, RetryPolicy};
+use std::time::Duration;
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+#[test]
+fn test_retry_policy_defaults_and_builder() {
+    let policy = RetryPolicy::default();
+    assert_eq!(policy.max_attempts, DEFAULT_RELIABLE_MAX_ATTEMPTS);
+    assert_eq!(policy.ack_timeout, DEFAULT_RELIABLE_ACK_TIMEOUT);
+    assert_eq!(policy.retry_delay, DEFAULT_RELIABLE_RETRY_DELAY);
+
+    let policy = RetryPolicy::new()
+        .with_max_attempts(0)
+        .with_ack_timeout(Duration::from_millis(100))
+        .with_retry_delay(Duration::from_millis(10));
+    assert_eq!(policy.max_attempts, 1);
+    assert_eq!(policy.ack_timeout, Duration::from_millis(100));
+    assert_eq!(policy.retry_delay, Duration::from_millis(10));
+}
+
+#[test]
+fn test_reliable_target_conversions() {
+    let pid = ExternalPid::new(Atom::new("erlang@localhost"), 1, 0, 1);
+    assert_eq!(ReliableTarget::from(pid.clone()), ReliableTarget::Pid(pid));
+    assert_eq!(
+        ReliableTarget::from(Atom::new("logger")),
+        ReliableTarget::Name(Atom::new("logger"))
+    );
+}
+
+#[test]
+fn test_reliable_helper_forms_are_complete() {
+    assert_eq!(
+        RELIABLE_HELPER_FORMS[0],
+        format!("-module({}).", RELIABLE_HELPER_MODULE)
+    );
+    for form in RELIABLE_HELPER_FORMS {
+        assert!(
+            form.trim_end().ends_with('.'),
+            "form is not terminated: {form}"
+        );
+    }
+}
+
+#[test]
+fn test_delivery_not_acknowledged_error() {
+    let err = Error::DeliveryNotAcknowledged(3);
+    assert_eq!(
+        err.to_string(),
+        "Delivery not acknowledged after 3 attempts"
+    );
+}
+
+#[tokio::test]
+async fn test_reliable_sender_requires_connection() {
+    let mut node = Node::new(test_node_name("reliable1"), "secret");
+    node.start(0).await.unwrap();
+
+    let result = ReliableSender::start(&node, "not_connected@localhost").await;
+    assert!(matches!(result, Err(Error::NodeNotConnected(_))));
+}
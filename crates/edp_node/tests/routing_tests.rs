@@ -0,0 +1,242 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::ctrl;
+use edp_client::test_peer::TestPeer;
+use edp_node::{Destination, NamePattern, Node, RoutedMessage, Router, RouterStats};
+use erltf::OwnedTerm;
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+const COOKIE: &str = "routing_cookie";
+const PEER: &str = "routing_peer@127.0.0.1";
+const PASS_THROUGH: u8 = 112;
+
+fn to_name(name: &str) -> RoutedMessage {
+    RoutedMessage {
+        from: None,
+        to: Destination::Name(Atom::new(name)),
+        body: OwnedTerm::atom(name),
+    }
+}
+
+type Recorded = Arc<Mutex<Vec<(String, &'static str)>>>;
+
+/// A router whose handlers record the destination and the pattern that matched.
+fn recording_router() -> (Router, Recorded) {
+    let router = Router::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    for (pattern, label) in [
+        (NamePattern::exact("jobs_admin"), "exact"),
+        (NamePattern::prefix("jobs_"), "jobs_"),
+        (NamePattern::prefix("jobs_high_"), "jobs_high_"),
+        (NamePattern::Fallback, "fallback"),
+    ] {
+        let seen = seen.clone();
+        router.register(pattern, move |message: RoutedMessage| {
+            seen.lock().unwrap().push((message.to.to_string(), label));
+        });
+    }
+    (router, seen)
+}
+
+#[test]
+fn test_the_most_specific_pattern_wins() {
+    let (router, seen) = recording_router();
+    for name in ["jobs_admin", "jobs_high_1", "jobs_low_1", "metrics"] {
+        router.dispatch(to_name(name));
+    }
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            ("jobs_admin".to_string(), "exact"),
+            ("jobs_high_1".to_string(), "jobs_high_"),
+            ("jobs_low_1".to_string(), "jobs_"),
+            ("metrics".to_string(), "fallback"),
+        ]
+    );
+    assert_eq!(
+        router.stats(),
+        RouterStats {
+            exact: 1,
+            prefix: 2,
+            fallback: 1,
+            dead_letters: 0,
+        }
+    );
+}
+
+#[test]
+fn test_unregistered_patterns_stop_matching() {
+    let (router, seen) = recording_router();
+    assert!(router.unregister(&NamePattern::prefix("jobs_high_")));
+    assert!(router.unregister(&NamePattern::Fallback));
+    assert!(!router.unregister(&NamePattern::Fallback));
+    assert!(!router.unregister(&NamePattern::exact("unknown")));
+
+    router.dispatch(to_name("jobs_high_1"));
+    router.dispatch(to_name("metrics"));
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![("jobs_high_1".to_string(), "jobs_")]
+    );
+    assert_eq!(router.stats().dead_letters, 1);
+}
+
+#[test]
+fn test_registering_a_pattern_again_replaces_its_handler() {
+    let (router, seen) = recording_router();
+    let replaced = Arc::new(Mutex::new(0));
+    let counter = replaced.clone();
+    router.register(NamePattern::prefix("jobs_"), move |_| {
+        *counter.lock().unwrap() += 1;
+    });
+
+    router.dispatch(to_name("jobs_low_1"));
+    assert!(seen.lock().unwrap().is_empty());
+    assert_eq!(*replaced.lock().unwrap(), 1);
+}
+
+#[test]
+fn test_dead_letters_go_to_the_dead_letter_handler() {
+    let router = Router::new();
+    router.register(NamePattern::exact("known"), |_| {});
+    let dead = Arc::new(Mutex::new(Vec::new()));
+    let recorded = dead.clone();
+    router.set_dead_letter_handler(move |message| recorded.lock().unwrap().push(message));
+
+    let pid = ExternalPid::new(Atom::new("rust@localhost"), 7, 0, 1);
+    let to_pid = RoutedMessage {
+        from: None,
+        to: Destination::Pid(pid.clone()),
+        body: OwnedTerm::integer(1),
+    };
+    router.dispatch(to_pid.clone());
+    router.dispatch(to_name("unknown"));
+    router.dispatch(to_name("known"));
+
+    assert_eq!(*dead.lock().unwrap(), vec![to_pid, to_name("unknown")]);
+    assert_eq!(router.stats().dead_letters, 2);
+    assert_eq!(router.stats().exact, 1);
+
+    router.clear_dead_letter_handler();
+    router.dispatch(to_name("unknown"));
+    assert_eq!(dead.lock().unwrap().len(), 2);
+    assert_eq!(router.stats().dead_letters, 3);
+}
+
+#[test]
+fn test_name_pattern_matching() {
+    let name = Atom::new("jobs_high_1");
+    assert!(NamePattern::exact("jobs_high_1").matches(&name));
+    assert!(!NamePattern::exact("jobs_high").matches(&name));
+    assert!(NamePattern::prefix("jobs_").matches(&name));
+    assert!(!NamePattern::prefix("metrics_").matches(&name));
+    assert!(NamePattern::Fallback.matches(&name));
+}
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+fn frame(control: OwnedTerm, message: &OwnedTerm) -> Vec<u8> {
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&control).unwrap());
+    body.extend(encode(message).unwrap());
+    let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+    frame.extend(body);
+    frame
+}
+
+fn reg_send_frame(from: ExternalPid, name: &str, message: &OwnedTerm) -> Vec<u8> {
+    frame(
+        ctrl!(RegSend from OwnedTerm::Pid(from), to name).to_term(),
+        message,
+    )
+}
+
+fn send_frame(to: ExternalPid, message: &OwnedTerm) -> Vec<u8> {
+    frame(ctrl!(Send to OwnedTerm::Pid(to)).to_term(), message)
+}
+
+#[tokio::test]
+async fn test_received_messages_are_routed_by_name_and_dead_lettered() {
+    let name = test_node_name("routing");
+    let node = Node::new(&name, COOKIE);
+    let (routed, mut routed_rx) = mpsc::unbounded_channel();
+    node.router()
+        .register(NamePattern::prefix("worker_"), move |message| {
+            let _ = routed.send(message);
+        });
+    let (dead, mut dead_rx) = mpsc::unbounded_channel();
+    node.router().set_dead_letter_handler(move |message| {
+        let _ = dead.send(message);
+    });
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let from = ExternalPid::new(Atom::new(PEER), 5, 0, 1);
+    let unknown = ExternalPid::new(Atom::new(&name), 99, 0, 1);
+    let (from_clone, unknown_clone) = (from.clone(), unknown.clone());
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        for frame in [
+            reg_send_frame(from_clone.clone(), "worker_1", &OwnedTerm::atom("job")),
+            reg_send_frame(from_clone, "nobody", &OwnedTerm::atom("lost")),
+            send_frame(unknown_clone, &OwnedTerm::atom("stray")),
+        ] {
+            stream.write_all(&frame).await.unwrap();
+        }
+        stream
+    });
+    node.connect_to_port(PEER, port).await.unwrap();
+    let _stream = peer.await.unwrap();
+
+    let message = timeout(Duration::from_secs(5), routed_rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(message.from, Some(from.clone()));
+    assert_eq!(message.to, Destination::Name(Atom::new("worker_1")));
+    assert_eq!(message.body, OwnedTerm::atom("job"));
+
+    let lost = timeout(Duration::from_secs(5), dead_rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(lost.to, Destination::Name(Atom::new("nobody")));
+    assert_eq!(lost.from, Some(from));
+    let stray = timeout(Duration::from_secs(5), dead_rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stray.to, Destination::Pid(unknown));
+    assert_eq!(stray.body, OwnedTerm::atom("stray"));
+
+    let stats = node.router().stats();
+    assert_eq!(stats.prefix, 1);
+    assert_eq!(stats.dead_letters, 2);
+}
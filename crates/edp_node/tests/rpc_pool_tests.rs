@@ -0,0 +1,59 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_node::{DEFAULT_RPC_POOL_SIZE, DEFAULT_RPC_TIMEOUT, RpcPool, RpcPoolConfig};
+use std::time::Duration;
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+#[test]
+fn test_rpc_pool_config_defaults_and_builder() {
+    let config = RpcPoolConfig::new("rpc@localhost", "erlang@localhost", "secret");
+    assert_eq!(config.size, DEFAULT_RPC_POOL_SIZE);
+    assert_eq!(config.timeout, DEFAULT_RPC_TIMEOUT);
+    assert!(config.hidden);
+
+    let config = config
+        .with_size(0)
+        .with_timeout(Duration::from_secs(1))
+        .with_hidden(false);
+    assert_eq!(config.size, 1);
+    assert_eq!(config.timeout, Duration::from_secs(1));
+    assert!(!config.hidden);
+}
+
+#[test]
+fn test_rpc_pool_member_node_names() {
+    let config = RpcPoolConfig::new("rpc@localhost", "erlang@localhost", "secret");
+    assert_eq!(config.member_node_name(0), "rpc_0@localhost");
+    assert_eq!(config.member_node_name(3), "rpc_3@localhost");
+
+    let config = RpcPoolConfig::new("rpc", "erlang@localhost", "secret");
+    assert_eq!(config.member_node_name(1), "rpc_1");
+}
+
+#[tokio::test]
+async fn test_rpc_pool_fails_when_no_member_connects() {
+    let config = RpcPoolConfig::new(
+        test_node_name("rpc_pool1"),
+        "nonexistent@localhost",
+        "secret",
+    )
+    .with_size(2);
+
+    let result = RpcPool::start(config).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,105 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use edp_node::{Error, OwnedTerm, RpcError, erl_atom, erl_int, erl_list, erl_tuple, rpc_result};
+use std::time::Duration;
+
+fn stacktrace() -> OwnedTerm {
+    erl_list![erl_tuple![
+        erl_atom!("lists"),
+        erl_atom!("nth"),
+        erl_int!(2),
+        erl_list![erl_tuple![
+            erl_atom!("file"),
+            OwnedTerm::charlist("lists.erl")
+        ]],
+    ]]
+}
+
+fn badrpc_exit(reason: OwnedTerm) -> OwnedTerm {
+    erl_tuple![erl_atom!("badrpc"), erl_tuple![erl_atom!("EXIT"), reason]]
+}
+
+#[test]
+fn test_rpc_result_passes_regular_replies_through() {
+    let reply = erl_tuple![erl_atom!("ok"), erl_int!(42)];
+    assert_eq!(rpc_result(Ok(reply.clone())).unwrap(), reply);
+}
+
+#[test]
+fn test_rpc_result_classifies_badrpc_reasons() {
+    let term = erl_tuple![erl_atom!("badrpc"), erl_atom!("nodedown")];
+    let err = rpc_result(Ok(term.clone())).unwrap_err();
+
+    assert!(
+        matches!(&err, RpcError::BadRpc { reason, .. } if reason.is_atom_with_name("nodedown"))
+    );
+    assert_eq!(err.term(), Some(&term));
+    assert!(!err.is_exception());
+}
+
+#[test]
+fn test_rpc_result_classifies_errors_with_a_stacktrace() {
+    let term = badrpc_exit(erl_tuple![erl_atom!("badarg"), stacktrace()]);
+    let err = rpc_result(Ok(term.clone())).unwrap_err();
+
+    let RpcError::Error {
+        reason,
+        stacktrace: stack,
+        ..
+    } = &err
+    else {
+        panic!("expected an error, got {err:?}");
+    };
+    assert!(reason.is_atom_with_name("badarg"));
+    assert_eq!(**stack, stacktrace());
+    assert_eq!(err.term(), Some(&term));
+    assert!(err.is_exception());
+}
+
+#[test]
+fn test_rpc_result_classifies_exits() {
+    let reason = erl_tuple![erl_atom!("shutdown"), erl_atom!("app_stopped")];
+    let err = rpc_result(Ok(badrpc_exit(reason.clone()))).unwrap_err();
+
+    assert!(matches!(&err, RpcError::Exit { reason: r, .. } if **r == reason));
+}
+
+#[test]
+fn test_rpc_result_classifies_uncaught_throws() {
+    let nocatch = erl_tuple![erl_atom!("nocatch"), erl_atom!("not_found")];
+    let err = rpc_result(Ok(badrpc_exit(erl_tuple![nocatch, stacktrace()]))).unwrap_err();
+
+    assert!(matches!(&err, RpcError::Throw { value, .. } if value.is_atom_with_name("not_found")));
+}
+
+#[test]
+fn test_rpc_result_unwraps_erpc_exceptions() {
+    let exception = erl_tuple![erl_atom!("exception"), erl_atom!("badarith"), stacktrace()];
+    let term = badrpc_exit(erl_tuple![exception, stacktrace()]);
+    let err = rpc_result(Ok(term)).unwrap_err();
+    assert!(matches!(&err, RpcError::Error { reason, .. } if reason.is_atom_with_name("badarith")));
+
+    let exception = erl_tuple![erl_atom!("exception"), erl_atom!("killed")];
+    let err = rpc_result(Ok(badrpc_exit(exception))).unwrap_err();
+    assert!(matches!(&err, RpcError::Exit { reason, .. } if reason.is_atom_with_name("killed")));
+}
+
+#[test]
+fn test_rpc_result_keeps_transport_errors() {
+    let err = rpc_result(Err(Error::RpcTimeout(Duration::from_secs(1)))).unwrap_err();
+
+    assert!(err.is_transport());
+    assert!(err.term().is_none());
+    assert!(matches!(err, RpcError::Transport(Error::RpcTimeout(_))));
+}
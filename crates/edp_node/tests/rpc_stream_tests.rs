@@ -0,0 +1,58 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_node::rpc_stream::{STREAM_SHIM_FORMS, STREAM_SHIM_MODULE};
+use edp_node::{Error, Node};
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+#[test]
+fn test_stream_shim_forms_are_complete() {
+    assert_eq!(
+        STREAM_SHIM_FORMS[0],
+        format!("-module({}).", STREAM_SHIM_MODULE)
+    );
+    for form in STREAM_SHIM_FORMS {
+        assert!(
+            form.trim_end().ends_with('.'),
+            "form is not terminated: {form}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_rpc_call_streamed_requires_connection() {
+    let mut node = Node::new(test_node_name("rpc_stream1"), "secret");
+    node.start(0).await.unwrap();
+
+    let result = node
+        .rpc_call_streamed("not_connected@localhost", "erlang", "processes", vec![])
+        .await;
+
+    assert!(matches!(result, Err(Error::NodeNotConnected(_))));
+}
+
+#[tokio::test]
+async fn test_ensure_stream_shim_requires_connection() {
+    let mut node = Node::new(test_node_name("rpc_stream2"), "secret");
+    node.start(0).await.unwrap();
+
+    let result = node
+        .ensure_stream_shim_loaded("not_connected@localhost")
+        .await;
+
+    assert!(matches!(result, Err(Error::NodeNotConnected(_))));
+}
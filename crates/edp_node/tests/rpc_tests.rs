@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use edp_node::Node;
+use edp_node::{Error, Node, RpcError, erl_atom, erl_int, erl_list, erl_tuple, is_erpc_timeout};
+use std::time::{Duration, Instant};
 
 fn test_node_name(base: &str) -> String {
     format!("{}_{}@localhost", base, std::process::id())
@@ -85,6 +86,21 @@ async fn test_rpc_requires_connection() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_rpc_call_result_reports_transport_errors() {
+    let mut node = Node::new(test_node_name("rpc_test_result"), "secret");
+    node.start(0).await.unwrap();
+
+    let result = node
+        .rpc_call_result("not_connected@localhost", "erlang", "node", vec![])
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(RpcError::Transport(Error::NodeNotConnected(_)))
+    ));
+}
+
 //
 // Node Metadata Tests
 //
@@ -106,3 +122,89 @@ async fn test_rpc_creation_increments() {
     let initial_creation = node.creation();
     assert!(initial_creation > 0);
 }
+
+//
+// Deadline Tests
+//
+
+#[tokio::test]
+async fn test_rpc_with_expired_deadline_times_out() {
+    let mut node = Node::new(test_node_name("rpc_test6"), "secret");
+    node.start(0).await.unwrap();
+
+    let deadline = Instant::now() - Duration::from_millis(1);
+    let result = node
+        .rpc_call_with_deadline(
+            "not_connected@localhost",
+            "erlang",
+            "node",
+            vec![],
+            deadline,
+        )
+        .await;
+
+    let err = result.unwrap_err();
+    assert!(matches!(err, Error::RpcTimeout(_)));
+    assert!(err.is_timeout());
+}
+
+#[tokio::test]
+async fn test_rpc_with_deadline_requires_connection() {
+    let mut node = Node::new(test_node_name("rpc_test7"), "secret");
+    node.start(0).await.unwrap();
+
+    let result = node
+        .rpc_call_with_remote_timeout(
+            "not_connected@localhost",
+            "erlang",
+            "node",
+            vec![],
+            Duration::from_secs(5),
+        )
+        .await;
+
+    assert!(matches!(result, Err(Error::NodeNotConnected(_))));
+}
+
+#[test]
+fn test_is_erpc_timeout_detects_bare_error() {
+    assert!(is_erpc_timeout(&erl_tuple!(
+        erl_atom!("erpc"),
+        erl_atom!("timeout")
+    )));
+    assert!(!is_erpc_timeout(&erl_tuple!(
+        erl_atom!("erpc"),
+        erl_atom!("noconnection")
+    )));
+}
+
+#[test]
+fn test_is_erpc_timeout_detects_badrpc_exit() {
+    let reason = erl_tuple!(
+        erl_tuple!(erl_atom!("erpc"), erl_atom!("timeout")),
+        erl_list![]
+    );
+    let result = erl_tuple!(erl_atom!("badrpc"), erl_tuple!(erl_atom!("EXIT"), reason));
+    assert!(is_erpc_timeout(&result));
+    assert!(is_erpc_timeout(&erl_tuple!(
+        erl_atom!("badrpc"),
+        erl_atom!("timeout")
+    )));
+}
+
+#[test]
+fn test_is_erpc_timeout_ignores_regular_results() {
+    assert!(!is_erpc_timeout(&erl_atom!("ok")));
+    assert!(!is_erpc_timeout(&erl_tuple!(erl_atom!("ok"), erl_int!(1))));
+    assert!(!is_erpc_timeout(&erl_tuple!(
+        erl_atom!("badrpc"),
+        erl_atom!("nodedown")
+    )));
+}
+
+#[test]
+fn test_error_is_timeout() {
+    assert!(Error::RpcTimeout(Duration::from_secs(1)).is_timeout());
+    assert!(Error::CallTimeout(Duration::from_secs(1)).is_timeout());
+    assert!(!Error::RpcCancelled.is_timeout());
+}
@@ -0,0 +1,115 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_client::test_peer::TestPeer;
+use edp_node::{Message, Node, Process, Result};
+use erltf::OwnedTerm;
+use erltf::encoder::encode;
+use erltf::types::{Atom, ExternalPid};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+const COOKIE: &str = "send_sender_cookie";
+const PEER: &str = "send_sender_peer@localhost";
+const PASS_THROUGH: u8 = 112;
+const SEND: i64 = 2;
+const SEND_SENDER: i64 = 22;
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+/// Reports the sender and body of every regular message to the test.
+struct Recorder {
+    received: mpsc::UnboundedSender<(Option<ExternalPid>, OwnedTerm)>,
+}
+
+impl Process for Recorder {
+    async fn handle_message(&mut self, msg: Message) -> Result<()> {
+        if let Message::Regular { from, body } = msg {
+            let _ = self.received.send((from, body));
+        }
+        Ok(())
+    }
+}
+
+async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+    TestPeer::new(PEER, COOKIE).accept(listener).await
+}
+
+async fn write_control(
+    stream: &mut TcpStream,
+    control: Vec<OwnedTerm>,
+    payload: Option<OwnedTerm>,
+) {
+    let mut body = vec![PASS_THROUGH];
+    body.extend(encode(&OwnedTerm::tuple(control)).unwrap());
+    if let Some(payload) = payload {
+        body.extend(encode(&payload).unwrap());
+    }
+    stream
+        .write_all(&(body.len() as u32).to_be_bytes())
+        .await
+        .unwrap();
+    stream.write_all(&body).await.unwrap();
+}
+
+fn remote_pid() -> ExternalPid {
+    ExternalPid::new(Atom::new(PEER), 77, 0, 1)
+}
+
+async fn deliver(control: Vec<OwnedTerm>) -> (Option<ExternalPid>, OwnedTerm) {
+    let mut node = Node::new(test_node_name("send_sender"), COOKIE);
+    node.start(0).await.unwrap();
+    let (received, mut received_rx) = mpsc::unbounded_channel();
+    let local = node.spawn(Recorder { received }).await.unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let mut control = control;
+    control.push(OwnedTerm::Pid(local));
+    let peer = tokio::spawn(async move {
+        let mut stream = accept_handshake(&listener).await;
+        write_control(&mut stream, control, Some(OwnedTerm::atom("hello"))).await;
+        stream
+    });
+    node.connect_to_port(PEER, port).await.unwrap();
+    let _stream = peer.await.unwrap();
+
+    timeout(Duration::from_secs(5), received_rx.recv())
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_send_sender_is_delivered_with_its_sender() {
+    let (from, body) = deliver(vec![
+        OwnedTerm::integer(SEND_SENDER),
+        OwnedTerm::Pid(remote_pid()),
+    ])
+    .await;
+    assert_eq!(from, Some(remote_pid()));
+    assert_eq!(body, OwnedTerm::atom("hello"));
+}
+
+#[tokio::test]
+async fn test_send_is_delivered_without_a_sender() {
+    let (from, body) = deliver(vec![OwnedTerm::integer(SEND), OwnedTerm::atom("")]).await;
+    assert_eq!(from, None);
+    assert_eq!(body, OwnedTerm::atom("hello"));
+}
@@ -0,0 +1,169 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_node::{Error, Message, Node, Process, Result, parse_timeout_message, timeout_message};
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid, ExternalReference};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+struct CollectorProcess {
+    messages: Arc<Mutex<Vec<OwnedTerm>>>,
+}
+
+impl Process for CollectorProcess {
+    async fn handle_message(&mut self, msg: Message) -> Result<()> {
+        if let Message::Regular { body, .. } = msg {
+            self.messages.lock().await.push(body);
+        }
+        Ok(())
+    }
+}
+
+async fn start_node_with_collector(base: &str) -> (Node, ExternalPid, Arc<Mutex<Vec<OwnedTerm>>>) {
+    let mut node = Node::new(test_node_name(base), "secret");
+    node.start(0).await.unwrap();
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let pid = node
+        .spawn(CollectorProcess {
+            messages: messages.clone(),
+        })
+        .await
+        .unwrap();
+    (node, pid, messages)
+}
+
+#[tokio::test]
+async fn test_send_after_delivers_message() {
+    let (node, pid, messages) = start_node_with_collector("timers_send_after").await;
+
+    let handle = node
+        .send_after(
+            Duration::from_millis(30),
+            &pid,
+            OwnedTerm::Atom(Atom::new("tick")),
+        )
+        .unwrap();
+    assert!(handle.is_active());
+    assert!(messages.lock().await.is_empty());
+
+    sleep(Duration::from_millis(150)).await;
+    assert_eq!(
+        *messages.lock().await,
+        vec![OwnedTerm::Atom(Atom::new("tick"))]
+    );
+    assert!(!handle.is_active());
+    assert_eq!(handle.cancel(), None);
+    assert_eq!(node.timers().active_count(), 0);
+}
+
+#[tokio::test]
+async fn test_start_timer_delivers_timeout_tuple() {
+    let (node, pid, messages) = start_node_with_collector("timers_start_timer").await;
+
+    let handle = node
+        .start_timer(Duration::from_millis(20), &pid, OwnedTerm::Integer(7))
+        .unwrap();
+    sleep(Duration::from_millis(150)).await;
+
+    let received = messages.lock().await;
+    assert_eq!(received.len(), 1);
+    assert_eq!(
+        received[0],
+        timeout_message(handle.reference(), OwnedTerm::Integer(7))
+    );
+    let (reference, msg) = parse_timeout_message(&received[0]).unwrap();
+    assert_eq!(reference, handle.reference());
+    assert_eq!(msg, &OwnedTerm::Integer(7));
+}
+
+#[tokio::test]
+async fn test_cancel_timer_prevents_delivery() {
+    let (node, pid, messages) = start_node_with_collector("timers_cancel").await;
+
+    let handle = node
+        .send_after(Duration::from_secs(5), &pid, OwnedTerm::Integer(1))
+        .unwrap();
+    let left = node.read_timer(handle.reference()).unwrap();
+    assert!(left > Duration::from_secs(4) && left <= Duration::from_secs(5));
+
+    let left = node.cancel_timer(handle.reference()).unwrap();
+    assert!(left > Duration::from_secs(4));
+    assert_eq!(node.cancel_timer(handle.reference()), None);
+    assert_eq!(handle.remaining(), None);
+
+    let short = node
+        .send_after(Duration::from_millis(20), &pid, OwnedTerm::Integer(2))
+        .unwrap();
+    short.cancel().unwrap();
+    sleep(Duration::from_millis(100)).await;
+    assert!(messages.lock().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_timers_deliver_in_deadline_order() {
+    let (node, pid, messages) = start_node_with_collector("timers_order").await;
+    let timers = node.timers();
+
+    timers
+        .send_after(Duration::from_millis(60), &pid, OwnedTerm::Integer(3))
+        .unwrap();
+    timers
+        .send_after(Duration::from_millis(10), &pid, OwnedTerm::Integer(1))
+        .unwrap();
+    timers
+        .send_after(Duration::from_millis(35), &pid, OwnedTerm::Integer(2))
+        .unwrap();
+    assert_eq!(timers.active_count(), 3);
+
+    sleep(Duration::from_millis(200)).await;
+    assert_eq!(
+        *messages.lock().await,
+        vec![
+            OwnedTerm::Integer(1),
+            OwnedTerm::Integer(2),
+            OwnedTerm::Integer(3)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_timers_reject_remote_pids() {
+    let (node, _pid, _messages) = start_node_with_collector("timers_remote").await;
+    let remote = ExternalPid::new(Atom::new("other@localhost"), 1, 0, 1);
+
+    let result = node.send_after(Duration::from_millis(10), &remote, OwnedTerm::Nil);
+    assert!(matches!(result, Err(Error::ProcessNotFound(pid)) if pid == remote));
+}
+
+#[test]
+fn test_parse_timeout_message_rejects_other_terms() {
+    let reference = ExternalReference::new(Atom::new("a@localhost"), 1, vec![1, 2, 3]);
+    assert!(parse_timeout_message(&OwnedTerm::Integer(1)).is_none());
+    assert!(
+        parse_timeout_message(&OwnedTerm::Tuple(vec![
+            OwnedTerm::Atom(Atom::new("other")),
+            OwnedTerm::Reference(reference.clone()),
+            OwnedTerm::Nil,
+        ]))
+        .is_none()
+    );
+    assert!(parse_timeout_message(&timeout_message(&reference, OwnedTerm::Nil)).is_some());
+}
@@ -0,0 +1,246 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use edp_node::tracer::{TRACE_RELAY_FORMS, TRACE_RELAY_MODULE};
+use edp_node::{
+    Atom, Error, ExternalPid, GcPhase, Mfa, Node, OwnedTerm, TraceEvent, TraceEventKind, TraceFlag,
+    TracePattern, Tracer, erl_atom, erl_int, erl_list, erl_tuple,
+};
+
+fn test_node_name(base: &str) -> String {
+    format!("{}_{}@localhost", base, std::process::id())
+}
+
+fn traced_pid() -> OwnedTerm {
+    OwnedTerm::Pid(ExternalPid::new(Atom::new("erlang@localhost"), 100, 0, 1))
+}
+
+#[test]
+fn test_trace_relay_forms_are_complete() {
+    assert_eq!(
+        TRACE_RELAY_FORMS[0],
+        format!("-module({}).", TRACE_RELAY_MODULE)
+    );
+    for form in TRACE_RELAY_FORMS {
+        assert!(
+            form.trim_end().ends_with('.'),
+            "form is not terminated: {form}"
+        );
+    }
+}
+
+#[test]
+fn test_trace_flag_names() {
+    assert_eq!(TraceFlag::Call.as_str(), "call");
+    assert_eq!(TraceFlag::Receive.as_str(), "receive");
+    assert_eq!(TraceFlag::GarbageCollection.as_str(), "garbage_collection");
+}
+
+#[test]
+fn test_trace_pattern_builder() {
+    let pattern = TracePattern::new("lists")
+        .with_function("reverse")
+        .with_arity(1)
+        .with_local(true);
+    assert_eq!(pattern.module, Atom::new("lists"));
+    assert_eq!(pattern.function, Some(Atom::new("reverse")));
+    assert_eq!(pattern.arity, Some(1));
+    assert!(pattern.local);
+    assert_eq!(pattern.match_spec, erl_atom!("true"));
+
+    let pattern = pattern.with_return_trace();
+    let expected = erl_list![erl_tuple!(
+        erl_atom!("_"),
+        OwnedTerm::List(vec![]),
+        erl_list![erl_tuple!(erl_atom!("return_trace"))]
+    )];
+    assert_eq!(pattern.match_spec, expected);
+}
+
+#[test]
+fn test_decode_call_event() {
+    let msg = erl_tuple!(
+        erl_atom!("trace"),
+        traced_pid(),
+        erl_atom!("call"),
+        erl_tuple!(
+            erl_atom!("lists"),
+            erl_atom!("reverse"),
+            erl_list![erl_list![erl_int!(1), erl_int!(2)]]
+        )
+    );
+
+    let event = TraceEvent::from_term(&msg).unwrap();
+    assert_eq!(event.pid.id, 100);
+    assert_eq!(event.timestamp, None);
+    assert_eq!(
+        event.kind,
+        TraceEventKind::Call {
+            module: Atom::new("lists"),
+            function: Atom::new("reverse"),
+            arity: 1,
+            args: Some(vec![erl_list![erl_int!(1), erl_int!(2)]]),
+        }
+    );
+}
+
+#[test]
+fn test_decode_call_event_with_arity_flag() {
+    let msg = erl_tuple!(
+        erl_atom!("trace"),
+        traced_pid(),
+        erl_atom!("call"),
+        erl_tuple!(erl_atom!("lists"), erl_atom!("reverse"), erl_int!(2))
+    );
+
+    let event = TraceEvent::from_term(&msg).unwrap();
+    assert_eq!(
+        event.kind,
+        TraceEventKind::Call {
+            module: Atom::new("lists"),
+            function: Atom::new("reverse"),
+            arity: 2,
+            args: None,
+        }
+    );
+}
+
+#[test]
+fn test_decode_return_and_exception_events() {
+    let mfa = erl_tuple!(erl_atom!("m"), erl_atom!("f"), erl_int!(0));
+    let msg = erl_tuple!(
+        erl_atom!("trace"),
+        traced_pid(),
+        erl_atom!("return_from"),
+        mfa.clone(),
+        erl_atom!("ok")
+    );
+    assert_eq!(
+        TraceEvent::from_term(&msg).unwrap().kind,
+        TraceEventKind::ReturnFrom {
+            mfa: Mfa::new("m", "f", 0),
+            value: erl_atom!("ok"),
+        }
+    );
+
+    let msg = erl_tuple!(
+        erl_atom!("trace"),
+        traced_pid(),
+        erl_atom!("exception_from"),
+        mfa,
+        erl_tuple!(erl_atom!("error"), erl_atom!("badarg"))
+    );
+    assert_eq!(
+        TraceEvent::from_term(&msg).unwrap().kind,
+        TraceEventKind::ExceptionFrom {
+            mfa: Mfa::new("m", "f", 0),
+            class: erl_atom!("error"),
+            reason: erl_atom!("badarg"),
+        }
+    );
+}
+
+#[test]
+fn test_decode_message_events_with_timestamp() {
+    let ts = erl_tuple!(erl_int!(1700), erl_int!(1), erl_int!(2));
+    let msg = erl_tuple!(
+        erl_atom!("trace_ts"),
+        traced_pid(),
+        erl_atom!("send"),
+        erl_atom!("hello"),
+        traced_pid(),
+        ts.clone()
+    );
+    let event = TraceEvent::from_term(&msg).unwrap();
+    assert_eq!(event.timestamp, Some(ts.clone()));
+    assert_eq!(
+        event.kind,
+        TraceEventKind::Send {
+            message: erl_atom!("hello"),
+            to: traced_pid(),
+        }
+    );
+
+    let msg = erl_tuple!(
+        erl_atom!("trace_ts"),
+        traced_pid(),
+        erl_atom!("receive"),
+        erl_atom!("hello"),
+        ts
+    );
+    assert_eq!(
+        TraceEvent::from_term(&msg).unwrap().kind,
+        TraceEventKind::Receive {
+            message: erl_atom!("hello"),
+        }
+    );
+}
+
+#[test]
+fn test_decode_gc_and_other_events() {
+    let info = erl_list![erl_tuple!(erl_atom!("heap_size"), erl_int!(233))];
+    let msg = erl_tuple!(
+        erl_atom!("trace"),
+        traced_pid(),
+        erl_atom!("gc_major_end"),
+        info.clone()
+    );
+    assert_eq!(
+        TraceEvent::from_term(&msg).unwrap().kind,
+        TraceEventKind::GarbageCollection {
+            phase: GcPhase::MajorEnd,
+            info,
+        }
+    );
+
+    let msg = erl_tuple!(
+        erl_atom!("trace"),
+        traced_pid(),
+        erl_atom!("exit"),
+        erl_atom!("normal")
+    );
+    assert_eq!(
+        TraceEvent::from_term(&msg).unwrap().kind,
+        TraceEventKind::Other {
+            tag: Atom::new("exit"),
+            data: vec![erl_atom!("normal")],
+        }
+    );
+}
+
+#[test]
+fn test_decode_rejects_non_trace_messages() {
+    assert_eq!(TraceEvent::from_term(&erl_atom!("trace")), None);
+    assert_eq!(
+        TraceEvent::from_term(&erl_tuple!(erl_atom!("hello"), traced_pid())),
+        None
+    );
+    assert_eq!(
+        TraceEvent::from_term(&erl_tuple!(
+            erl_atom!("trace"),
+            erl_atom!("not_a_pid"),
+            erl_atom!("call")
+        )),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_tracer_requires_connection() {
+    let mut node = Node::new(test_node_name("tracer1"), "secret");
+    node.start(0).await.unwrap();
+
+    let result = Tracer::start(&node, "not_connected@localhost").await;
+    assert!(matches!(result, Err(Error::NodeNotConnected(_))));
+}
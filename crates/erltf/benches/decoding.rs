@@ -15,7 +15,6 @@
 use criterion::BenchmarkId;
 use criterion::Criterion;
 use criterion::Throughput;
-use criterion::black_box;
 use criterion::criterion_group;
 use criterion::criterion_main;
 use erltf::OwnedTerm;
@@ -23,6 +22,7 @@ use erltf::decode;
 use erltf::encode;
 use erltf::erl_tuple;
 use std::collections::BTreeMap;
+use std::hint::black_box;
 use std::time::Duration;
 
 fn create_large_nested_structure() -> OwnedTerm {
@@ -294,6 +294,63 @@ impl<'a> BorrowedTerm<'a> {
         }
     }
 
+    pub fn estimated_encoded_size(&self) -> usize {
+        match self {
+            BorrowedTerm::Atom(a) => 3 + a.len(),
+            BorrowedTerm::Integer(i) => {
+                if (0..=255).contains(i) {
+                    2
+                } else if *i >= i32::MIN as i64 && *i <= i32::MAX as i64 {
+                    5
+                } else {
+                    let abs = i.unsigned_abs();
+                    let bytes = (64u32 - abs.leading_zeros()).div_ceil(8);
+                    3 + bytes as usize
+                }
+            }
+            BorrowedTerm::Float(_) => 9,
+            BorrowedTerm::Binary(b) => 5 + b.len(),
+            BorrowedTerm::BitBinary { bytes, .. } => 6 + bytes.len(),
+            BorrowedTerm::String(s) => 5 + s.len(),
+            BorrowedTerm::List(l) => {
+                5 + 1 + l.iter().map(|t| t.estimated_encoded_size()).sum::<usize>()
+            }
+            BorrowedTerm::ImproperList { elements, tail } => {
+                5 + elements
+                    .iter()
+                    .map(|t| t.estimated_encoded_size())
+                    .sum::<usize>()
+                    + tail.estimated_encoded_size()
+            }
+            BorrowedTerm::Tuple(t) => {
+                let base = if t.len() <= 255 { 2 } else { 5 };
+                base + t.iter().map(|t| t.estimated_encoded_size()).sum::<usize>()
+            }
+            BorrowedTerm::Map(m) => {
+                5 + m
+                    .iter()
+                    .map(|(k, v)| k.estimated_encoded_size() + v.estimated_encoded_size())
+                    .sum::<usize>()
+            }
+            BorrowedTerm::Pid(_) => 17,
+            BorrowedTerm::Port(_) => 16,
+            BorrowedTerm::Reference(r) => 7 + r.ids.len() * 4,
+            BorrowedTerm::BigInt(b) => {
+                let base = if b.digits.len() <= 255 { 2 } else { 5 };
+                base + 1 + b.digits.len()
+            }
+            BorrowedTerm::ExternalFun(_) => 32,
+            BorrowedTerm::InternalFun(f) => {
+                64 + f
+                    .free_vars
+                    .iter()
+                    .map(|t| t.estimated_encoded_size())
+                    .sum::<usize>()
+            }
+            BorrowedTerm::Nil => 1,
+        }
+    }
+
     pub fn map_get(&self, key: &BorrowedTerm<'a>) -> Option<&BorrowedTerm<'a>> {
         match self {
             BorrowedTerm::Map(m) => m.get(key),
@@ -363,6 +420,8 @@ impl<'a> From<&'a OwnedTerm> for BorrowedTerm<'a> {
             OwnedTerm::Port(p) => BorrowedTerm::Port(p.clone()),
             OwnedTerm::Reference(r) => BorrowedTerm::Reference(r.clone()),
             OwnedTerm::Binary(b) => BorrowedTerm::Binary(Cow::Borrowed(b.as_slice())),
+            OwnedTerm::SharedBinary(b) => BorrowedTerm::Binary(Cow::Borrowed(b.as_ref())),
+            OwnedTerm::Shared(shared) => BorrowedTerm::from(shared.as_ref()),
             OwnedTerm::BitBinary { bytes, bits } => BorrowedTerm::BitBinary {
                 bytes: Cow::Borrowed(bytes.as_slice()),
                 bits: *bits,
@@ -371,6 +430,12 @@ impl<'a> From<&'a OwnedTerm> for BorrowedTerm<'a> {
             OwnedTerm::List(elements) => {
                 BorrowedTerm::List(elements.iter().map(BorrowedTerm::from).collect())
             }
+            OwnedTerm::ByteList(bytes) => BorrowedTerm::List(
+                bytes
+                    .iter()
+                    .map(|&b| BorrowedTerm::Integer(i64::from(b)))
+                    .collect(),
+            ),
             OwnedTerm::ImproperList { elements, tail } => BorrowedTerm::ImproperList {
                 elements: elements.iter().map(BorrowedTerm::from).collect(),
                 tail: Box::new(BorrowedTerm::from(tail.as_ref())),
@@ -380,6 +445,12 @@ impl<'a> From<&'a OwnedTerm> for BorrowedTerm<'a> {
                     .map(|(k, v)| (BorrowedTerm::from(k), BorrowedTerm::from(v)))
                     .collect(),
             ),
+            OwnedTerm::OrderedMap(entries) => BorrowedTerm::Map(
+                entries
+                    .iter()
+                    .map(|(k, v)| (BorrowedTerm::from(k), BorrowedTerm::from(v)))
+                    .collect(),
+            ),
             OwnedTerm::Tuple(elements) => {
                 BorrowedTerm::Tuple(elements.iter().map(BorrowedTerm::from).collect())
             }
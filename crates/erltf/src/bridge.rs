@@ -0,0 +1,179 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shared options for converting terms to and from MessagePack and CBOR.
+//!
+//! The converters live in the `msgpack` and `cbor` modules, behind the features
+//! of the same name. Erlang-only types (pids, ports, references, funs and so on)
+//! are carried as extension values (MessagePack) or tagged values (CBOR) whose
+//! payload is the term's external term format encoding.
+
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+use crate::decoder::decode;
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+use crate::encoder::encode;
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+use crate::errors::BridgeError;
+use crate::term::OwnedTerm;
+use crate::types::{Atom, BigInt, Sign};
+
+/// MessagePack extension type used for Erlang-only terms.
+pub const MSGPACK_EXT_TYPE: i8 = 0x45;
+
+/// CBOR tag used for Erlang-only terms. It is in the first come, first served
+/// range and spells "ETF" in ASCII.
+pub const CBOR_TAG: u64 = 0x45_54_46;
+
+/// How terms with no MessagePack or CBOR equivalent are converted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErlangTypeHandling {
+    /// Carry the encoded term in an extension or tagged value, so it round-trips
+    #[default]
+    Tagged,
+    /// Convert to the term's string representation, losing the type
+    AsString,
+    /// Fail with `BridgeError::Unsupported`
+    Reject,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BridgeConfig {
+    pub erlang_types: ErlangTypeHandling,
+    /// Convert atoms other than booleans and `nil_atom` to strings instead of tagging them
+    pub atoms_as_strings: bool,
+    /// Convert tuples to arrays instead of tagging them
+    pub tuples_as_arrays: bool,
+    /// Convert binaries that are valid UTF-8 to strings
+    pub binaries_as_strings: bool,
+    /// The atom that stands for a MessagePack or CBOR nil
+    pub nil_atom: Atom,
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        let nil_atom = if cfg!(feature = "elixir-interop") {
+            "nil"
+        } else {
+            "undefined"
+        };
+        Self {
+            erlang_types: ErlangTypeHandling::default(),
+            atoms_as_strings: true,
+            tuples_as_arrays: true,
+            binaries_as_strings: true,
+            nil_atom: Atom::new(nil_atom),
+        }
+    }
+}
+
+impl BridgeConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_erlang_types(mut self, handling: ErlangTypeHandling) -> Self {
+        self.erlang_types = handling;
+        self
+    }
+
+    pub fn with_atoms_as_strings(mut self, enabled: bool) -> Self {
+        self.atoms_as_strings = enabled;
+        self
+    }
+
+    pub fn with_tuples_as_arrays(mut self, enabled: bool) -> Self {
+        self.tuples_as_arrays = enabled;
+        self
+    }
+
+    pub fn with_binaries_as_strings(mut self, enabled: bool) -> Self {
+        self.binaries_as_strings = enabled;
+        self
+    }
+
+    pub fn with_nil_atom(mut self, atom: impl AsRef<str>) -> Self {
+        self.nil_atom = Atom::new(atom);
+        self
+    }
+}
+
+/// The representation of a term that has no MessagePack or CBOR equivalent.
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+pub(crate) enum Opaque {
+    Tagged(Vec<u8>),
+    Text(String),
+}
+
+/// Applies `handling` to a term with no equivalent.
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+pub(crate) fn opaque(
+    term: &OwnedTerm,
+    handling: ErlangTypeHandling,
+) -> Result<Opaque, BridgeError> {
+    match handling {
+        ErlangTypeHandling::Tagged => tagged(term),
+        ErlangTypeHandling::AsString => Ok(Opaque::Text(term.to_string())),
+        ErlangTypeHandling::Reject => Err(BridgeError::Unsupported(term.type_name())),
+    }
+}
+
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+pub(crate) fn tagged(term: &OwnedTerm) -> Result<Opaque, BridgeError> {
+    Ok(Opaque::Tagged(encode(term)?))
+}
+
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+pub(crate) fn from_tagged(payload: &[u8]) -> Result<OwnedTerm, BridgeError> {
+    Ok(decode(payload)?)
+}
+
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+pub(crate) fn atom_from_bool(value: bool) -> OwnedTerm {
+    OwnedTerm::Atom(Atom::new(if value { "true" } else { "false" }))
+}
+
+/// Returns the value of a big integer that fits an `i128`.
+pub(crate) fn bigint_to_i128(big: &BigInt) -> Option<i128> {
+    if big.digits.iter().skip(16).any(|&d| d != 0) {
+        return None;
+    }
+    let magnitude = big
+        .digits
+        .iter()
+        .take(16)
+        .rev()
+        .fold(0u128, |acc, &d| (acc << 8) | u128::from(d));
+    if big.sign.is_negative() {
+        0i128.checked_sub_unsigned(magnitude)
+    } else {
+        i128::try_from(magnitude).ok()
+    }
+}
+
+/// Returns an integer term, using a big integer if `value` does not fit an `i64`.
+pub(crate) fn integer_term(value: i128) -> OwnedTerm {
+    match i64::try_from(value) {
+        Ok(value) => OwnedTerm::Integer(value),
+        Err(_) => {
+            let sign = if value < 0 {
+                Sign::Negative
+            } else {
+                Sign::Positive
+            };
+            let bytes = value.unsigned_abs().to_le_bytes();
+            let len = bytes.iter().rposition(|&b| b != 0).map_or(1, |p| p + 1);
+            OwnedTerm::BigInt(BigInt::new(sign, bytes[..len].to_vec()))
+        }
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deterministic encoding and HMAC signing of terms.
+//!
+//! Two equal terms always produce the same canonical bytes, which makes the
+//! encoding suitable for hashing and signing:
+//!
+//! * the version byte is always 131 and the output is never compressed
+//! * map entries are sorted by key in Erlang term order
+//! * atoms always use the UTF-8 atom tags
+//! * integers use the smallest tag that fits, big integers that fit an `i64` are encoded as integers
+//! * strings are encoded as binaries, proper lists with no elements as `NIL_EXT`
+//! * pids, ports and references are encoded from their fields, never as `LOCAL_EXT`
+
+use crate::encoder::encode;
+use crate::errors::EncodeError;
+use crate::term::OwnedTerm;
+use crate::types::{BigInt, ExternalPid, ExternalPort, ExternalReference, InternalFun};
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Length of an HMAC-SHA256 signature in bytes.
+pub const SIGNATURE_LEN: usize = 32;
+
+/// Encodes `term` deterministically. See the module documentation for the rules.
+pub fn canonical_encode(term: &OwnedTerm) -> Result<Vec<u8>, EncodeError> {
+    encode(&canonicalize(term))
+}
+
+/// Returns an equivalent term that encodes the same way as every other equal term.
+pub fn canonicalize(term: &OwnedTerm) -> OwnedTerm {
+    let term = term.resolve();
+    match term {
+        OwnedTerm::String(s) => OwnedTerm::Binary(s.as_bytes().to_vec()),
+        OwnedTerm::BigInt(big) => canonical_bigint(big),
+        OwnedTerm::List(elements) if elements.is_empty() => OwnedTerm::Nil,
+        OwnedTerm::ByteList(bytes) if bytes.is_empty() => OwnedTerm::Nil,
+        OwnedTerm::ByteList(_) => term.clone().into_integer_list(),
+        OwnedTerm::List(elements) => OwnedTerm::List(elements.iter().map(canonicalize).collect()),
+        OwnedTerm::ImproperList { elements, tail } => {
+            let elements: Vec<OwnedTerm> = elements.iter().map(canonicalize).collect();
+            match canonicalize(tail) {
+                OwnedTerm::Nil if elements.is_empty() => OwnedTerm::Nil,
+                OwnedTerm::Nil => OwnedTerm::List(elements),
+                tail => OwnedTerm::ImproperList {
+                    elements,
+                    tail: Box::new(tail),
+                },
+            }
+        }
+        OwnedTerm::Tuple(elements) => OwnedTerm::Tuple(elements.iter().map(canonicalize).collect()),
+        OwnedTerm::Map(map) => OwnedTerm::Map(
+            map.iter()
+                .map(|(k, v)| (canonicalize(k), canonicalize(v)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+        OwnedTerm::OrderedMap(entries) => OwnedTerm::Map(
+            entries
+                .iter()
+                .map(|(k, v)| (canonicalize(k), canonicalize(v)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+        OwnedTerm::Pid(pid) => OwnedTerm::Pid(canonical_pid(pid)),
+        OwnedTerm::Port(port) => {
+            OwnedTerm::Port(ExternalPort::new(port.node.clone(), port.id, port.creation))
+        }
+        OwnedTerm::Reference(ref_) => OwnedTerm::Reference(ExternalReference::new(
+            ref_.node.clone(),
+            ref_.creation,
+            ref_.ids.clone(),
+        )),
+        OwnedTerm::InternalFun(fun) => {
+            let mut fun: Box<InternalFun> = fun.clone();
+            fun.pid = canonical_pid(&fun.pid);
+            fun.free_vars = fun.free_vars.iter().map(canonicalize).collect();
+            OwnedTerm::InternalFun(fun)
+        }
+        other => other.clone(),
+    }
+}
+
+/// Signs the canonical encoding of `term` with HMAC-SHA256.
+pub fn sign(term: &OwnedTerm, key: &[u8]) -> Result<[u8; SIGNATURE_LEN], EncodeError> {
+    let mut mac = new_mac(key);
+    mac.update(&canonical_encode(term)?);
+    Ok(mac.finalize().into_bytes().into())
+}
+
+/// Verifies an HMAC-SHA256 signature produced by [`sign`] in constant time.
+pub fn verify(term: &OwnedTerm, key: &[u8], signature: &[u8]) -> Result<bool, EncodeError> {
+    let mut mac = new_mac(key);
+    mac.update(&canonical_encode(term)?);
+    Ok(mac.verify_slice(signature).is_ok())
+}
+
+fn new_mac(key: &[u8]) -> HmacSha256 {
+    HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length")
+}
+
+fn canonical_pid(pid: &ExternalPid) -> ExternalPid {
+    ExternalPid::new(pid.node.clone(), pid.id, pid.serial, pid.creation)
+}
+
+fn canonical_bigint(big: &BigInt) -> OwnedTerm {
+    let len = big
+        .digits
+        .iter()
+        .rposition(|&d| d != 0)
+        .map_or(0, |pos| pos + 1);
+    let digits = &big.digits[..len];
+
+    if len <= 8 {
+        let magnitude = digits
+            .iter()
+            .rev()
+            .fold(0u64, |acc, &d| (acc << 8) | u64::from(d));
+        if big.sign.is_negative() {
+            if magnitude <= i64::MAX as u64 + 1 {
+                return OwnedTerm::Integer((magnitude as i64).wrapping_neg());
+            }
+        } else if magnitude <= i64::MAX as u64 {
+            return OwnedTerm::Integer(magnitude as i64);
+        }
+    }
+
+    OwnedTerm::BigInt(BigInt::new(big.sign, digits.to_vec()))
+}
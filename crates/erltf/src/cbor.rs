@@ -0,0 +1,134 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion between terms and CBOR values. Requires the `cbor` feature.
+//!
+//! See [`crate::bridge`] for the options and how Erlang-only types are carried.
+
+use crate::bridge::{
+    BridgeConfig, CBOR_TAG, Opaque, atom_from_bool, bigint_to_i128, from_tagged, integer_term,
+    opaque, tagged,
+};
+use crate::errors::BridgeError;
+use crate::term::OwnedTerm;
+use ciborium::value::{Integer, Value};
+use std::collections::BTreeMap;
+use std::str;
+
+/// Converts a term to a CBOR value.
+pub fn to_cbor(term: &OwnedTerm, config: &BridgeConfig) -> Result<Value, BridgeError> {
+    match term {
+        OwnedTerm::Shared(shared) => to_cbor(shared, config),
+        OwnedTerm::Atom(atom) => match atom.as_str() {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            _ if *atom == config.nil_atom => Ok(Value::Null),
+            name if config.atoms_as_strings => Ok(Value::Text(name.to_string())),
+            _ => from_opaque(tagged(term)?),
+        },
+        OwnedTerm::Integer(i) => Ok(Value::Integer(Integer::from(*i))),
+        OwnedTerm::BigInt(big) => {
+            match bigint_to_i128(big).and_then(|v| Integer::try_from(v).ok()) {
+                Some(value) => Ok(Value::Integer(value)),
+                None => from_opaque(opaque(term, config.erlang_types)?),
+            }
+        }
+        OwnedTerm::Float(f) => Ok(Value::Float(*f)),
+        OwnedTerm::Binary(bytes) => Ok(binary_to_value(bytes, config)),
+        OwnedTerm::SharedBinary(bytes) => Ok(binary_to_value(bytes, config)),
+        OwnedTerm::String(s) => Ok(Value::Text(s.clone())),
+        OwnedTerm::List(elements) => to_array(elements, config),
+        OwnedTerm::ByteList(bytes) => Ok(Value::Array(
+            bytes
+                .iter()
+                .map(|&b| Value::Integer(Integer::from(b)))
+                .collect(),
+        )),
+        OwnedTerm::Tuple(elements) if config.tuples_as_arrays => to_array(elements, config),
+        OwnedTerm::Tuple(_) => from_opaque(tagged(term)?),
+        OwnedTerm::Nil => Ok(Value::Array(Vec::new())),
+        OwnedTerm::Map(map) => map
+            .iter()
+            .map(|(k, v)| Ok((to_cbor(k, config)?, to_cbor(v, config)?)))
+            .collect::<Result<Vec<_>, BridgeError>>()
+            .map(Value::Map),
+        OwnedTerm::OrderedMap(entries) => entries
+            .iter()
+            .map(|(k, v)| Ok((to_cbor(k, config)?, to_cbor(v, config)?)))
+            .collect::<Result<Vec<_>, BridgeError>>()
+            .map(Value::Map),
+        OwnedTerm::BitBinary { .. }
+        | OwnedTerm::ImproperList { .. }
+        | OwnedTerm::Pid(_)
+        | OwnedTerm::Port(_)
+        | OwnedTerm::Reference(_)
+        | OwnedTerm::ExternalFun(_)
+        | OwnedTerm::InternalFun(_) => from_opaque(opaque(term, config.erlang_types)?),
+    }
+}
+
+/// Converts a CBOR value to a term. Text becomes binaries, never atoms.
+pub fn from_cbor(value: &Value, config: &BridgeConfig) -> Result<OwnedTerm, BridgeError> {
+    match value {
+        Value::Null => Ok(OwnedTerm::Atom(config.nil_atom.clone())),
+        Value::Bool(b) => Ok(atom_from_bool(*b)),
+        Value::Integer(i) => Ok(integer_term(i128::from(*i))),
+        Value::Float(f) => Ok(OwnedTerm::Float(*f)),
+        Value::Text(s) => Ok(OwnedTerm::Binary(s.as_bytes().to_vec())),
+        Value::Bytes(bytes) => Ok(OwnedTerm::Binary(bytes.clone())),
+        Value::Array(elements) if elements.is_empty() => Ok(OwnedTerm::Nil),
+        Value::Array(elements) => elements
+            .iter()
+            .map(|e| from_cbor(e, config))
+            .collect::<Result<Vec<_>, _>>()
+            .map(OwnedTerm::List),
+        Value::Map(entries) => entries
+            .iter()
+            .map(|(k, v)| Ok((from_cbor(k, config)?, from_cbor(v, config)?)))
+            .collect::<Result<BTreeMap<_, _>, BridgeError>>()
+            .map(OwnedTerm::Map),
+        Value::Tag(CBOR_TAG, inner) => match inner.as_ref() {
+            Value::Bytes(payload) => from_tagged(payload),
+            _ => Err(BridgeError::InvalidValue(
+                "tagged Erlang term must wrap a byte string".to_string(),
+            )),
+        },
+        Value::Tag(tag, _) => Err(BridgeError::InvalidValue(format!("unknown tag {tag}"))),
+        other => Err(BridgeError::InvalidValue(format!("{other:?}"))),
+    }
+}
+
+fn to_array(elements: &[OwnedTerm], config: &BridgeConfig) -> Result<Value, BridgeError> {
+    elements
+        .iter()
+        .map(|e| to_cbor(e, config))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Value::Array)
+}
+
+fn from_opaque(opaque: Opaque) -> Result<Value, BridgeError> {
+    Ok(match opaque {
+        Opaque::Tagged(payload) => Value::Tag(CBOR_TAG, Box::new(Value::Bytes(payload))),
+        Opaque::Text(text) => Value::Text(text),
+    })
+}
+
+fn binary_to_value(bytes: &[u8], config: &BridgeConfig) -> Value {
+    if config.binaries_as_strings
+        && let Ok(s) = str::from_utf8(bytes)
+    {
+        return Value::Text(s.to_string());
+    }
+    Value::Bytes(bytes.to_vec())
+}
@@ -0,0 +1,464 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Conversions between Rust types and terms that do not go through serde.
+//!
+//! [`ToTerm`] and [`FromTerm`] are implemented for integers, floats, booleans, strings,
+//! atoms, pids, references, options, vectors, tuples, maps and `OwnedTerm` itself.
+//! With the `derive` feature, `#[derive(ToTerm, FromTerm)]` implements them for structs
+//! and unit-only enums:
+//!
+//! * structs with named fields become maps with atom keys, or, with `#[term(tuple)]`,
+//!   tuples of their fields in declaration order
+//! * `#[term(record = "user")]` produces Erlang records: tuples tagged with the `user` atom
+//! * `#[term(elixir_struct = "MyApp.User")]` produces Elixir structs: maps with a `__struct__` key
+//! * tuple structs become tuples, `#[term(transparent)]` on a single-field struct uses the field's term
+//! * unit-only enums become atoms named after the variants
+//! * `#[term(rename = "...")]` changes a field key or a variant atom,
+//!   `#[term(default)]` uses `Default::default()` for fields missing from a map
+//!
+//! ```ignore
+//! use erltf::{FromTerm, ToTerm};
+//!
+//! #[derive(ToTerm, FromTerm)]
+//! #[term(record = "user")]
+//! struct User {
+//!     name: String,
+//!     age: u32,
+//! }
+//! ```
+
+use crate::bridge::{bigint_to_i128, integer_term};
+use crate::errors::TermConversionError;
+use crate::term::OwnedTerm;
+use crate::types::{Atom, ExternalPid, ExternalReference};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+/// Converts a value to a term.
+pub trait ToTerm {
+    fn to_term(&self) -> OwnedTerm;
+}
+
+/// Converts a term to a value.
+pub trait FromTerm: Sized {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError>;
+}
+
+fn wrong_type(expected: &'static str, term: &OwnedTerm) -> TermConversionError {
+    TermConversionError::WrongType {
+        expected,
+        actual: term.type_name(),
+    }
+}
+
+impl ToTerm for OwnedTerm {
+    fn to_term(&self) -> OwnedTerm {
+        self.clone()
+    }
+}
+
+impl FromTerm for OwnedTerm {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        Ok(term.clone())
+    }
+}
+
+macro_rules! integer_conversions {
+    ($($ty:ty),*) => {
+        $(
+            impl ToTerm for $ty {
+                fn to_term(&self) -> OwnedTerm {
+                    OwnedTerm::Integer(*self as i64)
+                }
+            }
+
+            impl FromTerm for $ty {
+                fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+                    match term.resolve() {
+                        OwnedTerm::Integer(i) => {
+                            <$ty>::try_from(*i).map_err(|_| TermConversionError::OutOfRange)
+                        }
+                        _ => Err(wrong_type("Integer", term)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer_conversions!(i8, i16, i32, i64, u8, u16, u32);
+
+macro_rules! wide_integer_conversions {
+    ($($ty:ty),*) => {
+        $(
+            impl ToTerm for $ty {
+                fn to_term(&self) -> OwnedTerm {
+                    integer_term(*self as i128)
+                }
+            }
+
+            impl FromTerm for $ty {
+                fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+                    let value = match term.resolve() {
+                        OwnedTerm::Integer(i) => *i as i128,
+                        OwnedTerm::BigInt(big) => {
+                            bigint_to_i128(big).ok_or(TermConversionError::OutOfRange)?
+                        }
+                        _ => return Err(wrong_type("Integer", term)),
+                    };
+                    <$ty>::try_from(value).map_err(|_| TermConversionError::OutOfRange)
+                }
+            }
+        )*
+    };
+}
+
+wide_integer_conversions!(u64, i128, usize, isize);
+
+impl ToTerm for f64 {
+    fn to_term(&self) -> OwnedTerm {
+        OwnedTerm::Float(*self)
+    }
+}
+
+impl FromTerm for f64 {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        match term.resolve() {
+            OwnedTerm::Float(f) => Ok(*f),
+            _ => Err(wrong_type("Float", term)),
+        }
+    }
+}
+
+impl ToTerm for f32 {
+    fn to_term(&self) -> OwnedTerm {
+        OwnedTerm::Float(*self as f64)
+    }
+}
+
+impl FromTerm for f32 {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        f64::from_term(term).map(|f| f as f32)
+    }
+}
+
+impl ToTerm for bool {
+    fn to_term(&self) -> OwnedTerm {
+        OwnedTerm::boolean(*self)
+    }
+}
+
+impl FromTerm for bool {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        term.as_bool()
+            .ok_or_else(|| wrong_type("boolean atom (true/false)", term))
+    }
+}
+
+impl ToTerm for str {
+    fn to_term(&self) -> OwnedTerm {
+        OwnedTerm::String(self.to_string())
+    }
+}
+
+impl ToTerm for String {
+    fn to_term(&self) -> OwnedTerm {
+        OwnedTerm::String(self.clone())
+    }
+}
+
+impl FromTerm for String {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        match term.resolve() {
+            OwnedTerm::String(s) => Ok(s.clone()),
+            OwnedTerm::Binary(b) => {
+                String::from_utf8(b.clone()).map_err(|_| TermConversionError::OutOfRange)
+            }
+            OwnedTerm::SharedBinary(b) => {
+                String::from_utf8(b.to_vec()).map_err(|_| TermConversionError::OutOfRange)
+            }
+            _ => Err(wrong_type("String or Binary", term)),
+        }
+    }
+}
+
+impl ToTerm for Atom {
+    fn to_term(&self) -> OwnedTerm {
+        OwnedTerm::Atom(self.clone())
+    }
+}
+
+impl FromTerm for Atom {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        match term.resolve() {
+            OwnedTerm::Atom(a) => Ok(a.clone()),
+            _ => Err(wrong_type("Atom", term)),
+        }
+    }
+}
+
+impl ToTerm for ExternalPid {
+    fn to_term(&self) -> OwnedTerm {
+        OwnedTerm::Pid(self.clone())
+    }
+}
+
+impl FromTerm for ExternalPid {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        match term.resolve() {
+            OwnedTerm::Pid(p) => Ok(p.clone()),
+            _ => Err(wrong_type("Pid", term)),
+        }
+    }
+}
+
+impl ToTerm for ExternalReference {
+    fn to_term(&self) -> OwnedTerm {
+        OwnedTerm::Reference(self.clone())
+    }
+}
+
+impl FromTerm for ExternalReference {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        match term.resolve() {
+            OwnedTerm::Reference(r) => Ok(r.clone()),
+            _ => Err(wrong_type("Reference", term)),
+        }
+    }
+}
+
+impl<T: ToTerm + ?Sized> ToTerm for &T {
+    fn to_term(&self) -> OwnedTerm {
+        (**self).to_term()
+    }
+}
+
+impl<T: ToTerm + ?Sized> ToTerm for Box<T> {
+    fn to_term(&self) -> OwnedTerm {
+        (**self).to_term()
+    }
+}
+
+impl<T: FromTerm> FromTerm for Box<T> {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        T::from_term(term).map(Box::new)
+    }
+}
+
+/// `None` is the `undefined` atom, or `nil` with the `elixir-interop` feature.
+/// Both atoms convert to `None`.
+impl<T: ToTerm> ToTerm for Option<T> {
+    fn to_term(&self) -> OwnedTerm {
+        match self {
+            Some(value) => value.to_term(),
+            #[cfg(feature = "elixir-interop")]
+            None => OwnedTerm::atom("nil"),
+            #[cfg(not(feature = "elixir-interop"))]
+            None => OwnedTerm::atom("undefined"),
+        }
+    }
+}
+
+impl<T: FromTerm> FromTerm for Option<T> {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        if term.is_undefined() || term.is_nil_atom() {
+            Ok(None)
+        } else {
+            T::from_term(term).map(Some)
+        }
+    }
+}
+
+impl<T: ToTerm> ToTerm for [T] {
+    fn to_term(&self) -> OwnedTerm {
+        OwnedTerm::List(self.iter().map(ToTerm::to_term).collect())
+    }
+}
+
+impl<T: ToTerm> ToTerm for Vec<T> {
+    fn to_term(&self) -> OwnedTerm {
+        self.as_slice().to_term()
+    }
+}
+
+impl<T: FromTerm> FromTerm for Vec<T> {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        match term.resolve() {
+            OwnedTerm::List(elements) => elements.iter().map(T::from_term).collect(),
+            OwnedTerm::Nil => Ok(Vec::new()),
+            OwnedTerm::ByteList(bytes) => bytes
+                .iter()
+                .map(|b| T::from_term(&OwnedTerm::Integer(*b as i64)))
+                .collect(),
+            _ => Err(wrong_type("List", term)),
+        }
+    }
+}
+
+impl<K: ToTerm, V: ToTerm> ToTerm for BTreeMap<K, V> {
+    fn to_term(&self) -> OwnedTerm {
+        OwnedTerm::Map(
+            self.iter()
+                .map(|(k, v)| (k.to_term(), v.to_term()))
+                .collect(),
+        )
+    }
+}
+
+impl<K: FromTerm + Ord, V: FromTerm> FromTerm for BTreeMap<K, V> {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        map_entries(term)?
+            .map(|(k, v)| Ok((K::from_term(k)?, V::from_term(v)?)))
+            .collect()
+    }
+}
+
+impl<K: ToTerm, V: ToTerm, S> ToTerm for HashMap<K, V, S> {
+    fn to_term(&self) -> OwnedTerm {
+        OwnedTerm::Map(
+            self.iter()
+                .map(|(k, v)| (k.to_term(), v.to_term()))
+                .collect(),
+        )
+    }
+}
+
+impl<K: FromTerm + Eq + Hash, V: FromTerm, S: BuildHasher + Default> FromTerm for HashMap<K, V, S> {
+    fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+        map_entries(term)?
+            .map(|(k, v)| Ok((K::from_term(k)?, V::from_term(v)?)))
+            .collect()
+    }
+}
+
+fn map_entries(
+    term: &OwnedTerm,
+) -> Result<Box<dyn Iterator<Item = (&OwnedTerm, &OwnedTerm)> + '_>, TermConversionError> {
+    match term.resolve() {
+        OwnedTerm::Map(map) => Ok(Box::new(map.iter())),
+        OwnedTerm::OrderedMap(entries) => Ok(Box::new(entries.iter().map(|(k, v)| (k, v)))),
+        _ => Err(wrong_type("Map", term)),
+    }
+}
+
+macro_rules! tuple_conversions {
+    ($($len:literal => ($($name:ident $idx:tt),+)),* $(,)?) => {
+        $(
+            impl<$($name: ToTerm),+> ToTerm for ($($name,)+) {
+                fn to_term(&self) -> OwnedTerm {
+                    OwnedTerm::Tuple(vec![$(self.$idx.to_term()),+])
+                }
+            }
+
+            impl<$($name: FromTerm),+> FromTerm for ($($name,)+) {
+                fn from_term(term: &OwnedTerm) -> Result<Self, TermConversionError> {
+                    let elements = __private::tuple_elements(term, $len)?;
+                    Ok(($($name::from_term(&elements[$idx])?,)+))
+                }
+            }
+        )*
+    };
+}
+
+tuple_conversions!(
+    1 => (A 0),
+    2 => (A 0, B 1),
+    3 => (A 0, B 1, C 2),
+    4 => (A 0, B 1, C 2, D 3),
+    5 => (A 0, B 1, C 2, D 3, E 4),
+    6 => (A 0, B 1, C 2, D 3, E 4, F 5),
+);
+
+/// Helpers for the code generated by the `ToTerm` and `FromTerm` derive macros.
+#[doc(hidden)]
+pub mod __private {
+    use super::{FromTerm, wrong_type};
+    use crate::errors::TermConversionError;
+    use crate::term::OwnedTerm;
+
+    pub fn tuple_elements(
+        term: &OwnedTerm,
+        arity: usize,
+    ) -> Result<&[OwnedTerm], TermConversionError> {
+        let elements = term.as_tuple().ok_or_else(|| wrong_type("Tuple", term))?;
+        if elements.len() != arity {
+            return Err(TermConversionError::WrongArity {
+                expected: arity,
+                actual: elements.len(),
+            });
+        }
+        Ok(elements)
+    }
+
+    pub fn expect_map(term: &OwnedTerm) -> Result<(), TermConversionError> {
+        if term.is_map() {
+            Ok(())
+        } else {
+            Err(wrong_type("Map", term))
+        }
+    }
+
+    pub fn map_get<'a>(term: &'a OwnedTerm, key: &str) -> Option<&'a OwnedTerm> {
+        let matches =
+            |k: &OwnedTerm| matches!(k.resolve(), OwnedTerm::Atom(atom) if atom.as_str() == key);
+        match term.resolve() {
+            OwnedTerm::Map(map) => map.iter().find(|(k, _)| matches(k)).map(|(_, v)| v),
+            OwnedTerm::OrderedMap(entries) => {
+                entries.iter().find(|(k, _)| matches(k)).map(|(_, v)| v)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn expect_atom(
+        term: Option<&OwnedTerm>,
+        expected: &str,
+    ) -> Result<(), TermConversionError> {
+        match term.map(OwnedTerm::resolve) {
+            Some(OwnedTerm::Atom(atom)) if atom.as_str() == expected => Ok(()),
+            other => Err(TermConversionError::UnexpectedValue {
+                expected: format!("the {expected} atom"),
+                actual: other.map_or_else(|| "nothing".to_string(), |t| t.to_string()),
+            }),
+        }
+    }
+
+    pub fn field<T: FromTerm>(
+        term: Option<&OwnedTerm>,
+        field: &'static str,
+    ) -> Result<T, TermConversionError> {
+        let term = term.ok_or(TermConversionError::MissingField(field))?;
+        T::from_term(term).map_err(|reason| TermConversionError::InvalidField {
+            field,
+            reason: Box::new(reason),
+        })
+    }
+
+    pub fn field_or_default<T: FromTerm + Default>(
+        term: Option<&OwnedTerm>,
+        field: &'static str,
+    ) -> Result<T, TermConversionError> {
+        match term {
+            Some(_) => self::field(term, field),
+            None => Ok(T::default()),
+        }
+    }
+
+    pub fn unknown_variant(term: &OwnedTerm, expected: &str) -> TermConversionError {
+        TermConversionError::UnexpectedValue {
+            expected: expected.to_string(),
+            actual: term.to_string(),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding policies for untrusted input.
+
+use crate::types::Atom;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ADMITTED_ATOMS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of atoms currently recorded by all live [`AtomTable`]s in this process.
+///
+/// An atom seen by two tables is counted twice. Tables only record atoms under a limit.
+pub fn admitted_atom_count() -> usize {
+    ADMITTED_ATOMS.load(Ordering::Relaxed)
+}
+
+/// What to do with a new atom once an [`AtomTable`] reaches its limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtomLimitPolicy {
+    /// Decode the atom as a binary with the same UTF-8 contents. The node of a pid,
+    /// port or reference cannot be a binary and fails with `DecodeError::AtomLimitExceeded`
+    AsBinary,
+    /// Fail decoding with `DecodeError::AtomLimitExceeded`
+    Error,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecodeConfig {
+    pub(crate) atom_limit: Option<(usize, AtomLimitPolicy)>,
+    pub(crate) safe: bool,
+    pub(crate) ordered_maps: bool,
+    pub(crate) byte_lists: bool,
+    pub(crate) shared_binaries: Option<usize>,
+}
+
+impl DecodeConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decodes atoms not yet seen as binaries after `n` distinct atoms have been admitted.
+    pub fn atoms_as_binaries_after(mut self, n: usize) -> Self {
+        self.atom_limit = Some((n, AtomLimitPolicy::AsBinary));
+        self
+    }
+
+    /// Fails decoding on atoms not yet seen after `n` distinct atoms have been admitted.
+    pub fn atoms_error_after(mut self, n: usize) -> Self {
+        self.atom_limit = Some((n, AtomLimitPolicy::Error));
+        self
+    }
+
+    /// Rejects funs of any kind, like `binary_to_term/2` with the `safe` option.
+    pub fn with_safe(mut self, safe: bool) -> Self {
+        self.safe = safe;
+        self
+    }
+
+    /// Decodes maps as [`OwnedTerm::OrderedMap`](crate::OwnedTerm::OrderedMap), keeping
+    /// their entries in wire order so that re-encoding them reproduces the same bytes.
+    pub fn with_ordered_maps(mut self, ordered_maps: bool) -> Self {
+        self.ordered_maps = ordered_maps;
+        self
+    }
+
+    /// Decodes `STRING_EXT` as [`OwnedTerm::ByteList`](crate::OwnedTerm::ByteList) instead of
+    /// a list of integers, which takes a byte per element instead of a whole term.
+    pub fn with_byte_lists(mut self, byte_lists: bool) -> Self {
+        self.byte_lists = byte_lists;
+        self
+    }
+
+    /// Decodes binaries of at least `min_size` bytes as
+    /// [`OwnedTerm::SharedBinary`](crate::OwnedTerm::SharedBinary) slices of the input
+    /// instead of copies. Only applies to functions that decode from [`Bytes`](bytes::Bytes),
+    /// such as [`decode_bytes_with_config`](crate::decoder::decode_bytes_with_config).
+    pub fn with_shared_binaries(mut self, min_size: usize) -> Self {
+        self.shared_binaries = Some(min_size);
+        self
+    }
+
+    #[must_use]
+    pub fn is_safe(&self) -> bool {
+        self.safe
+    }
+
+    #[must_use]
+    pub fn ordered_maps(&self) -> bool {
+        self.ordered_maps
+    }
+
+    #[must_use]
+    pub fn byte_lists(&self) -> bool {
+        self.byte_lists
+    }
+
+    #[must_use]
+    pub fn shared_binaries(&self) -> Option<usize> {
+        self.shared_binaries
+    }
+
+    #[must_use]
+    pub fn atom_limit(&self) -> Option<usize> {
+        self.atom_limit.map(|(n, _)| n)
+    }
+
+    #[must_use]
+    pub fn atom_limit_policy(&self) -> Option<AtomLimitPolicy> {
+        self.atom_limit.map(|(_, policy)| policy)
+    }
+}
+
+/// The outcome of offering an atom to an [`AtomTable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AtomAdmission {
+    Admitted(Atom),
+    Rejected(AtomLimitPolicy),
+}
+
+/// Tracks the distinct atoms decoded from a single source, such as a connection.
+///
+/// Distinct atoms are only recorded while the [`DecodeConfig`] sets a limit, so the
+/// table never holds more atoms than that limit. Atoms that are pre-allocated by
+/// [`Atom::new`] (`ok`, `error`, `true` and so on) are never counted against it.
+#[derive(Debug, Default)]
+pub struct AtomTable {
+    seen: HashSet<Atom>,
+    total_decoded: u64,
+}
+
+impl AtomTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of distinct atoms recorded by this table under a limit.
+    #[must_use]
+    pub fn distinct_count(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Returns the number of atoms decoded through this table, including repeats and rejections.
+    #[must_use]
+    pub fn total_decoded(&self) -> u64 {
+        self.total_decoded
+    }
+
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.seen.contains(name)
+    }
+
+    pub fn admit(&mut self, name: &str, config: &DecodeConfig) -> AtomAdmission {
+        self.total_decoded += 1;
+
+        let Some((limit, policy)) = config.atom_limit else {
+            return AtomAdmission::Admitted(Atom::new(name));
+        };
+
+        if let Some(atom) = self.seen.get(name) {
+            return AtomAdmission::Admitted(atom.clone());
+        }
+
+        let atom = Atom::new(name);
+        if atom.is_preallocated() {
+            return AtomAdmission::Admitted(atom);
+        }
+
+        if self.seen.len() >= limit {
+            return AtomAdmission::Rejected(policy);
+        }
+
+        self.seen.insert(atom.clone());
+        ADMITTED_ATOMS.fetch_add(1, Ordering::Relaxed);
+        AtomAdmission::Admitted(atom)
+    }
+
+    pub fn clear(&mut self) {
+        ADMITTED_ATOMS.fetch_sub(self.seen.len(), Ordering::Relaxed);
+        self.seen.clear();
+        self.total_decoded = 0;
+    }
+}
+
+impl Clone for AtomTable {
+    fn clone(&self) -> Self {
+        ADMITTED_ATOMS.fetch_add(self.seen.len(), Ordering::Relaxed);
+        Self {
+            seen: self.seen.clone(),
+            total_decoded: self.total_decoded,
+        }
+    }
+}
+
+impl Drop for AtomTable {
+    fn drop(&mut self) {
+        ADMITTED_ATOMS.fetch_sub(self.seen.len(), Ordering::Relaxed);
+    }
+}
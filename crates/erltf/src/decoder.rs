@@ -13,27 +13,32 @@
 // limitations under the License.
 
 use crate::borrowed::BorrowedTerm;
-use crate::errors::{ContextualDecodeError, DecodeError, ParsingContext, PathSegment};
+use crate::decode_config::{AtomAdmission, AtomLimitPolicy, AtomTable, DecodeConfig};
+use crate::errors::{
+    ContextualDecodeError, DEFAULT_SNIPPET_WINDOW, DecodeError, ParsingContext, PathSegment,
+};
 use crate::tags::{
     ATOM_CACHE_REF, ATOM_EXT, ATOM_UTF8_EXT, BINARY_EXT, BIT_BINARY_EXT, COMPRESSED_EXT,
-    DIST_FRAG_HEADER, DIST_HEADER, EXPORT_EXT, FLOAT_EXT, INTEGER_EXT, LARGE_BIG_EXT,
-    LARGE_TUPLE_EXT, LIST_EXT, LOCAL_EXT, MAP_EXT, NEW_FLOAT_EXT, NEW_FUN_EXT, NEW_PID_EXT,
-    NEW_REFERENCE_EXT, NEWER_REFERENCE_EXT, NIL_EXT, PID_EXT, PORT_EXT, REFERENCE_EXT,
-    SMALL_ATOM_EXT, SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT, SMALL_INTEGER_EXT, SMALL_TUPLE_EXT,
-    STRING_EXT, V4_PORT_EXT, VERSION,
+    DIST_FRAG_CONT, DIST_FRAG_HEADER, DIST_HEADER, EXPORT_EXT, FLOAT_EXT, INTEGER_EXT,
+    LARGE_BIG_EXT, LARGE_TUPLE_EXT, LIST_EXT, LOCAL_EXT, MAP_EXT, NEW_FLOAT_EXT, NEW_FUN_EXT,
+    NEW_PID_EXT, NEW_PORT_EXT, NEW_REFERENCE_EXT, NEWER_REFERENCE_EXT, NIL_EXT, PID_EXT, PORT_EXT,
+    REFERENCE_EXT, SMALL_ATOM_EXT, SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT, SMALL_INTEGER_EXT,
+    SMALL_TUPLE_EXT, STRING_EXT, V4_PORT_EXT, VERSION, tag_name,
 };
 use crate::term::OwnedTerm;
 use crate::types::{
     Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun,
 };
+use bytes::Bytes;
 use flate2::read::ZlibDecoder;
 use nom::IResult;
 use nom::bytes::complete::take;
 use nom::error::{Error as NomError, ErrorKind};
 use nom::number::complete::{be_f64, be_i32, be_u8, be_u16, be_u32, be_u64};
 use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
 use std::collections::{BTreeMap, HashMap};
-use std::io::Read;
+use std::io::{self, Read};
 use std::str;
 
 const MAX_ATOM_SIZE: usize = 65535;
@@ -44,17 +49,30 @@ const MAX_BINARY_SIZE: usize = 100_000_000;
 
 type NomResult<'a, T> = IResult<&'a [u8], T, NomError<&'a [u8]>>;
 
+/// How many elements to preallocate for a declared count: every encoded term takes
+/// at least one byte, so a count larger than the remaining input is never reached.
+fn preallocation(count: usize, input: &[u8]) -> usize {
+    count.min(input.len())
+}
+
 const ATOM_CACHE_SIZE: usize = 256;
 
+/// The receiving side of a connection's atom cache.
+///
+/// `insert` and `get` work with `ATOM_CACHE_REF` indices, i.e. positions in the
+/// current distribution header. Entries announced by the peer are kept by their
+/// cache index so that later headers can refer to them without the atom text.
 #[derive(Debug, Clone)]
 pub struct AtomCache {
     atoms: HashMap<u8, Atom>,
+    entries: HashMap<u16, Atom>,
 }
 
 impl AtomCache {
     pub fn new() -> Self {
         Self {
             atoms: HashMap::with_capacity(ATOM_CACHE_SIZE),
+            entries: HashMap::new(),
         }
     }
 
@@ -73,6 +91,37 @@ impl AtomCache {
     pub fn is_empty(&self) -> bool {
         self.atoms.is_empty()
     }
+
+    pub fn insert_entry(&mut self, cache_index: u16, atom: Atom) {
+        self.entries.insert(cache_index, atom);
+    }
+
+    pub fn entry(&self, cache_index: u16) -> Option<&Atom> {
+        self.entries.get(&cache_index)
+    }
+
+    pub fn entries_len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Forgets all entries, e.g. after the peer reconnects.
+    pub fn clear(&mut self) {
+        self.atoms.clear();
+        self.entries.clear();
+    }
+
+    /// Releases unused capacity, e.g. while a connection is idle. Entries announced
+    /// by the peer are kept, the references of the last distribution header are dropped.
+    pub fn shrink_to_fit(&mut self) {
+        self.atoms.clear();
+        self.atoms.shrink_to_fit();
+        self.entries.shrink_to_fit();
+    }
+}
+
+/// Returns the LongAtoms flag, which follows the flags of the last atom cache ref.
+pub(crate) fn dist_header_long_atoms(flags: &[u8], refs: usize) -> bool {
+    (flags[refs / 2] >> (4 * (refs % 2))) & 0x01 != 0
 }
 
 impl Default for AtomCache {
@@ -81,9 +130,141 @@ impl Default for AtomCache {
     }
 }
 
+static DEFAULT_DECODE_CONFIG: DecodeConfig = DecodeConfig {
+    atom_limit: None,
+    safe: false,
+    ordered_maps: false,
+    byte_lists: false,
+    shared_binaries: None,
+};
+
+struct DecodeContext<'c> {
+    cache: &'c AtomCache,
+    config: &'c DecodeConfig,
+    atoms: RefCell<Option<&'c mut AtomTable>>,
+    failure: RefCell<Option<DecodeError>>,
+    source: Option<&'c Bytes>,
+    /// Set while the node of a pid, port or reference is parsed
+    parsing_node: Cell<bool>,
+}
+
+impl<'c> DecodeContext<'c> {
+    fn new(cache: &'c AtomCache) -> Self {
+        Self {
+            cache,
+            config: &DEFAULT_DECODE_CONFIG,
+            atoms: RefCell::new(None),
+            failure: RefCell::new(None),
+            source: None,
+            parsing_node: Cell::new(false),
+        }
+    }
+
+    fn with_config(
+        cache: &'c AtomCache,
+        config: &'c DecodeConfig,
+        atoms: Option<&'c mut AtomTable>,
+    ) -> Self {
+        Self {
+            cache,
+            config,
+            atoms: RefCell::new(atoms),
+            failure: RefCell::new(None),
+            source: None,
+            parsing_node: Cell::new(false),
+        }
+    }
+
+    /// Decodes binaries as slices of `source`, see [`DecodeConfig::with_shared_binaries`].
+    fn sharing(mut self, source: &'c Bytes) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Returns `data` as a slice of the source buffer when it is large enough to share.
+    /// Binaries from compressed terms are not part of the source and are copied.
+    fn share(&self, data: &[u8]) -> Option<Bytes> {
+        let min_size = self.config.shared_binaries?;
+        let source = self.source?;
+        let start = source.as_ptr() as usize;
+        let offset = (data.as_ptr() as usize).checked_sub(start)?;
+        (data.len() >= min_size && offset + data.len() <= source.len())
+            .then(|| source.slice(offset..offset + data.len()))
+    }
+
+    /// Records a policy violation that nom error kinds cannot express.
+    fn fail<'a>(&self, input: &'a [u8], error: DecodeError) -> nom::Err<NomError<&'a [u8]>> {
+        self.failure.borrow_mut().get_or_insert(error);
+        nom::Err::Failure(NomError::new(input, ErrorKind::Verify))
+    }
+
+    fn error(&self, e: nom::Err<NomError<&[u8]>>) -> DecodeError {
+        self.failure
+            .borrow_mut()
+            .take()
+            .unwrap_or_else(|| from_nom_error(e))
+    }
+}
+
+fn admit_atom<'a>(
+    input: &'a [u8],
+    name: &str,
+    ctx: &DecodeContext<'_>,
+) -> Result<OwnedTerm, nom::Err<NomError<&'a [u8]>>> {
+    let mut atoms = ctx.atoms.borrow_mut();
+    let Some(table) = atoms.as_mut() else {
+        return Ok(OwnedTerm::Atom(Atom::new(name)));
+    };
+
+    match table.admit(name, ctx.config) {
+        AtomAdmission::Admitted(atom) => Ok(OwnedTerm::Atom(atom)),
+        // A node name cannot be a binary
+        AtomAdmission::Rejected(AtomLimitPolicy::AsBinary) if !ctx.parsing_node.get() => {
+            Ok(OwnedTerm::Binary(name.as_bytes().to_vec()))
+        }
+        AtomAdmission::Rejected(_) => {
+            let limit = table.distinct_count();
+            Err(ctx.fail(input, DecodeError::AtomLimitExceeded { limit }))
+        }
+    }
+}
+
 pub fn decode(data: &[u8]) -> Result<OwnedTerm, DecodeError> {
     let cache = AtomCache::new();
-    let (remaining, term) = parse_versioned_term(data, &cache).map_err(from_nom_error)?;
+    let ctx = DecodeContext::new(&cache);
+    let (remaining, term) = parse_versioned_term(data, &ctx).map_err(|e| ctx.error(e))?;
+
+    if !remaining.is_empty() {
+        return Err(DecodeError::TrailingData(remaining.len()));
+    }
+
+    Ok(term)
+}
+
+/// Decodes a term, applying `config` and recording decoded atoms in `atoms`.
+pub fn decode_with_config(
+    data: &[u8],
+    config: &DecodeConfig,
+    atoms: &mut AtomTable,
+) -> Result<OwnedTerm, DecodeError> {
+    let (term, remaining) = decode_with_trailing_and_config(data, config, atoms)?;
+
+    if !remaining.is_empty() {
+        return Err(DecodeError::TrailingData(remaining.len()));
+    }
+
+    Ok(term)
+}
+
+/// Decodes a term that comes from an untrusted source, mirroring `binary_to_term/2`
+/// with the `safe` option: funs of any kind are rejected.
+///
+/// To also limit atoms, use [`decode_with_config`] with [`DecodeConfig::with_safe`].
+pub fn decode_safe(data: &[u8]) -> Result<OwnedTerm, DecodeError> {
+    let config = DecodeConfig::new().with_safe(true);
+    let cache = AtomCache::new();
+    let ctx = DecodeContext::with_config(&cache, &config, None);
+    let (remaining, term) = parse_versioned_term(data, &ctx).map_err(|e| ctx.error(e))?;
 
     if !remaining.is_empty() {
         return Err(DecodeError::TrailingData(remaining.len()));
@@ -94,13 +275,119 @@ pub fn decode(data: &[u8]) -> Result<OwnedTerm, DecodeError> {
 
 pub fn decode_with_trailing(data: &[u8]) -> Result<(OwnedTerm, &[u8]), DecodeError> {
     let cache = AtomCache::new();
-    let (remaining, term) = parse_versioned_term(data, &cache).map_err(from_nom_error)?;
+    let ctx = DecodeContext::new(&cache);
+    let (remaining, term) = parse_versioned_term(data, &ctx).map_err(|e| ctx.error(e))?;
     Ok((term, remaining))
 }
 
+pub fn decode_with_trailing_and_config<'a>(
+    data: &'a [u8],
+    config: &DecodeConfig,
+    atoms: &mut AtomTable,
+) -> Result<(OwnedTerm, &'a [u8]), DecodeError> {
+    let cache = AtomCache::new();
+    let ctx = DecodeContext::with_config(&cache, config, Some(atoms));
+    let (remaining, term) = parse_versioned_term(data, &ctx).map_err(|e| ctx.error(e))?;
+    Ok((term, remaining))
+}
+
+/// Like [`decode_with_config`] but decodes binaries as slices of `data` when
+/// [`DecodeConfig::with_shared_binaries`] is set.
+pub fn decode_bytes_with_config(
+    data: &Bytes,
+    config: &DecodeConfig,
+    atoms: &mut AtomTable,
+) -> Result<OwnedTerm, DecodeError> {
+    let (term, remaining) = decode_bytes_with_trailing_and_config(data, config, atoms)?;
+
+    if !remaining.is_empty() {
+        return Err(DecodeError::TrailingData(remaining.len()));
+    }
+
+    Ok(term)
+}
+
+/// Like [`decode_with_trailing_and_config`] but decodes binaries as slices of `data` when
+/// [`DecodeConfig::with_shared_binaries`] is set. The trailing bytes are a slice of `data` too.
+pub fn decode_bytes_with_trailing_and_config(
+    data: &Bytes,
+    config: &DecodeConfig,
+    atoms: &mut AtomTable,
+) -> Result<(OwnedTerm, Bytes), DecodeError> {
+    let cache = AtomCache::new();
+    let ctx = DecodeContext::with_config(&cache, config, Some(atoms)).sharing(data);
+    let (remaining, term) = parse_versioned_term(data, &ctx).map_err(|e| ctx.error(e))?;
+    Ok((term, data.slice_ref(remaining)))
+}
+
+/// Decodes a buffer of concatenated versioned terms, such as the control message
+/// and payload of a pass-through frame, one term at a time.
+///
+/// The iterator ends after the last term or the first error.
+pub fn decode_all(data: &[u8]) -> DecodeAll<'_> {
+    DecodeAll {
+        data,
+        decoding: None,
+        failed: false,
+    }
+}
+
+/// Like [`decode_all`] but applies `config` and records decoded atoms in `atoms`.
+pub fn decode_all_with_config<'a>(
+    data: &'a [u8],
+    config: &'a DecodeConfig,
+    atoms: &'a mut AtomTable,
+) -> DecodeAll<'a> {
+    DecodeAll {
+        data,
+        decoding: Some((config, atoms)),
+        failed: false,
+    }
+}
+
+/// The terms of a buffer, see [`decode_all`].
+#[derive(Debug)]
+pub struct DecodeAll<'a> {
+    data: &'a [u8],
+    decoding: Option<(&'a DecodeConfig, &'a mut AtomTable)>,
+    failed: bool,
+}
+
+impl<'a> DecodeAll<'a> {
+    /// The bytes not decoded yet, including those of a term that failed to decode.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+impl Iterator for DecodeAll<'_> {
+    type Item = Result<OwnedTerm, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() || self.failed {
+            return None;
+        }
+        let decoded = match &mut self.decoding {
+            Some((config, atoms)) => decode_with_trailing_and_config(self.data, config, atoms),
+            None => decode_with_trailing(self.data),
+        };
+        match decoded {
+            Ok((term, remaining)) => {
+                self.data = remaining;
+                Some(Ok(term))
+            }
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
 pub fn decode_raw_term(data: &[u8]) -> Result<OwnedTerm, DecodeError> {
     let cache = AtomCache::new();
-    let (remaining, term) = parse_term(data, &cache).map_err(from_nom_error)?;
+    let ctx = DecodeContext::new(&cache);
+    let (remaining, term) = parse_term(data, &ctx).map_err(|e| ctx.error(e))?;
 
     if !remaining.is_empty() {
         return Err(DecodeError::TrailingData(remaining.len()));
@@ -114,11 +401,13 @@ pub fn decode_with_cache(
     data: &[u8],
 ) -> Result<(OwnedTerm, Option<(OwnedTerm, &[u8])>), DecodeError> {
     let mut cache = AtomCache::new();
-    let (remaining, term) =
-        parse_versioned_term_with_cache(data, &mut cache).map_err(from_nom_error)?;
+    let (input, ()) = parse_version_and_dist_header(data, &mut cache).map_err(from_nom_error)?;
+
+    let ctx = DecodeContext::new(&cache);
+    let (remaining, term) = parse_term(input, &ctx).map_err(|e| ctx.error(e))?;
 
     if !remaining.is_empty() {
-        let (new_remaining, payload) = parse_term(remaining, &cache).map_err(from_nom_error)?;
+        let (new_remaining, payload) = parse_term(remaining, &ctx).map_err(|e| ctx.error(e))?;
         Ok((term, Some((payload, new_remaining))))
     } else {
         Ok((term, None))
@@ -129,10 +418,95 @@ pub fn decode_with_atom_cache(
     data: &[u8],
     cache: &mut AtomCache,
 ) -> Result<(OwnedTerm, Option<OwnedTerm>), DecodeError> {
-    let (remaining, term) = parse_versioned_term_with_cache(data, cache).map_err(from_nom_error)?;
+    let (input, ()) = parse_version_and_dist_header(data, cache).map_err(from_nom_error)?;
+    let ctx = DecodeContext::new(cache);
+    decode_control_and_payload(input, &ctx)
+}
 
+/// Decodes every term after a distribution header, for messages built with
+/// [`encode_with_dist_header_multi`](crate::encoder::encode_with_dist_header_multi)
+/// from more than a control message and a payload.
+pub fn decode_all_with_atom_cache(
+    data: &[u8],
+    cache: &mut AtomCache,
+) -> Result<Vec<OwnedTerm>, DecodeError> {
+    let (mut input, ()) = parse_version_and_dist_header(data, cache).map_err(from_nom_error)?;
+    let ctx = DecodeContext::new(cache);
+    let mut terms = Vec::new();
+    while !input.is_empty() {
+        let (remaining, term) = parse_term(input, &ctx).map_err(|e| ctx.error(e))?;
+        terms.push(term);
+        input = remaining;
+    }
+    Ok(terms)
+}
+
+/// Like [`decode_with_atom_cache`] but applies `config` and records decoded atoms in `atoms`.
+pub fn decode_with_atom_cache_and_config(
+    data: &[u8],
+    cache: &mut AtomCache,
+    config: &DecodeConfig,
+    atoms: &mut AtomTable,
+) -> Result<(OwnedTerm, Option<OwnedTerm>), DecodeError> {
+    let (input, ()) = parse_version_and_dist_header(data, cache).map_err(from_nom_error)?;
+    let ctx = DecodeContext::with_config(cache, config, Some(atoms));
+    decode_control_and_payload(input, &ctx)
+}
+
+/// Like [`decode_with_atom_cache_and_config`] but leaves the payload undecoded,
+/// see [`decode_payload_with_atom_cache_and_config`].
+pub fn decode_control_with_atom_cache_and_config<'a>(
+    data: &'a [u8],
+    cache: &mut AtomCache,
+    config: &DecodeConfig,
+    atoms: &mut AtomTable,
+) -> Result<(OwnedTerm, &'a [u8]), DecodeError> {
+    let (input, ()) = parse_version_and_dist_header(data, cache).map_err(from_nom_error)?;
+    let ctx = DecodeContext::with_config(cache, config, Some(atoms));
+    let (remaining, term) = parse_term(input, &ctx).map_err(|e| ctx.error(e))?;
+    Ok((term, remaining))
+}
+
+/// Decodes the payload left by [`decode_control_with_atom_cache_and_config`],
+/// resolving atom cache references against the same message header.
+pub fn decode_payload_with_atom_cache_and_config(
+    data: &[u8],
+    cache: &AtomCache,
+    config: &DecodeConfig,
+    atoms: &mut AtomTable,
+) -> Result<OwnedTerm, DecodeError> {
+    let ctx = DecodeContext::with_config(cache, config, Some(atoms));
+    let (remaining, term) = parse_term(data, &ctx).map_err(|e| ctx.error(e))?;
+    if !remaining.is_empty() {
+        return Err(DecodeError::TrailingData(remaining.len()));
+    }
+    Ok(term)
+}
+
+/// Like [`decode_payload_with_atom_cache_and_config`] but decodes binaries as slices
+/// of `data` when [`DecodeConfig::with_shared_binaries`] is set.
+pub fn decode_payload_bytes_with_atom_cache_and_config(
+    data: &Bytes,
+    cache: &AtomCache,
+    config: &DecodeConfig,
+    atoms: &mut AtomTable,
+) -> Result<OwnedTerm, DecodeError> {
+    let ctx = DecodeContext::with_config(cache, config, Some(atoms)).sharing(data);
+    let (remaining, term) = parse_term(data, &ctx).map_err(|e| ctx.error(e))?;
     if !remaining.is_empty() {
-        let (new_remaining, payload) = parse_term(remaining, cache).map_err(from_nom_error)?;
+        return Err(DecodeError::TrailingData(remaining.len()));
+    }
+    Ok(term)
+}
+
+fn decode_control_and_payload(
+    input: &[u8],
+    ctx: &DecodeContext<'_>,
+) -> Result<(OwnedTerm, Option<OwnedTerm>), DecodeError> {
+    let (remaining, term) = parse_term(input, ctx).map_err(|e| ctx.error(e))?;
+
+    if !remaining.is_empty() {
+        let (new_remaining, payload) = parse_term(remaining, ctx).map_err(|e| ctx.error(e))?;
         if !new_remaining.is_empty() {
             return Err(DecodeError::TrailingData(new_remaining.len()));
         }
@@ -190,10 +564,10 @@ pub fn decode_fragment_cont(data: &[u8]) -> Result<((u64, u64), &[u8]), DecodeEr
     }
 
     let (input, tag) = be_u8(input).map_err(from_nom_error)?;
-    if tag != NEW_FLOAT_EXT {
+    if tag != DIST_FRAG_CONT {
         return Err(DecodeError::InvalidFormat(format!(
-            "Expected DIST_FRAG_CONT (70), got {}",
-            tag
+            "Expected DIST_FRAG_CONT ({}), got {}",
+            DIST_FRAG_CONT, tag
         )));
     }
 
@@ -203,6 +577,565 @@ pub fn decode_fragment_cont(data: &[u8]) -> Result<((u64, u64), &[u8]), DecodeEr
     Ok(((sequence_id, fragment_id), input))
 }
 
+/// Structural information gathered by [`validate`] without decoding any terms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TermSummary {
+    pub encoded_len: usize,
+    /// 2 for a distribution frame with a control message and a payload, 1 otherwise
+    pub top_level_terms: usize,
+    /// Every term, including nested ones
+    pub terms: usize,
+    pub max_depth: usize,
+    pub atoms: usize,
+    pub binaries: usize,
+    pub binary_bytes: usize,
+    pub compressed: bool,
+}
+
+impl TermSummary {
+    /// Approximately the [`OwnedTerm::heap_size`] the terms decode to: a term each,
+    /// plus the bytes of binaries.
+    #[must_use]
+    pub fn estimated_heap_size(&self) -> usize {
+        self.terms
+            .saturating_mul(size_of::<OwnedTerm>())
+            .saturating_add(self.binary_bytes)
+    }
+}
+
+/// Returns the input that follows the first term in `data`, which must start at a tag
+/// (no version byte). Nested terms are skipped using their size information, without
+/// being decoded.
+pub fn skip_term(data: &[u8]) -> Result<&[u8], DecodeError> {
+    TermWalker::new(false).walk(data)
+}
+
+/// Checks that `data` is a well-formed versioned term, or a distribution frame with a
+/// `DIST_HEADER` followed by terms, without decoding it.
+///
+/// Tags, sizes and limits are verified as the decoder would, UTF-8 atoms are checked and
+/// compressed terms are inflated and validated. Atom cache references are only accepted
+/// after a distribution header, since the cache entries may come from earlier frames.
+pub fn validate(data: &[u8]) -> Result<TermSummary, DecodeError> {
+    let (&version, mut input) = data.split_first().ok_or(DecodeError::UnexpectedEof)?;
+    if version != VERSION {
+        return Err(DecodeError::InvalidVersion {
+            expected: VERSION,
+            actual: version,
+        });
+    }
+
+    let mut walker = TermWalker::new(true);
+    walker.summary.encoded_len = data.len();
+    if let Some((&DIST_HEADER, rest)) = input.split_first() {
+        input = validate_dist_header(rest, &mut walker.summary)?;
+        walker.cache_refs = true;
+        if input.is_empty() {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        while !input.is_empty() {
+            input = walker.walk(input)?;
+            walker.summary.top_level_terms += 1;
+        }
+    } else {
+        input = walker.walk(input)?;
+        walker.summary.top_level_terms = 1;
+        if !input.is_empty() {
+            return Err(DecodeError::TrailingData(input.len()));
+        }
+    }
+    Ok(walker.summary)
+}
+
+/// Like [`validate`] for a sequence of versioned terms, e.g. the control message
+/// and payload of a pass-through distribution frame that follow its marker.
+pub fn validate_all(data: &[u8]) -> Result<TermSummary, DecodeError> {
+    if data.is_empty() {
+        return Err(DecodeError::UnexpectedEof);
+    }
+    let mut walker = TermWalker::new(true);
+    walker.summary.encoded_len = data.len();
+    let mut input = data;
+    while let Some((&version, rest)) = input.split_first() {
+        if version != VERSION {
+            return Err(DecodeError::InvalidVersion {
+                expected: VERSION,
+                actual: version,
+            });
+        }
+        input = walker.walk(rest)?;
+        walker.summary.top_level_terms += 1;
+    }
+    Ok(walker.summary)
+}
+
+fn validate_dist_header<'a>(
+    input: &'a [u8],
+    summary: &mut TermSummary,
+) -> Result<&'a [u8], DecodeError> {
+    let (input, refs) = read_be(input, 1)?;
+    if refs == 0 {
+        return Ok(input);
+    }
+    let flags_len = refs / 2 + 1;
+    let flags = input.get(..flags_len).ok_or(DecodeError::UnexpectedEof)?;
+    let mut input = &input[flags_len..];
+    let long_atoms = dist_header_long_atoms(flags, refs);
+    for i in 0..refs {
+        let nibble = (flags[i / 2] >> (4 * (i % 2))) & 0x0F;
+        (input, _) = read_be(input, 1)?;
+        if nibble & 0x08 != 0 {
+            let (rest, len) = read_be(input, if long_atoms { 2 } else { 1 })?;
+            let text = rest.get(..len).ok_or(DecodeError::UnexpectedEof)?;
+            str::from_utf8(text).map_err(|e| DecodeError::InvalidUtf8(e.to_string()))?;
+            summary.atoms += 1;
+            input = &rest[len..];
+        }
+    }
+    Ok(input)
+}
+
+/// Walks encoded terms using their size information. In checked mode, it also verifies
+/// everything the decoder would reject, short of building terms.
+struct TermWalker {
+    checked: bool,
+    cache_refs: bool,
+    in_compressed: bool,
+    depth_offset: usize,
+    summary: TermSummary,
+}
+
+impl TermWalker {
+    fn new(checked: bool) -> Self {
+        Self {
+            checked,
+            cache_refs: !checked,
+            in_compressed: false,
+            depth_offset: 0,
+            summary: TermSummary::default(),
+        }
+    }
+
+    /// Returns the input that follows one term and all of its nested terms.
+    fn walk<'a>(&mut self, data: &'a [u8]) -> Result<&'a [u8], DecodeError> {
+        let mut input = data;
+        let mut remaining = vec![1usize];
+        while let Some(top) = remaining.last_mut() {
+            if *top == 0 {
+                remaining.pop();
+                continue;
+            }
+            *top -= 1;
+            let depth = self.depth_offset + remaining.len();
+            self.summary.terms += 1;
+            self.summary.max_depth = self.summary.max_depth.max(depth);
+
+            let (&tag, rest) = input.split_first().ok_or(DecodeError::UnexpectedEof)?;
+            let (rest, nested) = self.header(rest, tag, depth)?;
+            if nested > 0 {
+                remaining.push(nested);
+            }
+            input = rest;
+        }
+        Ok(input)
+    }
+
+    /// Skips the fixed part of a term and returns the number of nested terms that follow.
+    fn header<'a>(
+        &mut self,
+        input: &'a [u8],
+        tag: u8,
+        depth: usize,
+    ) -> Result<(&'a [u8], usize), DecodeError> {
+        match tag {
+            SMALL_INTEGER_EXT => skip_bytes(input, 1, 0),
+            INTEGER_EXT => skip_bytes(input, 4, 0),
+            FLOAT_EXT => skip_bytes(input, 31, 0),
+            NEW_FLOAT_EXT => skip_bytes(input, 8, 0),
+            ATOM_EXT | ATOM_UTF8_EXT | SMALL_ATOM_EXT | SMALL_ATOM_UTF8_EXT | ATOM_CACHE_REF => {
+                self.atom(input, tag)
+            }
+            STRING_EXT => {
+                let (input, len) = read_be(input, 2)?;
+                skip_bytes(input, len, 0)
+            }
+            SMALL_BIG_EXT | LARGE_BIG_EXT => {
+                let (input, len) = read_be(input, if tag == SMALL_BIG_EXT { 1 } else { 4 })?;
+                if self.checked && input.first().is_some_and(|&sign| sign > 1) {
+                    return Err(DecodeError::InvalidFormat(format!(
+                        "invalid big integer sign: {}",
+                        input[0]
+                    )));
+                }
+                skip_bytes(input, len.saturating_add(1), 0)
+            }
+            SMALL_TUPLE_EXT => read_be(input, 1),
+            LARGE_TUPLE_EXT => {
+                let (input, arity) = read_be(input, 4)?;
+                check_limit(arity, MAX_TUPLE_SIZE, |size, max| {
+                    DecodeError::TupleTooLarge { size, max }
+                })?;
+                Ok((input, arity))
+            }
+            NIL_EXT => Ok((input, 0)),
+            LIST_EXT => {
+                let (input, len) = read_be(input, 4)?;
+                check_limit(len, MAX_LIST_SIZE, |size, max| DecodeError::ListTooLarge {
+                    size,
+                    max,
+                })?;
+                Ok((input, len + 1))
+            }
+            MAP_EXT => {
+                let (input, pairs) = read_be(input, 4)?;
+                check_limit(pairs, MAX_MAP_SIZE, |size, max| DecodeError::MapTooLarge {
+                    size,
+                    max,
+                })?;
+                Ok((input, pairs * 2))
+            }
+            BINARY_EXT | BIT_BINARY_EXT => {
+                let (input, len) = read_be(input, 4)?;
+                check_limit(len, MAX_BINARY_SIZE, |size, max| {
+                    DecodeError::BinaryTooLarge { size, max }
+                })?;
+                self.summary.binaries += 1;
+                self.summary.binary_bytes += len;
+                let bits = usize::from(tag == BIT_BINARY_EXT);
+                skip_bytes(input, len + bits, 0)
+            }
+            PID_EXT => skip_bytes(self.node(input)?, 9, 0),
+            NEW_PID_EXT | V4_PORT_EXT => skip_bytes(self.node(input)?, 12, 0),
+            NEW_PORT_EXT => skip_bytes(self.node(input)?, 8, 0),
+            PORT_EXT | REFERENCE_EXT => skip_bytes(self.node(input)?, 5, 0),
+            NEW_REFERENCE_EXT | NEWER_REFERENCE_EXT => {
+                let (input, words) = read_be(input, 2)?;
+                let creation = if tag == NEW_REFERENCE_EXT { 1 } else { 4 };
+                skip_bytes(self.node(input)?, creation + 4 * words, 0)
+            }
+            EXPORT_EXT => Ok((input, 3)),
+            NEW_FUN_EXT => {
+                // The size includes the size field itself
+                let (input, size) = read_be(input, 4)?;
+                let remaining = size.checked_sub(4).ok_or_else(|| {
+                    DecodeError::InvalidFormat(format!("invalid NEW_FUN_EXT size: {size}"))
+                })?;
+                skip_bytes(input, remaining, 0)
+            }
+            LOCAL_EXT => skip_bytes(input, 8, 1),
+            COMPRESSED_EXT => self.compressed(input, depth),
+            _ => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+
+    fn atom<'a>(&mut self, input: &'a [u8], tag: u8) -> Result<(&'a [u8], usize), DecodeError> {
+        self.summary.atoms += 1;
+        if tag == ATOM_CACHE_REF {
+            if !self.cache_refs {
+                return Err(DecodeError::InvalidFormat(
+                    "atom cache reference without a distribution header".to_string(),
+                ));
+            }
+            return skip_bytes(input, 1, 0);
+        }
+        let width = if tag == ATOM_EXT || tag == ATOM_UTF8_EXT {
+            2
+        } else {
+            1
+        };
+        let (input, len) = read_be(input, width)?;
+        let text = input.get(..len).ok_or(DecodeError::UnexpectedEof)?;
+        if self.checked && (tag == ATOM_UTF8_EXT || tag == SMALL_ATOM_UTF8_EXT) {
+            str::from_utf8(text).map_err(|e| DecodeError::InvalidUtf8(e.to_string()))?;
+        }
+        Ok((&input[len..], 0))
+    }
+
+    /// Skips the node atom of a pid, port or reference.
+    fn node<'a>(&mut self, input: &'a [u8]) -> Result<&'a [u8], DecodeError> {
+        let (&tag, rest) = input.split_first().ok_or(DecodeError::UnexpectedEof)?;
+        match tag {
+            ATOM_EXT | ATOM_UTF8_EXT | SMALL_ATOM_EXT | SMALL_ATOM_UTF8_EXT | ATOM_CACHE_REF => {
+                Ok(self.atom(rest, tag)?.0)
+            }
+            _ => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+
+    fn compressed<'a>(
+        &mut self,
+        input: &'a [u8],
+        depth: usize,
+    ) -> Result<(&'a [u8], usize), DecodeError> {
+        self.summary.compressed = true;
+        if !self.checked {
+            let consumed = inflate_into(input, &mut io::sink())?;
+            return skip_bytes(input, consumed, 0);
+        }
+
+        if self.in_compressed {
+            return Err(DecodeError::InvalidFormat(
+                "compressed term inside a compressed term".to_string(),
+            ));
+        }
+        let (decompressed, consumed) = inflate(input)?;
+        // The compressed term replaces this one, so it starts at the same depth
+        self.summary.terms -= 1;
+        let outer_depth = self.depth_offset;
+        self.depth_offset = depth - 1;
+        self.in_compressed = true;
+        let walked = self.walk(&decompressed);
+        self.in_compressed = false;
+        self.depth_offset = outer_depth;
+        let rest = walked?;
+        if !rest.is_empty() {
+            return Err(DecodeError::TrailingData(rest.len()));
+        }
+        skip_bytes(input, consumed, 0)
+    }
+}
+
+fn check_limit(
+    size: usize,
+    max: usize,
+    error: impl FnOnce(usize, usize) -> DecodeError,
+) -> Result<(), DecodeError> {
+    if size > max {
+        Err(error(size, max))
+    } else {
+        Ok(())
+    }
+}
+
+fn skip_bytes(input: &[u8], len: usize, nested: usize) -> Result<(&[u8], usize), DecodeError> {
+    input
+        .get(len..)
+        .map(|rest| (rest, nested))
+        .ok_or(DecodeError::UnexpectedEof)
+}
+
+/// Reads a big-endian unsigned integer of `width` bytes.
+fn read_be(input: &[u8], width: usize) -> Result<(&[u8], usize), DecodeError> {
+    if input.len() < width {
+        return Err(DecodeError::UnexpectedEof);
+    }
+    let (bytes, rest) = input.split_at(width);
+    let value = bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+    Ok((rest, value))
+}
+
+/// Decodes only the sub-term of a versioned term found by following `path`, skipping
+/// sibling terms without decoding them. Returns `None` if the path does not exist.
+///
+/// Numeric segments are 1-based positions in tuples and lists, like `element/2` and
+/// `lists:nth/2`. Other segments select a map value or a `{Key, Value}` proplist entry
+/// by key, which can be an atom, a binary, a string or an integer.
+///
+/// ```
+/// use erltf::{decode_path, encode, erl_atom, erl_int, erl_list, erl_tuple};
+///
+/// let term = erl_tuple!(
+///     erl_atom!("request"),
+///     erl_list![erl_tuple!(erl_atom!("timeout"), erl_int!(5000))]
+/// );
+/// let data = encode(&term).unwrap();
+/// assert_eq!(decode_path(&data, &["2", "timeout"]).unwrap(), Some(erl_int!(5000)));
+/// ```
+pub fn decode_path(data: &[u8], path: &[&str]) -> Result<Option<OwnedTerm>, DecodeError> {
+    let (&version, input) = data.split_first().ok_or(DecodeError::UnexpectedEof)?;
+    if version != VERSION {
+        return Err(DecodeError::InvalidVersion {
+            expected: VERSION,
+            actual: version,
+        });
+    }
+    let cache = AtomCache::new();
+    let ctx = DecodeContext::new(&cache);
+    find_path(input, path, &ctx)
+}
+
+/// Returns the buffer holding the sub-term of a versioned term found by following `path`,
+/// and the offset of that sub-term in it. The buffer is only copied for compressed terms.
+#[allow(clippy::type_complexity)]
+pub(crate) fn locate<'a>(
+    data: &'a [u8],
+    path: &[&str],
+) -> Result<Option<(Cow<'a, [u8]>, usize)>, DecodeError> {
+    let (&version, _) = data.split_first().ok_or(DecodeError::UnexpectedEof)?;
+    if version != VERSION {
+        return Err(DecodeError::InvalidVersion {
+            expected: VERSION,
+            actual: version,
+        });
+    }
+    let cache = AtomCache::new();
+    let ctx = DecodeContext::new(&cache);
+    let mut buf = Cow::Borrowed(data);
+    let mut offset = 1;
+    let mut segments = path.iter();
+    loop {
+        if let Some((&COMPRESSED_EXT, rest)) = buf[offset..].split_first() {
+            let (decompressed, _) = inflate(rest)?;
+            buf = Cow::Owned(decompressed);
+            offset = 0;
+            continue;
+        }
+        let Some(segment) = segments.next() else {
+            return Ok(Some((buf, offset)));
+        };
+        match select_child(&buf[offset..], segment, &ctx)? {
+            Some(child) => offset = buf.len() - child.len(),
+            None => return Ok(None),
+        }
+    }
+}
+
+/// Decodes one unversioned term, returning it with the input that follows it.
+pub(crate) fn decode_next(input: &[u8]) -> Result<(OwnedTerm, &[u8]), DecodeError> {
+    let cache = AtomCache::new();
+    let ctx = DecodeContext::new(&cache);
+    let (rest, term) = parse_term(input, &ctx).map_err(|e| ctx.error(e))?;
+    Ok((term, rest))
+}
+
+/// Like [`decode_next`], borrowing binaries and atoms from `input`.
+pub(crate) fn decode_next_borrowed(input: &[u8]) -> Result<(BorrowedTerm<'_>, &[u8]), DecodeError> {
+    let mut ctx = ParsingContext::new();
+    let (rest, term) = parse_term_borrowed(input, input.len(), &mut ctx).map_err(from_nom_error)?;
+    Ok((term, rest))
+}
+
+fn find_path(
+    mut input: &[u8],
+    path: &[&str],
+    ctx: &DecodeContext<'_>,
+) -> Result<Option<OwnedTerm>, DecodeError> {
+    for (i, segment) in path.iter().enumerate() {
+        if let Some((&COMPRESSED_EXT, rest)) = input.split_first() {
+            let (decompressed, _) = inflate(rest)?;
+            return find_path(&decompressed, &path[i..], ctx);
+        }
+        match select_child(input, segment, ctx)? {
+            Some(child) => input = child,
+            None => return Ok(None),
+        }
+    }
+    let (_, term) = parse_term(input, ctx).map_err(|e| ctx.error(e))?;
+    Ok(Some(term))
+}
+
+/// Returns the input starting at the child of the term at `input` selected by `segment`.
+fn select_child<'a>(
+    input: &'a [u8],
+    segment: &str,
+    ctx: &DecodeContext<'_>,
+) -> Result<Option<&'a [u8]>, DecodeError> {
+    let position = segment.parse::<usize>().ok().filter(|&p| p > 0);
+    let (&tag, rest) = input.split_first().ok_or(DecodeError::UnexpectedEof)?;
+    match tag {
+        SMALL_TUPLE_EXT | LARGE_TUPLE_EXT => {
+            let (rest, arity) = read_be(rest, if tag == SMALL_TUPLE_EXT { 1 } else { 4 })?;
+            match position.filter(|&p| p <= arity) {
+                Some(p) => skip_siblings(rest, p - 1).map(Some),
+                None => Ok(None),
+            }
+        }
+        LIST_EXT => {
+            let (mut rest, len) = read_be(rest, 4)?;
+            if let Some(p) = position {
+                return if p <= len {
+                    skip_siblings(rest, p - 1).map(Some)
+                } else {
+                    Ok(None)
+                };
+            }
+            for _ in 0..len {
+                if let Some(value) = proplist_value(rest, segment, ctx)? {
+                    return Ok(Some(value));
+                }
+                rest = skip_term(rest)?;
+            }
+            Ok(None)
+        }
+        MAP_EXT => {
+            let (mut rest, pairs) = read_be(rest, 4)?;
+            for _ in 0..pairs {
+                let (value, key) = parse_term(rest, ctx).map_err(|e| ctx.error(e))?;
+                if key_matches(&key, segment) {
+                    return Ok(Some(value));
+                }
+                rest = skip_term(value)?;
+            }
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Returns the value of a `{Key, Value}` element if its key matches `segment`.
+fn proplist_value<'a>(
+    input: &'a [u8],
+    segment: &str,
+    ctx: &DecodeContext<'_>,
+) -> Result<Option<&'a [u8]>, DecodeError> {
+    let Some(([SMALL_TUPLE_EXT, 2], rest)) = input.split_first_chunk::<2>() else {
+        return Ok(None);
+    };
+    let (value, key) = parse_term(rest, ctx).map_err(|e| ctx.error(e))?;
+    Ok(key_matches(&key, segment).then_some(value))
+}
+
+fn skip_siblings(mut input: &[u8], count: usize) -> Result<&[u8], DecodeError> {
+    for _ in 0..count {
+        input = skip_term(input)?;
+    }
+    Ok(input)
+}
+
+fn key_matches(key: &OwnedTerm, segment: &str) -> bool {
+    match key {
+        OwnedTerm::Atom(atom) => atom.as_str() == segment,
+        OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) => {
+            key.as_binary() == Some(segment.as_bytes())
+        }
+        OwnedTerm::String(s) => s == segment,
+        OwnedTerm::Integer(i) => segment.parse::<i64>() == Ok(*i),
+        _ => false,
+    }
+}
+
+/// Inflates the body of a `COMPRESSED_EXT` term, returning the decompressed bytes and
+/// the number of input bytes consumed.
+fn inflate(input: &[u8]) -> Result<(Vec<u8>, usize), DecodeError> {
+    let mut decompressed = Vec::new();
+    let consumed = inflate_into(input, &mut decompressed)?;
+    Ok((decompressed, consumed))
+}
+
+/// Inflates the body of a `COMPRESSED_EXT` term into `out`, reading at most one byte
+/// past the declared uncompressed size, and returns the number of input bytes consumed.
+fn inflate_into(input: &[u8], out: &mut impl io::Write) -> Result<usize, DecodeError> {
+    let (rest, uncompressed_size) = read_be(input, 4)?;
+    if uncompressed_size > MAX_BINARY_SIZE {
+        return Err(DecodeError::BinaryTooLarge {
+            size: uncompressed_size,
+            max: MAX_BINARY_SIZE,
+        });
+    }
+    let mut decoder = ZlibDecoder::new(rest);
+    let inflated = io::copy(
+        &mut decoder.by_ref().take(uncompressed_size as u64 + 1),
+        out,
+    )
+    .map_err(|e| DecodeError::InvalidFormat(format!("invalid zlib data: {e}")))?;
+    if inflated != uncompressed_size as u64 {
+        return Err(DecodeError::InvalidFormat(format!(
+            "compressed term size mismatch: declared {uncompressed_size}, got {inflated} or more"
+        )));
+    }
+    Ok(4 + decoder.total_in() as usize)
+}
+
 fn from_nom_error(e: nom::Err<NomError<&[u8]>>) -> DecodeError {
     match e {
         nom::Err::Incomplete(_) => DecodeError::UnexpectedEof,
@@ -219,89 +1152,91 @@ fn from_nom_error(e: nom::Err<NomError<&[u8]>>) -> DecodeError {
     }
 }
 
-fn parse_versioned_term<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_versioned_term<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, version) = be_u8(input)?;
     if version != VERSION {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)));
     }
-    parse_term(input, cache)
+    parse_term(input, ctx)
 }
 
-fn parse_versioned_term_with_cache<'a>(
-    input: &'a [u8],
-    cache: &mut AtomCache,
-) -> NomResult<'a, OwnedTerm> {
+/// Consumes the version byte and an optional `DIST_HEADER`, updating the atom cache.
+fn parse_version_and_dist_header<'a>(input: &'a [u8], cache: &mut AtomCache) -> NomResult<'a, ()> {
     let (input, version) = be_u8(input)?;
     if version != VERSION {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)));
     }
 
-    let (input, tag) = be_u8(input)?;
-    if tag == DIST_HEADER {
-        parse_dist_header_with_cache(input, cache)
-    } else {
-        parse_term_from_tag(input, tag, cache)
+    match input.split_first() {
+        Some((&DIST_HEADER, rest)) => parse_dist_header_with_cache(rest, cache),
+        _ => Ok((input, ())),
     }
 }
 
-fn parse_term<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_term<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, tag) = be_u8(input)?;
-    parse_term_from_tag(input, tag, cache)
+    parse_term_from_tag(input, tag, ctx)
 }
 
 fn parse_term_from_tag<'a>(
     input: &'a [u8],
     tag: u8,
-    cache: &AtomCache,
+    ctx: &DecodeContext<'_>,
 ) -> NomResult<'a, OwnedTerm> {
     match tag {
         SMALL_INTEGER_EXT => parse_small_integer(input),
         INTEGER_EXT => parse_integer(input),
-        FLOAT_EXT => parse_old_float(input),
-        NEW_FLOAT_EXT => parse_new_float(input),
-        ATOM_EXT => parse_atom_latin1(input),
-        ATOM_UTF8_EXT => parse_atom_utf8(input),
-        SMALL_ATOM_UTF8_EXT => parse_small_atom_utf8(input),
-        SMALL_ATOM_EXT => parse_small_atom_latin1(input),
-        SMALL_TUPLE_EXT => parse_small_tuple(input, cache),
-        LARGE_TUPLE_EXT => parse_large_tuple(input, cache),
+        FLOAT_EXT => parse_old_float(input, ctx),
+        NEW_FLOAT_EXT => parse_new_float(input, ctx),
+        ATOM_EXT => parse_atom_latin1(input, ctx),
+        ATOM_UTF8_EXT => parse_atom_utf8(input, ctx),
+        SMALL_ATOM_UTF8_EXT => parse_small_atom_utf8(input, ctx),
+        SMALL_ATOM_EXT => parse_small_atom_latin1(input, ctx),
+        SMALL_TUPLE_EXT => parse_small_tuple(input, ctx),
+        LARGE_TUPLE_EXT => parse_large_tuple(input, ctx),
         NIL_EXT => Ok((input, OwnedTerm::Nil)),
-        STRING_EXT => parse_string_ext(input),
-        LIST_EXT => parse_list(input, cache),
-        BINARY_EXT => parse_binary(input),
+        STRING_EXT => parse_string_ext(input, ctx),
+        LIST_EXT => parse_list(input, ctx),
+        BINARY_EXT => parse_binary(input, ctx),
         BIT_BINARY_EXT => parse_bit_binary(input),
         SMALL_BIG_EXT => parse_small_big(input),
         LARGE_BIG_EXT => parse_large_big(input),
-        MAP_EXT => parse_map(input, cache),
-        NEW_PID_EXT => parse_new_pid(input, cache),
-        NEWER_REFERENCE_EXT => parse_newer_reference(input, cache),
-        V4_PORT_EXT => parse_v4_port(input, cache),
-        EXPORT_EXT => parse_export_ext(input, cache),
-        NEW_FUN_EXT => parse_new_fun_ext(input, cache),
+        MAP_EXT => parse_map(input, ctx),
+        NEW_PID_EXT => parse_new_pid(input, ctx),
+        NEWER_REFERENCE_EXT => parse_newer_reference(input, ctx),
+        V4_PORT_EXT => parse_v4_port(input, ctx),
+        NEW_PORT_EXT => parse_new_port(input, ctx),
+        EXPORT_EXT | NEW_FUN_EXT if ctx.config.safe => Err(ctx.fail(
+            input,
+            DecodeError::UnsafeTerm("funs are not allowed in safe mode".to_string()),
+        )),
+        EXPORT_EXT => parse_export_ext(input, ctx),
+        NEW_FUN_EXT => parse_new_fun_ext(input, ctx),
         DIST_HEADER => {
             log::error!("DIST_HEADER should not appear nested in terms");
             Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)))
         }
-        COMPRESSED_EXT => parse_compressed(input, cache),
-        REFERENCE_EXT => parse_reference_ext(input, cache),
-        PORT_EXT => parse_port_ext(input, cache),
-        PID_EXT => parse_pid_ext(input, cache),
-        NEW_REFERENCE_EXT => parse_new_reference_ext(input, cache),
-        LOCAL_EXT => parse_local_ext(input, cache),
+        COMPRESSED_EXT => parse_compressed(input, ctx),
+        REFERENCE_EXT => parse_reference_ext(input, ctx),
+        PORT_EXT => parse_port_ext(input, ctx),
+        PID_EXT => parse_pid_ext(input, ctx),
+        NEW_REFERENCE_EXT => parse_new_reference_ext(input, ctx),
+        LOCAL_EXT => parse_local_ext(input, ctx),
         ATOM_CACHE_REF => {
             let (input, cache_index) = be_u8(input)?;
-            if let Some(atom) = cache.get(cache_index) {
+            if let Some(atom) = ctx.cache.get(cache_index) {
                 log::debug!(
                     "Found ATOM_CACHE_REF index {} -> '{}'",
                     cache_index,
                     atom.as_str()
                 );
-                Ok((input, OwnedTerm::Atom(atom.clone())))
+                let term = admit_atom(input, atom.as_str(), ctx)?;
+                Ok((input, term))
             } else {
                 log::error!(
                     "ATOM_CACHE_REF index {} not found in cache (cache size: {})",
                     cache_index,
-                    cache.len()
+                    ctx.cache.len()
                 );
                 Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)))
             }
@@ -313,7 +1248,7 @@ fn parse_term_from_tag<'a>(
     }
 }
 
-fn parse_compressed<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_compressed<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (rest, uncompressed_size) = be_u32(input)?;
 
     if uncompressed_size as usize > MAX_BINARY_SIZE {
@@ -327,7 +1262,7 @@ fn parse_compressed<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, Own
         .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Fail)))?;
     let consumed = decoder.total_in() as usize;
 
-    let owned_term = match parse_term(&decompressed, cache) {
+    let owned_term = match parse_term(&decompressed, ctx) {
         Ok((_remaining, term)) => term,
         Err(_) => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Fail))),
     };
@@ -335,13 +1270,19 @@ fn parse_compressed<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, Own
     Ok((&rest[consumed..], owned_term))
 }
 
-fn parse_reference_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, node_term) = parse_term(input, cache)?;
-    let node = if let OwnedTerm::Atom(atom) = node_term {
-        atom
-    } else {
-        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)));
-    };
+/// Parses the node of a pid, port or reference.
+fn parse_node<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, Atom> {
+    ctx.parsing_node.set(true);
+    let parsed = parse_term(input, ctx);
+    ctx.parsing_node.set(false);
+    match parsed? {
+        (rest, OwnedTerm::Atom(atom)) => Ok((rest, atom)),
+        _ => Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
+    }
+}
+
+fn parse_reference_ext<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node) = parse_node(input, ctx)?;
     let (input, id) = be_u32(input)?;
     let (input, creation) = be_u8(input)?;
     Ok((
@@ -350,13 +1291,8 @@ fn parse_reference_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a,
     ))
 }
 
-fn parse_port_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, node_term) = parse_term(input, cache)?;
-    let node = if let OwnedTerm::Atom(atom) = node_term {
-        atom
-    } else {
-        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)));
-    };
+fn parse_port_ext<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node) = parse_node(input, ctx)?;
     let (input, id) = be_u32(input)?;
     let (input, creation) = be_u8(input)?;
     Ok((
@@ -365,13 +1301,8 @@ fn parse_port_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, Owned
     ))
 }
 
-fn parse_pid_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, node_term) = parse_term(input, cache)?;
-    let node = if let OwnedTerm::Atom(atom) = node_term {
-        atom
-    } else {
-        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)));
-    };
+fn parse_pid_ext<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node) = parse_node(input, ctx)?;
     let (input, id) = be_u32(input)?;
     let (input, serial) = be_u32(input)?;
     let (input, creation) = be_u8(input)?;
@@ -381,14 +1312,12 @@ fn parse_pid_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedT
     ))
 }
 
-fn parse_new_reference_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_new_reference_ext<'a>(
+    input: &'a [u8],
+    ctx: &DecodeContext<'_>,
+) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u16(input)?;
-    let (input, node_term) = parse_term(input, cache)?;
-    let node = if let OwnedTerm::Atom(atom) = node_term {
-        atom
-    } else {
-        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)));
-    };
+    let (input, node) = parse_node(input, ctx)?;
     let (input, creation) = be_u8(input)?;
     let mut ids = Vec::with_capacity(len as usize);
     let mut remaining = input;
@@ -403,11 +1332,11 @@ fn parse_new_reference_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<
     ))
 }
 
-fn parse_local_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_local_ext<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     // Record the start position to capture the entire LOCAL_EXT encoding
     let start = input;
     let (input, _hash) = be_u64(input)?;
-    let (remaining, term) = parse_term(input, cache)?;
+    let (remaining, term) = parse_term(input, ctx)?;
 
     // Calculate how many bytes the nested term consumed
     let nested_len = input.len() - remaining.len();
@@ -454,7 +1383,7 @@ fn parse_integer(input: &[u8]) -> NomResult<'_, OwnedTerm> {
     Ok((input, OwnedTerm::Integer(value as i64)))
 }
 
-fn parse_old_float(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_old_float<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, bytes) = take(31usize)(input)?;
     let s = str::from_utf8(bytes)
         .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Char)))?;
@@ -462,26 +1391,46 @@ fn parse_old_float(input: &[u8]) -> NomResult<'_, OwnedTerm> {
         .trim_end_matches('\0')
         .parse::<f64>()
         .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Float)))?;
-    Ok((input, OwnedTerm::Float(value)))
+    finite_float(input, value, ctx)
 }
 
-fn parse_new_float(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_new_float<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, value) = be_f64(input)?;
+    finite_float(input, value, ctx)
+}
+
+/// Erlang never produces NaN or infinities, so these can only come from a broken
+/// or hostile peer.
+fn finite_float<'a>(
+    input: &'a [u8],
+    value: f64,
+    ctx: &DecodeContext<'_>,
+) -> NomResult<'a, OwnedTerm> {
+    if !value.is_finite() {
+        return Err(ctx.fail(input, DecodeError::NonFiniteFloat(value)));
+    }
     Ok((input, OwnedTerm::Float(value)))
 }
 
-fn parse_atom_latin1(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+/// `ATOM_EXT` and `SMALL_ATOM_EXT` text is Latin-1: every byte is one character.
+fn latin1(bytes: &[u8]) -> Cow<'_, str> {
+    match str::from_utf8(bytes) {
+        Ok(ascii) if bytes.is_ascii() => Cow::Borrowed(ascii),
+        _ => Cow::Owned(bytes.iter().map(|&b| char::from(b)).collect()),
+    }
+}
+
+fn parse_atom_latin1<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u16(input)?;
     if len as usize > MAX_ATOM_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let (input, bytes) = take(len as usize)(input)?;
-    let name = str::from_utf8(bytes)
-        .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Char)))?;
-    Ok((input, OwnedTerm::Atom(Atom::new(name))))
+    let term = admit_atom(input, &latin1(bytes), ctx)?;
+    Ok((input, term))
 }
 
-fn parse_atom_utf8(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_atom_utf8<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u16(input)?;
     if len as usize > MAX_ATOM_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
@@ -489,10 +1438,11 @@ fn parse_atom_utf8(input: &[u8]) -> NomResult<'_, OwnedTerm> {
     let (input, bytes) = take(len as usize)(input)?;
     let name = str::from_utf8(bytes)
         .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Char)))?;
-    Ok((input, OwnedTerm::Atom(Atom::new(name))))
+    let term = admit_atom(input, name, ctx)?;
+    Ok((input, term))
 }
 
-fn parse_small_atom_utf8(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_small_atom_utf8<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u8(input)?;
     if len as usize > MAX_ATOM_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
@@ -500,35 +1450,35 @@ fn parse_small_atom_utf8(input: &[u8]) -> NomResult<'_, OwnedTerm> {
     let (input, bytes) = take(len as usize)(input)?;
     let name = str::from_utf8(bytes)
         .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Char)))?;
-    Ok((input, OwnedTerm::Atom(Atom::new(name))))
+    let term = admit_atom(input, name, ctx)?;
+    Ok((input, term))
 }
 
-fn parse_small_atom_latin1(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_small_atom_latin1<'a>(
+    input: &'a [u8],
+    ctx: &DecodeContext<'_>,
+) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u8(input)?;
     if len as usize > MAX_ATOM_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let (input, bytes) = take(len as usize)(input)?;
-    let name = str::from_utf8(bytes)
-        .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Char)))?;
-    Ok((input, OwnedTerm::Atom(Atom::new(name))))
+    let term = admit_atom(input, &latin1(bytes), ctx)?;
+    Ok((input, term))
 }
 
-fn parse_dist_header_with_cache<'a>(
-    input: &'a [u8],
-    cache: &mut AtomCache,
-) -> NomResult<'a, OwnedTerm> {
+fn parse_dist_header_with_cache<'a>(input: &'a [u8], cache: &mut AtomCache) -> NomResult<'a, ()> {
     let (input, num_atom_cache_refs) = be_u8(input)?;
+    cache.atoms.clear();
 
     if num_atom_cache_refs == 0 {
-        return parse_term(input, cache);
+        return Ok((input, ()));
     }
 
     let flags_len = (num_atom_cache_refs as usize) / 2 + 1;
     let (mut input, flags) = take(flags_len)(input)?;
 
-    let long_atoms_flag_byte = flags[flags_len - 1];
-    let long_atoms = (long_atoms_flag_byte & 0x01) != 0;
+    let long_atoms = dist_header_long_atoms(flags, num_atom_cache_refs as usize);
 
     for i in 0..num_atom_cache_refs {
         let (new_input, internal_segment_index) = be_u8(input)?;
@@ -542,6 +1492,7 @@ fn parse_dist_header_with_cache<'a>(
         };
 
         let is_new_entry = (flag_nibble & 0x08) != 0;
+        let cache_index = (u16::from(flag_nibble & 0x07) << 8) | u16::from(internal_segment_index);
 
         if is_new_entry {
             let (new_input, atom_len) = if long_atoms {
@@ -560,26 +1511,34 @@ fn parse_dist_header_with_cache<'a>(
             log::debug!(
                 "Inserting atom '{}' at cache index {}",
                 atom_str,
-                internal_segment_index
+                cache_index
             );
-            cache.insert(internal_segment_index, Atom::new(atom_str));
+            let atom = Atom::new(atom_str);
+            cache.insert_entry(cache_index, atom.clone());
+            cache.insert(i, atom);
             input = new_input;
+        } else if let Some(atom) = cache.entry(cache_index) {
+            let atom = atom.clone();
+            cache.insert(i, atom);
+        } else {
+            log::error!("Atom cache index {} has no entry", cache_index);
+            return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)));
         }
     }
 
-    parse_term(input, cache)
+    Ok((input, ()))
 }
 
-fn parse_small_tuple<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_small_tuple<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, arity) = be_u8(input)?;
     if arity as usize > MAX_TUPLE_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let mut remaining = input;
-    let mut elements = Vec::with_capacity(arity as usize);
+    let mut elements = Vec::with_capacity(preallocation(arity as usize, remaining));
 
     for _ in 0..arity {
-        let (new_remaining, term) = parse_term(remaining, cache)?;
+        let (new_remaining, term) = parse_term(remaining, ctx)?;
         elements.push(term);
         remaining = new_remaining;
     }
@@ -587,16 +1546,16 @@ fn parse_small_tuple<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, Ow
     Ok((remaining, OwnedTerm::Tuple(elements)))
 }
 
-fn parse_large_tuple<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_large_tuple<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, arity) = be_u32(input)?;
     if arity as usize > MAX_TUPLE_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let mut remaining = input;
-    let mut elements = Vec::with_capacity(arity as usize);
+    let mut elements = Vec::with_capacity(preallocation(arity as usize, remaining));
 
     for _ in 0..arity {
-        let (new_remaining, term) = parse_term(remaining, cache)?;
+        let (new_remaining, term) = parse_term(remaining, ctx)?;
         elements.push(term);
         remaining = new_remaining;
     }
@@ -604,9 +1563,12 @@ fn parse_large_tuple<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, Ow
     Ok((remaining, OwnedTerm::Tuple(elements)))
 }
 
-fn parse_string_ext(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_string_ext<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u16(input)?;
     let (input, bytes) = take(len as usize)(input)?;
+    if ctx.config.byte_lists {
+        return Ok((input, OwnedTerm::ByteList(bytes.to_vec())));
+    }
     let elements: Vec<OwnedTerm> = bytes
         .iter()
         .map(|&b| OwnedTerm::Integer(b as i64))
@@ -614,21 +1576,21 @@ fn parse_string_ext(input: &[u8]) -> NomResult<'_, OwnedTerm> {
     Ok((input, OwnedTerm::List(elements)))
 }
 
-fn parse_list<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_list<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u32(input)?;
     if len as usize > MAX_LIST_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let mut remaining = input;
-    let mut elements = Vec::with_capacity(len as usize);
+    let mut elements = Vec::with_capacity(preallocation(len as usize, remaining));
 
     for _ in 0..len {
-        let (new_remaining, term) = parse_term(remaining, cache)?;
+        let (new_remaining, term) = parse_term(remaining, ctx)?;
         elements.push(term);
         remaining = new_remaining;
     }
 
-    let (remaining, tail) = parse_term(remaining, cache)?;
+    let (remaining, tail) = parse_term(remaining, ctx)?;
 
     if tail == OwnedTerm::Nil {
         Ok((remaining, OwnedTerm::List(elements)))
@@ -643,12 +1605,15 @@ fn parse_list<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm
     }
 }
 
-fn parse_binary(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_binary<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u32(input)?;
     if len as usize > MAX_BINARY_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let (input, data) = take(len as usize)(input)?;
+    if let Some(shared) = ctx.share(data) {
+        return Ok((input, OwnedTerm::SharedBinary(shared)));
+    }
     Ok((input, OwnedTerm::Binary(data.to_vec())))
 }
 
@@ -694,30 +1659,35 @@ fn parse_large_big(input: &[u8]) -> NomResult<'_, OwnedTerm> {
     ))
 }
 
-fn parse_map<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_map<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, arity) = be_u32(input)?;
     if arity as usize > MAX_MAP_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let mut remaining = input;
     let mut map = BTreeMap::new();
+    let mut entries = Vec::new();
 
     for _ in 0..arity {
-        let (new_remaining, key) = parse_term(remaining, cache)?;
-        let (new_remaining, value) = parse_term(new_remaining, cache)?;
-        map.insert(key, value);
+        let (new_remaining, key) = parse_term(remaining, ctx)?;
+        let (new_remaining, value) = parse_term(new_remaining, ctx)?;
+        if ctx.config.ordered_maps {
+            entries.push((key, value));
+        } else {
+            map.insert(key, value);
+        }
         remaining = new_remaining;
     }
 
-    Ok((remaining, OwnedTerm::Map(map)))
+    if ctx.config.ordered_maps {
+        Ok((remaining, OwnedTerm::OrderedMap(entries)))
+    } else {
+        Ok((remaining, OwnedTerm::Map(map)))
+    }
 }
 
-fn parse_new_pid<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, node_term) = parse_term(input, cache)?;
-    let node = match node_term {
-        OwnedTerm::Atom(a) => a,
-        _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
-    };
+fn parse_new_pid<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node) = parse_node(input, ctx)?;
 
     let (input, id) = be_u32(input)?;
     let (input, serial) = be_u32(input)?;
@@ -731,13 +1701,9 @@ fn parse_new_pid<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedT
     ))
 }
 
-fn parse_newer_reference<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_newer_reference<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u16(input)?;
-    let (input, node_term) = parse_term(input, cache)?;
-    let node = match node_term {
-        OwnedTerm::Atom(a) => a,
-        _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
-    };
+    let (input, node) = parse_node(input, ctx)?;
 
     let (input, creation) = be_u32(input)?;
 
@@ -755,12 +1721,8 @@ fn parse_newer_reference<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a
     ))
 }
 
-fn parse_v4_port<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, node_term) = parse_term(input, cache)?;
-    let node = match node_term {
-        OwnedTerm::Atom(a) => a,
-        _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
-    };
+fn parse_v4_port<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node) = parse_node(input, ctx)?;
 
     let (input, id) = be_u64(input)?;
     let (input, creation) = be_u32(input)?;
@@ -771,20 +1733,32 @@ fn parse_v4_port<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedT
     ))
 }
 
-fn parse_export_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, module_term) = parse_term(input, cache)?;
+fn parse_new_port<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node) = parse_node(input, ctx)?;
+
+    let (input, id) = be_u32(input)?;
+    let (input, creation) = be_u32(input)?;
+
+    Ok((
+        input,
+        OwnedTerm::Port(ExternalPort::new(node, id as u64, creation)),
+    ))
+}
+
+fn parse_export_ext<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, module_term) = parse_term(input, ctx)?;
     let module = match module_term {
         OwnedTerm::Atom(a) => a,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, function_term) = parse_term(input, cache)?;
+    let (input, function_term) = parse_term(input, ctx)?;
     let function = match function_term {
         OwnedTerm::Atom(a) => a,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, arity_term) = parse_term(input, cache)?;
+    let (input, arity_term) = parse_term(input, ctx)?;
     let arity = match arity_term {
         OwnedTerm::Integer(i) if (0..=255).contains(&i) => i as u8,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
@@ -796,41 +1770,41 @@ fn parse_export_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, Own
     ))
 }
 
-fn parse_new_fun_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_new_fun_ext<'a>(input: &'a [u8], ctx: &DecodeContext<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, _size) = be_u32(input)?;
     let (input, arity) = be_u8(input)?;
     let (input, uniq) = take(16usize)(input)?;
     let (input, index) = be_u32(input)?;
     let (input, num_free) = be_u32(input)?;
 
-    let (input, module_term) = parse_term(input, cache)?;
+    let (input, module_term) = parse_term(input, ctx)?;
     let module = match module_term {
         OwnedTerm::Atom(a) => a,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, old_index_term) = parse_term(input, cache)?;
+    let (input, old_index_term) = parse_term(input, ctx)?;
     let old_index = match old_index_term {
         OwnedTerm::Integer(i) if i >= 0 => i as u32,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, old_uniq_term) = parse_term(input, cache)?;
+    let (input, old_uniq_term) = parse_term(input, ctx)?;
     let old_uniq = match old_uniq_term {
         OwnedTerm::Integer(i) if i >= 0 => i as u32,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, pid_term) = parse_term(input, cache)?;
+    let (input, pid_term) = parse_term(input, ctx)?;
     let pid = match pid_term {
         OwnedTerm::Pid(p) => p,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
     let mut remaining = input;
-    let mut free_vars = Vec::with_capacity(num_free as usize);
+    let mut free_vars = Vec::with_capacity(preallocation(num_free as usize, remaining));
     for _ in 0..num_free {
-        let (new_remaining, term) = parse_term(remaining, cache)?;
+        let (new_remaining, term) = parse_term(remaining, ctx)?;
         free_vars.push(term);
         remaining = new_remaining;
     }
@@ -851,11 +1825,12 @@ pub fn decode_borrowed(data: &[u8]) -> Result<BorrowedTerm<'_>, ContextualDecode
     let mut ctx = ParsingContext::new();
 
     let (remaining, term) = parse_versioned_term_borrowed(data, original_len, &mut ctx)
-        .map_err(|e| ContextualDecodeError::new(from_nom_error(e), ctx.clone()))?;
+        .map_err(|e| contextual_error(data, from_nom_error(e), ctx.clone()))?;
 
     if !remaining.is_empty() {
         ctx.byte_offset = original_len - remaining.len();
-        return Err(ContextualDecodeError::new(
+        return Err(contextual_error(
+            data,
             DecodeError::TrailingData(remaining.len()),
             ctx,
         ));
@@ -864,6 +1839,22 @@ pub fn decode_borrowed(data: &[u8]) -> Result<BorrowedTerm<'_>, ContextualDecode
     Ok(term)
 }
 
+/// Attaches the input around the error offset, and reports the actual byte found there
+/// when nom only told us that a tag did not match.
+fn contextual_error(data: &[u8], error: DecodeError, ctx: ParsingContext) -> ContextualDecodeError {
+    let found = data.get(ctx.byte_offset).copied();
+    let error = match (error, found) {
+        (DecodeError::InvalidVersion { expected, .. }, Some(actual)) if ctx.byte_offset == 0 => {
+            DecodeError::InvalidVersion { expected, actual }
+        }
+        (DecodeError::InvalidVersion { .. }, Some(tag)) if tag_name(tag).is_none() => {
+            DecodeError::InvalidTag(tag)
+        }
+        (error, _) => error,
+    };
+    ContextualDecodeError::new(error, ctx).with_source(data, DEFAULT_SNIPPET_WINDOW)
+}
+
 fn parse_versioned_term_borrowed<'a>(
     input: &'a [u8],
     original_len: usize,
@@ -891,6 +1882,7 @@ fn parse_term_borrowed<'a>(
         FLOAT_EXT => parse_old_float_borrowed(input),
         NEW_FLOAT_EXT => parse_new_float_borrowed(input),
         ATOM_EXT => parse_atom_latin1_borrowed(input),
+        SMALL_ATOM_EXT => parse_small_atom_latin1_borrowed(input),
         ATOM_UTF8_EXT => parse_atom_utf8_borrowed(input),
         SMALL_ATOM_UTF8_EXT => parse_small_atom_utf8_borrowed(input),
         SMALL_TUPLE_EXT => parse_small_tuple_borrowed(input, original_len, ctx),
@@ -906,6 +1898,11 @@ fn parse_term_borrowed<'a>(
         NEW_PID_EXT => parse_new_pid_borrowed(input, original_len, ctx),
         NEWER_REFERENCE_EXT => parse_newer_reference_borrowed(input, original_len, ctx),
         V4_PORT_EXT => parse_v4_port_borrowed(input, original_len, ctx),
+        NEW_PORT_EXT => parse_new_port_borrowed(input, original_len, ctx),
+        PID_EXT => parse_pid_ext_borrowed(input, original_len, ctx),
+        PORT_EXT => parse_port_ext_borrowed(input, original_len, ctx),
+        REFERENCE_EXT => parse_reference_ext_borrowed(input, original_len, ctx),
+        NEW_REFERENCE_EXT => parse_new_reference_ext_borrowed(input, original_len, ctx),
         EXPORT_EXT => parse_export_ext_borrowed(input, original_len, ctx),
         NEW_FUN_EXT => parse_new_fun_ext_borrowed(input, original_len, ctx),
         _ => Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
@@ -930,11 +1927,18 @@ fn parse_old_float_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_>> {
         .trim_end_matches('\0')
         .parse::<f64>()
         .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Float)))?;
-    Ok((input, BorrowedTerm::Float(value)))
+    finite_float_borrowed(input, value)
 }
 
 fn parse_new_float_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_>> {
     let (input, value) = be_f64(input)?;
+    finite_float_borrowed(input, value)
+}
+
+fn finite_float_borrowed(input: &[u8], value: f64) -> NomResult<'_, BorrowedTerm<'_>> {
+    if !value.is_finite() {
+        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Float)));
+    }
     Ok((input, BorrowedTerm::Float(value)))
 }
 
@@ -944,9 +1948,13 @@ fn parse_atom_latin1_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_>> {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let (input, bytes) = take(len as usize)(input)?;
-    let name = str::from_utf8(bytes)
-        .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Char)))?;
-    Ok((input, BorrowedTerm::Atom(Cow::Borrowed(name))))
+    Ok((input, BorrowedTerm::Atom(latin1(bytes))))
+}
+
+fn parse_small_atom_latin1_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_>> {
+    let (input, len) = be_u8(input)?;
+    let (input, bytes) = take(len as usize)(input)?;
+    Ok((input, BorrowedTerm::Atom(latin1(bytes))))
 }
 
 fn parse_atom_utf8_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_>> {
@@ -981,7 +1989,7 @@ fn parse_small_tuple_borrowed<'a>(
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let mut remaining = input;
-    let mut elements = Vec::with_capacity(arity as usize);
+    let mut elements = Vec::with_capacity(preallocation(arity as usize, remaining));
 
     for i in 0..arity {
         ctx.push(PathSegment::TupleElement(i as usize));
@@ -1004,7 +2012,7 @@ fn parse_large_tuple_borrowed<'a>(
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let mut remaining = input;
-    let mut elements = Vec::with_capacity(arity as usize);
+    let mut elements = Vec::with_capacity(preallocation(arity as usize, remaining));
 
     for i in 0..arity {
         ctx.push(PathSegment::TupleElement(i as usize));
@@ -1037,7 +2045,7 @@ fn parse_list_borrowed<'a>(
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let mut remaining = input;
-    let mut elements = Vec::with_capacity(len as usize);
+    let mut elements = Vec::with_capacity(preallocation(len as usize, remaining));
 
     for i in 0..len {
         ctx.push(PathSegment::ListElement(i as usize));
@@ -1217,6 +2225,98 @@ fn parse_v4_port_borrowed<'a>(
     ))
 }
 
+fn parse_new_port_borrowed<'a>(
+    input: &'a [u8],
+    original_len: usize,
+    ctx: &mut ParsingContext,
+) -> NomResult<'a, BorrowedTerm<'a>> {
+    let (input, node) = parse_node_borrowed(input, original_len, ctx)?;
+    let (input, id) = be_u32(input)?;
+    let (input, creation) = be_u32(input)?;
+    Ok((
+        input,
+        BorrowedTerm::Port(ExternalPort::new(node, id as u64, creation)),
+    ))
+}
+
+fn parse_node_borrowed<'a>(
+    input: &'a [u8],
+    original_len: usize,
+    ctx: &mut ParsingContext,
+) -> NomResult<'a, Atom> {
+    let (input, node_term) = parse_term_borrowed(input, original_len, ctx)?;
+    match node_term {
+        BorrowedTerm::Atom(a) => Ok((input, Atom::new(a.as_ref()))),
+        _ => Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
+    }
+}
+
+fn parse_pid_ext_borrowed<'a>(
+    input: &'a [u8],
+    original_len: usize,
+    ctx: &mut ParsingContext,
+) -> NomResult<'a, BorrowedTerm<'a>> {
+    let (input, node) = parse_node_borrowed(input, original_len, ctx)?;
+    let (input, id) = be_u32(input)?;
+    let (input, serial) = be_u32(input)?;
+    let (input, creation) = be_u8(input)?;
+    Ok((
+        input,
+        BorrowedTerm::Pid(ExternalPid::new(node, id, serial, creation as u32)),
+    ))
+}
+
+fn parse_port_ext_borrowed<'a>(
+    input: &'a [u8],
+    original_len: usize,
+    ctx: &mut ParsingContext,
+) -> NomResult<'a, BorrowedTerm<'a>> {
+    let (input, node) = parse_node_borrowed(input, original_len, ctx)?;
+    let (input, id) = be_u32(input)?;
+    let (input, creation) = be_u8(input)?;
+    Ok((
+        input,
+        BorrowedTerm::Port(ExternalPort::new(node, id as u64, creation as u32)),
+    ))
+}
+
+fn parse_reference_ext_borrowed<'a>(
+    input: &'a [u8],
+    original_len: usize,
+    ctx: &mut ParsingContext,
+) -> NomResult<'a, BorrowedTerm<'a>> {
+    let (input, node) = parse_node_borrowed(input, original_len, ctx)?;
+    let (input, id) = be_u32(input)?;
+    let (input, creation) = be_u8(input)?;
+    Ok((
+        input,
+        BorrowedTerm::Reference(ExternalReference::new(node, creation as u32, vec![id])),
+    ))
+}
+
+fn parse_new_reference_ext_borrowed<'a>(
+    input: &'a [u8],
+    original_len: usize,
+    ctx: &mut ParsingContext,
+) -> NomResult<'a, BorrowedTerm<'a>> {
+    let (input, len) = be_u16(input)?;
+    let (input, node) = parse_node_borrowed(input, original_len, ctx)?;
+    let (input, creation) = be_u8(input)?;
+
+    let mut remaining = input;
+    let mut ids = Vec::with_capacity(len as usize);
+    for _ in 0..len {
+        let (new_remaining, id) = be_u32(remaining)?;
+        ids.push(id);
+        remaining = new_remaining;
+    }
+
+    Ok((
+        remaining,
+        BorrowedTerm::Reference(ExternalReference::new(node, creation as u32, ids)),
+    ))
+}
+
 fn parse_export_ext_borrowed<'a>(
     input: &'a [u8],
     original_len: usize,
@@ -1282,7 +2382,7 @@ fn parse_new_fun_ext_borrowed<'a>(
     };
 
     let mut remaining = input;
-    let mut free_vars = Vec::with_capacity(num_free as usize);
+    let mut free_vars = Vec::with_capacity(preallocation(num_free as usize, remaining));
     for i in 0..num_free {
         ctx.push(PathSegment::FunFreeVar(i as usize));
         let (new_remaining, term) = parse_term_borrowed(remaining, original_len, ctx)?;
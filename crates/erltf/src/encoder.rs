@@ -12,27 +12,163 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::borrowed::BorrowedTerm;
 use crate::errors::EncodeError;
 use crate::tags::{
     ATOM_CACHE_REF, ATOM_UTF8_EXT, BINARY_EXT, BIT_BINARY_EXT, DIST_HEADER, EXPORT_EXT,
     INTEGER_EXT, LARGE_BIG_EXT, LARGE_TUPLE_EXT, LIST_EXT, LOCAL_EXT, MAP_EXT, NEW_FLOAT_EXT,
-    NEW_FUN_EXT, NEW_PID_EXT, NEWER_REFERENCE_EXT, NIL_EXT, SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT,
-    SMALL_INTEGER_EXT, SMALL_TUPLE_EXT, V4_PORT_EXT, VERSION,
+    NEW_FUN_EXT, NEW_PID_EXT, NEW_REFERENCE_EXT, NEWER_REFERENCE_EXT, NIL_EXT, PID_EXT, PORT_EXT,
+    SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT, SMALL_INTEGER_EXT, SMALL_TUPLE_EXT, STRING_EXT,
+    V4_PORT_EXT, VERSION,
 };
 use crate::term::OwnedTerm;
 use crate::types::{
     Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun,
 };
 use bytes::{BufMut, BytesMut};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::Write;
+use std::sync::Arc;
+
+/// Selects the tags used for pids, ports and references.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EncodeMode {
+    /// `NEW_PID_EXT`, `V4_PORT_EXT` and `NEWER_REFERENCE_EXT` with 32-bit creations
+    #[default]
+    Current,
+    /// `PID_EXT`, `PORT_EXT` and `NEW_REFERENCE_EXT` with 8-bit creations, for peers
+    /// that do not support `DFLAG_BIG_CREATION`, such as older C nodes. Terms decoded
+    /// from `LOCAL_EXT` are re-encoded from their fields, since such peers predate it
+    Legacy,
+}
+
+/// What to do with NaN and infinities, which Erlang floats cannot represent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatPolicy {
+    /// Fail encoding with `EncodeError::NonFiniteFloat`
+    #[default]
+    Error,
+    /// Encode infinities as `f64::MAX` and `f64::MIN`. NaN still fails encoding
+    SaturateToMax,
+    /// Encode NaN, positive and negative infinity as the atoms `nan`, `infinity`
+    /// and `neg_infinity`
+    EncodeAsAtomTag,
+}
+
+/// The most characters an atom can have on the BEAM
+pub const MAX_ATOM_CHARACTERS: usize = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeConfig {
+    pub(crate) mode: EncodeMode,
+    pub(crate) float_policy: FloatPolicy,
+    pub(crate) max_atom_len: usize,
+}
+
+impl Default for EncodeConfig {
+    fn default() -> Self {
+        Self {
+            mode: EncodeMode::default(),
+            float_policy: FloatPolicy::default(),
+            max_atom_len: MAX_ATOM_CHARACTERS,
+        }
+    }
+}
+
+impl EncodeConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_mode(mut self, mode: EncodeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_float_policy(mut self, float_policy: FloatPolicy) -> Self {
+        self.float_policy = float_policy;
+        self
+    }
+
+    /// Fails encoding with `EncodeError::AtomTooLong` for atoms of more than `max_atom_len`
+    /// characters, [`MAX_ATOM_CHARACTERS`] by default. A higher limit only suits peers
+    /// other than Erlang and Elixir nodes, which reject such atoms.
+    pub fn with_max_atom_len(mut self, max_atom_len: usize) -> Self {
+        self.max_atom_len = max_atom_len;
+        self
+    }
+
+    #[must_use]
+    pub fn mode(&self) -> EncodeMode {
+        self.mode
+    }
+
+    #[must_use]
+    pub fn float_policy(&self) -> FloatPolicy {
+        self.float_policy
+    }
+
+    #[must_use]
+    pub fn max_atom_len(&self) -> usize {
+        self.max_atom_len
+    }
+}
+
+/// State shared by the encoding functions: the atom cache positions of the current
+/// distribution header, if any, the encode mode, the float policy and the atom length limit.
+#[derive(Clone, Copy)]
+struct EncodeContext<'a> {
+    cache: Option<&'a HashMap<&'a str, u8>>,
+    mode: EncodeMode,
+    float_policy: FloatPolicy,
+    max_atom_len: usize,
+}
+
+impl Default for EncodeContext<'_> {
+    fn default() -> Self {
+        Self {
+            cache: None,
+            mode: EncodeMode::default(),
+            float_policy: FloatPolicy::default(),
+            max_atom_len: MAX_ATOM_CHARACTERS,
+        }
+    }
+}
+
+impl<'a> EncodeContext<'a> {
+    fn from_config(config: &EncodeConfig) -> Self {
+        Self {
+            cache: None,
+            mode: config.mode,
+            float_policy: config.float_policy,
+            max_atom_len: config.max_atom_len,
+        }
+    }
+
+    fn with_cache(config: &EncodeConfig, cache: &'a HashMap<&'a str, u8>) -> Self {
+        Self {
+            cache: Some(cache),
+            ..Self::from_config(config)
+        }
+    }
+}
 
 pub fn encode(term: &OwnedTerm) -> Result<Vec<u8>, EncodeError> {
+    encode_with_config(term, &EncodeConfig::default())
+}
+
+/// Like [`encode`], with pids, ports and references encoded according to `mode`.
+pub fn encode_with_mode(term: &OwnedTerm, mode: EncodeMode) -> Result<Vec<u8>, EncodeError> {
+    encode_with_config(term, &EncodeConfig::new().with_mode(mode))
+}
+
+/// Like [`encode`], with the encode mode and float policy taken from `config`.
+pub fn encode_with_config(term: &OwnedTerm, config: &EncodeConfig) -> Result<Vec<u8>, EncodeError> {
     let estimated_size = term.estimated_encoded_size() + 1;
     let capacity = estimated_size.max(64);
     let mut buf = BytesMut::with_capacity(capacity);
     buf.put_u8(VERSION);
-    encode_term(&mut buf, term)?;
+    encode_term_impl(&mut buf, term, EncodeContext::from_config(config))?;
     Ok(buf.to_vec())
 }
 
@@ -42,57 +178,60 @@ pub fn encode_to_writer<W: Write>(term: &OwnedTerm, writer: &mut W) -> Result<()
     Ok(())
 }
 
-fn encode_term(buf: &mut BytesMut, term: &OwnedTerm) -> Result<(), EncodeError> {
-    encode_term_impl(buf, term, None)
-}
-
-fn encode_term_impl<'a>(
+fn encode_term_impl(
     buf: &mut BytesMut,
-    term: &'a OwnedTerm,
-    cache: Option<&HashMap<&'a Atom, u8>>,
+    term: &OwnedTerm,
+    ctx: EncodeContext<'_>,
 ) -> Result<(), EncodeError> {
     match term {
-        OwnedTerm::Atom(atom) => encode_atom_impl(buf, atom, cache),
+        OwnedTerm::Atom(atom) => encode_atom_impl(buf, atom.as_str(), ctx),
         OwnedTerm::Integer(i) => encode_integer(buf, *i),
-        OwnedTerm::Float(f) => encode_float(buf, *f),
+        OwnedTerm::Float(f) => encode_float(buf, *f, ctx),
         OwnedTerm::Binary(b) => encode_binary(buf, b),
+        OwnedTerm::SharedBinary(b) => encode_binary(buf, b),
         OwnedTerm::BitBinary { bytes, bits } => encode_bit_binary(buf, bytes, *bits),
         OwnedTerm::String(s) => encode_string(buf, s),
-        OwnedTerm::List(l) => encode_list_impl(buf, l, cache),
+        OwnedTerm::List(l) => encode_list_impl(buf, l, ctx),
+        OwnedTerm::ByteList(bytes) => encode_byte_list(buf, bytes),
         OwnedTerm::ImproperList { elements, tail } => {
-            encode_improper_list_impl(buf, elements, tail, cache)
+            encode_improper_list_impl(buf, elements, tail, ctx)
         }
-        OwnedTerm::Map(m) => encode_map_impl(buf, m, cache),
-        OwnedTerm::Tuple(t) => encode_tuple_impl(buf, t, cache),
-        OwnedTerm::Pid(pid) => encode_pid_impl(buf, pid, cache),
-        OwnedTerm::Port(port) => encode_port_impl(buf, port, cache),
-        OwnedTerm::Reference(ref_) => encode_reference_impl(buf, ref_, cache),
+        OwnedTerm::Map(m) => encode_map_impl(buf, m.len(), m.iter(), ctx),
+        OwnedTerm::OrderedMap(entries) => {
+            encode_map_impl(buf, entries.len(), entries.iter().map(|(k, v)| (k, v)), ctx)
+        }
+        OwnedTerm::Tuple(t) => encode_tuple_impl(buf, t, ctx),
+        OwnedTerm::Shared(shared) => encode_term_impl(buf, shared, ctx),
+        OwnedTerm::Pid(pid) => encode_pid_impl(buf, pid, ctx),
+        OwnedTerm::Port(port) => encode_port_impl(buf, port, ctx),
+        OwnedTerm::Reference(ref_) => encode_reference_impl(buf, ref_, ctx),
         OwnedTerm::BigInt(big) => encode_bigint(buf, big),
-        OwnedTerm::ExternalFun(fun) => encode_export_ext_impl(buf, fun, cache),
-        OwnedTerm::InternalFun(fun) => encode_new_fun_ext_impl(buf, fun, cache),
+        OwnedTerm::ExternalFun(fun) => encode_export_ext_impl(buf, fun, ctx),
+        OwnedTerm::InternalFun(fun) => encode_new_fun_ext_impl(buf, fun, ctx),
         OwnedTerm::Nil => encode_nil(buf),
     }
 }
 
-fn encode_atom_impl<'a>(
+fn encode_atom_impl(
     buf: &mut BytesMut,
-    atom: &'a Atom,
-    cache: Option<&HashMap<&'a Atom, u8>>,
+    name: &str,
+    ctx: EncodeContext<'_>,
 ) -> Result<(), EncodeError> {
-    if let Some(atom_index_map) = cache
-        && let Some(&cache_index) = atom_index_map.get(&atom)
+    if let Some(atom_index_map) = ctx.cache
+        && let Some(&cache_index) = atom_index_map.get(name)
     {
         buf.put_u8(ATOM_CACHE_REF);
         buf.put_u8(cache_index);
         return Ok(());
     }
 
-    let bytes = atom.name.as_bytes();
+    let bytes = name.as_bytes();
     let len = bytes.len();
 
     if len > u16::MAX as usize {
         return Err(EncodeError::AtomTooLarge { size: len });
     }
+    check_atom_length(name, ctx.max_atom_len)?;
 
     if len > 255 {
         buf.put_u8(ATOM_UTF8_EXT);
@@ -105,6 +244,17 @@ fn encode_atom_impl<'a>(
     Ok(())
 }
 
+/// A character takes at least a byte, so only atoms of more bytes than `max` need counting.
+fn check_atom_length(name: &str, max: usize) -> Result<(), EncodeError> {
+    if name.len() > max {
+        let length = name.chars().count();
+        if length > max {
+            return Err(EncodeError::AtomTooLong { length, max });
+        }
+    }
+    Ok(())
+}
+
 fn encode_integer(buf: &mut BytesMut, value: i64) -> Result<(), EncodeError> {
     if (0..=255).contains(&value) {
         buf.put_u8(SMALL_INTEGER_EXT);
@@ -140,7 +290,25 @@ fn encode_integer(buf: &mut BytesMut, value: i64) -> Result<(), EncodeError> {
     Ok(())
 }
 
-fn encode_float(buf: &mut BytesMut, value: f64) -> Result<(), EncodeError> {
+fn encode_float(buf: &mut BytesMut, value: f64, ctx: EncodeContext<'_>) -> Result<(), EncodeError> {
+    if value.is_finite() {
+        buf.put_u8(NEW_FLOAT_EXT);
+        buf.put_f64(value);
+        return Ok(());
+    }
+
+    let value = match (ctx.float_policy, value) {
+        (FloatPolicy::EncodeAsAtomTag, v) if v.is_nan() => {
+            return encode_atom_impl(buf, "nan", ctx);
+        }
+        (FloatPolicy::EncodeAsAtomTag, v) if v > 0.0 => {
+            return encode_atom_impl(buf, "infinity", ctx);
+        }
+        (FloatPolicy::EncodeAsAtomTag, _) => return encode_atom_impl(buf, "neg_infinity", ctx),
+        (FloatPolicy::SaturateToMax, v) if v == f64::INFINITY => f64::MAX,
+        (FloatPolicy::SaturateToMax, v) if v == f64::NEG_INFINITY => f64::MIN,
+        (_, v) => return Err(EncodeError::NonFiniteFloat { value: v }),
+    };
     buf.put_u8(NEW_FLOAT_EXT);
     buf.put_f64(value);
     Ok(())
@@ -169,15 +337,24 @@ fn encode_string(buf: &mut BytesMut, s: &str) -> Result<(), EncodeError> {
     encode_binary(buf, s.as_bytes())
 }
 
-fn encode_list_impl<'a>(
+fn encode_list_impl(
     buf: &mut BytesMut,
-    elements: &'a [OwnedTerm],
-    cache: Option<&HashMap<&'a Atom, u8>>,
+    elements: &[OwnedTerm],
+    ctx: EncodeContext<'_>,
 ) -> Result<(), EncodeError> {
     if elements.is_empty() {
         return encode_nil(buf);
     }
 
+    if let Ok(len) = u16::try_from(elements.len())
+        && elements.iter().all(|e| list_byte(e).is_some())
+    {
+        buf.put_u8(STRING_EXT);
+        buf.put_u16(len);
+        buf.extend(elements.iter().filter_map(list_byte));
+        return Ok(());
+    }
+
     let len = u32::try_from(elements.len()).map_err(|_| EncodeError::ListTooLarge {
         size: elements.len(),
     })?;
@@ -185,17 +362,48 @@ fn encode_list_impl<'a>(
     buf.put_u8(LIST_EXT);
     buf.put_u32(len);
     for elem in elements {
-        encode_term_impl(buf, elem, cache)?;
+        encode_term_impl(buf, elem, ctx)?;
     }
     encode_nil(buf)?;
     Ok(())
 }
 
-fn encode_improper_list_impl<'a>(
+/// Lists of integers in the 0..=255 range are encoded as `STRING_EXT`, like OTP does.
+fn list_byte(term: &OwnedTerm) -> Option<u8> {
+    match term {
+        OwnedTerm::Integer(i) => u8::try_from(*i).ok(),
+        _ => None,
+    }
+}
+
+fn encode_byte_list(buf: &mut BytesMut, bytes: &[u8]) -> Result<(), EncodeError> {
+    if bytes.is_empty() {
+        return encode_nil(buf);
+    }
+
+    if let Ok(len) = u16::try_from(bytes.len()) {
+        buf.put_u8(STRING_EXT);
+        buf.put_u16(len);
+        buf.put_slice(bytes);
+        return Ok(());
+    }
+
+    let len =
+        u32::try_from(bytes.len()).map_err(|_| EncodeError::ListTooLarge { size: bytes.len() })?;
+    buf.put_u8(LIST_EXT);
+    buf.put_u32(len);
+    for &b in bytes {
+        buf.put_u8(SMALL_INTEGER_EXT);
+        buf.put_u8(b);
+    }
+    encode_nil(buf)
+}
+
+fn encode_improper_list_impl(
     buf: &mut BytesMut,
-    elements: &'a [OwnedTerm],
-    tail: &'a OwnedTerm,
-    cache: Option<&HashMap<&'a Atom, u8>>,
+    elements: &[OwnedTerm],
+    tail: &OwnedTerm,
+    ctx: EncodeContext<'_>,
 ) -> Result<(), EncodeError> {
     let len = u32::try_from(elements.len()).map_err(|_| EncodeError::ListTooLarge {
         size: elements.len(),
@@ -204,25 +412,26 @@ fn encode_improper_list_impl<'a>(
     buf.put_u8(LIST_EXT);
     buf.put_u32(len);
     for elem in elements {
-        encode_term_impl(buf, elem, cache)?;
+        encode_term_impl(buf, elem, ctx)?;
     }
-    encode_term_impl(buf, tail, cache)?;
+    encode_term_impl(buf, tail, ctx)?;
     Ok(())
 }
 
-fn encode_map_impl<'a>(
+fn encode_map_impl<'t>(
     buf: &mut BytesMut,
-    map: &'a BTreeMap<OwnedTerm, OwnedTerm>,
-    cache: Option<&HashMap<&'a Atom, u8>>,
+    size: usize,
+    entries: impl Iterator<Item = (&'t OwnedTerm, &'t OwnedTerm)>,
+    ctx: EncodeContext<'_>,
 ) -> Result<(), EncodeError> {
-    let len = u32::try_from(map.len()).map_err(|_| EncodeError::MapTooLarge { size: map.len() })?;
+    let len = u32::try_from(size).map_err(|_| EncodeError::MapTooLarge { size })?;
 
     buf.put_u8(MAP_EXT);
     buf.put_u32(len);
 
-    for (key, value) in map.iter() {
-        encode_term_impl(buf, key, cache)?;
-        encode_term_impl(buf, value, cache)?;
+    for (key, value) in entries {
+        encode_term_impl(buf, key, ctx)?;
+        encode_term_impl(buf, value, ctx)?;
     }
     Ok(())
 }
@@ -230,7 +439,7 @@ fn encode_map_impl<'a>(
 fn encode_tuple_impl(
     buf: &mut BytesMut,
     elements: &[OwnedTerm],
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: EncodeContext<'_>,
 ) -> Result<(), EncodeError> {
     if elements.len() <= 255 {
         buf.put_u8(SMALL_TUPLE_EXT);
@@ -243,7 +452,7 @@ fn encode_tuple_impl(
         buf.put_u32(len);
     }
     for elem in elements {
-        encode_term_impl(buf, elem, cache)?;
+        encode_term_impl(buf, elem, ctx)?;
     }
     Ok(())
 }
@@ -251,16 +460,22 @@ fn encode_tuple_impl(
 fn encode_pid_impl(
     buf: &mut BytesMut,
     pid: &ExternalPid,
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: EncodeContext<'_>,
 ) -> Result<(), EncodeError> {
     // If this PID was decoded from LOCAL_EXT, use the preserved bytes for transparent re-encoding.
     // Otherwise, encode as NEW_PID_EXT (which can be exactly reconstructed from parsed fields).
-    if let Some(local_bytes) = &pid.local_ext_bytes {
+    if ctx.mode == EncodeMode::Legacy {
+        buf.put_u8(PID_EXT);
+        encode_atom_impl(buf, pid.node.as_str(), ctx)?;
+        buf.put_u32(pid.id);
+        buf.put_u32(pid.serial);
+        buf.put_u8(legacy_creation(pid.creation)?);
+    } else if let Some(local_bytes) = &pid.local_ext_bytes {
         buf.put_u8(LOCAL_EXT);
         buf.put_slice(local_bytes);
     } else {
         buf.put_u8(NEW_PID_EXT);
-        encode_atom_impl(buf, &pid.node, cache)?;
+        encode_atom_impl(buf, pid.node.as_str(), ctx)?;
         buf.put_u32(pid.id);
         buf.put_u32(pid.serial);
         buf.put_u32(pid.creation);
@@ -271,15 +486,21 @@ fn encode_pid_impl(
 fn encode_port_impl(
     buf: &mut BytesMut,
     port: &ExternalPort,
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: EncodeContext<'_>,
 ) -> Result<(), EncodeError> {
     // Use preserved LOCAL_EXT bytes if available for transparent re-encoding
-    if let Some(ref local_ext_bytes) = port.local_ext_bytes {
+    if ctx.mode == EncodeMode::Legacy {
+        let id = u32::try_from(port.id).map_err(|_| EncodeError::PortIdTooLarge { id: port.id })?;
+        buf.put_u8(PORT_EXT);
+        encode_atom_impl(buf, port.node.as_str(), ctx)?;
+        buf.put_u32(id);
+        buf.put_u8(legacy_creation(port.creation)?);
+    } else if let Some(ref local_ext_bytes) = port.local_ext_bytes {
         buf.put_u8(LOCAL_EXT);
         buf.put_slice(local_ext_bytes);
     } else {
         buf.put_u8(V4_PORT_EXT);
-        encode_atom_impl(buf, &port.node, cache)?;
+        encode_atom_impl(buf, port.node.as_str(), ctx)?;
         buf.put_u64(port.id);
         buf.put_u32(port.creation);
     }
@@ -289,10 +510,12 @@ fn encode_port_impl(
 fn encode_reference_impl(
     buf: &mut BytesMut,
     ref_: &ExternalReference,
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: EncodeContext<'_>,
 ) -> Result<(), EncodeError> {
     // Use preserved LOCAL_EXT bytes if available for transparent re-encoding
-    if let Some(ref local_ext_bytes) = ref_.local_ext_bytes {
+    if let Some(ref local_ext_bytes) = ref_.local_ext_bytes
+        && ctx.mode == EncodeMode::Current
+    {
         buf.put_u8(LOCAL_EXT);
         buf.put_slice(local_ext_bytes);
     } else {
@@ -300,10 +523,17 @@ fn encode_reference_impl(
             size: ref_.ids.len(),
         })?;
 
-        buf.put_u8(NEWER_REFERENCE_EXT);
-        buf.put_u16(len);
-        encode_atom_impl(buf, &ref_.node, cache)?;
-        buf.put_u32(ref_.creation);
+        if ctx.mode == EncodeMode::Legacy {
+            buf.put_u8(NEW_REFERENCE_EXT);
+            buf.put_u16(len);
+            encode_atom_impl(buf, ref_.node.as_str(), ctx)?;
+            buf.put_u8(legacy_creation(ref_.creation)?);
+        } else {
+            buf.put_u8(NEWER_REFERENCE_EXT);
+            buf.put_u16(len);
+            encode_atom_impl(buf, ref_.node.as_str(), ctx)?;
+            buf.put_u32(ref_.creation);
+        }
         for id in &ref_.ids {
             buf.put_u32(*id);
         }
@@ -311,6 +541,11 @@ fn encode_reference_impl(
     Ok(())
 }
 
+/// Legacy tags carry a single creation byte.
+fn legacy_creation(creation: u32) -> Result<u8, EncodeError> {
+    u8::try_from(creation).map_err(|_| EncodeError::CreationTooLarge { creation })
+}
+
 fn encode_bigint(buf: &mut BytesMut, big: &BigInt) -> Result<(), EncodeError> {
     let len = big.digits.len();
     if len <= 255 {
@@ -333,11 +568,11 @@ fn encode_nil(buf: &mut BytesMut) -> Result<(), EncodeError> {
 fn encode_export_ext_impl(
     buf: &mut BytesMut,
     fun: &ExternalFun,
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: EncodeContext<'_>,
 ) -> Result<(), EncodeError> {
     buf.put_u8(EXPORT_EXT);
-    encode_atom_impl(buf, &fun.module, cache)?;
-    encode_atom_impl(buf, &fun.function, cache)?;
+    encode_atom_impl(buf, fun.module.as_str(), ctx)?;
+    encode_atom_impl(buf, fun.function.as_str(), ctx)?;
     encode_integer(buf, fun.arity as i64)?;
     Ok(())
 }
@@ -345,7 +580,7 @@ fn encode_export_ext_impl(
 fn encode_new_fun_ext_impl(
     buf: &mut BytesMut,
     fun: &InternalFun,
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: EncodeContext<'_>,
 ) -> Result<(), EncodeError> {
     let mut temp_buf = BytesMut::new();
 
@@ -354,13 +589,13 @@ fn encode_new_fun_ext_impl(
     temp_buf.put_u32(fun.index);
     temp_buf.put_u32(fun.num_free);
 
-    encode_atom_impl(&mut temp_buf, &fun.module, cache)?;
+    encode_atom_impl(&mut temp_buf, fun.module.as_str(), ctx)?;
     encode_integer(&mut temp_buf, fun.old_index as i64)?;
     encode_integer(&mut temp_buf, fun.old_uniq as i64)?;
-    encode_pid_impl(&mut temp_buf, &fun.pid, cache)?;
+    encode_pid_impl(&mut temp_buf, &fun.pid, ctx)?;
 
     for var in &fun.free_vars {
-        encode_term_impl(&mut temp_buf, var, cache)?;
+        encode_term_impl(&mut temp_buf, var, ctx)?;
     }
 
     buf.put_u8(NEW_FUN_EXT);
@@ -370,10 +605,10 @@ fn encode_new_fun_ext_impl(
     Ok(())
 }
 
-fn collect_atoms<'a>(term: &'a OwnedTerm, atoms: &mut HashSet<&'a Atom>) {
+fn collect_atoms<'a>(term: &'a OwnedTerm, atoms: &mut HashSet<&'a str>) {
     match term {
         OwnedTerm::Atom(atom) => {
-            atoms.insert(atom);
+            atoms.insert(atom.as_str());
         }
         OwnedTerm::Tuple(elements) | OwnedTerm::List(elements) => {
             for elem in elements {
@@ -392,118 +627,661 @@ fn collect_atoms<'a>(term: &'a OwnedTerm, atoms: &mut HashSet<&'a Atom>) {
                 collect_atoms(value, atoms);
             }
         }
+        OwnedTerm::OrderedMap(entries) => {
+            for (key, value) in entries {
+                collect_atoms(key, atoms);
+                collect_atoms(value, atoms);
+            }
+        }
         OwnedTerm::Pid(pid) => {
-            atoms.insert(&pid.node);
+            atoms.insert(pid.node.as_str());
         }
         OwnedTerm::Port(port) => {
-            atoms.insert(&port.node);
+            atoms.insert(port.node.as_str());
         }
         OwnedTerm::Reference(ref_) => {
-            atoms.insert(&ref_.node);
+            atoms.insert(ref_.node.as_str());
         }
         OwnedTerm::ExternalFun(fun) => {
-            atoms.insert(&fun.module);
-            atoms.insert(&fun.function);
-        }
-        OwnedTerm::InternalFun(fun) => {
-            atoms.insert(&fun.module);
-            atoms.insert(&fun.pid.node);
-            for var in &fun.free_vars {
-                collect_atoms(var, atoms);
-            }
+            atoms.insert(fun.module.as_str());
+            atoms.insert(fun.function.as_str());
         }
+        OwnedTerm::InternalFun(fun) => collect_internal_fun_atoms(fun, atoms),
+        OwnedTerm::Shared(shared) => collect_atoms(shared.as_ref(), atoms),
         _ => {}
     }
 }
 
-fn encode_term_with_cache<'a>(
-    buf: &mut BytesMut,
-    term: &'a OwnedTerm,
-    atom_index_map: &HashMap<&'a Atom, u8>,
-) -> Result<(), EncodeError> {
-    encode_term_impl(buf, term, Some(atom_index_map))
+fn collect_internal_fun_atoms<'a>(fun: &'a InternalFun, atoms: &mut HashSet<&'a str>) {
+    atoms.insert(fun.module.as_str());
+    atoms.insert(fun.pid.node.as_str());
+    for var in &fun.free_vars {
+        collect_atoms(var, atoms);
+    }
+}
+
+/// Lets the dist header encoder work with both owned and borrowed terms.
+trait DistEncodable {
+    fn collect_atoms<'a>(&'a self, atoms: &mut HashSet<&'a str>);
+    fn encode_into(&self, buf: &mut BytesMut, ctx: EncodeContext<'_>) -> Result<(), EncodeError>;
+    fn estimated_size(&self) -> usize;
+}
+
+impl DistEncodable for OwnedTerm {
+    fn collect_atoms<'a>(&'a self, atoms: &mut HashSet<&'a str>) {
+        collect_atoms(self, atoms)
+    }
+
+    fn encode_into(&self, buf: &mut BytesMut, ctx: EncodeContext<'_>) -> Result<(), EncodeError> {
+        encode_term_impl(buf, self, ctx)
+    }
+
+    fn estimated_size(&self) -> usize {
+        self.estimated_encoded_size()
+    }
+}
+
+impl DistEncodable for BorrowedTerm<'_> {
+    fn collect_atoms<'a>(&'a self, atoms: &mut HashSet<&'a str>) {
+        collect_borrowed_atoms(self, atoms)
+    }
+
+    fn encode_into(&self, buf: &mut BytesMut, ctx: EncodeContext<'_>) -> Result<(), EncodeError> {
+        encode_borrowed_term_impl(buf, self, ctx)
+    }
+
+    fn estimated_size(&self) -> usize {
+        self.estimated_encoded_size()
+    }
 }
 
 pub fn encode_with_dist_header(term: &OwnedTerm) -> Result<Vec<u8>, EncodeError> {
     encode_with_dist_header_multi(&[term])
 }
 
+/// Encodes any number of terms, e.g. a control message and its payload, after one
+/// distribution header whose atom cache refs are shared by all of them.
 pub fn encode_with_dist_header_multi(terms: &[&OwnedTerm]) -> Result<Vec<u8>, EncodeError> {
+    encode_dist_multi(terms)
+}
+
+/// Options for [`encode_with_dist_header_report`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DistHeaderOptions {
+    config: EncodeConfig,
+    predeclared: Vec<Atom>,
+}
+
+impl DistHeaderOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_mode(mut self, mode: EncodeMode) -> Self {
+        self.config.mode = mode;
+        self
+    }
+
+    /// Encodes the terms with the mode, float policy and atom length limit of `config`.
+    pub fn with_encode_config(mut self, config: EncodeConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Atoms to announce as new cache entries even if the terms do not use them,
+    /// so that later frames can refer to them. Like [`OutgoingAtomCache::seed`],
+    /// atoms that do not fit this header are announced in the next ones.
+    pub fn with_predeclared_atoms(mut self, atoms: impl IntoIterator<Item = Atom>) -> Self {
+        self.predeclared.extend(atoms);
+        self
+    }
+}
+
+/// The atom cache refs of a distribution header, in header order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AtomCacheReport {
+    /// Atoms sent as new entries, with the cache index the peer now has them under
+    pub new_entries: Vec<(Atom, u16)>,
+    /// Atoms sent as references to entries the peer already had
+    pub reused: Vec<(Atom, u16)>,
+}
+
+impl AtomCacheReport {
+    /// Atoms that became cached on the peer with this header.
+    pub fn newly_cached(&self) -> impl Iterator<Item = &Atom> {
+        self.new_entries.iter().map(|(atom, _)| atom)
+    }
+
+    /// Number of refs in the header.
+    pub fn len(&self) -> usize {
+        self.new_entries.len() + self.reused.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.new_entries.is_empty() && self.reused.is_empty()
+    }
+}
+
+/// Encodes `terms` after a distribution header, using and updating the connection's
+/// outgoing atom cache.
+///
+/// Atoms the peer already has are sent as references to their cache entries. Other
+/// atoms, and atoms queued with [`OutgoingAtomCache::seed`], are sent as new entries.
+pub fn encode_with_dist_header_cached(
+    terms: &[&OwnedTerm],
+    cache: &mut OutgoingAtomCache,
+) -> Result<Vec<u8>, EncodeError> {
+    encode_with_dist_header_cached_and_mode(terms, cache, EncodeMode::Current)
+}
+
+/// Like [`encode_with_dist_header_cached`], with pids, ports and references encoded
+/// according to `mode`.
+pub fn encode_with_dist_header_cached_and_mode(
+    terms: &[&OwnedTerm],
+    cache: &mut OutgoingAtomCache,
+    mode: EncodeMode,
+) -> Result<Vec<u8>, EncodeError> {
+    let options = DistHeaderOptions::new().with_mode(mode);
+    encode_with_dist_header_report(terms, cache, &options).map(|(bytes, _)| bytes)
+}
+
+/// Like [`encode_with_dist_header_cached`], and also reports which atoms the header
+/// announced as new cache entries and which it referred to.
+///
+/// Pass a fresh [`OutgoingAtomCache`] to encode without a connection's cache.
+pub fn encode_with_dist_header_report(
+    terms: &[&OwnedTerm],
+    cache: &mut OutgoingAtomCache,
+    options: &DistHeaderOptions,
+) -> Result<(Vec<u8>, AtomCacheReport), EncodeError> {
+    let config = &options.config;
     let mut atom_set = HashSet::new();
     for term in terms {
-        collect_atoms(term, &mut atom_set);
+        term.collect_atoms(&mut atom_set);
+    }
+    if atom_set.len() > MAX_DIST_HEADER_REFS {
+        return Err(EncodeError::TooManyAtoms {
+            count: atom_set.len(),
+        });
+    }
+    check_header_atoms(atom_set.iter().copied(), config.max_atom_len)?;
+    check_header_atoms(
+        options.predeclared.iter().map(Atom::as_str),
+        config.max_atom_len,
+    )?;
+    cache.seed(options.predeclared.iter().cloned());
+
+    let mut atoms: Vec<&str> = atom_set.into_iter().collect();
+    atoms.sort_unstable();
+    let frame_refs = cache.frame_refs(&atoms);
+    let mut report = AtomCacheReport::default();
+    for r in &frame_refs {
+        let entry = (
+            Atom {
+                name: r.atom.clone(),
+            },
+            r.cache_index,
+        );
+        if r.new_entry {
+            report.new_entries.push(entry);
+        } else {
+            report.reused.push(entry);
+        }
+    }
+    let refs: Vec<CacheRef<'_>> = frame_refs
+        .iter()
+        .map(|r| CacheRef {
+            atom: &r.atom,
+            cache_index: r.cache_index,
+            new_entry: r.new_entry,
+        })
+        .collect();
+
+    let estimated_size =
+        terms.iter().map(|t| t.estimated_size()).sum::<usize>() + refs.len() * 10 + 64;
+    let mut buf = BytesMut::with_capacity(estimated_size);
+    buf.put_u8(VERSION);
+    if refs.is_empty() {
+        for term in terms {
+            term.encode_into(&mut buf, EncodeContext::from_config(config))?;
+        }
+        return Ok((buf.to_vec(), report));
+    }
+
+    write_dist_header(&mut buf, &refs);
+    // Atoms used by the terms come first in the header, so their positions match `atoms`
+    let atom_index_map: HashMap<&str, u8> = atoms
+        .iter()
+        .enumerate()
+        .map(|(index, atom)| (*atom, index as u8))
+        .collect();
+    for term in terms {
+        term.encode_into(&mut buf, EncodeContext::with_cache(config, &atom_index_map))?;
+    }
+
+    Ok((buf.to_vec(), report))
+}
+
+fn encode_dist_multi<T: DistEncodable + ?Sized>(terms: &[&T]) -> Result<Vec<u8>, EncodeError> {
+    let mut atom_set = HashSet::new();
+    for term in terms {
+        term.collect_atoms(&mut atom_set);
     }
 
     if atom_set.is_empty() {
         let mut buf = BytesMut::new();
         buf.put_u8(VERSION);
         for term in terms {
-            encode_term(&mut buf, term)?;
+            term.encode_into(&mut buf, EncodeContext::default())?;
         }
         return Ok(buf.to_vec());
     }
 
-    if atom_set.len() > 255 {
+    if atom_set.len() > MAX_DIST_HEADER_REFS {
         return Err(EncodeError::TooManyAtoms {
             count: atom_set.len(),
         });
     }
+    check_header_atoms(atom_set.iter().copied(), MAX_ATOM_CHARACTERS)?;
 
-    let atoms: Vec<&Atom> = atom_set.iter().copied().collect();
+    let atoms: Vec<&str> = atom_set.iter().copied().collect();
 
     let mut atom_index_map = HashMap::new();
+    let mut refs = Vec::with_capacity(atoms.len());
     for (index, atom) in atoms.iter().enumerate() {
         atom_index_map.insert(*atom, index as u8);
+        refs.push(CacheRef {
+            atom,
+            cache_index: index as u16,
+            new_entry: true,
+        });
     }
 
-    let estimated_size = terms
-        .iter()
-        .map(|t| t.estimated_encoded_size())
-        .sum::<usize>()
-        + atoms.len() * 10
-        + 64;
+    let estimated_size =
+        terms.iter().map(|t| t.estimated_size()).sum::<usize>() + atoms.len() * 10 + 64;
     let mut buf = BytesMut::with_capacity(estimated_size);
 
     buf.put_u8(VERSION);
+    write_dist_header(&mut buf, &refs);
+
+    for term in terms {
+        term.encode_into(
+            &mut buf,
+            EncodeContext::with_cache(&EncodeConfig::default(), &atom_index_map),
+        )?;
+    }
+
+    Ok(buf.to_vec())
+}
+
+/// Number of entries in a connection's atom cache, 8 segments of 256.
+pub const DIST_ATOM_CACHE_SIZE: usize = 2048;
+
+const MAX_DIST_HEADER_REFS: usize = 255;
+
+/// Rejects atoms whose text does not fit the 2-byte length of a long atom cache entry
+/// or that have more than `max_atom_len` characters, before any of them is added to a cache.
+fn check_header_atoms<'a>(
+    atoms: impl IntoIterator<Item = &'a str>,
+    max_atom_len: usize,
+) -> Result<(), EncodeError> {
+    for atom in atoms {
+        if atom.len() > u16::MAX as usize {
+            return Err(EncodeError::AtomTooLarge { size: atom.len() });
+        }
+        check_atom_length(atom, max_atom_len)?;
+    }
+    Ok(())
+}
+
+struct CacheRef<'a> {
+    atom: &'a str,
+    cache_index: u16,
+    new_entry: bool,
+}
+
+/// Writes `DIST_HEADER` with its atom cache refs. New entries carry the atom text,
+/// the others only refer to an entry the peer already has.
+fn write_dist_header(buf: &mut BytesMut, refs: &[CacheRef<'_>]) {
     buf.put_u8(DIST_HEADER);
-    buf.put_u8(atoms.len() as u8);
+    buf.put_u8(refs.len() as u8);
 
-    let flags_len = (atoms.len() / 2) + 1;
-    let flags_start_pos = buf.len();
-    for _ in 0..flags_len {
-        buf.put_u8(0);
+    let flags_start = buf.len();
+    buf.put_bytes(0, refs.len() / 2 + 1);
+    for (i, cache_ref) in refs.iter().enumerate() {
+        let mut nibble = ((cache_ref.cache_index >> 8) & 0x07) as u8;
+        if cache_ref.new_entry {
+            nibble |= 0x08;
+        }
+        buf[flags_start + i / 2] |= nibble << (4 * (i % 2));
     }
 
-    let long_atoms = atoms.iter().any(|a| a.name.len() > 255);
+    // The LongAtoms flag takes the half byte after the last ref's flags
+    let long_atoms = refs.iter().any(|r| r.new_entry && r.atom.len() > 255);
     if long_atoms {
-        buf[flags_start_pos + flags_len - 1] |= 0x01;
+        buf[flags_start + refs.len() / 2] |= 0x01 << (4 * (refs.len() % 2));
     }
 
-    for (index, atom) in atoms.iter().enumerate() {
-        buf.put_u8(index as u8);
+    for cache_ref in refs {
+        buf.put_u8((cache_ref.cache_index & 0xFF) as u8);
+        if cache_ref.new_entry {
+            let atom_bytes = cache_ref.atom.as_bytes();
+            if long_atoms {
+                buf.put_u16(atom_bytes.len() as u16);
+            } else {
+                buf.put_u8(atom_bytes.len() as u8);
+            }
+            buf.put_slice(atom_bytes);
+        }
+    }
+}
 
-        let flag_byte_index = index / 2;
-        let nibble_shift = if index % 2 == 0 { 0 } else { 4 };
-        let new_entry_flag = 0x08u8;
+/// The sending side of a connection's atom cache: which atoms the peer has been sent
+/// and under which cache index, plus how often each atom was used.
+///
+/// A new connection starts with an empty cache on the peer, so the cache must be
+/// [reset](OutgoingAtomCache::reset) when reconnecting. The most used atoms from the
+/// previous connection can then be [seeded](OutgoingAtomCache::seed) so that they are
+/// announced in the first frames.
+#[derive(Debug, Clone, Default)]
+pub struct OutgoingAtomCache {
+    entries: HashMap<Arc<str>, u16>,
+    slots: Vec<Option<Arc<str>>>,
+    next_slot: usize,
+    uses: HashMap<Arc<str>, u64>,
+    pending: VecDeque<Arc<str>>,
+}
 
-        let flag_pos = flags_start_pos + flag_byte_index;
-        buf[flag_pos] |= new_entry_flag << nibble_shift;
+struct FrameRef {
+    atom: Arc<str>,
+    cache_index: u16,
+    new_entry: bool,
+}
 
-        let atom_bytes = atom.name.as_bytes();
-        let atom_len = atom_bytes.len();
+impl OutgoingAtomCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-        if long_atoms {
-            buf.put_u16(atom_len as u16);
-        } else {
-            buf.put_u8(atom_len as u8);
+    /// Number of atoms the peer has been sent.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, atom: &str) -> bool {
+        self.entries.contains_key(atom)
+    }
+
+    pub fn cache_index(&self, atom: &str) -> Option<u16> {
+        self.entries.get(atom).copied()
+    }
+
+    /// Queues atoms to be sent as new entries in the next frames, in addition to the
+    /// atoms those frames use.
+    pub fn seed(&mut self, atoms: impl IntoIterator<Item = Atom>) {
+        for atom in atoms {
+            if !self.entries.contains_key(&atom.name) && !self.pending.contains(&atom.name) {
+                self.pending.push_back(atom.name);
+            }
         }
+    }
 
-        buf.put_slice(atom_bytes);
+    /// Number of seeded atoms not sent yet.
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
     }
 
-    for term in terms {
-        encode_term_with_cache(&mut buf, term, &atom_index_map)?;
+    /// Returns up to `limit` atoms, most used first.
+    pub fn hottest(&self, limit: usize) -> Vec<Atom> {
+        let mut uses: Vec<(&Arc<str>, u64)> = self.uses.iter().map(|(a, n)| (a, *n)).collect();
+        uses.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        uses.into_iter()
+            .take(limit)
+            .map(|(name, _)| Atom { name: name.clone() })
+            .collect()
     }
 
+    /// Forgets which atoms the peer has, e.g. because the connection was re-established.
+    /// Usage counts are kept.
+    pub fn reset(&mut self) {
+        self.entries.clear();
+        self.slots.clear();
+        self.next_slot = 0;
+        self.pending.clear();
+    }
+
+    /// Forgets everything, including usage counts.
+    pub fn clear(&mut self) {
+        self.reset();
+        self.uses.clear();
+    }
+
+    /// Releases unused capacity. After [`OutgoingAtomCache::reset`], this includes the slot table.
+    pub fn shrink_to_fit(&mut self) {
+        self.entries.shrink_to_fit();
+        self.slots.shrink_to_fit();
+        self.uses.shrink_to_fit();
+        self.pending.shrink_to_fit();
+    }
+
+    fn frame_refs(&mut self, atoms: &[&str]) -> Vec<FrameRef> {
+        let mut in_frame: HashSet<u16> = atoms
+            .iter()
+            .filter_map(|atom| self.entries.get(*atom).copied())
+            .collect();
+        let mut refs: Vec<FrameRef> = Vec::with_capacity(atoms.len() + self.pending.len());
+
+        for atom in atoms {
+            let name = match self.entries.get_key_value(*atom) {
+                Some((name, _)) => name.clone(),
+                None => Arc::from(*atom),
+            };
+            *self.uses.entry(name.clone()).or_default() += 1;
+            match self.entries.get(&name) {
+                Some(&cache_index) => refs.push(FrameRef {
+                    atom: name,
+                    cache_index,
+                    new_entry: false,
+                }),
+                None => {
+                    let cache_index = self.allocate(name.clone(), &in_frame);
+                    in_frame.insert(cache_index);
+                    refs.push(FrameRef {
+                        atom: name,
+                        cache_index,
+                        new_entry: true,
+                    });
+                }
+            }
+        }
+
+        while refs.len() < MAX_DIST_HEADER_REFS
+            && let Some(name) = self.pending.pop_front()
+        {
+            if self.entries.contains_key(&name) {
+                continue;
+            }
+            let cache_index = self.allocate(name.clone(), &in_frame);
+            in_frame.insert(cache_index);
+            refs.push(FrameRef {
+                atom: name,
+                cache_index,
+                new_entry: true,
+            });
+        }
+
+        refs
+    }
+
+    /// Takes the next slot not used by the current frame, evicting its atom.
+    fn allocate(&mut self, name: Arc<str>, in_frame: &HashSet<u16>) -> u16 {
+        if self.slots.is_empty() {
+            self.slots.resize(DIST_ATOM_CACHE_SIZE, None);
+        }
+        loop {
+            let cache_index = self.next_slot as u16;
+            self.next_slot = (self.next_slot + 1) % DIST_ATOM_CACHE_SIZE;
+            if in_frame.contains(&cache_index) {
+                continue;
+            }
+            if let Some(evicted) = self.slots[cache_index as usize].take() {
+                self.entries.remove(&evicted);
+            }
+            self.slots[cache_index as usize] = Some(name.clone());
+            self.entries.insert(name, cache_index);
+            return cache_index;
+        }
+    }
+}
+
+/// Encodes a [`BorrowedTerm`] without converting it to an [`OwnedTerm`] first.
+pub fn encode_borrowed(term: &BorrowedTerm<'_>) -> Result<Vec<u8>, EncodeError> {
+    let capacity = (term.estimated_encoded_size() + 1).max(64);
+    let mut buf = BytesMut::with_capacity(capacity);
+    buf.put_u8(VERSION);
+    encode_borrowed_term_impl(&mut buf, term, EncodeContext::default())?;
     Ok(buf.to_vec())
 }
+
+pub fn encode_borrowed_with_dist_header(term: &BorrowedTerm<'_>) -> Result<Vec<u8>, EncodeError> {
+    encode_borrowed_with_dist_header_multi(&[term])
+}
+
+pub fn encode_borrowed_with_dist_header_multi(
+    terms: &[&BorrowedTerm<'_>],
+) -> Result<Vec<u8>, EncodeError> {
+    encode_dist_multi(terms)
+}
+
+fn borrowed_list_byte(term: &BorrowedTerm<'_>) -> Option<u8> {
+    match term {
+        BorrowedTerm::Integer(i) => u8::try_from(*i).ok(),
+        _ => None,
+    }
+}
+
+fn encode_borrowed_term_impl(
+    buf: &mut BytesMut,
+    term: &BorrowedTerm<'_>,
+    ctx: EncodeContext<'_>,
+) -> Result<(), EncodeError> {
+    match term {
+        BorrowedTerm::Atom(name) => encode_atom_impl(buf, name, ctx),
+        BorrowedTerm::Integer(i) => encode_integer(buf, *i),
+        BorrowedTerm::Float(f) => encode_float(buf, *f, ctx),
+        BorrowedTerm::Binary(b) => encode_binary(buf, b),
+        BorrowedTerm::BitBinary { bytes, bits } => encode_bit_binary(buf, bytes, *bits),
+        BorrowedTerm::String(s) => encode_string(buf, s),
+        BorrowedTerm::List(elements) => {
+            if elements.is_empty() {
+                return encode_nil(buf);
+            }
+            if let Ok(len) = u16::try_from(elements.len())
+                && elements.iter().all(|e| borrowed_list_byte(e).is_some())
+            {
+                buf.put_u8(STRING_EXT);
+                buf.put_u16(len);
+                buf.extend(elements.iter().filter_map(borrowed_list_byte));
+                return Ok(());
+            }
+            encode_borrowed_list_header(buf, elements.len())?;
+            for elem in elements {
+                encode_borrowed_term_impl(buf, elem, ctx)?;
+            }
+            encode_nil(buf)
+        }
+        BorrowedTerm::ImproperList { elements, tail } => {
+            encode_borrowed_list_header(buf, elements.len())?;
+            for elem in elements {
+                encode_borrowed_term_impl(buf, elem, ctx)?;
+            }
+            encode_borrowed_term_impl(buf, tail, ctx)
+        }
+        BorrowedTerm::Map(map) => {
+            let len = u32::try_from(map.len())
+                .map_err(|_| EncodeError::MapTooLarge { size: map.len() })?;
+            buf.put_u8(MAP_EXT);
+            buf.put_u32(len);
+            for (key, value) in map {
+                encode_borrowed_term_impl(buf, key, ctx)?;
+                encode_borrowed_term_impl(buf, value, ctx)?;
+            }
+            Ok(())
+        }
+        BorrowedTerm::Tuple(elements) => {
+            if elements.len() <= 255 {
+                buf.put_u8(SMALL_TUPLE_EXT);
+                buf.put_u8(elements.len() as u8);
+            } else {
+                let len =
+                    u32::try_from(elements.len()).map_err(|_| EncodeError::TupleTooLarge {
+                        size: elements.len(),
+                    })?;
+                buf.put_u8(LARGE_TUPLE_EXT);
+                buf.put_u32(len);
+            }
+            for elem in elements {
+                encode_borrowed_term_impl(buf, elem, ctx)?;
+            }
+            Ok(())
+        }
+        BorrowedTerm::Pid(pid) => encode_pid_impl(buf, pid, ctx),
+        BorrowedTerm::Port(port) => encode_port_impl(buf, port, ctx),
+        BorrowedTerm::Reference(ref_) => encode_reference_impl(buf, ref_, ctx),
+        BorrowedTerm::BigInt(big) => encode_bigint(buf, big),
+        BorrowedTerm::ExternalFun(fun) => encode_export_ext_impl(buf, fun, ctx),
+        BorrowedTerm::InternalFun(fun) => encode_new_fun_ext_impl(buf, fun, ctx),
+        BorrowedTerm::Nil => encode_nil(buf),
+    }
+}
+
+fn encode_borrowed_list_header(buf: &mut BytesMut, len: usize) -> Result<(), EncodeError> {
+    let len = u32::try_from(len).map_err(|_| EncodeError::ListTooLarge { size: len })?;
+    buf.put_u8(LIST_EXT);
+    buf.put_u32(len);
+    Ok(())
+}
+
+fn collect_borrowed_atoms<'a>(term: &'a BorrowedTerm<'_>, atoms: &mut HashSet<&'a str>) {
+    match term {
+        BorrowedTerm::Atom(name) => {
+            atoms.insert(name.as_ref());
+        }
+        BorrowedTerm::Tuple(elements) | BorrowedTerm::List(elements) => {
+            for elem in elements {
+                collect_borrowed_atoms(elem, atoms);
+            }
+        }
+        BorrowedTerm::ImproperList { elements, tail } => {
+            for elem in elements {
+                collect_borrowed_atoms(elem, atoms);
+            }
+            collect_borrowed_atoms(tail, atoms);
+        }
+        BorrowedTerm::Map(map) => {
+            for (key, value) in map {
+                collect_borrowed_atoms(key, atoms);
+                collect_borrowed_atoms(value, atoms);
+            }
+        }
+        BorrowedTerm::Pid(pid) => {
+            atoms.insert(pid.node.as_str());
+        }
+        BorrowedTerm::Port(port) => {
+            atoms.insert(port.node.as_str());
+        }
+        BorrowedTerm::Reference(ref_) => {
+            atoms.insert(ref_.node.as_str());
+        }
+        BorrowedTerm::ExternalFun(fun) => {
+            atoms.insert(fun.module.as_str());
+            atoms.insert(fun.function.as_str());
+        }
+        BorrowedTerm::InternalFun(fun) => collect_internal_fun_atoms(fun, atoms),
+        _ => {}
+    }
+}
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::tags::tag_name;
+use crate::types::Atom;
 use std::fmt;
 use std::io;
 use std::result::Result as StdResult;
@@ -62,6 +64,12 @@ pub enum DecodeError {
     TrailingData(usize),
     #[error("invalid PID format: {0}")]
     InvalidPidFormat(String),
+    #[error("atom limit exceeded: {limit} distinct atoms already decoded")]
+    AtomLimitExceeded { limit: usize },
+    #[error("unsafe term: {0}")]
+    UnsafeTerm(String),
+    #[error("non-finite float: {0}")]
+    NonFiniteFloat(f64),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -129,10 +137,71 @@ impl Default for ParsingContext {
     }
 }
 
+/// How many bytes on each side of the error offset [`decode_borrowed`](crate::decode_borrowed)
+/// includes in a [`ContextualDecodeError`].
+pub const DEFAULT_SNIPPET_WINDOW: usize = 8;
+
+/// The bytes around the offset of a decoding error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorSnippet {
+    /// Offset of the first byte of `bytes` in the input
+    pub start: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl ErrorSnippet {
+    /// Copies up to `window` bytes on each side of `offset` from `data`.
+    pub fn new(data: &[u8], offset: usize, window: usize) -> Self {
+        let offset = offset.min(data.len());
+        let start = offset.saturating_sub(window);
+        let end = offset
+            .saturating_add(window)
+            .saturating_add(1)
+            .min(data.len());
+        Self {
+            start,
+            bytes: data[start..end].to_vec(),
+        }
+    }
+
+    /// Returns the byte at `offset` in the input, if the snippet covers it.
+    #[must_use]
+    pub fn byte_at(&self, offset: usize) -> Option<u8> {
+        offset
+            .checked_sub(self.start)
+            .and_then(|i| self.bytes.get(i).copied())
+    }
+
+    /// Renders the snippet as hex, with the byte at `offset` in brackets.
+    #[must_use]
+    pub fn to_hex(&self, offset: usize) -> String {
+        let mut out = format!("{:08x}:", self.start);
+        for (i, byte) in self.bytes.iter().enumerate() {
+            if self.start + i == offset {
+                out.push_str(&format!(" [{byte:02x}]"));
+            } else {
+                out.push_str(&format!(" {byte:02x}"));
+            }
+        }
+        if offset >= self.start + self.bytes.len() {
+            out.push_str(" []");
+        }
+        out
+    }
+}
+
+fn describe_byte(byte: u8) -> String {
+    match tag_name(byte) {
+        Some(name) => format!("{name} ({byte})"),
+        None => format!("byte {byte}"),
+    }
+}
+
 #[derive(Error, Debug, Clone, PartialEq)]
 pub struct ContextualDecodeError {
     pub error: DecodeError,
     pub context: ParsingContext,
+    pub snippet: Option<ErrorSnippet>,
 }
 
 impl fmt::Display for ContextualDecodeError {
@@ -143,13 +212,52 @@ impl fmt::Display for ContextualDecodeError {
             self.error,
             self.context.byte_offset,
             self.context.display_path()
-        )
+        )?;
+        let Some(snippet) = &self.snippet else {
+            return Ok(());
+        };
+        match self.found_tag() {
+            Some(found) => write!(f, "\n  found {}", describe_byte(found))?,
+            None => write!(f, "\n  found end of input")?,
+        }
+        if let Some(expected) = self.expected_tag() {
+            write!(f, ", expected {}", describe_byte(expected))?;
+        }
+        write!(f, "\n  {}", snippet.to_hex(self.context.byte_offset))
     }
 }
 
 impl ContextualDecodeError {
     pub fn new(error: DecodeError, context: ParsingContext) -> Self {
-        ContextualDecodeError { error, context }
+        ContextualDecodeError {
+            error,
+            context,
+            snippet: None,
+        }
+    }
+
+    /// Attaches up to `window` bytes of `data` on each side of the error offset,
+    /// which [`Display`](fmt::Display) renders as hex along with the tag found there.
+    pub fn with_source(mut self, data: &[u8], window: usize) -> Self {
+        self.snippet = Some(ErrorSnippet::new(data, self.context.byte_offset, window));
+        self
+    }
+
+    /// The byte at the error offset, usually the tag of the term that failed to decode.
+    #[must_use]
+    pub fn found_tag(&self) -> Option<u8> {
+        self.snippet
+            .as_ref()
+            .and_then(|snippet| snippet.byte_at(self.context.byte_offset))
+    }
+
+    /// The byte the decoder expected at the error offset, when a single one would do.
+    #[must_use]
+    pub fn expected_tag(&self) -> Option<u8> {
+        match self.error {
+            DecodeError::InvalidVersion { expected, .. } => Some(expected),
+            _ => None,
+        }
     }
 }
 
@@ -157,6 +265,8 @@ impl ContextualDecodeError {
 pub enum EncodeError {
     #[error("atom too large: {size} bytes (max 65535)")]
     AtomTooLarge { size: usize },
+    #[error("atom too long: {length} characters (max {max})")]
+    AtomTooLong { length: usize, max: usize },
     #[error("string too large: {size} bytes")]
     StringTooLarge { size: usize },
     #[error("list too large: {size} elements")]
@@ -169,6 +279,12 @@ pub enum EncodeError {
     TupleTooLarge { size: usize },
     #[error("reference has too many IDs: {size} (max 65535)")]
     ReferenceTooLarge { size: usize },
+    #[error("creation {creation} does not fit a legacy pid, port or reference (max 255)")]
+    CreationTooLarge { creation: u32 },
+    #[error("port ID {id} does not fit PORT_EXT (max 4294967295)")]
+    PortIdTooLarge { id: u64 },
+    #[error("{value} cannot be encoded as an Erlang float")]
+    NonFiniteFloat { value: f64 },
     #[error("too many atoms for DIST_HEADER: {count} (max 255)")]
     TooManyAtoms { count: usize },
     #[error("I/O error: {0}")]
@@ -177,6 +293,28 @@ pub enum EncodeError {
     BufferOverflow,
 }
 
+/// Errors from converting terms to and from MessagePack or CBOR values.
+#[derive(Error, Debug)]
+pub enum BridgeError {
+    #[error("{0} has no equivalent outside of Erlang")]
+    Unsupported(&'static str),
+    #[error("invalid value: {0}")]
+    InvalidValue(String),
+    #[error("encode error: {0}")]
+    Encode(#[from] EncodeError),
+    #[error("decode error: {0}")]
+    Decode(#[from] DecodeError),
+}
+
+/// Errors from building a [`crate::node_mapping::NodeMapping`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NodeMappingError {
+    #[error("node {node} is already mapped to {existing}")]
+    SourceAlreadyMapped { node: Atom, existing: Atom },
+    #[error("node {node} is already the target of {existing}")]
+    TargetAlreadyMapped { node: Atom, existing: Atom },
+}
+
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum TermConversionError {
     #[error("expected {expected}, got {actual}")]
@@ -186,6 +324,17 @@ pub enum TermConversionError {
     },
     #[error("value out of range for target type")]
     OutOfRange,
+    #[error("expected a tuple of {expected} elements, got {actual}")]
+    WrongArity { expected: usize, actual: usize },
+    #[error("missing field {0}")]
+    MissingField(&'static str),
+    #[error("invalid value for {field}: {reason}")]
+    InvalidField {
+        field: &'static str,
+        reason: Box<TermConversionError>,
+    },
+    #[error("expected {expected}, got {actual}")]
+    UnexpectedValue { expected: String, actual: String },
 }
 
 impl From<Utf8Error> for DecodeError {
@@ -0,0 +1,570 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tag-by-tag inspection of raw External Term Format bytes.
+//!
+//! [`inspect`] walks encoded data without building terms and reports the offset,
+//! tag, size and a short preview of every element. Unlike the decoder, it does not
+//! give up on the first problem silently: truncated data and unknown tags are
+//! reported as an annotation with [`Annotation::error`] set, after the elements
+//! that were understood. [`annotated_hex_dump`] renders the result for humans.
+
+use std::fmt::Write;
+
+use crate::decoder::dist_header_long_atoms;
+use crate::tags::{
+    ATOM_CACHE_REF, ATOM_EXT, ATOM_UTF8_EXT, BINARY_EXT, BIT_BINARY_EXT, COMPRESSED_EXT,
+    DIST_FRAG_HEADER, DIST_HEADER, EXPORT_EXT, FLOAT_EXT, INTEGER_EXT, LARGE_BIG_EXT,
+    LARGE_TUPLE_EXT, LIST_EXT, LOCAL_EXT, MAP_EXT, NEW_FLOAT_EXT, NEW_FUN_EXT, NEW_PID_EXT,
+    NEW_PORT_EXT, NEW_REFERENCE_EXT, NEWER_REFERENCE_EXT, NIL_EXT, PID_EXT, PORT_EXT,
+    REFERENCE_EXT, SMALL_ATOM_EXT, SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT, SMALL_INTEGER_EXT,
+    SMALL_TUPLE_EXT, STRING_EXT, V4_PORT_EXT, VERSION, tag_name,
+};
+
+/// Binaries, strings and atoms longer than this are cut short in previews.
+pub const MAX_PREVIEW_BYTES: usize = 24;
+
+/// At most this many header bytes are printed per line of a hex dump.
+const HEX_COLUMN_BYTES: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    pub offset: usize,
+    /// Nesting level, 0 for top-level terms
+    pub depth: usize,
+    pub tag: u8,
+    pub name: &'static str,
+    /// Bytes of the tag and its fixed fields, excluding nested terms
+    pub header_len: usize,
+    /// Bytes spanned by the element, including nested terms
+    pub length: usize,
+    pub preview: String,
+    pub error: Option<String>,
+}
+
+impl Annotation {
+    pub fn is_error(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// Annotates every element of `data`, which may hold several terms, with or
+/// without version bytes and distribution headers.
+pub fn inspect(data: &[u8]) -> Vec<Annotation> {
+    let mut inspector = Inspector {
+        data,
+        pos: 0,
+        annotations: Vec::new(),
+        stack: Vec::new(),
+    };
+    let _ = inspector.run();
+    inspector.annotations
+}
+
+/// Renders annotations as an indented hex dump, one element per line.
+pub fn format_annotations(data: &[u8], annotations: &[Annotation]) -> String {
+    let hex_width = HEX_COLUMN_BYTES * 3 + 2;
+    let mut out = String::new();
+    for a in annotations {
+        let end = data
+            .len()
+            .min(a.offset + a.header_len.min(HEX_COLUMN_BYTES));
+        let mut hex = data[a.offset.min(end)..end]
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if a.header_len > HEX_COLUMN_BYTES {
+            hex.push_str(" ..");
+        }
+        let _ = write!(
+            out,
+            "{:08x}  {hex:<hex_width$}  {:indent$}{} [{} bytes]",
+            a.offset,
+            "",
+            a.name,
+            a.length,
+            indent = a.depth * 2
+        );
+        if !a.preview.is_empty() {
+            let _ = write!(out, " {}", a.preview);
+        }
+        if let Some(error) = &a.error {
+            let _ = write!(out, " !! {error}");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Shorthand for [`inspect`] followed by [`format_annotations`].
+pub fn annotated_hex_dump(data: &[u8]) -> String {
+    format_annotations(data, &inspect(data))
+}
+
+/// Fixed fields that follow the nested node atom of pids, ports and references.
+#[derive(Debug, Clone, Copy)]
+enum Trailer {
+    None,
+    Pid,
+    NewPid,
+    Port,
+    NewPort,
+    V4Port,
+    Reference,
+    NewReference(usize),
+    NewerReference(usize),
+}
+
+impl Trailer {
+    fn len(self) -> usize {
+        match self {
+            Trailer::None => 0,
+            Trailer::Pid => 9,
+            Trailer::NewPid => 12,
+            Trailer::Port | Trailer::Reference => 5,
+            Trailer::NewPort => 8,
+            Trailer::V4Port => 12,
+            Trailer::NewReference(words) => 1 + 4 * words,
+            Trailer::NewerReference(words) => 4 + 4 * words,
+        }
+    }
+
+    fn preview(self, b: &[u8]) -> String {
+        match self {
+            Trailer::None => String::new(),
+            Trailer::Pid => format!(
+                "id {}, serial {}, creation {}",
+                be_u32(b, 0),
+                be_u32(b, 4),
+                b[8]
+            ),
+            Trailer::NewPid => format!(
+                "id {}, serial {}, creation {}",
+                be_u32(b, 0),
+                be_u32(b, 4),
+                be_u32(b, 8)
+            ),
+            Trailer::Port => format!("id {}, creation {}", be_u32(b, 0), b[4]),
+            Trailer::NewPort => format!("id {}, creation {}", be_u32(b, 0), be_u32(b, 4)),
+            Trailer::V4Port => format!("id {}, creation {}", be_u64(b, 0), be_u32(b, 8)),
+            Trailer::Reference => format!("id {}, creation {}", be_u32(b, 0), b[4]),
+            Trailer::NewReference(words) => {
+                format!("creation {}, ids {:?}", b[0], words_of(&b[1..], words))
+            }
+            Trailer::NewerReference(words) => {
+                format!(
+                    "creation {}, ids {:?}",
+                    be_u32(b, 0),
+                    words_of(&b[4..], words)
+                )
+            }
+        }
+    }
+}
+
+struct Open {
+    index: usize,
+    remaining: usize,
+    trailer: Trailer,
+}
+
+struct Inspector<'a> {
+    data: &'a [u8],
+    pos: usize,
+    annotations: Vec<Annotation>,
+    stack: Vec<Open>,
+}
+
+/// Signals that an error annotation was recorded and inspection must stop.
+struct Halt;
+
+impl<'a> Inspector<'a> {
+    fn run(&mut self) -> Result<(), Halt> {
+        while self.pos < self.data.len() {
+            if self.data[self.pos] == VERSION {
+                self.leaf(VERSION, 1, String::new());
+                match self.data.get(self.pos) {
+                    None => break,
+                    Some(&tag @ (DIST_HEADER | DIST_FRAG_HEADER)) => self.dist_header(tag)?,
+                    Some(_) => {}
+                }
+            }
+            if self.pos < self.data.len() {
+                self.term()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn term(&mut self) -> Result<(), Halt> {
+        self.element()?;
+        loop {
+            while self.stack.last().is_some_and(|open| open.remaining == 0) {
+                self.close()?;
+            }
+            let Some(open) = self.stack.last_mut() else {
+                return Ok(());
+            };
+            open.remaining -= 1;
+            self.element()?;
+        }
+    }
+
+    fn element(&mut self) -> Result<(), Halt> {
+        let Some(&tag) = self.data.get(self.pos) else {
+            return Err(self.fail(0, "unexpected end of data, expected a term".to_string()));
+        };
+        match tag {
+            SMALL_INTEGER_EXT => {
+                let b = self.need(tag, 2)?;
+                self.leaf(tag, 2, b[1].to_string());
+            }
+            INTEGER_EXT => {
+                let b = self.need(tag, 5)?;
+                self.leaf(tag, 5, (be_u32(b, 1) as i32).to_string());
+            }
+            FLOAT_EXT => {
+                let b = self.need(tag, 32)?;
+                let text = String::from_utf8_lossy(&b[1..]);
+                self.leaf(tag, 32, text.trim_end_matches('\0').to_string());
+            }
+            NEW_FLOAT_EXT => {
+                let b = self.need(tag, 9)?;
+                self.leaf(tag, 9, f64::from_bits(be_u64(b, 1)).to_string());
+            }
+            ATOM_EXT | ATOM_UTF8_EXT => {
+                let len = 3 + be_u16(self.need(tag, 3)?, 1) as usize;
+                let b = self.need(tag, len)?;
+                self.leaf(tag, len, atom_preview(tag, &b[3..]));
+            }
+            SMALL_ATOM_EXT | SMALL_ATOM_UTF8_EXT => {
+                let len = 2 + self.need(tag, 2)?[1] as usize;
+                let b = self.need(tag, len)?;
+                self.leaf(tag, len, atom_preview(tag, &b[2..]));
+            }
+            ATOM_CACHE_REF => {
+                let b = self.need(tag, 2)?;
+                self.leaf(tag, 2, format!("index {}", b[1]));
+            }
+            SMALL_BIG_EXT => {
+                let len = 3 + self.need(tag, 2)?[1] as usize;
+                let b = self.need(tag, len)?;
+                self.leaf(tag, len, big_preview(b[2], &b[3..]));
+            }
+            LARGE_BIG_EXT => {
+                let len = 6 + be_u32(self.need(tag, 5)?, 1) as usize;
+                let b = self.need(tag, len)?;
+                self.leaf(tag, len, big_preview(b[5], &b[6..]));
+            }
+            SMALL_TUPLE_EXT => {
+                let arity = self.need(tag, 2)?[1] as usize;
+                self.open(tag, 2, arity, Trailer::None, format!("arity {arity}"));
+            }
+            LARGE_TUPLE_EXT => {
+                let arity = be_u32(self.need(tag, 5)?, 1) as usize;
+                self.open(tag, 5, arity, Trailer::None, format!("arity {arity}"));
+            }
+            NIL_EXT => self.leaf(tag, 1, "[]".to_string()),
+            STRING_EXT => {
+                let len = 3 + be_u16(self.need(tag, 3)?, 1) as usize;
+                let b = self.need(tag, len)?;
+                self.leaf(tag, len, format!("\"{}\"", latin1_preview(&b[3..])));
+            }
+            LIST_EXT => {
+                let count = be_u32(self.need(tag, 5)?, 1) as usize;
+                let preview = format!("{count} elements and a tail");
+                self.open(tag, 5, count.saturating_add(1), Trailer::None, preview);
+            }
+            MAP_EXT => {
+                let pairs = be_u32(self.need(tag, 5)?, 1) as usize;
+                let preview = format!("{pairs} pairs");
+                self.open(tag, 5, pairs.saturating_mul(2), Trailer::None, preview);
+            }
+            BINARY_EXT => {
+                let len = 5 + be_u32(self.need(tag, 5)?, 1) as usize;
+                let b = self.need(tag, len)?;
+                self.leaf(tag, len, binary_preview(&b[5..]));
+            }
+            BIT_BINARY_EXT => {
+                let len = 6 + be_u32(self.need(tag, 6)?, 1) as usize;
+                let b = self.need(tag, len)?;
+                let preview = format!("{}, {} bits in last byte", binary_preview(&b[6..]), b[5]);
+                self.leaf(tag, len, preview);
+            }
+            PID_EXT => self.open(tag, 1, 1, Trailer::Pid, String::new()),
+            NEW_PID_EXT => self.open(tag, 1, 1, Trailer::NewPid, String::new()),
+            PORT_EXT => self.open(tag, 1, 1, Trailer::Port, String::new()),
+            NEW_PORT_EXT => self.open(tag, 1, 1, Trailer::NewPort, String::new()),
+            V4_PORT_EXT => self.open(tag, 1, 1, Trailer::V4Port, String::new()),
+            REFERENCE_EXT => self.open(tag, 1, 1, Trailer::Reference, String::new()),
+            NEW_REFERENCE_EXT | NEWER_REFERENCE_EXT => {
+                let words = be_u16(self.need(tag, 3)?, 1) as usize;
+                let trailer = if tag == NEW_REFERENCE_EXT {
+                    Trailer::NewReference(words)
+                } else {
+                    Trailer::NewerReference(words)
+                };
+                self.open(tag, 3, 1, trailer, String::new());
+            }
+            EXPORT_EXT => self.open(tag, 1, 3, Trailer::None, String::new()),
+            NEW_FUN_EXT => {
+                let b = self.need(tag, 30)?;
+                let free = be_u32(b, 26) as usize;
+                let preview = format!(
+                    "arity {}, index {}, {free} free variables",
+                    b[5],
+                    be_u32(b, 22)
+                );
+                self.open(tag, 30, free.saturating_add(4), Trailer::None, preview);
+            }
+            LOCAL_EXT => {
+                let b = self.need(tag, 9)?;
+                let preview = format!("hash {:016x}", be_u64(b, 1));
+                self.open(tag, 9, 1, Trailer::None, preview);
+            }
+            COMPRESSED_EXT => {
+                let b = self.need(tag, 5)?;
+                let len = self.data.len() - self.pos;
+                let preview = format!(
+                    "{} bytes uncompressed, {} bytes of zlib data",
+                    be_u32(b, 1),
+                    len - 5
+                );
+                self.leaf(tag, len, preview);
+            }
+            DIST_HEADER | DIST_FRAG_HEADER => {
+                return Err(self.fail(1, "distribution header inside a term".to_string()));
+            }
+            _ => return Err(self.fail(1, format!("unknown tag {tag} (0x{tag:02x})"))),
+        }
+        Ok(())
+    }
+
+    fn dist_header(&mut self, tag: u8) -> Result<(), Halt> {
+        let mut len = if tag == DIST_FRAG_HEADER { 18 } else { 2 };
+        let b = self.need(tag, len)?;
+        let refs = b[len - 1] as usize;
+        let mut preview = format!("{refs} atom cache refs");
+        if tag == DIST_FRAG_HEADER {
+            preview = format!(
+                "sequence {}, fragment {}, {preview}",
+                be_u64(b, 1),
+                be_u64(b, 9)
+            );
+        }
+        if refs > 0 {
+            let flags_len = refs / 2 + 1;
+            len += flags_len;
+            let flags = &self.need(tag, len)?[len - flags_len..];
+            let long_atoms = dist_header_long_atoms(flags, refs);
+            let mut new_atoms = Vec::new();
+            for i in 0..refs {
+                let nibble = (flags[i / 2] >> (4 * (i % 2))) & 0x0F;
+                len += 1;
+                if nibble & 0x08 != 0 {
+                    let atom_len = if long_atoms {
+                        len += 2;
+                        be_u16(self.need(tag, len)?, len - 2) as usize
+                    } else {
+                        len += 1;
+                        self.need(tag, len)?[len - 1] as usize
+                    };
+                    len += atom_len;
+                    let b = self.need(tag, len)?;
+                    new_atoms.push(latin1_preview(&b[len - atom_len..]));
+                }
+            }
+            if !new_atoms.is_empty() {
+                let _ = write!(preview, ", new: {}", new_atoms.join(", "));
+            }
+        }
+        self.leaf(tag, len, preview);
+        Ok(())
+    }
+
+    fn leaf(&mut self, tag: u8, len: usize, preview: String) {
+        self.annotations.push(Annotation {
+            offset: self.pos,
+            depth: self.stack.len(),
+            tag,
+            name: tag_name(tag).unwrap_or("UNKNOWN"),
+            header_len: len,
+            length: len,
+            preview,
+            error: None,
+        });
+        self.pos += len;
+    }
+
+    fn open(
+        &mut self,
+        tag: u8,
+        header_len: usize,
+        children: usize,
+        trailer: Trailer,
+        preview: String,
+    ) {
+        let index = self.annotations.len();
+        self.leaf(tag, header_len, preview);
+        self.stack.push(Open {
+            index,
+            remaining: children,
+            trailer,
+        });
+    }
+
+    fn close(&mut self) -> Result<(), Halt> {
+        let Open { index, trailer, .. } = self.stack.pop().expect("an open element");
+        let trailer_len = trailer.len();
+        let available = self.data.len() - self.pos;
+        if available < trailer_len {
+            let tag = self.annotations[index].tag;
+            self.stack.push(Open {
+                index,
+                remaining: 0,
+                trailer,
+            });
+            self.annotations[index].error = Some(format!(
+                "truncated {}: expected {trailer_len} more bytes after the node, got {available}",
+                tag_name(tag).unwrap_or("UNKNOWN")
+            ));
+            self.end_open_elements();
+            return Err(Halt);
+        }
+        let b = &self.data[self.pos..self.pos + trailer_len];
+        self.pos += trailer_len;
+        let annotation = &mut self.annotations[index];
+        if !matches!(trailer, Trailer::None) {
+            annotation.preview = trailer.preview(b);
+        }
+        annotation.length = self.pos - annotation.offset;
+        Ok(())
+    }
+
+    /// Returns the first `len` bytes of the element at the current position,
+    /// or records a truncation error.
+    fn need(&mut self, tag: u8, len: usize) -> Result<&'a [u8], Halt> {
+        let data = self.data;
+        match data.get(self.pos..self.pos.saturating_add(len)) {
+            Some(b) => Ok(b),
+            None => {
+                let available = data.len() - self.pos;
+                Err(self.fail(
+                    available,
+                    format!(
+                        "truncated {}: expected {len} bytes, got {available}",
+                        tag_name(tag).unwrap_or("UNKNOWN")
+                    ),
+                ))
+            }
+        }
+    }
+
+    fn fail(&mut self, len: usize, error: String) -> Halt {
+        let tag = self.data.get(self.pos).copied().unwrap_or(0);
+        self.annotations.push(Annotation {
+            offset: self.pos,
+            depth: self.stack.len(),
+            tag,
+            name: tag_name(tag).unwrap_or("UNKNOWN"),
+            header_len: len,
+            length: len,
+            preview: String::new(),
+            error: Some(error),
+        });
+        self.pos += len;
+        self.end_open_elements();
+        Halt
+    }
+
+    /// Records how far every element that was still open got before inspection stopped.
+    fn end_open_elements(&mut self) {
+        for open in self.stack.drain(..) {
+            let annotation = &mut self.annotations[open.index];
+            annotation.length = self.pos - annotation.offset;
+        }
+    }
+}
+
+fn be_u16(b: &[u8], at: usize) -> u16 {
+    u16::from_be_bytes([b[at], b[at + 1]])
+}
+
+fn be_u32(b: &[u8], at: usize) -> u32 {
+    u32::from_be_bytes(b[at..at + 4].try_into().expect("four bytes"))
+}
+
+fn be_u64(b: &[u8], at: usize) -> u64 {
+    u64::from_be_bytes(b[at..at + 8].try_into().expect("eight bytes"))
+}
+
+fn words_of(b: &[u8], words: usize) -> Vec<u32> {
+    (0..words).map(|i| be_u32(b, i * 4)).collect()
+}
+
+fn atom_preview(tag: u8, b: &[u8]) -> String {
+    if tag == ATOM_UTF8_EXT || tag == SMALL_ATOM_UTF8_EXT {
+        let cut = &b[..b.len().min(MAX_PREVIEW_BYTES)];
+        let mut text = String::from_utf8_lossy(cut).into_owned();
+        if b.len() > MAX_PREVIEW_BYTES {
+            text.push_str("...");
+        }
+        text
+    } else {
+        latin1_preview(b)
+    }
+}
+
+fn latin1_preview(b: &[u8]) -> String {
+    let mut text: String = b
+        .iter()
+        .take(MAX_PREVIEW_BYTES)
+        .flat_map(|&c| (c as char).escape_debug())
+        .collect();
+    if b.len() > MAX_PREVIEW_BYTES {
+        text.push_str("...");
+    }
+    text
+}
+
+fn binary_preview(b: &[u8]) -> String {
+    let shown = &b[..b.len().min(MAX_PREVIEW_BYTES)];
+    let ellipsis = if b.len() > MAX_PREVIEW_BYTES {
+        "..."
+    } else {
+        ""
+    };
+    if shown.iter().all(|c| c.is_ascii_graphic() || *c == b' ') {
+        format!("<<\"{}{ellipsis}\">>", String::from_utf8_lossy(shown))
+    } else {
+        let bytes: Vec<String> = shown.iter().map(u8::to_string).collect();
+        format!("<<{}{ellipsis}>>", bytes.join(","))
+    }
+}
+
+fn big_preview(sign: u8, digits: &[u8]) -> String {
+    if digits.len() > 16 {
+        return format!("{} digit bytes", digits.len());
+    }
+    let magnitude = digits
+        .iter()
+        .rev()
+        .fold(0u128, |acc, &d| (acc << 8) | u128::from(d));
+    if sign == 0 {
+        magnitude.to_string()
+    } else {
+        format!("-{magnitude}")
+    }
+}
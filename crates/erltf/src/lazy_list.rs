@@ -0,0 +1,282 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Lists decoded on demand.
+//!
+//! A [`LazyList`] keeps the encoded bytes of a list and decodes its elements
+//! as they are iterated over, one at a time or in chunks, so that a list
+//! with millions of elements, such as the result of `ets:tab2list/1`, never has
+//! to be fully materialized. Elements are only checked as they are decoded.
+
+use crate::borrowed::BorrowedTerm;
+use crate::decoder::{decode, decode_next, decode_next_borrowed, locate, skip_term};
+use crate::errors::DecodeError;
+use crate::tags::{LIST_EXT, NIL_EXT, STRING_EXT, tag_name};
+use crate::term::OwnedTerm;
+use std::borrow::Cow;
+
+/// A list whose elements are decoded from its encoded bytes on demand.
+#[derive(Debug, Clone)]
+pub struct LazyList<'a> {
+    data: Cow<'a, [u8]>,
+    start: usize,
+    len: usize,
+    compact: bool,
+}
+
+/// The result of [`decode_lazily`].
+#[derive(Debug, Clone)]
+pub enum MaybeLazy<'a> {
+    Decoded(OwnedTerm),
+    Lazy(LazyList<'a>),
+}
+
+/// Decodes a versioned term, returning a [`LazyList`] instead if the term is a list
+/// of more than `threshold` elements.
+pub fn decode_lazily(data: &[u8], threshold: usize) -> Result<MaybeLazy<'_>, DecodeError> {
+    match LazyList::new(data) {
+        Ok(list) if list.len() > threshold => Ok(MaybeLazy::Lazy(list)),
+        _ => decode(data).map(MaybeLazy::Decoded),
+    }
+}
+
+impl<'a> LazyList<'a> {
+    /// Reads the header of a versioned term that must be a list, possibly compressed.
+    pub fn new(data: &'a [u8]) -> Result<Self, DecodeError> {
+        match locate(data, &[])? {
+            Some((data, offset)) => Self::from_buffer(data, offset),
+            None => Err(DecodeError::UnexpectedEof),
+        }
+    }
+
+    /// Like [`new`](Self::new), for the list found by following `path` as
+    /// [`decode_path`](crate::decode_path) does. Returns `None` if the path does not exist.
+    pub fn at_path(data: &'a [u8], path: &[&str]) -> Result<Option<Self>, DecodeError> {
+        locate(data, path)?
+            .map(|(data, offset)| Self::from_buffer(data, offset))
+            .transpose()
+    }
+
+    fn from_buffer(data: Cow<'a, [u8]>, offset: usize) -> Result<Self, DecodeError> {
+        let input = &data[offset..];
+        let (start, len, compact) = match input {
+            [NIL_EXT, ..] => (offset + 1, 0, true),
+            [STRING_EXT, a, b, rest @ ..] => {
+                let len = usize::from(u16::from_be_bytes([*a, *b]));
+                if rest.len() < len {
+                    return Err(DecodeError::UnexpectedEof);
+                }
+                (offset + 3, len, true)
+            }
+            [LIST_EXT, a, b, c, d, ..] => {
+                let len = u32::from_be_bytes([*a, *b, *c, *d]) as usize;
+                (offset + 5, len, false)
+            }
+            [] | [STRING_EXT, ..] | [LIST_EXT, ..] => return Err(DecodeError::UnexpectedEof),
+            [tag, ..] => {
+                return Err(DecodeError::InvalidFormat(format!(
+                    "expected a list, got {}",
+                    tag_name(*tag).unwrap_or("an unknown tag")
+                )));
+            }
+        };
+        Ok(Self {
+            data,
+            start,
+            len,
+            compact,
+        })
+    }
+
+    /// The number of elements, as declared by the list header.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Decodes the elements one at a time. Iteration stops after the first error.
+    pub fn iter(&self) -> LazyListIter<'_> {
+        LazyListIter {
+            input: &self.data[self.start..],
+            remaining: self.len,
+            compact: self.compact,
+        }
+    }
+
+    /// Like [`iter`](Self::iter), borrowing binaries and atoms from the encoded list.
+    pub fn iter_borrowed(&self) -> LazyListBorrowedIter<'_> {
+        LazyListBorrowedIter {
+            input: &self.data[self.start..],
+            remaining: self.len,
+            compact: self.compact,
+        }
+    }
+
+    /// Decodes the elements `size` at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn chunks(&self, size: usize) -> LazyListChunks<'_> {
+        assert!(size > 0, "chunk size must be greater than zero");
+        LazyListChunks {
+            iter: self.iter(),
+            size,
+        }
+    }
+
+    /// Decodes the element at `index`, skipping the ones before it without decoding them.
+    pub fn get(&self, index: usize) -> Result<Option<OwnedTerm>, DecodeError> {
+        if index >= self.len {
+            return Ok(None);
+        }
+        let input = &self.data[self.start..];
+        if self.compact {
+            return Ok(Some(OwnedTerm::Integer(i64::from(input[index]))));
+        }
+        let mut input = input;
+        for _ in 0..index {
+            input = skip_term(input)?;
+        }
+        decode_next(input).map(|(term, _)| Some(term))
+    }
+
+    /// Decodes the tail of the list, `[]` unless the list is improper.
+    pub fn tail(&self) -> Result<OwnedTerm, DecodeError> {
+        if self.compact {
+            return Ok(OwnedTerm::Nil);
+        }
+        let mut input = &self.data[self.start..];
+        for _ in 0..self.len {
+            input = skip_term(input)?;
+        }
+        decode_next(input).map(|(term, _)| term)
+    }
+
+    /// Decodes the whole list, like [`decode`] would.
+    pub fn to_term(&self) -> Result<OwnedTerm, DecodeError> {
+        let elements = self.iter().collect::<Result<Vec<_>, _>>()?;
+        match self.tail()? {
+            OwnedTerm::Nil if elements.is_empty() => Ok(OwnedTerm::Nil),
+            OwnedTerm::Nil => Ok(OwnedTerm::List(elements)),
+            tail => Ok(OwnedTerm::ImproperList {
+                elements,
+                tail: Box::new(tail),
+            }),
+        }
+    }
+}
+
+/// The elements of a [`LazyList`], see [`LazyList::iter`].
+#[derive(Debug, Clone)]
+pub struct LazyListIter<'l> {
+    input: &'l [u8],
+    remaining: usize,
+    compact: bool,
+}
+
+impl Iterator for LazyListIter<'_> {
+    type Item = Result<OwnedTerm, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        if self.compact {
+            let (&byte, rest) = self.input.split_first()?;
+            self.input = rest;
+            return Some(Ok(OwnedTerm::Integer(i64::from(byte))));
+        }
+        match decode_next(self.input) {
+            Ok((term, rest)) => {
+                self.input = rest;
+                Some(Ok(term))
+            }
+            Err(e) => {
+                self.remaining = 0;
+                Some(Err(e))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining))
+    }
+}
+
+/// The elements of a [`LazyList`], see [`LazyList::iter_borrowed`].
+#[derive(Debug, Clone)]
+pub struct LazyListBorrowedIter<'l> {
+    input: &'l [u8],
+    remaining: usize,
+    compact: bool,
+}
+
+impl<'l> Iterator for LazyListBorrowedIter<'l> {
+    type Item = Result<BorrowedTerm<'l>, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        if self.compact {
+            let (&byte, rest) = self.input.split_first()?;
+            self.input = rest;
+            return Some(Ok(BorrowedTerm::Integer(i64::from(byte))));
+        }
+        match decode_next_borrowed(self.input) {
+            Ok((term, rest)) => {
+                self.input = rest;
+                Some(Ok(term))
+            }
+            Err(e) => {
+                self.remaining = 0;
+                Some(Err(e))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining))
+    }
+}
+
+/// The elements of a [`LazyList`] in chunks, see [`LazyList::chunks`].
+#[derive(Debug, Clone)]
+pub struct LazyListChunks<'l> {
+    iter: LazyListIter<'l>,
+    size: usize,
+}
+
+impl Iterator for LazyListChunks<'_> {
+    type Item = Result<Vec<OwnedTerm>, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(self.size.min(self.iter.remaining));
+        for element in self.iter.by_ref().take(self.size) {
+            match element {
+                Ok(term) => chunk.push(term),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        (!chunk.is_empty()).then_some(Ok(chunk))
+    }
+}
@@ -13,49 +13,164 @@
 // limitations under the License.
 
 pub mod borrowed;
+pub mod bridge;
+pub mod canonical;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+pub mod convert;
+pub mod decode_config;
 pub mod decoder;
 pub mod encoder;
 pub mod errors;
+pub mod inspect;
+pub mod lazy_list;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+pub mod node_mapping;
+pub mod redact;
+pub mod shared;
 pub mod tags;
 pub mod term;
+pub mod term_helpers;
+pub mod truncate;
 pub mod types;
+pub mod walk;
 
 pub use borrowed::BorrowedTerm;
-pub use decoder::{AtomCache, decode, decode_borrowed, decode_with_atom_cache};
+pub use canonical::canonical_encode;
+pub use convert::{FromTerm, ToTerm};
+pub use decode_config::{AtomLimitPolicy, AtomTable, DecodeConfig};
+pub use decoder::{
+    AtomCache, DecodeAll, TermSummary, decode, decode_all, decode_all_with_atom_cache,
+    decode_all_with_config, decode_borrowed, decode_bytes_with_config, decode_path, decode_safe,
+    decode_with_atom_cache, decode_with_config, validate, validate_all,
+};
 pub use encoder::{
-    encode, encode_to_writer, encode_with_dist_header, encode_with_dist_header_multi,
+    AtomCacheReport, DistHeaderOptions, EncodeConfig, EncodeMode, FloatPolicy, MAX_ATOM_CHARACTERS,
+    OutgoingAtomCache, encode, encode_borrowed, encode_borrowed_with_dist_header, encode_to_writer,
+    encode_with_config, encode_with_dist_header, encode_with_dist_header_cached,
+    encode_with_dist_header_cached_and_mode, encode_with_dist_header_multi,
+    encode_with_dist_header_report, encode_with_mode,
 };
+#[cfg(feature = "derive")]
+pub use erltf_derive::{FromTerm, ToTerm};
 pub use errors::{
-    ContextualDecodeError, DecodeError, EncodeError, Error, ParsingContext, PathSegment, Result,
+    ContextualDecodeError, DecodeError, EncodeError, Error, ErrorSnippet, NodeMappingError,
+    ParsingContext, PathSegment, Result,
 };
-pub use term::{KeyValueAccess, OwnedTerm};
+pub use inspect::{Annotation, annotated_hex_dump, inspect};
+pub use lazy_list::{LazyList, MaybeLazy, decode_lazily};
+pub use node_mapping::NodeMapping;
+pub use redact::{RedactionRules, redact};
+pub use shared::SharedTerm;
+pub use term::{IMPROPER_LIST_TAG, KeyValueAccess, OwnedTerm};
+pub use term_helpers::ErlResult;
+pub use truncate::TruncatedDisplay;
 pub use types::{Atom, BigInt, ExternalPid, ExternalPort, ExternalReference, Mfa, Sign};
+pub use walk::{TermVisitor, Transform, WalkControl};
 
+/// Creates a tuple. Elements are converted with `Into<OwnedTerm>`,
+/// `..iter` splices in all elements of an iterable.
+///
+/// # Example
+/// ```
+/// use erltf::{erl_atom, erl_int, erl_tuple};
+///
+/// let rest = vec![erl_int!(2), erl_int!(3)];
+/// let tuple = erl_tuple![erl_atom!("ok"), ..rest, 4];
+///
+/// assert_eq!(tuple, erl_tuple![erl_atom!("ok"), 2, 3, 4]);
+/// ```
 #[macro_export]
 macro_rules! erl_tuple {
-    ($($elem:expr),* $(,)?) => {
-        $crate::OwnedTerm::Tuple(vec![$($elem.into()),*])
+    () => {
+        $crate::OwnedTerm::Tuple(Vec::new())
     };
+    ($($elems:tt)+) => {{
+        let mut elems: Vec<$crate::OwnedTerm> = Vec::new();
+        $crate::__erl_push_elems!(elems; $($elems)+);
+        $crate::OwnedTerm::Tuple(elems)
+    }};
 }
 
+/// Creates a list. Elements are converted with `Into<OwnedTerm>`,
+/// `..iter` splices in all elements of an iterable.
+///
+/// # Example
+/// ```
+/// use erltf::{erl_int, erl_list};
+///
+/// let existing = vec![erl_int!(1), erl_int!(2)];
+/// let list = erl_list![..existing, erl_int!(3), ..[4, 5]];
+///
+/// assert_eq!(list, erl_list![1, 2, 3, 4, 5]);
+/// ```
 #[macro_export]
 macro_rules! erl_list {
-    ($($elem:expr),* $(,)?) => {
-        $crate::OwnedTerm::List(vec![$($elem.into()),*])
+    () => {
+        $crate::OwnedTerm::List(Vec::new())
     };
+    ($($elems:tt)+) => {{
+        let mut elems: Vec<$crate::OwnedTerm> = Vec::new();
+        $crate::__erl_push_elems!(elems; $($elems)+);
+        $crate::OwnedTerm::List(elems)
+    }};
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __erl_push_elems {
+    ($elems:ident;) => {};
+    ($elems:ident; .. $spread:expr $(, $($rest:tt)*)?) => {
+        $elems.extend(::core::iter::IntoIterator::into_iter($spread).map(::core::convert::Into::<$crate::OwnedTerm>::into));
+        $($crate::__erl_push_elems!($elems; $($rest)*);)?
+    };
+    ($elems:ident; $elem:expr $(, $($rest:tt)*)?) => {
+        $elems.push($elem.into());
+        $($crate::__erl_push_elems!($elems; $($rest)*);)?
+    };
+}
+
+/// Creates a map from `key => value` pairs converted with `Into<OwnedTerm>`.
+/// `..iter` inserts all pairs of an iterable of `(key, value)` tuples.
+///
+/// # Example
+/// ```
+/// use erltf::{erl_atom, erl_bin, erl_map};
+///
+/// let headers = [("content-type", erl_bin!("text/plain"))];
+/// let map = erl_map! { erl_atom!("status") => 200, ..headers };
+///
+/// assert_eq!(map.as_map().map(|m| m.len()), Some(2));
+/// ```
 #[macro_export]
 macro_rules! erl_map {
-    ($($key:expr => $value:expr),* $(,)?) => {{
-        let mut map = std::collections::BTreeMap::new();
-        $(
-            map.insert($key.into(), $value.into());
-        )*
-        $crate::OwnedTerm::Map(map)
+    () => {
+        $crate::OwnedTerm::Map(Default::default())
+    };
+    ($($entries:tt)+) => {{
+        let mut entries: Vec<($crate::OwnedTerm, $crate::OwnedTerm)> = Vec::new();
+        $crate::__erl_insert_entries!(entries; $($entries)+);
+        $crate::OwnedTerm::Map(entries.into_iter().collect())
     }};
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __erl_insert_entries {
+    ($entries:ident;) => {};
+    ($entries:ident; .. $spread:expr $(, $($rest:tt)*)?) => {
+        for (key, value) in $spread {
+            $entries.push((key.into(), value.into()));
+        }
+        $($crate::__erl_insert_entries!($entries; $($rest)*);)?
+    };
+    ($entries:ident; $key:expr => $value:expr $(, $($rest:tt)*)?) => {
+        $entries.push(($key.into(), $value.into()));
+        $($crate::__erl_insert_entries!($entries; $($rest)*);)?
+    };
+}
+
 #[macro_export]
 macro_rules! erl_atom {
     ($name:expr) => {
@@ -77,6 +192,30 @@ macro_rules! erl_int {
     };
 }
 
+#[macro_export]
+macro_rules! erl_float {
+    ($val:expr) => {
+        $crate::OwnedTerm::Float($val as f64)
+    };
+}
+
+/// Creates a binary from anything that implements `AsRef<[u8]>`: byte string literals,
+/// byte slices and vectors, and strings, which become UTF-8 binaries (Elixir strings).
+///
+/// # Example
+/// ```
+/// use erltf::{OwnedTerm, erl_bin};
+///
+/// assert_eq!(erl_bin!(b"\x01\x02"), OwnedTerm::Binary(vec![1, 2]));
+/// assert_eq!(erl_bin!("héllo").as_binary(), Some("héllo".as_bytes()));
+/// ```
+#[macro_export]
+macro_rules! erl_bin {
+    ($val:expr) => {
+        $crate::OwnedTerm::Binary(::core::convert::AsRef::<[u8]>::as_ref(&$val).to_vec())
+    };
+}
+
 /// Creates an Elixir-style keyword list (list of 2-tuples with atom keys).
 ///
 /// # Example
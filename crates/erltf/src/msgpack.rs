@@ -0,0 +1,130 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion between terms and MessagePack values. Requires the `msgpack` feature.
+//!
+//! See [`crate::bridge`] for the options and how Erlang-only types are carried.
+
+use crate::bridge::{
+    BridgeConfig, MSGPACK_EXT_TYPE, Opaque, atom_from_bool, bigint_to_i128, from_tagged,
+    integer_term, opaque, tagged,
+};
+use crate::errors::BridgeError;
+use crate::term::OwnedTerm;
+use rmpv::Value;
+use std::collections::BTreeMap;
+use std::str;
+
+/// Converts a term to a MessagePack value.
+pub fn to_msgpack(term: &OwnedTerm, config: &BridgeConfig) -> Result<Value, BridgeError> {
+    match term {
+        OwnedTerm::Shared(shared) => to_msgpack(shared, config),
+        OwnedTerm::Atom(atom) => match atom.as_str() {
+            "true" => Ok(Value::Boolean(true)),
+            "false" => Ok(Value::Boolean(false)),
+            _ if *atom == config.nil_atom => Ok(Value::Nil),
+            name if config.atoms_as_strings => Ok(Value::from(name)),
+            _ => from_opaque(tagged(term)?),
+        },
+        OwnedTerm::Integer(i) => Ok(Value::from(*i)),
+        OwnedTerm::BigInt(big) => match bigint_to_i128(big).and_then(|v| u64::try_from(v).ok()) {
+            Some(value) => Ok(Value::from(value)),
+            None => from_opaque(opaque(term, config.erlang_types)?),
+        },
+        OwnedTerm::Float(f) => Ok(Value::F64(*f)),
+        OwnedTerm::Binary(bytes) => Ok(binary_to_value(bytes, config)),
+        OwnedTerm::SharedBinary(bytes) => Ok(binary_to_value(bytes, config)),
+        OwnedTerm::String(s) => Ok(Value::from(s.as_str())),
+        OwnedTerm::List(elements) => to_array(elements, config),
+        OwnedTerm::ByteList(bytes) => Ok(Value::Array(
+            bytes.iter().map(|&b| Value::from(b)).collect(),
+        )),
+        OwnedTerm::Tuple(elements) if config.tuples_as_arrays => to_array(elements, config),
+        OwnedTerm::Tuple(_) => from_opaque(tagged(term)?),
+        OwnedTerm::Nil => Ok(Value::Array(Vec::new())),
+        OwnedTerm::Map(map) => map
+            .iter()
+            .map(|(k, v)| Ok((to_msgpack(k, config)?, to_msgpack(v, config)?)))
+            .collect::<Result<Vec<_>, BridgeError>>()
+            .map(Value::Map),
+        OwnedTerm::OrderedMap(entries) => entries
+            .iter()
+            .map(|(k, v)| Ok((to_msgpack(k, config)?, to_msgpack(v, config)?)))
+            .collect::<Result<Vec<_>, BridgeError>>()
+            .map(Value::Map),
+        OwnedTerm::BitBinary { .. }
+        | OwnedTerm::ImproperList { .. }
+        | OwnedTerm::Pid(_)
+        | OwnedTerm::Port(_)
+        | OwnedTerm::Reference(_)
+        | OwnedTerm::ExternalFun(_)
+        | OwnedTerm::InternalFun(_) => from_opaque(opaque(term, config.erlang_types)?),
+    }
+}
+
+/// Converts a MessagePack value to a term. Strings become binaries, never atoms.
+pub fn from_msgpack(value: &Value, config: &BridgeConfig) -> Result<OwnedTerm, BridgeError> {
+    match value {
+        Value::Nil => Ok(OwnedTerm::Atom(config.nil_atom.clone())),
+        Value::Boolean(b) => Ok(atom_from_bool(*b)),
+        Value::Integer(i) => match (i.as_i64(), i.as_u64()) {
+            (Some(value), _) => Ok(OwnedTerm::Integer(value)),
+            (None, Some(value)) => Ok(integer_term(i128::from(value))),
+            (None, None) => Err(BridgeError::InvalidValue(format!("integer {i}"))),
+        },
+        Value::F32(f) => Ok(OwnedTerm::Float(f64::from(*f))),
+        Value::F64(f) => Ok(OwnedTerm::Float(*f)),
+        Value::String(s) => Ok(OwnedTerm::Binary(s.as_bytes().to_vec())),
+        Value::Binary(bytes) => Ok(OwnedTerm::Binary(bytes.clone())),
+        Value::Array(elements) if elements.is_empty() => Ok(OwnedTerm::Nil),
+        Value::Array(elements) => elements
+            .iter()
+            .map(|e| from_msgpack(e, config))
+            .collect::<Result<Vec<_>, _>>()
+            .map(OwnedTerm::List),
+        Value::Map(entries) => entries
+            .iter()
+            .map(|(k, v)| Ok((from_msgpack(k, config)?, from_msgpack(v, config)?)))
+            .collect::<Result<BTreeMap<_, _>, BridgeError>>()
+            .map(OwnedTerm::Map),
+        Value::Ext(MSGPACK_EXT_TYPE, payload) => from_tagged(payload),
+        Value::Ext(ext_type, _) => Err(BridgeError::InvalidValue(format!(
+            "unknown extension type {ext_type}"
+        ))),
+    }
+}
+
+fn to_array(elements: &[OwnedTerm], config: &BridgeConfig) -> Result<Value, BridgeError> {
+    elements
+        .iter()
+        .map(|e| to_msgpack(e, config))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Value::Array)
+}
+
+fn from_opaque(opaque: Opaque) -> Result<Value, BridgeError> {
+    Ok(match opaque {
+        Opaque::Tagged(payload) => Value::Ext(MSGPACK_EXT_TYPE, payload),
+        Opaque::Text(text) => Value::from(text),
+    })
+}
+
+fn binary_to_value(bytes: &[u8], config: &BridgeConfig) -> Value {
+    if config.binaries_as_strings
+        && let Ok(s) = str::from_utf8(bytes)
+    {
+        return Value::from(s);
+    }
+    Value::Binary(bytes.to_vec())
+}
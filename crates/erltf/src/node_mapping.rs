@@ -0,0 +1,154 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rewriting the node of pids, ports and references in a term.
+//!
+//! A gateway that relays messages between two clusters with different node names
+//! rewrites the identifiers in a message before forwarding it with
+//! [`NodeMapping::rewrite`], and restores the identifiers in replies with
+//! [`NodeMapping::restore`]. Atoms, including atoms that happen to be node names,
+//! are left unchanged.
+
+use crate::errors::NodeMappingError;
+use crate::term::OwnedTerm;
+use crate::types::{Atom, ExternalPid, ExternalPort, ExternalReference};
+use crate::walk::Transform;
+use std::collections::HashMap;
+
+/// A one-to-one mapping between node names, kept in both directions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeMapping {
+    forward: HashMap<Atom, Atom>,
+    reverse: HashMap<Atom, Atom>,
+}
+
+impl NodeMapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `from` to `to`. Every node can be mapped once in each direction,
+    /// otherwise replies could not be routed back.
+    pub fn insert(
+        &mut self,
+        from: impl Into<Atom>,
+        to: impl Into<Atom>,
+    ) -> Result<(), NodeMappingError> {
+        let (from, to) = (from.into(), to.into());
+        if let Some(existing) = self.forward.get(&from) {
+            if *existing == to {
+                return Ok(());
+            }
+            return Err(NodeMappingError::SourceAlreadyMapped {
+                node: from,
+                existing: existing.clone(),
+            });
+        }
+        if let Some(existing) = self.reverse.get(&to) {
+            return Err(NodeMappingError::TargetAlreadyMapped {
+                node: to,
+                existing: existing.clone(),
+            });
+        }
+        self.forward.insert(from.clone(), to.clone());
+        self.reverse.insert(to, from);
+        Ok(())
+    }
+
+    /// Removes the mapping for `from` and returns the node it was mapped to.
+    pub fn remove(&mut self, from: &Atom) -> Option<Atom> {
+        let to = self.forward.remove(from)?;
+        self.reverse.remove(&to);
+        Some(to)
+    }
+
+    pub fn get(&self, from: &Atom) -> Option<&Atom> {
+        self.forward.get(from)
+    }
+
+    pub fn get_reverse(&self, to: &Atom) -> Option<&Atom> {
+        self.reverse.get(to)
+    }
+
+    pub fn len(&self) -> usize {
+        self.forward.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.forward.is_empty()
+    }
+
+    /// Returns the mapping with the directions swapped.
+    pub fn inverse(&self) -> Self {
+        Self {
+            forward: self.reverse.clone(),
+            reverse: self.forward.clone(),
+        }
+    }
+
+    /// Replaces mapped nodes in every pid, port and reference in `term`.
+    pub fn rewrite(&self, term: OwnedTerm) -> OwnedTerm {
+        rewrite_nodes(term, &self.forward)
+    }
+
+    /// Undoes [`NodeMapping::rewrite`].
+    pub fn restore(&self, term: OwnedTerm) -> OwnedTerm {
+        rewrite_nodes(term, &self.reverse)
+    }
+
+    pub fn rewrite_pid(&self, pid: &ExternalPid) -> ExternalPid {
+        map_pid(pid, &self.forward).unwrap_or_else(|| pid.clone())
+    }
+
+    pub fn restore_pid(&self, pid: &ExternalPid) -> ExternalPid {
+        map_pid(pid, &self.reverse).unwrap_or_else(|| pid.clone())
+    }
+}
+
+fn rewrite_nodes(term: OwnedTerm, table: &HashMap<Atom, Atom>) -> OwnedTerm {
+    if table.is_empty() {
+        return term;
+    }
+    term.transform(|t| match t {
+        OwnedTerm::Pid(pid) => {
+            Transform::Replace(OwnedTerm::Pid(map_pid(&pid, table).unwrap_or(pid)))
+        }
+        OwnedTerm::Port(port) => Transform::Replace(match table.get(&port.node) {
+            Some(node) => OwnedTerm::Port(ExternalPort::new(node.clone(), port.id, port.creation)),
+            None => OwnedTerm::Port(port),
+        }),
+        OwnedTerm::Reference(reference) => Transform::Replace(match table.get(&reference.node) {
+            Some(node) => OwnedTerm::Reference(ExternalReference::new(
+                node.clone(),
+                reference.creation,
+                reference.ids,
+            )),
+            None => OwnedTerm::Reference(reference),
+        }),
+        OwnedTerm::InternalFun(mut fun) => {
+            if let Some(pid) = map_pid(&fun.pid, table) {
+                fun.pid = pid;
+            }
+            Transform::Descend(OwnedTerm::InternalFun(fun))
+        }
+        other => Transform::Descend(other),
+    })
+}
+
+// LOCAL_EXT bytes embed the original node, so rewritten identifiers drop them
+fn map_pid(pid: &ExternalPid, table: &HashMap<Atom, Atom>) -> Option<ExternalPid> {
+    table
+        .get(&pid.node)
+        .map(|node| ExternalPid::new(node.clone(), pid.id, pid.serial, pid.creation))
+}
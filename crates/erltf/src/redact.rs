@@ -0,0 +1,215 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Redaction of sensitive values before logging terms.
+//!
+//! [`redact`] returns a copy of a term with the values of sensitive keys,
+//! values at specific paths and oversized binaries replaced by a placeholder.
+//! Keys are matched in maps and in `{Key, Value}` tuples (proplist entries),
+//! whether they are atoms, binaries or strings, ignoring ASCII case.
+
+use crate::term::OwnedTerm;
+use crate::walk::Transform;
+use std::str;
+
+pub const DEFAULT_PLACEHOLDER: &str = "[redacted]";
+
+/// Matches any key or list element in a path passed to [`RedactionRules::with_path`].
+pub const PATH_WILDCARD: &str = "*";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactionRules {
+    keys: Vec<String>,
+    paths: Vec<Vec<String>>,
+    max_binary_len: Option<usize>,
+    placeholder: OwnedTerm,
+}
+
+impl Default for RedactionRules {
+    fn default() -> Self {
+        Self {
+            keys: Vec::new(),
+            paths: Vec::new(),
+            max_binary_len: None,
+            placeholder: OwnedTerm::binary(DEFAULT_PLACEHOLDER.as_bytes().to_vec()),
+        }
+    }
+}
+
+impl RedactionRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rules that redact common credential keys such as `password`, `secret` and `token`.
+    pub fn credentials() -> Self {
+        Self::new().with_keys([
+            "password",
+            "passwd",
+            "secret",
+            "token",
+            "access_token",
+            "refresh_token",
+            "api_key",
+            "cookie",
+            "authorization",
+        ])
+    }
+
+    /// Redacts the value of `key` wherever it appears.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.keys.push(key.into().to_ascii_lowercase());
+        self
+    }
+
+    pub fn with_keys<I, S>(self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        keys.into_iter()
+            .fold(self, |rules, key| rules.with_key(key))
+    }
+
+    /// Redacts the value reached by following `path` from the root term,
+    /// e.g. `["config", "credentials", "password"]`. [`PATH_WILDCARD`] matches any key
+    /// or list element.
+    pub fn with_path<I, S>(mut self, path: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.paths.push(
+            path.into_iter()
+                .map(|s| s.into().to_ascii_lowercase())
+                .collect(),
+        );
+        self
+    }
+
+    /// Redacts binaries and strings longer than `max_len` bytes.
+    pub fn with_max_binary_len(mut self, max_len: usize) -> Self {
+        self.max_binary_len = Some(max_len);
+        self
+    }
+
+    pub fn with_placeholder(mut self, placeholder: OwnedTerm) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+
+    fn is_sensitive_key(&self, key: &OwnedTerm) -> bool {
+        key_name(key).is_some_and(|name| self.keys.iter().any(|k| k.eq_ignore_ascii_case(name)))
+    }
+
+    fn is_oversized(&self, term: &OwnedTerm) -> bool {
+        let Some(max_len) = self.max_binary_len else {
+            return false;
+        };
+        match term.resolve() {
+            OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) => term.len() > max_len,
+            OwnedTerm::BitBinary { bytes, .. } => bytes.len() > max_len,
+            OwnedTerm::String(s) => s.len() > max_len,
+            _ => false,
+        }
+    }
+}
+
+/// Returns a copy of `term` with sensitive values replaced according to `rules`.
+pub fn redact(term: &OwnedTerm, rules: &RedactionRules) -> OwnedTerm {
+    let mut term = term.clone();
+    for path in &rules.paths {
+        redact_path(&mut term, path, &rules.placeholder);
+    }
+    if rules.keys.is_empty() && rules.max_binary_len.is_none() {
+        return term;
+    }
+
+    term.transform(|t| match t {
+        t if rules.is_oversized(&t) => Transform::Replace(rules.placeholder.clone()),
+        OwnedTerm::Map(mut map) => {
+            for (k, v) in map.iter_mut() {
+                if rules.is_sensitive_key(k) {
+                    *v = rules.placeholder.clone();
+                }
+            }
+            Transform::Descend(OwnedTerm::Map(map))
+        }
+        OwnedTerm::OrderedMap(mut entries) => {
+            for (k, v) in entries.iter_mut() {
+                if rules.is_sensitive_key(k) {
+                    *v = rules.placeholder.clone();
+                }
+            }
+            Transform::Descend(OwnedTerm::OrderedMap(entries))
+        }
+        OwnedTerm::Tuple(mut elements)
+            if elements.len() == 2 && rules.is_sensitive_key(&elements[0]) =>
+        {
+            elements[1] = rules.placeholder.clone();
+            Transform::Descend(OwnedTerm::Tuple(elements))
+        }
+        other => Transform::Descend(other),
+    })
+}
+
+fn redact_path(term: &mut OwnedTerm, path: &[String], placeholder: &OwnedTerm) {
+    let Some((segment, rest)) = path.split_first() else {
+        *term = placeholder.clone();
+        return;
+    };
+    let wildcard = segment == PATH_WILDCARD;
+    let matches = |key: &OwnedTerm| {
+        wildcard || key_name(key).is_some_and(|k| k.eq_ignore_ascii_case(segment))
+    };
+
+    match term.make_mut() {
+        OwnedTerm::Map(map) => {
+            for (k, v) in map.iter_mut() {
+                if matches(k) {
+                    redact_path(v, rest, placeholder);
+                }
+            }
+        }
+        OwnedTerm::OrderedMap(entries) => {
+            for (k, v) in entries.iter_mut() {
+                if matches(k) {
+                    redact_path(v, rest, placeholder);
+                }
+            }
+        }
+        OwnedTerm::List(elements) => {
+            for element in elements.iter_mut() {
+                if wildcard {
+                    redact_path(element, rest, placeholder);
+                } else if let OwnedTerm::Tuple(pair) = element.make_mut()
+                    && pair.len() == 2
+                    && matches(&pair[0])
+                {
+                    redact_path(&mut pair[1], rest, placeholder);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn key_name(key: &OwnedTerm) -> Option<&str> {
+    match key.resolve() {
+        OwnedTerm::Atom(atom) => Some(atom.as_str()),
+        OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) => str::from_utf8(key.as_binary()?).ok(),
+        OwnedTerm::String(s) => Some(s),
+        _ => None,
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reference-counted terms for fanning out one message to many consumers and for
+//! sharing subterms between terms.
+
+use crate::term::OwnedTerm;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A cheap-clone handle to an immutable [`OwnedTerm`].
+///
+/// Cloning a `SharedTerm` copies a pointer, not the term. Embedded in other terms as an
+/// [`OwnedTerm::Shared`], it lets them share subterms, e.g. one payload in the messages
+/// to many processes. Mutation goes through [`SharedTerm::make_mut`], which deep-clones
+/// the term if other handles to it exist.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct SharedTerm(Arc<OwnedTerm>);
+
+impl SharedTerm {
+    pub fn new(term: OwnedTerm) -> Self {
+        Self(Arc::new(term))
+    }
+
+    /// Returns a mutable reference to the term, cloning it first if other
+    /// handles to it exist.
+    pub fn make_mut(&mut self) -> &mut OwnedTerm {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// Applies `f` to a private copy of the term and returns the result.
+    /// The term is cloned only if it is shared.
+    pub fn update<F: FnOnce(&mut OwnedTerm)>(mut self, f: F) -> Self {
+        f(self.make_mut());
+        self
+    }
+
+    /// Returns the term, cloning it only if other handles to it exist.
+    pub fn into_owned(self) -> OwnedTerm {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone())
+    }
+
+    #[must_use]
+    pub fn ref_count(&self) -> usize {
+        Arc::strong_count(&self.0)
+    }
+
+    #[must_use]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    pub fn as_arc(&self) -> &Arc<OwnedTerm> {
+        &self.0
+    }
+
+    pub fn into_arc(self) -> Arc<OwnedTerm> {
+        self.0
+    }
+}
+
+impl Deref for SharedTerm {
+    type Target = OwnedTerm;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<OwnedTerm> for SharedTerm {
+    fn as_ref(&self) -> &OwnedTerm {
+        &self.0
+    }
+}
+
+impl From<OwnedTerm> for SharedTerm {
+    fn from(term: OwnedTerm) -> Self {
+        Self::new(term)
+    }
+}
+
+impl From<Arc<OwnedTerm>> for SharedTerm {
+    fn from(term: Arc<OwnedTerm>) -> Self {
+        Self(term)
+    }
+}
+
+impl From<SharedTerm> for OwnedTerm {
+    fn from(term: SharedTerm) -> Self {
+        term.into_owned()
+    }
+}
+
+impl fmt::Debug for SharedTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for SharedTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+impl OwnedTerm {
+    /// Moves the term behind a reference count so that it can be handed to
+    /// many consumers without deep copies.
+    pub fn shared(self) -> SharedTerm {
+        SharedTerm::new(self)
+    }
+
+    /// Moves the term behind a reference count, as an [`OwnedTerm::Shared`] that can be
+    /// embedded in other terms or handed to many consumers: clones of it share one copy.
+    ///
+    /// Atoms, numbers, pids, ports, references, external funs and `[]` are returned as
+    /// they are, since cloning them costs about as much as a reference count.
+    pub fn share(self) -> Self {
+        match self {
+            OwnedTerm::Shared(_)
+            | OwnedTerm::Atom(_)
+            | OwnedTerm::Integer(_)
+            | OwnedTerm::Float(_)
+            | OwnedTerm::Pid(_)
+            | OwnedTerm::Port(_)
+            | OwnedTerm::Reference(_)
+            | OwnedTerm::SharedBinary(_)
+            | OwnedTerm::ExternalFun(_)
+            | OwnedTerm::Nil => self,
+            other => OwnedTerm::Shared(SharedTerm::new(other)),
+        }
+    }
+
+    /// Returns the term an [`OwnedTerm::Shared`] wraps, or this term, for matching on
+    /// its variant.
+    pub fn resolve(&self) -> &Self {
+        match self {
+            OwnedTerm::Shared(shared) => shared.resolve(),
+            other => other,
+        }
+    }
+
+    /// Takes the term out of an [`OwnedTerm::Shared`], cloning it only if other terms
+    /// share it. Other terms are returned as they are.
+    pub fn unshare(self) -> Self {
+        match self {
+            OwnedTerm::Shared(shared) => shared.into_owned().unshare(),
+            other => other,
+        }
+    }
+
+    /// Returns a mutable reference to the term an [`OwnedTerm::Shared`] wraps, cloning
+    /// it first if other terms share it, or to this term.
+    pub fn make_mut(&mut self) -> &mut Self {
+        match self {
+            OwnedTerm::Shared(shared) => shared.make_mut().make_mut(),
+            other => other,
+        }
+    }
+}
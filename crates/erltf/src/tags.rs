@@ -57,6 +57,7 @@ pub const NEW_REFERENCE_EXT: u8 = 114;
 
 // Process/Port/Reference tags (modern)
 pub const NEW_PID_EXT: u8 = 88;
+pub const NEW_PORT_EXT: u8 = 89;
 pub const NEWER_REFERENCE_EXT: u8 = 90;
 pub const V4_PORT_EXT: u8 = 120;
 
@@ -70,6 +71,50 @@ pub const EXPORT_EXT: u8 = 113;
 // Distribution header tags
 pub const DIST_HEADER: u8 = 68;
 pub const DIST_FRAG_HEADER: u8 = 69;
+/// Shares its value with [`NEW_FLOAT_EXT`] but only follows the version byte of a frame
+pub const DIST_FRAG_CONT: u8 = 70;
 
 // Compression
 pub const COMPRESSED_EXT: u8 = 80;
+
+/// Returns the symbolic name of a tag byte, e.g. `"SMALL_TUPLE_EXT"` for 104.
+pub fn tag_name(tag: u8) -> Option<&'static str> {
+    let name = match tag {
+        VERSION => "VERSION",
+        ATOM_EXT => "ATOM_EXT",
+        SMALL_ATOM_EXT => "SMALL_ATOM_EXT",
+        ATOM_UTF8_EXT => "ATOM_UTF8_EXT",
+        SMALL_ATOM_UTF8_EXT => "SMALL_ATOM_UTF8_EXT",
+        ATOM_CACHE_REF => "ATOM_CACHE_REF",
+        SMALL_INTEGER_EXT => "SMALL_INTEGER_EXT",
+        INTEGER_EXT => "INTEGER_EXT",
+        SMALL_BIG_EXT => "SMALL_BIG_EXT",
+        LARGE_BIG_EXT => "LARGE_BIG_EXT",
+        FLOAT_EXT => "FLOAT_EXT",
+        NEW_FLOAT_EXT => "NEW_FLOAT_EXT",
+        SMALL_TUPLE_EXT => "SMALL_TUPLE_EXT",
+        LARGE_TUPLE_EXT => "LARGE_TUPLE_EXT",
+        NIL_EXT => "NIL_EXT",
+        STRING_EXT => "STRING_EXT",
+        LIST_EXT => "LIST_EXT",
+        MAP_EXT => "MAP_EXT",
+        BINARY_EXT => "BINARY_EXT",
+        BIT_BINARY_EXT => "BIT_BINARY_EXT",
+        REFERENCE_EXT => "REFERENCE_EXT",
+        PORT_EXT => "PORT_EXT",
+        PID_EXT => "PID_EXT",
+        NEW_REFERENCE_EXT => "NEW_REFERENCE_EXT",
+        NEW_PID_EXT => "NEW_PID_EXT",
+        NEW_PORT_EXT => "NEW_PORT_EXT",
+        NEWER_REFERENCE_EXT => "NEWER_REFERENCE_EXT",
+        V4_PORT_EXT => "V4_PORT_EXT",
+        LOCAL_EXT => "LOCAL_EXT",
+        NEW_FUN_EXT => "NEW_FUN_EXT",
+        EXPORT_EXT => "EXPORT_EXT",
+        DIST_HEADER => "DIST_HEADER",
+        DIST_FRAG_HEADER => "DIST_FRAG_HEADER",
+        COMPRESSED_EXT => "COMPRESSED_EXT",
+        _ => return None,
+    };
+    Some(name)
+}
@@ -13,15 +13,19 @@
 // limitations under the License.
 
 use crate::errors::TermConversionError;
+use crate::shared::SharedTerm;
 use crate::types::{
     Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun, Mfa, Sign,
 };
+use crate::walk::WalkControl;
+use bytes::Bytes;
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt;
 use std::hash::{BuildHasher, Hash, Hasher};
 use std::mem::discriminant;
 use std::ops::Index;
+use std::str;
 use std::sync::{Arc, OnceLock};
 
 #[cfg(feature = "serde")]
@@ -29,7 +33,11 @@ use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
 
-#[derive(Debug, Clone, PartialEq, Default)]
+/// Map key used by the serde representation of improper lists: a single-entry
+/// map whose value is the `(elements, tail)` pair.
+pub const IMPROPER_LIST_TAG: &str = "__erltf_improper_list__";
+
+#[derive(Debug, Clone, Default)]
 pub enum OwnedTerm {
     Atom(Atom),
     Integer(i64),
@@ -38,18 +46,30 @@ pub enum OwnedTerm {
     Port(ExternalPort),
     Reference(ExternalReference),
     Binary(Vec<u8>),
+    /// A binary that shares the buffer it was decoded from instead of owning a copy.
+    /// Decoded from `BINARY_EXT`, see [`DecodeConfig::with_shared_binaries`](crate::DecodeConfig::with_shared_binaries)
+    SharedBinary(Bytes),
     BitBinary {
         bytes: Vec<u8>,
         bits: u8,
     },
     String(String),
     List(Vec<Self>),
+    /// A list of integers in the 0..=255 range, such as a Latin-1 charlist, kept as bytes.
+    /// Decoded from `STRING_EXT`, see [`DecodeConfig::with_byte_lists`](crate::DecodeConfig::with_byte_lists)
+    ByteList(Vec<u8>),
     ImproperList {
         elements: Vec<Self>,
         tail: Box<OwnedTerm>,
     },
     Map(BTreeMap<Self, Self>),
+    /// A map that keeps its entries in the order they were decoded or inserted,
+    /// see [`DecodeConfig::with_ordered_maps`](crate::DecodeConfig::with_ordered_maps)
+    OrderedMap(Vec<(Self, Self)>),
     Tuple(Vec<Self>),
+    /// A term behind a reference count, shared with other terms instead of copied.
+    /// Compares, hashes and encodes like the term it wraps, see [`OwnedTerm::share`]
+    Shared(SharedTerm),
     BigInt(BigInt),
     ExternalFun(ExternalFun),
     InternalFun(Box<InternalFun>),
@@ -153,13 +173,48 @@ pub trait KeyValueAccess {
         self.kv_get_mfa_string(key)
             .unwrap_or_else(|| default.to_string())
     }
+
+    /// Retrieve the value associated with a binary (Elixir string) key
+    fn kv_get_binary_key(&self, _key: &str) -> Option<&OwnedTerm> {
+        None
+    }
+
+    /// Retrieve the value associated with an atom key, falling back to a binary key
+    fn kv_get_any_key(&self, key: &str) -> Option<&OwnedTerm> {
+        self.kv_get(key).or_else(|| self.kv_get_binary_key(key))
+    }
+
+    /// Retrieve an i64 value by atom or binary key
+    fn kv_get_any_key_i64(&self, key: &str) -> Option<i64> {
+        self.kv_get_any_key(key).and_then(|t| t.as_integer())
+    }
+
+    /// Retrieve a boolean value by atom or binary key
+    fn kv_get_any_key_bool(&self, key: &str) -> Option<bool> {
+        self.kv_get_any_key(key).and_then(|t| t.as_bool())
+    }
+
+    /// Retrieve a string value by atom or binary key
+    fn kv_get_any_key_string(&self, key: &str) -> Option<String> {
+        self.kv_get_any_key(key).and_then(|t| t.as_erlang_string())
+    }
 }
 
 impl KeyValueAccess for OwnedTerm {
     fn kv_get(&self, key: &str) -> Option<&OwnedTerm> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Map(_) => self.map_get_atom_key(key),
-            OwnedTerm::List(_) => self.proplist_get_atom_key(key),
+            OwnedTerm::List(_) | OwnedTerm::ImproperList { .. } => self.proplist_get_atom_key(key),
+            _ => None,
+        }
+    }
+
+    fn kv_get_binary_key(&self, key: &str) -> Option<&OwnedTerm> {
+        match self.resolve() {
+            OwnedTerm::Map(_) => self.map_get_binary_key(key),
+            OwnedTerm::List(_) | OwnedTerm::ImproperList { .. } => {
+                self.proplist_get_binary_key(key)
+            }
             _ => None,
         }
     }
@@ -182,6 +237,11 @@ impl OwnedTerm {
         OwnedTerm::Binary(data)
     }
 
+    /// A binary backed by a reference-counted buffer, cloned without copying.
+    pub fn shared_binary<B: Into<Bytes>>(data: B) -> Self {
+        OwnedTerm::SharedBinary(data.into())
+    }
+
     pub fn string<S: Into<String>>(value: S) -> Self {
         OwnedTerm::String(value.into())
     }
@@ -190,6 +250,11 @@ impl OwnedTerm {
         OwnedTerm::List(elements)
     }
 
+    /// A list of integers in the 0..=255 range, kept as bytes.
+    pub fn byte_list<B: Into<Vec<u8>>>(bytes: B) -> Self {
+        OwnedTerm::ByteList(bytes.into())
+    }
+
     pub fn improper_list(elements: Vec<Self>, tail: Self) -> Self {
         OwnedTerm::ImproperList {
             elements,
@@ -201,6 +266,11 @@ impl OwnedTerm {
         OwnedTerm::Map(entries)
     }
 
+    /// A map that is encoded with its entries in the given order.
+    pub fn ordered_map(entries: Vec<(Self, Self)>) -> Self {
+        OwnedTerm::OrderedMap(entries)
+    }
+
     pub fn tuple(elements: Vec<Self>) -> Self {
         OwnedTerm::Tuple(elements)
     }
@@ -232,37 +302,40 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn is_atom(&self) -> bool {
-        matches!(self, OwnedTerm::Atom(_))
+        matches!(self.resolve(), OwnedTerm::Atom(_))
     }
 
     #[inline]
     #[must_use]
     pub fn is_integer(&self) -> bool {
-        matches!(self, OwnedTerm::Integer(_))
+        matches!(self.resolve(), OwnedTerm::Integer(_))
     }
 
     #[inline]
     #[must_use]
     pub fn is_list(&self) -> bool {
-        matches!(self, OwnedTerm::List(_) | OwnedTerm::Nil)
+        matches!(
+            self.resolve(),
+            OwnedTerm::List(_) | OwnedTerm::ByteList(_) | OwnedTerm::Nil
+        )
     }
 
     #[inline]
     #[must_use]
     pub fn is_map(&self) -> bool {
-        matches!(self, OwnedTerm::Map(_))
+        matches!(self.resolve(), OwnedTerm::Map(_) | OwnedTerm::OrderedMap(_))
     }
 
     #[inline]
     #[must_use]
     pub fn is_tuple(&self) -> bool {
-        matches!(self, OwnedTerm::Tuple(_))
+        matches!(self.resolve(), OwnedTerm::Tuple(_))
     }
 
     #[inline]
     #[must_use]
     pub fn as_atom(&self) -> Option<&Atom> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Atom(a) => Some(a),
             _ => None,
         }
@@ -271,7 +344,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn as_integer(&self) -> Option<i64> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Integer(i) => Some(*i),
             _ => None,
         }
@@ -280,7 +353,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn as_float(&self) -> Option<f64> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Float(f) => Some(*f),
             _ => None,
         }
@@ -289,8 +362,18 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn as_binary(&self) -> Option<&[u8]> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Binary(b) => Some(b),
+            OwnedTerm::SharedBinary(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_shared_binary(&self) -> Option<&Bytes> {
+        match self.resolve() {
+            OwnedTerm::SharedBinary(b) => Some(b),
             _ => None,
         }
     }
@@ -298,7 +381,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn as_string(&self) -> Option<&str> {
-        match self {
+        match self.resolve() {
             OwnedTerm::String(s) => Some(s),
             _ => None,
         }
@@ -307,16 +390,77 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn as_list(&self) -> Option<&[OwnedTerm]> {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(l) => Some(l),
             _ => None,
         }
     }
 
+    /// Returns the elements and the tail of an improper list.
+    #[inline]
+    #[must_use]
+    pub fn as_improper(&self) -> Option<(&[OwnedTerm], &OwnedTerm)> {
+        match self.resolve() {
+            OwnedTerm::ImproperList { elements, tail } => Some((elements, tail)),
+            _ => None,
+        }
+    }
+
+    /// Turns the serde representation of an improper list, a single-entry map
+    /// keyed by [`IMPROPER_LIST_TAG`], back into an improper list. Other terms
+    /// are returned as is.
+    #[must_use]
+    pub fn untag_improper_list(self) -> OwnedTerm {
+        if let OwnedTerm::Map(m) = &self
+            && m.len() == 1
+            && let Some((OwnedTerm::Binary(key), OwnedTerm::List(pair) | OwnedTerm::Tuple(pair))) =
+                m.first_key_value()
+            && key == IMPROPER_LIST_TAG.as_bytes()
+            && pair.len() == 2
+        {
+            match &pair[0] {
+                OwnedTerm::List(elements) if !elements.is_empty() => {
+                    return OwnedTerm::improper_list(elements.clone(), pair[1].clone());
+                }
+                OwnedTerm::List(_) | OwnedTerm::Nil => return pair[1].clone(),
+                _ => {}
+            }
+        }
+        self
+    }
+
+    /// Converts an improper list into a proper one by appending the tail
+    /// as the last element, nested improper tails included. The distinction
+    /// between `[a | b]` and `[a, b]` is lost; other terms are returned as is.
+    #[must_use]
+    pub fn to_proper_lossy(&self) -> OwnedTerm {
+        match self.resolve() {
+            OwnedTerm::ImproperList { elements, tail } => {
+                let mut result = elements.clone();
+                let mut tail = tail.as_ref();
+                while let OwnedTerm::ImproperList {
+                    elements,
+                    tail: next,
+                } = tail
+                {
+                    result.extend(elements.iter().cloned());
+                    tail = next;
+                }
+                match tail {
+                    OwnedTerm::Nil => {}
+                    OwnedTerm::List(rest) => result.extend(rest.iter().cloned()),
+                    other => result.push(other.clone()),
+                }
+                OwnedTerm::List(result)
+            }
+            other => other.clone(),
+        }
+    }
+
     #[inline]
     #[must_use]
     pub fn as_map(&self) -> Option<&BTreeMap<Self, Self>> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Map(m) => Some(m),
             _ => None,
         }
@@ -324,8 +468,71 @@ impl OwnedTerm {
 
     #[inline]
     #[must_use]
-    pub fn as_tuple(&self) -> Option<&[OwnedTerm]> {
+    pub fn as_ordered_map(&self) -> Option<&[(Self, Self)]> {
+        match self.resolve() {
+            OwnedTerm::OrderedMap(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_byte_list(&self) -> Option<&[u8]> {
+        match self.resolve() {
+            OwnedTerm::ByteList(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Converts an [`OwnedTerm::Binary`] into an [`OwnedTerm::SharedBinary`] without copying.
+    /// Other terms are returned unchanged.
+    #[must_use]
+    pub fn into_shared_binary(self) -> Self {
+        match self {
+            OwnedTerm::Binary(b) => OwnedTerm::SharedBinary(Bytes::from(b)),
+            other => other,
+        }
+    }
+
+    /// Converts an [`OwnedTerm::SharedBinary`] into an [`OwnedTerm::Binary`], copying
+    /// its bytes unless the buffer is not shared. Other terms are returned unchanged.
+    #[must_use]
+    pub fn into_owned_binary(self) -> Self {
         match self {
+            OwnedTerm::SharedBinary(b) => OwnedTerm::Binary(b.into()),
+            other => other,
+        }
+    }
+
+    /// Converts an [`OwnedTerm::ByteList`] into an [`OwnedTerm::List`] of integers.
+    /// Other terms are returned unchanged.
+    #[must_use]
+    pub fn into_integer_list(self) -> Self {
+        match self {
+            OwnedTerm::ByteList(bytes) => OwnedTerm::List(
+                bytes
+                    .into_iter()
+                    .map(|b| OwnedTerm::Integer(i64::from(b)))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+
+    /// Converts an [`OwnedTerm::OrderedMap`] into an [`OwnedTerm::Map`], later entries
+    /// replacing earlier ones with the same key. Other terms are returned unchanged.
+    #[must_use]
+    pub fn into_unordered_map(self) -> Self {
+        match self {
+            OwnedTerm::OrderedMap(entries) => OwnedTerm::Map(entries.into_iter().collect()),
+            other => other,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_tuple(&self) -> Option<&[OwnedTerm]> {
+        match self.resolve() {
             OwnedTerm::Tuple(t) => Some(t),
             _ => None,
         }
@@ -333,7 +540,7 @@ impl OwnedTerm {
 
     #[inline]
     pub fn as_list_mut(&mut self) -> Option<&mut Vec<Self>> {
-        match self {
+        match self.make_mut() {
             OwnedTerm::List(l) => Some(l),
             _ => None,
         }
@@ -341,7 +548,7 @@ impl OwnedTerm {
 
     #[inline]
     pub fn as_map_mut(&mut self) -> Option<&mut BTreeMap<Self, Self>> {
-        match self {
+        match self.make_mut() {
             OwnedTerm::Map(m) => Some(m),
             _ => None,
         }
@@ -349,7 +556,7 @@ impl OwnedTerm {
 
     #[inline]
     pub fn as_tuple_mut(&mut self) -> Option<&mut Vec<Self>> {
-        match self {
+        match self.make_mut() {
             OwnedTerm::Tuple(t) => Some(t),
             _ => None,
         }
@@ -357,7 +564,7 @@ impl OwnedTerm {
 
     #[inline]
     pub fn as_binary_mut(&mut self) -> Option<&mut Vec<u8>> {
-        match self {
+        match self.make_mut() {
             OwnedTerm::Binary(b) => Some(b),
             _ => None,
         }
@@ -430,11 +637,11 @@ impl OwnedTerm {
     pub fn into_map_iter(
         self,
     ) -> Result<impl Iterator<Item = (OwnedTerm, OwnedTerm)>, TermConversionError> {
-        match self {
+        match self.unshare() {
             OwnedTerm::Map(m) => Ok(m.into_iter()),
-            _ => Err(TermConversionError::WrongType {
+            other => Err(TermConversionError::WrongType {
                 expected: "Map",
-                actual: self.type_name(),
+                actual: other.type_name(),
             }),
         }
     }
@@ -443,18 +650,19 @@ impl OwnedTerm {
     #[must_use]
     pub fn type_name(&self) -> &'static str {
         match self {
+            OwnedTerm::Shared(shared) => shared.type_name(),
             OwnedTerm::Atom(_) => "Atom",
             OwnedTerm::Integer(_) => "Integer",
             OwnedTerm::Float(_) => "Float",
             OwnedTerm::Pid(_) => "Pid",
             OwnedTerm::Port(_) => "Port",
             OwnedTerm::Reference(_) => "Reference",
-            OwnedTerm::Binary(_) => "Binary",
+            OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) => "Binary",
             OwnedTerm::BitBinary { .. } => "BitBinary",
             OwnedTerm::String(_) => "String",
-            OwnedTerm::List(_) => "List",
+            OwnedTerm::List(_) | OwnedTerm::ByteList(_) => "List",
             OwnedTerm::ImproperList { .. } => "ImproperList",
-            OwnedTerm::Map(_) => "Map",
+            OwnedTerm::Map(_) | OwnedTerm::OrderedMap(_) => "Map",
             OwnedTerm::Tuple(_) => "Tuple",
             OwnedTerm::BigInt(_) => "BigInt",
             OwnedTerm::ExternalFun(_) => "ExternalFun",
@@ -466,7 +674,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn atom_name(&self) -> Option<&str> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Atom(a) => Some(&a.name),
             _ => None,
         }
@@ -475,7 +683,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn is_atom_with_name(&self, name: &str) -> bool {
-        match self {
+        match self.resolve() {
             OwnedTerm::Atom(a) => a == name,
             _ => false,
         }
@@ -516,7 +724,7 @@ impl OwnedTerm {
     }
 
     pub fn into_ok_value(self) -> Option<OwnedTerm> {
-        match self {
+        match self.unshare() {
             OwnedTerm::Tuple(mut elements) if elements.len() == 2 => {
                 if elements[0] == OwnedTerm::ok() {
                     Some(elements.swap_remove(1))
@@ -529,7 +737,7 @@ impl OwnedTerm {
     }
 
     pub fn into_rex_response(self) -> Result<OwnedTerm, TermConversionError> {
-        match self {
+        match self.unshare() {
             OwnedTerm::Tuple(mut elements) if elements.len() == 2 => {
                 if elements[0].is_atom_with_name("rex") {
                     Ok(elements.swap_remove(1))
@@ -540,15 +748,15 @@ impl OwnedTerm {
                     })
                 }
             }
-            _ => Err(TermConversionError::WrongType {
+            other => Err(TermConversionError::WrongType {
                 expected: "{rex, Result} tuple",
-                actual: self.type_name(),
+                actual: other.type_name(),
             }),
         }
     }
 
     pub fn into_error_reason(self) -> Option<OwnedTerm> {
-        match self {
+        match self.unshare() {
             OwnedTerm::Tuple(mut elements) if elements.len() == 2 => {
                 if elements[0] == OwnedTerm::error() {
                     Some(elements.swap_remove(1))
@@ -561,7 +769,7 @@ impl OwnedTerm {
     }
 
     pub fn map_get(&self, key: &OwnedTerm) -> Option<&OwnedTerm> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Map(m) => m.get(key),
             _ => None,
         }
@@ -572,7 +780,7 @@ impl OwnedTerm {
     }
 
     pub fn iter(&self) -> OwnedTermIter<'_> {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) => {
                 OwnedTermIter::Slice(elements.iter())
             }
@@ -582,8 +790,8 @@ impl OwnedTerm {
     }
 
     pub fn proplist_get_atom_key(&self, key: &str) -> Option<&OwnedTerm> {
-        match self {
-            OwnedTerm::List(elements) => {
+        match self.resolve() {
+            OwnedTerm::List(elements) | OwnedTerm::ImproperList { elements, .. } => {
                 for element in elements {
                     if let OwnedTerm::Tuple(tuple_elements) = element
                         && tuple_elements.len() == 2
@@ -600,7 +808,7 @@ impl OwnedTerm {
     }
 
     pub fn is_proplist(&self) -> bool {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(elements) => elements.iter().all(Self::is_proplist_element),
             OwnedTerm::Nil => true,
             _ => false,
@@ -612,7 +820,10 @@ impl OwnedTerm {
             OwnedTerm::Tuple(elements) if elements.len() == 2 => {
                 matches!(
                     &elements[0],
-                    OwnedTerm::Atom(_) | OwnedTerm::Binary(_) | OwnedTerm::String(_)
+                    OwnedTerm::Atom(_)
+                        | OwnedTerm::Binary(_)
+                        | OwnedTerm::SharedBinary(_)
+                        | OwnedTerm::String(_)
                 )
             }
             OwnedTerm::Atom(_) => true,
@@ -621,7 +832,7 @@ impl OwnedTerm {
     }
 
     pub fn normalize_proplist(&self) -> Result<OwnedTerm, TermConversionError> {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(elements) => {
                 let normalized: Vec<OwnedTerm> = elements
                     .iter()
@@ -645,7 +856,7 @@ impl OwnedTerm {
     }
 
     pub fn proplist_to_map(&self) -> Result<OwnedTerm, TermConversionError> {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(elements) => {
                 let mut map = BTreeMap::new();
                 for element in elements {
@@ -671,7 +882,7 @@ impl OwnedTerm {
     }
 
     pub fn map_to_proplist(&self) -> Result<OwnedTerm, TermConversionError> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Map(map) => {
                 let elements: Vec<OwnedTerm> = map
                     .iter()
@@ -688,7 +899,7 @@ impl OwnedTerm {
     }
 
     pub fn to_map_recursive(&self) -> Result<OwnedTerm, TermConversionError> {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(elements) if elements.is_empty() => Ok(OwnedTerm::List(vec![])),
             OwnedTerm::List(_) if self.is_proplist() => {
                 let normalized = self.normalize_proplist()?;
@@ -721,7 +932,7 @@ impl OwnedTerm {
     }
 
     pub fn atomize_keys(&self) -> Result<OwnedTerm, TermConversionError> {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(elements) => {
                 let converted: Vec<OwnedTerm> = elements
                     .iter()
@@ -731,8 +942,8 @@ impl OwnedTerm {
                         {
                             let key = match &t[0] {
                                 OwnedTerm::Atom(_) => t[0].clone(),
-                                OwnedTerm::Binary(b) => {
-                                    let s = String::from_utf8_lossy(b);
+                                OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) => {
+                                    let s = String::from_utf8_lossy(t[0].as_binary()?);
                                     OwnedTerm::Atom(Atom::new(s.as_ref()))
                                 }
                                 OwnedTerm::String(s) => OwnedTerm::Atom(Atom::new(s)),
@@ -751,8 +962,8 @@ impl OwnedTerm {
                 for (k, v) in m {
                     let key = match k {
                         OwnedTerm::Atom(_) => k.clone(),
-                        OwnedTerm::Binary(b) => {
-                            let s = String::from_utf8_lossy(b);
+                        OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) => {
+                            let s = String::from_utf8_lossy(k.as_binary().unwrap_or_default());
                             OwnedTerm::Atom(Atom::new(s.as_ref()))
                         }
                         OwnedTerm::String(s) => OwnedTerm::Atom(Atom::new(s)),
@@ -771,14 +982,31 @@ impl OwnedTerm {
     }
 
     pub fn as_list_wrapped(&self) -> OwnedTerm {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(_) | OwnedTerm::Nil => self.clone(),
             _ => OwnedTerm::List(vec![self.clone()]),
         }
     }
 
+    pub fn proplist_get_binary_key(&self, key: &str) -> Option<&OwnedTerm> {
+        match self.resolve() {
+            OwnedTerm::List(elements) | OwnedTerm::ImproperList { elements, .. } => {
+                elements.iter().find_map(|element| {
+                    if let OwnedTerm::Tuple(tuple_elements) = element
+                        && tuple_elements.len() == 2
+                        && tuple_elements[0].is_binary_key(key)
+                    {
+                        return Some(&tuple_elements[1]);
+                    }
+                    None
+                })
+            }
+            _ => None,
+        }
+    }
+
     pub fn proplist_iter(&self) -> Option<ProplistIter<'_>> {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(elements) => Some(ProplistIter {
                 iter: elements.iter(),
             }),
@@ -788,7 +1016,7 @@ impl OwnedTerm {
     }
 
     pub fn map_get_atom_key(&self, key: &str) -> Option<&OwnedTerm> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Map(map) => map.iter().find_map(|(k, v)| {
                 if let OwnedTerm::Atom(atom) = k
                     && atom.as_ref() == key
@@ -801,6 +1029,79 @@ impl OwnedTerm {
         }
     }
 
+    /// Looks up a binary (Elixir string) key, such as those in JSON-derived maps.
+    pub fn map_get_binary_key(&self, key: &str) -> Option<&OwnedTerm> {
+        match self.resolve() {
+            OwnedTerm::Map(map) => map
+                .iter()
+                .find_map(|(k, v)| k.is_binary_key(key).then_some(v)),
+            _ => None,
+        }
+    }
+
+    /// Looks up an atom key first, then a binary key with the same name.
+    pub fn map_get_any_key(&self, key: &str) -> Option<&OwnedTerm> {
+        self.map_get_atom_key(key)
+            .or_else(|| self.map_get_binary_key(key))
+    }
+
+    #[inline]
+    fn is_binary_key(&self, key: &str) -> bool {
+        match self.resolve() {
+            OwnedTerm::Binary(b) => b.as_slice() == key.as_bytes(),
+            OwnedTerm::SharedBinary(b) => b.as_ref() == key.as_bytes(),
+            OwnedTerm::String(s) => s == key,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn map_get_binary_key_i64(&self, key: &str) -> Option<i64> {
+        self.map_get_binary_key(key).and_then(|t| t.as_integer())
+    }
+
+    #[inline]
+    pub fn map_get_binary_key_i64_or(&self, key: &str, default: i64) -> i64 {
+        self.map_get_binary_key_i64(key).unwrap_or(default)
+    }
+
+    #[inline]
+    pub fn map_get_binary_key_bool(&self, key: &str) -> Option<bool> {
+        self.map_get_binary_key(key).and_then(|t| t.as_bool())
+    }
+
+    #[inline]
+    pub fn map_get_binary_key_bool_or(&self, key: &str, default: bool) -> bool {
+        self.map_get_binary_key_bool(key).unwrap_or(default)
+    }
+
+    #[inline]
+    pub fn map_get_binary_key_float(&self, key: &str) -> Option<f64> {
+        self.map_get_binary_key(key).and_then(|t| t.as_float())
+    }
+
+    #[inline]
+    pub fn map_get_binary_key_string(&self, key: &str) -> Option<String> {
+        self.map_get_binary_key(key)
+            .and_then(|t| t.as_erlang_string())
+    }
+
+    #[inline]
+    pub fn map_get_binary_key_string_or(&self, key: &str, default: &str) -> String {
+        self.map_get_binary_key_string(key)
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    #[inline]
+    pub fn map_get_binary_key_map(&self, key: &str) -> Option<&BTreeMap<OwnedTerm, OwnedTerm>> {
+        self.map_get_binary_key(key).and_then(|t| t.as_map())
+    }
+
+    #[inline]
+    pub fn map_get_binary_key_list(&self, key: &str) -> Option<&[OwnedTerm]> {
+        self.map_get_binary_key(key).and_then(|t| t.as_list())
+    }
+
     #[inline]
     pub fn map_get_i64(&self, key: &str) -> Option<i64> {
         self.map_get_atom_key(key).and_then(|t| t.as_integer())
@@ -877,7 +1178,7 @@ impl OwnedTerm {
     }
 
     pub fn as_erlang_string(&self) -> Option<String> {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(integers) => {
                 let bytes: Vec<u8> = integers
                     .iter()
@@ -901,6 +1202,7 @@ impl OwnedTerm {
             }
             OwnedTerm::String(s) => Some(s.clone()),
             OwnedTerm::Binary(b) => Some(String::from_utf8_lossy(b).to_string()),
+            OwnedTerm::SharedBinary(b) => Some(String::from_utf8_lossy(b).to_string()),
             _ => None,
         }
     }
@@ -914,7 +1216,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn tuple_get(&self, index: usize) -> Option<&OwnedTerm> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Tuple(t) => t.get(index),
             _ => None,
         }
@@ -961,11 +1263,11 @@ impl OwnedTerm {
         fn is_valid_unicode_scalar(i: i64) -> bool {
             (0..=0x10FFFF).contains(&i) && !(0xD800..=0xDFFF).contains(&i)
         }
-        match self {
+        match self.resolve() {
             OwnedTerm::List(elements) => elements
                 .iter()
                 .all(|t| matches!(t, OwnedTerm::Integer(i) if is_valid_unicode_scalar(*i))),
-            OwnedTerm::Nil => true,
+            OwnedTerm::ByteList(_) | OwnedTerm::Nil => true,
             _ => false,
         }
     }
@@ -973,7 +1275,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn as_charlist_string(&self) -> Option<String> {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(elements) => {
                 let chars: Option<String> = elements
                     .iter()
@@ -986,9 +1288,11 @@ impl OwnedTerm {
                     .collect();
                 chars
             }
+            OwnedTerm::ByteList(bytes) => Some(bytes.iter().map(|&b| char::from(b)).collect()),
             OwnedTerm::Nil => Some(String::new()),
             OwnedTerm::String(s) => Some(s.clone()),
             OwnedTerm::Binary(b) => Some(String::from_utf8_lossy(b).to_string()),
+            OwnedTerm::SharedBinary(b) => Some(String::from_utf8_lossy(b).to_string()),
             _ => None,
         }
     }
@@ -996,7 +1300,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn as_list_or_empty(&self) -> &[OwnedTerm] {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(l) => l,
             OwnedTerm::Nil => &[],
             _ => &[],
@@ -1018,7 +1322,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn as_pid(&self) -> Option<&ExternalPid> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Pid(pid) => Some(pid),
             _ => None,
         }
@@ -1035,7 +1339,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn is_pid(&self) -> bool {
-        matches!(self, OwnedTerm::Pid(_))
+        matches!(self.resolve(), OwnedTerm::Pid(_))
     }
 
     #[inline]
@@ -1044,6 +1348,24 @@ impl OwnedTerm {
         self.as_pid().map(|p| p.to_string())
     }
 
+    /// Returns the nodes of every pid, port and reference in this term, including
+    /// the pids of funs and those in their free variables.
+    #[must_use]
+    pub fn referenced_nodes(&self) -> BTreeSet<Atom> {
+        let mut nodes = BTreeSet::new();
+        self.walk(&mut |term: &OwnedTerm, _depth: usize| {
+            match term {
+                OwnedTerm::Pid(pid) => nodes.insert(pid.node.clone()),
+                OwnedTerm::Port(port) => nodes.insert(port.node.clone()),
+                OwnedTerm::Reference(reference) => nodes.insert(reference.node.clone()),
+                OwnedTerm::InternalFun(fun) => nodes.insert(fun.pid.node.clone()),
+                _ => false,
+            };
+            WalkControl::Continue
+        });
+        nodes
+    }
+
     #[inline]
     pub fn proplist_get_i64(&self, key: &str) -> Option<i64> {
         self.proplist_get_atom_key(key).and_then(|t| t.as_integer())
@@ -1132,84 +1454,90 @@ impl OwnedTerm {
     }
 
     pub fn map_iter(&self) -> Option<impl Iterator<Item = (&OwnedTerm, &OwnedTerm)>> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Map(m) => Some(m.iter()),
             _ => None,
         }
     }
 
     pub fn try_into_list(self) -> Result<Vec<OwnedTerm>, TermConversionError> {
-        match self {
+        match self.unshare() {
             OwnedTerm::List(l) => Ok(l),
             OwnedTerm::Nil => Ok(Vec::new()),
-            _ => Err(TermConversionError::WrongType {
+            other => Err(TermConversionError::WrongType {
                 expected: "List or Nil",
-                actual: self.type_name(),
+                actual: other.type_name(),
             }),
         }
     }
 
     pub fn try_into_tuple(self) -> Result<Vec<OwnedTerm>, TermConversionError> {
-        match self {
+        match self.unshare() {
             OwnedTerm::Tuple(t) => Ok(t),
-            _ => Err(TermConversionError::WrongType {
+            other => Err(TermConversionError::WrongType {
                 expected: "Tuple",
-                actual: self.type_name(),
+                actual: other.type_name(),
             }),
         }
     }
 
     pub fn try_into_map(self) -> Result<BTreeMap<OwnedTerm, OwnedTerm>, TermConversionError> {
-        match self {
+        match self.unshare() {
             OwnedTerm::Map(m) => Ok(m),
-            _ => Err(TermConversionError::WrongType {
+            other => Err(TermConversionError::WrongType {
                 expected: "Map",
-                actual: self.type_name(),
+                actual: other.type_name(),
             }),
         }
     }
 
     pub fn try_into_binary(self) -> Result<Vec<u8>, TermConversionError> {
-        match self {
+        match self.unshare() {
             OwnedTerm::Binary(b) => Ok(b),
+            OwnedTerm::SharedBinary(b) => Ok(b.into()),
             OwnedTerm::String(s) => Ok(s.into_bytes()),
-            _ => Err(TermConversionError::WrongType {
+            other => Err(TermConversionError::WrongType {
                 expected: "Binary or String",
-                actual: self.type_name(),
+                actual: other.type_name(),
             }),
         }
     }
 
     pub fn try_into_string(self) -> Result<String, TermConversionError> {
-        match self {
+        match self.unshare() {
             OwnedTerm::String(s) => Ok(s),
             OwnedTerm::Binary(b) => {
                 String::from_utf8(b).map_err(|_| TermConversionError::OutOfRange)
             }
-            _ => Err(TermConversionError::WrongType {
+            OwnedTerm::SharedBinary(b) => {
+                String::from_utf8(b.into()).map_err(|_| TermConversionError::OutOfRange)
+            }
+            other => Err(TermConversionError::WrongType {
                 expected: "String or Binary",
-                actual: self.type_name(),
+                actual: other.type_name(),
             }),
         }
     }
 
     pub fn try_into_atom(self) -> Result<Arc<str>, TermConversionError> {
-        match self {
+        match self.unshare() {
             OwnedTerm::Atom(a) => Ok(a.name),
-            _ => Err(TermConversionError::WrongType {
+            other => Err(TermConversionError::WrongType {
                 expected: "Atom",
-                actual: self.type_name(),
+                actual: other.type_name(),
             }),
         }
     }
 
     #[must_use]
     pub fn len(&self) -> usize {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(l) => l.len(),
+            OwnedTerm::ByteList(b) => b.len(),
             OwnedTerm::Tuple(t) => t.len(),
             OwnedTerm::Map(m) => m.len(),
             OwnedTerm::Binary(b) => b.len(),
+            OwnedTerm::SharedBinary(b) => b.len(),
             OwnedTerm::String(s) => s.len(),
             OwnedTerm::Nil => 0,
             _ => 0,
@@ -1218,11 +1546,13 @@ impl OwnedTerm {
 
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(l) => l.is_empty(),
+            OwnedTerm::ByteList(b) => b.is_empty(),
             OwnedTerm::Tuple(t) => t.is_empty(),
             OwnedTerm::Map(m) => m.is_empty(),
             OwnedTerm::Binary(b) => b.is_empty(),
+            OwnedTerm::SharedBinary(b) => b.is_empty(),
             OwnedTerm::String(s) => s.is_empty(),
             OwnedTerm::Nil => true,
             _ => false,
@@ -1231,6 +1561,7 @@ impl OwnedTerm {
 
     pub fn estimated_encoded_size(&self) -> usize {
         match self {
+            OwnedTerm::Shared(shared) => shared.estimated_encoded_size(),
             OwnedTerm::Atom(a) => 3 + a.len(),
             OwnedTerm::Integer(i) => {
                 if (0..=255).contains(i) {
@@ -1245,11 +1576,14 @@ impl OwnedTerm {
             }
             OwnedTerm::Float(_) => 9,
             OwnedTerm::Binary(b) => 5 + b.len(),
+            OwnedTerm::SharedBinary(b) => 5 + b.len(),
             OwnedTerm::BitBinary { bytes, .. } => 6 + bytes.len(),
             OwnedTerm::String(s) => 5 + s.len(),
             OwnedTerm::List(l) => {
                 5 + 1 + l.iter().map(|t| t.estimated_encoded_size()).sum::<usize>()
             }
+            OwnedTerm::ByteList(b) if b.len() <= u16::MAX as usize => 3 + b.len(),
+            OwnedTerm::ByteList(b) => 5 + 1 + 2 * b.len(),
             OwnedTerm::ImproperList { elements, tail } => {
                 5 + elements
                     .iter()
@@ -1267,6 +1601,12 @@ impl OwnedTerm {
                     .map(|(k, v)| k.estimated_encoded_size() + v.estimated_encoded_size())
                     .sum::<usize>()
             }
+            OwnedTerm::OrderedMap(entries) => {
+                5 + entries
+                    .iter()
+                    .map(|(k, v)| k.estimated_encoded_size() + v.estimated_encoded_size())
+                    .sum::<usize>()
+            }
             OwnedTerm::Pid(_) => 17,
             OwnedTerm::Port(_) => 16,
             OwnedTerm::Reference(r) => 7 + r.ids.len() * 4,
@@ -1286,6 +1626,48 @@ impl OwnedTerm {
         }
     }
 
+    /// Approximately how many bytes the term takes in memory: its own size plus what it
+    /// allocates. Atom names are shared with the atom table and are not counted.
+    #[must_use]
+    pub fn heap_size(&self) -> usize {
+        size_of::<Self>() + self.allocated_size()
+    }
+
+    fn allocated_size(&self) -> usize {
+        match self {
+            OwnedTerm::Binary(b) | OwnedTerm::ByteList(b) => b.capacity(),
+            OwnedTerm::SharedBinary(b) => b.len(),
+            OwnedTerm::Shared(shared) => shared.heap_size(),
+            OwnedTerm::BitBinary { bytes, .. } => bytes.capacity(),
+            OwnedTerm::String(s) => s.capacity(),
+            OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) => elements_size(elements),
+            OwnedTerm::ImproperList { elements, tail } => {
+                elements_size(elements) + tail.heap_size()
+            }
+            OwnedTerm::Map(m) => m.iter().map(|(k, v)| k.heap_size() + v.heap_size()).sum(),
+            OwnedTerm::OrderedMap(entries) => {
+                (entries.capacity() - entries.len()) * size_of::<(Self, Self)>()
+                    + entries
+                        .iter()
+                        .map(|(k, v)| k.heap_size() + v.heap_size())
+                        .sum::<usize>()
+            }
+            OwnedTerm::Pid(p) => p.local_ext_bytes.as_ref().map_or(0, |b| b.len()),
+            OwnedTerm::Reference(r) => {
+                r.ids.capacity() * size_of::<u32>()
+                    + r.local_ext_bytes.as_ref().map_or(0, |b| b.len())
+            }
+            OwnedTerm::BigInt(b) => b.digits.capacity(),
+            OwnedTerm::InternalFun(f) => size_of::<InternalFun>() + elements_size(&f.free_vars),
+            OwnedTerm::Atom(_)
+            | OwnedTerm::Integer(_)
+            | OwnedTerm::Float(_)
+            | OwnedTerm::Port(_)
+            | OwnedTerm::ExternalFun(_)
+            | OwnedTerm::Nil => 0,
+        }
+    }
+
     /// Returns the elements of a 2-tuple, or None if not a 2-tuple.
     ///
     /// # Example
@@ -1300,7 +1682,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn as_2_tuple(&self) -> Option<(&OwnedTerm, &OwnedTerm)> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Tuple(elements) if elements.len() == 2 => Some((&elements[0], &elements[1])),
             _ => None,
         }
@@ -1310,7 +1692,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn as_3_tuple(&self) -> Option<(&OwnedTerm, &OwnedTerm, &OwnedTerm)> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Tuple(elements) if elements.len() == 3 => {
                 Some((&elements[0], &elements[1], &elements[2]))
             }
@@ -1322,7 +1704,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn as_4_tuple(&self) -> Option<(&OwnedTerm, &OwnedTerm, &OwnedTerm, &OwnedTerm)> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Tuple(elements) if elements.len() == 4 => {
                 Some((&elements[0], &elements[1], &elements[2], &elements[3]))
             }
@@ -1424,7 +1806,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn is_atom_one_of(&self, names: &[&str]) -> bool {
-        match self {
+        match self.resolve() {
             OwnedTerm::Atom(a) => names.iter().any(|&n| a == n),
             _ => false,
         }
@@ -1455,7 +1837,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn elixir_struct_module(&self) -> Option<&str> {
-        match self {
+        match self.resolve() {
             OwnedTerm::Map(m) => {
                 let struct_key = OwnedTerm::atom("__struct__");
                 m.get(&struct_key).and_then(|v| v.atom_name())
@@ -1468,7 +1850,7 @@ impl OwnedTerm {
     #[inline]
     #[must_use]
     pub fn is_elixir_exception(&self) -> bool {
-        match self {
+        match self.resolve() {
             OwnedTerm::Map(m) => {
                 let struct_key = OwnedTerm::atom("__struct__");
                 let exception_key = OwnedTerm::atom("__exception__");
@@ -1497,6 +1879,7 @@ impl OwnedTerm {
         }
 
         match self {
+            OwnedTerm::Shared(shared) => shared.inspect_impl(depth),
             OwnedTerm::Atom(a) => {
                 let name = a.as_str();
                 if name.starts_with("Elixir.") {
@@ -1518,20 +1901,24 @@ impl OwnedTerm {
                     f.to_string()
                 }
             }
-            OwnedTerm::Binary(b) => match std::str::from_utf8(b) {
-                Ok(s) if s.chars().all(|c| !c.is_control() || c == '\n' || c == '\t') => {
-                    format!("\"{}\"", s.replace('\"', "\\\""))
-                }
-                _ => {
-                    let bytes: Vec<String> = b.iter().take(10).map(|b| b.to_string()).collect();
-                    if b.len() > 10 {
-                        format!("<<{}, ...>>", bytes.join(", "))
-                    } else {
-                        format!("<<{}>>", bytes.join(", "))
+            OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) => {
+                let b = self.as_binary().unwrap_or_default();
+                match str::from_utf8(b) {
+                    Ok(s) if s.chars().all(|c| !c.is_control() || c == '\n' || c == '\t') => {
+                        format!("\"{}\"", s.replace('\"', "\\\""))
+                    }
+                    _ => {
+                        let bytes: Vec<String> = b.iter().take(10).map(|b| b.to_string()).collect();
+                        if b.len() > 10 {
+                            format!("<<{}, ...>>", bytes.join(", "))
+                        } else {
+                            format!("<<{}>>", bytes.join(", "))
+                        }
                     }
                 }
-            },
+            }
             OwnedTerm::String(s) => format!("\"{}\"", s.replace('\"', "\\\"")),
+            OwnedTerm::ByteList(_) => self.clone().into_integer_list().inspect_impl(depth),
             OwnedTerm::List(elements) => {
                 if elements.is_empty() {
                     "[]".to_string()
@@ -1636,6 +2023,28 @@ impl OwnedTerm {
                     }
                 }
             }
+            OwnedTerm::OrderedMap(entries) => {
+                let items: Vec<String> = entries
+                    .iter()
+                    .take(20)
+                    .map(|(k, v)| {
+                        if let Some(name) = k.atom_name() {
+                            format!("{}: {}", name, v.inspect_impl(depth + 1))
+                        } else {
+                            format!(
+                                "{} => {}",
+                                k.inspect_impl(depth + 1),
+                                v.inspect_impl(depth + 1)
+                            )
+                        }
+                    })
+                    .collect();
+                if entries.len() > 20 {
+                    format!("%{{{}, ...}}", items.join(", "))
+                } else {
+                    format!("%{{{}}}", items.join(", "))
+                }
+            }
             OwnedTerm::Pid(p) => format!("#PID<{}>", p),
             OwnedTerm::Port(p) => format!("#Port<{:?}>", p),
             OwnedTerm::Reference(r) => format!("#Reference<{:?}>", r),
@@ -1645,6 +2054,9 @@ impl OwnedTerm {
             }
             OwnedTerm::ExternalFun(f) => format!("&{}.{}/{}", f.module, f.function, f.arity),
             OwnedTerm::InternalFun(f) => format!("#Function<{}/{}>", f.module, f.arity),
+            OwnedTerm::ImproperList { elements, tail } if elements.is_empty() => {
+                tail.inspect_impl(depth)
+            }
             OwnedTerm::ImproperList { elements, tail } => {
                 let items: Vec<String> =
                     elements.iter().map(|e| e.inspect_impl(depth + 1)).collect();
@@ -1730,6 +2142,12 @@ impl From<Vec<u8>> for OwnedTerm {
     }
 }
 
+impl From<Bytes> for OwnedTerm {
+    fn from(b: Bytes) -> Self {
+        OwnedTerm::SharedBinary(b)
+    }
+}
+
 impl From<String> for OwnedTerm {
     fn from(s: String) -> Self {
         OwnedTerm::String(s)
@@ -1836,6 +2254,9 @@ impl TryFrom<OwnedTerm> for String {
             OwnedTerm::Binary(b) => {
                 String::from_utf8(b).map_err(|_| TermConversionError::OutOfRange)
             }
+            OwnedTerm::SharedBinary(b) => {
+                String::from_utf8(b.into()).map_err(|_| TermConversionError::OutOfRange)
+            }
             _ => Err(TermConversionError::WrongType {
                 expected: "String or Binary",
                 actual: term.type_name(),
@@ -1850,6 +2271,7 @@ impl TryFrom<OwnedTerm> for Vec<u8> {
     fn try_from(term: OwnedTerm) -> Result<Self, Self::Error> {
         match term {
             OwnedTerm::Binary(b) => Ok(b),
+            OwnedTerm::SharedBinary(b) => Ok(b.into()),
             OwnedTerm::String(s) => Ok(s.into_bytes()),
             _ => Err(TermConversionError::WrongType {
                 expected: "Binary or String",
@@ -1931,14 +2353,113 @@ impl TryFrom<OwnedTerm> for u8 {
     }
 }
 
+/// Binaries are equal whether they own or share their bytes, an [`OwnedTerm::Shared`]
+/// equals the term it wraps, a [`OwnedTerm::ByteList`]
+/// equals the list of the same integers and an [`OwnedTerm::OrderedMap`] equals any map
+/// with the same entries, in line with [`Ord`].
+impl PartialEq for OwnedTerm {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (OwnedTerm::Shared(a), b) => a.as_ref() == b,
+            (a, OwnedTerm::Shared(b)) => a == b.as_ref(),
+            (OwnedTerm::Atom(a), OwnedTerm::Atom(b)) => a == b,
+            (OwnedTerm::Integer(a), OwnedTerm::Integer(b)) => a == b,
+            (OwnedTerm::Float(a), OwnedTerm::Float(b)) => a == b,
+            (OwnedTerm::Pid(a), OwnedTerm::Pid(b)) => a == b,
+            (OwnedTerm::Port(a), OwnedTerm::Port(b)) => a == b,
+            (OwnedTerm::Reference(a), OwnedTerm::Reference(b)) => a == b,
+            (OwnedTerm::Binary(a), OwnedTerm::Binary(b)) => a == b,
+            (OwnedTerm::SharedBinary(a), OwnedTerm::SharedBinary(b)) => a == b,
+            (OwnedTerm::Binary(a), OwnedTerm::SharedBinary(b)) => a.as_slice() == b.as_ref(),
+            (OwnedTerm::SharedBinary(a), OwnedTerm::Binary(b)) => a.as_ref() == b.as_slice(),
+            (
+                OwnedTerm::BitBinary {
+                    bytes: a,
+                    bits: abits,
+                },
+                OwnedTerm::BitBinary {
+                    bytes: b,
+                    bits: bbits,
+                },
+            ) => a == b && abits == bbits,
+            (OwnedTerm::String(a), OwnedTerm::String(b)) => a == b,
+            (OwnedTerm::List(a), OwnedTerm::List(b)) => a == b,
+            (OwnedTerm::ByteList(a), OwnedTerm::ByteList(b)) => a == b,
+            (OwnedTerm::ByteList(a), OwnedTerm::List(b)) => byte_list_eq(a, b),
+            (OwnedTerm::List(a), OwnedTerm::ByteList(b)) => byte_list_eq(b, a),
+            (
+                OwnedTerm::ImproperList {
+                    elements: a,
+                    tail: atail,
+                },
+                OwnedTerm::ImproperList {
+                    elements: b,
+                    tail: btail,
+                },
+            ) => a == b && atail == btail,
+            (OwnedTerm::Map(a), OwnedTerm::Map(b)) => a == b,
+            (OwnedTerm::OrderedMap(a), OwnedTerm::OrderedMap(b)) => {
+                map_entries_eq(&ordered_map_view(a), &ordered_map_view(b))
+            }
+            (OwnedTerm::OrderedMap(a), OwnedTerm::Map(b)) => {
+                map_entries_eq(&ordered_map_view(a), &map_view(b))
+            }
+            (OwnedTerm::Map(a), OwnedTerm::OrderedMap(b)) => {
+                map_entries_eq(&map_view(a), &ordered_map_view(b))
+            }
+            (OwnedTerm::Tuple(a), OwnedTerm::Tuple(b)) => a == b,
+            (OwnedTerm::BigInt(a), OwnedTerm::BigInt(b)) => a == b,
+            (OwnedTerm::ExternalFun(a), OwnedTerm::ExternalFun(b)) => a == b,
+            (OwnedTerm::InternalFun(a), OwnedTerm::InternalFun(b)) => a == b,
+            (OwnedTerm::Nil, OwnedTerm::Nil) => true,
+            _ => false,
+        }
+    }
+}
+
+fn byte_list_eq(bytes: &[u8], elements: &[OwnedTerm]) -> bool {
+    bytes.len() == elements.len()
+        && bytes
+            .iter()
+            .zip(elements)
+            .all(|(&b, e)| matches!(e, OwnedTerm::Integer(i) if *i == i64::from(b)))
+}
+
+/// The entries of an ordered map as [`OwnedTerm::into_unordered_map`] would keep them.
+fn ordered_map_view(entries: &[(OwnedTerm, OwnedTerm)]) -> BTreeMap<&OwnedTerm, &OwnedTerm> {
+    entries.iter().map(|(k, v)| (k, v)).collect()
+}
+
+fn map_view(map: &BTreeMap<OwnedTerm, OwnedTerm>) -> BTreeMap<&OwnedTerm, &OwnedTerm> {
+    map.iter().collect()
+}
+
+fn map_entries_eq(
+    a: &BTreeMap<&OwnedTerm, &OwnedTerm>,
+    b: &BTreeMap<&OwnedTerm, &OwnedTerm>,
+) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|((k1, v1), (k2, v2))| k1 == k2 && v1 == v2)
+}
+
 impl Hash for OwnedTerm {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        discriminant(self).hash(state);
+        // Variants that compare equal to another variant hash like it
+        match self {
+            OwnedTerm::Shared(shared) => return shared.hash(state),
+            OwnedTerm::SharedBinary(_) => discriminant(&OwnedTerm::Binary(Vec::new())).hash(state),
+            OwnedTerm::ByteList(_) => discriminant(&OwnedTerm::List(Vec::new())).hash(state),
+            OwnedTerm::OrderedMap(_) => discriminant(&OwnedTerm::Map(BTreeMap::new())).hash(state),
+            _ => discriminant(self).hash(state),
+        }
 
         match self {
             OwnedTerm::Atom(a) => a.hash(state),
             OwnedTerm::Integer(i) => i.hash(state),
-            OwnedTerm::Binary(b) => b.hash(state),
+            OwnedTerm::Binary(b) => b.as_slice().hash(state),
+            OwnedTerm::SharedBinary(b) => b.as_ref().hash(state),
             OwnedTerm::String(s) => s.hash(state),
             OwnedTerm::Pid(p) => p.hash(state),
             OwnedTerm::Port(p) => p.hash(state),
@@ -1956,6 +2477,12 @@ impl Hash for OwnedTerm {
                     elem.hash(state);
                 }
             }
+            OwnedTerm::ByteList(bytes) => {
+                bytes.len().hash(state);
+                for &b in bytes {
+                    OwnedTerm::Integer(i64::from(b)).hash(state);
+                }
+            }
             OwnedTerm::ImproperList { elements, tail } => {
                 elements.len().hash(state);
                 for elem in elements {
@@ -1976,7 +2503,17 @@ impl Hash for OwnedTerm {
                     v.hash(state);
                 }
             }
+            OwnedTerm::OrderedMap(entries) => {
+                let map = ordered_map_view(entries);
+                map.len().hash(state);
+                for (k, v) in map {
+                    k.hash(state);
+                    v.hash(state);
+                }
+            }
             OwnedTerm::ExternalFun(f) => f.hash(state),
+            // Hashed as the term it wraps above
+            OwnedTerm::Shared(_) => (),
             OwnedTerm::InternalFun(f) => {
                 f.arity.hash(state);
                 f.uniq.hash(state);
@@ -1996,8 +2533,9 @@ impl Hash for OwnedTerm {
 
 /// Returns the Erlang type ordering value for a term.
 /// Erlang ordering: numbers < atoms < references < funs < ports < pids < tuples < maps < lists < binaries
-const fn term_type_order(t: &OwnedTerm) -> u8 {
+fn term_type_order(t: &OwnedTerm) -> u8 {
     match t {
+        OwnedTerm::Shared(shared) => term_type_order(shared),
         OwnedTerm::Integer(_) | OwnedTerm::BigInt(_) | OwnedTerm::Float(_) => 0,
         OwnedTerm::Atom(_) => 1,
         OwnedTerm::Reference(_) => 2,
@@ -2005,9 +2543,15 @@ const fn term_type_order(t: &OwnedTerm) -> u8 {
         OwnedTerm::Port(_) => 4,
         OwnedTerm::Pid(_) => 5,
         OwnedTerm::Tuple(_) => 6,
-        OwnedTerm::Map(_) => 7,
-        OwnedTerm::Nil | OwnedTerm::List(_) | OwnedTerm::ImproperList { .. } => 8,
-        OwnedTerm::Binary(_) | OwnedTerm::BitBinary { .. } | OwnedTerm::String(_) => 9,
+        OwnedTerm::Map(_) | OwnedTerm::OrderedMap(_) => 7,
+        OwnedTerm::Nil
+        | OwnedTerm::List(_)
+        | OwnedTerm::ByteList(_)
+        | OwnedTerm::ImproperList { .. } => 8,
+        OwnedTerm::Binary(_)
+        | OwnedTerm::SharedBinary(_)
+        | OwnedTerm::BitBinary { .. }
+        | OwnedTerm::String(_) => 9,
     }
 }
 
@@ -2015,11 +2559,17 @@ impl Eq for OwnedTerm {}
 
 impl Ord for OwnedTerm {
     fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (OwnedTerm::Shared(a), b) => return a.as_ref().cmp(b),
+            (a, OwnedTerm::Shared(b)) => return a.cmp(b),
+            _ => {}
+        }
         if discriminant(self) == discriminant(other) {
             match (self, other) {
                 (OwnedTerm::Integer(a), OwnedTerm::Integer(b)) => return a.cmp(b),
                 (OwnedTerm::Atom(a), OwnedTerm::Atom(b)) => return a.name.cmp(&b.name),
                 (OwnedTerm::Binary(a), OwnedTerm::Binary(b)) => return a.cmp(b),
+                (OwnedTerm::SharedBinary(a), OwnedTerm::SharedBinary(b)) => return a.cmp(b),
                 (OwnedTerm::String(a), OwnedTerm::String(b)) => return a.cmp(b),
                 (OwnedTerm::Nil, OwnedTerm::Nil) => return Ordering::Equal,
                 _ => {}
@@ -2154,6 +2704,11 @@ impl Ord for OwnedTerm {
                 (OwnedTerm::String(a), OwnedTerm::String(b)) => a.cmp(b),
                 (OwnedTerm::Binary(a), OwnedTerm::String(b)) => a.as_slice().cmp(b.as_bytes()),
                 (OwnedTerm::String(a), OwnedTerm::Binary(b)) => a.as_bytes().cmp(b.as_slice()),
+                (OwnedTerm::SharedBinary(a), OwnedTerm::SharedBinary(b)) => a.cmp(b),
+                (OwnedTerm::SharedBinary(a), OwnedTerm::Binary(b)) => a.as_ref().cmp(b.as_slice()),
+                (OwnedTerm::Binary(a), OwnedTerm::SharedBinary(b)) => a.as_slice().cmp(b.as_ref()),
+                (OwnedTerm::SharedBinary(a), OwnedTerm::String(b)) => a.as_ref().cmp(b.as_bytes()),
+                (OwnedTerm::String(a), OwnedTerm::SharedBinary(b)) => a.as_bytes().cmp(b.as_ref()),
                 (
                     OwnedTerm::BitBinary {
                         bytes: a,
@@ -2164,6 +2719,15 @@ impl Ord for OwnedTerm {
                         bits: bbits,
                     },
                 ) => a.cmp(b).then_with(|| abits.cmp(bbits)),
+                (OwnedTerm::ByteList(a), OwnedTerm::ByteList(b)) => a.cmp(b),
+                (OwnedTerm::ByteList(_), _) | (_, OwnedTerm::ByteList(_)) => self
+                    .clone()
+                    .into_integer_list()
+                    .cmp(&other.clone().into_integer_list()),
+                (OwnedTerm::OrderedMap(_), _) | (_, OwnedTerm::OrderedMap(_)) => self
+                    .clone()
+                    .into_unordered_map()
+                    .cmp(&other.clone().into_unordered_map()),
                 _ => Ordering::Equal,
             },
             other => other,
@@ -2180,10 +2744,12 @@ impl PartialOrd for OwnedTerm {
 impl fmt::Display for OwnedTerm {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            OwnedTerm::Shared(shared) => fmt::Display::fmt(shared, f),
             OwnedTerm::Atom(a) => write!(f, "{}", a.name),
             OwnedTerm::Integer(i) => write!(f, "{}", i),
             OwnedTerm::Float(fl) => write!(f, "{}", fl),
             OwnedTerm::Binary(b) => write!(f, "<<{} bytes>>", b.len()),
+            OwnedTerm::SharedBinary(b) => write!(f, "<<{} bytes>>", b.len()),
             OwnedTerm::BitBinary { bytes, bits } => {
                 write!(f, "<<{} bytes, {} bits>>", bytes.len(), bits)
             }
@@ -2198,6 +2764,16 @@ impl fmt::Display for OwnedTerm {
                 }
                 write!(f, "]")
             }
+            OwnedTerm::ByteList(bytes) => {
+                write!(f, "[")?;
+                for (i, b) in bytes.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", b)?;
+                }
+                write!(f, "]")
+            }
             OwnedTerm::Tuple(t) => {
                 write!(f, "{{")?;
                 for (i, term) in t.iter().enumerate() {
@@ -2218,6 +2794,16 @@ impl fmt::Display for OwnedTerm {
                 }
                 write!(f, "}}")
             }
+            OwnedTerm::OrderedMap(entries) => {
+                write!(f, "#{{")?;
+                for (i, (k, v)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{} => {}", k, v)?;
+                }
+                write!(f, "}}")
+            }
             OwnedTerm::Nil => write!(f, "[]"),
             OwnedTerm::Pid(p) => write!(f, "<{}.{}.{}>", p.id, p.serial, p.creation),
             OwnedTerm::Port(p) => write!(f, "#Port<{}>", p.id),
@@ -2232,6 +2818,9 @@ impl fmt::Display for OwnedTerm {
                 fun.module.name, fun.function.name, fun.arity
             ),
             OwnedTerm::InternalFun(fun) => write!(f, "fun {}/{}", fun.module.name, fun.arity),
+            OwnedTerm::ImproperList { elements, tail } if elements.is_empty() => {
+                write!(f, "{}", tail)
+            }
             OwnedTerm::ImproperList { elements, tail } => {
                 write!(f, "[")?;
                 for (i, term) in elements.iter().enumerate() {
@@ -2283,7 +2872,7 @@ impl IntoIterator for OwnedTerm {
     type IntoIter = OwnedTermIntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        match self {
+        match self.unshare() {
             OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) => {
                 OwnedTermIntoIter::Vec(elements.into_iter())
             }
@@ -2359,7 +2948,7 @@ impl Index<usize> for OwnedTerm {
 
     #[track_caller]
     fn index(&self, index: usize) -> &Self::Output {
-        match self {
+        match self.resolve() {
             OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) => &elements[index],
             OwnedTerm::Nil => panic!(
                 "index out of bounds: the len is 0 but the index is {}",
@@ -2375,7 +2964,7 @@ impl Index<&OwnedTerm> for OwnedTerm {
 
     #[track_caller]
     fn index(&self, key: &OwnedTerm) -> &Self::Output {
-        match self {
+        match self.resolve() {
             OwnedTerm::Map(m) => m.get(key).unwrap_or_else(|| panic!("key not found in map")),
             _ => panic!("cannot index {} with a key", self.type_name()),
         }
@@ -2388,7 +2977,7 @@ pub trait TermIndex {
 
 impl TermIndex for usize {
     fn get_from_term<'a>(&self, term: &'a OwnedTerm) -> Option<&'a OwnedTerm> {
-        match term {
+        match term.resolve() {
             OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) => elements.get(*self),
             _ => None,
         }
@@ -2397,7 +2986,7 @@ impl TermIndex for usize {
 
 impl TermIndex for &OwnedTerm {
     fn get_from_term<'a>(&self, term: &'a OwnedTerm) -> Option<&'a OwnedTerm> {
-        match term {
+        match term.resolve() {
             OwnedTerm::Map(m) => m.get(self),
             _ => None,
         }
@@ -2406,7 +2995,7 @@ impl TermIndex for &OwnedTerm {
 
 impl TermIndex for &str {
     fn get_from_term<'a>(&self, term: &'a OwnedTerm) -> Option<&'a OwnedTerm> {
-        match term {
+        match term.resolve() {
             OwnedTerm::Map(m) => {
                 let key = OwnedTerm::atom(*self);
                 m.get(&key)
@@ -2619,6 +3208,12 @@ fn compare_term_lists(a: &[OwnedTerm], b: &[OwnedTerm]) -> Ordering {
     a.len().cmp(&b.len())
 }
 
+/// The heap size of a vector of terms, including its unused capacity.
+fn elements_size(elements: &Vec<OwnedTerm>) -> usize {
+    (elements.capacity() - elements.len()) * size_of::<OwnedTerm>()
+        + elements.iter().map(OwnedTerm::heap_size).sum::<usize>()
+}
+
 #[cfg(feature = "serde")]
 impl Serialize for OwnedTerm {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -2626,6 +3221,7 @@ impl Serialize for OwnedTerm {
         S: Serializer,
     {
         match self {
+            OwnedTerm::Shared(shared) => shared.as_ref().serialize(serializer),
             OwnedTerm::Atom(atom) => match atom.as_str() {
                 "true" => serializer.serialize_bool(true),
                 "false" => serializer.serialize_bool(false),
@@ -2635,6 +3231,7 @@ impl Serialize for OwnedTerm {
             OwnedTerm::Integer(i) => serializer.serialize_i64(*i),
             OwnedTerm::Float(f) => serializer.serialize_f64(*f),
             OwnedTerm::Binary(b) => serializer.serialize_bytes(b),
+            OwnedTerm::SharedBinary(b) => serializer.serialize_bytes(b),
             OwnedTerm::String(s) => serializer.serialize_str(s),
             OwnedTerm::List(elements) => {
                 let mut seq = serializer.serialize_seq(Some(elements.len()))?;
@@ -2643,6 +3240,13 @@ impl Serialize for OwnedTerm {
                 }
                 seq.end()
             }
+            OwnedTerm::ByteList(bytes) => {
+                let mut seq = serializer.serialize_seq(Some(bytes.len()))?;
+                for b in bytes {
+                    seq.serialize_element(&i64::from(*b))?;
+                }
+                seq.end()
+            }
             OwnedTerm::Tuple(elements) => {
                 let mut seq = serializer.serialize_seq(Some(elements.len()))?;
                 for elem in elements {
@@ -2657,6 +3261,13 @@ impl Serialize for OwnedTerm {
                 }
                 map.end()
             }
+            OwnedTerm::OrderedMap(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (k, v) in entries {
+                    map.serialize_entry(k, v)?;
+                }
+                map.end()
+            }
             OwnedTerm::Nil => {
                 let seq = serializer.serialize_seq(Some(0))?;
                 seq.end()
@@ -2680,12 +3291,9 @@ impl Serialize for OwnedTerm {
                 }
             }
             OwnedTerm::ImproperList { elements, tail } => {
-                let mut seq = serializer.serialize_seq(Some(elements.len() + 1))?;
-                for elem in elements {
-                    seq.serialize_element(elem)?;
-                }
-                seq.serialize_element(tail.as_ref())?;
-                seq.end()
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(IMPROPER_LIST_TAG, &(elements, tail.as_ref()))?;
+                map.end()
             }
             OwnedTerm::BitBinary { bytes, .. } => serializer.serialize_bytes(bytes),
             OwnedTerm::Pid(pid) => serializer.serialize_str(&pid.to_string()),
@@ -2801,7 +3409,7 @@ impl<'de> Visitor<'de> for OwnedTermVisitor {
         while let Some((k, v)) = map.next_entry::<OwnedTerm, OwnedTerm>()? {
             m.insert(k, v);
         }
-        Ok(OwnedTerm::Map(m))
+        Ok(OwnedTerm::Map(m).untag_improper_list())
     }
 }
 
@@ -0,0 +1,183 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A typed bridge between the `{ok, Value}` / `{error, Reason}` convention and [`Result`].
+
+use crate::term::OwnedTerm;
+
+/// An `ok`, `{ok, Value}`, `{error, Reason}` or `{error, Reason, Stacktrace}` term.
+///
+/// # Example
+///
+/// ```
+/// use erltf::{ErlResult, OwnedTerm};
+///
+/// let term = OwnedTerm::ok_tuple(OwnedTerm::integer(42));
+/// assert_eq!(ErlResult::from_term(term), Ok(OwnedTerm::integer(42)));
+///
+/// let term: OwnedTerm = ErlResult::error(OwnedTerm::atom("enoent")).into();
+/// assert_eq!(term, OwnedTerm::error_tuple(OwnedTerm::atom("enoent")));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErlResult {
+    /// `{ok, Value}`, or the bare `ok` atom, in which case the value is `ok`.
+    Ok(OwnedTerm),
+    /// `{error, Reason}`, or `{error, Reason, Stacktrace}` when `stacktrace` is set.
+    Error {
+        reason: OwnedTerm,
+        stacktrace: Option<OwnedTerm>,
+    },
+}
+
+impl ErlResult {
+    pub fn ok(value: OwnedTerm) -> Self {
+        ErlResult::Ok(value)
+    }
+
+    pub fn error(reason: OwnedTerm) -> Self {
+        ErlResult::Error {
+            reason,
+            stacktrace: None,
+        }
+    }
+
+    pub fn error_with_stacktrace(reason: OwnedTerm, stacktrace: OwnedTerm) -> Self {
+        ErlResult::Error {
+            reason,
+            stacktrace: Some(stacktrace),
+        }
+    }
+
+    /// Parses a term that follows the ok/error convention, or returns `None`.
+    #[must_use]
+    pub fn parse(term: &OwnedTerm) -> Option<Self> {
+        if term.is_atom_with_name("ok") {
+            return Some(ErlResult::Ok(term.clone()));
+        }
+        match term.as_tuple()? {
+            [tag, value] if tag.is_atom_with_name("ok") => Some(ErlResult::ok(value.clone())),
+            [tag, reason] if tag.is_atom_with_name("error") => {
+                Some(ErlResult::error(reason.clone()))
+            }
+            [tag, reason, stacktrace] if tag.is_atom_with_name("error") => Some(
+                ErlResult::error_with_stacktrace(reason.clone(), stacktrace.clone()),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Converts a term to a [`Result`] with the value or the error reason.
+    /// A term that does not follow the convention becomes the error.
+    pub fn from_term(term: OwnedTerm) -> Result<OwnedTerm, OwnedTerm> {
+        match Self::parse(&term) {
+            Some(result) => result.into_result(),
+            None => Err(term),
+        }
+    }
+
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        matches!(self, ErlResult::Ok(_))
+    }
+
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        !self.is_ok()
+    }
+
+    #[must_use]
+    pub fn value(&self) -> Option<&OwnedTerm> {
+        match self {
+            ErlResult::Ok(value) => Some(value),
+            ErlResult::Error { .. } => None,
+        }
+    }
+
+    #[must_use]
+    pub fn reason(&self) -> Option<&OwnedTerm> {
+        match self {
+            ErlResult::Ok(_) => None,
+            ErlResult::Error { reason, .. } => Some(reason),
+        }
+    }
+
+    #[must_use]
+    pub fn stacktrace(&self) -> Option<&OwnedTerm> {
+        match self {
+            ErlResult::Ok(_) => None,
+            ErlResult::Error { stacktrace, .. } => stacktrace.as_ref(),
+        }
+    }
+
+    /// Drops the stacktrace, if any, and returns the value or the error reason.
+    pub fn into_result(self) -> Result<OwnedTerm, OwnedTerm> {
+        match self {
+            ErlResult::Ok(value) => Ok(value),
+            ErlResult::Error { reason, .. } => Err(reason),
+        }
+    }
+
+    /// Returns the value, or maps the error reason and stacktrace with `f`.
+    pub fn as_ok_or_else<E, F>(self, f: F) -> Result<OwnedTerm, E>
+    where
+        F: FnOnce(OwnedTerm, Option<OwnedTerm>) -> E,
+    {
+        match self {
+            ErlResult::Ok(value) => Ok(value),
+            ErlResult::Error { reason, stacktrace } => Err(f(reason, stacktrace)),
+        }
+    }
+
+    /// Maps the value of an `ok` result, leaving errors untouched.
+    pub fn map_ok<F>(self, f: F) -> Self
+    where
+        F: FnOnce(OwnedTerm) -> OwnedTerm,
+    {
+        match self {
+            ErlResult::Ok(value) => ErlResult::Ok(f(value)),
+            error => error,
+        }
+    }
+}
+
+impl From<ErlResult> for OwnedTerm {
+    fn from(result: ErlResult) -> Self {
+        match result {
+            ErlResult::Ok(value) => OwnedTerm::ok_tuple(value),
+            ErlResult::Error {
+                reason,
+                stacktrace: None,
+            } => OwnedTerm::error_tuple(reason),
+            ErlResult::Error {
+                reason,
+                stacktrace: Some(stacktrace),
+            } => OwnedTerm::Tuple(vec![OwnedTerm::error(), reason, stacktrace]),
+        }
+    }
+}
+
+impl From<Result<OwnedTerm, OwnedTerm>> for ErlResult {
+    fn from(result: Result<OwnedTerm, OwnedTerm>) -> Self {
+        match result {
+            Ok(value) => ErlResult::ok(value),
+            Err(reason) => ErlResult::error(reason),
+        }
+    }
+}
+
+impl From<ErlResult> for Result<OwnedTerm, OwnedTerm> {
+    fn from(result: ErlResult) -> Self {
+        result.into_result()
+    }
+}
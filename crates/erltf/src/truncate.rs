@@ -0,0 +1,175 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Size-bounded copies of terms, for logging and tracing message payloads.
+//!
+//! [`OwnedTerm::truncated`] keeps the first elements of lists, tuples and maps and
+//! the first bytes of binaries and strings, marking what was left out with [`ELISION`].
+//! Elided parts are never cloned, so truncating a huge term is cheap.
+
+use crate::term::OwnedTerm;
+use crate::types::Atom;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Marks elided elements: an atom in lists, tuples and maps, a suffix in binaries and strings.
+pub const ELISION: &str = "...";
+
+impl OwnedTerm {
+    /// Returns a copy with at most `max_elements` elements in each list, tuple and map,
+    /// and at most `max_binary_preview` bytes of each binary and string.
+    ///
+    /// Collections that were cut short end with a `'...'` atom (a `'...' => '...'` entry
+    /// in maps), binaries and strings with `...`. Byte lists that were cut short
+    /// become lists of integers.
+    ///
+    /// # Example
+    /// ```
+    /// use erltf::OwnedTerm;
+    ///
+    /// let list = OwnedTerm::List((0..1_000_000).map(OwnedTerm::integer).collect());
+    /// assert_eq!(list.truncated(2, 16).to_string(), "[0, 1, ...]");
+    /// ```
+    #[must_use]
+    pub fn truncated(&self, max_elements: usize, max_binary_preview: usize) -> OwnedTerm {
+        let limits = Limits {
+            max_elements,
+            max_binary_preview,
+        };
+        limits.truncate(self)
+    }
+
+    /// A [`Display`](fmt::Display) of [`OwnedTerm::truncated`] for log statements.
+    #[must_use]
+    pub fn display_truncated(
+        &self,
+        max_elements: usize,
+        max_binary_preview: usize,
+    ) -> TruncatedDisplay<'_> {
+        TruncatedDisplay {
+            term: self,
+            max_elements,
+            max_binary_preview,
+        }
+    }
+}
+
+/// Displays a term truncated with [`OwnedTerm::truncated`], see [`OwnedTerm::display_truncated`].
+#[derive(Debug, Clone, Copy)]
+pub struct TruncatedDisplay<'a> {
+    term: &'a OwnedTerm,
+    max_elements: usize,
+    max_binary_preview: usize,
+}
+
+impl fmt::Display for TruncatedDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let truncated = self
+            .term
+            .truncated(self.max_elements, self.max_binary_preview);
+        fmt::Display::fmt(&truncated, f)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Limits {
+    max_elements: usize,
+    max_binary_preview: usize,
+}
+
+impl Limits {
+    fn truncate(&self, term: &OwnedTerm) -> OwnedTerm {
+        let term = term.resolve();
+        match term {
+            OwnedTerm::List(elements) => OwnedTerm::List(self.elements(elements)),
+            OwnedTerm::Tuple(elements) => OwnedTerm::Tuple(self.elements(elements)),
+            OwnedTerm::ImproperList { elements, .. } if elements.len() > self.max_elements => {
+                OwnedTerm::List(self.elements(elements))
+            }
+            OwnedTerm::ImproperList { elements, tail } => OwnedTerm::ImproperList {
+                elements: self.elements(elements),
+                tail: Box::new(self.truncate(tail)),
+            },
+            OwnedTerm::ByteList(bytes) if bytes.len() > self.max_elements => {
+                let mut elements: Vec<OwnedTerm> = bytes[..self.max_elements]
+                    .iter()
+                    .map(|b| OwnedTerm::Integer(i64::from(*b)))
+                    .collect();
+                elements.push(elision());
+                OwnedTerm::List(elements)
+            }
+            OwnedTerm::Map(map) => {
+                let mut truncated: BTreeMap<OwnedTerm, OwnedTerm> = map
+                    .iter()
+                    .take(self.max_elements)
+                    .map(|(k, v)| (self.truncate(k), self.truncate(v)))
+                    .collect();
+                if map.len() > self.max_elements {
+                    truncated.insert(elision(), elision());
+                }
+                OwnedTerm::Map(truncated)
+            }
+            OwnedTerm::OrderedMap(entries) => {
+                let mut truncated: Vec<(OwnedTerm, OwnedTerm)> = entries
+                    .iter()
+                    .take(self.max_elements)
+                    .map(|(k, v)| (self.truncate(k), self.truncate(v)))
+                    .collect();
+                if entries.len() > self.max_elements {
+                    truncated.push((elision(), elision()));
+                }
+                OwnedTerm::OrderedMap(truncated)
+            }
+            OwnedTerm::Binary(bytes) if bytes.len() > self.max_binary_preview => {
+                OwnedTerm::Binary(self.bytes(bytes))
+            }
+            OwnedTerm::SharedBinary(bytes) if bytes.len() > self.max_binary_preview => {
+                OwnedTerm::Binary(self.bytes(bytes))
+            }
+            OwnedTerm::BitBinary { bytes, .. } if bytes.len() > self.max_binary_preview => {
+                OwnedTerm::Binary(self.bytes(bytes))
+            }
+            OwnedTerm::String(s) if s.len() > self.max_binary_preview => {
+                let mut end = self.max_binary_preview;
+                while !s.is_char_boundary(end) {
+                    end -= 1;
+                }
+                OwnedTerm::String(format!("{}{}", &s[..end], ELISION))
+            }
+            other => other.clone(),
+        }
+    }
+
+    fn elements(&self, elements: &[OwnedTerm]) -> Vec<OwnedTerm> {
+        let mut truncated: Vec<OwnedTerm> = elements
+            .iter()
+            .take(self.max_elements)
+            .map(|e| self.truncate(e))
+            .collect();
+        if elements.len() > self.max_elements {
+            truncated.push(elision());
+        }
+        truncated
+    }
+
+    fn bytes(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut preview = bytes[..self.max_binary_preview].to_vec();
+        preview.extend_from_slice(ELISION.as_bytes());
+        preview
+    }
+}
+
+fn elision() -> OwnedTerm {
+    OwnedTerm::Atom(Atom::new(ELISION))
+}
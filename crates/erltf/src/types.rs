@@ -92,6 +92,14 @@ impl Atom {
         &self.name
     }
 
+    /// Returns true for the common atoms that share a single allocation.
+    #[must_use]
+    pub fn is_preallocated(&self) -> bool {
+        COMMON_ATOMS
+            .iter()
+            .any(|(atom_str, _)| *atom_str == self.as_str())
+    }
+
     #[inline]
     #[must_use]
     pub fn len(&self) -> usize {
@@ -353,6 +361,21 @@ impl ExternalPid {
         self.local_ext_bytes.is_some()
     }
 
+    /// Returns true if this pid was created on `node`, in any of its incarnations.
+    #[inline]
+    #[must_use]
+    pub fn belongs_to(&self, node: &str) -> bool {
+        self.node.as_str() == node
+    }
+
+    /// Returns true if this pid was created by the current incarnation of `node`,
+    /// the one with `creation`. A pid from an earlier incarnation is stale.
+    #[inline]
+    #[must_use]
+    pub fn is_local(&self, node: &str, creation: u32) -> bool {
+        self.belongs_to(node) && self.creation == creation
+    }
+
     pub fn from_string(node: Atom, pid_str: &str) -> Result<Self, DecodeError> {
         let trimmed = pid_str.trim();
 
@@ -533,6 +556,21 @@ impl ExternalPort {
     pub fn is_local_ext(&self) -> bool {
         self.local_ext_bytes.is_some()
     }
+
+    /// Returns true if this port was created on `node`, in any of its incarnations.
+    #[inline]
+    #[must_use]
+    pub fn belongs_to(&self, node: &str) -> bool {
+        self.node.as_str() == node
+    }
+
+    /// Returns true if this port was created by the current incarnation of `node`,
+    /// the one with `creation`. A port from an earlier incarnation is stale.
+    #[inline]
+    #[must_use]
+    pub fn is_local(&self, node: &str, creation: u32) -> bool {
+        self.belongs_to(node) && self.creation == creation
+    }
 }
 
 /// Represents an Erlang reference originating from a remote node.
@@ -617,6 +655,21 @@ impl ExternalReference {
     pub fn is_local_ext(&self) -> bool {
         self.local_ext_bytes.is_some()
     }
+
+    /// Returns true if this reference was created on `node`, in any of its incarnations.
+    #[inline]
+    #[must_use]
+    pub fn belongs_to(&self, node: &str) -> bool {
+        self.node.as_str() == node
+    }
+
+    /// Returns true if this reference was created by the current incarnation of `node`,
+    /// the one with `creation`. A reference from an earlier incarnation is stale.
+    #[inline]
+    #[must_use]
+    pub fn is_local(&self, node: &str, creation: u32) -> bool {
+        self.belongs_to(node) && self.creation == creation
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -659,7 +712,7 @@ impl Mfa {
     }
 
     pub fn try_from_term(term: &OwnedTerm) -> Option<Self> {
-        match term {
+        match term.resolve() {
             OwnedTerm::Tuple(elems) if elems.len() == 3 => {
                 let module = elems[0].as_atom()?.clone();
                 let function = elems[1].as_atom()?.clone();
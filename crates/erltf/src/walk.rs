@@ -0,0 +1,248 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traversal of nested terms.
+//!
+//! [`OwnedTerm::walk`] visits every term in pre-order and [`OwnedTerm::transform`]
+//! rebuilds a term bottom-up. Both use an explicit stack instead of recursion,
+//! so deeply nested terms cannot overflow the call stack.
+//!
+//! Children are visited in order: list and tuple elements, then the tail of
+//! an improper list, map keys each followed by its value, and the free variables
+//! of a fun.
+
+use crate::term::OwnedTerm;
+use crate::types::InternalFun;
+use std::collections::BTreeMap;
+use std::mem;
+use std::vec;
+
+/// What [`OwnedTerm::walk`] does after visiting a term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalkControl {
+    /// Visit the children of this term.
+    Continue,
+    /// Do not visit the children of this term.
+    SkipChildren,
+    /// End the walk.
+    Stop,
+}
+
+/// Receives every term visited by [`OwnedTerm::walk`] with its depth.
+/// The root term has depth 0.
+pub trait TermVisitor {
+    fn visit(&mut self, term: &OwnedTerm, depth: usize) -> WalkControl;
+}
+
+impl<F> TermVisitor for F
+where
+    F: FnMut(&OwnedTerm, usize) -> WalkControl,
+{
+    fn visit(&mut self, term: &OwnedTerm, depth: usize) -> WalkControl {
+        self(term, depth)
+    }
+}
+
+/// The result of a [`OwnedTerm::transform`] step.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transform {
+    /// Use this term and transform its children.
+    Descend(OwnedTerm),
+    /// Use this term as is.
+    Replace(OwnedTerm),
+}
+
+impl OwnedTerm {
+    /// Visits this term and its descendants in pre-order.
+    ///
+    /// Returns `false` if the visitor stopped the walk.
+    pub fn walk<V: TermVisitor + ?Sized>(&self, visitor: &mut V) -> bool {
+        let mut stack = vec![(self, 0)];
+        while let Some((term, depth)) = stack.pop() {
+            let term = term.resolve();
+            match visitor.visit(term, depth) {
+                WalkControl::Stop => return false,
+                WalkControl::SkipChildren => continue,
+                WalkControl::Continue => {}
+            }
+
+            let children_start = stack.len();
+            match term {
+                OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) => {
+                    stack.extend(elements.iter().map(|e| (e, depth + 1)));
+                }
+                OwnedTerm::ImproperList { elements, tail } => {
+                    stack.extend(elements.iter().map(|e| (e, depth + 1)));
+                    stack.push((tail, depth + 1));
+                }
+                OwnedTerm::Map(map) => {
+                    for (k, v) in map {
+                        stack.push((k, depth + 1));
+                        stack.push((v, depth + 1));
+                    }
+                }
+                OwnedTerm::OrderedMap(entries) => {
+                    for (k, v) in entries {
+                        stack.push((k, depth + 1));
+                        stack.push((v, depth + 1));
+                    }
+                }
+                OwnedTerm::InternalFun(fun) => {
+                    stack.extend(fun.free_vars.iter().map(|v| (v, depth + 1)));
+                }
+                _ => {}
+            }
+            // the stack is LIFO, reverse so that children are visited in order
+            stack[children_start..].reverse();
+        }
+        true
+    }
+
+    /// Rebuilds this term by passing it and its descendants to `f` in pre-order.
+    ///
+    /// `f` decides whether the children of the term it returns are transformed as well.
+    pub fn transform<F>(self, mut f: F) -> OwnedTerm
+    where
+        F: FnMut(OwnedTerm) -> Transform,
+    {
+        let mut stack: Vec<Frame> = Vec::new();
+        let mut next = self;
+
+        loop {
+            let mut completed = match f(next.unshare()) {
+                Transform::Replace(term) => Some(term),
+                Transform::Descend(term) => match Frame::open(term) {
+                    Ok(frame) => {
+                        stack.push(frame);
+                        None
+                    }
+                    Err(leaf) => Some(leaf),
+                },
+            };
+
+            loop {
+                if let Some(value) = completed.take() {
+                    match stack.last_mut() {
+                        Some(frame) => frame.done.push(value),
+                        None => return value,
+                    }
+                }
+
+                let frame = stack.last_mut().expect("a frame is open");
+                match frame.remaining.next() {
+                    Some(child) => {
+                        next = child;
+                        break;
+                    }
+                    None => {
+                        let frame = stack.pop().expect("a frame is open");
+                        completed = Some(frame.finish());
+                    }
+                }
+            }
+        }
+    }
+}
+
+enum FrameKind {
+    List,
+    Tuple,
+    ImproperList,
+    Map,
+    OrderedMap,
+    Fun(Box<InternalFun>),
+}
+
+struct Frame {
+    kind: FrameKind,
+    done: Vec<OwnedTerm>,
+    remaining: vec::IntoIter<OwnedTerm>,
+}
+
+impl Frame {
+    fn new(kind: FrameKind, children: Vec<OwnedTerm>) -> Self {
+        Frame {
+            kind,
+            done: Vec::with_capacity(children.len()),
+            remaining: children.into_iter(),
+        }
+    }
+
+    fn open(term: OwnedTerm) -> Result<Self, OwnedTerm> {
+        match term {
+            OwnedTerm::List(elements) => Ok(Frame::new(FrameKind::List, elements)),
+            OwnedTerm::Tuple(elements) => Ok(Frame::new(FrameKind::Tuple, elements)),
+            OwnedTerm::ImproperList { mut elements, tail } => {
+                elements.push(*tail);
+                Ok(Frame::new(FrameKind::ImproperList, elements))
+            }
+            OwnedTerm::Map(map) => {
+                let mut children = Vec::with_capacity(map.len() * 2);
+                for (k, v) in map {
+                    children.push(k);
+                    children.push(v);
+                }
+                Ok(Frame::new(FrameKind::Map, children))
+            }
+            OwnedTerm::OrderedMap(entries) => {
+                let mut children = Vec::with_capacity(entries.len() * 2);
+                for (k, v) in entries {
+                    children.push(k);
+                    children.push(v);
+                }
+                Ok(Frame::new(FrameKind::OrderedMap, children))
+            }
+            OwnedTerm::InternalFun(mut fun) => {
+                let free_vars = mem::take(&mut fun.free_vars);
+                Ok(Frame::new(FrameKind::Fun(fun), free_vars))
+            }
+            leaf => Err(leaf),
+        }
+    }
+
+    fn finish(self) -> OwnedTerm {
+        let mut done = self.done;
+        match self.kind {
+            FrameKind::List => OwnedTerm::List(done),
+            FrameKind::Tuple => OwnedTerm::Tuple(done),
+            FrameKind::ImproperList => {
+                let tail = done.pop().unwrap_or(OwnedTerm::Nil);
+                OwnedTerm::ImproperList {
+                    elements: done,
+                    tail: Box::new(tail),
+                }
+            }
+            FrameKind::Map => {
+                let mut map = BTreeMap::new();
+                let mut iter = done.into_iter();
+                while let (Some(k), Some(v)) = (iter.next(), iter.next()) {
+                    map.insert(k, v);
+                }
+                OwnedTerm::Map(map)
+            }
+            FrameKind::OrderedMap => {
+                let mut entries = Vec::with_capacity(done.len() / 2);
+                let mut iter = done.into_iter();
+                while let (Some(k), Some(v)) = (iter.next(), iter.next()) {
+                    entries.push((k, v));
+                }
+                OwnedTerm::OrderedMap(entries)
+            }
+            FrameKind::Fun(mut fun) => {
+                fun.free_vars = done;
+                OwnedTerm::InternalFun(fun)
+            }
+        }
+    }
+}
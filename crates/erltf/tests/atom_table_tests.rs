@@ -0,0 +1,244 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::decode_config::{AtomAdmission, admitted_atom_count};
+use erltf::decoder::{
+    AtomCache, decode_control_with_atom_cache_and_config,
+    decode_payload_with_atom_cache_and_config, decode_with_atom_cache_and_config,
+    decode_with_config,
+};
+use erltf::types::{Atom, ExternalPid, ExternalPort, ExternalReference};
+use erltf::{
+    AtomLimitPolicy, AtomTable, DecodeConfig, DecodeError, OwnedTerm, encode,
+    encode_with_dist_header, encode_with_dist_header_multi, erl_atom, erl_list, erl_tuple,
+};
+
+#[test]
+fn test_atom_table_counts_distinct_atoms() {
+    let config = DecodeConfig::new().atoms_error_after(10);
+    let mut atoms = AtomTable::new();
+    let encoded = encode(&erl_list![erl_atom!("a"), erl_atom!("b"), erl_atom!("a")]).unwrap();
+
+    let term = decode_with_config(&encoded, &config, &mut atoms).unwrap();
+
+    assert_eq!(
+        term,
+        erl_list![erl_atom!("a"), erl_atom!("b"), erl_atom!("a")]
+    );
+    assert_eq!(atoms.distinct_count(), 2);
+    assert_eq!(atoms.total_decoded(), 3);
+    assert!(atoms.contains("a"));
+    assert!(!atoms.contains("c"));
+}
+
+#[test]
+fn test_atom_table_records_nothing_without_a_limit() {
+    let config = DecodeConfig::new();
+    let mut atoms = AtomTable::new();
+    let encoded = encode(&erl_list![erl_atom!("a"), erl_atom!("b")]).unwrap();
+
+    decode_with_config(&encoded, &config, &mut atoms).unwrap();
+
+    assert_eq!(atoms.distinct_count(), 0);
+    assert_eq!(atoms.total_decoded(), 2);
+    assert!(!atoms.contains("a"));
+}
+
+#[test]
+fn test_atom_table_does_not_count_preallocated_atoms() {
+    let config = DecodeConfig::new().atoms_error_after(0);
+    let mut atoms = AtomTable::new();
+    let encoded = encode(&erl_tuple![erl_atom!("ok"), erl_atom!("true")]).unwrap();
+
+    let term = decode_with_config(&encoded, &config, &mut atoms).unwrap();
+
+    assert_eq!(term, erl_tuple![erl_atom!("ok"), erl_atom!("true")]);
+    assert_eq!(atoms.distinct_count(), 0);
+}
+
+#[test]
+fn test_atoms_as_binaries_after_limit() {
+    let config = DecodeConfig::new().atoms_as_binaries_after(2);
+    let mut atoms = AtomTable::new();
+    let encoded = encode(&erl_list![
+        erl_atom!("first"),
+        erl_atom!("second"),
+        erl_atom!("third"),
+        erl_atom!("first")
+    ])
+    .unwrap();
+
+    let term = decode_with_config(&encoded, &config, &mut atoms).unwrap();
+
+    assert_eq!(
+        term,
+        erl_list![
+            erl_atom!("first"),
+            erl_atom!("second"),
+            OwnedTerm::Binary(b"third".to_vec()),
+            erl_atom!("first")
+        ]
+    );
+    assert_eq!(atoms.distinct_count(), 2);
+    assert_eq!(atoms.total_decoded(), 4);
+}
+
+#[test]
+fn test_atoms_error_after_limit() {
+    let config = DecodeConfig::new().atoms_error_after(1);
+    let mut atoms = AtomTable::new();
+    let encoded = encode(&erl_list![erl_atom!("first"), erl_atom!("second")]).unwrap();
+
+    let result = decode_with_config(&encoded, &config, &mut atoms);
+
+    assert_eq!(result, Err(DecodeError::AtomLimitExceeded { limit: 1 }));
+}
+
+#[test]
+fn test_node_atoms_past_the_limit_fail_instead_of_becoming_binaries() {
+    let config = DecodeConfig::new().atoms_as_binaries_after(1);
+    let node = Atom::new("remote@host");
+    let terms = [
+        OwnedTerm::Pid(ExternalPid::new(node.clone(), 1, 0, 1)),
+        OwnedTerm::Port(ExternalPort::new(node.clone(), 1, 1)),
+        OwnedTerm::Reference(ExternalReference::new(node, 1, vec![1, 2, 3])),
+    ];
+
+    for term in terms {
+        let mut atoms = AtomTable::new();
+        let encoded = encode(&erl_tuple![erl_atom!("first"), term]).unwrap();
+
+        let result = decode_with_config(&encoded, &config, &mut atoms);
+
+        assert_eq!(result, Err(DecodeError::AtomLimitExceeded { limit: 1 }));
+    }
+}
+
+#[test]
+fn test_node_atoms_within_the_limit_are_admitted() {
+    let config = DecodeConfig::new().atoms_as_binaries_after(1);
+    let mut atoms = AtomTable::new();
+    let pid = OwnedTerm::Pid(ExternalPid::new(Atom::new("remote@host"), 1, 0, 1));
+    let encoded = encode(&erl_tuple![pid.clone(), erl_atom!("later")]).unwrap();
+
+    let term = decode_with_config(&encoded, &config, &mut atoms).unwrap();
+
+    assert_eq!(term, erl_tuple![pid, OwnedTerm::Binary(b"later".to_vec())]);
+    assert!(atoms.contains("remote@host"));
+}
+
+#[test]
+fn test_atom_limit_persists_across_decodes() {
+    let config = DecodeConfig::new().atoms_error_after(1);
+    let mut atoms = AtomTable::new();
+
+    let first = encode(&erl_atom!("first")).unwrap();
+    let second = encode(&erl_atom!("second")).unwrap();
+
+    assert!(decode_with_config(&first, &config, &mut atoms).is_ok());
+    assert!(decode_with_config(&first, &config, &mut atoms).is_ok());
+    assert!(decode_with_config(&second, &config, &mut atoms).is_err());
+
+    atoms.clear();
+    assert!(decode_with_config(&second, &config, &mut atoms).is_ok());
+}
+
+#[test]
+fn test_atom_limit_applies_to_atom_cache_refs() {
+    let config = DecodeConfig::new().atoms_as_binaries_after(1);
+    let mut atoms = AtomTable::new();
+    let mut cache = AtomCache::new();
+    let encoded =
+        encode_with_dist_header(&erl_tuple![erl_atom!("alpha"), erl_atom!("beta")]).unwrap();
+
+    let (term, payload) =
+        decode_with_atom_cache_and_config(&encoded, &mut cache, &config, &mut atoms).unwrap();
+
+    assert!(payload.is_none());
+    let elements = term.as_tuple().unwrap();
+    let binaries = elements.iter().filter(|t| t.as_binary().is_some()).count();
+    assert_eq!(binaries, 1);
+    assert_eq!(atoms.distinct_count(), 1);
+}
+
+#[test]
+fn test_control_and_payload_decode_separately() {
+    let config = DecodeConfig::new();
+    let mut atoms = AtomTable::new();
+    let mut cache = AtomCache::new();
+    let control = erl_tuple![erl_atom!("send"), erl_atom!("shared")];
+    let payload = erl_tuple![erl_atom!("shared"), erl_atom!("payload_only")];
+    let encoded = encode_with_dist_header_multi(&[&control, &payload]).unwrap();
+
+    let (decoded_control, remaining) =
+        decode_control_with_atom_cache_and_config(&encoded, &mut cache, &config, &mut atoms)
+            .unwrap();
+    let decoded_payload =
+        decode_payload_with_atom_cache_and_config(remaining, &cache, &config, &mut atoms).unwrap();
+
+    assert_eq!(decoded_control, control);
+    assert_eq!(decoded_payload, payload);
+}
+
+#[test]
+fn test_undecodable_payload_leaves_control_decodable() {
+    let config = DecodeConfig::new();
+    let mut atoms = AtomTable::new();
+    let mut cache = AtomCache::new();
+    let control = erl_tuple![erl_atom!("send")];
+    let mut encoded = encode_with_dist_header(&control).unwrap();
+    encoded.extend([200, 1, 2, 3]);
+
+    let (decoded_control, remaining) =
+        decode_control_with_atom_cache_and_config(&encoded, &mut cache, &config, &mut atoms)
+            .unwrap();
+
+    assert_eq!(decoded_control, control);
+    assert_eq!(remaining, &[200, 1, 2, 3]);
+    assert!(
+        decode_payload_with_atom_cache_and_config(remaining, &cache, &config, &mut atoms).is_err()
+    );
+}
+
+#[test]
+fn test_atom_table_admit() {
+    let config = DecodeConfig::new().atoms_error_after(1);
+    let mut atoms = AtomTable::new();
+
+    assert!(matches!(
+        atoms.admit("one", &config),
+        AtomAdmission::Admitted(_)
+    ));
+    assert!(matches!(
+        atoms.admit("one", &config),
+        AtomAdmission::Admitted(_)
+    ));
+    assert_eq!(
+        atoms.admit("two", &config),
+        AtomAdmission::Rejected(AtomLimitPolicy::Error)
+    );
+    // Tables of other tests come and go, this table's atom is counted while it lives
+    assert!(admitted_atom_count() >= atoms.distinct_count());
+}
+
+#[test]
+fn test_decode_config_accessors() {
+    let config = DecodeConfig::new();
+    assert_eq!(config.atom_limit(), None);
+    assert_eq!(config.atom_limit_policy(), None);
+
+    let config = config.atoms_as_binaries_after(10);
+    assert_eq!(config.atom_limit(), Some(10));
+    assert_eq!(config.atom_limit_policy(), Some(AtomLimitPolicy::AsBinary));
+}
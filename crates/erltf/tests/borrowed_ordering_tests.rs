@@ -24,8 +24,8 @@ fn test_borrowed_owned_equivalence_basic_types() {
     let owned_int = OwnedTerm::integer(42);
     let borrowed_int = BorrowedTerm::Integer(42);
 
-    let owned_float = OwnedTerm::float(3.14);
-    let borrowed_float = BorrowedTerm::Float(3.14);
+    let owned_float = OwnedTerm::float(2.5);
+    let borrowed_float = BorrowedTerm::Float(2.5);
 
     assert_eq!(
         owned_int.cmp(&owned_float),
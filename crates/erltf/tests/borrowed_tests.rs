@@ -12,7 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use erltf::{BorrowedTerm, ParsingContext, PathSegment, decode, decode_borrowed, encode};
+use erltf::decoder::AtomCache;
+use erltf::encoder::encode_borrowed_with_dist_header_multi;
+use erltf::{
+    BorrowedTerm, ParsingContext, PathSegment, decode, decode_borrowed, decode_with_atom_cache,
+    encode, encode_borrowed, encode_borrowed_with_dist_header, erl_atom, erl_int, erl_list,
+    erl_map, erl_tuple,
+};
 use std::borrow::Cow;
 
 #[test]
@@ -185,3 +191,65 @@ fn test_parse_context_path_display() {
     let path = ctx.display_path();
     assert_eq!(path, "root[0][5].key");
 }
+
+#[test]
+fn test_encode_borrowed_matches_owned_encoding() {
+    let owned = erl_tuple![
+        erl_atom!("reply"),
+        erl_list![erl_int!(1), erl_int!(70000), erl_int!(i64::MAX)],
+        erl_map! { erl_atom!("k") => erltf::OwnedTerm::binary(b"value".to_vec()) },
+        erltf::OwnedTerm::Float(1.5),
+    ];
+    let data = encode(&owned).unwrap();
+    let borrowed = decode_borrowed(&data).unwrap();
+
+    assert_eq!(encode_borrowed(&borrowed).unwrap(), data);
+}
+
+#[test]
+fn test_encode_borrowed_empty_list_and_improper_list() {
+    let owned = erl_tuple![
+        erltf::OwnedTerm::Nil,
+        erltf::OwnedTerm::improper_list(vec![erl_int!(1)], erl_atom!("tail")),
+    ];
+    let data = encode(&owned).unwrap();
+    let borrowed = decode_borrowed(&data).unwrap();
+
+    let reencoded = encode_borrowed(&borrowed).unwrap();
+    assert_eq!(reencoded, data);
+    assert_eq!(decode(&reencoded).unwrap(), owned);
+    assert_eq!(
+        encode_borrowed(&BorrowedTerm::List(vec![])).unwrap(),
+        encode(&erl_list![]).unwrap()
+    );
+}
+
+#[test]
+fn test_encode_borrowed_with_dist_header() {
+    let owned = erl_tuple![erl_atom!("event"), erl_atom!("payload"), erl_atom!("event")];
+    let data = encode(&owned).unwrap();
+    let borrowed = decode_borrowed(&data).unwrap();
+
+    let encoded = encode_borrowed_with_dist_header(&borrowed).unwrap();
+    let mut cache = AtomCache::new();
+    let (decoded, payload) = decode_with_atom_cache(&encoded, &mut cache).unwrap();
+
+    assert_eq!(decoded, owned);
+    assert!(payload.is_none());
+}
+
+#[test]
+fn test_encode_borrowed_with_dist_header_multi() {
+    let control = BorrowedTerm::Tuple(vec![
+        BorrowedTerm::Integer(6),
+        BorrowedTerm::Atom(Cow::Borrowed("registered")),
+    ]);
+    let message = BorrowedTerm::Atom(Cow::Borrowed("hello"));
+
+    let encoded = encode_borrowed_with_dist_header_multi(&[&control, &message]).unwrap();
+    let mut cache = AtomCache::new();
+    let (decoded_control, payload) = decode_with_atom_cache(&encoded, &mut cache).unwrap();
+
+    assert_eq!(decoded_control, control.to_owned());
+    assert_eq!(payload, Some(message.to_owned()));
+}
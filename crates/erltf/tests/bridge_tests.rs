@@ -0,0 +1,158 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(all(feature = "msgpack", feature = "cbor"))]
+
+use erltf::bridge::{BridgeConfig, CBOR_TAG, ErlangTypeHandling, MSGPACK_EXT_TYPE};
+use erltf::cbor::{from_cbor, to_cbor};
+use erltf::errors::BridgeError;
+use erltf::msgpack::{from_msgpack, to_msgpack};
+use erltf::types::{Atom, BigInt, ExternalPid, Sign};
+use erltf::{OwnedTerm, erl_atom, erl_int, erl_list, erl_map, erl_tuple};
+
+fn pid() -> OwnedTerm {
+    OwnedTerm::Pid(ExternalPid::new(Atom::new("node@host"), 1, 2, 3))
+}
+
+fn sample() -> OwnedTerm {
+    erl_map! {
+        OwnedTerm::binary(b"name".to_vec()) => OwnedTerm::binary(b"widget".to_vec()),
+        OwnedTerm::binary(b"count".to_vec()) => erl_int!(3),
+        OwnedTerm::binary(b"enabled".to_vec()) => erl_atom!("true"),
+        OwnedTerm::binary(b"owner".to_vec()) => pid(),
+        OwnedTerm::binary(b"tags".to_vec()) => erl_list![OwnedTerm::Float(1.5)]
+    }
+}
+
+#[test]
+fn test_msgpack_round_trip_with_tagged_pid() {
+    let config = BridgeConfig::new();
+    let value = to_msgpack(&sample(), &config).unwrap();
+
+    let owner = value
+        .as_map()
+        .unwrap()
+        .iter()
+        .find(|(k, _)| k.as_str() == Some("owner"))
+        .map(|(_, v)| v)
+        .unwrap();
+    assert!(matches!(owner, rmpv::Value::Ext(MSGPACK_EXT_TYPE, _)));
+
+    assert_eq!(from_msgpack(&value, &config).unwrap(), sample());
+}
+
+#[test]
+fn test_cbor_round_trip_with_tagged_pid() {
+    let config = BridgeConfig::new();
+    let value = to_cbor(&sample(), &config).unwrap();
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+    let value: ciborium::Value = ciborium::from_reader(bytes.as_slice()).unwrap();
+
+    let has_tag = value
+        .as_map()
+        .unwrap()
+        .iter()
+        .any(|(_, v)| matches!(v, ciborium::Value::Tag(CBOR_TAG, _)));
+    assert!(has_tag);
+    assert_eq!(from_cbor(&value, &config).unwrap(), sample());
+}
+
+#[test]
+fn test_atoms_and_tuples_are_lossy_by_default() {
+    let config = BridgeConfig::new().with_nil_atom("nil");
+    let term = erl_tuple![erl_atom!("ok"), erl_atom!("nil")];
+
+    let value = to_msgpack(&term, &config).unwrap();
+    assert_eq!(
+        value,
+        rmpv::Value::Array(vec![rmpv::Value::from("ok"), rmpv::Value::Nil])
+    );
+    assert_eq!(
+        from_msgpack(&value, &config).unwrap(),
+        erl_list![OwnedTerm::binary(b"ok".to_vec()), erl_atom!("nil")]
+    );
+}
+
+#[test]
+fn test_tagged_atoms_and_tuples_round_trip() {
+    let config = BridgeConfig::new()
+        .with_atoms_as_strings(false)
+        .with_tuples_as_arrays(false);
+    let term = erl_list![erl_tuple![erl_atom!("ok"), erl_int!(1)], erl_atom!("done")];
+
+    let msgpack = to_msgpack(&term, &config).unwrap();
+    assert_eq!(from_msgpack(&msgpack, &config).unwrap(), term);
+
+    let cbor = to_cbor(&term, &config).unwrap();
+    assert_eq!(from_cbor(&cbor, &config).unwrap(), term);
+}
+
+#[test]
+fn test_erlang_type_handling() {
+    let as_string = BridgeConfig::new().with_erlang_types(ErlangTypeHandling::AsString);
+    assert_eq!(
+        to_cbor(&pid(), &as_string).unwrap(),
+        ciborium::Value::Text(pid().to_string())
+    );
+
+    let reject = BridgeConfig::new().with_erlang_types(ErlangTypeHandling::Reject);
+    assert!(matches!(
+        to_msgpack(&pid(), &reject),
+        Err(BridgeError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn test_binaries_as_strings() {
+    let bytes = BridgeConfig::new().with_binaries_as_strings(false);
+    let term = OwnedTerm::binary(b"abc".to_vec());
+    assert_eq!(
+        to_msgpack(&term, &bytes).unwrap(),
+        rmpv::Value::Binary(b"abc".to_vec())
+    );
+
+    let invalid_utf8 = OwnedTerm::binary(vec![0xff, 0xfe]);
+    assert_eq!(
+        to_cbor(&invalid_utf8, &BridgeConfig::new()).unwrap(),
+        ciborium::Value::Bytes(vec![0xff, 0xfe])
+    );
+}
+
+#[test]
+fn test_big_integers() {
+    let config = BridgeConfig::new();
+    let big = OwnedTerm::BigInt(BigInt::new(Sign::Positive, u64::MAX.to_le_bytes().to_vec()));
+
+    let msgpack = to_msgpack(&big, &config).unwrap();
+    assert_eq!(msgpack, rmpv::Value::from(u64::MAX));
+    assert_eq!(from_msgpack(&msgpack, &config).unwrap(), big);
+
+    let cbor = to_cbor(&big, &config).unwrap();
+    assert_eq!(from_cbor(&cbor, &config).unwrap(), big);
+
+    let huge = OwnedTerm::BigInt(BigInt::new(Sign::Negative, vec![1; 20]));
+    let cbor = to_cbor(&huge, &config).unwrap();
+    assert!(matches!(cbor, ciborium::Value::Tag(CBOR_TAG, _)));
+    assert_eq!(from_cbor(&cbor, &config).unwrap(), huge);
+}
+
+#[test]
+fn test_unknown_extensions_are_rejected() {
+    let config = BridgeConfig::new();
+    assert!(from_msgpack(&rmpv::Value::Ext(1, vec![]), &config).is_err());
+    let tagged = ciborium::Value::Tag(1, Box::new(ciborium::Value::Integer(0.into())));
+    assert!(from_cbor(&tagged, &config).is_err());
+}
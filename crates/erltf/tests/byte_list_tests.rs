@@ -0,0 +1,137 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::decoder::decode_with_config;
+use erltf::tags::{LIST_EXT, NIL_EXT, SMALL_INTEGER_EXT, STRING_EXT};
+use erltf::{
+    AtomTable, DecodeConfig, OwnedTerm, canonical_encode, decode, decode_borrowed, encode,
+    encode_borrowed, erl_int, erl_list,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+fn hash_of(term: &OwnedTerm) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    term.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn decode_byte_lists(bytes: &[u8]) -> OwnedTerm {
+    let config = DecodeConfig::new().with_byte_lists(true);
+    decode_with_config(bytes, &config, &mut AtomTable::new()).unwrap()
+}
+
+#[test]
+fn test_string_ext_decodes_as_integer_list_by_default() {
+    let decoded = decode(&[131, STRING_EXT, 0, 2, 104, 105]).unwrap();
+    assert_eq!(decoded, erl_list![erl_int!(104), erl_int!(105)]);
+    assert!(decoded.as_byte_list().is_none());
+}
+
+#[test]
+fn test_string_ext_decodes_as_byte_list() {
+    let bytes = [131, STRING_EXT, 0, 3, 0, 104, 255];
+    let decoded = decode_byte_lists(&bytes);
+    assert_eq!(decoded, OwnedTerm::byte_list([0, 104, 255]));
+    assert_eq!(decoded.as_byte_list(), Some(&[0u8, 104, 255][..]));
+    assert_eq!(encode(&decoded).unwrap(), bytes);
+}
+
+#[test]
+fn test_byte_list_option() {
+    assert!(!DecodeConfig::new().byte_lists());
+    assert!(DecodeConfig::new().with_byte_lists(true).byte_lists());
+}
+
+#[test]
+fn test_small_integer_lists_encode_as_string_ext() {
+    let term = erl_list![erl_int!(104), erl_int!(105)];
+    assert_eq!(encode(&term).unwrap(), [131, STRING_EXT, 0, 2, 104, 105]);
+}
+
+#[test]
+fn test_lists_with_large_integers_encode_as_list_ext() {
+    let term = erl_list![erl_int!(104), erl_int!(256)];
+    let encoded = encode(&term).unwrap();
+    assert_eq!(encoded[1], LIST_EXT);
+    assert_eq!(decode(&encoded).unwrap(), term);
+}
+
+#[test]
+fn test_long_integer_lists_encode_as_list_ext() {
+    let term = OwnedTerm::List(vec![erl_int!(1); 70_000]);
+    let encoded = encode(&term).unwrap();
+    assert_eq!(encoded[1], LIST_EXT);
+    assert_eq!(decode(&encoded).unwrap(), term);
+}
+
+#[test]
+fn test_long_byte_lists_encode_as_list_ext() {
+    let term = OwnedTerm::byte_list(vec![7; 70_000]);
+    let encoded = encode(&term).unwrap();
+    assert_eq!(&encoded[1..6], &[LIST_EXT, 0, 1, 17, 112]);
+    assert_eq!(&encoded[6..8], &[SMALL_INTEGER_EXT, 7]);
+    assert_eq!(encoded.last(), Some(&NIL_EXT));
+    assert_eq!(decode(&encoded).unwrap(), term.into_integer_list());
+}
+
+#[test]
+fn test_empty_byte_list_encodes_as_nil() {
+    let encoded = encode(&OwnedTerm::byte_list(Vec::new())).unwrap();
+    assert_eq!(encoded, [131, NIL_EXT]);
+}
+
+#[test]
+fn test_borrowed_encoder_uses_string_ext() {
+    let bytes = [131, STRING_EXT, 0, 2, 104, 105];
+    let decoded = decode_borrowed(&bytes).unwrap();
+    assert_eq!(encode_borrowed(&decoded).unwrap(), bytes);
+}
+
+#[test]
+fn test_byte_list_behaves_like_a_list() {
+    let bytes = OwnedTerm::byte_list(*b"abc");
+    let list = erl_list![erl_int!(97), erl_int!(98), erl_int!(99)];
+    assert!(bytes.is_list());
+    assert!(bytes.is_charlist());
+    assert_eq!(bytes.len(), 3);
+    assert_eq!(bytes.type_name(), "List");
+    assert_eq!(bytes.as_charlist_string(), Some("abc".to_string()));
+    assert_eq!(bytes.cmp(&list), std::cmp::Ordering::Equal);
+    assert_eq!(bytes.to_string(), list.to_string());
+    assert_eq!(bytes.clone().into_integer_list(), list);
+}
+
+#[test]
+fn test_byte_list_equals_the_integer_list() {
+    let bytes = OwnedTerm::byte_list([1, 2]);
+    let list = erl_list![erl_int!(1), erl_int!(2)];
+
+    assert_eq!(bytes, list);
+    assert_eq!(list, bytes);
+    assert_eq!(hash_of(&bytes), hash_of(&list));
+    assert_ne!(bytes, erl_list![erl_int!(1), erl_int!(3)]);
+    assert_ne!(bytes, erl_list![erl_int!(1)]);
+    assert_ne!(OwnedTerm::byte_list([1]), erl_list![OwnedTerm::Float(1.0)]);
+}
+
+#[test]
+fn test_canonical_encoding_of_byte_lists() {
+    let bytes = OwnedTerm::byte_list(*b"abc");
+    let list = erl_list![erl_int!(97), erl_int!(98), erl_int!(99)];
+    assert_eq!(
+        canonical_encode(&bytes).unwrap(),
+        canonical_encode(&list).unwrap()
+    );
+}
@@ -0,0 +1,100 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::canonical::{SIGNATURE_LEN, canonicalize, sign, verify};
+use erltf::types::{Atom, BigInt, ExternalPid, Sign};
+use erltf::{OwnedTerm, canonical_encode, decode, encode, erl_atom, erl_int, erl_list, erl_map};
+
+#[test]
+fn test_canonical_encode_is_stable_for_equal_terms() {
+    let a = erl_map! {
+        erl_atom!("b") => erl_int!(2),
+        erl_atom!("a") => OwnedTerm::String("x".to_string())
+    };
+    let b = erl_map! {
+        erl_atom!("a") => OwnedTerm::binary(b"x".to_vec()),
+        erl_atom!("b") => erl_int!(2)
+    };
+
+    assert_eq!(canonical_encode(&a).unwrap(), canonical_encode(&b).unwrap());
+    assert_eq!(canonical_encode(&a).unwrap()[0], 131);
+}
+
+#[test]
+fn test_canonical_encode_round_trips() {
+    let term = erl_map! {
+        erl_atom!("items") => erl_list![erl_int!(1), erl_int!(-70000)],
+        erl_atom!("name") => OwnedTerm::binary(b"widget".to_vec())
+    };
+    let encoded = canonical_encode(&term).unwrap();
+    assert_eq!(decode(&encoded).unwrap(), term);
+}
+
+#[test]
+fn test_canonicalize_normalizes_small_bigints_and_lists() {
+    let big = OwnedTerm::BigInt(BigInt::new(Sign::Negative, vec![5, 0, 0]));
+    assert_eq!(canonicalize(&big), erl_int!(-5));
+
+    let min = OwnedTerm::BigInt(BigInt::new(Sign::Negative, vec![0, 0, 0, 0, 0, 0, 0, 0x80]));
+    assert_eq!(canonicalize(&min), erl_int!(i64::MIN));
+
+    let huge = OwnedTerm::BigInt(BigInt::new(Sign::Positive, vec![1; 9]));
+    assert_eq!(canonicalize(&huge), huge);
+
+    assert_eq!(canonicalize(&erl_list![]), OwnedTerm::Nil);
+    let improper = OwnedTerm::improper_list(vec![erl_int!(1)], OwnedTerm::Nil);
+    assert_eq!(canonicalize(&improper), erl_list![erl_int!(1)]);
+}
+
+#[test]
+fn test_canonical_encode_ignores_local_ext_bytes() {
+    let plain = ExternalPid::new(Atom::new("node@host"), 1, 2, 3);
+    let local = ExternalPid::with_local_ext_bytes(Atom::new("node@host"), 1, 2, 3, vec![1, 2, 3]);
+
+    let plain = OwnedTerm::Pid(plain);
+    let local = OwnedTerm::Pid(local);
+    assert_ne!(encode(&plain).unwrap(), encode(&local).unwrap());
+    assert_eq!(
+        canonical_encode(&plain).unwrap(),
+        canonical_encode(&local).unwrap()
+    );
+}
+
+#[test]
+fn test_sign_and_verify() {
+    let key = b"secret";
+    let term = erl_map! { erl_atom!("amount") => erl_int!(100) };
+    let signature = sign(&term, key).unwrap();
+    assert_eq!(signature.len(), SIGNATURE_LEN);
+
+    assert!(verify(&term, key, &signature).unwrap());
+    assert!(!verify(&term, b"other", &signature).unwrap());
+    assert!(
+        !verify(
+            &erl_map! { erl_atom!("amount") => erl_int!(101) },
+            key,
+            &signature
+        )
+        .unwrap()
+    );
+    assert!(!verify(&term, key, &signature[..16]).unwrap());
+}
+
+#[test]
+fn test_sign_matches_equivalent_representations() {
+    let key = b"k";
+    let a = erl_list![OwnedTerm::String("hello".to_string())];
+    let b = erl_list![OwnedTerm::binary(b"hello".to_vec())];
+    assert_eq!(sign(&a, key).unwrap(), sign(&b, key).unwrap());
+}
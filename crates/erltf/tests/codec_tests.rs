@@ -13,8 +13,13 @@
 // limitations under the License.
 
 use erltf::OwnedTerm;
+use erltf::errors::EncodeError;
+use erltf::tags::ATOM_UTF8_EXT;
 use erltf::types::{Atom, BigInt, ExternalPid, ExternalPort, ExternalReference};
-use erltf::{decode, encode, erl_atom, erl_int, erl_list, erl_map, erl_tuple};
+use erltf::{
+    EncodeConfig, MAX_ATOM_CHARACTERS, decode, decode_borrowed, encode, encode_with_config,
+    encode_with_dist_header, erl_atom, erl_int, erl_list, erl_map, erl_tuple,
+};
 
 #[test]
 fn test_encode_decode_small_integer() {
@@ -245,13 +250,62 @@ fn test_encode_decode_bigint_negative() {
 
 #[test]
 fn test_encode_decode_long_atom() {
-    let long_name = "a".repeat(300);
+    // 255 characters, 510 bytes
+    let long_name = "é".repeat(255);
     let term = OwnedTerm::Atom(Atom::new(long_name.clone()));
     let encoded = encode(&term).unwrap();
+    assert_eq!(encoded[1], ATOM_UTF8_EXT);
     let decoded = decode(&encoded).unwrap();
     assert_eq!(term, decoded);
 }
 
+#[test]
+fn test_encode_atom_over_the_character_limit() {
+    let term = OwnedTerm::Atom(Atom::new("a".repeat(MAX_ATOM_CHARACTERS + 1)));
+    assert!(matches!(
+        encode(&term),
+        Err(EncodeError::AtomTooLong {
+            length: 256,
+            max: MAX_ATOM_CHARACTERS
+        })
+    ));
+
+    let config = EncodeConfig::new().with_max_atom_len(300);
+    assert_eq!(config.max_atom_len(), 300);
+    let encoded = encode_with_config(&term, &config).unwrap();
+    assert_eq!(decode(&encoded).unwrap(), term);
+
+    let config = EncodeConfig::new().with_max_atom_len(3);
+    assert!(encode_with_config(&OwnedTerm::atom("abc"), &config).is_ok());
+    assert!(encode_with_config(&OwnedTerm::atom("éèê"), &config).is_ok());
+    assert!(matches!(
+        encode_with_config(&OwnedTerm::atom("abcd"), &config),
+        Err(EncodeError::AtomTooLong { length: 4, max: 3 })
+    ));
+}
+
+#[test]
+fn test_atom_limit_applies_to_nested_atoms() {
+    let long = OwnedTerm::Atom(Atom::new("a".repeat(256)));
+    let term = OwnedTerm::Tuple(vec![
+        OwnedTerm::atom("ok"),
+        OwnedTerm::List(vec![long.clone()]),
+    ]);
+    assert!(matches!(
+        encode(&term),
+        Err(EncodeError::AtomTooLong { .. })
+    ));
+    assert!(matches!(
+        encode_with_dist_header(&term),
+        Err(EncodeError::AtomTooLong { .. })
+    ));
+    let pid = ExternalPid::new(Atom::new("a".repeat(256)), 1, 0, 1);
+    assert!(matches!(
+        encode(&OwnedTerm::Pid(pid)),
+        Err(EncodeError::AtomTooLong { .. })
+    ));
+}
+
 #[test]
 fn test_encode_atom_too_large() {
     let too_long = "a".repeat(70000);
@@ -293,28 +347,28 @@ fn test_bit_binary_empty_with_bits() {
 #[test]
 fn test_float_positive_infinity() {
     let pos_inf = OwnedTerm::Float(f64::INFINITY);
-    let encoded = encode(&pos_inf).unwrap();
-    let decoded = decode(&encoded).unwrap();
-    assert_eq!(decoded, pos_inf);
+    assert!(matches!(
+        encode(&pos_inf),
+        Err(EncodeError::NonFiniteFloat { value }) if value == f64::INFINITY
+    ));
 }
 
 #[test]
 fn test_float_negative_infinity() {
     let neg_inf = OwnedTerm::Float(f64::NEG_INFINITY);
-    let encoded = encode(&neg_inf).unwrap();
-    let decoded = decode(&encoded).unwrap();
-    assert_eq!(decoded, neg_inf);
+    assert!(matches!(
+        encode(&neg_inf),
+        Err(EncodeError::NonFiniteFloat { value }) if value == f64::NEG_INFINITY
+    ));
 }
 
 #[test]
 fn test_float_nan() {
     let nan = OwnedTerm::Float(f64::NAN);
-    let encoded = encode(&nan).unwrap();
-    let decoded = decode(&encoded).unwrap();
-    match decoded {
-        OwnedTerm::Float(f) if f.is_nan() => (),
-        _ => panic!("Expected NaN"),
-    }
+    assert!(matches!(
+        encode(&nan),
+        Err(EncodeError::NonFiniteFloat { value }) if value.is_nan()
+    ));
 }
 
 #[test]
@@ -357,3 +411,28 @@ fn test_deeply_nested_lists() {
     let decoded = decode(&encoded).unwrap();
     assert_eq!(term, decoded);
 }
+
+#[test]
+fn test_decode_rejects_counts_beyond_the_input() {
+    // A large tuple and a list that declare far more elements than follow them
+    for data in [
+        vec![131, 105, 0, 152, 150, 128, 97, 1],
+        vec![131, 108, 0, 152, 150, 128, 97, 1],
+    ] {
+        assert!(decode(&data).is_err());
+        assert!(decode_borrowed(&data).is_err());
+    }
+}
+
+#[test]
+fn test_decode_rejects_fun_with_missing_free_variables() {
+    // NEW_FUN_EXT that declares u32::MAX free variables and carries none
+    let mut data = vec![131, 112, 0, 0, 0, 0, 0];
+    data.extend([0; 16]);
+    data.extend([0, 0, 0, 0]);
+    data.extend([255, 255, 255, 255]);
+    data.extend([119, 1, b'm', 97, 0, 97, 0]);
+    data.extend([88, 119, 1, b'n', 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert!(decode(&data).is_err());
+    assert!(decode_borrowed(&data).is_err());
+}
@@ -0,0 +1,735 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Byte-exact vectors in the formats emitted by `term_to_binary/1,2` (OTP 24 to 27)
+//! and by `ei`/`erl_interface`, one or more per tag.
+//!
+//! Every vector must decode to the given term, pass validation and inspection, and
+//! re-encode either to the same bytes or, for dialects this crate only decodes,
+//! to the canonical form it emits instead.
+
+use erltf::decoder::AtomCache;
+use erltf::inspect::inspect;
+use erltf::tags::{
+    ATOM_EXT, ATOM_UTF8_EXT, BINARY_EXT, BIT_BINARY_EXT, COMPRESSED_EXT, EXPORT_EXT, FLOAT_EXT,
+    INTEGER_EXT, LARGE_BIG_EXT, LARGE_TUPLE_EXT, LIST_EXT, LOCAL_EXT, MAP_EXT, NEW_FLOAT_EXT,
+    NEW_FUN_EXT, NEW_PID_EXT, NEW_PORT_EXT, NEW_REFERENCE_EXT, NEWER_REFERENCE_EXT, NIL_EXT,
+    PID_EXT, PORT_EXT, REFERENCE_EXT, SMALL_ATOM_EXT, SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT,
+    SMALL_INTEGER_EXT, SMALL_TUPLE_EXT, STRING_EXT, V4_PORT_EXT, tag_name,
+};
+use erltf::types::{
+    Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun,
+};
+use erltf::{OwnedTerm, decode, decode_borrowed, decode_with_atom_cache, encode, validate};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+const NODE: &[u8] = b"a@host";
+const CREATION: [u8; 4] = [0x64, 0xB2, 0xC9, 0xF1];
+
+/// When set, `test_write_fuzz_seeds` writes the vectors there as `cargo fuzz` seeds
+const FUZZ_SEEDS_ENV_VAR: &str = "EDP_FUZZ_SEEDS_DIR";
+
+/// `decode_borrowed` returns slices of its input, so it does not handle these
+const NOT_BORROWABLE: [u8; 2] = [COMPRESSED_EXT, LOCAL_EXT];
+
+/// How `encode` output compares to a vector.
+enum Reencode {
+    /// Byte for byte the same
+    Exact,
+    /// The form this crate emits for a dialect it only decodes
+    Canonical(Vec<u8>),
+}
+
+struct Vector {
+    name: &'static str,
+    /// Encoders known to emit these bytes
+    source: &'static str,
+    bytes: Vec<u8>,
+    term: OwnedTerm,
+    reencode: Reencode,
+}
+
+fn vector(
+    name: &'static str,
+    source: &'static str,
+    bytes: Vec<u8>,
+    term: OwnedTerm,
+    reencode: Reencode,
+) -> Vector {
+    Vector {
+        name,
+        source,
+        bytes,
+        term,
+        reencode,
+    }
+}
+
+fn bytes(parts: &[&[u8]]) -> Vec<u8> {
+    parts.concat()
+}
+
+/// The node name as `SMALL_ATOM_UTF8_EXT`, emitted by OTP 26 and later.
+fn utf8_node() -> Vec<u8> {
+    bytes(&[&[SMALL_ATOM_UTF8_EXT, NODE.len() as u8], NODE])
+}
+
+/// The node name as `ATOM_EXT`, emitted by OTP 25 and earlier.
+fn latin1_node() -> Vec<u8> {
+    bytes(&[&[ATOM_EXT, 0, NODE.len() as u8], NODE])
+}
+
+fn node() -> Atom {
+    Atom::new("a@host")
+}
+
+fn big_creation() -> u32 {
+    u32::from_be_bytes(CREATION)
+}
+
+fn vectors() -> Vec<Vector> {
+    vec![
+        vector(
+            "small integer",
+            "term_to_binary, ei_x_encode_long",
+            vec![131, SMALL_INTEGER_EXT, 42],
+            OwnedTerm::Integer(42),
+            Reencode::Exact,
+        ),
+        vector(
+            "integer above 255",
+            "term_to_binary, ei_x_encode_long",
+            vec![131, INTEGER_EXT, 0, 0, 1, 0],
+            OwnedTerm::Integer(256),
+            Reencode::Exact,
+        ),
+        vector(
+            "negative integer",
+            "term_to_binary, ei_x_encode_long",
+            vec![131, INTEGER_EXT, 255, 255, 255, 255],
+            OwnedTerm::Integer(-1),
+            Reencode::Exact,
+        ),
+        vector(
+            "smallest integer",
+            "term_to_binary",
+            vec![131, INTEGER_EXT, 128, 0, 0, 0],
+            OwnedTerm::Integer(i32::MIN as i64),
+            Reencode::Exact,
+        ),
+        vector(
+            "small big, 2^31",
+            "term_to_binary",
+            vec![131, SMALL_BIG_EXT, 4, 0, 0, 0, 0, 128],
+            OwnedTerm::BigInt(BigInt::new(false, vec![0, 0, 0, 128])),
+            Reencode::Exact,
+        ),
+        vector(
+            "negative small big, -2^40",
+            "term_to_binary",
+            vec![131, SMALL_BIG_EXT, 6, 1, 0, 0, 0, 0, 0, 1],
+            OwnedTerm::BigInt(BigInt::new(true, vec![0, 0, 0, 0, 0, 1])),
+            Reencode::Exact,
+        ),
+        vector(
+            "small big, 2^64",
+            "term_to_binary",
+            vec![131, SMALL_BIG_EXT, 9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            OwnedTerm::BigInt(BigInt::new(false, vec![0, 0, 0, 0, 0, 0, 0, 0, 1])),
+            Reencode::Exact,
+        ),
+        vector(
+            "large big, 2^2048",
+            "term_to_binary",
+            bytes(&[&[131, LARGE_BIG_EXT, 0, 0, 1, 1, 0], &[0; 256], &[1]]),
+            OwnedTerm::BigInt(BigInt::new(false, [&[0u8; 256][..], &[1]].concat())),
+            Reencode::Exact,
+        ),
+        vector(
+            "new float",
+            "term_to_binary, ei_x_encode_double",
+            vec![131, NEW_FLOAT_EXT, 63, 248, 0, 0, 0, 0, 0, 0],
+            OwnedTerm::Float(1.5),
+            Reencode::Exact,
+        ),
+        vector(
+            "float as text",
+            "term_to_binary(1.5, [{minor_version, 0}])",
+            bytes(&[&[131, FLOAT_EXT], b"1.50000000000000000000e+00", &[0; 5]]),
+            OwnedTerm::Float(1.5),
+            Reencode::Canonical(vec![131, NEW_FLOAT_EXT, 63, 248, 0, 0, 0, 0, 0, 0]),
+        ),
+        vector(
+            "UTF-8 atom",
+            "term_to_binary in OTP 26+, ei_x_encode_atom",
+            bytes(&[&[131, SMALL_ATOM_UTF8_EXT, 5], b"hello"]),
+            OwnedTerm::atom("hello"),
+            Reencode::Exact,
+        ),
+        vector(
+            "UTF-8 atom with non-ASCII characters",
+            "term_to_binary",
+            vec![131, SMALL_ATOM_UTF8_EXT, 6, 104, 195, 169, 108, 108, 111],
+            OwnedTerm::atom("héllo"),
+            Reencode::Exact,
+        ),
+        vector(
+            "UTF-8 atom longer than 255 bytes",
+            "term_to_binary",
+            bytes(&[&[131, ATOM_UTF8_EXT, 1, 0], &[195, 184].repeat(128)]),
+            OwnedTerm::atom("ø".repeat(128)),
+            Reencode::Exact,
+        ),
+        vector(
+            "Latin-1 atom",
+            "term_to_binary in OTP 24 and 25",
+            bytes(&[&[131, ATOM_EXT, 0, 5], b"hello"]),
+            OwnedTerm::atom("hello"),
+            Reencode::Canonical(bytes(&[&[131, SMALL_ATOM_UTF8_EXT, 5], b"hello"])),
+        ),
+        vector(
+            "Latin-1 atom with non-ASCII characters",
+            "term_to_binary in OTP 24 and 25",
+            vec![131, ATOM_EXT, 0, 5, 104, 233, 108, 108, 111],
+            OwnedTerm::atom("héllo"),
+            Reencode::Canonical(vec![
+                131,
+                SMALL_ATOM_UTF8_EXT,
+                6,
+                104,
+                195,
+                169,
+                108,
+                108,
+                111,
+            ]),
+        ),
+        vector(
+            "small Latin-1 atom",
+            "ei_x_encode_atom_as with ERLANG_LATIN1",
+            bytes(&[&[131, SMALL_ATOM_EXT, 5], b"hello"]),
+            OwnedTerm::atom("hello"),
+            Reencode::Canonical(bytes(&[&[131, SMALL_ATOM_UTF8_EXT, 5], b"hello"])),
+        ),
+        vector(
+            "small tuple",
+            "term_to_binary, ei_x_encode_tuple_header",
+            vec![
+                131,
+                SMALL_TUPLE_EXT,
+                2,
+                SMALL_INTEGER_EXT,
+                1,
+                SMALL_INTEGER_EXT,
+                2,
+            ],
+            OwnedTerm::Tuple(vec![OwnedTerm::Integer(1), OwnedTerm::Integer(2)]),
+            Reencode::Exact,
+        ),
+        vector(
+            "large tuple",
+            "term_to_binary, ei_x_encode_tuple_header",
+            bytes(&[
+                &[131, LARGE_TUPLE_EXT, 0, 0, 1, 0],
+                &[SMALL_INTEGER_EXT, 0].repeat(256),
+            ]),
+            OwnedTerm::Tuple(vec![OwnedTerm::Integer(0); 256]),
+            Reencode::Exact,
+        ),
+        vector(
+            "empty list",
+            "term_to_binary, ei_x_encode_empty_list",
+            vec![131, NIL_EXT],
+            OwnedTerm::Nil,
+            Reencode::Exact,
+        ),
+        vector(
+            "string",
+            "term_to_binary, ei_x_encode_string",
+            vec![131, STRING_EXT, 0, 3, 97, 98, 99],
+            OwnedTerm::List(vec![
+                OwnedTerm::Integer(97),
+                OwnedTerm::Integer(98),
+                OwnedTerm::Integer(99),
+            ]),
+            Reencode::Exact,
+        ),
+        vector(
+            "list",
+            "term_to_binary in OTP 26+",
+            bytes(&[
+                &[131, LIST_EXT, 0, 0, 0, 2, INTEGER_EXT, 0, 0, 1, 0],
+                &[SMALL_ATOM_UTF8_EXT, 2],
+                b"ok",
+                &[NIL_EXT],
+            ]),
+            OwnedTerm::List(vec![OwnedTerm::Integer(256), OwnedTerm::atom("ok")]),
+            Reencode::Exact,
+        ),
+        vector(
+            "improper list",
+            "term_to_binary",
+            vec![
+                131,
+                LIST_EXT,
+                0,
+                0,
+                0,
+                1,
+                SMALL_INTEGER_EXT,
+                1,
+                SMALL_INTEGER_EXT,
+                2,
+            ],
+            OwnedTerm::ImproperList {
+                elements: vec![OwnedTerm::Integer(1)],
+                tail: Box::new(OwnedTerm::Integer(2)),
+            },
+            Reencode::Exact,
+        ),
+        vector(
+            "binary",
+            "term_to_binary, ei_x_encode_binary",
+            vec![131, BINARY_EXT, 0, 0, 0, 3, 1, 2, 3],
+            OwnedTerm::Binary(vec![1, 2, 3]),
+            Reencode::Exact,
+        ),
+        vector(
+            "bitstring",
+            "term_to_binary, ei_x_encode_bitstring",
+            vec![131, BIT_BINARY_EXT, 0, 0, 0, 2, 3, 1, 64],
+            OwnedTerm::BitBinary {
+                bytes: vec![1, 64],
+                bits: 3,
+            },
+            Reencode::Exact,
+        ),
+        vector(
+            "map",
+            "term_to_binary in OTP 26+, keys in term order",
+            bytes(&[
+                &[131, MAP_EXT, 0, 0, 0, 2, SMALL_INTEGER_EXT, 1],
+                &[SMALL_ATOM_UTF8_EXT, 1, b'a'],
+                &[SMALL_ATOM_UTF8_EXT, 1, b'a', SMALL_INTEGER_EXT, 1],
+            ]),
+            OwnedTerm::Map(BTreeMap::from([
+                (OwnedTerm::Integer(1), OwnedTerm::atom("a")),
+                (OwnedTerm::atom("a"), OwnedTerm::Integer(1)),
+            ])),
+            Reencode::Exact,
+        ),
+        vector(
+            "pid",
+            "term_to_binary in OTP 26+",
+            bytes(&[
+                &[131, NEW_PID_EXT],
+                &utf8_node(),
+                &[0, 0, 0, 80, 0, 0, 0, 0],
+                &CREATION,
+            ]),
+            OwnedTerm::Pid(ExternalPid::new(node(), 80, 0, big_creation())),
+            Reencode::Exact,
+        ),
+        vector(
+            "pid with a Latin-1 node name",
+            "term_to_binary in OTP 24 and 25",
+            bytes(&[
+                &[131, NEW_PID_EXT],
+                &latin1_node(),
+                &[0, 0, 0, 80, 0, 0, 0, 0],
+                &CREATION,
+            ]),
+            OwnedTerm::Pid(ExternalPid::new(node(), 80, 0, big_creation())),
+            Reencode::Canonical(bytes(&[
+                &[131, NEW_PID_EXT],
+                &utf8_node(),
+                &[0, 0, 0, 80, 0, 0, 0, 0],
+                &CREATION,
+            ])),
+        ),
+        vector(
+            "pid with an 8-bit creation",
+            "ei in OTP 22 and earlier, peers without DFLAG_BIG_CREATION",
+            bytes(&[
+                &[131, PID_EXT],
+                &latin1_node(),
+                &[0, 0, 0, 80, 0, 0, 0, 0, 2],
+            ]),
+            OwnedTerm::Pid(ExternalPid::new(node(), 80, 0, 2)),
+            Reencode::Canonical(bytes(&[
+                &[131, NEW_PID_EXT],
+                &utf8_node(),
+                &[0, 0, 0, 80, 0, 0, 0, 0, 0, 0, 0, 2],
+            ])),
+        ),
+        vector(
+            "local pid",
+            "term_to_binary(Pid, [local]) in OTP 26+",
+            bytes(&[
+                &[
+                    131,
+                    LOCAL_EXT,
+                    0x8F,
+                    0x1E,
+                    0x5A,
+                    0x03,
+                    0xC4,
+                    0x77,
+                    0x10,
+                    0x2B,
+                    NEW_PID_EXT,
+                ],
+                &utf8_node(),
+                &[0, 0, 0, 80, 0, 0, 0, 0],
+                &CREATION,
+            ]),
+            OwnedTerm::Pid(ExternalPid::new(node(), 80, 0, big_creation())),
+            Reencode::Exact,
+        ),
+        vector(
+            "port",
+            "term_to_binary in OTP 26+",
+            bytes(&[
+                &[131, V4_PORT_EXT],
+                &utf8_node(),
+                &[0, 0, 0, 0, 0, 0, 0, 5],
+                &CREATION,
+            ]),
+            OwnedTerm::Port(ExternalPort::new(node(), 5, big_creation())),
+            Reencode::Exact,
+        ),
+        vector(
+            "port with a 32-bit ID",
+            "term_to_binary in OTP 24 and 25",
+            bytes(&[
+                &[131, NEW_PORT_EXT],
+                &latin1_node(),
+                &[0, 0, 0, 5],
+                &CREATION,
+            ]),
+            OwnedTerm::Port(ExternalPort::new(node(), 5, big_creation())),
+            Reencode::Canonical(bytes(&[
+                &[131, V4_PORT_EXT],
+                &utf8_node(),
+                &[0, 0, 0, 0, 0, 0, 0, 5],
+                &CREATION,
+            ])),
+        ),
+        vector(
+            "port with an 8-bit creation",
+            "ei in OTP 22 and earlier, peers without DFLAG_BIG_CREATION",
+            bytes(&[&[131, PORT_EXT], &latin1_node(), &[0, 0, 0, 5, 2]]),
+            OwnedTerm::Port(ExternalPort::new(node(), 5, 2)),
+            Reencode::Canonical(bytes(&[
+                &[131, V4_PORT_EXT],
+                &utf8_node(),
+                &[0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 2],
+            ])),
+        ),
+        vector(
+            "reference",
+            "term_to_binary in OTP 26+",
+            bytes(&[
+                &[131, NEWER_REFERENCE_EXT, 0, 3],
+                &utf8_node(),
+                &CREATION,
+                &[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3],
+            ]),
+            OwnedTerm::Reference(ExternalReference::new(
+                node(),
+                big_creation(),
+                vec![1, 2, 3],
+            )),
+            Reencode::Exact,
+        ),
+        vector(
+            "reference with an 8-bit creation",
+            "ei in OTP 22 and earlier, peers without DFLAG_BIG_CREATION",
+            bytes(&[
+                &[131, NEW_REFERENCE_EXT, 0, 3],
+                &latin1_node(),
+                &[2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3],
+            ]),
+            OwnedTerm::Reference(ExternalReference::new(node(), 2, vec![1, 2, 3])),
+            Reencode::Canonical(bytes(&[
+                &[131, NEWER_REFERENCE_EXT, 0, 3],
+                &utf8_node(),
+                &[0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3],
+            ])),
+        ),
+        vector(
+            "single word reference",
+            "the oldest encoders",
+            bytes(&[&[131, REFERENCE_EXT], &latin1_node(), &[0, 0, 0, 7, 1]]),
+            OwnedTerm::Reference(ExternalReference::new(node(), 1, vec![7])),
+            Reencode::Canonical(bytes(&[
+                &[131, NEWER_REFERENCE_EXT, 0, 1],
+                &utf8_node(),
+                &[0, 0, 0, 1, 0, 0, 0, 7],
+            ])),
+        ),
+        vector(
+            "external fun",
+            "term_to_binary(fun lists:map/2) in OTP 26+",
+            bytes(&[
+                &[131, EXPORT_EXT, SMALL_ATOM_UTF8_EXT, 5],
+                b"lists",
+                &[SMALL_ATOM_UTF8_EXT, 3],
+                b"map",
+                &[SMALL_INTEGER_EXT, 2],
+            ]),
+            OwnedTerm::ExternalFun(ExternalFun::new(Atom::new("lists"), Atom::new("map"), 2)),
+            Reencode::Exact,
+        ),
+        vector(
+            "local fun",
+            "term_to_binary of a fun without free variables",
+            bytes(&[
+                &[131, NEW_FUN_EXT, 0, 0, 0, 67, 1],
+                &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+                &[0, 0, 0, 0, 0, 0, 0, 0, SMALL_ATOM_UTF8_EXT, 8],
+                b"erl_eval",
+                &[
+                    SMALL_INTEGER_EXT,
+                    0,
+                    INTEGER_EXT,
+                    0,
+                    188,
+                    97,
+                    78,
+                    NEW_PID_EXT,
+                ],
+                &utf8_node(),
+                &[0, 0, 0, 80, 0, 0, 0, 0],
+                &CREATION,
+            ]),
+            OwnedTerm::InternalFun(Box::new(InternalFun::new(
+                1,
+                [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+                0,
+                0,
+                Atom::new("erl_eval"),
+                0,
+                12_345_678,
+                ExternalPid::new(node(), 80, 0, big_creation()),
+                vec![],
+            ))),
+            Reencode::Exact,
+        ),
+        vector(
+            "compressed term",
+            "term_to_binary(lists:duplicate(100, 0), [compressed])",
+            vec![
+                131,
+                COMPRESSED_EXT,
+                0,
+                0,
+                0,
+                103,
+                120,
+                156,
+                203,
+                102,
+                72,
+                97,
+                160,
+                3,
+                0,
+                0,
+                82,
+                232,
+                0,
+                208,
+            ],
+            OwnedTerm::List(vec![OwnedTerm::Integer(0); 100]),
+            Reencode::Canonical(bytes(&[&[131, STRING_EXT, 0, 100], &[0; 100]])),
+        ),
+    ]
+}
+
+fn describe(v: &Vector) -> String {
+    format!("{} ({})", v.name, v.source)
+}
+
+#[test]
+fn test_vectors_decode() {
+    for v in vectors() {
+        let decoded = decode(&v.bytes).unwrap_or_else(|e| panic!("{}: {e}", describe(&v)));
+        assert_eq!(decoded, v.term, "{}", describe(&v));
+    }
+}
+
+#[test]
+fn test_vectors_decode_borrowed() {
+    for v in vectors()
+        .into_iter()
+        .filter(|v| !NOT_BORROWABLE.contains(&v.bytes[1]))
+    {
+        let decoded = decode_borrowed(&v.bytes).unwrap_or_else(|e| panic!("{}: {e}", describe(&v)));
+        assert_eq!(decoded.to_owned(), v.term, "{}", describe(&v));
+    }
+}
+
+#[test]
+fn test_vectors_reencode() {
+    for v in vectors() {
+        let decoded = decode(&v.bytes).unwrap();
+        let encoded = encode(&decoded).unwrap();
+        match &v.reencode {
+            Reencode::Exact => assert_eq!(encoded, v.bytes, "{}", describe(&v)),
+            Reencode::Canonical(expected) => {
+                assert_eq!(&encoded, expected, "{}", describe(&v));
+                assert_eq!(decode(expected).unwrap(), v.term, "{}", describe(&v));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_vectors_validate_and_skip() {
+    for v in vectors() {
+        validate(&v.bytes).unwrap_or_else(|e| panic!("{}: {e}", describe(&v)));
+        let rest = erltf::decoder::skip_term(&v.bytes[1..])
+            .unwrap_or_else(|e| panic!("{}: {e}", describe(&v)));
+        assert!(rest.is_empty(), "{}", describe(&v));
+    }
+}
+
+#[test]
+fn test_vectors_inspect_without_errors() {
+    for v in vectors() {
+        let annotations = inspect(&v.bytes);
+        assert!(
+            annotations.iter().all(|a| a.error.is_none()),
+            "{}: {annotations:?}",
+            describe(&v)
+        );
+    }
+}
+
+#[test]
+fn test_vectors_cover_every_term_tag() {
+    let covered: BTreeSet<u8> = vectors()
+        .iter()
+        .flat_map(|v| inspect(&v.bytes))
+        .map(|a| a.tag)
+        .collect();
+    let term_tags = [
+        SMALL_INTEGER_EXT,
+        INTEGER_EXT,
+        FLOAT_EXT,
+        NEW_FLOAT_EXT,
+        ATOM_EXT,
+        SMALL_ATOM_EXT,
+        ATOM_UTF8_EXT,
+        SMALL_ATOM_UTF8_EXT,
+        SMALL_TUPLE_EXT,
+        LARGE_TUPLE_EXT,
+        NIL_EXT,
+        STRING_EXT,
+        LIST_EXT,
+        BINARY_EXT,
+        BIT_BINARY_EXT,
+        SMALL_BIG_EXT,
+        LARGE_BIG_EXT,
+        MAP_EXT,
+        PID_EXT,
+        NEW_PID_EXT,
+        PORT_EXT,
+        NEW_PORT_EXT,
+        V4_PORT_EXT,
+        REFERENCE_EXT,
+        NEW_REFERENCE_EXT,
+        NEWER_REFERENCE_EXT,
+        EXPORT_EXT,
+        NEW_FUN_EXT,
+        COMPRESSED_EXT,
+        LOCAL_EXT,
+    ];
+    let missing: Vec<&str> = term_tags
+        .iter()
+        .filter(|tag| !covered.contains(tag))
+        .map(|&tag| tag_name(tag).unwrap_or("unknown"))
+        .collect();
+    assert!(missing.is_empty(), "tags without a vector: {missing:?}");
+}
+
+#[test]
+fn test_dist_header_vector() {
+    // A SEND control message {2, '', Pid} with both atoms in the atom cache:
+    // '' as a new entry at index 3, the node name as a new entry at index 0x105
+    let frame = bytes(&[
+        &[131, 68, 2, 0x98, 0x00, 3, 0, 5, NODE.len() as u8],
+        NODE,
+        &[
+            SMALL_TUPLE_EXT,
+            3,
+            SMALL_INTEGER_EXT,
+            2,
+            82,
+            0,
+            NEW_PID_EXT,
+            82,
+            1,
+        ],
+        &[0, 0, 0, 80, 0, 0, 0, 0],
+        &CREATION,
+    ]);
+    let mut cache = AtomCache::new();
+    let (control, payload) = decode_with_atom_cache(&frame, &mut cache).unwrap();
+    assert_eq!(
+        control,
+        OwnedTerm::Tuple(vec![
+            OwnedTerm::Integer(2),
+            OwnedTerm::atom(""),
+            OwnedTerm::Pid(ExternalPid::new(node(), 80, 0, big_creation())),
+        ])
+    );
+    assert_eq!(payload, None);
+    assert_eq!(cache.entry(3), Some(&Atom::new("")));
+    assert_eq!(cache.entry(0x105), Some(&node()));
+}
+
+/// Turns a vector name into a seed file name, e.g. "small big, 2^31" into "small_big_2_31".
+fn seed_file_name(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[test]
+fn test_write_fuzz_seeds() {
+    let Some(dir) = std::env::var_os(FUZZ_SEEDS_ENV_VAR) else {
+        return;
+    };
+    let dir = Path::new(&dir);
+
+    let decode_dir = dir.join("decode");
+    fs::create_dir_all(&decode_dir).unwrap();
+    for v in vectors() {
+        fs::write(decode_dir.join(seed_file_name(v.name)), &v.bytes).unwrap();
+    }
+
+    // The first fragment of sequence 7 out of 3, then the second one
+    let fragment_dir = dir.join("decode_fragment_header");
+    fs::create_dir_all(&fragment_dir).unwrap();
+    let header = bytes(&[&[131, 69], &7u64.to_be_bytes(), &3u64.to_be_bytes(), &[0]]);
+    let cont = bytes(&[&[131, 70], &7u64.to_be_bytes(), &2u64.to_be_bytes()]);
+    fs::write(fragment_dir.join("fragment_header"), header).unwrap();
+    fs::write(fragment_dir.join("fragment_cont"), cont).unwrap();
+}
@@ -0,0 +1,109 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::errors::{DEFAULT_SNIPPET_WINDOW, DecodeError};
+use erltf::{ContextualDecodeError, ErrorSnippet, ParsingContext, decode_borrowed};
+
+#[test]
+fn test_decode_borrowed_errors_carry_a_snippet() {
+    let data = [131, 104, 2, 97, 1, 200];
+    let err = decode_borrowed(&data).unwrap_err();
+
+    assert_eq!(err.context.byte_offset, 5);
+    assert_eq!(err.error, DecodeError::InvalidTag(200));
+    assert_eq!(err.found_tag(), Some(200));
+    assert_eq!(
+        err.snippet,
+        Some(ErrorSnippet {
+            start: 0,
+            bytes: data.to_vec(),
+        })
+    );
+}
+
+#[test]
+fn test_display_renders_the_found_tag_and_hex_context() {
+    let data = [131, 104, 2, 97, 1, 200];
+    let rendered = decode_borrowed(&data).unwrap_err().to_string();
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].contains("at byte offset 5 in path root[1]"));
+    assert_eq!(lines[1], "  found byte 200");
+    assert_eq!(lines[2], "  00000000: 83 68 02 61 01 [c8]");
+}
+
+#[test]
+fn test_display_renders_expected_version() {
+    let err = decode_borrowed(&[132, 97, 1]).unwrap_err();
+    assert_eq!(
+        err.error,
+        DecodeError::InvalidVersion {
+            expected: 131,
+            actual: 132
+        }
+    );
+    let rendered = err.to_string();
+    assert!(rendered.contains("found byte 132, expected VERSION (131)"));
+}
+
+#[test]
+fn test_display_renders_the_end_of_input() {
+    let data = [131, 108, 0, 0, 0, 2, 97, 1];
+    let err = decode_borrowed(&data).unwrap_err();
+
+    assert_eq!(err.found_tag(), None);
+    let rendered = err.to_string();
+    assert!(rendered.contains("found end of input"));
+    assert!(rendered.ends_with("61 01 []"));
+}
+
+#[test]
+fn test_snippet_window_is_configurable() {
+    let mut data = vec![131, 108, 0, 0, 0, 40];
+    data.extend([97, 1].repeat(20));
+    data[30] = 200;
+    let err = decode_borrowed(&data).unwrap_err();
+    assert_eq!(err.context.byte_offset, 30);
+    let default = err.snippet.clone().unwrap();
+    assert_eq!(default.start, 30 - DEFAULT_SNIPPET_WINDOW);
+    assert_eq!(default.bytes.len(), 2 * DEFAULT_SNIPPET_WINDOW + 1);
+
+    let narrow = err.with_source(&data, 2);
+    let snippet = narrow.snippet.as_ref().unwrap();
+    assert_eq!(snippet.start, 28);
+    assert_eq!(snippet.bytes, vec![97, 1, 200, 1, 97]);
+    assert!(narrow.to_string().ends_with("0000001c: 61 01 [c8] 01 61"));
+}
+
+#[test]
+fn test_errors_without_a_snippet_render_on_one_line() {
+    let err = ContextualDecodeError::new(DecodeError::InvalidList, ParsingContext::with_offset(3));
+    assert_eq!(
+        err.to_string(),
+        "invalid list structure at byte offset 3 in path root"
+    );
+    assert_eq!(err.found_tag(), None);
+    assert_eq!(err.expected_tag(), None);
+}
+
+#[test]
+fn test_error_snippet_byte_at() {
+    let snippet = ErrorSnippet::new(&[1, 2, 3, 4, 5, 6], 4, 1);
+    assert_eq!(snippet.start, 3);
+    assert_eq!(snippet.bytes, vec![4, 5, 6]);
+    assert_eq!(snippet.byte_at(2), None);
+    assert_eq!(snippet.byte_at(4), Some(5));
+    assert_eq!(snippet.to_hex(4), "00000003: 04 [05] 06");
+}
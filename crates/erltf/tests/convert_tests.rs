@@ -0,0 +1,98 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use erltf::errors::TermConversionError;
+use erltf::types::{Atom, BigInt, Sign};
+use erltf::{FromTerm, OwnedTerm, ToTerm, erl_atom, erl_int, erl_list, erl_map, erl_tuple};
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+fn integers() {
+    assert_eq!(42u8.to_term(), erl_int!(42));
+    assert_eq!(u8::from_term(&erl_int!(255)), Ok(255));
+    assert_eq!(
+        u8::from_term(&erl_int!(256)),
+        Err(TermConversionError::OutOfRange)
+    );
+    assert_eq!(i32::from_term(&erl_int!(-7)), Ok(-7));
+    assert!(matches!(
+        i64::from_term(&erl_atom!("one")),
+        Err(TermConversionError::WrongType { .. })
+    ));
+}
+
+#[test]
+fn wide_integers_use_big_integers() {
+    let term = u64::MAX.to_term();
+    assert_eq!(
+        term,
+        OwnedTerm::BigInt(BigInt::new(Sign::Positive, vec![0xff; 8]))
+    );
+    assert_eq!(u64::from_term(&term), Ok(u64::MAX));
+    assert_eq!(i128::from_term(&i128::MIN.to_term()), Ok(i128::MIN));
+    assert_eq!(
+        u64::from_term(&erl_int!(-1)),
+        Err(TermConversionError::OutOfRange)
+    );
+    assert_eq!(usize::from_term(&erl_int!(3)), Ok(3));
+}
+
+#[test]
+fn strings_atoms_and_booleans() {
+    assert_eq!("hi".to_term(), OwnedTerm::String("hi".to_string()));
+    assert_eq!(
+        String::from_term(&OwnedTerm::Binary(b"hi".to_vec())),
+        Ok("hi".to_string())
+    );
+    assert_eq!(Atom::new("ok").to_term(), erl_atom!("ok"));
+    assert_eq!(true.to_term(), erl_atom!("true"));
+    assert_eq!(bool::from_term(&erl_atom!("false")), Ok(false));
+    assert_eq!(f64::from_term(&1.5f64.to_term()), Ok(1.5));
+}
+
+#[test]
+fn options() {
+    assert_eq!(Some(1i64).to_term(), erl_int!(1));
+    assert_eq!(Option::<i64>::from_term(&erl_atom!("undefined")), Ok(None));
+    assert_eq!(Option::<i64>::from_term(&erl_atom!("nil")), Ok(None));
+    assert_eq!(Option::<i64>::from_term(&erl_int!(2)), Ok(Some(2)));
+}
+
+#[test]
+fn lists_tuples_and_maps() {
+    assert_eq!(vec![1i64, 2].to_term(), erl_list![1i64, 2i64]);
+    assert_eq!(Vec::<i64>::from_term(&OwnedTerm::Nil), Ok(vec![]));
+    assert_eq!(
+        Vec::<u8>::from_term(&OwnedTerm::ByteList(vec![1, 2])),
+        Ok(vec![1, 2])
+    );
+
+    let pair = (erl_atom!("ok"), 1u32);
+    assert_eq!(pair.to_term(), erl_tuple![erl_atom!("ok"), 1]);
+    assert_eq!(<(OwnedTerm, u32)>::from_term(&pair.to_term()), Ok(pair));
+    assert_eq!(
+        <(i64, i64)>::from_term(&erl_tuple![1]),
+        Err(TermConversionError::WrongArity {
+            expected: 2,
+            actual: 1
+        })
+    );
+
+    let map = BTreeMap::from([("a".to_string(), 1i64)]);
+    assert_eq!(map.to_term(), erl_map! { "a" => 1i64 });
+    assert_eq!(BTreeMap::from_term(&map.to_term()), Ok(map));
+
+    let ordered = OwnedTerm::OrderedMap(vec![(erl_atom!("k"), erl_int!(1))]);
+    let hash_map = HashMap::<Atom, i64>::from_term(&ordered).unwrap();
+    assert_eq!(hash_map.get(&Atom::new("k")), Some(&1));
+}
@@ -0,0 +1,84 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::decoder::{decode_all, decode_all_with_config};
+use erltf::{AtomTable, DecodeConfig, DecodeError, encode, erl_atom, erl_int, erl_tuple};
+
+fn concatenated(terms: &[erltf::OwnedTerm]) -> Vec<u8> {
+    terms.iter().flat_map(|t| encode(t).unwrap()).collect()
+}
+
+#[test]
+fn test_decode_all_yields_each_term() {
+    let terms = vec![
+        erl_tuple![erl_int!(2), erl_atom!(""), erl_atom!("to")],
+        erl_atom!("payload"),
+        erl_int!(1024),
+    ];
+    let data = concatenated(&terms);
+
+    let decoded: Result<Vec<_>, _> = decode_all(&data).collect();
+
+    assert_eq!(decoded.unwrap(), terms);
+}
+
+#[test]
+fn test_decode_all_of_an_empty_buffer_is_empty() {
+    assert_eq!(decode_all(&[]).count(), 0);
+}
+
+#[test]
+fn test_decode_all_stops_at_the_first_error() {
+    let mut data = concatenated(&[erl_atom!("ok")]);
+    let valid_len = data.len();
+    data.extend([131, 200, 1, 2]);
+    data.extend(encode(&erl_atom!("unreachable")).unwrap());
+
+    let mut terms = decode_all(&data);
+    assert_eq!(terms.next().unwrap().unwrap(), erl_atom!("ok"));
+    assert!(terms.next().unwrap().is_err());
+    assert!(terms.next().is_none());
+    assert_eq!(terms.remaining(), &data[valid_len..]);
+}
+
+#[test]
+fn test_decode_all_remaining_tracks_progress() {
+    let first = encode(&erl_atom!("first")).unwrap();
+    let second = encode(&erl_atom!("second")).unwrap();
+    let data = [first.clone(), second.clone()].concat();
+
+    let mut terms = decode_all(&data);
+    assert_eq!(terms.remaining().len(), data.len());
+    terms.next().unwrap().unwrap();
+    assert_eq!(terms.remaining(), second.as_slice());
+    terms.next().unwrap().unwrap();
+    assert!(terms.remaining().is_empty());
+}
+
+#[test]
+fn test_decode_all_with_config_applies_the_atom_limit() {
+    let config = DecodeConfig::new().atoms_error_after(1);
+    let mut atoms = AtomTable::new();
+    let data = concatenated(&[erl_atom!("a"), erl_atom!("a"), erl_atom!("b")]);
+
+    let decoded: Vec<_> = decode_all_with_config(&data, &config, &mut atoms).collect();
+
+    assert_eq!(decoded.len(), 3);
+    assert!(decoded[0].is_ok() && decoded[1].is_ok());
+    assert!(matches!(
+        decoded[2],
+        Err(DecodeError::AtomLimitExceeded { .. })
+    ));
+    assert_eq!(atoms.distinct_count(), 1);
+}
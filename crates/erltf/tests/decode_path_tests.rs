@@ -0,0 +1,230 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::decoder::skip_term;
+use erltf::types::{Atom, ExternalPid, ExternalReference};
+use erltf::{
+    DecodeError, OwnedTerm, decode, decode_path, encode, erl_atom, erl_int, erl_list, erl_map,
+    erl_tuple,
+};
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use proptest::prelude::*;
+use std::io::Write;
+
+fn bin(s: &str) -> OwnedTerm {
+    OwnedTerm::binary(s.as_bytes().to_vec())
+}
+
+fn message() -> OwnedTerm {
+    erl_tuple!(
+        erl_atom!("publish"),
+        OwnedTerm::binary(vec![0; 4096]),
+        erl_map! {
+            erl_atom!("exchange") => bin("amq.topic"),
+            bin("routing_key") => bin("orders.created"),
+            erl_int!(7) => erl_atom!("seven"),
+        },
+        erl_list![
+            erl_tuple!(erl_atom!("timeout"), erl_int!(5000)),
+            erl_tuple!(
+                erl_atom!("opts"),
+                erl_list![erl_tuple!(erl_atom!("mandatory"), erl_atom!("true"))]
+            ),
+        ]
+    )
+}
+
+#[test]
+fn test_decode_path_tuple_element() {
+    let data = encode(&message()).unwrap();
+    assert_eq!(
+        decode_path(&data, &["1"]).unwrap(),
+        Some(erl_atom!("publish"))
+    );
+}
+
+#[test]
+fn test_decode_path_map_keys() {
+    let data = encode(&message()).unwrap();
+
+    assert_eq!(
+        decode_path(&data, &["3", "exchange"]).unwrap(),
+        Some(bin("amq.topic"))
+    );
+    assert_eq!(
+        decode_path(&data, &["3", "routing_key"]).unwrap(),
+        Some(bin("orders.created"))
+    );
+    assert_eq!(
+        decode_path(&data, &["3", "7"]).unwrap(),
+        Some(erl_atom!("seven"))
+    );
+}
+
+#[test]
+fn test_decode_path_proplists() {
+    let data = encode(&message()).unwrap();
+
+    assert_eq!(
+        decode_path(&data, &["4", "timeout"]).unwrap(),
+        Some(erl_int!(5000))
+    );
+    assert_eq!(
+        decode_path(&data, &["4", "opts", "mandatory"]).unwrap(),
+        Some(erl_atom!("true"))
+    );
+}
+
+#[test]
+fn test_decode_path_list_position() {
+    let data = encode(&message()).unwrap();
+    assert_eq!(
+        decode_path(&data, &["4", "1", "2"]).unwrap(),
+        Some(erl_int!(5000))
+    );
+}
+
+#[test]
+fn test_decode_path_empty_path_decodes_everything() {
+    let data = encode(&message()).unwrap();
+    assert_eq!(decode_path(&data, &[]).unwrap(), Some(message()));
+}
+
+#[test]
+fn test_decode_path_missing() {
+    let data = encode(&message()).unwrap();
+
+    assert_eq!(decode_path(&data, &["5"]).unwrap(), None);
+    assert_eq!(decode_path(&data, &["0"]).unwrap(), None);
+    assert_eq!(decode_path(&data, &["3", "missing"]).unwrap(), None);
+    assert_eq!(decode_path(&data, &["4", "missing"]).unwrap(), None);
+    assert_eq!(decode_path(&data, &["1", "anything"]).unwrap(), None);
+}
+
+#[test]
+fn test_decode_path_compressed() {
+    let term = erl_tuple!(
+        erl_atom!("ok"),
+        erl_map! { erl_atom!("key") => erl_int!(42) }
+    );
+    let uncompressed = encode(&term).unwrap();
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&uncompressed[1..]).unwrap();
+    let zlib = encoder.finish().unwrap();
+
+    let mut data = vec![131, 80];
+    data.extend_from_slice(&(uncompressed.len() as u32 - 1).to_be_bytes());
+    data.extend_from_slice(&zlib);
+
+    assert_eq!(
+        decode_path(&data, &["2", "key"]).unwrap(),
+        Some(erl_int!(42))
+    );
+    assert_eq!(skip_term(&data[1..]).unwrap(), &[] as &[u8]);
+}
+
+#[test]
+fn test_skip_term_compressed_size_mismatch() {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&[0; 1 << 20]).unwrap();
+    let zlib = encoder.finish().unwrap();
+
+    let mut data = vec![80];
+    data.extend_from_slice(&16u32.to_be_bytes());
+    data.extend_from_slice(&zlib);
+
+    assert!(matches!(
+        skip_term(&data),
+        Err(DecodeError::InvalidFormat(msg)) if msg.contains("size mismatch")
+    ));
+
+    let mut tuple = vec![131, 104, 2];
+    tuple.extend_from_slice(&data);
+    tuple.extend_from_slice(&encode(&erl_atom!("ok")).unwrap()[1..]);
+    assert!(matches!(
+        decode_path(&tuple, &["2"]),
+        Err(DecodeError::InvalidFormat(msg)) if msg.contains("size mismatch")
+    ));
+}
+
+#[test]
+fn test_decode_path_invalid_version() {
+    assert!(matches!(
+        decode_path(&[130, 106], &[]),
+        Err(DecodeError::InvalidVersion { .. })
+    ));
+}
+
+#[test]
+fn test_decode_path_truncated() {
+    let data = encode(&message()).unwrap();
+    assert_eq!(
+        decode_path(&data[..100], &["4", "timeout"]),
+        Err(DecodeError::UnexpectedEof)
+    );
+}
+
+#[test]
+fn test_skip_term_leaves_trailing_data() {
+    let pid = ExternalPid::new(Atom::new("a@host"), 1, 2, 3);
+    let reference = ExternalReference::new(Atom::new("a@host"), 3, vec![1, 2, 3]);
+    let term = erl_tuple!(OwnedTerm::Pid(pid), OwnedTerm::Reference(reference));
+    let mut data = encode(&term).unwrap();
+    data.extend_from_slice(&[1, 2, 3]);
+
+    assert_eq!(skip_term(&data[1..]).unwrap(), &[1, 2, 3]);
+}
+
+#[test]
+fn test_skip_term_unknown_tag() {
+    assert_eq!(skip_term(&[0xFE]), Err(DecodeError::InvalidTag(0xFE)));
+}
+
+fn arb_term() -> impl Strategy<Value = OwnedTerm> {
+    let leaf = prop_oneof![
+        any::<i32>().prop_map(|v| OwnedTerm::Integer(v as i64)),
+        (-1.0e9..1.0e9f64).prop_map(OwnedTerm::Float),
+        "[a-z]{1,10}".prop_map(|s| OwnedTerm::Atom(Atom::new(s))),
+        prop::collection::vec(any::<u8>(), 0..50).prop_map(OwnedTerm::Binary),
+    ];
+    leaf.prop_recursive(3, 32, 8, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..8).prop_map(OwnedTerm::List),
+            prop::collection::vec(inner.clone(), 0..8).prop_map(OwnedTerm::Tuple),
+            prop::collection::vec((inner.clone(), inner), 0..4)
+                .prop_map(|pairs| OwnedTerm::Map(pairs.into_iter().collect())),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn test_prop_skip_term_consumes_whole_term(term in arb_term()) {
+        let data = encode(&term).unwrap();
+        prop_assert_eq!(skip_term(&data[1..]).unwrap(), &[] as &[u8]);
+    }
+
+    #[test]
+    fn test_prop_decode_path_tuple_elements(elements in prop::collection::vec(arb_term(), 1..8)) {
+        let data = encode(&OwnedTerm::Tuple(elements.clone())).unwrap();
+        let OwnedTerm::Tuple(decoded) = decode(&data).unwrap() else {
+            panic!("expected a tuple");
+        };
+        for (i, element) in decoded.iter().enumerate() {
+            let found = decode_path(&data, &[&(i + 1).to_string()]).unwrap();
+            prop_assert_eq!(found.as_ref(), Some(element));
+        }
+    }
+}
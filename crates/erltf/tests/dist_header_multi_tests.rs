@@ -0,0 +1,141 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::decoder::AtomCache;
+use erltf::{
+    Atom, DistHeaderOptions, EncodeConfig, EncodeError, EncodeMode, FloatPolicy, OutgoingAtomCache,
+    OwnedTerm, decode_all_with_atom_cache, decode_with_atom_cache, encode_with_dist_header_cached,
+    encode_with_dist_header_multi, encode_with_dist_header_report, erl_atom, erl_int, erl_tuple,
+};
+
+#[test]
+fn test_multi_encodes_any_number_of_terms_with_one_header() {
+    let control = erl_tuple![erl_int!(2), erl_atom!(""), erl_atom!("shared")];
+    let first = erl_tuple![erl_atom!("shared"), erl_int!(1)];
+    let second = erl_tuple![erl_atom!("shared"), erl_atom!("other")];
+    let third = erl_atom!("other");
+
+    let encoded = encode_with_dist_header_multi(&[&control, &first, &second, &third]).unwrap();
+    let decoded = decode_all_with_atom_cache(&encoded, &mut AtomCache::new()).unwrap();
+
+    assert_eq!(decoded, vec![control, first, second, third]);
+    // Each atom is written once, in the header
+    assert_eq!(
+        encoded
+            .windows(b"shared".len())
+            .filter(|w| *w == b"shared")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_report_lists_new_entries_then_reused_ones() {
+    let mut outgoing = OutgoingAtomCache::new();
+    let mut incoming = AtomCache::new();
+    let control = erl_tuple![erl_int!(6), erl_atom!("alpha")];
+    let payload = erl_tuple![erl_atom!("beta"), erl_int!(1)];
+    let options = DistHeaderOptions::new();
+
+    let (encoded, report) =
+        encode_with_dist_header_report(&[&control, &payload], &mut outgoing, &options).unwrap();
+    decode_with_atom_cache(&encoded, &mut incoming).unwrap();
+    let newly_cached: Vec<&str> = report.newly_cached().map(Atom::as_str).collect();
+    assert_eq!(newly_cached, vec!["alpha", "beta"]);
+    assert!(report.reused.is_empty());
+    assert_eq!(report.len(), 2);
+
+    let next = erl_tuple![erl_atom!("beta"), erl_atom!("gamma")];
+    let (encoded, report) =
+        encode_with_dist_header_report(&[&control, &next], &mut outgoing, &options).unwrap();
+    let (_, decoded) = decode_with_atom_cache(&encoded, &mut incoming).unwrap();
+    assert_eq!(decoded, Some(next));
+    assert_eq!(report.new_entries, vec![(Atom::new("gamma"), 2)]);
+    assert_eq!(
+        report.reused,
+        vec![(Atom::new("alpha"), 0), (Atom::new("beta"), 1)]
+    );
+}
+
+#[test]
+fn test_predeclared_atoms_are_cached_without_being_used() {
+    let mut outgoing = OutgoingAtomCache::new();
+    let mut incoming = AtomCache::new();
+    let options = DistHeaderOptions::new()
+        .with_predeclared_atoms([Atom::new("later"), Atom::new("much_later")]);
+    let control = erl_tuple![erl_int!(6), erl_atom!("now")];
+
+    let (encoded, report) =
+        encode_with_dist_header_report(&[&control], &mut outgoing, &options).unwrap();
+    decode_with_atom_cache(&encoded, &mut incoming).unwrap();
+
+    let newly_cached: Vec<&str> = report.newly_cached().map(Atom::as_str).collect();
+    assert_eq!(newly_cached, vec!["now", "later", "much_later"]);
+    assert_eq!(incoming.entries_len(), 3);
+
+    let next = erl_tuple![erl_atom!("later"), erl_atom!("much_later")];
+    let encoded = encode_with_dist_header_cached(&[&next], &mut outgoing).unwrap();
+    assert!(!encoded.windows(b"later".len()).any(|w| w == b"later"));
+    let (decoded, _) = decode_with_atom_cache(&encoded, &mut incoming).unwrap();
+    assert_eq!(decoded, next);
+}
+
+#[test]
+fn test_report_respects_the_encode_mode() {
+    let pid = erltf::ExternalPid::new(Atom::new("legacy@localhost"), 1, 0, 2);
+    let term = OwnedTerm::Pid(pid.clone());
+    let options = DistHeaderOptions::new().with_mode(EncodeMode::Legacy);
+
+    let (encoded, report) =
+        encode_with_dist_header_report(&[&term], &mut OutgoingAtomCache::new(), &options).unwrap();
+    let (decoded, _) = decode_with_atom_cache(&encoded, &mut AtomCache::new()).unwrap();
+
+    assert_eq!(decoded, term);
+    assert_eq!(report.new_entries, vec![(Atom::new("legacy@localhost"), 0)]);
+}
+
+#[test]
+fn test_predeclared_atoms_are_validated() {
+    let options = DistHeaderOptions::new().with_predeclared_atoms([Atom::new("a".repeat(256))]);
+    let err = encode_with_dist_header_report(
+        &[&erl_atom!("ok")],
+        &mut OutgoingAtomCache::new(),
+        &options,
+    )
+    .unwrap_err();
+    assert!(matches!(err, EncodeError::AtomTooLong { .. }));
+}
+
+#[test]
+fn test_report_applies_the_encode_config() {
+    let terms = erl_tuple![erl_atom!("sensor"), OwnedTerm::Float(f64::INFINITY)];
+    let config = EncodeConfig::new().with_float_policy(FloatPolicy::SaturateToMax);
+    let options = DistHeaderOptions::new().with_encode_config(config);
+
+    let (encoded, _) =
+        encode_with_dist_header_report(&[&terms], &mut OutgoingAtomCache::new(), &options).unwrap();
+    let (decoded, _) = decode_with_atom_cache(&encoded, &mut AtomCache::new()).unwrap();
+    assert_eq!(
+        decoded,
+        erl_tuple![erl_atom!("sensor"), OwnedTerm::Float(f64::MAX)]
+    );
+
+    let config = EncodeConfig::new().with_max_atom_len(3);
+    let options = DistHeaderOptions::new().with_encode_config(config);
+    let result = encode_with_dist_header_report(&[&terms], &mut OutgoingAtomCache::new(), &options);
+    assert!(matches!(
+        result,
+        Err(EncodeError::AtomTooLong { max: 3, .. })
+    ));
+}
@@ -0,0 +1,139 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::errors::{DecodeError, EncodeError};
+use erltf::{
+    EncodeConfig, EncodeMode, FloatPolicy, OwnedTerm, decode, decode_borrowed, encode,
+    encode_with_config, erl_atom, erl_list,
+};
+
+const NEW_FLOAT_EXT: u8 = 70;
+
+fn new_float(value: f64) -> Vec<u8> {
+    let mut data = vec![131, NEW_FLOAT_EXT];
+    data.extend_from_slice(&value.to_be_bytes());
+    data
+}
+
+fn encode_with_policy(term: &OwnedTerm, policy: FloatPolicy) -> Result<Vec<u8>, EncodeError> {
+    encode_with_config(term, &EncodeConfig::new().with_float_policy(policy))
+}
+
+#[test]
+fn test_encode_config_defaults() {
+    let config = EncodeConfig::new();
+    assert_eq!(config.mode(), EncodeMode::Current);
+    assert_eq!(config.float_policy(), FloatPolicy::Error);
+}
+
+#[test]
+fn test_encode_config_builders() {
+    let config = EncodeConfig::new()
+        .with_mode(EncodeMode::Legacy)
+        .with_float_policy(FloatPolicy::SaturateToMax);
+    assert_eq!(config.mode(), EncodeMode::Legacy);
+    assert_eq!(config.float_policy(), FloatPolicy::SaturateToMax);
+}
+
+#[test]
+fn test_finite_floats_are_unaffected_by_policy() {
+    let term = OwnedTerm::Float(1.5);
+    for policy in [
+        FloatPolicy::Error,
+        FloatPolicy::SaturateToMax,
+        FloatPolicy::EncodeAsAtomTag,
+    ] {
+        assert_eq!(encode_with_policy(&term, policy).unwrap(), new_float(1.5));
+    }
+}
+
+#[test]
+fn test_error_policy_rejects_nested_nan() {
+    let term = erl_list![OwnedTerm::Integer(1), OwnedTerm::Float(f64::NAN)];
+    let result = encode_with_policy(&term, FloatPolicy::Error);
+    assert!(matches!(result, Err(EncodeError::NonFiniteFloat { value }) if value.is_nan()));
+}
+
+#[test]
+fn test_saturate_to_max_policy() {
+    let encoded = encode_with_policy(&OwnedTerm::Float(f64::INFINITY), FloatPolicy::SaturateToMax);
+    assert_eq!(
+        decode(&encoded.unwrap()).unwrap(),
+        OwnedTerm::Float(f64::MAX)
+    );
+
+    let encoded = encode_with_policy(
+        &OwnedTerm::Float(f64::NEG_INFINITY),
+        FloatPolicy::SaturateToMax,
+    );
+    assert_eq!(
+        decode(&encoded.unwrap()).unwrap(),
+        OwnedTerm::Float(f64::MIN)
+    );
+}
+
+#[test]
+fn test_saturate_to_max_policy_rejects_nan() {
+    let result = encode_with_policy(&OwnedTerm::Float(f64::NAN), FloatPolicy::SaturateToMax);
+    assert!(matches!(result, Err(EncodeError::NonFiniteFloat { .. })));
+}
+
+#[test]
+fn test_encode_as_atom_tag_policy() {
+    let cases = [
+        (f64::NAN, "nan"),
+        (f64::INFINITY, "infinity"),
+        (f64::NEG_INFINITY, "neg_infinity"),
+    ];
+    for (value, atom) in cases {
+        let encoded =
+            encode_with_policy(&OwnedTerm::Float(value), FloatPolicy::EncodeAsAtomTag).unwrap();
+        assert_eq!(decode(&encoded).unwrap(), erl_atom!(atom));
+    }
+}
+
+#[test]
+fn test_decode_rejects_nan_from_the_wire() {
+    let result = decode(&new_float(f64::NAN));
+    assert!(matches!(result, Err(DecodeError::NonFiniteFloat(v)) if v.is_nan()));
+}
+
+#[test]
+fn test_decode_rejects_infinity_from_the_wire() {
+    let result = decode(&new_float(f64::INFINITY));
+    assert!(matches!(result, Err(DecodeError::NonFiniteFloat(v)) if v == f64::INFINITY));
+}
+
+#[test]
+fn test_decode_rejects_old_float_infinity() {
+    let mut data = vec![131, 99];
+    let mut text = b"inf".to_vec();
+    text.resize(31, 0);
+    data.extend_from_slice(&text);
+    assert!(matches!(
+        decode(&data),
+        Err(DecodeError::NonFiniteFloat(v)) if v == f64::INFINITY
+    ));
+}
+
+#[test]
+fn test_decode_borrowed_rejects_nan() {
+    assert!(decode_borrowed(&new_float(f64::NAN)).is_err());
+}
+
+#[test]
+fn test_encode_defaults_to_error_policy() {
+    let result = encode(&OwnedTerm::Float(f64::INFINITY));
+    assert!(matches!(result, Err(EncodeError::NonFiniteFloat { .. })));
+}
@@ -0,0 +1,83 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalReference};
+use std::collections::BTreeMap;
+
+const TERM: usize = size_of::<OwnedTerm>();
+
+#[test]
+fn test_immediates_take_only_their_own_size() {
+    assert_eq!(OwnedTerm::integer(42).heap_size(), TERM);
+    assert_eq!(OwnedTerm::float(1.5).heap_size(), TERM);
+    assert_eq!(OwnedTerm::Nil.heap_size(), TERM);
+    assert_eq!(
+        OwnedTerm::atom("a_rather_long_atom_name").heap_size(),
+        TERM,
+        "atom names are shared with the atom table"
+    );
+}
+
+#[test]
+fn test_binaries_and_strings_count_their_capacity() {
+    assert_eq!(OwnedTerm::Binary(vec![0; 1000]).heap_size(), TERM + 1000);
+    assert_eq!(OwnedTerm::ByteList(vec![1; 10]).heap_size(), TERM + 10);
+
+    let mut s = String::with_capacity(64);
+    s.push_str("hello");
+    assert_eq!(OwnedTerm::String(s).heap_size(), TERM + 64);
+}
+
+#[test]
+fn test_containers_count_their_elements() {
+    let list = OwnedTerm::List(vec![OwnedTerm::integer(1), OwnedTerm::Binary(vec![0; 100])]);
+    assert_eq!(list.heap_size(), TERM + TERM + (TERM + 100));
+
+    let tuple = OwnedTerm::Tuple(vec![OwnedTerm::atom("ok"), list.clone()]);
+    assert_eq!(tuple.heap_size(), TERM + TERM + list.heap_size());
+
+    let mut map = BTreeMap::new();
+    map.insert(OwnedTerm::atom("k"), OwnedTerm::Binary(vec![0; 10]));
+    assert_eq!(OwnedTerm::Map(map).heap_size(), TERM + TERM + (TERM + 10));
+
+    let improper = OwnedTerm::ImproperList {
+        elements: vec![OwnedTerm::integer(1)],
+        tail: Box::new(OwnedTerm::integer(2)),
+    };
+    assert_eq!(improper.heap_size(), 3 * TERM);
+}
+
+#[test]
+fn test_spare_vector_capacity_is_counted() {
+    let mut elements = Vec::with_capacity(8);
+    elements.push(OwnedTerm::integer(1));
+    assert_eq!(OwnedTerm::List(elements).heap_size(), TERM + 8 * TERM);
+}
+
+#[test]
+fn test_references_count_their_ids() {
+    let reference = ExternalReference::new(Atom::new("node@host"), 1, vec![1, 2, 3]);
+    assert_eq!(
+        OwnedTerm::Reference(reference).heap_size(),
+        TERM + 3 * size_of::<u32>()
+    );
+}
+
+#[test]
+fn test_nested_terms_grow_with_their_payload() {
+    let small = OwnedTerm::List(vec![OwnedTerm::Binary(vec![0; 10]); 10]);
+    let large = OwnedTerm::List(vec![OwnedTerm::Binary(vec![0; 10_000]); 10]);
+    assert!(large.heap_size() > 100_000);
+    assert!(large.heap_size() > small.heap_size());
+}
@@ -0,0 +1,178 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::tags::{
+    ATOM_CACHE_REF, BINARY_EXT, DIST_HEADER, LIST_EXT, MAP_EXT, NEW_PID_EXT, NIL_EXT,
+    SMALL_ATOM_UTF8_EXT, SMALL_INTEGER_EXT, SMALL_TUPLE_EXT, VERSION,
+};
+use erltf::{
+    Annotation, ExternalPid, OwnedTerm, annotated_hex_dump, encode, encode_with_dist_header_multi,
+    erl_atom, erl_int, erl_list, erl_map, erl_tuple, inspect,
+};
+
+fn tags(annotations: &[Annotation]) -> Vec<u8> {
+    annotations.iter().map(|a| a.tag).collect()
+}
+
+#[test]
+fn test_inspect_tuple() {
+    let data = encode(&erl_tuple!(erl_atom!("ok"), erl_int!(7))).unwrap();
+    let annotations = inspect(&data);
+
+    assert_eq!(
+        tags(&annotations),
+        vec![
+            VERSION,
+            SMALL_TUPLE_EXT,
+            SMALL_ATOM_UTF8_EXT,
+            SMALL_INTEGER_EXT
+        ]
+    );
+    let tuple = &annotations[1];
+    assert_eq!(tuple.offset, 1);
+    assert_eq!(tuple.depth, 0);
+    assert_eq!(tuple.header_len, 2);
+    assert_eq!(tuple.length, data.len() - 1);
+    assert_eq!(tuple.preview, "arity 2");
+    assert_eq!(annotations[2].name, "SMALL_ATOM_UTF8_EXT");
+    assert_eq!(annotations[2].depth, 1);
+    assert_eq!(annotations[2].preview, "ok");
+    assert_eq!(annotations[3].preview, "7");
+    assert!(annotations.iter().all(|a| !a.is_error()));
+}
+
+#[test]
+fn test_inspect_nested_containers() {
+    let term = erl_map! {
+        erl_atom!("items") => erl_list![erl_int!(1), OwnedTerm::binary(b"abc".to_vec())],
+    };
+    let data = encode(&term).unwrap();
+    let annotations = inspect(&data);
+
+    assert_eq!(
+        tags(&annotations),
+        vec![
+            VERSION,
+            MAP_EXT,
+            SMALL_ATOM_UTF8_EXT,
+            LIST_EXT,
+            SMALL_INTEGER_EXT,
+            BINARY_EXT,
+            NIL_EXT
+        ]
+    );
+    let depths: Vec<usize> = annotations.iter().map(|a| a.depth).collect();
+    assert_eq!(depths, vec![0, 0, 1, 1, 2, 2, 2]);
+    assert_eq!(annotations[1].preview, "1 pairs");
+    assert_eq!(annotations[5].preview, "<<\"abc\">>");
+    assert_eq!(annotations[1].length, data.len() - 1);
+}
+
+#[test]
+fn test_inspect_pid() {
+    let pid = ExternalPid::new(erltf::Atom::new("a@host"), 42, 1, 3);
+    let data = encode(&OwnedTerm::Pid(pid)).unwrap();
+    let annotations = inspect(&data);
+
+    assert_eq!(annotations[1].tag, NEW_PID_EXT);
+    assert_eq!(annotations[1].preview, "id 42, serial 1, creation 3");
+    assert_eq!(annotations[1].length, data.len() - 1);
+    assert_eq!(annotations[2].preview, "a@host");
+    assert_eq!(annotations[2].depth, 1);
+}
+
+#[test]
+fn test_inspect_truncated_data() {
+    let data = encode(&erl_tuple!(
+        erl_atom!("hello"),
+        OwnedTerm::binary(vec![0; 64])
+    ))
+    .unwrap();
+    let truncated = &data[..data.len() - 10];
+    let annotations = inspect(truncated);
+
+    let last = annotations.last().unwrap();
+    assert_eq!(last.tag, BINARY_EXT);
+    assert!(
+        last.error
+            .as_ref()
+            .unwrap()
+            .contains("truncated BINARY_EXT")
+    );
+    assert_eq!(annotations[1].length, truncated.len() - 1);
+    assert_eq!(annotations.iter().filter(|a| a.is_error()).count(), 1);
+}
+
+#[test]
+fn test_inspect_unknown_tag() {
+    let annotations = inspect(&[VERSION, SMALL_TUPLE_EXT, 2, SMALL_INTEGER_EXT, 1, 0xFE]);
+
+    let last = annotations.last().unwrap();
+    assert_eq!(last.offset, 5);
+    assert_eq!(last.name, "UNKNOWN");
+    assert!(last.error.as_ref().unwrap().contains("unknown tag 254"));
+}
+
+#[test]
+fn test_inspect_missing_element() {
+    let annotations = inspect(&[VERSION, SMALL_TUPLE_EXT, 2, SMALL_INTEGER_EXT, 1]);
+
+    let last = annotations.last().unwrap();
+    assert!(
+        last.error
+            .as_ref()
+            .unwrap()
+            .contains("unexpected end of data")
+    );
+}
+
+#[test]
+fn test_inspect_dist_header_with_multiple_terms() {
+    let control = erl_tuple!(erl_int!(2), erl_atom!("cookie"));
+    let payload = erl_atom!("hello");
+    let data = encode_with_dist_header_multi(&[&control, &payload]).unwrap();
+    let annotations = inspect(&data);
+
+    assert_eq!(annotations[0].tag, VERSION);
+    assert_eq!(annotations[1].tag, DIST_HEADER);
+    assert!(annotations[1].preview.contains("atom cache refs"));
+    assert!(annotations.iter().any(|a| a.tag == ATOM_CACHE_REF));
+    assert!(annotations.iter().all(|a| !a.is_error()));
+    let top_level = annotations.iter().filter(|a| a.depth == 0).count();
+    assert_eq!(top_level, 4);
+}
+
+#[test]
+fn test_annotated_hex_dump() {
+    let data = encode(&erl_tuple!(erl_atom!("ok"), erl_int!(7))).unwrap();
+    let dump = annotated_hex_dump(&data);
+    let lines: Vec<&str> = dump.lines().collect();
+
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("00000000  83 "));
+    assert!(lines[1].starts_with("00000001  68 02 "));
+    assert!(lines[1].contains("SMALL_TUPLE_EXT [8 bytes] arity 2"));
+    assert!(lines[2].contains("  SMALL_ATOM_UTF8_EXT [4 bytes] ok"));
+}
+
+#[test]
+fn test_annotated_hex_dump_reports_errors() {
+    let dump = annotated_hex_dump(&[VERSION, 0xFE]);
+    assert!(dump.contains("!! unknown tag 254 (0xfe)"));
+}
+
+#[test]
+fn test_inspect_empty_input() {
+    assert!(inspect(&[]).is_empty());
+}
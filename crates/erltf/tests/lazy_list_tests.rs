@@ -0,0 +1,187 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::errors::DecodeError;
+use erltf::tags::{COMPRESSED_EXT, LIST_EXT};
+use erltf::{
+    BorrowedTerm, LazyList, MaybeLazy, OwnedTerm, decode, decode_lazily, encode, erl_atom, erl_int,
+    erl_list, erl_tuple,
+};
+use std::io::Write;
+
+fn rows(count: i64) -> OwnedTerm {
+    OwnedTerm::List(
+        (0..count)
+            .map(|i| erl_tuple!(erl_atom!("row"), erl_int!(i), OwnedTerm::binary(vec![1, 2])))
+            .collect(),
+    )
+}
+
+fn compress(encoded: &[u8]) -> Vec<u8> {
+    let mut data = vec![131, COMPRESSED_EXT];
+    data.extend(((encoded.len() - 1) as u32).to_be_bytes());
+    let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&encoded[1..]).unwrap();
+    data.extend(encoder.finish().unwrap());
+    data
+}
+
+#[test]
+fn test_lazy_list_decodes_elements_on_demand() {
+    let term = rows(100);
+    let data = encode(&term).unwrap();
+    let list = LazyList::new(&data).unwrap();
+
+    assert_eq!(list.len(), 100);
+    assert!(!list.is_empty());
+    let elements: Vec<OwnedTerm> = list.iter().map(Result::unwrap).collect();
+    assert_eq!(OwnedTerm::List(elements), term);
+    assert_eq!(list.to_term().unwrap(), term);
+}
+
+#[test]
+fn test_lazy_list_get() {
+    let data = encode(&rows(10)).unwrap();
+    let list = LazyList::new(&data).unwrap();
+
+    assert_eq!(
+        list.get(7).unwrap(),
+        Some(erl_tuple!(
+            erl_atom!("row"),
+            erl_int!(7),
+            OwnedTerm::binary(vec![1, 2])
+        ))
+    );
+    assert_eq!(list.get(10).unwrap(), None);
+}
+
+#[test]
+fn test_lazy_list_chunks() {
+    let data = encode(&rows(10)).unwrap();
+    let list = LazyList::new(&data).unwrap();
+
+    let sizes: Vec<usize> = list.chunks(4).map(|chunk| chunk.unwrap().len()).collect();
+    assert_eq!(sizes, vec![4, 4, 2]);
+    let flattened: Vec<OwnedTerm> = list.chunks(3).flat_map(Result::unwrap).collect();
+    assert_eq!(OwnedTerm::List(flattened), rows(10));
+}
+
+#[test]
+fn test_lazy_list_borrowed_iteration() {
+    let data = encode(&erl_list![
+        OwnedTerm::binary(b"abc".to_vec()),
+        erl_atom!("ok")
+    ])
+    .unwrap();
+    let list = LazyList::new(&data).unwrap();
+
+    let elements: Vec<BorrowedTerm<'_>> = list.iter_borrowed().map(Result::unwrap).collect();
+    assert_eq!(elements[0], BorrowedTerm::Binary(b"abc"[..].into()));
+    assert_eq!(elements[1].to_owned(), erl_atom!("ok"));
+}
+
+#[test]
+fn test_lazy_list_of_small_integers() {
+    let term = erl_list![erl_int!(1), erl_int!(2), erl_int!(3)];
+    let data = encode(&term).unwrap();
+    let list = LazyList::new(&data).unwrap();
+
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.get(2).unwrap(), Some(erl_int!(3)));
+    assert_eq!(list.tail().unwrap(), OwnedTerm::Nil);
+    assert_eq!(list.to_term().unwrap(), term);
+    let borrowed: Vec<_> = list.iter_borrowed().map(Result::unwrap).collect();
+    assert_eq!(borrowed[0], BorrowedTerm::Integer(1));
+}
+
+#[test]
+fn test_lazy_list_of_nil() {
+    let list = LazyList::new(&[131, 106]).unwrap();
+    assert!(list.is_empty());
+    assert_eq!(list.iter().count(), 0);
+    assert_eq!(list.to_term().unwrap(), OwnedTerm::Nil);
+}
+
+#[test]
+fn test_lazy_improper_list() {
+    let term = OwnedTerm::improper_list(vec![erl_atom!("a")], erl_atom!("b"));
+    let data = encode(&term).unwrap();
+    let list = LazyList::new(&data).unwrap();
+
+    assert_eq!(list.len(), 1);
+    assert_eq!(list.tail().unwrap(), erl_atom!("b"));
+    assert_eq!(list.to_term().unwrap(), term);
+}
+
+#[test]
+fn test_lazy_list_rejects_other_terms() {
+    let data = encode(&erl_tuple!(erl_atom!("ok"))).unwrap();
+    assert!(matches!(
+        LazyList::new(&data),
+        Err(DecodeError::InvalidFormat(_))
+    ));
+}
+
+#[test]
+fn test_lazy_list_stops_after_the_first_error() {
+    let mut data = vec![131, LIST_EXT, 0, 0, 0, 3];
+    data.extend([97, 1, 200]);
+    let list = LazyList::new(&data).unwrap();
+
+    let results: Vec<_> = list.iter().collect();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0], Ok(erl_int!(1)));
+    assert!(results[1].is_err());
+}
+
+#[test]
+fn test_lazy_list_of_a_compressed_term() {
+    let term = rows(50);
+    let data = compress(&encode(&term).unwrap());
+    let list = LazyList::new(&data).unwrap();
+
+    assert_eq!(list.len(), 50);
+    assert_eq!(list.to_term().unwrap(), term);
+}
+
+#[test]
+fn test_lazy_list_at_path() {
+    let reply = erl_tuple!(erl_atom!("rex"), rows(20));
+    let data = encode(&reply).unwrap();
+
+    let list = LazyList::at_path(&data, &["2"]).unwrap().unwrap();
+    assert_eq!(list.len(), 20);
+    assert_eq!(list.to_term().unwrap(), rows(20));
+    assert!(LazyList::at_path(&data, &["3"]).unwrap().is_none());
+}
+
+#[test]
+fn test_decode_lazily_threshold() {
+    let data = encode(&rows(10)).unwrap();
+
+    match decode_lazily(&data, 5).unwrap() {
+        MaybeLazy::Lazy(list) => assert_eq!(list.len(), 10),
+        MaybeLazy::Decoded(term) => panic!("expected a lazy list, got {term}"),
+    }
+    match decode_lazily(&data, 10).unwrap() {
+        MaybeLazy::Decoded(term) => assert_eq!(term, decode(&data).unwrap()),
+        MaybeLazy::Lazy(_) => panic!("expected a decoded term"),
+    }
+
+    let data = encode(&erl_atom!("ok")).unwrap();
+    assert!(matches!(
+        decode_lazily(&data, 0).unwrap(),
+        MaybeLazy::Decoded(_)
+    ));
+}
@@ -0,0 +1,153 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::decoder::AtomCache;
+use erltf::tags::{
+    LOCAL_EXT, NEW_PID_EXT, NEW_REFERENCE_EXT, NEWER_REFERENCE_EXT, PID_EXT, PORT_EXT,
+    SMALL_ATOM_UTF8_EXT, V4_PORT_EXT, VERSION,
+};
+use erltf::types::{Atom, ExternalPid, ExternalPort, ExternalReference};
+use erltf::{
+    EncodeError, EncodeMode, OutgoingAtomCache, OwnedTerm, decode, decode_borrowed,
+    decode_with_atom_cache, encode, encode_with_dist_header_cached_and_mode, encode_with_mode,
+};
+use proptest::prelude::*;
+
+fn pid(creation: u32) -> OwnedTerm {
+    OwnedTerm::Pid(ExternalPid::new(Atom::new("c@host"), 42, 7, creation))
+}
+
+fn port(id: u64, creation: u32) -> OwnedTerm {
+    OwnedTerm::Port(ExternalPort::new(Atom::new("c@host"), id, creation))
+}
+
+fn reference(creation: u32) -> OwnedTerm {
+    OwnedTerm::Reference(ExternalReference::new(
+        Atom::new("c@host"),
+        creation,
+        vec![1, 2, 3],
+    ))
+}
+
+#[test]
+fn test_current_mode_matches_encode() {
+    for term in [pid(1), port(5, 1), reference(1)] {
+        assert_eq!(
+            encode_with_mode(&term, EncodeMode::Current).unwrap(),
+            encode(&term).unwrap()
+        );
+    }
+    assert_eq!(encode(&pid(1)).unwrap()[1], NEW_PID_EXT);
+    assert_eq!(encode(&port(5, 1)).unwrap()[1], V4_PORT_EXT);
+    assert_eq!(encode(&reference(1)).unwrap()[1], NEWER_REFERENCE_EXT);
+}
+
+#[test]
+fn test_legacy_pid_layout() {
+    let encoded = encode_with_mode(&pid(3), EncodeMode::Legacy).unwrap();
+    let mut expected = vec![VERSION, PID_EXT, SMALL_ATOM_UTF8_EXT, 6];
+    expected.extend_from_slice(b"c@host");
+    expected.extend_from_slice(&42u32.to_be_bytes());
+    expected.extend_from_slice(&7u32.to_be_bytes());
+    expected.push(3);
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_legacy_port_layout() {
+    let encoded = encode_with_mode(&port(9, 2), EncodeMode::Legacy).unwrap();
+    let mut expected = vec![VERSION, PORT_EXT, SMALL_ATOM_UTF8_EXT, 6];
+    expected.extend_from_slice(b"c@host");
+    expected.extend_from_slice(&9u32.to_be_bytes());
+    expected.push(2);
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_legacy_reference_layout() {
+    let encoded = encode_with_mode(&reference(1), EncodeMode::Legacy).unwrap();
+    let mut expected = vec![VERSION, NEW_REFERENCE_EXT, 0, 3, SMALL_ATOM_UTF8_EXT, 6];
+    expected.extend_from_slice(b"c@host");
+    expected.push(1);
+    for id in [1u32, 2, 3] {
+        expected.extend_from_slice(&id.to_be_bytes());
+    }
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_legacy_terms_roundtrip_in_nested_terms() {
+    let term = OwnedTerm::Tuple(vec![
+        OwnedTerm::atom("reply"),
+        OwnedTerm::List(vec![pid(1), port(3, 1), reference(2)]),
+    ]);
+    let encoded = encode_with_mode(&term, EncodeMode::Legacy).unwrap();
+    assert_eq!(decode(&encoded).unwrap(), term);
+    assert_eq!(decode_borrowed(&encoded).unwrap().to_owned(), term);
+}
+
+#[test]
+fn test_legacy_mode_rejects_large_creations() {
+    for term in [pid(256), port(1, 300), reference(u32::MAX)] {
+        let err = encode_with_mode(&term, EncodeMode::Legacy).unwrap_err();
+        assert!(matches!(err, EncodeError::CreationTooLarge { .. }));
+    }
+}
+
+#[test]
+fn test_legacy_mode_rejects_large_port_ids() {
+    let id = u64::from(u32::MAX) + 1;
+    let err = encode_with_mode(&port(id, 1), EncodeMode::Legacy).unwrap_err();
+    assert!(matches!(err, EncodeError::PortIdTooLarge { id: got } if got == id));
+}
+
+#[test]
+fn test_legacy_mode_does_not_emit_local_ext() {
+    let local = ExternalPid::with_local_ext_bytes(Atom::new("c@host"), 42, 7, 1, vec![0u8; 8]);
+    let term = OwnedTerm::Pid(local);
+    assert_eq!(encode(&term).unwrap()[1], LOCAL_EXT);
+    let encoded = encode_with_mode(&term, EncodeMode::Legacy).unwrap();
+    assert_eq!(encoded[1], PID_EXT);
+    assert_eq!(decode(&encoded).unwrap(), pid(1));
+}
+
+#[test]
+fn test_legacy_mode_with_dist_header() {
+    let mut cache = OutgoingAtomCache::new();
+    let control = OwnedTerm::Tuple(vec![OwnedTerm::Integer(2), OwnedTerm::atom(""), pid(1)]);
+    let message = reference(2);
+    let encoded = encode_with_dist_header_cached_and_mode(
+        &[&control, &message],
+        &mut cache,
+        EncodeMode::Legacy,
+    )
+    .unwrap();
+
+    let mut incoming = AtomCache::new();
+    let (decoded_control, decoded_message) =
+        decode_with_atom_cache(&encoded, &mut incoming).unwrap();
+    assert_eq!(decoded_control, control);
+    assert_eq!(decoded_message, Some(message));
+    assert!(encoded.contains(&PID_EXT));
+}
+
+proptest! {
+    #[test]
+    fn prop_legacy_pid_roundtrip(id in any::<u32>(), serial in any::<u32>(), creation in any::<u8>()) {
+        let term = OwnedTerm::Pid(ExternalPid::new(Atom::new("c@host"), id, serial, creation as u32));
+        let encoded = encode_with_mode(&term, EncodeMode::Legacy).unwrap();
+        prop_assert_eq!(encoded[1], PID_EXT);
+        prop_assert_eq!(decode(&encoded).unwrap(), term);
+    }
+}
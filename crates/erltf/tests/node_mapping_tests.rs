@@ -0,0 +1,171 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::types::InternalFun;
+use erltf::{
+    Atom, ExternalPid, ExternalPort, ExternalReference, NodeMapping, NodeMappingError, OwnedTerm,
+    erl_atom, erl_int, erl_list, erl_map, erl_tuple,
+};
+
+const CLUSTER_A: &str = "rabbit@cluster-a";
+const GATEWAY: &str = "gateway_a@gateway";
+
+fn mapping() -> NodeMapping {
+    let mut mapping = NodeMapping::new();
+    mapping.insert(CLUSTER_A, GATEWAY).unwrap();
+    mapping
+}
+
+fn pid(node: &str, id: u32) -> ExternalPid {
+    ExternalPid::new(Atom::new(node), id, 0, 3)
+}
+
+#[test]
+fn test_insert_and_lookup() {
+    let mapping = mapping();
+    assert_eq!(mapping.len(), 1);
+    assert!(!mapping.is_empty());
+    assert_eq!(
+        mapping.get(&Atom::new(CLUSTER_A)),
+        Some(&Atom::new(GATEWAY))
+    );
+    assert_eq!(
+        mapping.get_reverse(&Atom::new(GATEWAY)),
+        Some(&Atom::new(CLUSTER_A))
+    );
+    assert_eq!(mapping.get(&Atom::new(GATEWAY)), None);
+}
+
+#[test]
+fn test_insert_rejects_conflicting_mappings() {
+    let mut mapping = mapping();
+    assert!(mapping.insert(CLUSTER_A, GATEWAY).is_ok());
+
+    assert_eq!(
+        mapping.insert(CLUSTER_A, "other@gateway"),
+        Err(NodeMappingError::SourceAlreadyMapped {
+            node: Atom::new(CLUSTER_A),
+            existing: Atom::new(GATEWAY),
+        })
+    );
+    assert_eq!(
+        mapping.insert("other@cluster-a", GATEWAY),
+        Err(NodeMappingError::TargetAlreadyMapped {
+            node: Atom::new(GATEWAY),
+            existing: Atom::new(CLUSTER_A),
+        })
+    );
+}
+
+#[test]
+fn test_remove() {
+    let mut mapping = mapping();
+    assert_eq!(
+        mapping.remove(&Atom::new(CLUSTER_A)),
+        Some(Atom::new(GATEWAY))
+    );
+    assert!(mapping.is_empty());
+    assert_eq!(mapping.get_reverse(&Atom::new(GATEWAY)), None);
+    assert!(mapping.insert("other@cluster-a", GATEWAY).is_ok());
+}
+
+#[test]
+fn test_rewrite_and_restore_nested_identifiers() {
+    let mapping = mapping();
+    let original = erl_tuple!(
+        erl_atom!("$gen_call"),
+        erl_tuple!(
+            OwnedTerm::Pid(pid(CLUSTER_A, 10)),
+            OwnedTerm::Reference(ExternalReference::new(
+                Atom::new(CLUSTER_A),
+                3,
+                vec![1, 2, 3]
+            ))
+        ),
+        erl_map! {
+            OwnedTerm::Pid(pid(CLUSTER_A, 11)) => erl_list![
+                OwnedTerm::Port(ExternalPort::new(Atom::new(CLUSTER_A), 7, 3)),
+                erl_int!(1)
+            ]
+        }
+    );
+
+    let rewritten = mapping.rewrite(original.clone());
+    let expected = erl_tuple!(
+        erl_atom!("$gen_call"),
+        erl_tuple!(
+            OwnedTerm::Pid(pid(GATEWAY, 10)),
+            OwnedTerm::Reference(ExternalReference::new(Atom::new(GATEWAY), 3, vec![1, 2, 3]))
+        ),
+        erl_map! {
+            OwnedTerm::Pid(pid(GATEWAY, 11)) => erl_list![
+                OwnedTerm::Port(ExternalPort::new(Atom::new(GATEWAY), 7, 3)),
+                erl_int!(1)
+            ]
+        }
+    );
+    assert_eq!(rewritten, expected);
+    assert_eq!(mapping.restore(rewritten), original);
+}
+
+#[test]
+fn test_rewrite_leaves_unmapped_nodes_and_atoms() {
+    let mapping = mapping();
+    let term = erl_list![
+        OwnedTerm::Pid(pid("elsewhere@host", 1)),
+        erl_atom!(CLUSTER_A)
+    ];
+    assert_eq!(mapping.rewrite(term.clone()), term);
+}
+
+#[test]
+fn test_rewrite_improper_lists_and_funs() {
+    let mapping = mapping();
+    let fun = InternalFun {
+        arity: 0,
+        uniq: [0; 16],
+        index: 0,
+        num_free: 1,
+        module: Atom::new("m"),
+        old_index: 0,
+        old_uniq: 0,
+        pid: pid(CLUSTER_A, 1),
+        free_vars: vec![OwnedTerm::Pid(pid(CLUSTER_A, 2))],
+    };
+    let term = OwnedTerm::ImproperList {
+        elements: vec![OwnedTerm::InternalFun(Box::new(fun))],
+        tail: Box::new(OwnedTerm::Pid(pid(CLUSTER_A, 3))),
+    };
+
+    let OwnedTerm::ImproperList { elements, tail } = mapping.rewrite(term) else {
+        panic!("expected an improper list");
+    };
+    let OwnedTerm::InternalFun(fun) = &elements[0] else {
+        panic!("expected a fun");
+    };
+    assert_eq!(fun.pid, pid(GATEWAY, 1));
+    assert_eq!(fun.free_vars, vec![OwnedTerm::Pid(pid(GATEWAY, 2))]);
+    assert_eq!(*tail, OwnedTerm::Pid(pid(GATEWAY, 3)));
+}
+
+#[test]
+fn test_pid_helpers_and_inverse() {
+    let mapping = mapping();
+    assert_eq!(mapping.rewrite_pid(&pid(CLUSTER_A, 5)), pid(GATEWAY, 5));
+    assert_eq!(mapping.restore_pid(&pid(GATEWAY, 5)), pid(CLUSTER_A, 5));
+    assert_eq!(mapping.rewrite_pid(&pid("x@y", 5)), pid("x@y", 5));
+
+    let inverse = mapping.inverse();
+    assert_eq!(inverse.rewrite_pid(&pid(GATEWAY, 5)), pid(CLUSTER_A, 5));
+}
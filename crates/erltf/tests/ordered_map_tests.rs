@@ -0,0 +1,180 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::decoder::decode_with_config;
+use erltf::walk::Transform;
+use erltf::{
+    AtomTable, DecodeConfig, OwnedTerm, canonical_encode, decode, encode, erl_atom, erl_int,
+    erl_map,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+const MAP_EXT: u8 = 116;
+const SMALL_ATOM_UTF8_EXT: u8 = 119;
+const SMALL_INTEGER_EXT: u8 = 97;
+
+/// A map with its keys in the given order, as another encoder may emit it.
+fn map_bytes(entries: &[(&str, u8)]) -> Vec<u8> {
+    let mut bytes = vec![131, MAP_EXT];
+    bytes.extend((entries.len() as u32).to_be_bytes());
+    for (key, value) in entries {
+        bytes.extend([SMALL_ATOM_UTF8_EXT, key.len() as u8]);
+        bytes.extend(key.as_bytes());
+        bytes.extend([SMALL_INTEGER_EXT, *value]);
+    }
+    bytes
+}
+
+fn hash_of(term: &OwnedTerm) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    term.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn decode_ordered(bytes: &[u8]) -> OwnedTerm {
+    let config = DecodeConfig::new().with_ordered_maps(true);
+    decode_with_config(bytes, &config, &mut AtomTable::new()).unwrap()
+}
+
+#[test]
+fn test_ordered_maps_keep_wire_order() {
+    let bytes = map_bytes(&[("zeta", 1), ("alpha", 2), ("mu", 3)]);
+
+    let term = decode_ordered(&bytes);
+
+    assert_eq!(
+        term.as_ordered_map().unwrap(),
+        [
+            (erl_atom!("zeta"), erl_int!(1)),
+            (erl_atom!("alpha"), erl_int!(2)),
+            (erl_atom!("mu"), erl_int!(3)),
+        ]
+    );
+    assert!(term.is_map());
+    assert_eq!(term.type_name(), "Map");
+}
+
+#[test]
+fn test_ordered_maps_round_trip_byte_for_byte() {
+    let bytes = map_bytes(&[("zeta", 1), ("alpha", 2), ("mu", 3)]);
+
+    assert_eq!(encode(&decode_ordered(&bytes)).unwrap(), bytes);
+    assert_ne!(encode(&decode(&bytes).unwrap()).unwrap(), bytes);
+}
+
+#[test]
+fn test_maps_are_unordered_by_default() {
+    let bytes = map_bytes(&[("b", 1), ("a", 2)]);
+
+    let term = decode(&bytes).unwrap();
+
+    assert_eq!(
+        term,
+        erl_map! { erl_atom!("a") => erl_int!(2), erl_atom!("b") => erl_int!(1) }
+    );
+    assert!(term.as_ordered_map().is_none());
+}
+
+#[test]
+fn test_ordered_maps_keep_duplicate_keys() {
+    let bytes = map_bytes(&[("k", 1), ("k", 2)]);
+
+    let term = decode_ordered(&bytes);
+
+    assert_eq!(term.as_ordered_map().unwrap().len(), 2);
+    assert_eq!(
+        term.into_unordered_map(),
+        erl_map! { erl_atom!("k") => erl_int!(2) }
+    );
+}
+
+#[test]
+fn test_ordered_map_compares_like_a_map() {
+    let ordered = OwnedTerm::ordered_map(vec![
+        (erl_atom!("b"), erl_int!(1)),
+        (erl_atom!("a"), erl_int!(2)),
+    ]);
+    let unordered = ordered.clone().into_unordered_map();
+
+    assert_eq!(ordered.cmp(&unordered), std::cmp::Ordering::Equal);
+    assert!(ordered < OwnedTerm::Nil);
+    assert!(ordered > OwnedTerm::tuple(vec![]));
+}
+
+#[test]
+fn test_ordered_map_equals_a_map_with_the_same_entries() {
+    let ordered = OwnedTerm::ordered_map(vec![
+        (erl_atom!("b"), erl_int!(1)),
+        (erl_atom!("a"), erl_int!(2)),
+    ]);
+    let reversed = OwnedTerm::ordered_map(vec![
+        (erl_atom!("a"), erl_int!(2)),
+        (erl_atom!("b"), erl_int!(1)),
+    ]);
+    let unordered = erl_map! { erl_atom!("a") => erl_int!(2), erl_atom!("b") => erl_int!(1) };
+
+    assert_eq!(ordered, unordered);
+    assert_eq!(unordered, ordered);
+    assert_eq!(ordered, reversed);
+    assert_eq!(hash_of(&ordered), hash_of(&unordered));
+    assert_eq!(hash_of(&ordered), hash_of(&reversed));
+    assert_ne!(ordered, erl_map! { erl_atom!("a") => erl_int!(2) });
+}
+
+#[test]
+fn test_ordered_map_with_duplicate_keys_equals_the_last_entry() {
+    let ordered = OwnedTerm::ordered_map(vec![
+        (erl_atom!("k"), erl_int!(1)),
+        (erl_atom!("k"), erl_int!(2)),
+    ]);
+    let unordered = erl_map! { erl_atom!("k") => erl_int!(2) };
+
+    assert_eq!(ordered, unordered);
+    assert_eq!(hash_of(&ordered), hash_of(&unordered));
+    assert_eq!(ordered.cmp(&unordered), std::cmp::Ordering::Equal);
+}
+
+#[test]
+fn test_ordered_map_display_keeps_order() {
+    let term = decode_ordered(&map_bytes(&[("b", 1), ("a", 2)]));
+
+    assert_eq!(term.to_string(), "#{b => 1, a => 2}");
+}
+
+#[test]
+fn test_canonical_encoding_sorts_ordered_maps() {
+    let ordered = decode_ordered(&map_bytes(&[("b", 1), ("a", 2)]));
+    let unordered = ordered.clone().into_unordered_map();
+
+    assert_eq!(
+        canonical_encode(&ordered).unwrap(),
+        canonical_encode(&unordered).unwrap()
+    );
+}
+
+#[test]
+fn test_transform_preserves_ordered_maps() {
+    let term = decode_ordered(&map_bytes(&[("b", 1), ("a", 2)]));
+
+    let doubled = term.transform(|t| match t {
+        OwnedTerm::Integer(i) => Transform::Replace(OwnedTerm::Integer(i * 2)),
+        other => Transform::Descend(other),
+    });
+
+    assert_eq!(
+        doubled.as_ordered_map().unwrap(),
+        [(erl_atom!("b"), erl_int!(2)), (erl_atom!("a"), erl_int!(4))]
+    );
+}
@@ -0,0 +1,329 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::decoder::AtomCache;
+use erltf::tags::{DIST_HEADER, VERSION};
+use erltf::{
+    Atom, EncodeError, OutgoingAtomCache, OwnedTerm, decode_with_atom_cache,
+    encode_with_dist_header, encode_with_dist_header_cached, erl_atom, erl_int, erl_tuple,
+    validate,
+};
+use proptest::prelude::*;
+
+fn atom_term(name: &str) -> OwnedTerm {
+    OwnedTerm::Atom(Atom::new(name))
+}
+
+fn roundtrip(
+    terms: &[&OwnedTerm],
+    outgoing: &mut OutgoingAtomCache,
+    incoming: &mut AtomCache,
+) -> Vec<u8> {
+    let encoded = encode_with_dist_header_cached(terms, outgoing).unwrap();
+    let (control, payload) = decode_with_atom_cache(&encoded, incoming).unwrap();
+    assert_eq!(&control, terms[0]);
+    assert_eq!(payload.as_ref(), terms.get(1).copied());
+    encoded
+}
+
+#[test]
+fn test_repeated_atoms_are_sent_as_cache_refs() {
+    let mut outgoing = OutgoingAtomCache::new();
+    let mut incoming = AtomCache::new();
+    let control = erl_tuple![
+        erl_int!(2),
+        erl_atom!(""),
+        erl_atom!("a_rather_long_atom_name")
+    ];
+    let payload = erl_tuple![erl_atom!("hello"), erl_atom!("world")];
+
+    let first = roundtrip(&[&control, &payload], &mut outgoing, &mut incoming);
+    let second = roundtrip(&[&control, &payload], &mut outgoing, &mut incoming);
+
+    assert_eq!(outgoing.len(), 4);
+    assert_eq!(incoming.entries_len(), 4);
+    assert!(second.len() < first.len());
+    assert!(
+        !second
+            .windows(b"a_rather_long_atom_name".len())
+            .any(|w| w == b"a_rather_long_atom_name")
+    );
+
+    // A peer without the entries cannot decode the second frame
+    assert!(decode_with_atom_cache(&second, &mut AtomCache::new()).is_err());
+}
+
+#[test]
+fn test_seeded_atoms_are_announced_in_the_first_frame() {
+    let mut outgoing = OutgoingAtomCache::new();
+    let mut incoming = AtomCache::new();
+    outgoing.seed([Atom::new("warm"), Atom::new("warmer")]);
+    assert_eq!(outgoing.pending_len(), 2);
+
+    let control = erl_tuple![erl_int!(6), erl_atom!("cold")];
+    let first = roundtrip(&[&control], &mut outgoing, &mut incoming);
+    assert_eq!(&first[..3], &[VERSION, DIST_HEADER, 3]);
+    assert_eq!(outgoing.pending_len(), 0);
+    assert!(outgoing.contains("warm") && outgoing.contains("warmer"));
+    assert_eq!(incoming.entries_len(), 3);
+
+    let control = erl_tuple![erl_int!(6), erl_atom!("warmer")];
+    let second = roundtrip(&[&control], &mut outgoing, &mut incoming);
+    assert!(!second.windows(6).any(|w| w == b"warmer"));
+}
+
+#[test]
+fn test_seeding_known_atoms_is_a_no_op() {
+    let mut outgoing = OutgoingAtomCache::new();
+    let control = erl_tuple![erl_atom!("known")];
+    encode_with_dist_header_cached(&[&control], &mut outgoing).unwrap();
+
+    outgoing.seed([Atom::new("known"), Atom::new("new"), Atom::new("new")]);
+    assert_eq!(outgoing.pending_len(), 1);
+}
+
+#[test]
+fn test_seeds_without_atoms_in_terms() {
+    let mut outgoing = OutgoingAtomCache::new();
+    let mut incoming = AtomCache::new();
+    let control = erl_tuple![erl_int!(1), erl_int!(2)];
+
+    let plain = roundtrip(&[&control], &mut outgoing, &mut incoming);
+    assert_ne!(plain[1], DIST_HEADER);
+
+    outgoing.seed([Atom::new("warm")]);
+    let seeded = roundtrip(&[&control], &mut outgoing, &mut incoming);
+    assert_eq!(&seeded[..3], &[VERSION, DIST_HEADER, 1]);
+    assert_eq!(incoming.entries_len(), 1);
+}
+
+#[test]
+fn test_hottest_atoms_and_reset() {
+    let mut outgoing = OutgoingAtomCache::new();
+    for _ in 0..3 {
+        encode_with_dist_header_cached(&[&erl_tuple![erl_atom!("hot")]], &mut outgoing).unwrap();
+    }
+    encode_with_dist_header_cached(
+        &[&erl_tuple![erl_atom!("warm"), erl_atom!("hot")]],
+        &mut outgoing,
+    )
+    .unwrap();
+    encode_with_dist_header_cached(
+        &[&erl_tuple![erl_atom!("warm"), erl_atom!("cold")]],
+        &mut outgoing,
+    )
+    .unwrap();
+
+    assert_eq!(
+        outgoing.hottest(2),
+        vec![Atom::new("hot"), Atom::new("warm")]
+    );
+    assert_eq!(outgoing.hottest(10).len(), 3);
+
+    outgoing.reset();
+    assert!(outgoing.is_empty());
+    assert_eq!(outgoing.hottest(1), vec![Atom::new("hot")]);
+
+    // After a reset, atoms are announced again
+    let mut incoming = AtomCache::new();
+    roundtrip(
+        &[&erl_tuple![erl_atom!("hot")]],
+        &mut outgoing,
+        &mut incoming,
+    );
+    assert_eq!(incoming.entries_len(), 1);
+
+    outgoing.clear();
+    assert!(outgoing.hottest(10).is_empty());
+}
+
+#[test]
+fn test_cache_indices_beyond_first_segment_and_eviction() {
+    let mut outgoing = OutgoingAtomCache::new();
+    let mut incoming = AtomCache::new();
+
+    // 12 frames of 200 distinct atoms use more than the 2048 cache entries
+    for frame in 0..12 {
+        let atoms: Vec<OwnedTerm> = (0..200)
+            .map(|i| atom_term(&format!("atom_{}_{}", frame, i)))
+            .collect();
+        let control = OwnedTerm::Tuple(atoms);
+        roundtrip(&[&control], &mut outgoing, &mut incoming);
+    }
+    assert_eq!(outgoing.len(), 2048);
+    assert!(!outgoing.contains("atom_0_0"));
+    assert!(outgoing.cache_index("atom_11_199").is_some());
+
+    // Evicted atoms are announced again, recent ones are references
+    let control = erl_tuple![erl_atom!("atom_0_0"), erl_atom!("atom_11_199")];
+    roundtrip(&[&control], &mut outgoing, &mut incoming);
+    assert!(outgoing.contains("atom_0_0"));
+}
+
+#[test]
+fn test_long_atoms_flag_follows_last_ref() {
+    let long = "ö".repeat(150);
+    let encoded = encode_with_dist_header(&atom_term(&long)).unwrap();
+    // One ref: its flags in the low half byte, LongAtoms in the high half byte
+    assert_eq!(&encoded[..4], &[VERSION, DIST_HEADER, 1, 0x18]);
+
+    let mut cache = AtomCache::new();
+    let (decoded, _) = decode_with_atom_cache(&encoded, &mut cache).unwrap();
+    assert_eq!(decoded, atom_term(&long));
+
+    let control = erl_tuple![erl_atom!("short"), atom_term(&long)];
+    let encoded = encode_with_dist_header(&control).unwrap();
+    assert_eq!(encoded[4] & 0x01, 0x01);
+    let (decoded, _) = decode_with_atom_cache(&encoded, &mut AtomCache::new()).unwrap();
+    assert_eq!(decoded, control);
+}
+
+#[test]
+fn test_long_atoms_with_cache_refs() {
+    let mut outgoing = OutgoingAtomCache::new();
+    let mut incoming = AtomCache::new();
+    let long = atom_term(&"ÿ".repeat(128));
+
+    roundtrip(&[&erl_tuple![erl_atom!("a")]], &mut outgoing, &mut incoming);
+    roundtrip(
+        &[&erl_tuple![erl_atom!("a"), long.clone()]],
+        &mut outgoing,
+        &mut incoming,
+    );
+    roundtrip(
+        &[&erl_tuple![erl_atom!("a"), long, erl_atom!("b")]],
+        &mut outgoing,
+        &mut incoming,
+    );
+}
+
+#[test]
+fn test_long_atoms_flag_is_set_past_255_bytes() {
+    let at_limit = atom_term(&"z".repeat(255));
+    let encoded = encode_with_dist_header(&at_limit).unwrap();
+    assert_eq!(&encoded[..4], &[VERSION, DIST_HEADER, 1, 0x08]);
+    assert_eq!(encoded[5], 255);
+
+    let past_limit = atom_term(&"é".repeat(128));
+    let encoded = encode_with_dist_header(&past_limit).unwrap();
+    assert_eq!(&encoded[..4], &[VERSION, DIST_HEADER, 1, 0x18]);
+    assert_eq!(&encoded[5..7], &[0x01, 0x00]);
+}
+
+#[test]
+fn test_multibyte_atoms_use_their_byte_length() {
+    // 255 characters, the most an atom can have, take 510 bytes
+    let name = "ö".repeat(255);
+    let control = erl_tuple![erl_int!(6), erl_atom!(""), atom_term(&name)];
+    let payload = erl_tuple![atom_term(&name), erl_atom!("ok")];
+
+    let encoded =
+        encode_with_dist_header_cached(&[&control, &payload], &mut OutgoingAtomCache::new())
+            .unwrap();
+    let refs = encoded[2] as usize;
+    assert_eq!(encoded[3 + refs / 2] >> (4 * (refs % 2)) & 0x01, 0x01);
+    let text = name.as_bytes();
+    assert!(
+        encoded
+            .windows(2 + text.len())
+            .any(|w| w[..2] == (text.len() as u16).to_be_bytes() && &w[2..] == text)
+    );
+
+    let summary = validate(&encoded).unwrap();
+    assert_eq!(summary.top_level_terms, 2);
+    let mut outgoing = OutgoingAtomCache::new();
+    let mut incoming = AtomCache::new();
+    roundtrip(&[&control, &payload], &mut outgoing, &mut incoming);
+    // The second frame refers to the entries of the first one
+    let second = roundtrip(&[&control, &payload], &mut outgoing, &mut incoming);
+    assert!(second.len() < text.len());
+}
+
+#[test]
+fn test_atoms_over_the_character_limit_are_rejected() {
+    let mut outgoing = OutgoingAtomCache::new();
+    let control = erl_tuple![erl_atom!("fine"), atom_term(&"a".repeat(256))];
+
+    let result = encode_with_dist_header_cached(&[&control], &mut outgoing);
+    assert!(matches!(
+        result,
+        Err(EncodeError::AtomTooLong {
+            length: 256,
+            max: 255
+        })
+    ));
+    assert!(outgoing.is_empty());
+}
+
+#[test]
+fn test_atoms_beyond_the_long_length_are_rejected() {
+    let mut outgoing = OutgoingAtomCache::new();
+    let control = erl_tuple![erl_atom!("fine"), atom_term(&"a".repeat(70_000))];
+
+    let result = encode_with_dist_header_cached(&[&control], &mut outgoing);
+    assert!(matches!(
+        result,
+        Err(EncodeError::AtomTooLarge { size: 70_000 })
+    ));
+    // Nothing was announced, so the cache still matches the peer's
+    assert!(outgoing.is_empty());
+    assert!(matches!(
+        encode_with_dist_header(&control),
+        Err(EncodeError::AtomTooLarge { .. })
+    ));
+}
+
+#[test]
+fn test_shrink_to_fit_keeps_cache_entries() {
+    let mut outgoing = OutgoingAtomCache::new();
+    let mut incoming = AtomCache::new();
+    let control = erl_tuple![erl_int!(2), erl_atom!(""), erl_atom!("shrunk")];
+    let payload = erl_tuple![erl_atom!("hello"), erl_atom!("world")];
+    let first = roundtrip(&[&control, &payload], &mut outgoing, &mut incoming);
+
+    outgoing.shrink_to_fit();
+    incoming.shrink_to_fit();
+    assert_eq!(outgoing.len(), 4);
+    assert_eq!(incoming.entries_len(), 4);
+    assert!(incoming.is_empty());
+    let second = roundtrip(&[&control, &payload], &mut outgoing, &mut incoming);
+    assert!(second.len() < first.len());
+
+    // The peer overwrites its entries when they are announced again
+    outgoing.reset();
+    outgoing.shrink_to_fit();
+    assert!(outgoing.is_empty());
+    let third = roundtrip(&[&control, &payload], &mut outgoing, &mut incoming);
+    assert_eq!(third.len(), first.len());
+}
+
+proptest! {
+    #[test]
+    fn prop_frames_roundtrip_through_shared_caches(
+        frames in prop::collection::vec(prop::collection::vec(0usize..400, 1..40), 1..30),
+        seeds in prop::collection::vec(0usize..400, 0..20),
+    ) {
+        let mut outgoing = OutgoingAtomCache::new();
+        let mut incoming = AtomCache::new();
+        outgoing.seed(seeds.iter().map(|i| Atom::new(format!("seed_{}", i))));
+        for frame in frames {
+            let control = OwnedTerm::Tuple(
+                frame.iter().map(|i| atom_term(&format!("atom_{}", i))).collect(),
+            );
+            let encoded = encode_with_dist_header_cached(&[&control], &mut outgoing).unwrap();
+            let (decoded, _) = decode_with_atom_cache(&encoded, &mut incoming).unwrap();
+            prop_assert_eq!(decoded, control);
+        }
+    }
+}
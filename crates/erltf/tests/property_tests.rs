@@ -133,7 +133,9 @@ fn arb_simple_term() -> impl Strategy<Value = OwnedTerm> {
     prop_oneof![
         any::<u8>().prop_map(|v| OwnedTerm::Integer(v as i64)),
         any::<i32>().prop_map(|v| OwnedTerm::Integer(v as i64)),
-        any::<f64>().prop_map(OwnedTerm::Float),
+        any::<f64>()
+            .prop_filter("finite floats only", |f| f.is_finite())
+            .prop_map(OwnedTerm::Float),
         arb_atom().prop_map(OwnedTerm::Atom),
         prop::collection::vec(any::<u8>(), 0..100).prop_map(OwnedTerm::Binary),
     ]
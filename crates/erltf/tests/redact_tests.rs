@@ -0,0 +1,169 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::{OwnedTerm, RedactionRules, erl_atom, erl_int, erl_list, erl_map, erl_tuple, redact};
+
+fn placeholder() -> OwnedTerm {
+    OwnedTerm::binary(b"[redacted]".to_vec())
+}
+
+fn bin(s: &str) -> OwnedTerm {
+    OwnedTerm::binary(s.as_bytes().to_vec())
+}
+
+#[test]
+fn test_redact_map_keys() {
+    let term = erl_map! {
+        erl_atom!("user") => bin("guest"),
+        bin("Password") => bin("s3kr3t"),
+    };
+    let rules = RedactionRules::new().with_key("password");
+
+    let expected = erl_map! {
+        erl_atom!("user") => bin("guest"),
+        bin("Password") => placeholder(),
+    };
+    assert_eq!(redact(&term, &rules), expected);
+}
+
+#[test]
+fn test_redact_proplist_entries() {
+    let term = erl_list![
+        erl_tuple!(erl_atom!("host"), bin("localhost")),
+        erl_tuple!(erl_atom!("token"), bin("abc123")),
+    ];
+    let rules = RedactionRules::credentials();
+
+    let expected = erl_list![
+        erl_tuple!(erl_atom!("host"), bin("localhost")),
+        erl_tuple!(erl_atom!("token"), placeholder()),
+    ];
+    assert_eq!(redact(&term, &rules), expected);
+}
+
+#[test]
+fn test_redact_nested_keys() {
+    let term = erl_tuple!(
+        erl_atom!("connect"),
+        erl_map! {
+            erl_atom!("auth") => erl_list![erl_tuple!(erl_atom!("secret"), erl_int!(42))],
+        }
+    );
+    let rules = RedactionRules::credentials();
+
+    let expected = erl_tuple!(
+        erl_atom!("connect"),
+        erl_map! {
+            erl_atom!("auth") => erl_list![erl_tuple!(erl_atom!("secret"), placeholder())],
+        }
+    );
+    assert_eq!(redact(&term, &rules), expected);
+}
+
+#[test]
+fn test_redact_oversized_binaries() {
+    let term = erl_list![bin("short"), OwnedTerm::binary(vec![0u8; 1024])];
+    let rules = RedactionRules::new().with_max_binary_len(16);
+
+    assert_eq!(
+        redact(&term, &rules),
+        erl_list![bin("short"), placeholder()]
+    );
+}
+
+#[test]
+fn test_redact_oversized_shared_binaries() {
+    let term = erl_list![OwnedTerm::shared_binary(vec![0u8; 1024])];
+    let rules = RedactionRules::new().with_max_binary_len(16);
+
+    assert_eq!(redact(&term, &rules), erl_list![placeholder()]);
+}
+
+#[test]
+fn test_redact_shared_binary_keys() {
+    let term = erl_map! {
+        OwnedTerm::shared_binary(b"password".to_vec()) => bin("hunter2"),
+    };
+    let rules = RedactionRules::new().with_key("password");
+
+    assert_eq!(
+        redact(&term, &rules),
+        erl_map! { bin("password") => placeholder() }
+    );
+}
+
+#[test]
+fn test_redact_path() {
+    let term = erl_map! {
+        erl_atom!("db") => erl_map! {
+            erl_atom!("user") => bin("app"),
+            erl_atom!("pass") => bin("hunter2"),
+        },
+        erl_atom!("pass") => bin("kept"),
+    };
+    let rules = RedactionRules::new().with_path(["db", "pass"]);
+
+    let expected = erl_map! {
+        erl_atom!("db") => erl_map! {
+            erl_atom!("user") => bin("app"),
+            erl_atom!("pass") => placeholder(),
+        },
+        erl_atom!("pass") => bin("kept"),
+    };
+    assert_eq!(redact(&term, &rules), expected);
+}
+
+#[test]
+fn test_redact_path_with_wildcard() {
+    let term = erl_map! {
+        erl_atom!("users") => erl_list![
+            erl_map! { erl_atom!("name") => bin("a"), erl_atom!("pin") => erl_int!(1234) },
+            erl_map! { erl_atom!("name") => bin("b"), erl_atom!("pin") => erl_int!(5678) },
+        ],
+    };
+    let rules = RedactionRules::new().with_path(["users", "*", "pin"]);
+
+    let expected = erl_map! {
+        erl_atom!("users") => erl_list![
+            erl_map! { erl_atom!("name") => bin("a"), erl_atom!("pin") => placeholder() },
+            erl_map! { erl_atom!("name") => bin("b"), erl_atom!("pin") => placeholder() },
+        ],
+    };
+    assert_eq!(redact(&term, &rules), expected);
+}
+
+#[test]
+fn test_redact_custom_placeholder() {
+    let term = erl_map! { erl_atom!("password") => bin("x") };
+    let rules = RedactionRules::credentials().with_placeholder(erl_atom!("hidden"));
+
+    assert_eq!(
+        redact(&term, &rules),
+        erl_map! { erl_atom!("password") => erl_atom!("hidden") }
+    );
+}
+
+#[test]
+fn test_redact_leaves_original_untouched() {
+    let term = erl_map! { erl_atom!("password") => bin("x") };
+    let _ = redact(&term, &RedactionRules::credentials());
+
+    assert_eq!(term, erl_map! { erl_atom!("password") => bin("x") });
+}
+
+#[test]
+fn test_redact_without_rules_is_identity() {
+    let term = erl_tuple!(erl_atom!("password"), bin("x"));
+    assert_eq!(redact(&term, &RedactionRules::new()), term);
+}
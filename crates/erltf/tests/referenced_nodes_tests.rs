@@ -0,0 +1,96 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::types::{Atom, ExternalPid, ExternalPort, ExternalReference, InternalFun};
+use erltf::{OwnedTerm, erl_atom, erl_int, erl_list, erl_map, erl_tuple};
+use std::collections::BTreeSet;
+
+fn pid(node: &str, creation: u32) -> ExternalPid {
+    ExternalPid::new(Atom::new(node), 42, 0, creation)
+}
+
+fn nodes(names: &[&str]) -> BTreeSet<Atom> {
+    names.iter().map(Atom::new).collect()
+}
+
+#[test]
+fn test_pid_belongs_to_its_node() {
+    let pid = pid("a@host", 3);
+    assert!(pid.belongs_to("a@host"));
+    assert!(!pid.belongs_to("b@host"));
+}
+
+#[test]
+fn test_pid_is_local_only_to_the_same_incarnation() {
+    let pid = pid("a@host", 3);
+    assert!(pid.is_local("a@host", 3));
+    assert!(!pid.is_local("a@host", 4));
+    assert!(!pid.is_local("b@host", 3));
+}
+
+#[test]
+fn test_port_and_reference_helpers() {
+    let port = ExternalPort::new(Atom::new("a@host"), 7, 3);
+    assert!(port.belongs_to("a@host"));
+    assert!(port.is_local("a@host", 3));
+    assert!(!port.is_local("a@host", 1));
+
+    let reference = ExternalReference::new(Atom::new("a@host"), 3, vec![1, 2, 3]);
+    assert!(reference.belongs_to("a@host"));
+    assert!(!reference.belongs_to("b@host"));
+    assert!(reference.is_local("a@host", 3));
+    assert!(!reference.is_local("a@host", 2));
+}
+
+#[test]
+fn test_referenced_nodes_of_terms_without_identifiers() {
+    let term = erl_tuple![erl_atom!("ok"), erl_list![erl_int!(1), erl_int!(2)]];
+    assert!(term.referenced_nodes().is_empty());
+}
+
+#[test]
+fn test_referenced_nodes_collects_nested_pids_ports_and_references() {
+    let term = erl_map! {
+        erl_atom!("owner") => OwnedTerm::Pid(pid("a@host", 1)),
+        erl_atom!("peers") => erl_list![
+            OwnedTerm::Pid(pid("b@host", 1)),
+            erl_tuple![OwnedTerm::Pid(pid("a@host", 2))]
+        ],
+        erl_atom!("port") => OwnedTerm::Port(ExternalPort::new(Atom::new("c@host"), 1, 1)),
+        OwnedTerm::Reference(ExternalReference::new(Atom::new("d@host"), 1, vec![1])) => erl_atom!("ref"),
+    };
+
+    assert_eq!(
+        term.referenced_nodes(),
+        nodes(&["a@host", "b@host", "c@host", "d@host"])
+    );
+}
+
+#[test]
+fn test_referenced_nodes_includes_fun_pids_and_free_variables() {
+    let fun = InternalFun::new(
+        0,
+        [0; 16],
+        0,
+        1,
+        Atom::new("m"),
+        0,
+        0,
+        pid("a@host", 1),
+        vec![OwnedTerm::Pid(pid("b@host", 1))],
+    );
+    let term = OwnedTerm::InternalFun(Box::new(fun));
+
+    assert_eq!(term.referenced_nodes(), nodes(&["a@host", "b@host"]));
+}
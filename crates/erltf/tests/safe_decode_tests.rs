@@ -0,0 +1,93 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::types::{ExternalFun, InternalFun};
+use erltf::{
+    Atom, AtomTable, DecodeConfig, DecodeError, ExternalPid, OwnedTerm, decode_safe,
+    decode_with_config, encode, erl_atom, erl_int, erl_list, erl_tuple,
+};
+
+fn external_fun() -> OwnedTerm {
+    OwnedTerm::ExternalFun(ExternalFun::new(Atom::new("erlang"), Atom::new("halt"), 0))
+}
+
+fn internal_fun() -> OwnedTerm {
+    OwnedTerm::InternalFun(Box::new(InternalFun::new(
+        0,
+        [0; 16],
+        1,
+        0,
+        Atom::new("my_module"),
+        1,
+        1,
+        ExternalPid::new(Atom::new("node@host"), 1, 0, 1),
+        vec![],
+    )))
+}
+
+#[test]
+fn test_decode_safe_accepts_plain_terms() {
+    let term = erl_tuple![erl_atom!("ok"), erl_list![erl_int!(1), erl_int!(2)]];
+    let encoded = encode(&term).unwrap();
+
+    assert_eq!(decode_safe(&encoded).unwrap(), term);
+}
+
+#[test]
+fn test_decode_safe_rejects_external_fun() {
+    let encoded = encode(&external_fun()).unwrap();
+
+    assert!(matches!(
+        decode_safe(&encoded),
+        Err(DecodeError::UnsafeTerm(_))
+    ));
+}
+
+#[test]
+fn test_decode_safe_rejects_nested_internal_fun() {
+    let encoded = encode(&erl_tuple![erl_atom!("callback"), internal_fun()]).unwrap();
+
+    assert!(matches!(
+        decode_safe(&encoded),
+        Err(DecodeError::UnsafeTerm(_))
+    ));
+}
+
+#[test]
+fn test_safe_config_combines_with_atom_limit() {
+    let config = DecodeConfig::new().with_safe(true).atoms_error_after(1);
+    let mut atoms = AtomTable::new();
+
+    let encoded = encode(&erl_list![erl_atom!("a"), erl_atom!("b")]).unwrap();
+    assert_eq!(
+        decode_with_config(&encoded, &config, &mut atoms),
+        Err(DecodeError::AtomLimitExceeded { limit: 1 })
+    );
+
+    let encoded = encode(&external_fun()).unwrap();
+    assert!(matches!(
+        decode_with_config(&encoded, &config, &mut atoms),
+        Err(DecodeError::UnsafeTerm(_))
+    ));
+}
+
+#[test]
+fn test_default_config_accepts_funs() {
+    let config = DecodeConfig::new();
+    assert!(!config.is_safe());
+
+    let encoded = encode(&external_fun()).unwrap();
+    let decoded = decode_with_config(&encoded, &config, &mut AtomTable::new()).unwrap();
+    assert_eq!(decoded, external_fun());
+}
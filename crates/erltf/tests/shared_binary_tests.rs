@@ -0,0 +1,192 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Bytes;
+use erltf::decoder::{
+    AtomCache, decode_bytes_with_trailing_and_config,
+    decode_payload_bytes_with_atom_cache_and_config,
+};
+use erltf::{
+    AtomTable, DecodeConfig, OwnedTerm, decode, decode_bytes_with_config, decode_with_config,
+    encode, erl_int, erl_tuple,
+};
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+fn shared_config(min_size: usize) -> DecodeConfig {
+    DecodeConfig::new().with_shared_binaries(min_size)
+}
+
+fn is_slice_of(shared: &Bytes, buffer: &Bytes) -> bool {
+    buffer.as_ptr_range().contains(&shared.as_ptr())
+}
+
+fn hash_of(term: &OwnedTerm) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    term.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_shared_binaries_option() {
+    assert_eq!(DecodeConfig::new().shared_binaries(), None);
+    assert_eq!(shared_config(64).shared_binaries(), Some(64));
+}
+
+#[test]
+fn test_large_binaries_decode_as_slices_of_the_input() {
+    let term = erl_tuple![
+        OwnedTerm::binary(vec![9; 2048]),
+        OwnedTerm::binary(b"small".to_vec()),
+        erl_int!(1)
+    ];
+    let buffer = Bytes::from(encode(&term).unwrap());
+
+    let decoded =
+        decode_bytes_with_config(&buffer, &shared_config(1024), &mut AtomTable::new()).unwrap();
+
+    let shared = decoded[0].as_shared_binary().unwrap();
+    assert!(is_slice_of(shared, &buffer));
+    assert_eq!(shared.as_ref(), &[9; 2048][..]);
+    assert_eq!(decoded[1], OwnedTerm::binary(b"small".to_vec()));
+    assert_eq!(decoded.clone().into_owned_binary(), decoded);
+    assert_eq!(decoded[0].clone().into_owned_binary(), term[0]);
+}
+
+#[test]
+fn test_binaries_are_copied_without_the_option() {
+    let term = OwnedTerm::binary(vec![1; 4096]);
+    let buffer = Bytes::from(encode(&term).unwrap());
+
+    let decoded =
+        decode_bytes_with_config(&buffer, &DecodeConfig::new(), &mut AtomTable::new()).unwrap();
+    assert_eq!(decoded, term);
+
+    let from_slice = decode_with_config(&buffer, &shared_config(0), &mut AtomTable::new()).unwrap();
+    assert_eq!(from_slice, term);
+}
+
+#[test]
+fn test_binaries_of_compressed_terms_are_copied() {
+    let term = OwnedTerm::binary(vec![5; 4096]);
+    let encoded = encode(&term).unwrap();
+    let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+    zlib.write_all(&encoded[1..]).unwrap();
+    let mut compressed = vec![131, 80];
+    compressed.extend(((encoded.len() - 1) as u32).to_be_bytes());
+    compressed.extend(zlib.finish().unwrap());
+
+    let decoded = decode_bytes_with_config(
+        &Bytes::from(compressed),
+        &shared_config(0),
+        &mut AtomTable::new(),
+    )
+    .unwrap();
+    assert_eq!(decoded, term);
+}
+
+#[test]
+fn test_trailing_bytes_and_payloads_are_slices_too() {
+    let mut buffer = encode(&OwnedTerm::atom("control")).unwrap();
+    buffer.extend(encode(&OwnedTerm::binary(vec![3; 512])).unwrap());
+    let buffer = Bytes::from(buffer);
+    let config = shared_config(256);
+    let mut atoms = AtomTable::new();
+
+    let (control, rest) =
+        decode_bytes_with_trailing_and_config(&buffer, &config, &mut atoms).unwrap();
+    assert_eq!(control, OwnedTerm::atom("control"));
+    assert!(is_slice_of(&rest, &buffer));
+
+    let (payload, rest) =
+        decode_bytes_with_trailing_and_config(&rest, &config, &mut atoms).unwrap();
+    assert!(is_slice_of(payload.as_shared_binary().unwrap(), &buffer));
+    assert!(rest.is_empty());
+
+    let unversioned = buffer.slice(buffer.len() - 517..);
+    let payload = decode_payload_bytes_with_atom_cache_and_config(
+        &unversioned,
+        &AtomCache::new(),
+        &config,
+        &mut atoms,
+    )
+    .unwrap();
+    assert!(is_slice_of(payload.as_shared_binary().unwrap(), &buffer));
+}
+
+#[test]
+fn test_shared_binaries_behave_like_binaries() {
+    let owned = OwnedTerm::binary(b"hello".to_vec());
+    let shared = OwnedTerm::shared_binary(Bytes::from_static(b"hello"));
+
+    assert_eq!(shared.type_name(), "Binary");
+    assert_eq!(shared.as_binary(), Some(&b"hello"[..]));
+    assert_eq!(shared.len(), 5);
+    assert_eq!(shared.to_string(), owned.to_string());
+    assert_eq!(shared.inspect(), owned.inspect());
+    assert_eq!(encode(&shared).unwrap(), encode(&owned).unwrap());
+    assert_eq!(decode(&encode(&shared).unwrap()).unwrap(), owned);
+    assert_eq!(shared.cmp(&owned), Ordering::Equal);
+    assert!(shared < OwnedTerm::binary(b"help".to_vec()));
+    assert_eq!(
+        hash_of(&shared),
+        hash_of(&OwnedTerm::from(Bytes::from_static(b"hello")))
+    );
+    assert_eq!(shared.clone().try_into_string().unwrap(), "hello");
+    assert_eq!(shared.clone().try_into_binary().unwrap(), b"hello");
+    assert_eq!(owned.clone().into_shared_binary(), shared);
+}
+
+#[test]
+fn test_shared_and_owned_binaries_are_the_same_term() {
+    let owned = OwnedTerm::binary(b"hello".to_vec());
+    let shared = OwnedTerm::shared_binary(Bytes::from_static(b"hello"));
+
+    assert_eq!(shared, owned);
+    assert_eq!(owned, shared);
+    assert_ne!(shared, OwnedTerm::binary(b"help".to_vec()));
+    assert_ne!(shared, OwnedTerm::string("hello"));
+    assert_eq!(hash_of(&shared), hash_of(&owned));
+    assert_eq!(
+        erl_tuple![shared.clone(), erl_int!(1)],
+        erl_tuple![owned.clone(), erl_int!(1)]
+    );
+
+    let set: HashSet<OwnedTerm> = [owned, shared].into_iter().collect();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn test_decoding_with_and_without_sharing_gives_equal_terms() {
+    let term = erl_tuple![OwnedTerm::binary(vec![4; 300]), erl_int!(2)];
+    let buffer = Bytes::from(encode(&term).unwrap());
+
+    let shared =
+        decode_bytes_with_config(&buffer, &shared_config(1), &mut AtomTable::new()).unwrap();
+    let copied = decode(&buffer).unwrap();
+    assert!(shared[0].as_shared_binary().is_some());
+    assert_eq!(shared, copied);
+    assert_eq!(hash_of(&shared), hash_of(&copied));
+}
+
+#[test]
+fn test_heap_size_counts_the_shared_bytes() {
+    let shared = OwnedTerm::shared_binary(vec![0; 1000]);
+    assert_eq!(shared.heap_size(), size_of::<OwnedTerm>() + 1000);
+}
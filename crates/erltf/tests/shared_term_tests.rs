@@ -0,0 +1,184 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::{
+    OwnedTerm, SharedTerm, decode, encode, erl_atom, erl_int, erl_list, erl_map, erl_tuple,
+};
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+fn sample() -> OwnedTerm {
+    erl_tuple![erl_atom!("event"), erl_list![erl_int!(1), erl_int!(2)]]
+}
+
+fn hash_of(term: &OwnedTerm) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    term.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn shared_storage(term: &OwnedTerm) -> &SharedTerm {
+    match term {
+        OwnedTerm::Shared(shared) => shared,
+        other => panic!("expected a shared term, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_shared_term_clone_shares_storage() {
+    let a = sample().shared();
+    let b = a.clone();
+    assert!(a.ptr_eq(&b));
+    assert_eq!(a.ref_count(), 2);
+    assert_eq!(*a, sample());
+}
+
+#[test]
+fn test_shared_term_make_mut_copies_on_write() {
+    let a = sample().shared();
+    let mut b = a.clone();
+    *b.make_mut() = erl_atom!("changed");
+    assert!(!a.ptr_eq(&b));
+    assert_eq!(*a, sample());
+    assert_eq!(*b, erl_atom!("changed"));
+    assert_eq!(a.ref_count(), 1);
+}
+
+#[test]
+fn test_shared_term_make_mut_unique_does_not_copy() {
+    let mut a = sample().shared();
+    let before = Arc::as_ptr(a.as_arc());
+    if let OwnedTerm::Tuple(elements) = a.make_mut() {
+        elements.push(erl_atom!("extra"));
+    }
+    assert_eq!(before, Arc::as_ptr(a.as_arc()));
+    assert_eq!(a.tuple_get(2), Some(&erl_atom!("extra")));
+}
+
+#[test]
+fn test_shared_term_update() {
+    let a = sample().shared();
+    let b = a.clone().update(|t| *t = erl_int!(42));
+    assert_eq!(*a, sample());
+    assert_eq!(*b, erl_int!(42));
+}
+
+#[test]
+fn test_shared_term_into_owned() {
+    let a = sample().shared();
+    let b = a.clone();
+    assert_eq!(a.into_owned(), sample());
+    assert_eq!(b.ref_count(), 1);
+    assert_eq!(OwnedTerm::from(b), sample());
+}
+
+#[test]
+fn test_shared_term_from_arc_and_encode() {
+    let arc = Arc::new(sample());
+    let shared = SharedTerm::from(Arc::clone(&arc));
+    assert_eq!(shared.ref_count(), 2);
+    let encoded = encode(&shared).unwrap();
+    assert_eq!(decode(&encoded).unwrap(), sample());
+    assert_eq!(format!("{}", shared), format!("{}", sample()));
+}
+
+#[test]
+fn test_share_wraps_compound_terms_and_clones_share_them() {
+    let a = sample().share();
+    let b = a.clone();
+    assert!(shared_storage(&a).ptr_eq(shared_storage(&b)));
+    assert_eq!(shared_storage(&a).ref_count(), 2);
+
+    let twice = a.clone().share();
+    assert!(shared_storage(&twice).ptr_eq(shared_storage(&a)));
+}
+
+#[test]
+fn test_share_leaves_immediate_terms_alone() {
+    assert_eq!(erl_atom!("ok").share(), erl_atom!("ok"));
+    assert!(matches!(erl_atom!("ok").share(), OwnedTerm::Atom(_)));
+    assert!(matches!(erl_int!(7).share(), OwnedTerm::Integer(7)));
+    assert!(matches!(OwnedTerm::Nil.share(), OwnedTerm::Nil));
+}
+
+#[test]
+fn test_shared_subterms_compare_and_hash_like_the_terms_they_wrap() {
+    let payload = erl_list![erl_int!(1), erl_int!(2)];
+    let plain = erl_tuple![erl_atom!("event"), payload.clone()];
+    let with_shared = erl_tuple![erl_atom!("event"), payload.share()];
+
+    assert_eq!(with_shared, plain);
+    assert_eq!(plain, with_shared);
+    assert_eq!(hash_of(&with_shared), hash_of(&plain));
+    assert_eq!(with_shared.cmp(&plain), Ordering::Equal);
+    assert_eq!(sample().share(), sample());
+    assert_eq!(hash_of(&sample().share()), hash_of(&sample()));
+    assert!(sample().share() < erl_map! {});
+    assert_eq!(format!("{}", with_shared), format!("{}", plain));
+}
+
+#[test]
+fn test_shared_subterms_encode_like_the_terms_they_wrap() {
+    let payload = erl_list![erl_int!(1), erl_int!(2)].share();
+    let messages: Vec<OwnedTerm> = (0..3)
+        .map(|i| erl_tuple![erl_int!(i), payload.clone()])
+        .collect();
+    assert_eq!(shared_storage(&payload).ref_count(), 4);
+
+    for (i, message) in messages.iter().enumerate() {
+        let encoded = encode(message).unwrap();
+        let plain = erl_tuple![erl_int!(i as i64), erl_list![erl_int!(1), erl_int!(2)]];
+        assert_eq!(encoded, encode(&plain).unwrap());
+        assert!(matches!(decode(&encoded).unwrap(), OwnedTerm::Tuple(_)));
+    }
+}
+
+#[test]
+fn test_accessors_look_through_shared_terms() {
+    let shared = sample().share();
+    assert!(shared.is_tuple());
+    assert_eq!(shared.len(), 2);
+    assert_eq!(shared.as_tuple().unwrap()[0], erl_atom!("event"));
+    assert_eq!(shared.tuple_get(1).unwrap().as_list().unwrap().len(), 2);
+    assert_eq!(shared[0], erl_atom!("event"));
+    assert_eq!(shared.resolve(), &sample());
+
+    let map = erl_map! { erl_atom!("key") => erl_int!(1) }.share();
+    assert_eq!(map.map_get_atom_key("key"), Some(&erl_int!(1)));
+    assert_eq!(map.clone().try_into_map().unwrap().len(), 1);
+}
+
+#[test]
+fn test_make_mut_copies_shared_terms_on_write() {
+    let original = sample().share();
+    let mut copy = original.clone();
+    copy.as_tuple_mut().unwrap()[0] = erl_atom!("changed");
+
+    assert_eq!(original, sample());
+    assert_eq!(copy.as_tuple().unwrap()[0], erl_atom!("changed"));
+    assert!(!shared_storage(&copy).ptr_eq(shared_storage(&original)));
+    assert_eq!(shared_storage(&original).ref_count(), 1);
+}
+
+#[test]
+fn test_unshare_takes_the_term_out() {
+    let shared = sample().share().share();
+    let other = shared.clone();
+    assert_eq!(shared.unshare(), sample());
+    assert!(matches!(other.clone().unshare(), OwnedTerm::Tuple(_)));
+    assert_eq!(shared_storage(&other).ref_count(), 1);
+    assert!(matches!(erl_int!(1).unshare(), OwnedTerm::Integer(1)));
+}
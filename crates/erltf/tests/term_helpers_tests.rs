@@ -14,7 +14,10 @@
 
 use erltf::OwnedTerm;
 use erltf::types::{Atom, ExternalPid, Mfa};
-use erltf::{KeyValueAccess, erl_atom, erl_atoms, erl_int, erl_list, erl_map, erl_tuple};
+use erltf::{
+    ErlResult, KeyValueAccess, erl_atom, erl_atoms, erl_bin, erl_float, erl_int, erl_list, erl_map,
+    erl_tuple,
+};
 
 #[test]
 fn test_proplist_get_finds_value() {
@@ -106,6 +109,83 @@ fn test_map_get_atom_on_non_map() {
     assert_eq!(erl_int!(42).map_get_atom_key("key"), None);
 }
 
+fn string_keyed_params() -> OwnedTerm {
+    erl_map! {
+        OwnedTerm::binary(b"name".to_vec()) => OwnedTerm::binary(b"Charlie".to_vec()),
+        OwnedTerm::binary(b"age".to_vec()) => erl_int!(25),
+        OwnedTerm::binary(b"admin".to_vec()) => erl_atom!("true"),
+        OwnedTerm::binary(b"score".to_vec()) => OwnedTerm::Float(9.5),
+        OwnedTerm::binary(b"tags".to_vec()) => erl_list![erl_int!(1)],
+        erl_atom!("age") => erl_int!(30)
+    }
+}
+
+#[test]
+fn test_map_get_binary_key() {
+    let params = string_keyed_params();
+
+    assert_eq!(
+        params.map_get_binary_key("name"),
+        Some(&OwnedTerm::binary(b"Charlie".to_vec()))
+    );
+    assert_eq!(params.map_get_binary_key("age"), Some(&erl_int!(25)));
+    assert_eq!(params.map_get_binary_key("missing"), None);
+    assert_eq!(erl_int!(1).map_get_binary_key("name"), None);
+}
+
+#[test]
+fn test_map_get_binary_key_typed_getters() {
+    let params = string_keyed_params();
+
+    assert_eq!(params.map_get_binary_key_i64("age"), Some(25));
+    assert_eq!(params.map_get_binary_key_i64_or("missing", 7), 7);
+    assert_eq!(params.map_get_binary_key_bool("admin"), Some(true));
+    assert!(!params.map_get_binary_key_bool_or("missing", false));
+    assert_eq!(params.map_get_binary_key_float("score"), Some(9.5));
+    assert_eq!(
+        params.map_get_binary_key_string("name"),
+        Some("Charlie".to_string())
+    );
+    assert_eq!(params.map_get_binary_key_string_or("missing", "n/a"), "n/a");
+    assert_eq!(
+        params.map_get_binary_key_list("tags").map(|l| l.len()),
+        Some(1)
+    );
+    assert!(params.map_get_binary_key_map("name").is_none());
+}
+
+#[test]
+fn test_map_get_any_key_prefers_atom_keys() {
+    let params = string_keyed_params();
+
+    assert_eq!(params.map_get_any_key("age"), Some(&erl_int!(30)));
+    assert_eq!(params.kv_get_any_key_i64("age"), Some(30));
+    assert_eq!(
+        params.kv_get_any_key_string("name"),
+        Some("Charlie".to_string())
+    );
+    assert_eq!(params.kv_get_any_key_bool("admin"), Some(true));
+    assert_eq!(params.kv_get_any_key("missing"), None);
+}
+
+#[test]
+fn test_kv_get_binary_key_on_proplist() {
+    let proplist = erl_list![
+        erl_tuple![
+            OwnedTerm::binary(b"host".to_vec()),
+            OwnedTerm::binary(b"localhost".to_vec())
+        ],
+        erl_tuple![erl_atom!("port"), erl_int!(5672)]
+    ];
+
+    assert_eq!(
+        proplist.kv_get_any_key_string("host"),
+        Some("localhost".to_string())
+    );
+    assert_eq!(proplist.kv_get_any_key_i64("port"), Some(5672));
+    assert_eq!(proplist.proplist_get_binary_key("port"), None);
+}
+
 #[test]
 fn test_as_erlang_string_from_integer_list() {
     let term = erl_list![
@@ -1026,3 +1106,214 @@ fn test_proplist_get_i64_or_variations() {
     assert_eq!(proplist.proplist_get_i64_or("y", 0), 20);
     assert_eq!(proplist.proplist_get_i64_or("z", 99), 99);
 }
+
+#[test]
+fn test_erl_list_macro_spreads() {
+    let existing = vec![erl_int!(1), erl_int!(2)];
+    let term = erl_list![
+        ..existing.clone(),
+        erl_int!(3),
+        ..vec![4i64, 5],
+        ..Vec::<OwnedTerm>::new()
+    ];
+    assert_eq!(
+        term,
+        OwnedTerm::List(vec![
+            erl_int!(1),
+            erl_int!(2),
+            erl_int!(3),
+            erl_int!(4),
+            erl_int!(5)
+        ])
+    );
+    assert_eq!(
+        erl_list![..existing],
+        OwnedTerm::List(vec![erl_int!(1), erl_int!(2)])
+    );
+}
+
+#[test]
+fn test_erl_tuple_macro_spreads() {
+    let fields = ["a", "b"].map(Atom::new);
+    let term = erl_tuple![erl_atom!("record"), ..fields, 42,];
+    assert_eq!(
+        term,
+        OwnedTerm::Tuple(vec![
+            erl_atom!("record"),
+            erl_atom!("a"),
+            erl_atom!("b"),
+            erl_int!(42)
+        ])
+    );
+}
+
+#[test]
+fn test_erl_list_macro_with_many_elements() {
+    let term = erl_list![
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+        25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47,
+        48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63
+    ];
+    assert_eq!(term.len(), 64);
+}
+
+#[test]
+fn test_erl_map_macro_spreads() {
+    let defaults = vec![
+        (erl_atom!("timeout"), erl_int!(5000)),
+        (erl_atom!("retries"), erl_int!(3)),
+    ];
+    let term = erl_map! { ..defaults, erl_atom!("timeout") => 100 };
+    assert_eq!(term.map_get_atom_key("timeout"), Some(&erl_int!(100)));
+    assert_eq!(term.map_get_atom_key("retries"), Some(&erl_int!(3)));
+
+    let from_iter = erl_map! { ..(1..=3).map(|i: i64| (i, i * i)) };
+    assert_eq!(from_iter.as_map().map(|m| m.len()), Some(3));
+    assert_eq!(from_iter.map_get(&erl_int!(3)), Some(&erl_int!(9)));
+}
+
+#[test]
+fn test_erl_bin_and_erl_float_macros() {
+    assert_eq!(erl_bin!(b"\x00\xff"), OwnedTerm::Binary(vec![0, 255]));
+    assert_eq!(erl_bin!("hello"), OwnedTerm::Binary(b"hello".to_vec()));
+    assert_eq!(
+        erl_bin!(String::from("hi")),
+        OwnedTerm::Binary(b"hi".to_vec())
+    );
+    assert_eq!(erl_bin!(vec![1u8, 2]), OwnedTerm::Binary(vec![1, 2]));
+    assert_eq!(erl_float!(1.5), OwnedTerm::Float(1.5));
+    assert_eq!(erl_float!(2), OwnedTerm::Float(2.0));
+    assert_eq!(
+        erl_list![erl_bin!("a"), erl_float!(0.5)],
+        OwnedTerm::List(vec![
+            OwnedTerm::Binary(b"a".to_vec()),
+            OwnedTerm::Float(0.5)
+        ])
+    );
+}
+
+#[test]
+fn test_as_improper_returns_elements_and_tail() {
+    let term = OwnedTerm::improper_list(vec![erl_int!(1), erl_int!(2)], erl_atom!("tail"));
+    let (elements, tail) = term.as_improper().unwrap();
+    assert_eq!(elements, &[erl_int!(1), erl_int!(2)]);
+    assert_eq!(tail, &erl_atom!("tail"));
+
+    assert_eq!(erl_list![erl_int!(1)].as_improper(), None);
+    assert_eq!(OwnedTerm::Nil.as_improper(), None);
+}
+
+#[test]
+fn test_to_proper_lossy_appends_the_tail() {
+    let term = OwnedTerm::improper_list(vec![erl_int!(1), erl_int!(2)], erl_int!(3));
+    assert_eq!(
+        term.to_proper_lossy(),
+        erl_list![erl_int!(1), erl_int!(2), erl_int!(3)]
+    );
+
+    let nested = OwnedTerm::improper_list(
+        vec![erl_int!(1)],
+        OwnedTerm::improper_list(vec![erl_int!(2)], erl_int!(3)),
+    );
+    assert_eq!(
+        nested.to_proper_lossy(),
+        erl_list![erl_int!(1), erl_int!(2), erl_int!(3)]
+    );
+
+    let proper = erl_list![erl_int!(1)];
+    assert_eq!(proper.to_proper_lossy(), proper);
+    assert_eq!(erl_atom!("a").to_proper_lossy(), erl_atom!("a"));
+}
+
+#[test]
+fn test_kv_get_searches_improper_list_elements() {
+    let term = OwnedTerm::improper_list(
+        vec![
+            erl_tuple![erl_atom!("name"), erl_bin!("rabbit")],
+            erl_tuple![erl_bin!("port"), erl_int!(5672)],
+        ],
+        erl_atom!("legacy"),
+    );
+    assert_eq!(term.kv_get("name"), Some(&erl_bin!("rabbit")));
+    assert_eq!(term.kv_get_binary_key("port"), Some(&erl_int!(5672)));
+    assert_eq!(term.kv_get_i64("port"), None);
+    assert_eq!(term.kv_get_any_key_i64("port"), Some(5672));
+    assert_eq!(term.kv_get("legacy"), None);
+}
+
+#[test]
+fn test_improper_list_printing() {
+    let term = OwnedTerm::improper_list(vec![erl_int!(1), erl_int!(2)], erl_int!(3));
+    assert_eq!(term.to_string(), "[1, 2 | 3]");
+    assert_eq!(term.inspect(), "[1, 2 | 3]");
+
+    let empty = OwnedTerm::improper_list(vec![], erl_atom!("tail"));
+    assert_eq!(empty.to_string(), "tail");
+    assert_eq!(empty.inspect(), ":tail");
+}
+
+#[test]
+fn test_erl_result_parses_ok_tuples_and_bare_ok() {
+    let term = erl_tuple![erl_atom!("ok"), erl_int!(42)];
+    assert_eq!(ErlResult::parse(&term), Some(ErlResult::ok(erl_int!(42))));
+    assert_eq!(ErlResult::from_term(term), Ok(erl_int!(42)));
+
+    let bare = ErlResult::parse(&erl_atom!("ok")).unwrap();
+    assert!(bare.is_ok());
+    assert_eq!(bare.value(), Some(&erl_atom!("ok")));
+}
+
+#[test]
+fn test_erl_result_parses_errors_with_and_without_stacktraces() {
+    let stacktrace = erl_list![erl_tuple![
+        erl_atom!("lists"),
+        erl_atom!("map"),
+        erl_int!(2)
+    ]];
+    let term = erl_tuple![erl_atom!("error"), erl_atom!("badarg"), stacktrace.clone()];
+
+    let result = ErlResult::parse(&term).unwrap();
+    assert!(result.is_error());
+    assert_eq!(result.reason(), Some(&erl_atom!("badarg")));
+    assert_eq!(result.stacktrace(), Some(&stacktrace));
+    assert_eq!(ErlResult::from_term(term), Err(erl_atom!("badarg")));
+
+    let result = ErlResult::parse(&erl_tuple![erl_atom!("error"), erl_atom!("enoent")]).unwrap();
+    assert_eq!(result.stacktrace(), None);
+}
+
+#[test]
+fn test_erl_result_from_term_returns_unconventional_terms_as_errors() {
+    let term = erl_tuple![erl_atom!("noreply"), erl_int!(1)];
+    assert_eq!(ErlResult::parse(&term), None);
+    assert_eq!(ErlResult::from_term(term.clone()), Err(term));
+}
+
+#[test]
+fn test_erl_result_roundtrips_through_terms() {
+    for result in [
+        ErlResult::ok(erl_int!(1)),
+        ErlResult::error(erl_atom!("timeout")),
+        ErlResult::error_with_stacktrace(erl_atom!("badarith"), erl_list![]),
+    ] {
+        let term = OwnedTerm::from(result.clone());
+        assert_eq!(ErlResult::parse(&term), Some(result));
+    }
+    assert_eq!(
+        OwnedTerm::from(ErlResult::error(erl_atom!("timeout"))),
+        OwnedTerm::error_tuple(erl_atom!("timeout"))
+    );
+}
+
+#[test]
+fn test_erl_result_combinators() {
+    let doubled = ErlResult::ok(erl_int!(21)).map_ok(|v| erl_int!(v.as_integer().unwrap() * 2));
+    assert_eq!(doubled.into_result(), Ok(erl_int!(42)));
+
+    let failed = ErlResult::error_with_stacktrace(erl_atom!("badarg"), erl_list![]);
+    let mapped = failed.as_ok_or_else(|reason, stacktrace| (reason, stacktrace.is_some()));
+    assert_eq!(mapped, Err((erl_atom!("badarg"), true)));
+
+    let result: Result<OwnedTerm, OwnedTerm> = ErlResult::from(Err(erl_atom!("nope"))).into();
+    assert_eq!(result, Err(erl_atom!("nope")));
+}
@@ -0,0 +1,117 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::truncate::ELISION;
+use erltf::{OwnedTerm, erl_atom, erl_int, erl_list, erl_map, erl_tuple};
+
+fn elision() -> OwnedTerm {
+    erl_atom!(ELISION)
+}
+
+#[test]
+fn test_small_terms_are_unchanged() {
+    let term = erl_tuple![
+        erl_atom!("ok"),
+        erl_list![erl_int!(1), erl_int!(2)],
+        OwnedTerm::binary(b"abc".to_vec())
+    ];
+    assert_eq!(term.truncated(3, 3), term);
+}
+
+#[test]
+fn test_long_lists_and_tuples_end_with_an_elision() {
+    let list = OwnedTerm::List((0..1_000_000).map(OwnedTerm::integer).collect());
+    assert_eq!(
+        list.truncated(2, 16),
+        erl_list![erl_int!(0), erl_int!(1), elision()]
+    );
+
+    let tuple = erl_tuple![erl_int!(1), erl_int!(2), erl_int!(3)];
+    assert_eq!(tuple.truncated(1, 16), erl_tuple![erl_int!(1), elision()]);
+}
+
+#[test]
+fn test_nested_terms_are_truncated() {
+    let inner = OwnedTerm::List((0..10).map(OwnedTerm::integer).collect());
+    let term = erl_tuple![erl_atom!("batch"), inner];
+    assert_eq!(
+        term.truncated(2, 16),
+        erl_tuple![
+            erl_atom!("batch"),
+            erl_list![erl_int!(0), erl_int!(1), elision()]
+        ]
+    );
+}
+
+#[test]
+fn test_binaries_and_strings_keep_a_preview() {
+    let binary = OwnedTerm::binary(vec![b'x'; 100 * 1024 * 1024]);
+    assert_eq!(
+        binary.truncated(10, 4),
+        OwnedTerm::binary(b"xxxx...".to_vec())
+    );
+
+    let string = OwnedTerm::String("héllo world".to_string());
+    // The preview never splits a multi-byte character
+    assert_eq!(
+        string.truncated(10, 2),
+        OwnedTerm::String("h...".to_string())
+    );
+}
+
+#[test]
+fn test_maps_keep_their_first_entries() {
+    let map = erl_map! {
+        erl_atom!("a") => erl_int!(1),
+        erl_atom!("b") => erl_int!(2),
+        erl_atom!("c") => erl_int!(3)
+    };
+    let truncated = map.truncated(1, 16);
+    let entries = truncated.as_map().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries.get(&erl_atom!("a")), Some(&erl_int!(1)));
+    assert_eq!(entries.get(&elision()), Some(&elision()));
+
+    let ordered = OwnedTerm::OrderedMap(vec![
+        (erl_atom!("z"), erl_int!(1)),
+        (erl_atom!("y"), erl_int!(2)),
+    ]);
+    assert_eq!(
+        ordered.truncated(1, 16),
+        OwnedTerm::OrderedMap(vec![(erl_atom!("z"), erl_int!(1)), (elision(), elision())])
+    );
+}
+
+#[test]
+fn test_long_byte_lists_become_integer_lists() {
+    let bytes = OwnedTerm::ByteList(b"abcdef".to_vec());
+    assert_eq!(
+        bytes.truncated(2, 16),
+        erl_list![erl_int!(97), erl_int!(98), elision()]
+    );
+    assert_eq!(bytes.truncated(6, 16), bytes);
+}
+
+#[test]
+fn test_display_truncated() {
+    let term = erl_tuple![
+        erl_atom!("data"),
+        OwnedTerm::List((0..1000).map(OwnedTerm::integer).collect())
+    ];
+    assert_eq!(
+        term.display_truncated(3, 16).to_string(),
+        "{data, [0, 1, 2, ...]}"
+    );
+    assert_eq!(format!("{}", term.display_truncated(1, 16)), "{data, ...}");
+}
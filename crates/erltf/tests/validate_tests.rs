@@ -0,0 +1,224 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::types::{Atom, ExternalPid};
+use erltf::{
+    DecodeError, OwnedTerm, TermSummary, encode, encode_with_dist_header_multi, erl_atom, erl_int,
+    erl_list, erl_map, erl_tuple, validate, validate_all,
+};
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use std::io::Write;
+
+fn compress(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&data[1..]).unwrap();
+    let zlib = encoder.finish().unwrap();
+
+    let mut out = vec![131, 80];
+    out.extend_from_slice(&(data.len() as u32 - 1).to_be_bytes());
+    out.extend_from_slice(&zlib);
+    out
+}
+
+#[test]
+fn test_validate_summary() {
+    let term = erl_tuple!(
+        erl_atom!("ok"),
+        erl_list![OwnedTerm::binary(vec![1, 2, 3]), erl_int!(7)],
+        erl_map! { erl_atom!("k") => OwnedTerm::binary(vec![4; 10]) }
+    );
+    let data = encode(&term).unwrap();
+
+    assert_eq!(
+        validate(&data).unwrap(),
+        TermSummary {
+            encoded_len: data.len(),
+            top_level_terms: 1,
+            terms: 9,
+            max_depth: 3,
+            atoms: 2,
+            binaries: 2,
+            binary_bytes: 13,
+            compressed: false,
+        }
+    );
+}
+
+#[test]
+fn test_validate_pid() {
+    let pid = ExternalPid::new(Atom::new("a@host"), 1, 0, 3);
+    let data = encode(&OwnedTerm::Pid(pid)).unwrap();
+
+    let summary = validate(&data).unwrap();
+    assert_eq!(summary.terms, 1);
+    assert_eq!(summary.atoms, 1);
+}
+
+#[test]
+fn test_validate_dist_frame() {
+    let control = erl_tuple!(erl_int!(6), erl_atom!("from"), erl_atom!("to"));
+    let payload = erl_atom!("hello");
+    let data = encode_with_dist_header_multi(&[&control, &payload]).unwrap();
+
+    let summary = validate(&data).unwrap();
+    assert_eq!(summary.top_level_terms, 2);
+}
+
+#[test]
+fn test_validate_compressed() {
+    let term = erl_list![erl_tuple!(erl_atom!("a"), OwnedTerm::binary(vec![0; 256]))];
+    let plain = encode(&term).unwrap();
+    let data = compress(&plain);
+
+    let summary = validate(&data).unwrap();
+    assert!(summary.compressed);
+    assert_eq!(summary.terms, validate(&plain).unwrap().terms);
+    assert_eq!(summary.max_depth, 3);
+}
+
+#[test]
+fn test_validate_compressed_size_mismatch() {
+    let plain = encode(&OwnedTerm::binary(vec![0; 64])).unwrap();
+    let mut data = compress(&plain);
+    data[5] += 1;
+
+    assert!(matches!(
+        validate(&data),
+        Err(DecodeError::InvalidFormat(msg)) if msg.contains("size mismatch")
+    ));
+}
+
+#[test]
+fn test_validate_rejects_truncated_data() {
+    let data = encode(&erl_tuple!(erl_atom!("ok"), OwnedTerm::binary(vec![0; 32]))).unwrap();
+    assert_eq!(
+        validate(&data[..data.len() - 1]),
+        Err(DecodeError::UnexpectedEof)
+    );
+}
+
+#[test]
+fn test_validate_rejects_trailing_data() {
+    let mut data = encode(&erl_atom!("ok")).unwrap();
+    data.push(0);
+    assert_eq!(validate(&data), Err(DecodeError::TrailingData(1)));
+}
+
+#[test]
+fn test_validate_rejects_invalid_version() {
+    assert!(matches!(
+        validate(&[130, 106]),
+        Err(DecodeError::InvalidVersion { actual: 130, .. })
+    ));
+}
+
+#[test]
+fn test_validate_rejects_unknown_tag() {
+    assert_eq!(validate(&[131, 0xFE]), Err(DecodeError::InvalidTag(0xFE)));
+}
+
+#[test]
+fn test_validate_rejects_invalid_utf8_atom() {
+    assert!(matches!(
+        validate(&[131, 119, 2, 0xC3, 0x28]),
+        Err(DecodeError::InvalidUtf8(_))
+    ));
+}
+
+#[test]
+fn test_validate_rejects_atom_cache_ref_without_dist_header() {
+    assert!(matches!(
+        validate(&[131, 82, 0]),
+        Err(DecodeError::InvalidFormat(_))
+    ));
+}
+
+#[test]
+fn test_validate_rejects_non_atom_pid_node() {
+    assert_eq!(
+        validate(&[131, 88, 97, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Err(DecodeError::InvalidTag(97))
+    );
+}
+
+#[test]
+fn test_validate_rejects_oversized_list() {
+    assert!(matches!(
+        validate(&[131, 108, 0xFF, 0xFF, 0xFF, 0xFF]),
+        Err(DecodeError::ListTooLarge { .. })
+    ));
+}
+
+#[test]
+fn test_validate_rejects_invalid_big_sign() {
+    assert!(matches!(
+        validate(&[131, 110, 1, 2, 5]),
+        Err(DecodeError::InvalidFormat(_))
+    ));
+}
+
+#[test]
+fn test_validate_agrees_with_decode() {
+    let term = erl_map! {
+        erl_atom!("nested") => erl_list![erl_list![erl_list![erl_int!(1)]]],
+        erl_atom!("float") => OwnedTerm::Float(1.5),
+    };
+    let data = encode(&term).unwrap();
+
+    assert!(validate(&data).is_ok());
+    assert!(erltf::decode(&data).is_ok());
+}
+
+#[test]
+fn test_validate_all_summarizes_every_term() {
+    let control = erl_tuple!(erl_int!(2), erl_atom!(""), erl_atom!("to"));
+    let payload = erl_list![OwnedTerm::binary(vec![0; 100]), erl_int!(1)];
+    let mut data = encode(&control).unwrap();
+    data.extend(encode(&payload).unwrap());
+
+    let summary = validate_all(&data).unwrap();
+    assert_eq!(summary.top_level_terms, 2);
+    assert_eq!(summary.terms, 8);
+    assert_eq!(summary.binary_bytes, 100);
+    assert_eq!(summary.encoded_len, data.len());
+}
+
+#[test]
+fn test_validate_all_rejects_a_missing_version() {
+    let mut data = encode(&erl_atom!("ok")).unwrap();
+    data.extend(&encode(&erl_int!(1)).unwrap()[1..]);
+    assert!(matches!(
+        validate_all(&data),
+        Err(DecodeError::InvalidVersion { .. })
+    ));
+    assert_eq!(validate_all(&[]), Err(DecodeError::UnexpectedEof));
+}
+
+#[test]
+fn test_estimated_heap_size_is_close_to_the_decoded_size() {
+    let term = erl_tuple!(
+        erl_list![OwnedTerm::binary(vec![7; 4096]), erl_int!(7)],
+        erl_map! { erl_atom!("k") => OwnedTerm::binary(vec![4; 1024]) }
+    );
+    let data = encode(&term).unwrap();
+
+    let estimate = validate(&data).unwrap().estimated_heap_size();
+    let actual = term.heap_size();
+    assert!(estimate >= 5120);
+    assert!(
+        estimate.abs_diff(actual) <= actual / 10,
+        "{estimate} vs {actual}"
+    );
+}
@@ -0,0 +1,194 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::{OwnedTerm, Transform, WalkControl, erl_atom, erl_int, erl_list, erl_map, erl_tuple};
+
+fn sample() -> OwnedTerm {
+    erl_tuple!(
+        erl_atom!("a"),
+        erl_list![erl_int!(1), erl_int!(2)],
+        erl_map! { erl_atom!("k") => erl_int!(3) }
+    )
+}
+
+fn deeply_nested(depth: usize) -> OwnedTerm {
+    let mut term = erl_int!(0);
+    for _ in 0..depth {
+        term = OwnedTerm::List(vec![term]);
+    }
+    term
+}
+
+#[test]
+fn test_walk_visits_in_pre_order_with_depth() {
+    let mut visited = Vec::new();
+    let completed = sample().walk(&mut |term: &OwnedTerm, depth| {
+        visited.push((term.to_string(), depth));
+        WalkControl::Continue
+    });
+
+    assert!(completed);
+    let visited: Vec<(&str, usize)> = visited.iter().map(|(s, d)| (s.as_str(), *d)).collect();
+    assert_eq!(
+        visited[1..],
+        [
+            ("a", 1),
+            ("[1, 2]", 1),
+            ("1", 2),
+            ("2", 2),
+            ("#{k => 3}", 1),
+            ("k", 2),
+            ("3", 2),
+        ]
+    );
+    assert_eq!(visited[0].1, 0);
+}
+
+#[test]
+fn test_walk_skip_children() {
+    let mut integers = 0;
+    sample().walk(&mut |term: &OwnedTerm, _| match term {
+        OwnedTerm::List(_) => WalkControl::SkipChildren,
+        OwnedTerm::Integer(_) => {
+            integers += 1;
+            WalkControl::Continue
+        }
+        _ => WalkControl::Continue,
+    });
+    assert_eq!(integers, 1);
+}
+
+#[test]
+fn test_walk_stop() {
+    let mut visited = 0;
+    let completed = sample().walk(&mut |term: &OwnedTerm, _| {
+        visited += 1;
+        if term.is_list() {
+            WalkControl::Stop
+        } else {
+            WalkControl::Continue
+        }
+    });
+    assert!(!completed);
+    assert_eq!(visited, 3);
+}
+
+#[test]
+fn test_walk_improper_list_tail() {
+    let term = OwnedTerm::ImproperList {
+        elements: vec![erl_int!(1)],
+        tail: Box::new(erl_atom!("tail")),
+    };
+    let mut visited = Vec::new();
+    term.walk(&mut |t: &OwnedTerm, _| {
+        visited.push(t.clone());
+        WalkControl::Continue
+    });
+    assert_eq!(visited[1..], [erl_int!(1), erl_atom!("tail")]);
+}
+
+#[test]
+fn test_walk_deeply_nested_term() {
+    let term = deeply_nested(200_000);
+    let mut max_depth = 0;
+    assert!(term.walk(&mut |_: &OwnedTerm, depth| {
+        max_depth = max_depth.max(depth);
+        WalkControl::Continue
+    }));
+    assert_eq!(max_depth, 200_000);
+    // dropping a term this deep recurses in the destructor
+    std::mem::forget(term);
+}
+
+#[test]
+fn test_transform_replaces_leaves() {
+    let transformed = sample().transform(|term| match term {
+        OwnedTerm::Integer(i) => Transform::Replace(erl_int!(i * 10)),
+        other => Transform::Descend(other),
+    });
+    assert_eq!(
+        transformed,
+        erl_tuple!(
+            erl_atom!("a"),
+            erl_list![erl_int!(10), erl_int!(20)],
+            erl_map! { erl_atom!("k") => erl_int!(30) }
+        )
+    );
+}
+
+#[test]
+fn test_transform_replace_does_not_descend() {
+    let transformed = sample().transform(|term| match term {
+        OwnedTerm::List(_) => Transform::Replace(erl_atom!("redacted")),
+        OwnedTerm::Integer(_) => Transform::Replace(erl_int!(0)),
+        other => Transform::Descend(other),
+    });
+    assert_eq!(
+        transformed,
+        erl_tuple!(
+            erl_atom!("a"),
+            erl_atom!("redacted"),
+            erl_map! { erl_atom!("k") => erl_int!(0) }
+        )
+    );
+}
+
+#[test]
+fn test_transform_descends_into_replacement() {
+    let transformed = erl_atom!("wrap").transform(|term| match term {
+        OwnedTerm::Atom(a) if a.as_str() == "wrap" => {
+            Transform::Descend(erl_list![erl_atom!("x"), erl_atom!("y")])
+        }
+        OwnedTerm::Atom(a) => Transform::Replace(OwnedTerm::binary(a.as_str().as_bytes().to_vec())),
+        other => Transform::Descend(other),
+    });
+    assert_eq!(
+        transformed,
+        erl_list![
+            OwnedTerm::binary(b"x".to_vec()),
+            OwnedTerm::binary(b"y".to_vec())
+        ]
+    );
+}
+
+#[test]
+fn test_transform_keeps_structure_of_empty_and_improper_terms() {
+    let term = erl_tuple!(
+        OwnedTerm::List(vec![]),
+        OwnedTerm::Tuple(vec![]),
+        OwnedTerm::ImproperList {
+            elements: vec![erl_int!(1)],
+            tail: Box::new(erl_int!(2)),
+        }
+    );
+    assert_eq!(term.clone().transform(Transform::Descend), term);
+}
+
+#[test]
+fn test_transform_deeply_nested_term() {
+    let term = deeply_nested(200_000);
+    let transformed = term.transform(|t| match t {
+        OwnedTerm::Integer(_) => Transform::Replace(erl_int!(1)),
+        other => Transform::Descend(other),
+    });
+    let mut leaf = None;
+    transformed.walk(&mut |t: &OwnedTerm, _| {
+        if let OwnedTerm::Integer(i) = t {
+            leaf = Some(*i);
+        }
+        WalkControl::Continue
+    });
+    assert_eq!(leaf, Some(1));
+    std::mem::forget(transformed);
+}
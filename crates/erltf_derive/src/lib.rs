@@ -0,0 +1,374 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! `ToTerm` and `FromTerm` derive macros, re-exported by `erltf` with the `derive` feature.
+//! See the `erltf::convert` module for the supported attributes.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Data, DataEnum, DeriveInput, Fields, GenericParam, Generics, Index, LitStr, Member,
+    parse_macro_input, parse_quote,
+};
+
+/// Derives `erltf::convert::ToTerm`.
+#[proc_macro_derive(ToTerm, attributes(term))]
+pub fn derive_to_term(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_to_term(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `erltf::convert::FromTerm`.
+#[proc_macro_derive(FromTerm, attributes(term))]
+pub fn derive_from_term(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_term(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum Shape {
+    Map,
+    Tuple,
+    Record(String),
+    ElixirStruct(String),
+    Transparent,
+}
+
+struct Field {
+    member: Member,
+    /// The map key, or the field index of tuple structs
+    key: String,
+    default: bool,
+}
+
+struct Variant {
+    ident: syn::Ident,
+    atom: String,
+}
+
+enum Layout {
+    Struct(Shape, Vec<Field>),
+    Enum(Vec<Variant>),
+}
+
+fn layout(input: &DeriveInput) -> syn::Result<Layout> {
+    let shape = container_shape(input)?;
+    match &input.data {
+        Data::Struct(data) => {
+            let named = matches!(data.fields, Fields::Named(_));
+            let fields = data
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let (member, name) = match &field.ident {
+                        Some(ident) => (Member::Named(ident.clone()), ident.to_string()),
+                        None => (Member::Unnamed(Index::from(i)), i.to_string()),
+                    };
+                    let (rename, default) = field_attrs(&field.attrs)?;
+                    Ok(Field {
+                        member,
+                        key: rename.unwrap_or(name),
+                        default,
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            let shape = match shape {
+                Some(Shape::Map | Shape::ElixirStruct(_)) if !named => {
+                    return Err(syn::Error::new_spanned(
+                        &input.ident,
+                        "only structs with named fields can be converted to maps",
+                    ));
+                }
+                Some(Shape::Transparent) if fields.len() != 1 => {
+                    return Err(syn::Error::new_spanned(
+                        &input.ident,
+                        "#[term(transparent)] requires exactly one field",
+                    ));
+                }
+                Some(shape) => shape,
+                None if matches!(data.fields, Fields::Unit) => {
+                    return Err(syn::Error::new_spanned(
+                        &input.ident,
+                        "unit structs require #[term(record = \"...\")]",
+                    ));
+                }
+                None if named => Shape::Map,
+                None => Shape::Tuple,
+            };
+            Ok(Layout::Struct(shape, fields))
+        }
+        Data::Enum(data) => {
+            if shape.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "enums are converted to atoms and take no container #[term(...)] attributes",
+                ));
+            }
+            enum_variants(data).map(Layout::Enum)
+        }
+        Data::Union(_) => Err(syn::Error::new_spanned(
+            &input.ident,
+            "ToTerm and FromTerm cannot be derived for unions",
+        )),
+    }
+}
+
+fn container_shape(input: &DeriveInput) -> syn::Result<Option<Shape>> {
+    let mut shape = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("term")) {
+        attr.parse_nested_meta(|meta| {
+            let parsed = if meta.path.is_ident("map") {
+                Shape::Map
+            } else if meta.path.is_ident("tuple") {
+                Shape::Tuple
+            } else if meta.path.is_ident("transparent") {
+                Shape::Transparent
+            } else if meta.path.is_ident("record") {
+                Shape::Record(meta.value()?.parse::<LitStr>()?.value())
+            } else if meta.path.is_ident("elixir_struct") {
+                let module = meta.value()?.parse::<LitStr>()?.value();
+                Shape::ElixirStruct(format!("Elixir.{module}"))
+            } else {
+                return Err(meta.error("unsupported #[term(...)] attribute"));
+            };
+            if shape.replace(parsed).is_some() {
+                return Err(meta.error(
+                    "only one of map, tuple, record, elixir_struct and transparent can be used",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(shape)
+}
+
+fn field_attrs(attrs: &[syn::Attribute]) -> syn::Result<(Option<String>, bool)> {
+    let mut rename = None;
+    let mut default = false;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("term")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                default = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported #[term(...)] field attribute"))
+            }
+        })?;
+    }
+    Ok((rename, default))
+}
+
+fn enum_variants(data: &DataEnum) -> syn::Result<Vec<Variant>> {
+    data.variants
+        .iter()
+        .map(|variant| {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "only enums with unit variants are supported",
+                ));
+            }
+            let (rename, default) = field_attrs(&variant.attrs)?;
+            if default {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "#[term(default)] is not supported on variants",
+                ));
+            }
+            Ok(Variant {
+                ident: variant.ident.clone(),
+                atom: rename.unwrap_or_else(|| variant.ident.to_string()),
+            })
+        })
+        .collect()
+}
+
+fn with_bound(generics: &Generics, bound: syn::Path) -> Generics {
+    let mut generics = generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds.push(parse_quote!(#bound));
+        }
+    }
+    generics
+}
+
+fn expand_to_term(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let generics = with_bound(&input.generics, parse_quote!(::erltf::convert::ToTerm));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match layout(input)? {
+        Layout::Struct(shape, fields) => {
+            let values = fields.iter().map(|f| {
+                let member = &f.member;
+                quote! { ::erltf::convert::ToTerm::to_term(&self.#member) }
+            });
+            match shape {
+                Shape::Map | Shape::ElixirStruct(_) => {
+                    let module = match &shape {
+                        Shape::ElixirStruct(module) => Some(quote! {
+                            map.insert(
+                                ::erltf::OwnedTerm::atom("__struct__"),
+                                ::erltf::OwnedTerm::atom(#module),
+                            );
+                        }),
+                        _ => None,
+                    };
+                    let keys = fields.iter().map(|f| &f.key);
+                    quote! {
+                        let mut map = ::std::collections::BTreeMap::new();
+                        #module
+                        #(map.insert(::erltf::OwnedTerm::atom(#keys), #values);)*
+                        ::erltf::OwnedTerm::Map(map)
+                    }
+                }
+                Shape::Tuple => quote! {
+                    ::erltf::OwnedTerm::Tuple(::std::vec![#(#values),*])
+                },
+                Shape::Record(tag) => quote! {
+                    ::erltf::OwnedTerm::Tuple(::std::vec![::erltf::OwnedTerm::atom(#tag), #(#values),*])
+                },
+                Shape::Transparent => quote! { #(#values)* },
+            }
+        }
+        Layout::Enum(variants) => {
+            let arms = variants.iter().map(|v| {
+                let ident = &v.ident;
+                let atom = &v.atom;
+                quote! { Self::#ident => ::erltf::OwnedTerm::atom(#atom), }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::erltf::convert::ToTerm for #name #ty_generics #where_clause {
+            fn to_term(&self) -> ::erltf::OwnedTerm {
+                #body
+            }
+        }
+    })
+}
+
+fn expand_from_term(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let generics = with_bound(&input.generics, parse_quote!(::erltf::convert::FromTerm));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let private = quote! { ::erltf::convert::__private };
+
+    let body = match layout(input)? {
+        Layout::Struct(shape, fields) => {
+            let field_value = |f: &Field, source: TokenStream2| {
+                let key = &f.key;
+                if f.default {
+                    quote! { #private::field_or_default(#source, #key)? }
+                } else {
+                    quote! { #private::field(#source, #key)? }
+                }
+            };
+            let construct = |values: Vec<TokenStream2>| {
+                let members = fields.iter().map(|f| &f.member);
+                quote! { Ok(Self { #(#members: #values),* }) }
+            };
+            match shape {
+                Shape::Map | Shape::ElixirStruct(_) => {
+                    let module = match &shape {
+                        Shape::ElixirStruct(module) => Some(quote! {
+                            #private::expect_atom(#private::map_get(term, "__struct__"), #module)?;
+                        }),
+                        _ => None,
+                    };
+                    let values = fields
+                        .iter()
+                        .map(|f| {
+                            let key = &f.key;
+                            field_value(f, quote! { #private::map_get(term, #key) })
+                        })
+                        .collect();
+                    let construct = construct(values);
+                    quote! {
+                        #private::expect_map(term)?;
+                        #module
+                        #construct
+                    }
+                }
+                Shape::Tuple | Shape::Record(_) => {
+                    let (offset, tag) = match &shape {
+                        Shape::Record(tag) => (
+                            1,
+                            Some(quote! { #private::expect_atom(elements.first(), #tag)?; }),
+                        ),
+                        _ => (0, None),
+                    };
+                    let arity = fields.len() + offset;
+                    let values = fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, f)| {
+                            let index = i + offset;
+                            field_value(f, quote! { elements.get(#index) })
+                        })
+                        .collect();
+                    let construct = construct(values);
+                    quote! {
+                        let elements = #private::tuple_elements(term, #arity)?;
+                        #tag
+                        #construct
+                    }
+                }
+                Shape::Transparent => construct(vec![quote! {
+                    ::erltf::convert::FromTerm::from_term(term)?
+                }]),
+            }
+        }
+        Layout::Enum(variants) => {
+            let atoms = variants.iter().map(|v| &v.atom);
+            let idents = variants.iter().map(|v| &v.ident);
+            let expected = format!(
+                "one of the atoms {}",
+                variants
+                    .iter()
+                    .map(|v| v.atom.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            quote! {
+                match term.atom_name() {
+                    #(Some(#atoms) => Ok(Self::#idents),)*
+                    _ => Err(#private::unknown_variant(term, #expected)),
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::erltf::convert::FromTerm for #name #ty_generics #where_clause {
+            fn from_term(term: &::erltf::OwnedTerm) -> ::core::result::Result<Self, ::erltf::errors::TermConversionError> {
+                #body
+            }
+        }
+    })
+}
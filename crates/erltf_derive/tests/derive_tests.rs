@@ -0,0 +1,223 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use erltf::convert::{FromTerm, ToTerm};
+use erltf::errors::TermConversionError;
+use erltf::{OwnedTerm, erl_atom, erl_int, erl_list, erl_map, erl_tuple};
+use erltf_derive::{FromTerm, ToTerm};
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, ToTerm, FromTerm)]
+struct Settings {
+    name: String,
+    #[term(rename = "max_connections")]
+    limit: u32,
+    #[term(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, ToTerm, FromTerm)]
+#[term(tuple)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+#[derive(Debug, PartialEq, ToTerm, FromTerm)]
+#[term(record = "user")]
+struct User {
+    name: String,
+    age: u8,
+    email: Option<String>,
+}
+
+#[derive(Debug, PartialEq, ToTerm, FromTerm)]
+#[term(elixir_struct = "MyApp.Account")]
+struct Account {
+    id: u64,
+    active: bool,
+}
+
+#[derive(Debug, PartialEq, ToTerm, FromTerm)]
+struct Pair(i32, String);
+
+#[derive(Debug, PartialEq, ToTerm, FromTerm)]
+#[term(transparent)]
+struct UserId(u64);
+
+#[derive(Debug, PartialEq, ToTerm, FromTerm)]
+#[term(record = "ping")]
+struct Ping;
+
+#[derive(Debug, PartialEq, ToTerm, FromTerm)]
+enum Level {
+    #[term(rename = "debug")]
+    Debug,
+    #[term(rename = "info")]
+    Info,
+    Warning,
+}
+
+#[derive(Debug, PartialEq, ToTerm, FromTerm)]
+#[term(tuple)]
+struct Wrapper<T> {
+    value: T,
+    level: Level,
+}
+
+fn roundtrip<T: ToTerm + FromTerm + PartialEq + std::fmt::Debug>(value: T) -> OwnedTerm {
+    let term = value.to_term();
+    assert_eq!(T::from_term(&term).unwrap(), value);
+    term
+}
+
+#[test]
+fn named_struct_as_a_map() {
+    let term = roundtrip(Settings {
+        name: "db".to_string(),
+        limit: 10,
+        tags: vec!["a".to_string()],
+    });
+    assert_eq!(
+        term,
+        erl_map! {
+            erl_atom!("name") => "db",
+            erl_atom!("max_connections") => 10,
+            erl_atom!("tags") => erl_list!["a"],
+        }
+    );
+}
+
+#[test]
+fn named_struct_with_a_missing_default_field() {
+    let term = erl_map! { erl_atom!("name") => "db", erl_atom!("max_connections") => 1 };
+    assert_eq!(
+        Settings::from_term(&term).unwrap(),
+        Settings {
+            name: "db".to_string(),
+            limit: 1,
+            tags: vec![],
+        }
+    );
+}
+
+#[test]
+fn named_struct_with_a_missing_or_invalid_field() {
+    let missing = erl_map! { erl_atom!("name") => "db" };
+    assert_eq!(
+        Settings::from_term(&missing),
+        Err(TermConversionError::MissingField("max_connections"))
+    );
+
+    let invalid = erl_map! { erl_atom!("name") => "db", erl_atom!("max_connections") => -1 };
+    assert_eq!(
+        Settings::from_term(&invalid),
+        Err(TermConversionError::InvalidField {
+            field: "max_connections",
+            reason: Box::new(TermConversionError::OutOfRange),
+        })
+    );
+    assert!(Settings::from_term(&erl_int!(1)).is_err());
+}
+
+#[test]
+fn named_struct_as_a_tuple() {
+    let term = roundtrip(Point { x: 1, y: -2 });
+    assert_eq!(term, erl_tuple![1i64, -2i64]);
+    assert_eq!(
+        Point::from_term(&erl_tuple![1]),
+        Err(TermConversionError::WrongArity {
+            expected: 2,
+            actual: 1
+        })
+    );
+}
+
+#[test]
+fn named_struct_as_a_record() {
+    let term = roundtrip(User {
+        name: "joe".to_string(),
+        age: 42,
+        email: None,
+    });
+    assert_eq!(
+        term,
+        erl_tuple![erl_atom!("user"), "joe", 42, None::<String>.to_term()]
+    );
+
+    let with_email = erl_tuple![erl_atom!("user"), "joe", 42, "joe@example.com"];
+    assert_eq!(
+        User::from_term(&with_email).unwrap().email.as_deref(),
+        Some("joe@example.com")
+    );
+
+    let other_record = erl_tuple![erl_atom!("group"), "joe", 42, erl_atom!("undefined")];
+    assert!(matches!(
+        User::from_term(&other_record),
+        Err(TermConversionError::UnexpectedValue { .. })
+    ));
+}
+
+#[test]
+fn named_struct_as_an_elixir_struct() {
+    let term = roundtrip(Account {
+        id: u64::MAX,
+        active: true,
+    });
+    assert_eq!(term.elixir_struct_module(), Some("Elixir.MyApp.Account"));
+    assert!(matches!(
+        term.map_get_atom_key("id"),
+        Some(OwnedTerm::BigInt(_))
+    ));
+
+    let mut other = BTreeMap::new();
+    other.insert(erl_atom!("__struct__"), erl_atom!("Elixir.MyApp.User"));
+    other.insert(erl_atom!("id"), erl_int!(1));
+    other.insert(erl_atom!("active"), erl_atom!("true"));
+    assert!(Account::from_term(&OwnedTerm::Map(other)).is_err());
+}
+
+#[test]
+fn tuple_transparent_and_unit_structs() {
+    assert_eq!(
+        roundtrip(Pair(7, "seven".to_string())),
+        erl_tuple![7, "seven"]
+    );
+    assert_eq!(roundtrip(UserId(42)), erl_int!(42));
+    assert_eq!(roundtrip(Ping), erl_tuple![erl_atom!("ping")]);
+}
+
+#[test]
+fn unit_enum_as_atoms() {
+    assert_eq!(roundtrip(Level::Debug), erl_atom!("debug"));
+    assert_eq!(roundtrip(Level::Warning), erl_atom!("Warning"));
+    assert!(matches!(
+        Level::from_term(&erl_atom!("error")),
+        Err(TermConversionError::UnexpectedValue { .. })
+    ));
+}
+
+#[test]
+fn generic_struct() {
+    let term = roundtrip(Wrapper {
+        value: vec![(1u8, true)],
+        level: Level::Info,
+    });
+    assert_eq!(
+        term,
+        erl_tuple![
+            erl_list![erl_tuple![1, erl_atom!("true")]],
+            erl_atom!("info")
+        ]
+    );
+}
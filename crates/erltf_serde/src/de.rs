@@ -13,11 +13,14 @@
 // limitations under the License.
 
 use crate::error::{Error, Result};
-use erltf::term::OwnedTerm;
+use erltf::term::{IMPROPER_LIST_TAG, OwnedTerm};
 use erltf::types::Atom;
-use serde::de::{DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::de::{
+    DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor,
+    value,
+};
 use serde::{Deserialize, Deserializer as SerdeDeserializer};
-use std::collections::{BTreeMap, btree_map};
+use std::collections::BTreeMap;
 use std::str;
 use std::sync::OnceLock;
 
@@ -27,12 +30,12 @@ pub fn from_bytes<T: for<'a> Deserialize<'a>>(bytes: &[u8]) -> Result<T> {
 }
 
 pub fn from_term<'a, T: Deserialize<'a>>(term: &'a OwnedTerm) -> Result<T> {
-    let mut deserializer = Deserializer { term };
+    let mut deserializer = Deserializer::new(term);
     T::deserialize(&mut deserializer)
 }
 
 pub fn from_proplist<'a, T: Deserialize<'a>>(term: &'a OwnedTerm) -> Result<T> {
-    match term {
+    match term.resolve() {
         OwnedTerm::List(elements) => {
             let deserializer = ProplistDeserializer::new(elements);
             T::deserialize(deserializer)
@@ -48,11 +51,21 @@ pub fn from_proplist<'a, T: Deserialize<'a>>(term: &'a OwnedTerm) -> Result<T> {
     }
 }
 
+fn byte_list(bytes: &[u8]) -> value::SeqDeserializer<impl Iterator<Item = u8> + '_, Error> {
+    value::SeqDeserializer::new(bytes.iter().copied())
+}
+
 pub struct Deserializer<'de> {
     term: &'de OwnedTerm,
 }
 
 impl<'de> Deserializer<'de> {
+    fn new(term: &'de OwnedTerm) -> Self {
+        Deserializer {
+            term: term.resolve(),
+        }
+    }
+
     fn expect_atom(&self, expected: &str) -> Result<&Atom> {
         match self.term {
             OwnedTerm::Atom(atom) => {
@@ -87,7 +100,8 @@ impl<'de> SerdeDeserializer<'de> for &mut Deserializer<'de> {
             },
             OwnedTerm::Integer(i) => visitor.visit_i64(*i),
             OwnedTerm::Float(f) => visitor.visit_f64(*f),
-            OwnedTerm::Binary(b) => {
+            OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) => {
+                let b = self.term.as_binary().unwrap_or_default();
                 if let Ok(s) = str::from_utf8(b) {
                     visitor.visit_str(s)
                 } else {
@@ -96,9 +110,14 @@ impl<'de> SerdeDeserializer<'de> for &mut Deserializer<'de> {
             }
             OwnedTerm::String(s) => visitor.visit_str(s),
             OwnedTerm::List(l) => visitor.visit_seq(SeqDeserializer::new(l)),
+            OwnedTerm::ByteList(bytes) => visitor.visit_seq(byte_list(bytes)),
             OwnedTerm::Tuple(t) => visitor.visit_seq(SeqDeserializer::new(t)),
             OwnedTerm::Map(m) => visitor.visit_map(MapDeserializer::new(m)),
+            OwnedTerm::OrderedMap(entries) => visitor.visit_map(MapDeserializer::ordered(entries)),
             OwnedTerm::Nil => visitor.visit_seq(SeqDeserializer::new(&[])),
+            OwnedTerm::ImproperList { elements, tail } => {
+                visitor.visit_map(ImproperListDeserializer::new(elements, tail))
+            }
             _ => Err(Error::UnsupportedType(format!("{:?}", self.term))),
         }
     }
@@ -264,6 +283,10 @@ impl<'de> SerdeDeserializer<'de> for &mut Deserializer<'de> {
                 let s = str::from_utf8(b).map_err(|e| Error::InvalidValue(e.to_string()))?;
                 visitor.visit_borrowed_str(s)
             }
+            OwnedTerm::SharedBinary(b) => {
+                let s = str::from_utf8(b).map_err(|e| Error::InvalidValue(e.to_string()))?;
+                visitor.visit_borrowed_str(s)
+            }
             OwnedTerm::String(s) => visitor.visit_borrowed_str(s),
             OwnedTerm::Atom(a) => visitor.visit_borrowed_str(a.as_str()),
             _ => Err(Error::TypeMismatch {
@@ -279,7 +302,8 @@ impl<'de> SerdeDeserializer<'de> for &mut Deserializer<'de> {
 
     fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
         match self.term {
-            OwnedTerm::Binary(b) => visitor.visit_borrowed_bytes(b),
+            OwnedTerm::Binary(b) | OwnedTerm::ByteList(b) => visitor.visit_borrowed_bytes(b),
+            OwnedTerm::SharedBinary(b) => visitor.visit_borrowed_bytes(b),
             _ => Err(Error::TypeMismatch {
                 expected: "binary".into(),
                 found: format!("{:?}", self.term),
@@ -325,6 +349,7 @@ impl<'de> SerdeDeserializer<'de> for &mut Deserializer<'de> {
     fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
         match self.term {
             OwnedTerm::List(l) => visitor.visit_seq(SeqDeserializer::new(l)),
+            OwnedTerm::ByteList(bytes) => visitor.visit_seq(byte_list(bytes)),
             OwnedTerm::Nil => visitor.visit_seq(SeqDeserializer::new(&[])),
             _ => Err(Error::TypeMismatch {
                 expected: "list".into(),
@@ -361,6 +386,7 @@ impl<'de> SerdeDeserializer<'de> for &mut Deserializer<'de> {
     fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
         match self.term {
             OwnedTerm::Map(m) => visitor.visit_map(MapDeserializer::new(m)),
+            OwnedTerm::OrderedMap(entries) => visitor.visit_map(MapDeserializer::ordered(entries)),
             _ => Err(Error::TypeMismatch {
                 expected: "map".into(),
                 found: format!("{:?}", self.term),
@@ -420,7 +446,7 @@ impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
     fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
         match self.iter.next() {
             Some(term) => {
-                let mut de = Deserializer { term };
+                let mut de = Deserializer::new(term);
                 seed.deserialize(&mut de).map(Some)
             }
             None => Ok(None),
@@ -429,14 +455,21 @@ impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
 }
 
 struct MapDeserializer<'de> {
-    iter: btree_map::Iter<'de, OwnedTerm, OwnedTerm>,
+    iter: Box<dyn Iterator<Item = (&'de OwnedTerm, &'de OwnedTerm)> + 'de>,
     value: Option<&'de OwnedTerm>,
 }
 
 impl<'de> MapDeserializer<'de> {
     fn new(map: &'de BTreeMap<OwnedTerm, OwnedTerm>) -> Self {
         MapDeserializer {
-            iter: map.iter(),
+            iter: Box::new(map.iter()),
+            value: None,
+        }
+    }
+
+    fn ordered(entries: &'de [(OwnedTerm, OwnedTerm)]) -> Self {
+        MapDeserializer {
+            iter: Box::new(entries.iter().map(|(k, v)| (k, v))),
             value: None,
         }
     }
@@ -449,7 +482,7 @@ impl<'de> MapAccess<'de> for MapDeserializer<'de> {
         match self.iter.next() {
             Some((key, value)) => {
                 self.value = Some(value);
-                let mut de = Deserializer { term: key };
+                let mut de = Deserializer::new(key);
                 seed.deserialize(&mut de).map(Some)
             }
             None => Ok(None),
@@ -459,7 +492,7 @@ impl<'de> MapAccess<'de> for MapDeserializer<'de> {
     fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
         match self.value.take() {
             Some(value) => {
-                let mut de = Deserializer { term: value };
+                let mut de = Deserializer::new(value);
                 seed.deserialize(&mut de)
             }
             None => Err(Error::Message("next_value called without next_key".into())),
@@ -467,6 +500,59 @@ impl<'de> MapAccess<'de> for MapDeserializer<'de> {
     }
 }
 
+/// Presents an improper list as a single-entry map tagged with
+/// [`IMPROPER_LIST_TAG`] whose value is the `(elements, tail)` pair.
+struct ImproperListDeserializer<'de> {
+    elements: Option<&'de [OwnedTerm]>,
+    tail: Option<&'de OwnedTerm>,
+    key_emitted: bool,
+}
+
+impl<'de> ImproperListDeserializer<'de> {
+    fn new(elements: &'de [OwnedTerm], tail: &'de OwnedTerm) -> Self {
+        ImproperListDeserializer {
+            elements: Some(elements),
+            tail: Some(tail),
+            key_emitted: false,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for ImproperListDeserializer<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.key_emitted {
+            return Ok(None);
+        }
+        self.key_emitted = true;
+        let key: value::StrDeserializer<'_, Error> = IMPROPER_LIST_TAG.into_deserializer();
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(value::SeqAccessDeserializer::new(self))
+    }
+}
+
+impl<'de> SeqAccess<'de> for &mut ImproperListDeserializer<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if let Some(elements) = self.elements.take() {
+            let de = value::SeqAccessDeserializer::new(SeqDeserializer::new(elements));
+            return seed.deserialize(de).map(Some);
+        }
+        match self.tail.take() {
+            Some(term) => {
+                let mut de = Deserializer::new(term);
+                seed.deserialize(&mut de).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
 struct EnumDeserializer<'de> {
     term: &'de OwnedTerm,
 }
@@ -478,12 +564,12 @@ impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
     fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant)> {
         match self.term {
             OwnedTerm::Atom(_) => {
-                let mut de = Deserializer { term: self.term };
+                let mut de = Deserializer::new(self.term);
                 let val = seed.deserialize(&mut de)?;
                 Ok((val, VariantDeserializer { rest: &[] }))
             }
             OwnedTerm::Tuple(elements) if !elements.is_empty() => {
-                let mut de = Deserializer { term: &elements[0] };
+                let mut de = Deserializer::new(&elements[0]);
                 let val = seed.deserialize(&mut de)?;
                 let rest = if elements.len() > 1 {
                     &elements[1..]
@@ -520,9 +606,7 @@ impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
 
     fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
         if self.rest.len() == 1 {
-            let mut de = Deserializer {
-                term: &self.rest[0],
-            };
+            let mut de = Deserializer::new(&self.rest[0]);
             seed.deserialize(&mut de)
         } else {
             Err(Error::TypeMismatch {
@@ -542,8 +626,11 @@ impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
         visitor: V,
     ) -> Result<V::Value> {
         if self.rest.len() == 1 {
-            match &self.rest[0] {
+            match self.rest[0].resolve() {
                 OwnedTerm::Map(m) => visitor.visit_map(MapDeserializer::new(m)),
+                OwnedTerm::OrderedMap(entries) => {
+                    visitor.visit_map(MapDeserializer::ordered(entries))
+                }
                 _ => Err(Error::TypeMismatch {
                     expected: "struct variant (map)".into(),
                     found: format!("{:?}", self.rest[0]),
@@ -620,18 +707,18 @@ impl<'de> MapAccess<'de> for ProplistMapAccess<'de> {
 
     fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
         while self.index < self.elements.len() {
-            let element = &self.elements[self.index];
+            let element = self.elements[self.index].resolve();
             self.index += 1;
 
             match element {
                 OwnedTerm::Tuple(t) if t.len() == 2 => {
                     self.current_value = ProplistValue::Ref(&t[1]);
-                    let mut de = Deserializer { term: &t[0] };
+                    let mut de = Deserializer::new(&t[0]);
                     return seed.deserialize(&mut de).map(Some);
                 }
                 OwnedTerm::Atom(_) => {
                     self.current_value = ProplistValue::BareAtom;
-                    let mut de = Deserializer { term: element };
+                    let mut de = Deserializer::new(element);
                     return seed.deserialize(&mut de).map(Some);
                 }
                 _ => continue,
@@ -643,13 +730,13 @@ impl<'de> MapAccess<'de> for ProplistMapAccess<'de> {
     fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
         match std::mem::replace(&mut self.current_value, ProplistValue::None) {
             ProplistValue::Ref(value) => {
-                let mut de = Deserializer { term: value };
+                let mut de = Deserializer::new(value);
                 seed.deserialize(&mut de)
             }
             ProplistValue::BareAtom => {
                 static TRUE_TERM: OnceLock<OwnedTerm> = OnceLock::new();
                 let true_term = TRUE_TERM.get_or_init(|| OwnedTerm::Atom(Atom::new("true")));
-                let mut de = Deserializer { term: true_term };
+                let mut de = Deserializer::new(true_term);
                 seed.deserialize(&mut de)
             }
             ProplistValue::None => Err(Error::Message("next_value called without next_key".into())),
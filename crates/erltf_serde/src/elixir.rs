@@ -30,8 +30,18 @@
 //!     age: i32,
 //! }
 //! ```
+//!
+//! [`StructRegistry`] maps `__struct__` module names to decoders, so that
+//! streams of heterogeneous structs can be turned into typed values.
 
+use crate::de::from_term;
+use crate::error;
+use erltf::OwnedTerm;
 use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
 
 pub const ATOM_KEY_MARKER: &str = "__erltf_atom_key__";
 pub const ATOM_VALUE_MARKER: &str = "__erltf_atom_value__";
@@ -67,3 +77,152 @@ impl Serialize for AtomValue<'_> {
         serializer.serialize_newtype_struct(ATOM_VALUE_MARKER, self.0)
     }
 }
+
+/// Implemented by the `ElixirStruct` derive macro.
+pub trait ElixirModule {
+    /// The full module name, including the `Elixir.` prefix.
+    const MODULE: &'static str;
+}
+
+type StructDecoder<T> = Box<dyn Fn(&OwnedTerm) -> error::Result<T> + Send + Sync>;
+
+/// Maps Elixir struct module names to decoders that produce a `T`.
+///
+/// `T` is usually an application enum with one variant per struct. Use
+/// [`AnyStructRegistry`] when the set of types is open-ended.
+pub struct StructRegistry<T> {
+    decoders: HashMap<String, StructDecoder<T>>,
+}
+
+/// A registry that decodes structs into type-erased values.
+pub type AnyStructRegistry = StructRegistry<DecodedStruct>;
+
+impl<T> StructRegistry<T> {
+    pub fn new() -> Self {
+        Self {
+            decoders: HashMap::new(),
+        }
+    }
+
+    /// Registers `S` under its `ElixirStruct` module name, converting decoded
+    /// values into `T`.
+    pub fn register<S>(&mut self) -> &mut Self
+    where
+        S: ElixirModule + DeserializeOwned + Into<T> + 'static,
+    {
+        self.register_fn(S::MODULE, |term| from_term::<S>(term).map(Into::into))
+    }
+
+    /// Registers a decoder function for a module name such as `Elixir.MyApp.User`.
+    pub fn register_fn<F>(&mut self, module: impl Into<String>, decoder: F) -> &mut Self
+    where
+        F: Fn(&OwnedTerm) -> error::Result<T> + Send + Sync + 'static,
+    {
+        self.decoders.insert(module.into(), Box::new(decoder));
+        self
+    }
+
+    pub fn unregister(&mut self, module: &str) -> bool {
+        self.decoders.remove(module).is_some()
+    }
+
+    #[must_use]
+    pub fn contains(&self, module: &str) -> bool {
+        self.decoders.contains_key(module)
+    }
+
+    pub fn modules(&self) -> impl Iterator<Item = &str> {
+        self.decoders.keys().map(String::as_str)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.decoders.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.decoders.is_empty()
+    }
+
+    /// Decodes `term` with the decoder registered for its `__struct__` module.
+    ///
+    /// Returns `None` if `term` is not an Elixir struct or its module is not registered.
+    pub fn decode(&self, term: &OwnedTerm) -> Option<error::Result<T>> {
+        let module = term.elixir_struct_module()?;
+        let decoder = self.decoders.get(module)?;
+        Some(decoder(term))
+    }
+}
+
+impl AnyStructRegistry {
+    /// Registers `S` under its `ElixirStruct` module name as a type-erased value.
+    pub fn register_any<S>(&mut self) -> &mut Self
+    where
+        S: ElixirModule + DeserializeOwned + Send + 'static,
+    {
+        self.register_fn(S::MODULE, |term| {
+            from_term::<S>(term).map(|value| DecodedStruct::new(S::MODULE, value))
+        })
+    }
+}
+
+impl<T> Default for StructRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for StructRegistry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StructRegistry")
+            .field("modules", &self.decoders.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// A decoded struct value produced by an [`AnyStructRegistry`].
+pub struct DecodedStruct {
+    module: &'static str,
+    value: Box<dyn Any + Send>,
+}
+
+impl DecodedStruct {
+    pub fn new<S: Any + Send>(module: &'static str, value: S) -> Self {
+        Self {
+            module,
+            value: Box::new(value),
+        }
+    }
+
+    #[must_use]
+    pub fn module(&self) -> &'static str {
+        self.module
+    }
+
+    #[must_use]
+    pub fn is<S: Any>(&self) -> bool {
+        self.value.is::<S>()
+    }
+
+    pub fn downcast_ref<S: Any>(&self) -> Option<&S> {
+        self.value.downcast_ref::<S>()
+    }
+
+    /// Returns the value as `S`, or `self` unchanged if it is a different type.
+    pub fn downcast<S: Any>(self) -> Result<S, Self> {
+        if self.value.is::<S>() {
+            Ok(*self.value.downcast::<S>().expect("type was just checked"))
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Debug for DecodedStruct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodedStruct")
+            .field("module", &self.module)
+            .finish_non_exhaustive()
+    }
+}
@@ -321,7 +321,7 @@ impl ser::SerializeMap for SerializeMap {
     }
 
     fn end(self) -> Result<OwnedTerm> {
-        Ok(OwnedTerm::Map(self.map))
+        Ok(OwnedTerm::Map(self.map).untag_improper_list())
     }
 }
 
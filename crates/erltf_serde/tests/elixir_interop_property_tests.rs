@@ -57,7 +57,7 @@ proptest! {
     }
 
     #[test]
-    fn prop_roundtrip_option_f64(value in any::<Option<f64>>().prop_filter("finite floats only", |v| v.map_or(true, |f| f.is_finite()))) {
+    fn prop_roundtrip_option_f64(value in any::<Option<f64>>().prop_filter("finite floats only", |v| v.is_none_or(|f| f.is_finite()))) {
         let term = to_term(&value).unwrap();
         let result: Option<f64> = from_term(&term).unwrap();
         prop_assert_eq!(value, result);
@@ -0,0 +1,91 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::{IMPROPER_LIST_TAG, OwnedTerm};
+use erltf_serde::{from_term, to_term};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Wrapper {
+    data: OwnedTerm,
+}
+
+fn sample() -> OwnedTerm {
+    OwnedTerm::improper_list(
+        vec![OwnedTerm::Integer(1), OwnedTerm::Binary(b"two".to_vec())],
+        OwnedTerm::Integer(3),
+    )
+}
+
+#[test]
+fn test_improper_list_serializes_as_tagged_pair() {
+    let json = serde_json::to_value(sample()).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({ IMPROPER_LIST_TAG: [[1, [116, 119, 111]], 3] })
+    );
+}
+
+#[test]
+fn test_improper_list_json_roundtrip() {
+    let json = serde_json::to_string(&sample()).unwrap();
+    let back: OwnedTerm = serde_json::from_str(&json).unwrap();
+    let (elements, tail) = back.as_improper().unwrap();
+    assert_eq!(elements.len(), 2);
+    assert_eq!(elements[0], OwnedTerm::Integer(1));
+    assert_eq!(tail, &OwnedTerm::Integer(3));
+}
+
+#[test]
+fn test_improper_list_to_term_roundtrip() {
+    let term = sample();
+    assert_eq!(to_term(&term).unwrap(), term);
+    assert_eq!(from_term::<OwnedTerm>(&term).unwrap(), term);
+}
+
+#[test]
+fn test_improper_list_nested_in_struct_roundtrip() {
+    let wrapper = Wrapper { data: sample() };
+    let term = to_term(&wrapper).unwrap();
+    assert_eq!(term.map_get_binary_key("data"), Some(&sample()));
+    let back: Wrapper = from_term(&term).unwrap();
+    assert_eq!(back, wrapper);
+}
+
+#[test]
+fn test_improper_list_into_json_value() {
+    let value: serde_json::Value = from_term(&sample()).unwrap();
+    assert_eq!(
+        value,
+        serde_json::json!({ IMPROPER_LIST_TAG: [[1, "two"], 3] })
+    );
+}
+
+#[test]
+fn test_map_with_other_keys_is_not_untagged() {
+    let mut m = BTreeMap::new();
+    m.insert(
+        OwnedTerm::Binary(IMPROPER_LIST_TAG.as_bytes().to_vec()),
+        OwnedTerm::Integer(1),
+    );
+    let term = OwnedTerm::Map(m);
+    assert_eq!(to_term(&term).unwrap(), term);
+    assert_eq!(from_term::<OwnedTerm>(&term).unwrap(), term);
+}
+
+#[test]
+fn test_improper_list_into_proper_sequence_is_an_error() {
+    assert!(from_term::<Vec<i64>>(&sample()).is_err());
+}
@@ -202,9 +202,9 @@ fn test_deserialize_negative_integer_to_owned_term() {
 
 #[test]
 fn test_deserialize_float_to_owned_term() {
-    let term = to_term(&3.14f64).unwrap();
+    let term = to_term(&2.5f64).unwrap();
     let result: OwnedTerm = from_term(&term).unwrap();
-    assert_eq!(result, OwnedTerm::Float(3.14));
+    assert_eq!(result, OwnedTerm::Float(2.5));
 }
 
 #[test]
@@ -328,7 +328,7 @@ fn test_deserialize_to_vec_of_owned_terms_mixed() {
     let items: Vec<OwnedTerm> = vec![
         OwnedTerm::Integer(42),
         OwnedTerm::Binary(b"test".to_vec()),
-        OwnedTerm::Float(3.14),
+        OwnedTerm::Float(2.5),
     ];
 
     let original = MixedResult { items };
@@ -338,7 +338,7 @@ fn test_deserialize_to_vec_of_owned_terms_mixed() {
     assert_eq!(result.items.len(), 3);
     assert_eq!(result.items[0], OwnedTerm::Integer(42));
     assert_eq!(result.items[1], OwnedTerm::Binary(b"test".to_vec()));
-    assert_eq!(result.items[2], OwnedTerm::Float(3.14));
+    assert_eq!(result.items[2], OwnedTerm::Float(2.5));
 }
 
 // Option<OwnedTerm>
@@ -547,27 +547,20 @@ fn test_tuple_becomes_list_on_roundtrip() {
 }
 
 #[test]
-fn test_improper_list_becomes_list_on_roundtrip() {
-    // ImproperList flattens to regular List through serde.
+fn test_improper_list_survives_roundtrip() {
+    let improper = OwnedTerm::ImproperList {
+        elements: vec![OwnedTerm::Integer(1), OwnedTerm::Integer(2)],
+        tail: Box::new(OwnedTerm::Integer(3)),
+    };
     let payload = Payload {
         op: 1,
-        d: OwnedTerm::ImproperList {
-            elements: vec![OwnedTerm::Integer(1), OwnedTerm::Integer(2)],
-            tail: Box::new(OwnedTerm::Integer(3)),
-        },
+        d: improper.clone(),
     };
 
     let term = to_term(&payload).unwrap();
     let result: Payload = from_term(&term).unwrap();
 
-    assert_eq!(
-        result.d,
-        OwnedTerm::List(vec![
-            OwnedTerm::Integer(1),
-            OwnedTerm::Integer(2),
-            OwnedTerm::Integer(3)
-        ])
-    );
+    assert_eq!(result.d, improper);
 }
 
 #[test]
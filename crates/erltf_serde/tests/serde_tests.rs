@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use erltf::OwnedTerm;
 use erltf_serde::{from_bytes, from_term, to_bytes, to_term};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -249,6 +250,30 @@ fn test_to_term_from_term() {
     assert_eq!(val, result);
 }
 
+#[test]
+fn test_from_term_accepts_ordered_maps() {
+    let val = SimpleStruct {
+        name: "Dana".to_string(),
+        age: 41,
+        active: false,
+    };
+    let entries = to_term(&val).unwrap().as_map().unwrap().clone();
+    let term = OwnedTerm::ordered_map(entries.into_iter().rev().collect());
+
+    let result: SimpleStruct = from_term(&term).unwrap();
+    assert_eq!(val, result);
+}
+
+#[test]
+fn test_from_term_accepts_byte_lists() {
+    let term = OwnedTerm::byte_list(vec![1, 2, 255]);
+
+    let result: Vec<u8> = from_term(&term).unwrap();
+    assert_eq!(result, vec![1, 2, 255]);
+    let result: Vec<i64> = from_term(&term).unwrap();
+    assert_eq!(result, vec![1, 2, 255]);
+}
+
 //
 // Edge Cases
 //
@@ -0,0 +1,147 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::{OwnedTerm, erl_atom, erl_int, erl_map};
+use erltf_serde::elixir::{AnyStructRegistry, ElixirModule, StructRegistry};
+use erltf_serde::{ElixirStruct, to_term};
+
+#[derive(Debug, PartialEq, ElixirStruct)]
+#[elixir_module = "MyApp.UserCreated"]
+struct UserCreated {
+    name: String,
+    age: i32,
+}
+
+#[derive(Debug, PartialEq, ElixirStruct)]
+#[elixir_module = "MyApp.UserDeleted"]
+struct UserDeleted {
+    id: i64,
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Created(UserCreated),
+    Deleted(UserDeleted),
+    Other(String),
+}
+
+impl From<UserCreated> for Event {
+    fn from(value: UserCreated) -> Self {
+        Event::Created(value)
+    }
+}
+
+impl From<UserDeleted> for Event {
+    fn from(value: UserDeleted) -> Self {
+        Event::Deleted(value)
+    }
+}
+
+fn created() -> OwnedTerm {
+    to_term(&UserCreated {
+        name: "Alice".to_string(),
+        age: 30,
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_derive_implements_elixir_module() {
+    assert_eq!(UserCreated::MODULE, "Elixir.MyApp.UserCreated");
+    assert_eq!(UserDeleted::MODULE, "Elixir.MyApp.UserDeleted");
+}
+
+#[test]
+fn test_enum_registry_dispatches_on_struct_module() {
+    let mut registry: StructRegistry<Event> = StructRegistry::new();
+    registry
+        .register::<UserCreated>()
+        .register::<UserDeleted>()
+        .register_fn("Elixir.MyApp.Custom", |term| {
+            Ok(Event::Other(
+                term.map_get_atom_key("label")
+                    .and_then(|t| t.as_erlang_string())
+                    .unwrap_or_default(),
+            ))
+        });
+    assert_eq!(registry.len(), 3);
+    assert!(registry.contains("Elixir.MyApp.UserDeleted"));
+
+    let decoded = registry.decode(&created()).unwrap().unwrap();
+    assert_eq!(
+        decoded,
+        Event::Created(UserCreated {
+            name: "Alice".to_string(),
+            age: 30
+        })
+    );
+
+    let deleted = to_term(&UserDeleted { id: 7 }).unwrap();
+    assert_eq!(
+        registry.decode(&deleted).unwrap().unwrap(),
+        Event::Deleted(UserDeleted { id: 7 })
+    );
+
+    let custom = erl_map! {
+        erl_atom!("__struct__") => erl_atom!("Elixir.MyApp.Custom"),
+        erl_atom!("label") => OwnedTerm::binary(b"hi".to_vec())
+    };
+    assert_eq!(
+        registry.decode(&custom).unwrap().unwrap(),
+        Event::Other("hi".to_string())
+    );
+}
+
+#[test]
+fn test_registry_returns_none_for_unknown_terms() {
+    let mut registry: StructRegistry<Event> = StructRegistry::new();
+    registry.register::<UserCreated>();
+
+    let unknown = erl_map! { erl_atom!("__struct__") => erl_atom!("Elixir.Unknown") };
+    assert!(registry.decode(&unknown).is_none());
+    assert!(registry.decode(&erl_int!(1)).is_none());
+
+    assert!(registry.unregister("Elixir.MyApp.UserCreated"));
+    assert!(registry.is_empty());
+    assert!(registry.decode(&created()).is_none());
+}
+
+#[test]
+fn test_registry_reports_decoder_errors() {
+    let mut registry: StructRegistry<Event> = StructRegistry::new();
+    registry.register::<UserCreated>();
+
+    let malformed = erl_map! {
+        erl_atom!("__struct__") => erl_atom!("Elixir.MyApp.UserCreated"),
+        erl_atom!("name") => OwnedTerm::binary(b"Alice".to_vec())
+    };
+    assert!(registry.decode(&malformed).unwrap().is_err());
+}
+
+#[test]
+fn test_any_registry_downcasts() {
+    let mut registry = AnyStructRegistry::new();
+    registry
+        .register_any::<UserCreated>()
+        .register_any::<UserDeleted>();
+
+    let decoded = registry.decode(&created()).unwrap().unwrap();
+    assert_eq!(decoded.module(), "Elixir.MyApp.UserCreated");
+    assert!(decoded.is::<UserCreated>());
+    assert_eq!(decoded.downcast_ref::<UserCreated>().unwrap().age, 30);
+
+    let decoded = decoded.downcast::<UserDeleted>().unwrap_err();
+    let user = decoded.downcast::<UserCreated>().unwrap();
+    assert_eq!(user.name, "Alice");
+}
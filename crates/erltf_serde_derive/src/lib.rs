@@ -89,6 +89,10 @@ pub fn derive_elixir_struct(input: TokenStream) -> TokenStream {
     );
 
     let expanded = quote! {
+        impl #impl_generics erltf_serde::elixir::ElixirModule for #name #ty_generics #where_clause {
+            const MODULE: &'static str = #full_module_name;
+        }
+
         #serialize_impl
         #deserialize_impl
     };
@@ -0,0 +1,56 @@
+%% Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+%%
+%% Licensed under the Apache License, Version 2.0 (the "License");
+%% you may not use this file except in compliance with the License.
+%% You may obtain a copy of the License at
+%%
+%% http://www.apache.org/licenses/LICENSE-2.0
+%%
+%% Unless required by applicable law or agreed to in writing, software
+%% distributed under the License is distributed on an "AS IS" BASIS,
+%% WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+%% See the License for the specific language governing permissions and
+%% limitations under the License.
+
+%% The Erlang side of the interop tests. The registered process records
+%% everything it receives so that tests can assert on what arrived.
+-module(edp_it_peer).
+-export([start/0, received/0, spawn_worker/0, is_alive/1]).
+
+start() ->
+    Pid = spawn(fun() -> loop([]) end),
+    true = register(edp_it_peer, Pid),
+    ok.
+
+received() ->
+    edp_it_peer ! {received, self()},
+    receive
+        {received, Terms} -> Terms
+    after 5000 ->
+        timeout
+    end.
+
+%% Exits with the given reason on `{stop, Reason}`.
+spawn_worker() ->
+    spawn(fun() ->
+                  receive
+                      {stop, Reason} -> exit(Reason)
+                  end
+          end).
+
+is_alive(Pid) ->
+    erlang:is_process_alive(Pid).
+
+loop(Received) ->
+    receive
+        {received, From} ->
+            From ! {received, lists:reverse(Received)},
+            loop(Received);
+        {echo, From, Term} = Msg ->
+            From ! {echo, node(), Term},
+            loop([Msg | Received]);
+        halt ->
+            erlang:halt(0);
+        Other ->
+            loop([Other | Received])
+    end.
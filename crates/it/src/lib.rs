@@ -0,0 +1,255 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A harness for interop tests against a real Erlang node.
+//!
+//! The tests only run when `EDP_TEST_ERLANG=1`. The peer is started with a local `erl`
+//! unless `EDP_TEST_ERLANG_DOCKER_IMAGE` names an image (e.g. `erlang:27`) to run it in.
+
+use anyhow::{Context, Result, bail};
+use edp_node::Node;
+use erltf::OwnedTerm;
+use erltf::types::ExternalPid;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{self, Child, Command, Stdio};
+use std::time::Duration;
+use tokio::time::sleep;
+
+pub const ENABLE_ENV_VAR: &str = "EDP_TEST_ERLANG";
+pub const DOCKER_IMAGE_ENV_VAR: &str = "EDP_TEST_ERLANG_DOCKER_IMAGE";
+pub const COOKIE: &str = "edp_it_cookie";
+pub const HOST: &str = "127.0.0.1";
+pub const PEER_MODULE: &str = "edp_it_peer";
+
+const READY_ATTEMPTS: u32 = 100;
+const READY_DELAY: Duration = Duration::from_millis(100);
+
+/// Returns early from a test unless `EDP_TEST_ERLANG=1`.
+#[macro_export]
+macro_rules! require_erlang {
+    () => {
+        if !$crate::enabled() {
+            eprintln!(
+                "Skipping: set {}=1 to run against a real Erlang node",
+                $crate::ENABLE_ENV_VAR
+            );
+            return;
+        }
+    };
+}
+
+pub fn enabled() -> bool {
+    env::var(ENABLE_ENV_VAR).is_ok_and(|v| v == "1")
+}
+
+/// Long node names on the loopback address work the same way on the host
+/// and in a container that uses host networking.
+pub fn node_name(prefix: &str) -> String {
+    format!("{}_{}@{}", prefix, process::id(), HOST)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerRuntime {
+    Local,
+    Docker { image: String },
+}
+
+impl PeerRuntime {
+    pub fn from_env() -> Self {
+        match env::var(DOCKER_IMAGE_ENV_VAR) {
+            Ok(image) if !image.is_empty() => PeerRuntime::Docker { image },
+            _ => PeerRuntime::Local,
+        }
+    }
+}
+
+/// An Erlang node running `edp_it_peer`. Stopped on drop.
+pub struct ErlangPeer {
+    child: Child,
+    node_name: String,
+    container: Option<String>,
+    work_dir: PathBuf,
+}
+
+impl ErlangPeer {
+    pub fn start(prefix: &str) -> Result<Self> {
+        Self::start_with(prefix, PeerRuntime::from_env())
+    }
+
+    pub fn start_with(prefix: &str, runtime: PeerRuntime) -> Result<Self> {
+        let node_name = node_name(prefix);
+        let work_dir = env::temp_dir().join(format!("edp_it_{}_{}", prefix, process::id()));
+        fs::create_dir_all(&work_dir)?;
+        let source = work_dir.join(format!("{}.erl", PEER_MODULE));
+        fs::copy(peer_source(), &source)?;
+
+        let eval = format!("{}:start()", PEER_MODULE);
+        let (mut command, container) = match runtime {
+            PeerRuntime::Local => {
+                let status = Command::new("erlc")
+                    .arg("-o")
+                    .arg(&work_dir)
+                    .arg(&source)
+                    .status()
+                    .context("failed to run erlc")?;
+                if !status.success() {
+                    bail!("erlc failed to compile {}", source.display());
+                }
+
+                let mut command = Command::new("erl");
+                command
+                    .args(["-noshell", "-name", &node_name, "-setcookie", COOKIE, "-pa"])
+                    .arg(&work_dir)
+                    .args(["-eval", &eval]);
+                (command, None)
+            }
+            PeerRuntime::Docker { image } => {
+                let container = format!("edp_it_{}_{}", prefix, process::id());
+                let script = format!(
+                    "erlc -o /tmp /edp_it/{module}.erl && exec erl -noshell -name {node_name} \
+                     -setcookie {COOKIE} -pa /tmp -eval '{eval}'",
+                    module = PEER_MODULE,
+                );
+                let mut command = Command::new("docker");
+                command
+                    .args(["run", "--rm", "--name", &container, "--network", "host"])
+                    .arg("-v")
+                    .arg(format!("{}:/edp_it:ro", work_dir.display()))
+                    .args([image.as_str(), "sh", "-c", &script]);
+                (command, Some(container))
+            }
+        };
+
+        let child = command
+            .stdin(Stdio::null())
+            .spawn()
+            .with_context(|| format!("failed to start Erlang peer {}", node_name))?;
+
+        Ok(Self {
+            child,
+            node_name,
+            container,
+            work_dir,
+        })
+    }
+
+    pub fn node_name(&self) -> &str {
+        &self.node_name
+    }
+}
+
+impl Drop for ErlangPeer {
+    fn drop(&mut self) {
+        if let Some(container) = &self.container {
+            let _ = Command::new("docker")
+                .args(["rm", "-f", container])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_dir_all(&self.work_dir);
+    }
+}
+
+/// A started Rust node connected to a running [`ErlangPeer`].
+pub struct InteropContext {
+    pub node: Node,
+    pub peer: ErlangPeer,
+    /// The pid of the `edp_it_peer` registered process.
+    pub peer_pid: ExternalPid,
+}
+
+impl InteropContext {
+    pub async fn start(prefix: &str) -> Result<Self> {
+        let peer = ErlangPeer::start(&format!("{}_erl", prefix))?;
+        // The peer starts EPMD, so registration can only succeed once it is up
+        let node = retry(|| async {
+            let mut node = Node::new(node_name(&format!("{}_rs", prefix)), COOKIE);
+            node.start(0).await?;
+            Ok(node)
+        })
+        .await
+        .context("failed to register with EPMD")?;
+
+        retry(|| async { Ok(node.connect(peer.node_name()).await?) })
+            .await
+            .with_context(|| format!("failed to connect to {}", peer.node_name()))?;
+
+        let peer_pid = retry(|| async {
+            match node
+                .rpc_call(
+                    peer.node_name(),
+                    "erlang",
+                    "whereis",
+                    vec![OwnedTerm::atom(PEER_MODULE)],
+                )
+                .await?
+            {
+                OwnedTerm::Pid(pid) => Ok(pid),
+                other => bail!("{} is not registered yet: {}", PEER_MODULE, other),
+            }
+        })
+        .await?;
+
+        Ok(Self {
+            node,
+            peer,
+            peer_pid,
+        })
+    }
+
+    pub fn peer_node(&self) -> &str {
+        self.peer.node_name()
+    }
+
+    pub async fn rpc(
+        &self,
+        module: &str,
+        function: &str,
+        args: Vec<OwnedTerm>,
+    ) -> Result<OwnedTerm> {
+        Ok(self
+            .node
+            .rpc_call(self.peer_node(), module, function, args)
+            .await?)
+    }
+}
+
+fn peer_source() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("erl")
+        .join(format!("{}.erl", PEER_MODULE))
+}
+
+async fn retry<T, F, Fut>(mut f: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= READY_ATTEMPTS => return Err(e),
+            Err(_) => {
+                attempt += 1;
+                sleep(READY_DELAY).await;
+            }
+        }
+    }
+}
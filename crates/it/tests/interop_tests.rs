@@ -0,0 +1,260 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! End-to-end scenarios against a real Erlang node.
+//!
+//! Run with `EDP_TEST_ERLANG=1 cargo test -p it`. Set `EDP_TEST_ERLANG_DOCKER_IMAGE=erlang:27`
+//! to start the peer in a container instead of with a local `erl`.
+
+use edp_node::{Message, NodeEvent, Process, Result};
+use erltf::OwnedTerm;
+use erltf::types::{Atom, ExternalPid};
+use it::{InteropContext, require_erlang};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use tokio::time::timeout;
+
+const RECEIVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Collector {
+    tx: UnboundedSender<Message>,
+}
+
+impl Process for Collector {
+    async fn handle_message(&mut self, msg: Message) -> Result<()> {
+        let _ = self.tx.send(msg);
+        Ok(())
+    }
+}
+
+async fn spawn_collector(ctx: &InteropContext) -> (ExternalPid, UnboundedReceiver<Message>) {
+    let (tx, rx) = unbounded_channel();
+    let pid = ctx.node.spawn(Collector { tx }).await.unwrap();
+    (pid, rx)
+}
+
+async fn next_message(rx: &mut UnboundedReceiver<Message>) -> Message {
+    timeout(RECEIVE_TIMEOUT, rx.recv())
+        .await
+        .expect("timed out waiting for a message")
+        .expect("collector mailbox closed")
+}
+
+fn sample_terms() -> Vec<OwnedTerm> {
+    vec![
+        OwnedTerm::atom("hello"),
+        OwnedTerm::atom("héllo_wörld"),
+        OwnedTerm::integer(-42),
+        OwnedTerm::integer(1_000_000),
+        OwnedTerm::float(3.5),
+        OwnedTerm::binary(b"edp".to_vec()),
+        OwnedTerm::Nil,
+        OwnedTerm::tuple(vec![OwnedTerm::atom("ok"), OwnedTerm::binary(vec![0, 255])]),
+        OwnedTerm::list(vec![OwnedTerm::atom("a"), OwnedTerm::float(1.0)]),
+        OwnedTerm::map(BTreeMap::from([(
+            OwnedTerm::atom("key"),
+            OwnedTerm::binary(b"value".to_vec()),
+        )])),
+    ]
+}
+
+//
+// Handshake
+//
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_handshake_is_visible_on_both_sides() {
+    require_erlang!();
+    let ctx = InteropContext::start("handshake").await.unwrap();
+
+    assert!(ctx.node.connections().contains_key(ctx.peer_node()));
+
+    let peer = ctx.rpc("erlang", "node", vec![]).await.unwrap();
+    assert_eq!(peer, OwnedTerm::atom(ctx.peer_node()));
+
+    let nodes = ctx.rpc("erlang", "nodes", vec![]).await.unwrap();
+    let nodes = nodes.as_list().expect("nodes/0 returns a list");
+    assert!(nodes.contains(&OwnedTerm::Atom(ctx.node.name().clone())));
+}
+
+//
+// Send and Receive
+//
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_send_and_receive_echo() {
+    require_erlang!();
+    let ctx = InteropContext::start("echo").await.unwrap();
+    let (collector, mut rx) = spawn_collector(&ctx).await;
+
+    let mut sent = Vec::new();
+    for term in sample_terms() {
+        let msg = OwnedTerm::tuple(vec![
+            OwnedTerm::atom("echo"),
+            OwnedTerm::Pid(collector.clone()),
+            term.clone(),
+        ]);
+        ctx.node.send(&ctx.peer_pid, msg.clone()).await.unwrap();
+        sent.push(msg);
+
+        match next_message(&mut rx).await {
+            Message::Regular { body, .. } => assert_eq!(
+                body,
+                OwnedTerm::tuple(vec![
+                    OwnedTerm::atom("echo"),
+                    OwnedTerm::atom(ctx.peer_node()),
+                    term,
+                ])
+            ),
+            other => panic!("expected an echo, got {:?}", other),
+        }
+    }
+
+    let received = ctx.rpc("edp_it_peer", "received", vec![]).await.unwrap();
+    assert_eq!(received, OwnedTerm::list(sent));
+}
+
+//
+// RPC
+//
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_round_trips() {
+    require_erlang!();
+    let ctx = InteropContext::start("rpc").await.unwrap();
+
+    let reversed = ctx
+        .rpc(
+            "lists",
+            "reverse",
+            vec![OwnedTerm::list(vec![
+                OwnedTerm::atom("a"),
+                OwnedTerm::atom("b"),
+                OwnedTerm::atom("c"),
+            ])],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        reversed,
+        OwnedTerm::list(vec![
+            OwnedTerm::atom("c"),
+            OwnedTerm::atom("b"),
+            OwnedTerm::atom("a"),
+        ])
+    );
+
+    let binary = ctx
+        .rpc("erlang", "atom_to_binary", vec![OwnedTerm::atom("hello")])
+        .await
+        .unwrap();
+    assert_eq!(binary, OwnedTerm::binary(b"hello".to_vec()));
+
+    let sum = ctx
+        .rpc(
+            "erlang",
+            "+",
+            vec![OwnedTerm::integer(40), OwnedTerm::integer(2)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(sum, OwnedTerm::integer(42));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_returns_badrpc_on_remote_error() {
+    require_erlang!();
+    let ctx = InteropContext::start("badrpc").await.unwrap();
+
+    let result = ctx
+        .rpc("erlang", "atom_to_binary", vec![OwnedTerm::integer(1)])
+        .await
+        .unwrap();
+    let elements = result.as_tuple().expect("a badrpc tuple");
+    assert_eq!(elements[0], OwnedTerm::atom("badrpc"));
+}
+
+//
+// Monitors
+//
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_monitor_reports_remote_process_exit() {
+    require_erlang!();
+    let ctx = InteropContext::start("monitor").await.unwrap();
+    let (collector, mut rx) = spawn_collector(&ctx).await;
+
+    let worker = match ctx
+        .rpc("edp_it_peer", "spawn_worker", vec![])
+        .await
+        .unwrap()
+    {
+        OwnedTerm::Pid(pid) => pid,
+        other => panic!("expected a pid, got {:?}", other),
+    };
+    let reference = ctx.node.monitor(&collector, &worker).await.unwrap();
+
+    ctx.node
+        .send(
+            &worker,
+            OwnedTerm::tuple(vec![OwnedTerm::atom("stop"), OwnedTerm::atom("shutdown")]),
+        )
+        .await
+        .unwrap();
+
+    match next_message(&mut rx).await {
+        Message::MonitorExit {
+            monitored,
+            reference: received,
+            reason,
+        } => {
+            assert_eq!(monitored, worker);
+            assert_eq!(received, reference);
+            assert_eq!(reason, OwnedTerm::atom("shutdown"));
+        }
+        other => panic!("expected a monitor exit, got {:?}", other),
+    }
+
+    let alive = ctx
+        .rpc("edp_it_peer", "is_alive", vec![OwnedTerm::Pid(worker)])
+        .await
+        .unwrap();
+    assert_eq!(alive, OwnedTerm::atom("false"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_nodedown_when_peer_halts() {
+    require_erlang!();
+    let ctx = InteropContext::start("nodedown").await.unwrap();
+    let mut events = ctx.node.monitor_nodes();
+    let peer = Atom::new(ctx.peer_node());
+
+    ctx.node
+        .send(&ctx.peer_pid, OwnedTerm::atom("halt"))
+        .await
+        .unwrap();
+
+    timeout(RECEIVE_TIMEOUT, async {
+        loop {
+            match events.recv().await {
+                Some(NodeEvent::NodeDown { node, .. }) if node == peer => break,
+                Some(_) => continue,
+                None => panic!("node event stream closed"),
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for nodedown");
+}
@@ -0,0 +1,30 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![no_main]
+
+use edp_client::control::ControlMessage;
+use erltf::decode;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(term) = decode(data)
+        && let Ok(message) = ControlMessage::from_term(&term)
+    {
+        // Every control message that parses must convert back to the same message
+        let reparsed = ControlMessage::from_term(&message.to_term())
+            .expect("a parsed control message must convert back to a valid term");
+        assert_eq!(reparsed, message);
+    }
+});
@@ -0,0 +1,28 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![no_main]
+
+use edp_client::handshake::{Challenge, ChallengeAck, ChallengeReply, SendName, StatusMessage};
+use libfuzzer_sys::fuzz_target;
+
+// Handshake messages without their 2-byte length prefix, as the parsers receive them
+fuzz_target!(|data: &[u8]| {
+    let _ = SendName::decode(data);
+    let _ = StatusMessage::decode(data);
+    let _ = Challenge::decode(data);
+    let _ = Challenge::decode_old(data);
+    let _ = ChallengeReply::decode(data);
+    let _ = ChallengeAck::decode(data);
+});